@@ -0,0 +1,3 @@
+-- Ledger backfill checkpoints live in backfill_state next to the event
+-- backfill; cursor is the Horizon paging token to resume from
+ALTER TABLE backfill_state ADD COLUMN cursor TEXT;
@@ -0,0 +1,13 @@
+-- Stored responses for requests carrying an Idempotency-Key header.
+-- request_hash is the SHA-256 of the request the key was first used with, so
+-- a repeat with a different body is refused instead of replayed.
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+    scope TEXT NOT NULL,
+    idempotency_key TEXT NOT NULL,
+    request_hash TEXT NOT NULL,
+    status_code INTEGER,
+    response_body TEXT,
+    resource_id TEXT,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (scope, idempotency_key)
+);
@@ -0,0 +1,9 @@
+-- Per-corridor alert threshold overrides; NULL limits use the global default
+CREATE TABLE IF NOT EXISTS corridor_alert_config (
+    corridor_id TEXT PRIMARY KEY,
+    success_rate_drop REAL,
+    latency_increase_factor REAL,
+    liquidity_decrease_factor REAL,
+    muted INTEGER NOT NULL DEFAULT 0,
+    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
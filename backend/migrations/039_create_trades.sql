@@ -0,0 +1,16 @@
+-- Raw DEX trades, the source for OHLCV candles
+CREATE TABLE IF NOT EXISTS trades (
+    id TEXT PRIMARY KEY,
+    ledger_close_time TEXT NOT NULL,
+    base_asset TEXT NOT NULL,
+    counter_asset TEXT NOT NULL,
+    base_amount TEXT NOT NULL,
+    counter_amount TEXT NOT NULL,
+    price_n INTEGER NOT NULL,
+    price_d INTEGER NOT NULL,
+    trade_type TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_trades_pair_time
+    ON trades (base_asset, counter_asset, ledger_close_time);
@@ -0,0 +1,12 @@
+-- Claimable balances; rows with NULL claimed_at are still locked
+CREATE TABLE IF NOT EXISTS claimable_balances (
+    id TEXT PRIMARY KEY,
+    asset TEXT NOT NULL, -- `native` or `CODE:ISSUER`, as in `trades`
+    amount TEXT NOT NULL,
+    sponsor TEXT,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    claimed_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_claimable_balances_asset
+    ON claimable_balances (asset, claimed_at);
@@ -0,0 +1,9 @@
+-- Outcome of each asset revalidation cycle, newest row is the last run
+CREATE TABLE IF NOT EXISTS asset_revalidation_runs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    started_at TEXT NOT NULL,
+    assets_checked INTEGER NOT NULL,
+    revalidated INTEGER NOT NULL,
+    failures INTEGER NOT NULL,
+    duration_ms INTEGER NOT NULL
+);
@@ -0,0 +1,7 @@
+-- Set when webhook_events.payload holds a truncated preview instead of the
+-- full event, see WEBHOOK_MAX_STORED_PAYLOAD_BYTES
+ALTER TABLE webhook_events ADD COLUMN payload_truncated INTEGER NOT NULL DEFAULT 0;
+
+-- Full event for truncated payloads, kept only until the event will not be
+-- delivered again so retries after a restart still send the whole event
+ALTER TABLE webhook_events ADD COLUMN full_payload TEXT;
@@ -0,0 +1,7 @@
+-- Horizon operation id of each ingested payment, so re-processing a ledger
+-- (restart without a checkpoint, backfill overlapping live ingestion) does
+-- not store its payments twice. Rows ingested before this have no id.
+ALTER TABLE ledger_payments ADD COLUMN operation_id TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_ledger_payments_operation
+    ON ledger_payments (ledger_sequence, operation_id);
@@ -0,0 +1,23 @@
+-- Durable queue of snapshot hashes awaiting on-chain submission.
+-- status: pending (due for an attempt) -> submitted (sent, awaiting
+-- confirmation) -> confirmed, or failed once attempts are exhausted
+CREATE TABLE IF NOT EXISTS snapshot_submissions (
+    id TEXT PRIMARY KEY,
+    snapshot_id TEXT NOT NULL,
+    epoch INTEGER NOT NULL,
+    hash TEXT NOT NULL, -- hex SHA-256 of the canonical snapshot
+    status TEXT NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    max_fee INTEGER, -- stroops offered on the latest attempt
+    transaction_hash TEXT,
+    previous_transaction_hashes TEXT NOT NULL DEFAULT '[]', -- JSON array, earlier attempts
+    ledger INTEGER,
+    last_error TEXT,
+    submitted_at TEXT, -- RFC 3339; when the latest attempt was sent
+    next_attempt_at TEXT NOT NULL, -- RFC 3339
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_snapshot_submissions_status
+    ON snapshot_submissions (status, next_attempt_at);
@@ -0,0 +1,3 @@
+-- Range scans for the per-day counts in the daily summary
+CREATE INDEX IF NOT EXISTS idx_ledgers_close_time ON ledgers (close_time);
+CREATE INDEX IF NOT EXISTS idx_claimable_balances_created_at ON claimable_balances (created_at);
@@ -1,19 +1,101 @@
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
 use tokio::sync::broadcast;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use crate::rpc::HealthResponse;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AlertType {
     SuccessRateDrop,
     LatencyIncrease,
     LiquidityDecrease,
     AnchorStatusChange,
     AnchorMetricChange,
+    /// Ingestion is close to falling behind the RPC retention window
+    IngestionLag,
+}
+
+/// How urgently an alert needs attention, derived from the size of the change.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertSeverity {
+    #[default]
+    Info,
+    Warning,
+    Critical,
+}
+
+impl AlertSeverity {
+    /// Classify a change from `old_value` to `new_value`.
+    ///
+    /// Decreases are graded by the fraction lost (25% warning, 50% critical),
+    /// increases by the growth factor (2x warning, 3x critical).
+    #[must_use]
+    pub fn from_change(old_value: f64, new_value: f64) -> Self {
+        if old_value <= 0.0 {
+            return if new_value > 0.0 {
+                Self::Warning
+            } else {
+                Self::Info
+            };
+        }
+
+        if new_value < old_value {
+            let drop = (old_value - new_value) / old_value;
+            if drop >= 0.5 {
+                Self::Critical
+            } else if drop >= 0.25 {
+                Self::Warning
+            } else {
+                Self::Info
+            }
+        } else {
+            let factor = new_value / old_value;
+            if factor >= 3.0 {
+                Self::Critical
+            } else if factor >= 2.0 {
+                Self::Warning
+            } else {
+                Self::Info
+            }
+        }
+    }
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Critical => "critical",
+        }
+    }
+}
+
+impl std::fmt::Display for AlertSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for AlertSeverity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "info" => Ok(Self::Info),
+            "warning" => Ok(Self::Warning),
+            "critical" => Ok(Self::Critical),
+            other => Err(format!("unknown alert severity: {other}")),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Alert {
     pub alert_type: AlertType,
+    #[serde(default)]
+    pub severity: AlertSeverity,
     pub corridor_id: Option<String>,
     pub anchor_id: Option<String>,
     pub message: String,
@@ -22,19 +104,145 @@ pub struct Alert {
     pub timestamp: String,
 }
 
+/// Limits a corridor's metrics must cross before `check_and_alert` fires.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AlertThresholds {
+    /// Success rate drop, in percentage points
+    pub success_rate_drop: f64,
+    /// Latency growth factor (1.5 = 50% slower)
+    pub latency_increase_factor: f64,
+    /// Remaining liquidity fraction (0.7 = lost 30%)
+    pub liquidity_decrease_factor: f64,
+    /// Suppress every corridor alert
+    pub muted: bool,
+}
+
+impl Default for AlertThresholds {
+    fn default() -> Self {
+        Self {
+            success_rate_drop: 10.0,
+            latency_increase_factor: 1.5,
+            liquidity_decrease_factor: 0.7,
+            muted: false,
+        }
+    }
+}
+
+impl AlertThresholds {
+    /// Defaults with the liquidity limit read from `LIQUIDITY_DROP_THRESHOLD`,
+    /// the fraction of liquidity lost (in `(0, 1)`) that counts as a drop
+    #[must_use]
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let liquidity_decrease_factor = std::env::var("LIQUIDITY_DROP_THRESHOLD")
+            .ok()
+            .and_then(|s| s.parse::<f64>().ok())
+            .filter(|t| *t > 0.0 && *t < 1.0)
+            .map_or(defaults.liquidity_decrease_factor, |t| 1.0 - t);
+        Self {
+            liquidity_decrease_factor,
+            ..defaults
+        }
+    }
+}
+
+/// Per-corridor override; unset limits fall back to the global default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CorridorAlertOverride {
+    #[serde(default)]
+    pub success_rate_drop: Option<f64>,
+    #[serde(default)]
+    pub latency_increase_factor: Option<f64>,
+    #[serde(default)]
+    pub liquidity_decrease_factor: Option<f64>,
+    #[serde(default)]
+    pub muted: bool,
+}
+
+impl CorridorAlertOverride {
+    #[must_use]
+    pub fn resolve(&self, defaults: AlertThresholds) -> AlertThresholds {
+        AlertThresholds {
+            success_rate_drop: self.success_rate_drop.unwrap_or(defaults.success_rate_drop),
+            latency_increase_factor: self
+                .latency_increase_factor
+                .unwrap_or(defaults.latency_increase_factor),
+            liquidity_decrease_factor: self
+                .liquidity_decrease_factor
+                .unwrap_or(defaults.liquidity_decrease_factor),
+            muted: self.muted || defaults.muted,
+        }
+    }
+}
+
 pub struct AlertManager {
     tx: broadcast::Sender<Alert>,
     webhook_event_service: Option<Arc<crate::services::webhook_event_service::WebhookEventService>>,
+    default_thresholds: AlertThresholds,
+    corridor_overrides: RwLock<HashMap<String, CorridorAlertOverride>>,
+    /// Lag level last alerted on, so repeated checks don't resend the same alert
+    ingestion_lag_level: Mutex<IngestionLagLevel>,
+}
+
+/// How far ingestion has fallen towards the RPC retention edge, ordered by severity
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+enum IngestionLagLevel {
+    #[default]
+    Clear,
+    NearEdge,
+    Missed,
+}
+
+const DEFAULT_ALERT_CHANNEL_CAPACITY: usize = 100;
+
+/// Broadcast buffer size from `ALERT_CHANNEL_CAPACITY` (default 100).
+///
+/// Subscribers that fall further behind than this lose the oldest alerts.
+#[must_use]
+pub fn alert_channel_capacity_from_env() -> usize {
+    std::env::var("ALERT_CHANNEL_CAPACITY")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or(DEFAULT_ALERT_CHANNEL_CAPACITY)
+}
+
+/// Default headroom, in ledgers (about an hour), below which a lagging
+/// ingestion cursor raises an [`AlertType::IngestionLag`] alert.
+pub const DEFAULT_INGESTION_LAG_SAFETY_MARGIN: u64 = 720;
+
+/// Safety margin from `INGESTION_LAG_SAFETY_MARGIN_LEDGERS` (default 720).
+#[must_use]
+pub fn ingestion_lag_safety_margin_from_env() -> u64 {
+    std::env::var("INGESTION_LAG_SAFETY_MARGIN_LEDGERS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_INGESTION_LAG_SAFETY_MARGIN)
 }
 
 impl AlertManager {
+    /// Create a manager whose channel capacity comes from `ALERT_CHANNEL_CAPACITY`
+    /// and default thresholds from [`AlertThresholds::from_env`].
     #[must_use]
     pub fn new() -> (Self, broadcast::Receiver<Alert>) {
-        let (tx, rx) = broadcast::channel(100);
+        let (manager, rx) = Self::with_capacity(alert_channel_capacity_from_env());
+        (
+            manager.with_default_thresholds(AlertThresholds::from_env()),
+            rx,
+        )
+    }
+
+    /// Create a manager buffering up to `capacity` alerts per subscriber.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> (Self, broadcast::Receiver<Alert>) {
+        let (tx, rx) = broadcast::channel(capacity.max(1));
         (
             Self {
                 tx,
                 webhook_event_service: None,
+                default_thresholds: AlertThresholds::default(),
+                corridor_overrides: RwLock::new(HashMap::new()),
+                ingestion_lag_level: Mutex::new(IngestionLagLevel::Clear),
             },
             rx,
         )
@@ -44,16 +252,70 @@ impl AlertManager {
     pub fn new_with_webhooks(
         webhook_event_service: Arc<crate::services::webhook_event_service::WebhookEventService>,
     ) -> (Self, broadcast::Receiver<Alert>) {
-        let (tx, rx) = broadcast::channel(100);
+        let (tx, rx) = broadcast::channel(alert_channel_capacity_from_env());
         (
             Self {
                 tx,
                 webhook_event_service: Some(webhook_event_service),
+                default_thresholds: AlertThresholds::from_env(),
+                corridor_overrides: RwLock::new(HashMap::new()),
+                ingestion_lag_level: Mutex::new(IngestionLagLevel::Clear),
             },
             rx,
         )
     }
 
+    /// Replace the global thresholds used by corridors without an override.
+    #[must_use]
+    pub const fn with_default_thresholds(mut self, thresholds: AlertThresholds) -> Self {
+        self.default_thresholds = thresholds;
+        self
+    }
+
+    /// Effective thresholds for `corridor_id`: its override if any, else the default.
+    #[must_use]
+    pub fn thresholds_for(&self, corridor_id: &str) -> AlertThresholds {
+        self.corridor_overrides
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(corridor_id)
+            .map_or(self.default_thresholds, |o| {
+                o.resolve(self.default_thresholds)
+            })
+    }
+
+    /// The override stored for `corridor_id`, if any
+    #[must_use]
+    pub fn corridor_override(&self, corridor_id: &str) -> Option<CorridorAlertOverride> {
+        self.corridor_overrides
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(corridor_id)
+            .copied()
+    }
+
+    pub fn set_corridor_override(&self, corridor_id: &str, config: CorridorAlertOverride) {
+        self.corridor_overrides
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(corridor_id.to_string(), config);
+    }
+
+    pub fn remove_corridor_override(&self, corridor_id: &str) {
+        self.corridor_overrides
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(corridor_id);
+    }
+
+    /// Swap in a full set of overrides, e.g. as loaded from the database.
+    pub fn replace_corridor_overrides(&self, overrides: HashMap<String, CorridorAlertOverride>) {
+        *self
+            .corridor_overrides
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = overrides;
+    }
+
     pub fn check_and_alert(
         &self,
         corridor_id: &str,
@@ -64,9 +326,15 @@ impl AlertManager {
         old_liquidity: f64,
         new_liquidity: f64,
     ) {
-        if new_success < old_success - 10.0 {
+        let thresholds = self.thresholds_for(corridor_id);
+        if thresholds.muted {
+            return;
+        }
+
+        if new_success < old_success - thresholds.success_rate_drop {
             let _ = self.tx.send(Alert {
                 alert_type: AlertType::SuccessRateDrop,
+                severity: AlertSeverity::from_change(old_success, new_success),
                 corridor_id: Some(corridor_id.to_string()),
                 anchor_id: None,
                 message: format!(
@@ -78,9 +346,10 @@ impl AlertManager {
             });
         }
 
-        if new_latency > old_latency * 1.5 {
+        if new_latency > old_latency * thresholds.latency_increase_factor {
             let _ = self.tx.send(Alert {
                 alert_type: AlertType::LatencyIncrease,
+                severity: AlertSeverity::from_change(old_latency, new_latency),
                 corridor_id: Some(corridor_id.to_string()),
                 anchor_id: None,
                 message: format!("Latency increased from {old_latency:.0}ms to {new_latency:.0}ms"),
@@ -90,9 +359,10 @@ impl AlertManager {
             });
         }
 
-        if new_liquidity < old_liquidity * 0.7 {
+        if new_liquidity < old_liquidity * thresholds.liquidity_decrease_factor {
             let _ = self.tx.send(Alert {
                 alert_type: AlertType::LiquidityDecrease,
+                severity: AlertSeverity::from_change(old_liquidity, new_liquidity),
                 corridor_id: Some(corridor_id.to_string()),
                 anchor_id: None,
                 message: format!(
@@ -105,6 +375,63 @@ impl AlertManager {
         }
     }
 
+    /// Raise a critical [`AlertType::IngestionLag`] alert when
+    /// `last_ingested_ledger` is within `safety_margin` ledgers of dropping
+    /// below the RPC's `oldest_ledger`, after which ledgers are lost for good.
+    ///
+    /// Only a worsening lag is alerted on: repeated checks at the same level
+    /// stay quiet until ingestion recovers past the margin or misses ledgers.
+    /// Returns whether an alert was sent.
+    pub fn check_ingestion_lag(
+        &self,
+        health: &HealthResponse,
+        last_ingested_ledger: u64,
+        safety_margin: u64,
+    ) -> bool {
+        let headroom = last_ingested_ledger.saturating_sub(health.oldest_ledger);
+        let level = if last_ingested_ledger < health.oldest_ledger {
+            IngestionLagLevel::Missed
+        } else if headroom <= safety_margin {
+            IngestionLagLevel::NearEdge
+        } else {
+            IngestionLagLevel::Clear
+        };
+        let previous = std::mem::replace(
+            &mut *self
+                .ingestion_lag_level
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
+            level,
+        );
+        if level <= previous || level == IngestionLagLevel::Clear {
+            return false;
+        }
+
+        let lag = health.latest_ledger.saturating_sub(last_ingested_ledger);
+        let message = if level == IngestionLagLevel::Missed {
+            format!(
+                "Ingestion at ledger {last_ingested_ledger} is behind the oldest retained ledger {}; ledgers have been missed",
+                health.oldest_ledger
+            )
+        } else {
+            format!(
+                "Ingestion is {lag} ledgers behind with {headroom} ledgers left before the {}-ledger retention window passes it",
+                health.ledger_retention_window
+            )
+        };
+        let _ = self.tx.send(Alert {
+            alert_type: AlertType::IngestionLag,
+            severity: AlertSeverity::Critical,
+            corridor_id: None,
+            anchor_id: None,
+            message,
+            old_value: health.ledger_retention_window as f64,
+            new_value: lag as f64,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        });
+        true
+    }
+
     #[must_use]
     pub fn subscribe(&self) -> broadcast::Receiver<Alert> {
         self.tx.subscribe()
@@ -120,6 +447,7 @@ impl AlertManager {
     ) {
         let alert = Alert {
             alert_type,
+            severity: AlertSeverity::from_change(old_value, new_value),
             corridor_id: None,
             anchor_id: Some(anchor_id.to_string()),
             message: message.clone(),
@@ -167,3 +495,177 @@ impl AlertManager {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_severity_scales_with_drop() {
+        assert_eq!(AlertSeverity::from_change(95.0, 84.0), AlertSeverity::Info);
+        assert_eq!(
+            AlertSeverity::from_change(95.0, 65.0),
+            AlertSeverity::Warning
+        );
+        assert_eq!(
+            AlertSeverity::from_change(95.0, 40.0),
+            AlertSeverity::Critical
+        );
+        assert_eq!(
+            AlertSeverity::from_change(100.0, 180.0),
+            AlertSeverity::Info
+        );
+        assert_eq!(
+            AlertSeverity::from_change(100.0, 250.0),
+            AlertSeverity::Warning
+        );
+        assert_eq!(
+            AlertSeverity::from_change(100.0, 400.0),
+            AlertSeverity::Critical
+        );
+    }
+
+    #[test]
+    fn test_check_and_alert_assigns_severity() {
+        let (manager, mut rx) = AlertManager::new();
+
+        manager.check_and_alert("USDC-XLM", 95.0, 84.0, 100.0, 100.0, 1000.0, 1000.0);
+        let alert = rx.try_recv().unwrap();
+        assert!(matches!(alert.alert_type, AlertType::SuccessRateDrop));
+        assert_eq!(alert.severity, AlertSeverity::Info);
+
+        manager.check_and_alert("USDC-XLM", 95.0, 30.0, 100.0, 100.0, 1000.0, 1000.0);
+        let alert = rx.try_recv().unwrap();
+        assert_eq!(alert.severity, AlertSeverity::Critical);
+
+        manager.check_and_alert("USDC-XLM", 95.0, 95.0, 100.0, 100.0, 1000.0, 600.0);
+        let alert = rx.try_recv().unwrap();
+        assert!(matches!(alert.alert_type, AlertType::LiquidityDecrease));
+        assert_eq!(alert.severity, AlertSeverity::Warning);
+    }
+
+    #[test]
+    fn test_muted_corridor_is_silent_while_default_corridor_alerts() {
+        let (manager, mut rx) = AlertManager::with_capacity(10);
+        manager.set_corridor_override(
+            "EURC-XLM",
+            CorridorAlertOverride {
+                muted: true,
+                ..Default::default()
+            },
+        );
+
+        manager.check_and_alert("EURC-XLM", 95.0, 30.0, 100.0, 400.0, 1000.0, 100.0);
+        assert!(rx.try_recv().is_err());
+
+        manager.check_and_alert("USDC-XLM", 95.0, 30.0, 100.0, 100.0, 1000.0, 1000.0);
+        let alert = rx.try_recv().unwrap();
+        assert_eq!(alert.corridor_id.as_deref(), Some("USDC-XLM"));
+        assert!(matches!(alert.alert_type, AlertType::SuccessRateDrop));
+    }
+
+    #[test]
+    fn test_corridor_override_falls_back_to_default_per_field() {
+        let (manager, mut rx) = AlertManager::with_capacity(10);
+        manager.set_corridor_override(
+            "USDC-XLM",
+            CorridorAlertOverride {
+                success_rate_drop: Some(50.0),
+                ..Default::default()
+            },
+        );
+
+        let thresholds = manager.thresholds_for("USDC-XLM");
+        assert!((thresholds.success_rate_drop - 50.0).abs() < f64::EPSILON);
+        assert!((thresholds.latency_increase_factor - 1.5).abs() < f64::EPSILON);
+
+        // A 20 point drop is below the custom limit but latency still uses the default
+        manager.check_and_alert("USDC-XLM", 95.0, 75.0, 100.0, 200.0, 1000.0, 1000.0);
+        let alert = rx.try_recv().unwrap();
+        assert!(matches!(alert.alert_type, AlertType::LatencyIncrease));
+        assert!(rx.try_recv().is_err());
+
+        manager.remove_corridor_override("USDC-XLM");
+        assert_eq!(
+            manager.thresholds_for("USDC-XLM"),
+            AlertThresholds::default()
+        );
+    }
+
+    #[test]
+    fn test_liquidity_drop_threshold_from_env() {
+        let _guard = crate::lock_env_test();
+        std::env::set_var("LIQUIDITY_DROP_THRESHOLD", "0.2");
+        let thresholds = AlertThresholds::from_env();
+        assert!((thresholds.liquidity_decrease_factor - 0.8).abs() < 1e-9);
+
+        std::env::set_var("LIQUIDITY_DROP_THRESHOLD", "1.5");
+        assert_eq!(AlertThresholds::from_env(), AlertThresholds::default());
+
+        std::env::remove_var("LIQUIDITY_DROP_THRESHOLD");
+    }
+
+    #[test]
+    fn test_alert_channel_capacity_from_env() {
+        let _guard = crate::lock_env_test();
+        std::env::remove_var("ALERT_CHANNEL_CAPACITY");
+        assert_eq!(alert_channel_capacity_from_env(), 100);
+
+        std::env::set_var("ALERT_CHANNEL_CAPACITY", "500");
+        assert_eq!(alert_channel_capacity_from_env(), 500);
+
+        std::env::set_var("ALERT_CHANNEL_CAPACITY", "0");
+        assert_eq!(alert_channel_capacity_from_env(), 100);
+        std::env::remove_var("ALERT_CHANNEL_CAPACITY");
+    }
+
+    #[test]
+    fn test_ingestion_lag_alert_fires_near_retention_edge() {
+        let (manager, mut rx) = AlertManager::with_capacity(10);
+        let health = HealthResponse {
+            status: "healthy".to_string(),
+            latest_ledger: 1_100,
+            oldest_ledger: 1_001,
+            ledger_retention_window: 100,
+        };
+
+        assert!(!manager.check_ingestion_lag(&health, 1_090, 10));
+        assert!(rx.try_recv().is_err());
+
+        assert!(manager.check_ingestion_lag(&health, 1_005, 10));
+        let alert = rx.try_recv().unwrap();
+        assert_eq!(alert.alert_type, AlertType::IngestionLag);
+        assert_eq!(alert.severity, AlertSeverity::Critical);
+        assert!((alert.new_value - 95.0).abs() < f64::EPSILON);
+
+        assert!(manager.check_ingestion_lag(&health, 900, 10));
+        assert!(rx.try_recv().unwrap().message.contains("missed"));
+    }
+
+    #[test]
+    fn test_ingestion_lag_alert_is_not_repeated_until_it_clears() {
+        let (manager, mut rx) = AlertManager::with_capacity(10);
+        let health = HealthResponse {
+            status: "healthy".to_string(),
+            latest_ledger: 1_100,
+            oldest_ledger: 1_001,
+            ledger_retention_window: 100,
+        };
+
+        assert!(manager.check_ingestion_lag(&health, 1_005, 10));
+        assert!(!manager.check_ingestion_lag(&health, 1_006, 10));
+        assert!(rx.try_recv().is_ok());
+        assert!(rx.try_recv().is_err());
+
+        // Recovering past the margin re-arms the alert
+        assert!(!manager.check_ingestion_lag(&health, 1_090, 10));
+        assert!(manager.check_ingestion_lag(&health, 1_005, 10));
+    }
+
+    #[test]
+    fn test_severity_serializes_lowercase() {
+        let json = serde_json::to_string(&AlertSeverity::Critical).unwrap();
+        assert_eq!(json, "\"critical\"");
+        assert!(AlertSeverity::Critical > AlertSeverity::Warning);
+    }
+}
@@ -0,0 +1,174 @@
+//! Exact Stellar amounts.
+//!
+//! Horizon reports amounts as decimal strings with 7 fractional digits (one
+//! stroop = 0.0000001). Parsing them into `f64` silently drops the trailing
+//! digits once a value passes ~2^53 stroops, so sums are kept in [`Amount`]
+//! and only converted to `f64` at the storage/metric boundary.
+
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Fractional digits in a Stellar amount
+pub const STELLAR_DECIMALS: u32 = 7;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum AmountError {
+    #[error("invalid amount: {0}")]
+    Invalid(String),
+    #[error("amount {0} has more than {STELLAR_DECIMALS} decimal places")]
+    TooPrecise(String),
+}
+
+/// A non-lossy Stellar amount; serializes to JSON as a string.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Amount(Decimal);
+
+impl Amount {
+    pub const ZERO: Self = Self(Decimal::ZERO);
+
+    /// Parse a Horizon amount string such as `"1234.5678901"`.
+    pub fn parse(value: &str) -> Result<Self, AmountError> {
+        let trimmed = value.trim();
+        let decimal =
+            Decimal::from_str(trimmed).map_err(|_| AmountError::Invalid(trimmed.to_string()))?;
+        if decimal.scale() > STELLAR_DECIMALS {
+            return Err(AmountError::TooPrecise(trimmed.to_string()));
+        }
+        Ok(Self(decimal))
+    }
+
+    /// Sum amount strings, skipping any that fail to parse.
+    pub fn sum_parsed<S: AsRef<str>>(values: impl IntoIterator<Item = S>) -> Self {
+        values
+            .into_iter()
+            .filter_map(|v| Self::parse(v.as_ref()).ok())
+            .sum()
+    }
+
+    /// Multiply by a price or ratio, rounded to the nearest stroop. `None`
+    /// when `factor` is not finite or the product overflows.
+    #[must_use]
+    pub fn scaled(self, factor: f64) -> Option<Self> {
+        Decimal::from_f64(factor)
+            .and_then(|factor| self.0.checked_mul(factor))
+            .map(|value| Self(value.round_dp(STELLAR_DECIMALS)))
+    }
+
+    #[must_use]
+    pub const fn as_decimal(self) -> Decimal {
+        self.0
+    }
+
+    /// Lossy conversion for legacy `f64` fields and metrics.
+    #[must_use]
+    pub fn to_f64(self) -> f64 {
+        self.0.to_f64().unwrap_or(0.0)
+    }
+}
+
+impl FromStr for Amount {
+    type Err = AmountError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.*}", STELLAR_DECIMALS as usize, self.0)
+    }
+}
+
+impl Add for Amount {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for Amount {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Sum for Amount {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl Serialize for Amount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Self::parse(&value).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_large_sum_keeps_every_stroop_where_f64_does_not() {
+        let amounts = ["922337203685.4775807", "0.0000001", "0.0000001"];
+
+        let exact = Amount::sum_parsed(amounts);
+        assert_eq!(exact.to_string(), "922337203685.4775809");
+
+        let lossy: f64 = amounts.iter().map(|a| a.parse::<f64>().unwrap()).sum();
+        assert_ne!(format!("{lossy:.7}"), "922337203685.4775809");
+    }
+
+    #[test]
+    fn test_parse_rejects_garbage_and_extra_precision() {
+        assert_eq!(Amount::parse(" 10.5 ").unwrap().to_string(), "10.5000000");
+        assert!(matches!(
+            Amount::parse("1.00000001"),
+            Err(AmountError::TooPrecise(_))
+        ));
+        assert!(matches!(Amount::parse("abc"), Err(AmountError::Invalid(_))));
+        assert_eq!(
+            Amount::sum_parsed(["1.0", "oops", "2.5"]).to_string(),
+            "3.5000000"
+        );
+    }
+
+    #[test]
+    fn test_scaled_rounds_to_stroops() {
+        let amount = Amount::parse("922337203685.4775807").unwrap();
+        assert_eq!(amount.scaled(1.0), Some(amount));
+        assert_eq!(
+            Amount::parse("0.0000003")
+                .unwrap()
+                .scaled(0.5)
+                .unwrap()
+                .to_string(),
+            "0.0000002"
+        );
+        assert_eq!(amount.scaled(f64::NAN), None);
+        assert_eq!(amount.scaled(1e20), None);
+    }
+
+    #[test]
+    fn test_amount_serializes_as_string() {
+        let amount = Amount::parse("12345678901.2345678").unwrap();
+        let json = serde_json::to_string(&amount).unwrap();
+        assert_eq!(json, "\"12345678901.2345678\"");
+        assert_eq!(serde_json::from_str::<Amount>(&json).unwrap(), amount);
+    }
+}
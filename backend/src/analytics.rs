@@ -2,6 +2,7 @@ use crate::models::{AnchorMetrics, AnchorStatus};
 
 pub mod anomaly;
 pub mod corridor;
+pub mod operations;
 
 /// Performance metrics for an anchor's individual asset
 #[derive(Debug, Clone)]
@@ -0,0 +1,391 @@
+//! Operation-level corridor and anchor volume.
+//!
+//! A transaction may carry several payment operations, and a fee-bump
+//! transaction wraps an inner transaction whose operations are the ones that
+//! move value. Counting per operation, and attributing every operation to its
+//! inner transaction, keeps multi-operation and fee-bumped transactions from
+//! being counted as a single payment.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::rpc::{HorizonTransaction, Payment};
+
+/// Horizon operation types that move value along a corridor
+const PAYMENT_OPERATION_TYPES: [&str; 3] = [
+    "payment",
+    "path_payment_strict_send",
+    "path_payment_strict_receive",
+];
+
+/// Operation counts and volume for one corridor
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorridorOperationVolume {
+    pub corridor_key: String,
+    pub operation_count: i64,
+    pub successful_operations: i64,
+    pub failed_operations: i64,
+    /// Distinct transactions the operations belong to; fee bumps count as
+    /// their inner transaction
+    pub transaction_count: i64,
+    /// Sum of successful operation amounts, in destination asset units
+    pub volume: f64,
+}
+
+/// Operation counts and volume for one asset issuer
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnchorOperationVolume {
+    pub anchor_account: String,
+    pub operation_count: i64,
+    pub successful_operations: i64,
+    pub failed_operations: i64,
+    pub transaction_count: i64,
+    /// Sum of successful operation amounts in this issuer's assets
+    pub volume: f64,
+}
+
+/// Transaction an operation is attributed to
+#[derive(Debug, Clone, Copy)]
+struct Attribution<'a> {
+    /// Inner transaction hash for fee bumps, otherwise the transaction hash
+    hash: &'a str,
+    successful: bool,
+}
+
+/// Map outer and inner hashes of `transactions` to the transaction their
+/// operations belong to
+fn attributions(transactions: &[HorizonTransaction]) -> HashMap<&str, Attribution<'_>> {
+    let mut map = HashMap::new();
+    for tx in transactions {
+        let inner_hash = tx
+            .inner_transaction
+            .as_ref()
+            .map_or(tx.hash.as_str(), |inner| inner.hash.as_str());
+        let attribution = Attribution {
+            hash: inner_hash,
+            successful: tx.successful,
+        };
+        map.insert(tx.hash.as_str(), attribution);
+        map.insert(inner_hash, attribution);
+        if let Some(fee_bump) = &tx.fee_bump_transaction {
+            map.insert(fee_bump.hash.as_str(), attribution);
+        }
+    }
+    map
+}
+
+fn is_payment_operation(payment: &Payment) -> bool {
+    payment
+        .operation_type
+        .as_deref()
+        .is_none_or(|kind| PAYMENT_OPERATION_TYPES.contains(&kind))
+}
+
+fn parse_amount(amount: &str) -> f64 {
+    amount.parse::<f64>().unwrap_or(0.0)
+}
+
+#[derive(Default)]
+struct Tally<'a> {
+    successful: i64,
+    failed: i64,
+    transactions: HashSet<&'a str>,
+    volume: f64,
+}
+
+impl<'a> Tally<'a> {
+    fn add(&mut self, attribution: Attribution<'a>, amount: f64) {
+        self.transactions.insert(attribution.hash);
+        if attribution.successful {
+            self.successful += 1;
+            self.volume += amount.max(0.0);
+        } else {
+            self.failed += 1;
+        }
+    }
+}
+
+/// Payment operations of `payments` with the transaction each belongs to.
+///
+/// Operations whose transaction is not in `transactions` are attributed to
+/// their own `transaction_hash` and counted as successful, as Horizon only
+/// returns failed payments when asked to.
+fn attributed_operations<'a>(
+    payments: &'a [Payment],
+    transactions: &'a [HorizonTransaction],
+) -> impl Iterator<Item = (&'a Payment, Attribution<'a>)> {
+    let attributions = attributions(transactions);
+    payments
+        .iter()
+        .filter(|payment| is_payment_operation(payment))
+        .map(move |payment| {
+            let attribution = attributions
+                .get(payment.transaction_hash.as_str())
+                .copied()
+                .unwrap_or(Attribution {
+                    hash: payment.transaction_hash.as_str(),
+                    successful: true,
+                });
+            (payment, attribution)
+        })
+}
+
+/// Count payment and path-payment operations per corridor.
+///
+/// Path payments missing a source asset cannot be attributed and are
+/// skipped. Results are sorted by corridor key.
+#[must_use]
+pub fn compute_corridor_operation_volume(
+    payments: &[Payment],
+    transactions: &[HorizonTransaction],
+) -> Vec<CorridorOperationVolume> {
+    let mut tallies: BTreeMap<String, Tally<'_>> = BTreeMap::new();
+    for (payment, attribution) in attributed_operations(payments, transactions) {
+        let Some(corridor_key) = payment.corridor_key() else {
+            continue;
+        };
+        tallies
+            .entry(corridor_key)
+            .or_default()
+            .add(attribution, parse_amount(&payment.get_amount()));
+    }
+
+    tallies
+        .into_iter()
+        .map(|(corridor_key, tally)| CorridorOperationVolume {
+            corridor_key,
+            operation_count: tally.successful + tally.failed,
+            successful_operations: tally.successful,
+            failed_operations: tally.failed,
+            transaction_count: tally.transactions.len() as i64,
+            volume: tally.volume,
+        })
+        .collect()
+}
+
+/// Count payment and path-payment operations per asset issuer.
+///
+/// An operation counts once for each distinct issuer of its source and
+/// destination assets; its volume is the amount in that issuer's asset.
+/// Native XLM has no issuer and is not attributed. Results are sorted by
+/// issuer account.
+#[must_use]
+pub fn compute_anchor_operation_volume(
+    payments: &[Payment],
+    transactions: &[HorizonTransaction],
+) -> Vec<AnchorOperationVolume> {
+    let mut tallies: BTreeMap<String, Tally<'_>> = BTreeMap::new();
+    for (payment, attribution) in attributed_operations(payments, transactions) {
+        let destination_issuer = payment.get_asset_issuer();
+        if let Some(issuer) = &destination_issuer {
+            tallies
+                .entry(issuer.clone())
+                .or_default()
+                .add(attribution, parse_amount(&payment.get_amount()));
+        }
+
+        if !payment.is_path_payment() {
+            continue;
+        }
+        if let Some(issuer) = &payment.source_asset_issuer {
+            if destination_issuer.as_ref() != Some(issuer) {
+                let amount = payment.source_amount.as_deref().map_or(0.0, parse_amount);
+                tallies
+                    .entry(issuer.clone())
+                    .or_default()
+                    .add(attribution, amount);
+            }
+        }
+    }
+
+    tallies
+        .into_iter()
+        .map(|(anchor_account, tally)| AnchorOperationVolume {
+            anchor_account,
+            operation_count: tally.successful + tally.failed,
+            successful_operations: tally.successful,
+            failed_operations: tally.failed,
+            transaction_count: tally.transactions.len() as i64,
+            volume: tally.volume,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::{FeeBumpTransactionInfo, InnerTransaction};
+
+    const USDC_ISSUER: &str = "GUSDCISSUER";
+    const EURC_ISSUER: &str = "GEURCISSUER";
+
+    fn transaction(hash: &str, operation_count: u32, successful: bool) -> HorizonTransaction {
+        HorizonTransaction {
+            id: hash.to_string(),
+            hash: hash.to_string(),
+            ledger: 1,
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            source_account: "GSOURCE".to_string(),
+            fee_account: None,
+            fee_charged: Some("100".to_string()),
+            max_fee: Some("100".to_string()),
+            operation_count,
+            successful,
+            paging_token: hash.to_string(),
+            fee_bump_transaction: None,
+            inner_transaction: None,
+        }
+    }
+
+    fn fee_bump(outer_hash: &str, inner_hash: &str, operation_count: u32) -> HorizonTransaction {
+        HorizonTransaction {
+            fee_account: Some("GSPONSOR".to_string()),
+            fee_bump_transaction: Some(FeeBumpTransactionInfo {
+                hash: outer_hash.to_string(),
+                signatures: vec!["sig".to_string()],
+            }),
+            inner_transaction: Some(InnerTransaction {
+                hash: inner_hash.to_string(),
+                max_fee: Some("100".to_string()),
+                signatures: vec!["sig".to_string()],
+            }),
+            ..transaction(outer_hash, operation_count, true)
+        }
+    }
+
+    fn payment(tx_hash: &str, code: &str, issuer: &str, amount: &str) -> Payment {
+        Payment {
+            id: format!("{tx_hash}-{code}-{amount}"),
+            paging_token: String::new(),
+            transaction_hash: tx_hash.to_string(),
+            source_account: "GSOURCE".to_string(),
+            destination: "GDEST".to_string(),
+            asset_type: "credit_alphanum4".to_string(),
+            asset_code: Some(code.to_string()),
+            asset_issuer: Some(issuer.to_string()),
+            amount: amount.to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            operation_type: Some("payment".to_string()),
+            source_asset_type: None,
+            source_asset_code: None,
+            source_asset_issuer: None,
+            source_amount: None,
+            from: None,
+            to: None,
+            asset_balance_changes: None,
+        }
+    }
+
+    fn path_payment(tx_hash: &str, amount: &str, source_amount: &str) -> Payment {
+        Payment {
+            operation_type: Some("path_payment_strict_send".to_string()),
+            source_asset_type: Some("credit_alphanum4".to_string()),
+            source_asset_code: Some("USDC".to_string()),
+            source_asset_issuer: Some(USDC_ISSUER.to_string()),
+            source_amount: Some(source_amount.to_string()),
+            ..payment(tx_hash, "EURC", EURC_ISSUER, amount)
+        }
+    }
+
+    fn usdc_key() -> String {
+        format!("USDC:{USDC_ISSUER}->USDC:{USDC_ISSUER}")
+    }
+
+    fn eurc_key() -> String {
+        format!("EURC:{EURC_ISSUER}->EURC:{EURC_ISSUER}")
+    }
+
+    fn corridor<'a>(
+        volumes: &'a [CorridorOperationVolume],
+        key: &str,
+    ) -> &'a CorridorOperationVolume {
+        volumes.iter().find(|v| v.corridor_key == key).unwrap()
+    }
+
+    #[test]
+    fn test_fee_bump_wrapping_multiple_payments_attributes_each_operation() {
+        let transactions = vec![fee_bump("outer", "inner", 3)];
+        // Horizon may report an operation under either the outer or inner hash
+        let payments = vec![
+            payment("outer", "USDC", USDC_ISSUER, "100"),
+            payment("inner", "USDC", USDC_ISSUER, "50"),
+            payment("outer", "EURC", EURC_ISSUER, "20"),
+        ];
+
+        let volumes = compute_corridor_operation_volume(&payments, &transactions);
+        assert_eq!(volumes.len(), 2);
+
+        let usdc = corridor(&volumes, &usdc_key());
+        assert_eq!(usdc.operation_count, 2);
+        assert_eq!(usdc.successful_operations, 2);
+        assert_eq!(usdc.transaction_count, 1);
+        assert!((usdc.volume - 150.0).abs() < f64::EPSILON);
+
+        let eurc = corridor(&volumes, &eurc_key());
+        assert_eq!(eurc.operation_count, 1);
+        assert_eq!(eurc.transaction_count, 1);
+        assert!((eurc.volume - 20.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_failed_transaction_counts_operations_without_volume() {
+        let transactions = vec![transaction("ok", 1, true), transaction("failed", 2, false)];
+        let payments = vec![
+            payment("ok", "USDC", USDC_ISSUER, "10"),
+            payment("failed", "USDC", USDC_ISSUER, "30"),
+            payment("failed", "USDC", USDC_ISSUER, "40"),
+        ];
+
+        let volumes = compute_corridor_operation_volume(&payments, &transactions);
+        let usdc = corridor(&volumes, &usdc_key());
+        assert_eq!(usdc.operation_count, 3);
+        assert_eq!(usdc.successful_operations, 1);
+        assert_eq!(usdc.failed_operations, 2);
+        assert_eq!(usdc.transaction_count, 2);
+        assert!((usdc.volume - 10.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_non_payment_operations_are_ignored() {
+        let mut create_account = payment("tx", "USDC", USDC_ISSUER, "1000");
+        create_account.operation_type = Some("create_account".to_string());
+        let payments = vec![create_account, payment("tx", "USDC", USDC_ISSUER, "5")];
+
+        let volumes = compute_corridor_operation_volume(&payments, &[]);
+        assert_eq!(volumes.len(), 1);
+        assert_eq!(volumes[0].operation_count, 1);
+        assert!((volumes[0].volume - 5.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_anchor_volume_attributes_path_payments_to_both_issuers() {
+        let transactions = vec![fee_bump("outer", "inner", 2)];
+        let payments = vec![
+            path_payment("outer", "90", "100"),
+            payment("outer", "USDC", USDC_ISSUER, "25"),
+        ];
+
+        let corridors = compute_corridor_operation_volume(&payments, &transactions);
+        let path = corridor(
+            &corridors,
+            &format!("USDC:{USDC_ISSUER}->EURC:{EURC_ISSUER}"),
+        );
+        assert_eq!(path.operation_count, 1);
+        assert!((path.volume - 90.0).abs() < f64::EPSILON);
+
+        let anchors = compute_anchor_operation_volume(&payments, &transactions);
+        assert_eq!(anchors.len(), 2);
+        let eurc = anchors
+            .iter()
+            .find(|a| a.anchor_account == EURC_ISSUER)
+            .unwrap();
+        assert_eq!(eurc.operation_count, 1);
+        assert!((eurc.volume - 90.0).abs() < f64::EPSILON);
+        let usdc = anchors
+            .iter()
+            .find(|a| a.anchor_account == USDC_ISSUER)
+            .unwrap();
+        assert_eq!(usdc.operation_count, 2);
+        assert_eq!(usdc.transaction_count, 1);
+        assert!((usdc.volume - 125.0).abs() < f64::EPSILON);
+    }
+}
@@ -6,6 +6,8 @@ use axum::{
 use serde::Deserialize;
 use std::sync::Arc;
 
+use crate::error::ApiResult;
+use crate::pagination::validate_limit;
 use crate::services::account_merge_detector::{
     AccountMergeDetector, AccountMergeEvent, AccountMergeStats, DestinationAccountPattern,
 };
@@ -68,20 +70,21 @@ async fn get_account_merge_stats(
     get,
     path = "/api/account-merges/recent",
     params(
-        ("limit" = Option<i64>, Query, description = "Maximum number of results (1-200, default 50)")
+        ("limit" = Option<i64>, Query, description = "Maximum number of results (1-MAX_PAGE_SIZE, default 50)")
     ),
     responses(
-        (status = 200, description = "List of recent account merge events")
+        (status = 200, description = "List of recent account merge events"),
+        (status = 400, description = "limit exceeds MAX_PAGE_SIZE")
     ),
     tag = "Account Merges"
 )]
 async fn get_recent_account_merges(
     State(detector): State<Arc<AccountMergeDetector>>,
     Query(params): Query<RecentMergesParams>,
-) -> Json<Vec<AccountMergeEvent>> {
-    let limit = params.limit.clamp(1, 200);
+) -> ApiResult<Json<Vec<AccountMergeEvent>>> {
+    let limit = validate_limit(params.limit)?;
     let merges = detector.get_recent_merges(limit).await.unwrap_or_default();
-    Json(merges)
+    Ok(Json(merges))
 }
 
 /// GET /api/account-merges/destinations - Get destination account patterns
@@ -89,21 +92,22 @@ async fn get_recent_account_merges(
     get,
     path = "/api/account-merges/destinations",
     params(
-        ("limit" = Option<i64>, Query, description = "Maximum number of results (1-100, default 20)")
+        ("limit" = Option<i64>, Query, description = "Maximum number of results (1-MAX_PAGE_SIZE, default 20)")
     ),
     responses(
-        (status = 200, description = "List of destination account patterns")
+        (status = 200, description = "List of destination account patterns"),
+        (status = 400, description = "limit exceeds MAX_PAGE_SIZE")
     ),
     tag = "Account Merges"
 )]
 async fn get_destination_patterns(
     State(detector): State<Arc<AccountMergeDetector>>,
     Query(params): Query<DestinationParams>,
-) -> Json<Vec<DestinationAccountPattern>> {
-    let limit = params.limit.clamp(1, 100);
+) -> ApiResult<Json<Vec<DestinationAccountPattern>>> {
+    let limit = validate_limit(params.limit)?;
     let patterns = detector
         .get_destination_patterns(limit)
         .await
         .unwrap_or_default();
-    Json(patterns)
+    Ok(Json(patterns))
 }
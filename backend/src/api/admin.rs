@@ -0,0 +1,142 @@
+//! Bearer-token guard for everything mounted under `/admin`.
+//!
+//! Admin routers (backfill, read-only toggle, job control, circuit breakers,
+//! RPC error log) are merged in `main.rs` and wrapped once with
+//! [`require_admin`], so a new admin endpoint can't ship unauthenticated by
+//! forgetting its own check. Requests need
+//! `Authorization: Bearer <ADMIN_API_TOKEN>`; with no token configured every
+//! admin endpoint answers `503 ADMIN_API_DISABLED`.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Request, State},
+    http::header::AUTHORIZATION,
+    middleware::{self, Next},
+    response::Response,
+    Router,
+};
+
+use crate::crypto::constant_time_eq;
+use crate::error::ApiError;
+
+#[derive(Clone, Default)]
+pub struct AdminAuth {
+    /// Shared bearer token; `None` disables the admin API
+    token: Option<Arc<str>>,
+}
+
+impl AdminAuth {
+    #[must_use]
+    pub fn new(token: Option<&str>) -> Self {
+        Self {
+            token: token.filter(|t| !t.trim().is_empty()).map(Arc::from),
+        }
+    }
+
+    /// Read the bearer token from `ADMIN_API_TOKEN`, falling back to the
+    /// older `ADMIN_JOBS_TOKEN`
+    #[must_use]
+    pub fn from_env() -> Self {
+        let token = std::env::var("ADMIN_API_TOKEN")
+            .ok()
+            .filter(|t| !t.trim().is_empty())
+            .or_else(|| std::env::var("ADMIN_JOBS_TOKEN").ok());
+        Self::new(token.as_deref())
+    }
+
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.token.is_some()
+    }
+}
+
+async fn require_admin_token(
+    State(auth): State<AdminAuth>,
+    req: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let Some(expected) = auth.token.as_deref() else {
+        return Err(ApiError::service_unavailable(
+            "ADMIN_API_DISABLED",
+            "Set ADMIN_API_TOKEN to enable admin endpoints",
+        ));
+    };
+    let provided = req
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .unwrap_or_default();
+    if !constant_time_eq(provided.as_bytes(), expected.as_bytes()) {
+        return Err(ApiError::unauthorized(
+            "INVALID_ADMIN_TOKEN",
+            "Missing or invalid admin bearer token",
+        ));
+    }
+    Ok(next.run(req).await)
+}
+
+/// Require the admin bearer token on every route of `router`
+pub fn require_admin<S>(router: Router<S>, auth: AdminAuth) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    router.route_layer(middleware::from_fn_with_state(auth, require_admin_token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use axum::routing::get;
+    use tower::ServiceExt;
+
+    async fn get_status(auth: AdminAuth, header: Option<&str>) -> (StatusCode, serde_json::Value) {
+        let app = require_admin(Router::new().route("/ping", get(|| async { "{}" })), auth);
+        let mut request = Request::builder().uri("/ping");
+        if let Some(header) = header {
+            request = request.header(AUTHORIZATION, header);
+        }
+        let response = app
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_admin_routes_require_bearer_token() {
+        let auth = AdminAuth::new(Some("s3cret"));
+
+        let (status, json) = get_status(auth.clone(), None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(json["error"]["code"], "INVALID_ADMIN_TOKEN");
+        let (status, _) = get_status(auth.clone(), Some("Bearer wrong")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, _) = get_status(auth, Some("Bearer s3cret")).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_admin_api_disabled_without_token() {
+        let (status, json) = get_status(AdminAuth::new(None), Some("Bearer anything")).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(json["error"]["code"], "ADMIN_API_DISABLED");
+    }
+
+    #[test]
+    fn test_token_falls_back_to_jobs_token() {
+        let _guard = crate::lock_env_test();
+        std::env::remove_var("ADMIN_API_TOKEN");
+        std::env::set_var("ADMIN_JOBS_TOKEN", "legacy");
+        assert!(AdminAuth::from_env().is_enabled());
+        std::env::remove_var("ADMIN_JOBS_TOKEN");
+        assert!(!AdminAuth::from_env().is_enabled());
+    }
+}
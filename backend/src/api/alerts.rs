@@ -1,15 +1,20 @@
 use axum::{
-    extract::{ws::WebSocket, Path, State, WebSocketUpgrade},
+    extract::{
+        ws::{Message, WebSocket},
+        Path, State, WebSocketUpgrade,
+    },
     http::StatusCode,
     response::{IntoResponse, Response},
     routing::{get, post, put},
     Json, Router,
 };
 use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use tokio::sync::broadcast;
 
 use crate::{
-    alerts::AlertManager,
+    alerts::{Alert, AlertManager, AlertType},
     auth_middleware::AuthUser,
     error::{ApiError, ApiResult},
     models::alerts::{CreateAlertRuleRequest, SnoozeAlertRequest, UpdateAlertRuleRequest},
@@ -249,7 +254,63 @@ async fn snooze_rule_from_history(
 }
 // WebSocket Handler for real-time alerts
 
-pub fn alert_websocket_handler(
+/// Filter a client sends over the alert socket; unset fields match everything.
+///
+/// Each message replaces the previous filter, so `{}` resets to all alerts.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AlertSubscription {
+    #[serde(default)]
+    pub alert_types: Option<Vec<AlertType>>,
+    #[serde(default)]
+    pub corridor_id: Option<String>,
+    #[serde(default)]
+    pub anchor_id: Option<String>,
+}
+
+impl AlertSubscription {
+    #[must_use]
+    pub fn matches(&self, alert: &Alert) -> bool {
+        self.alert_types
+            .as_ref()
+            .is_none_or(|types| types.contains(&alert.alert_type))
+            && self
+                .corridor_id
+                .as_ref()
+                .is_none_or(|id| alert.corridor_id.as_ref() == Some(id))
+            && self
+                .anchor_id
+                .as_ref()
+                .is_none_or(|id| alert.anchor_id.as_ref() == Some(id))
+    }
+}
+
+/// Messages pushed to alert socket clients
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AlertServerMessage {
+    Alert(Alert),
+    /// Acknowledges a filter update
+    Subscribed {
+        filter: AlertSubscription,
+    },
+    /// The client fell behind and `dropped` alerts were discarded
+    Lagged {
+        dropped: u64,
+    },
+    /// The last client message could not be parsed as a filter
+    Error {
+        message: String,
+    },
+}
+
+/// Mounts `GET /ws/alerts`.
+pub fn ws_routes(alert_manager: Arc<AlertManager>) -> Router {
+    Router::new()
+        .route("/ws/alerts", get(alert_websocket_handler))
+        .with_state(alert_manager)
+}
+
+pub async fn alert_websocket_handler(
     ws: WebSocketUpgrade,
     State(alert_manager): State<Arc<AlertManager>>,
 ) -> Response {
@@ -259,26 +320,130 @@ pub fn alert_websocket_handler(
 async fn handle_alert_socket(socket: WebSocket, alert_manager: Arc<AlertManager>) {
     let (mut sender, mut receiver) = socket.split();
     let mut rx = alert_manager.subscribe();
+    let mut filter = AlertSubscription::default();
 
-    let mut send_task = tokio::spawn(async move {
-        while let Ok(alert) = rx.recv().await {
-            if let Ok(msg) = serde_json::to_string(&alert) {
-                if sender
-                    .send(axum::extract::ws::Message::Text(msg.into()))
-                    .await
-                    .is_err()
-                {
-                    break;
+    loop {
+        let outgoing = tokio::select! {
+            received = rx.recv() => match received {
+                Ok(alert) if filter.matches(&alert) => AlertServerMessage::Alert(alert),
+                Ok(_) => continue,
+                Err(broadcast::error::RecvError::Lagged(dropped)) => {
+                    tracing::warn!(dropped, "Alert websocket client lagged");
+                    AlertServerMessage::Lagged { dropped }
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            incoming = receiver.next() => match incoming {
+                Some(Ok(Message::Text(text))) => {
+                    match serde_json::from_str::<AlertSubscription>(text.as_str()) {
+                        Ok(subscription) => {
+                            filter = subscription;
+                            AlertServerMessage::Subscribed {
+                                filter: filter.clone(),
+                            }
+                        }
+                        Err(e) => AlertServerMessage::Error {
+                            message: format!("invalid subscription: {e}"),
+                        },
+                    }
                 }
-            }
+                Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                Some(Ok(_)) => continue,
+            },
+        };
+
+        let Ok(msg) = serde_json::to_string(&outgoing) else {
+            continue;
+        };
+        if sender.send(Message::Text(msg.into())).await.is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{SinkExt, StreamExt};
+    use tokio_tungstenite::{connect_async, tungstenite::Message as WsMessage};
+
+    async fn serve(alert_manager: Arc<AlertManager>) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, ws_routes(alert_manager))
+                .await
+                .unwrap();
+        });
+        format!("ws://{addr}/ws/alerts")
+    }
+
+    async fn next_json<S>(stream: &mut S) -> serde_json::Value
+    where
+        S: StreamExt<Item = Result<WsMessage, tokio_tungstenite::tungstenite::Error>> + Unpin,
+    {
+        let msg = tokio::time::timeout(std::time::Duration::from_secs(5), stream.next())
+            .await
+            .expect("timed out waiting for alert")
+            .unwrap()
+            .unwrap();
+        serde_json::from_str(msg.to_text().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_alert_socket_streams_filtered_alerts() {
+        let (manager, _rx) = AlertManager::with_capacity(10);
+        let manager = Arc::new(manager);
+        let (mut socket, _) = connect_async(serve(Arc::clone(&manager)).await)
+            .await
+            .unwrap();
+
+        socket
+            .send(WsMessage::Text(
+                r#"{"corridor_id": "USDC-XLM", "alert_types": ["SuccessRateDrop"]}"#.into(),
+            ))
+            .await
+            .unwrap();
+        let ack = next_json(&mut socket).await;
+        assert_eq!(ack["type"], "subscribed");
+        assert_eq!(ack["filter"]["corridor_id"], "USDC-XLM");
+
+        // Other corridors and alert types are filtered out
+        manager.check_and_alert("EURC-XLM", 95.0, 30.0, 100.0, 100.0, 1000.0, 1000.0);
+        manager.check_and_alert("USDC-XLM", 95.0, 95.0, 100.0, 400.0, 1000.0, 1000.0);
+        manager.check_and_alert("USDC-XLM", 95.0, 30.0, 100.0, 100.0, 1000.0, 1000.0);
+
+        let alert = next_json(&mut socket).await;
+        assert_eq!(alert["type"], "alert");
+        assert_eq!(alert["alert_type"], "SuccessRateDrop");
+        assert_eq!(alert["corridor_id"], "USDC-XLM");
+        assert_eq!(alert["new_value"], 30.0);
+    }
+
+    #[tokio::test]
+    async fn test_lagged_client_is_notified_instead_of_disconnected() {
+        let (manager, _rx) = AlertManager::with_capacity(1);
+        let manager = Arc::new(manager);
+        let (mut socket, _) = connect_async(serve(Arc::clone(&manager)).await)
+            .await
+            .unwrap();
+        socket.send(WsMessage::Text("{}".into())).await.unwrap();
+        assert_eq!(next_json(&mut socket).await["type"], "subscribed");
+
+        for new_success in [80.0, 70.0, 60.0] {
+            manager.check_and_alert("USDC-XLM", 95.0, new_success, 100.0, 100.0, 1000.0, 1000.0);
         }
-    });
 
-    let mut recv_task =
-        tokio::spawn(async move { while let Some(Ok(_)) = receiver.next().await {} });
+        let lagged = next_json(&mut socket).await;
+        assert_eq!(lagged["type"], "lagged");
+        assert_eq!(lagged["dropped"], 2);
 
-    tokio::select! {
-        _ = &mut send_task => recv_task.abort(),
-        _ = &mut recv_task => send_task.abort(),
+        let alert = next_json(&mut socket).await;
+        assert_eq!(alert["type"], "alert");
+        assert_eq!(alert["new_value"], 60.0);
+        assert!(matches!(
+            serde_json::from_value::<Alert>(alert).unwrap().alert_type,
+            AlertType::SuccessRateDrop
+        ));
     }
 }
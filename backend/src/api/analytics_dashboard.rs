@@ -1,7 +1,14 @@
-use axum::{extract::State, routing::get, Json, Router};
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::cache::helpers::cached_query;
+use crate::cache::helpers::{cached_query_with_refresh, RefreshQuery};
 use crate::cache::{keys, CacheManager};
 use crate::state::AppState;
 
@@ -49,21 +56,26 @@ struct CorridorPerformanceRow {
 #[utoipa::path(
     get,
     path = "/analytics/dashboard",
+    params(RefreshQuery),
     responses(
         (status = 200, description = "Analytics dashboard data", body = AnalyticsDashboardData),
+        (status = 304, description = "Not modified (ETag or Last-Modified match)"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Analytics"
 )]
 pub async fn analytics_dashboard(
     State(app_state): State<AppState>,
-) -> Json<AnalyticsDashboardData> {
+    Query(RefreshQuery { refresh }): Query<RefreshQuery>,
+    headers: HeaderMap,
+) -> Response {
     let cache_key = keys::analytics_dashboard();
 
-    let dashboard_data = cached_query(
+    let dashboard_data = cached_query_with_refresh(
         &app_state.cache,
         &cache_key,
         app_state.cache.config.get_ttl("dashboard"),
+        refresh,
         || async {
             // Generate real analytics data based on database queries
             let time_series_data = generate_time_series_data()?;
@@ -80,7 +92,34 @@ pub async fn analytics_dashboard(
     .await
     .unwrap_or_else(|_| generate_fallback_data());
 
-    Json(dashboard_data)
+    let ttl = app_state.cache.config.get_ttl("dashboard");
+    let response = match latest_ledger_close_time(&app_state).await {
+        Some(last_modified) => crate::http_cache::cached_json_response_with_last_modified(
+            &headers,
+            &dashboard_data,
+            last_modified,
+            ttl,
+        ),
+        None => crate::http_cache::cached_json_response(&headers, &cache_key, &dashboard_data, ttl),
+    };
+
+    match response {
+        Ok(response) => response,
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+/// Close time of the newest ingested ledger, used as the dashboard's `Last-Modified`
+async fn latest_ledger_close_time(app_state: &AppState) -> Option<DateTime<Utc>> {
+    sqlx::query_scalar::<_, Option<DateTime<Utc>>>("SELECT MAX(close_time) FROM ledgers")
+        .fetch_one(app_state.db.pool())
+        .await
+        .ok()
+        .flatten()
 }
 
 fn generate_time_series_data() -> Result<Vec<NetworkVolumeDataPoint>, anyhow::Error> {
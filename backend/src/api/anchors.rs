@@ -13,15 +13,16 @@ use uuid::Uuid;
 // use anyhow::Context;
 
 use crate::broadcast::broadcast_anchor_update;
-use crate::cache::helpers::cached_query;
+use crate::cache::helpers::{cached_query_or_stale_with_refresh, RefreshQuery};
 use crate::cache::keys;
 use crate::cache::CacheManager;
 use crate::database::Database;
 use crate::error::{ApiError, ApiResult};
 use crate::models::{AnchorDetailResponse, CreateAnchorRequest};
-use crate::pagination::PaginatedResponse;
+use crate::pagination::{validate_limit, PaginatedResponse};
 use crate::rpc::circuit_breaker::rpc_circuit_breaker;
 use crate::rpc::error::{with_retry, RetryConfig, RpcError};
+use crate::rpc::retry_budget::rpc_retry_budget;
 use crate::rpc::StellarRpcClient;
 use crate::services::price_feed::PriceFeedClient;
 use crate::state::AppState;
@@ -46,8 +47,9 @@ pub struct ListAnchorsResponse {
 #[derive(Debug, Deserialize, IntoParams)]
 #[into_params(parameter_in = Query)]
 pub struct MuxedAnalyticsQuery {
+    /// Top muxed accounts to return (1-MAX_PAGE_SIZE)
     #[serde(default = "default_muxed_limit")]
-    #[param(example = 20, minimum = 1, maximum = 100)]
+    #[param(example = 20, minimum = 1)]
     pub limit: i64,
 }
 
@@ -141,6 +143,7 @@ pub async fn get_anchor_by_account(
     params(MuxedAnalyticsQuery),
     responses(
         (status = 200, description = "Muxed account analytics retrieved successfully"),
+        (status = 400, description = "limit exceeds MAX_PAGE_SIZE"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Anchors"
@@ -150,7 +153,7 @@ pub async fn get_muxed_analytics(
     State(app_state): State<AppState>,
     Query(params): Query<MuxedAnalyticsQuery>,
 ) -> ApiResult<Json<crate::models::MuxedAccountAnalytics>> {
-    let limit = params.limit.clamp(1, 100);
+    let limit = validate_limit(params.limit)?;
     let analytics = app_state.db.get_muxed_analytics(limit).await?;
     Ok(Json(analytics))
 }
@@ -161,8 +164,11 @@ pub async fn create_anchor(
     State(app_state): State<AppState>,
     crate::validation::ValidatedJson(req): crate::validation::ValidatedJson<CreateAnchorRequest>,
 ) -> ApiResult<Json<crate::models::Anchor>> {
-    // Business logic: stellar account must start with 'G'
-    crate::validation::validate_stellar_account(&req.stellar_account)?;
+    // Business logic: anchors register under a plain G-address
+    crate::validation::validate_stellar_address(
+        &req.stellar_account,
+        crate::validation::StellarAddressKind::Anchor,
+    )?;
 
     let anchor = app_state.db.create_anchor(req).await?;
 
@@ -297,7 +303,7 @@ pub async fn create_anchor_asset(
 #[derive(Debug, Deserialize, IntoParams)]
 #[into_params(parameter_in = Query)]
 pub struct ListAnchorsQuery {
-    /// Maximum number of results to return (default: 50)
+    /// Maximum number of results to return (1-MAX_PAGE_SIZE, default: 50)
     #[serde(default = "default_limit")]
     #[param(example = 50)]
     pub limit: i64,
@@ -325,6 +331,7 @@ pub async fn get_anchor_metrics_with_rpc(
         },
         RetryConfig::default(),
         circuit_breaker,
+        &rpc_retry_budget(),
     )
     .await
     .map_err(|e| match e {
@@ -401,7 +408,8 @@ pub struct AnchorsResponse {
 /// List all anchors with key metrics
 ///
 /// Returns a paginated list of all anchors with their performance metrics.
-/// Data is cached for improved performance.
+/// Data is cached for improved performance. While the RPC circuit breaker is
+/// open the last good list is served with `x-data-stale: true`.
 ///
 /// **DATA SOURCE: RPC + Database**
 /// - Anchor metadata (name, account) from database
@@ -409,14 +417,15 @@ pub struct AnchorsResponse {
 #[utoipa::path(
     get,
     path = "/api/anchors",
-    params(ListAnchorsQuery),
+    params(ListAnchorsQuery, RefreshQuery),
     responses(
         (status = 200, description = "List of anchors retrieved successfully", body = AnchorsResponse),
+        (status = 400, description = "limit exceeds MAX_PAGE_SIZE"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Anchors"
 )]
-#[tracing::instrument(skip(db, cache, rpc_client, _price_feed, params, headers), fields(limit = params.limit, offset = params.offset))]
+#[tracing::instrument(skip(db, cache, rpc_client, _price_feed, params, refresh, headers), fields(limit = params.limit, offset = params.offset))]
 pub async fn get_anchors(
     State((db, cache, rpc_client, _price_feed)): State<(
         Arc<Database>,
@@ -425,14 +434,17 @@ pub async fn get_anchors(
         Arc<PriceFeedClient>,
     )>,
     Query(params): Query<ListAnchorsQuery>,
+    Query(RefreshQuery { refresh }): Query<RefreshQuery>,
     headers: HeaderMap,
 ) -> ApiResult<Response> {
+    validate_limit(params.limit)?;
     let cache_key = keys::anchor_list(params.limit, params.offset);
 
-    let response = cached_query(
+    let response = cached_query_or_stale_with_refresh(
         &cache,
         &cache_key,
         cache.config.get_ttl("anchor"),
+        refresh,
         || async {
             // Get anchor metadata from database (names, accounts, etc.)
             let anchors: Vec<crate::models::Anchor> =
@@ -471,7 +483,7 @@ pub async fn get_anchors(
 
                 // **RPC DATA**: Fetch real-time payment data for this anchor with pagination
                 // Wrapped in circuit breaker as requested in Issue #671
-                let payments: Vec<crate::rpc::Payment> = with_retry(
+                let payments: Vec<crate::rpc::Payment> = match with_retry(
                     || async {
                         rpc_client
                             .fetch_all_account_payments(&anchor.stellar_account, Some(500))
@@ -480,17 +492,24 @@ pub async fn get_anchors(
                     },
                     RetryConfig::default(),
                     circuit_breaker.clone(),
+                    &rpc_retry_budget(),
                 )
                 .await
-                .map_err(|e| anyhow::anyhow!(e.to_string()))
-                .unwrap_or_else(|e| {
-                    tracing::warn!(
-                        "Failed to fetch payments for anchor {}: {}",
-                        anchor.stellar_account,
-                        e
-                    );
-                    vec![]
-                });
+                {
+                    Ok(payments) => payments,
+                    // Fail the whole list so the stale copy is served instead
+                    Err(RpcError::CircuitBreakerOpen) => {
+                        anyhow::bail!("Circuit breaker open - RPC service unavailable")
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            "Failed to fetch payments for anchor {}: {}",
+                            anchor.stellar_account,
+                            e
+                        );
+                        vec![]
+                    }
+                };
 
                 // Calculate metrics from RPC payment data
                 let (total_transactions, successful_transactions, failed_transactions) =
@@ -556,8 +575,10 @@ pub async fn get_anchors(
     .await?;
 
     let ttl = cache.config.get_ttl("anchor");
-    let response = crate::http_cache::cached_json_response(&headers, &cache_key, &response, ttl)?;
-    Ok(response)
+    let mut cached =
+        crate::http_cache::cached_json_response(&headers, &cache_key, &response.value, ttl)?;
+    response.mark_response(&mut cached);
+    Ok(cached)
 }
 
 #[cfg(test)]
@@ -592,6 +613,82 @@ mod tests {
             .contains("Circuit breaker open"));
     }
 
+    #[tokio::test]
+    async fn test_anchor_list_serves_stale_copy_while_breaker_is_open() {
+        let _guard = crate::lock_env_test();
+        let pool = sqlx::SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::query(include_str!("../../migrations/001_create_anchors.sql"))
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO anchors (id, name, stellar_account, created_at, updated_at)
+             VALUES (?, 'Live Anchor', 'GLIVE', '2026-01-01T00:00:00Z', '2026-01-01T00:00:00Z')",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .execute(&pool)
+        .await
+        .unwrap();
+        let cache = Arc::new(CacheManager::new_in_memory_for_tests(CacheConfig::default()));
+        let price_feed = Arc::new(PriceFeedClient::new(
+            crate::services::price_feed::PriceFeedConfig::default(),
+            crate::services::price_feed::default_asset_mapping(),
+        ));
+
+        // A successful earlier fetch left the stale copy; the fresh entry expired
+        let key = keys::anchor_list(50, 0);
+        let stale = AnchorMetricsResponse {
+            id: "1".to_string(),
+            name: "Stale Anchor".to_string(),
+            stellar_account: "GSTALE".to_string(),
+            reliability_score: 99.0,
+            asset_coverage: 0,
+            failure_rate: 0.0,
+            total_transactions: 10,
+            successful_transactions: 10,
+            failed_transactions: 0,
+            status: "green".to_string(),
+        };
+        crate::cache::helpers::cached_query_or_stale(&cache, &key, 60, || async {
+            Ok(PaginatedResponse::new(vec![stale], 1, 50, 0))
+        })
+        .await
+        .unwrap();
+        cache.delete(&key).await.unwrap();
+
+        let circuit_breaker = rpc_circuit_breaker();
+        while matches!(
+            circuit_breaker.call(|| Err::<(), anyhow::Error>(anyhow::anyhow!("forced failure"))),
+            Err(failsafe::Error::Inner(_))
+        ) {}
+
+        let response = get_anchors(
+            State((
+                Arc::new(Database::new(pool)),
+                cache,
+                Arc::new(StellarRpcClient::new_with_defaults(true)),
+                price_feed,
+            )),
+            Query(ListAnchorsQuery {
+                limit: 50,
+                offset: 0,
+            }),
+            Query(RefreshQuery { refresh: false }),
+            HeaderMap::new(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            response.headers()[crate::cache::helpers::DATA_STALE_HEADER],
+            "true"
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["data"][0]["name"], "Stale Anchor");
+    }
+
     #[tokio::test]
     #[ignore = "StellarRpcClient::fetch_anchor_metrics is currently a stub that always \
         returns Ok(..) with hardcoded data (see rpc/stellar.rs), so the circuit breaker \
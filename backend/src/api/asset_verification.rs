@@ -13,6 +13,7 @@ use uuid::Uuid;
 use crate::models::asset_verification::{
     ListVerifiedAssetsQuery, ReportAssetRequest, VerifiedAssetResponse,
 };
+use crate::muxed::is_valid_stellar_public_key;
 use crate::services::asset_verifier::AssetVerifier;
 
 /// Create asset verification routes
@@ -398,11 +399,6 @@ async fn report_suspicious_asset(
     }
 }
 
-/// Validate Stellar public key format
-fn is_valid_stellar_public_key(key: &str) -> bool {
-    key.len() == 56 && key.starts_with('G')
-}
-
 /// Validate URL format
 fn is_valid_url(url: &str) -> bool {
     url.starts_with("http://") || url.starts_with("https://")
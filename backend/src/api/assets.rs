@@ -0,0 +1,392 @@
+//! Per-asset overview endpoints.
+//!
+//! # Endpoints
+//!
+//! | Method | Path                          | Description                          |
+//! |--------|-------------------------------|--------------------------------------|
+//! | GET    | `/api/assets`                 | Paginated asset rollups              |
+//! | GET    | `/api/assets/{code}:{issuer}` | Rollup for one asset (or `native`)   |
+//!
+//! Each rollup combines payment and trade volume (in units of the asset)
+//! over the rows still inside their retention windows (`RETENTION_*_DAYS`),
+//! active corridors touching the asset, still-locked claimable balances and
+//! the trustline count as a holder estimate.
+
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+use crate::error::{ApiError, ApiResult};
+use crate::pagination::{validate_limit, PaginatedResponse};
+
+/// Every asset seen in payments, trades, corridors, claimable balances or
+/// trustline stats, keyed as `native` or `CODE:ISSUER`.
+const ASSET_STATS_CTE: &str = "
+WITH volumes AS (
+    SELECT asset, SUM(volume) AS total_volume FROM (
+        SELECT CASE WHEN asset_type = 'native' OR asset_code IS NULL THEN 'native'
+                    ELSE asset_code || ':' || COALESCE(asset_issuer, '') END AS asset,
+               amount AS volume
+        FROM payments
+        UNION ALL
+        SELECT base_asset, CAST(base_amount AS REAL) FROM trades
+        UNION ALL
+        SELECT counter_asset, CAST(counter_amount AS REAL) FROM trades
+    )
+    GROUP BY asset
+),
+corridor_assets AS (
+    SELECT asset, COUNT(DISTINCT id) AS active_corridors FROM (
+        SELECT id, CASE WHEN source_asset_issuer = 'native' THEN 'native'
+                        ELSE source_asset_code || ':' || source_asset_issuer END AS asset
+        FROM corridors WHERE status = 'active'
+        UNION ALL
+        SELECT id, CASE WHEN destination_asset_issuer = 'native' THEN 'native'
+                        ELSE destination_asset_code || ':' || destination_asset_issuer END
+        FROM corridors WHERE status = 'active'
+    )
+    GROUP BY asset
+),
+locked AS (
+    SELECT asset, COUNT(*) AS balance_count, SUM(CAST(amount AS REAL)) AS balance_amount
+    FROM claimable_balances
+    WHERE claimed_at IS NULL
+    GROUP BY asset
+),
+holders AS (
+    SELECT asset_code || ':' || asset_issuer AS asset, total_trustlines
+    FROM trustline_stats
+),
+assets AS (
+    SELECT asset FROM volumes
+    UNION SELECT asset FROM corridor_assets
+    UNION SELECT asset FROM locked
+    UNION SELECT asset FROM holders
+)";
+
+const SELECT_ASSET_STATS: &str = "
+SELECT a.asset,
+       COALESCE(v.total_volume, 0.0) AS total_volume,
+       COALESCE(c.active_corridors, 0) AS active_corridors,
+       COALESCE(l.balance_count, 0) AS locked_claimable_balances,
+       COALESCE(l.balance_amount, 0.0) AS locked_claimable_amount,
+       h.total_trustlines AS holder_count_estimate
+FROM assets a
+LEFT JOIN volumes v ON v.asset = a.asset
+LEFT JOIN corridor_assets c ON c.asset = a.asset
+LEFT JOIN locked l ON l.asset = a.asset
+LEFT JOIN holders h ON h.asset = a.asset";
+
+/// Aggregated statistics for a single asset
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssetStats {
+    /// `native` or `CODE:ISSUER`
+    pub asset: String,
+    pub asset_code: String,
+    pub asset_issuer: Option<String>,
+    /// Payment plus trade volume, in units of the asset, over the rows not
+    /// yet pruned by the retention job
+    pub total_volume: f64,
+    pub active_corridors: i64,
+    /// Claimable balances of this asset that have not been claimed yet
+    pub locked_claimable_balances: i64,
+    pub locked_claimable_amount: f64,
+    /// Trustline count; `null` for XLM and assets without trustline stats
+    pub holder_count_estimate: Option<i64>,
+}
+
+type AssetStatsRow = (String, f64, i64, i64, f64, Option<i64>);
+
+fn from_row(
+    (
+        asset,
+        total_volume,
+        active_corridors,
+        locked_claimable_balances,
+        locked_claimable_amount,
+        holder_count_estimate,
+    ): AssetStatsRow,
+) -> AssetStats {
+    let (asset_code, asset_issuer) = match asset.split_once(':') {
+        Some((code, issuer)) => (code.to_string(), Some(issuer.to_string())),
+        None => ("XLM".to_string(), None),
+    };
+    AssetStats {
+        asset,
+        asset_code,
+        asset_issuer,
+        total_volume,
+        active_corridors,
+        locked_claimable_balances,
+        locked_claimable_amount,
+        holder_count_estimate,
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetSortBy {
+    #[default]
+    Volume,
+    ActiveCorridors,
+    LockedBalances,
+    Holders,
+}
+
+impl AssetSortBy {
+    const fn order_by(self) -> &'static str {
+        match self {
+            Self::Volume => "total_volume DESC",
+            Self::ActiveCorridors => "active_corridors DESC",
+            Self::LockedBalances => "locked_claimable_balances DESC",
+            Self::Holders => "COALESCE(holder_count_estimate, 0) DESC",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListAssetsQuery {
+    /// Maximum number of assets to return (1-MAX_PAGE_SIZE, default 50)
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    /// Pagination offset (default: 0)
+    #[serde(default)]
+    pub offset: i64,
+    /// `volume` (default), `active_corridors`, `locked_balances` or `holders`
+    #[serde(default)]
+    pub sort_by: AssetSortBy,
+}
+
+const fn default_limit() -> i64 {
+    50
+}
+
+pub async fn list_asset_stats(
+    pool: &SqlitePool,
+    sort_by: AssetSortBy,
+    limit: i64,
+    offset: i64,
+) -> Result<(Vec<AssetStats>, i64), sqlx::Error> {
+    let (total,): (i64,) =
+        sqlx::query_as(&format!("{ASSET_STATS_CTE} SELECT COUNT(*) FROM assets"))
+            .fetch_one(pool)
+            .await?;
+
+    let rows: Vec<AssetStatsRow> = sqlx::query_as(&format!(
+        "{ASSET_STATS_CTE} {SELECT_ASSET_STATS} ORDER BY {}, a.asset LIMIT ? OFFSET ?",
+        sort_by.order_by()
+    ))
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool)
+    .await?;
+
+    Ok((rows.into_iter().map(from_row).collect(), total))
+}
+
+pub async fn get_asset_stats(
+    pool: &SqlitePool,
+    asset: &str,
+) -> Result<Option<AssetStats>, sqlx::Error> {
+    let row: Option<AssetStatsRow> = sqlx::query_as(&format!(
+        "{ASSET_STATS_CTE} {SELECT_ASSET_STATS} WHERE a.asset = ?"
+    ))
+    .bind(asset)
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.map(from_row))
+}
+
+/// GET /api/assets - Paginated per-asset rollups, sorted by volume by default
+async fn list_assets(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<ListAssetsQuery>,
+) -> ApiResult<Json<PaginatedResponse<AssetStats>>> {
+    let limit = validate_limit(params.limit)?;
+    let offset = params.offset.max(0);
+    let (assets, total) = list_asset_stats(&pool, params.sort_by, limit, offset).await?;
+    Ok(Json(PaginatedResponse::new(assets, total, limit, offset)))
+}
+
+/// GET /api/assets/{code}:{issuer} - Rollup for one asset; `native` for XLM
+async fn get_asset(
+    State(pool): State<SqlitePool>,
+    Path(asset): Path<String>,
+) -> ApiResult<Json<AssetStats>> {
+    let valid = asset == "native"
+        || asset
+            .split_once(':')
+            .is_some_and(|(code, issuer)| !code.is_empty() && !issuer.is_empty());
+    if !valid {
+        return Err(ApiError::bad_request(
+            "INVALID_ASSET",
+            "Asset must be `native` or `CODE:ISSUER`",
+        ));
+    }
+
+    get_asset_stats(&pool, &asset)
+        .await?
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found("ASSET_NOT_FOUND", format!("No data for asset {asset}")))
+}
+
+pub fn routes(pool: SqlitePool) -> Router {
+    Router::new()
+        .route("/", get(list_assets))
+        .route("/{asset}", get(get_asset))
+        .with_state(pool)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    const USDC: &str = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZAA";
+
+    async fn seeded_pool() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        for migration in [
+            include_str!("../../migrations/002_create_metrics_corridors_snapshots.sql"),
+            include_str!("../../migrations/003_create_ingestion_and_payments.sql"),
+            include_str!("../../migrations/010_create_trustlines.sql"),
+            include_str!("../../migrations/039_create_trades.sql"),
+            include_str!("../../migrations/040_create_claimable_balances.sql"),
+        ] {
+            sqlx::query(migration).execute(&pool).await.unwrap();
+        }
+
+        let (code, issuer) = USDC.split_once(':').unwrap();
+        sqlx::query(
+            "INSERT INTO payments (id, transaction_hash, source_account, destination_account,
+                                   asset_type, asset_code, asset_issuer, amount, created_at)
+             VALUES ('p1', 'tx1', 'GA', 'GB', 'credit_alphanum4', ?1, ?2, 100.0, '2026-01-01'),
+                    ('p2', 'tx2', 'GA', 'GB', 'credit_alphanum4', ?1, ?2, 50.5, '2026-01-01'),
+                    ('p3', 'tx3', 'GA', 'GB', 'native', NULL, NULL, 10.0, '2026-01-01')",
+        )
+        .bind(code)
+        .bind(issuer)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO trades (id, ledger_close_time, base_asset, counter_asset,
+                                 base_amount, counter_amount, price_n, price_d, trade_type)
+             VALUES ('t1', '2026-01-01T00:00:00Z', 'native', ?, '400', '49.5', 1, 8, 'orderbook')",
+        )
+        .bind(USDC)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO claimable_balances (id, asset, amount, claimed_at)
+             VALUES ('cb1', ?1, '25', NULL),
+                    ('cb2', ?1, '5', NULL),
+                    ('cb3', ?1, '1000', '2026-01-02'),
+                    ('cb4', 'native', '7', NULL)",
+        )
+        .bind(USDC)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO corridors (id, source_asset_code, source_asset_issuer,
+                                    destination_asset_code, destination_asset_issuer, status)
+             VALUES ('c1', 'XLM', 'native', ?1, ?2, 'active'),
+                    ('c2', ?1, ?2, 'EURC', ?2, 'inactive')",
+        )
+        .bind(code)
+        .bind(issuer)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO trustline_stats (asset_code, asset_issuer, total_trustlines)
+             VALUES (?, ?, 42)",
+        )
+        .bind(code)
+        .bind(issuer)
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool
+    }
+
+    async fn get_json(app: &Router, uri: &str) -> (StatusCode, serde_json::Value) {
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_asset_stats_aggregate_volume_and_locked_balances() {
+        let pool = seeded_pool().await;
+
+        let (assets, total) = list_asset_stats(&pool, AssetSortBy::Volume, 10, 0)
+            .await
+            .unwrap();
+        assert_eq!(total, 2);
+        assert_eq!(
+            assets.iter().map(|a| a.asset.as_str()).collect::<Vec<_>>(),
+            ["native", USDC]
+        );
+
+        let native = &assets[0];
+        assert_eq!(native.asset_code, "XLM");
+        assert!((native.total_volume - 410.0).abs() < 1e-9);
+        assert_eq!(native.locked_claimable_balances, 1);
+        assert_eq!(native.active_corridors, 1);
+        assert_eq!(native.holder_count_estimate, None);
+
+        let usdc = &assets[1];
+        assert!((usdc.total_volume - 200.0).abs() < 1e-9);
+        assert_eq!(usdc.locked_claimable_balances, 2);
+        assert!((usdc.locked_claimable_amount - 30.0).abs() < 1e-9);
+        assert_eq!(usdc.active_corridors, 1);
+        assert_eq!(usdc.holder_count_estimate, Some(42));
+
+        let (by_holders, _) = list_asset_stats(&pool, AssetSortBy::Holders, 1, 0)
+            .await
+            .unwrap();
+        assert_eq!(by_holders[0].asset, USDC);
+    }
+
+    #[tokio::test]
+    async fn test_asset_routes() {
+        let app = routes(seeded_pool().await);
+
+        let (status, json) = get_json(&app, "/?limit=1").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["data"][0]["asset"], "native");
+        assert_eq!(json["pagination"]["total"], 2);
+        assert_eq!(json["pagination"]["has_next"], true);
+
+        let (status, json) = get_json(&app, &format!("/{USDC}")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["locked_claimable_balances"], 2);
+        assert_eq!(json["asset_code"], "USDC");
+
+        let (status, _) = get_json(&app, "/EURC:GUNKNOWN").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _) = get_json(&app, "/USDC").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let oversized = crate::pagination::max_page_size() + 1;
+        let (status, json) = get_json(&app, &format!("/?limit={oversized}")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(json["error"]["code"], "INVALID_LIMIT");
+    }
+}
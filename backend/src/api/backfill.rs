@@ -1,12 +1,19 @@
-//! Admin endpoints for contract event backfill.
+//! Admin endpoints for contract event and ledger backfill.
 //!
 //! # Endpoints
 //!
-//! | Method | Path                    | Description                          |
-//! |--------|-------------------------|--------------------------------------|
-//! | POST   | `/admin/backfill`       | Start a backfill for a ledger range  |
-//! | GET    | `/admin/backfill/status`| Poll current backfill progress       |
+//! | Method | Path                             | Description                         |
+//! |--------|----------------------------------|-------------------------------------|
+//! | POST   | `/admin/backfill`                | Start a backfill for a ledger range |
+//! | POST   | `/admin/backfill/cancel`         | Stop the running backfill cleanly   |
+//! | GET    | `/admin/backfill/status`         | Poll current backfill progress      |
+//! | GET    | `/admin/backfill/ledgers`        | Poll the background ledger backfill |
+//! | POST   | `/admin/backfill/ledgers/cancel` | Stop the ledger backfill cleanly    |
+//!
+//! Starting or cancelling a backfill changes indexing for every client, so
+//! these are mounted behind [`require_admin`](crate::api::admin::require_admin).
 
+use crate::ingestion::backfill_checkpoint::{BackfillProgress, CheckpointedBackfill};
 use crate::jobs::backfill::{BackfillJob, BackfillRequest, BackfillState};
 use axum::{
     extract::State,
@@ -75,9 +82,39 @@ pub async fn start_backfill(
     ))
 }
 
+/// POST /admin/backfill/cancel
+///
+/// Asks the running backfill to stop. The worker commits its buffered events
+/// and checkpoint before exiting; status reads `cancelling` until then and
+/// `cancelled` afterwards.
+///
+/// # Responses
+///
+/// - `202 Accepted` — cancellation requested
+/// - `400 Bad Request` — no backfill is running
+pub async fn cancel_backfill(
+    State(job): State<Arc<BackfillJob>>,
+) -> Result<(StatusCode, Json<Value>), (StatusCode, Json<Value>)> {
+    job.cancel().await.map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": e.to_string() })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(json!({
+            "message": "Backfill cancellation requested",
+            "status_url": "/admin/backfill/status"
+        })),
+    ))
+}
+
 /// GET /admin/backfill/status
 ///
-/// Returns the current (or last completed) backfill state.
+/// Returns the current (or last completed) backfill state. `status` is one of
+/// `idle`, `running`, `completed`, `failed`, `cancelling` or `cancelled`.
 ///
 /// # Response
 ///
@@ -100,16 +137,165 @@ pub async fn get_backfill_status(State(job): State<Arc<BackfillJob>>) -> Json<Ba
     Json(job.status().await)
 }
 
+/// GET /admin/backfill/ledgers
+///
+/// Returns the [`BackfillProgress`] of the background ledger backfill started
+/// at boot. `cancelled` is `true` once a cancellation has taken effect.
+pub async fn get_ledger_backfill_progress(
+    State(backfill): State<Arc<CheckpointedBackfill>>,
+) -> Json<BackfillProgress> {
+    Json(backfill.progress().read().await.clone())
+}
+
+/// POST /admin/backfill/ledgers/cancel
+///
+/// Stops the ledger backfill once the page it is writing has been committed
+/// with its checkpoint. The next process start resumes from that checkpoint.
+///
+/// # Responses
+///
+/// - `202 Accepted` — cancellation requested
+/// - `400 Bad Request` — the ledger backfill was already cancelled
+pub async fn cancel_ledger_backfill(
+    State(backfill): State<Arc<CheckpointedBackfill>>,
+) -> Result<(StatusCode, Json<Value>), (StatusCode, Json<Value>)> {
+    backfill.cancel().map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": e.to_string() })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(json!({
+            "message": "Ledger backfill cancellation requested",
+            "status_url": "/admin/backfill/ledgers"
+        })),
+    ))
+}
+
+/// Build the admin router for the background ledger backfill, mounted next
+/// to [`routes`] behind the admin token.
+pub fn ledger_routes(backfill: Arc<CheckpointedBackfill>) -> Router {
+    Router::new()
+        .route("/backfill/ledgers", get(get_ledger_backfill_progress))
+        .route("/backfill/ledgers/cancel", post(cancel_ledger_backfill))
+        .with_state(backfill)
+}
+
 /// Build the admin backfill router.
 ///
-/// Mount this at `/admin` in the main router:
+/// Mount this at `/admin` in the main router, behind the admin token:
 ///
 /// ```rust,ignore
-/// app.nest("/admin", backfill::routes(backfill_job));
+/// app.nest("/admin", require_admin(backfill::routes(backfill_job), AdminAuth::from_env()));
 /// ```
 pub fn routes(job: Arc<BackfillJob>) -> Router {
     Router::new()
         .route("/backfill", post(start_backfill))
+        .route("/backfill/cancel", post(cancel_backfill))
         .route("/backfill/status", get(get_backfill_status))
         .with_state(job)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::admin::{require_admin, AdminAuth};
+    use crate::database::Database;
+    use crate::jobs::backfill::BackfillStatus;
+    use crate::services::event_indexer::EventIndexer;
+    use axum::body::Body;
+    use axum::http::{header::AUTHORIZATION, Method, Request};
+    use tokio::sync::RwLock;
+    use tower::ServiceExt;
+
+    async fn cancel(app: &Router, auth: Option<&str>) -> StatusCode {
+        post_status(app, "/backfill/cancel", auth).await
+    }
+
+    async fn post_status(app: &Router, uri: &str, auth: Option<&str>) -> StatusCode {
+        let mut request = Request::builder().method(Method::POST).uri(uri);
+        if let Some(auth) = auth {
+            request = request.header(AUTHORIZATION, auth);
+        }
+        app.clone()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn test_cancel_requires_admin_token() {
+        let pool = sqlx::SqlitePool::connect(":memory:").await.unwrap();
+        let state = Arc::new(RwLock::new(BackfillState {
+            status: BackfillStatus::Running,
+            ..BackfillState::default()
+        }));
+        let job = Arc::new(BackfillJob::new(
+            Arc::new(EventIndexer::new(Arc::new(Database::new(pool)))),
+            Arc::new(crate::rpc::StellarRpcClient::new_with_defaults(true)),
+            state.clone(),
+        ));
+        let app = require_admin(routes(job), AdminAuth::new(Some("s3cret")));
+
+        assert_eq!(cancel(&app, None).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            cancel(&app, Some("Bearer wrong")).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(state.read().await.status, BackfillStatus::Running);
+
+        assert_eq!(
+            cancel(&app, Some("Bearer s3cret")).await,
+            StatusCode::ACCEPTED
+        );
+        assert_eq!(state.read().await.status, BackfillStatus::Cancelling);
+    }
+
+    #[tokio::test]
+    async fn test_ledger_backfill_progress_and_cancel() {
+        let pool = sqlx::SqlitePool::connect(":memory:").await.unwrap();
+        let backfill = Arc::new(CheckpointedBackfill::new(
+            Arc::new(crate::rpc::StellarRpcClient::new_with_defaults(true)),
+            pool,
+        ));
+        let app = require_admin(
+            ledger_routes(Arc::clone(&backfill)),
+            AdminAuth::new(Some("s3cret")),
+        );
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/backfill/ledgers")
+                    .header(AUTHORIZATION, "Bearer s3cret")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let progress: BackfillProgress = serde_json::from_slice(&body).unwrap();
+        assert!(!progress.cancelled);
+        assert_eq!(progress.last_ledger_sequence, None);
+
+        let uri = "/backfill/ledgers/cancel";
+        assert_eq!(post_status(&app, uri, None).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            post_status(&app, uri, Some("Bearer s3cret")).await,
+            StatusCode::ACCEPTED
+        );
+        assert!(backfill.cancel().is_err());
+        assert_eq!(
+            post_status(&app, uri, Some("Bearer s3cret")).await,
+            StatusCode::BAD_REQUEST
+        );
+    }
+}
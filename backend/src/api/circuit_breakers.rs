@@ -0,0 +1,167 @@
+//! Admin endpoints for pinning RPC circuit breakers open or closed.
+//!
+//! # Endpoints
+//!
+//! | Method | Path                                               | Description                         |
+//! |--------|----------------------------------------------------|-------------------------------------|
+//! | GET    | `/admin/circuit-breakers`                          | State and override of every breaker |
+//! | POST   | `/admin/circuit-breakers/{endpoint}/force-open`    | Reject all calls until reset        |
+//! | POST   | `/admin/circuit-breakers/{endpoint}/force-close`   | Admit all calls until reset         |
+//! | POST   | `/admin/circuit-breakers/{endpoint}/reset`         | Return to automatic behaviour       |
+//!
+//! Forcing a breaker open takes down every endpoint behind it, so these are
+//! only reachable through [`require_admin`](crate::api::admin::require_admin).
+
+use axum::{extract::Path, response::Json, routing::get, routing::post, Router};
+use serde::Serialize;
+
+use crate::error::{ApiError, ApiResult};
+use crate::rpc::circuit_breaker::{
+    circuit_breaker_states, circuit_breakers, find_circuit_breaker, CircuitState, ForcedState,
+    SharedCircuitBreaker,
+};
+
+#[derive(Debug, Serialize)]
+pub struct BreakerStatus {
+    pub endpoint: String,
+    pub state: Option<CircuitState>,
+    pub forced: Option<ForcedState>,
+}
+
+fn status(breaker: &SharedCircuitBreaker) -> BreakerStatus {
+    BreakerStatus {
+        endpoint: breaker.endpoint().to_string(),
+        state: circuit_breaker_states().get(breaker.endpoint()).copied(),
+        forced: breaker.forced_state(),
+    }
+}
+
+fn lookup(endpoint: &str) -> ApiResult<SharedCircuitBreaker> {
+    find_circuit_breaker(endpoint).ok_or_else(|| {
+        ApiError::not_found(
+            "CIRCUIT_BREAKER_NOT_FOUND",
+            format!("No circuit breaker for endpoint '{endpoint}'"),
+        )
+    })
+}
+
+/// GET /admin/circuit-breakers
+async fn list_breakers() -> Json<Vec<BreakerStatus>> {
+    Json(circuit_breakers().values().map(status).collect())
+}
+
+/// POST /admin/circuit-breakers/{endpoint}/force-open
+async fn force_open(Path(endpoint): Path<String>) -> ApiResult<Json<BreakerStatus>> {
+    let breaker = lookup(&endpoint)?;
+    breaker.force_open();
+    Ok(Json(status(&breaker)))
+}
+
+/// POST /admin/circuit-breakers/{endpoint}/force-close
+async fn force_close(Path(endpoint): Path<String>) -> ApiResult<Json<BreakerStatus>> {
+    let breaker = lookup(&endpoint)?;
+    breaker.force_close();
+    Ok(Json(status(&breaker)))
+}
+
+/// POST /admin/circuit-breakers/{endpoint}/reset
+async fn force_reset(Path(endpoint): Path<String>) -> ApiResult<Json<BreakerStatus>> {
+    let breaker = lookup(&endpoint)?;
+    breaker.force_reset();
+    Ok(Json(status(&breaker)))
+}
+
+/// Build the circuit breaker control router. Mount this at `/admin`.
+pub fn routes() -> Router {
+    Router::new()
+        .route("/circuit-breakers", get(list_breakers))
+        .route("/circuit-breakers/{endpoint}/force-open", post(force_open))
+        .route(
+            "/circuit-breakers/{endpoint}/force-close",
+            post(force_close),
+        )
+        .route("/circuit-breakers/{endpoint}/reset", post(force_reset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::admin::{require_admin, AdminAuth};
+    use crate::rpc::circuit_breaker::{build_circuit_breaker, CircuitBreakerConfig};
+    use axum::body::Body;
+    use axum::http::{header::AUTHORIZATION, Method, Request, StatusCode};
+    use tower::ServiceExt;
+
+    const TOKEN: &str = "Bearer s3cret";
+
+    fn app() -> Router {
+        require_admin(routes(), AdminAuth::new(Some("s3cret")))
+    }
+
+    async fn post_with(
+        app: &Router,
+        uri: &str,
+        auth: Option<&str>,
+    ) -> (StatusCode, serde_json::Value) {
+        let mut request = Request::builder().method(Method::POST).uri(uri);
+        if let Some(auth) = auth {
+            request = request.header(AUTHORIZATION, auth);
+        }
+        let response = app
+            .clone()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    async fn post(app: &Router, uri: &str) -> (StatusCode, serde_json::Value) {
+        post_with(app, uri, Some(TOKEN)).await
+    }
+
+    #[tokio::test]
+    async fn test_force_open_and_reset_by_endpoint() {
+        let breaker =
+            build_circuit_breaker("admin_endpoint", &CircuitBreakerConfig::default(), None);
+        let app = app();
+
+        let (status, json) = post(&app, "/circuit-breakers/admin_endpoint/force-open").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["forced"], "open");
+        assert_eq!(json["state"], "open");
+        assert!(!breaker.is_call_permitted());
+
+        let (status, json) = post(&app, "/circuit-breakers/admin_endpoint/reset").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(json["forced"].is_null());
+        assert!(breaker.is_call_permitted());
+
+        let (status, _) = post(&app, "/circuit-breakers/missing/force-close").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_force_open_requires_admin_token() {
+        let breaker =
+            build_circuit_breaker("unauthenticated", &CircuitBreakerConfig::default(), None);
+        let app = app();
+
+        let (status, json) =
+            post_with(&app, "/circuit-breakers/unauthenticated/force-open", None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(json["error"]["code"], "INVALID_ADMIN_TOKEN");
+        let (status, _) = post_with(
+            &app,
+            "/circuit-breakers/unauthenticated/force-open",
+            Some("Bearer wrong"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert!(breaker.forced_state().is_none());
+        assert!(breaker.is_call_permitted());
+    }
+}
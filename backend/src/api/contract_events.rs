@@ -3,11 +3,15 @@
 //! Provides REST API endpoints for querying contract events,
 //! verification status, and on-chain audit trails.
 
-use crate::services::event_indexer::{EventIndexer, EventOrderBy, EventQuery, VerificationSummary};
+use crate::error::ApiError;
+use crate::pagination::{validate_limit, PaginatedResponse};
+use crate::services::event_indexer::{
+    EventIndexer, EventOrderBy, EventQuery, SnapshotReconciliation, VerificationSummary,
+};
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
-    response::Json,
+    response::{IntoResponse, Json, Response},
     routing::get,
     Router,
 };
@@ -41,6 +45,10 @@ pub struct EventListQuery {
     pub verification_status: Option<String>,
     /// Filter by one or more contract IDs (repeated query param, e.g. contract_ids=a&contract_ids=b)
     pub contract_ids: Option<Vec<String>>,
+    /// Wrap the page in a [`PaginatedResponse`] with a total count instead
+    /// of returning a bare array
+    #[serde(default)]
+    pub envelope: bool,
 }
 
 /// Handler for GET /api/analytics/verification-summary
@@ -88,14 +96,16 @@ pub async fn get_verification_summary(
     get,
     path = "/api/analytics/contract-events",
     params(
-        ("limit" = Option<i64>, Query, description = "Maximum number of events to return"),
+        ("limit" = Option<i64>, Query, description = "Maximum number of events to return (1-MAX_PAGE_SIZE, default 50)"),
         ("offset" = Option<i64>, Query, description = "Number of events to skip"),
         ("event_type" = Option<String>, Query, description = "Filter by event type"),
         ("verification_status" = Option<String>, Query, description = "Filter by verification status"),
-        ("contract_ids" = Option<Vec<String>>, Query, description = "Filter by one or more contract IDs (repeated query param)")
+        ("contract_ids" = Option<Vec<String>>, Query, description = "Filter by one or more contract IDs (repeated query param)"),
+        ("envelope" = Option<bool>, Query, description = "Return a paginated envelope with total count instead of a bare array")
     ),
     responses(
-        (status = 200, description = "List of contract events", body = Vec<crate::services::event_indexer::IndexedEvent>),
+        (status = 200, description = "List of contract events; a PaginatedResponse when envelope=true", body = Vec<crate::services::event_indexer::IndexedEvent>),
+        (status = 400, description = "limit exceeds MAX_PAGE_SIZE"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Contract Events"
@@ -103,19 +113,37 @@ pub async fn get_verification_summary(
 pub async fn list_contract_events(
     State(event_indexer): State<Arc<EventIndexer>>,
     Query(params): Query<EventListQuery>,
-) -> Result<Json<Vec<crate::services::event_indexer::IndexedEvent>>, (StatusCode, String)> {
+) -> Result<Response, (StatusCode, String)> {
     info!("Listing contract events with params: {:?}", params);
 
+    let limit = match validate_limit(params.limit.unwrap_or(50)) {
+        Ok(limit) => limit,
+        Err(e) => return Ok(ApiError::from(e).into_response()),
+    };
+    let offset = params.offset.unwrap_or(0);
     let query = EventQuery {
         event_type: params.event_type,
         verification_status: params.verification_status,
         contract_ids: params.contract_ids.unwrap_or_default(),
-        limit: params.limit.or(Some(50)),
-        offset: params.offset,
+        limit: Some(limit),
+        offset: Some(offset),
         order_by: Some(EventOrderBy::CreatedAtDesc),
         ..Default::default()
     };
 
+    let total = if params.envelope {
+        let total = event_indexer.count_events(&query).await.map_err(|e| {
+            error!("Failed to count events: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to count events: {e}"),
+            )
+        })?;
+        Some(total)
+    } else {
+        None
+    };
+
     let events = event_indexer.query_events(query).await.map_err(|e| {
         error!("Failed to query events: {}", e);
         (
@@ -124,7 +152,10 @@ pub async fn list_contract_events(
         )
     })?;
 
-    Ok(Json(events))
+    Ok(match total {
+        Some(total) => Json(PaginatedResponse::new(events, total, limit, offset)).into_response(),
+        None => Json(events).into_response(),
+    })
 }
 
 /// Handler for GET /api/analytics/contract-events/:id
@@ -195,6 +226,49 @@ pub async fn get_events_for_epoch(
     Ok(Json(events))
 }
 
+/// Handler for GET /api/analytics/reconciliation/:epoch
+///
+/// Recomputes the canonical hash of the backend snapshot for the epoch and
+/// compares it with the hash indexed from the on-chain submission.
+#[utoipa::path(
+    get,
+    path = "/api/analytics/reconciliation/{epoch}",
+    params(
+        ("epoch" = u64, Path, description = "Epoch number")
+    ),
+    responses(
+        (status = 200, description = "Backend and on-chain hashes for the epoch"),
+        (status = 404, description = "No on-chain snapshot indexed for the epoch"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Contract Events"
+)]
+pub async fn get_snapshot_reconciliation(
+    State(event_indexer): State<Arc<EventIndexer>>,
+    Path(epoch): Path<u64>,
+) -> Result<Json<SnapshotReconciliation>, (StatusCode, String)> {
+    info!("Reconciling snapshot for epoch: {}", epoch);
+
+    let reconciliation = event_indexer
+        .reconcile_epoch(epoch)
+        .await
+        .map_err(|e| {
+            error!("Failed to reconcile snapshot: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to reconcile snapshot: {e}"),
+            )
+        })?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                format!("No on-chain snapshot for epoch {epoch}"),
+            )
+        })?;
+
+    Ok(Json(reconciliation))
+}
+
 /// Handler for GET /api/analytics/event-stats
 #[utoipa::path(
     get,
@@ -238,5 +312,156 @@ pub fn routes(event_indexer: Arc<EventIndexer>) -> Router {
             get(get_events_for_epoch),
         )
         .route("/api/analytics/event-stats", get(get_event_stats))
+        .route(
+            "/api/analytics/reconciliation/{epoch}",
+            get(get_snapshot_reconciliation),
+        )
         .with_state(event_indexer)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use crate::db::schema::Schema;
+    use crate::services::event_indexer::IndexedEvent;
+    use crate::services::snapshot::{canonical_hash, SnapshotService};
+    use crate::snapshot::schema::AnalyticsSnapshot;
+    use axum::body::Body;
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    async fn test_pool() -> sqlx::SqlitePool {
+        let pool = sqlx::SqlitePool::connect(":memory:").await.unwrap();
+        for ddl in [
+            Schema::CREATE_CONTRACT_EVENTS,
+            Schema::CREATE_CONTRACT_EVENTS_INDEXES,
+            Schema::CREATE_SNAPSHOTS,
+        ] {
+            sqlx::query(ddl).execute(&pool).await.unwrap();
+        }
+        pool
+    }
+
+    fn snapshot_event(i: u64, hash: String) -> IndexedEvent {
+        IndexedEvent {
+            id: format!("ev-{i}"),
+            contract_id: "c1".to_string(),
+            event_type: "SNAP_SUB".to_string(),
+            epoch: Some(i),
+            hash: Some(hash),
+            timestamp: Some(i),
+            ledger: 100 + i,
+            transaction_hash: format!("tx-{i}"),
+            created_at: chrono::Utc::now(),
+            verification_status: None,
+        }
+    }
+
+    async fn indexer_with_events(count: u64) -> Arc<EventIndexer> {
+        let indexer = Arc::new(EventIndexer::new(Arc::new(Database::new(
+            test_pool().await,
+        ))));
+        for i in 0..count {
+            indexer
+                .index_event(snapshot_event(i, format!("h{i}")))
+                .await
+                .unwrap();
+        }
+        indexer
+    }
+
+    async fn get_json(app: Router, uri: &str) -> serde_json::Value {
+        let response = app
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_envelope_reports_total_at_page_boundary() {
+        let app = routes(indexer_with_events(4).await);
+
+        // Without the flag the response stays a bare array
+        let json = get_json(app.clone(), "/api/analytics/contract-events?limit=2").await;
+        assert_eq!(json.as_array().map(Vec::len), Some(2));
+
+        let json = get_json(
+            app.clone(),
+            "/api/analytics/contract-events?limit=2&offset=0&envelope=true",
+        )
+        .await;
+        assert_eq!(json["data"].as_array().map(Vec::len), Some(2));
+        assert_eq!(json["pagination"]["total"], 4);
+        assert_eq!(json["pagination"]["has_next"], true);
+
+        // The second page ends exactly at the last event
+        let json = get_json(
+            app,
+            "/api/analytics/contract-events?limit=2&offset=2&envelope=true",
+        )
+        .await;
+        assert_eq!(json["data"].as_array().map(Vec::len), Some(2));
+        assert_eq!(json["pagination"]["total"], 4);
+        assert_eq!(json["pagination"]["has_next"], false);
+        assert_eq!(json["pagination"]["next_offset"], serde_json::Value::Null);
+    }
+
+    #[tokio::test]
+    async fn test_reconciliation_compares_backend_and_onchain_hashes() {
+        let pool = test_pool().await;
+        let indexer = Arc::new(EventIndexer::new(Arc::new(Database::new(pool.clone()))));
+        for epoch in [1, 2] {
+            let snapshot = AnalyticsSnapshot::new(epoch, chrono::Utc::now());
+            let data = SnapshotService::serialize_deterministically(snapshot.clone()).unwrap();
+            let hash = canonical_hash(&snapshot);
+            sqlx::query(
+                "INSERT INTO snapshots (id, entity_id, entity_type, data, hash, epoch, timestamp)
+                 VALUES (?, 'system', 'analytics_snapshot', ?, ?, ?, ?)",
+            )
+            .bind(format!("snap-{epoch}"))
+            .bind(data)
+            .bind(&hash)
+            .bind(epoch as i64)
+            .bind(snapshot.timestamp)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+            // Epoch 2's on-chain hash deliberately disagrees with the backend
+            let onchain = if epoch == 1 { hash } else { "0".repeat(64) };
+            indexer
+                .index_event(snapshot_event(epoch, onchain))
+                .await
+                .unwrap();
+        }
+        let app = routes(indexer);
+
+        let json = get_json(app.clone(), "/api/analytics/reconciliation/1").await;
+        assert_eq!(json["epoch"], 1);
+        assert_eq!(json["match"], true);
+        assert_eq!(json["backend_hash"], json["onchain_hash"]);
+
+        let json = get_json(app.clone(), "/api/analytics/reconciliation/2").await;
+        assert_eq!(json["match"], false);
+        assert_eq!(json["onchain_hash"], "0".repeat(64));
+        assert_ne!(json["backend_hash"], json["onchain_hash"]);
+
+        // Nothing has been submitted on-chain for epoch 3 yet
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/analytics/reconciliation/3")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,319 @@
+//! Admin endpoints for per-corridor alert thresholds.
+//!
+//! # Endpoints
+//!
+//! | Method | Path                                  | Description                       |
+//! |--------|---------------------------------------|-----------------------------------|
+//! | GET    | `/admin/alerts/corridors`             | List all corridor overrides       |
+//! | GET    | `/admin/alerts/corridors/{corridor}`  | Fetch one corridor's override     |
+//! | PUT    | `/admin/alerts/corridors/{corridor}`  | Create or replace an override     |
+//! | DELETE | `/admin/alerts/corridors/{corridor}`  | Drop an override (use defaults)   |
+//!
+//! Every write is persisted to `corridor_alert_config` and applied to the
+//! running [`AlertManager`] immediately.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+use crate::alerts::{AlertManager, CorridorAlertOverride};
+use crate::error::{ApiError, ApiResult};
+
+#[derive(Clone)]
+pub struct CorridorAlertConfigState {
+    pub pool: SqlitePool,
+    pub alert_manager: Arc<AlertManager>,
+}
+
+/// Stored override for a single corridor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CorridorAlertConfig {
+    pub corridor_id: String,
+    #[serde(flatten)]
+    pub config: CorridorAlertOverride,
+    pub updated_at: String,
+}
+
+type ConfigRow = (String, Option<f64>, Option<f64>, Option<f64>, bool, String);
+
+fn from_row(
+    (
+        corridor_id,
+        success_rate_drop,
+        latency_increase_factor,
+        liquidity_decrease_factor,
+        muted,
+        updated_at,
+    ): ConfigRow,
+) -> CorridorAlertConfig {
+    CorridorAlertConfig {
+        corridor_id,
+        config: CorridorAlertOverride {
+            success_rate_drop,
+            latency_increase_factor,
+            liquidity_decrease_factor,
+            muted,
+        },
+        updated_at,
+    }
+}
+
+const SELECT_CONFIG: &str = "SELECT corridor_id, success_rate_drop, latency_increase_factor,
+            liquidity_decrease_factor, muted, updated_at
+     FROM corridor_alert_config";
+
+pub async fn list_configs(pool: &SqlitePool) -> Result<Vec<CorridorAlertConfig>, sqlx::Error> {
+    let rows: Vec<ConfigRow> = sqlx::query_as(&format!("{SELECT_CONFIG} ORDER BY corridor_id"))
+        .fetch_all(pool)
+        .await?;
+    Ok(rows.into_iter().map(from_row).collect())
+}
+
+/// Load every stored override into `alert_manager`, replacing what it held.
+pub async fn load_overrides(
+    pool: &SqlitePool,
+    alert_manager: &AlertManager,
+) -> Result<usize, sqlx::Error> {
+    let overrides: HashMap<_, _> = list_configs(pool)
+        .await?
+        .into_iter()
+        .map(|c| (c.corridor_id, c.config))
+        .collect();
+    let count = overrides.len();
+    alert_manager.replace_corridor_overrides(overrides);
+    Ok(count)
+}
+
+fn validate(config: &CorridorAlertOverride) -> ApiResult<()> {
+    let positive = |v: Option<f64>| v.is_none_or(|v| v.is_finite() && v > 0.0);
+    if !positive(config.success_rate_drop) || !positive(config.latency_increase_factor) {
+        return Err(ApiError::bad_request(
+            "INVALID_ALERT_THRESHOLD",
+            "success_rate_drop and latency_increase_factor must be positive",
+        ));
+    }
+    if config
+        .liquidity_decrease_factor
+        .is_some_and(|v| v.is_nan() || v <= 0.0 || v > 1.0)
+    {
+        return Err(ApiError::bad_request(
+            "INVALID_ALERT_THRESHOLD",
+            "liquidity_decrease_factor must be in (0, 1]",
+        ));
+    }
+    Ok(())
+}
+
+/// GET /admin/alerts/corridors
+async fn list_corridor_configs(
+    State(state): State<CorridorAlertConfigState>,
+) -> ApiResult<Json<Vec<CorridorAlertConfig>>> {
+    Ok(Json(list_configs(&state.pool).await?))
+}
+
+/// GET /admin/alerts/corridors/{corridor_id}
+async fn get_corridor_config(
+    State(state): State<CorridorAlertConfigState>,
+    Path(corridor_id): Path<String>,
+) -> ApiResult<Json<CorridorAlertConfig>> {
+    let row: Option<ConfigRow> = sqlx::query_as(&format!("{SELECT_CONFIG} WHERE corridor_id = ?"))
+        .bind(&corridor_id)
+        .fetch_optional(&state.pool)
+        .await?;
+    row.map(|r| Json(from_row(r))).ok_or_else(|| {
+        ApiError::not_found(
+            "CORRIDOR_ALERT_CONFIG_NOT_FOUND",
+            format!("No alert override for corridor {corridor_id}"),
+        )
+    })
+}
+
+/// PUT /admin/alerts/corridors/{corridor_id}
+async fn put_corridor_config(
+    State(state): State<CorridorAlertConfigState>,
+    Path(corridor_id): Path<String>,
+    Json(config): Json<CorridorAlertOverride>,
+) -> ApiResult<Json<CorridorAlertConfig>> {
+    validate(&config)?;
+
+    let row: ConfigRow = sqlx::query_as(
+        "INSERT INTO corridor_alert_config
+             (corridor_id, success_rate_drop, latency_increase_factor,
+              liquidity_decrease_factor, muted, updated_at)
+         VALUES (?, ?, ?, ?, ?, datetime('now'))
+         ON CONFLICT(corridor_id) DO UPDATE SET
+             success_rate_drop = excluded.success_rate_drop,
+             latency_increase_factor = excluded.latency_increase_factor,
+             liquidity_decrease_factor = excluded.liquidity_decrease_factor,
+             muted = excluded.muted,
+             updated_at = excluded.updated_at
+         RETURNING corridor_id, success_rate_drop, latency_increase_factor,
+                   liquidity_decrease_factor, muted, updated_at",
+    )
+    .bind(&corridor_id)
+    .bind(config.success_rate_drop)
+    .bind(config.latency_increase_factor)
+    .bind(config.liquidity_decrease_factor)
+    .bind(config.muted)
+    .fetch_one(&state.pool)
+    .await?;
+
+    state
+        .alert_manager
+        .set_corridor_override(&corridor_id, config);
+    tracing::info!(corridor_id = %corridor_id, muted = config.muted, "Corridor alert override saved");
+    Ok(Json(from_row(row)))
+}
+
+/// DELETE /admin/alerts/corridors/{corridor_id}
+async fn delete_corridor_config(
+    State(state): State<CorridorAlertConfigState>,
+    Path(corridor_id): Path<String>,
+) -> ApiResult<impl IntoResponse> {
+    let deleted = sqlx::query("DELETE FROM corridor_alert_config WHERE corridor_id = ?")
+        .bind(&corridor_id)
+        .execute(&state.pool)
+        .await?;
+    state.alert_manager.remove_corridor_override(&corridor_id);
+
+    if deleted.rows_affected() == 0 {
+        return Err(ApiError::not_found(
+            "CORRIDOR_ALERT_CONFIG_NOT_FOUND",
+            format!("No alert override for corridor {corridor_id}"),
+        ));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Build the corridor alert config router. Mount this at `/admin`.
+pub fn routes(state: CorridorAlertConfigState) -> Router {
+    Router::new()
+        .route("/alerts/corridors", get(list_corridor_configs))
+        .route(
+            "/alerts/corridors/{corridor_id}",
+            get(get_corridor_config)
+                .put(put_corridor_config)
+                .delete(delete_corridor_config),
+        )
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{Method, Request};
+    use tower::ServiceExt;
+
+    async fn setup() -> (
+        CorridorAlertConfigState,
+        tokio::sync::broadcast::Receiver<crate::alerts::Alert>,
+    ) {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::query(include_str!(
+            "../../migrations/038_create_corridor_alert_config.sql"
+        ))
+        .execute(&pool)
+        .await
+        .unwrap();
+        let (alert_manager, rx) = AlertManager::with_capacity(10);
+        (
+            CorridorAlertConfigState {
+                pool,
+                alert_manager: Arc::new(alert_manager),
+            },
+            rx,
+        )
+    }
+
+    async fn send(app: &Router, method: Method, uri: &str, body: Body) -> StatusCode {
+        app.clone()
+            .oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .header("content-type", "application/json")
+                    .body(body)
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn test_muted_corridor_via_api_suppresses_alerts() {
+        let (state, mut rx) = setup().await;
+        let app = routes(state.clone());
+
+        let status = send(
+            &app,
+            Method::PUT,
+            "/alerts/corridors/EURC-XLM",
+            Body::from(r#"{"muted": true}"#),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        state
+            .alert_manager
+            .check_and_alert("EURC-XLM", 95.0, 30.0, 100.0, 100.0, 1000.0, 1000.0);
+        assert!(rx.try_recv().is_err());
+        state
+            .alert_manager
+            .check_and_alert("USDC-XLM", 95.0, 30.0, 100.0, 100.0, 1000.0, 1000.0);
+        assert_eq!(
+            rx.try_recv().unwrap().corridor_id.as_deref(),
+            Some("USDC-XLM")
+        );
+
+        // Overrides survive a restart by reloading from the table
+        let (fresh, _rx) = AlertManager::with_capacity(1);
+        assert_eq!(load_overrides(&state.pool, &fresh).await.unwrap(), 1);
+        assert!(fresh.thresholds_for("EURC-XLM").muted);
+
+        let status = send(
+            &app,
+            Method::DELETE,
+            "/alerts/corridors/EURC-XLM",
+            Body::empty(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        assert!(!state.alert_manager.thresholds_for("EURC-XLM").muted);
+
+        let status = send(
+            &app,
+            Method::GET,
+            "/alerts/corridors/EURC-XLM",
+            Body::empty(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_threshold_is_rejected() {
+        let (state, _rx) = setup().await;
+        let app = routes(state.clone());
+
+        let status = send(
+            &app,
+            Method::PUT,
+            "/alerts/corridors/USDC-XLM",
+            Body::from(r#"{"liquidity_decrease_factor": 1.5}"#),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(list_configs(&state.pool).await.unwrap().is_empty());
+    }
+}
@@ -11,23 +11,28 @@ use tracing::{error, info, warn};
 use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
+use crate::amount::Amount;
+use crate::analytics::operations::{compute_corridor_operation_volume, CorridorOperationVolume};
 use crate::broadcast::broadcast_corridor_update;
-use crate::cache::helpers::cached_query;
+use crate::cache::helpers::{cached_query_or_stale_with_refresh, RefreshQuery};
 use crate::cache::keys;
 use crate::cache::CacheManager;
 use crate::database::Database;
+use crate::db::aggregates::{CorridorHeatmap, MAX_HEATMAP_ASSETS};
 use crate::error::{ApiError, ApiResult};
 use crate::models::corridor::Corridor;
 use crate::models::{CreateCorridorRequest, SortBy};
-use crate::pagination::PaginatedResponse;
+use crate::pagination::{validate_limit, PaginatedResponse};
 use crate::request_id::RequestId;
 use crate::rpc::{
     circuit_breaker::rpc_circuit_breaker,
     error::{with_retry, RetryConfig, RpcError},
+    retry_budget::rpc_retry_budget,
     StellarRpcClient,
 };
 use crate::services::analytics::{compute_corridor_metrics, CorridorPayment};
 use crate::services::price_feed::PriceFeedClient;
+use crate::services::trade_pricing::{Quote, USD};
 use crate::state::AppState;
 use crate::validation;
 
@@ -47,60 +52,52 @@ impl AssetPair {
 /// Extract asset pair from a payment operation
 /// Handles regular payments, `path_payment_strict_send`, and `path_payment_strict_receive`
 fn extract_asset_pair_from_payment(payment: &crate::rpc::Payment) -> Option<AssetPair> {
-    let operation_type = payment.operation_type.as_deref().unwrap_or("payment");
-
-    match operation_type {
-        "path_payment_strict_send" | "path_payment_strict_receive" => {
-            // Path payments have explicit source and destination assets
-            let source_asset = if let Some(src_type) = &payment.source_asset_type {
-                if src_type == "native" {
-                    "XLM:native".to_string()
-                } else {
-                    format!(
-                        "{}:{}",
-                        payment.source_asset_code.as_deref().unwrap_or("UNKNOWN"),
-                        payment.source_asset_issuer.as_deref().unwrap_or("unknown")
-                    )
-                }
-            } else {
-                return None;
-            };
-
-            let destination_asset = if payment.asset_type == "native" {
-                "XLM:native".to_string()
-            } else {
-                format!(
-                    "{}:{}",
-                    payment.get_asset_code().as_deref().unwrap_or("UNKNOWN"),
-                    payment.get_asset_issuer().as_deref().unwrap_or("unknown")
-                )
-            };
+    Some(AssetPair {
+        source_asset: payment.source_asset_key()?,
+        destination_asset: payment.destination_asset_key(),
+    })
+}
 
-            Some(AssetPair {
-                source_asset,
-                destination_asset,
-            })
-        }
-        _ => {
-            // Regular payments: same asset for source and destination
-            let asset = if payment.asset_type == "native" {
-                "XLM:native".to_string()
-            } else {
-                format!(
-                    "{}:{}",
-                    payment.get_asset_code().as_deref().unwrap_or("UNKNOWN"),
-                    payment.get_asset_issuer().as_deref().unwrap_or("unknown")
-                )
-            };
+/// Payment-operation counts per corridor key.
+///
+/// Counting operations rather than payment records keeps `create_account` and
+/// other non-payment records out of corridor totals.
+fn operation_volumes(payments: &[crate::rpc::Payment]) -> HashMap<String, CorridorOperationVolume> {
+    compute_corridor_operation_volume(payments, &[])
+        .into_iter()
+        .map(|volume| (volume.corridor_key.clone(), volume))
+        .collect()
+}
 
-            Some(AssetPair {
-                source_asset: asset.clone(),
-                destination_asset: asset,
-            })
-        }
+/// Success rate percentage of a corridor's operations
+fn operation_success_rate(volume: &CorridorOperationVolume) -> f64 {
+    if volume.operation_count > 0 {
+        volume.successful_operations as f64 / volume.operation_count as f64 * 100.0
+    } else {
+        0.0
     }
 }
 
+/// Volume of a corridor's payments, summed as [`Amount`] and valued at the
+/// source asset's USD `price` when one is known. Products too large for an
+/// exact [`Amount`] are approximated in `f64`.
+fn corridor_volume(payments: &[&crate::rpc::Payment], price: Option<f64>) -> f64 {
+    let total = Amount::sum_parsed(payments.iter().map(|p| p.get_amount()));
+    let Some(price) = price else {
+        return total.to_f64();
+    };
+    total.scaled(price).map_or_else(
+        || {
+            warn!(
+                "Corridor volume {} at price {} overflows, approximating",
+                total, price
+            );
+            total.to_f64() * price
+        },
+        Amount::to_f64,
+    )
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CorridorResponse {
     /// Unique identifier for the corridor
@@ -204,6 +201,10 @@ pub struct CorridorDetailResponse {
     pub liquidity_trends: Vec<LiquidityDataPoint>,
     /// Related corridors
     pub related_corridors: Option<Vec<CorridorResponse>>,
+    /// Smoothed latency from the corridor monitor, when it has a recent sample
+    #[serde(default)]
+    #[schema(example = 412.3)]
+    pub latency_ewma_ms: Option<f64>,
 }
 
 /// Query parameters for listing corridors with filtering and pagination.
@@ -211,7 +212,7 @@ pub struct CorridorDetailResponse {
 #[serde(default)]
 #[into_params(parameter_in = Query)]
 pub struct ListCorridorsQuery {
-    /// Maximum number of results to return (default: 50)
+    /// Maximum number of results to return (1-MAX_PAGE_SIZE, default: 50)
     #[serde(default = "default_limit")]
     #[param(example = 50)]
     pub limit: i64,
@@ -304,15 +305,16 @@ fn generate_corridor_list_cache_key(params: &ListCorridorsQuery) -> String {
 #[utoipa::path(
     get,
     path = "/api/corridors",
-    params(ListCorridorsQuery),
+    params(ListCorridorsQuery, RefreshQuery),
     responses(
         (status = 200, description = "List of corridors retrieved successfully", body = Vec<CorridorResponse>),
+        (status = 400, description = "Invalid filters or limit exceeds MAX_PAGE_SIZE"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Corridors"
 )]
 #[tracing::instrument(
-    skip(_db, cache, rpc_client, price_feed, params),
+    skip(_db, cache, rpc_client, price_feed, params, refresh),
     fields(request_id = %request_id.0, query = ?params)
 )]
 pub async fn list_corridors(
@@ -324,10 +326,12 @@ pub async fn list_corridors(
         Arc<PriceFeedClient>,
     )>,
     Query(params): Query<ListCorridorsQuery>,
+    Query(RefreshQuery { refresh }): Query<RefreshQuery>,
     headers: HeaderMap,
 ) -> ApiResult<Response> {
     info!("Listing corridors");
 
+    validate_limit(params.limit)?;
     validation::validate_corridor_filters(
         params.success_rate_min,
         params.success_rate_max,
@@ -337,10 +341,11 @@ pub async fn list_corridors(
 
     let cache_key = generate_corridor_list_cache_key(&params);
 
-    let corridors = cached_query(
+    let corridors = cached_query_or_stale_with_refresh(
         &cache,
         &cache_key,
         cache.config.get_ttl("corridor"),
+        refresh,
         || async {
             let circuit_breaker = rpc_circuit_breaker();
 
@@ -354,12 +359,13 @@ pub async fn list_corridors(
                 },
                 RetryConfig::default(),
                 circuit_breaker.clone(),
+                &rpc_retry_budget(),
             )
             .await
             .map_err(|e| anyhow::anyhow!("Failed to fetch payments from RPC: {e}"))?;
 
             // **RPC DATA**: Fetch recent trades with pagination for volume data
-            let _trades = with_retry(
+            let trades = with_retry(
                 || async {
                     rpc_client
                         .fetch_all_trades(Some(1000))
@@ -368,6 +374,7 @@ pub async fn list_corridors(
                 },
                 RetryConfig::default(),
                 circuit_breaker.clone(),
+                &rpc_retry_budget(),
             )
             .await
             .map_err(|e| anyhow::anyhow!("Failed to fetch trades from RPC: {e}"))?;
@@ -400,15 +407,29 @@ pub async fn list_corridors(
                 .filter_map(|k| k.split("->").next())
                 .map(String::from)
                 .collect();
-            let prices = price_feed.get_prices(&source_assets).await;
+            let mut prices = price_feed.get_prices(&source_assets).await;
+            // Source assets the price feed doesn't cover can still be valued
+            // through a recent trade against one it does
+            let traded_assets: Vec<String> = trades
+                .iter()
+                .flat_map(|t| [t.base_asset_key(), t.counter_asset_key()])
+                .filter(|asset| !prices.contains_key(asset))
+                .collect::<std::collections::HashSet<_>>()
+                .into_iter()
+                .collect();
+            prices.extend(price_feed.get_prices(&traded_assets).await);
+            let implied = Quote::new(USD, prices.clone()).implied_prices(&trades);
+            prices.extend(implied);
 
+            let op_volumes = operation_volumes(&payments);
             for (corridor_key, corridor_payments) in &corridor_map {
-                let total_attempts = corridor_payments.len() as i64;
-
-                // In Stellar, payments in the stream are successful
-                let successful_payments = total_attempts;
-                let failed_payments = 0;
-                let success_rate = if total_attempts > 0 { 100.0 } else { 0.0 };
+                let Some(op_volume) = op_volumes.get(corridor_key) else {
+                    continue;
+                };
+                let total_attempts = op_volume.operation_count;
+                let successful_payments = op_volume.successful_operations;
+                let failed_payments = op_volume.failed_operations;
+                let success_rate = operation_success_rate(op_volume);
 
                 // Parse corridor key to get assets
                 let parts: Vec<&str> = corridor_key.split("->").collect();
@@ -424,27 +445,18 @@ pub async fn list_corridors(
                 }
 
                 // Calculate volume from payment amounts and convert to USD
-                let mut volume_usd: f64 = 0.0;
                 let source_asset_key = parts[0];
 
                 // Get price for source asset from the batch fetched above
-                if let Some(&price) = prices.get(source_asset_key) {
-                    for payment in corridor_payments {
-                        if let Ok(amount) = payment.get_amount().parse::<f64>() {
-                            volume_usd += amount * price;
-                        }
-                    }
-                } else {
+                let price = prices.get(source_asset_key).copied();
+                if price.is_none() {
                     // Fallback: use raw amounts if price unavailable
                     tracing::warn!(
                         "Price unavailable for {}, using raw amounts",
                         source_asset_key
                     );
-                    volume_usd = corridor_payments
-                        .iter()
-                        .filter_map(|p| p.get_amount().parse::<f64>().ok())
-                        .sum();
                 }
+                let volume_usd = corridor_volume(corridor_payments, price);
 
                 // Calculate health score
                 let health_score = calculate_health_score(success_rate, total_attempts, volume_usd);
@@ -530,10 +542,12 @@ pub async fn list_corridors(
     )
     .await?;
 
-    crate::observability::metrics::set_corridors_tracked(corridors.pagination.total);
+    crate::observability::metrics::set_corridors_tracked(corridors.value.pagination.total);
 
     let ttl = cache.config.get_ttl("corridor");
-    let response = crate::http_cache::cached_json_response(&headers, &cache_key, &corridors, ttl)?;
+    let mut response =
+        crate::http_cache::cached_json_response(&headers, &cache_key, &corridors.value, ttl)?;
+    corridors.mark_response(&mut response);
     Ok(response)
 }
 
@@ -707,7 +721,8 @@ fn find_related_corridors(
     get,
     path = "/api/corridors/{corridor_key}",
     params(
-        ("corridor_key" = String, Path, description = "Corridor identifier (e.g., USDC:native->XLM:native)")
+        ("corridor_key" = String, Path, description = "Corridor identifier (e.g., USDC:native->XLM:native)"),
+        RefreshQuery
     ),
     responses(
         (status = 200, description = "Corridor details retrieved successfully", body = CorridorDetailResponse),
@@ -717,7 +732,7 @@ fn find_related_corridors(
     tag = "Corridors"
 )]
 #[tracing::instrument(
-    skip(_db, cache, rpc_client, price_feed, headers),
+    skip(_db, cache, rpc_client, price_feed, refresh, headers),
     fields(request_id = %request_id.0, corridor_key = %corridor_key)
 )]
 pub async fn get_corridor_detail(
@@ -729,6 +744,7 @@ pub async fn get_corridor_detail(
         Arc<PriceFeedClient>,
     )>,
     Path(corridor_key): Path<String>,
+    Query(RefreshQuery { refresh }): Query<RefreshQuery>,
     headers: HeaderMap,
 ) -> ApiResult<Response> {
     use std::collections::HashMap;
@@ -758,7 +774,7 @@ pub async fn get_corridor_detail(
     }
 
     let cache_key = keys::corridor_detail(&corridor_key);
-    let response = cached_query(&cache, &cache_key, 300, || async {
+    let fetch_detail = || async {
         // Fetch payments from RPC
         let circuit_breaker = rpc_circuit_breaker();
 
@@ -771,6 +787,7 @@ pub async fn get_corridor_detail(
             },
             RetryConfig::default(),
             circuit_breaker.clone(),
+            &rpc_retry_budget(),
         )
         .await
         .map_err(|e| {
@@ -814,11 +831,15 @@ pub async fn get_corridor_detail(
         let related_prices = price_feed.get_prices(&related_source_assets).await;
 
         // Build all corridor responses for related corridors lookup
+        let op_volumes = operation_volumes(&payments);
         for (key, corr_payments) in &corridor_map {
-            let total_attempts = corr_payments.len() as i64;
-            let successful_payments = total_attempts;
-            let failed_payments = 0;
-            let success_rate = 100.0; // All payments in Stellar stream are successful
+            let Some(op_volume) = op_volumes.get(key) else {
+                continue;
+            };
+            let total_attempts = op_volume.operation_count;
+            let successful_payments = op_volume.successful_operations;
+            let failed_payments = op_volume.failed_operations;
+            let success_rate = operation_success_rate(op_volume);
 
             let parts: Vec<&str> = key.split("->").collect();
             if parts.len() != 2 {
@@ -833,19 +854,8 @@ pub async fn get_corridor_detail(
             }
 
             // Calculate volume from the batch fetched above
-            let mut volume_usd = 0.0;
-            if let Some(&price) = related_prices.get(parts[0]) {
-                for payment in corr_payments {
-                    if let Ok(amount) = payment.get_amount().parse::<f64>() {
-                        volume_usd += amount * price;
-                    }
-                }
-            } else {
-                volume_usd = corr_payments
-                    .iter()
-                    .filter_map(|p| p.get_amount().parse::<f64>().ok())
-                    .sum();
-            }
+            let price = related_prices.get(parts[0]).copied();
+            let volume_usd = corridor_volume(corr_payments, price);
 
             let health_score = calculate_health_score(success_rate, total_attempts, volume_usd);
             let liquidity_trend = get_liquidity_trend(volume_usd);
@@ -872,24 +882,18 @@ pub async fn get_corridor_detail(
         }
 
         // Calculate volume for target corridor
-        let total_attempts = corridor_payments.len() as i64;
-        let successful_payments = total_attempts;
-        let failed_payments = 0;
-        let success_rate = 100.0;
-
-        let mut volume_usd = 0.0;
-        if let Ok(price) = price_feed.get_price(source_key).await {
-            for payment in &corridor_payments {
-                if let Ok(amount) = payment.get_amount().parse::<f64>() {
-                    volume_usd += amount * price;
-                }
-            }
-        } else {
-            volume_usd = corridor_payments
-                .iter()
-                .filter_map(|p| p.get_amount().parse::<f64>().ok())
-                .sum();
-        }
+        let Some(op_volume) = op_volumes.get(&corridor_key) else {
+            return Err(anyhow::anyhow!(
+                "No payment operations found for corridor: {corridor_key}"
+            ));
+        };
+        let total_attempts = op_volume.operation_count;
+        let successful_payments = op_volume.successful_operations;
+        let failed_payments = op_volume.failed_operations;
+        let success_rate = operation_success_rate(op_volume);
+
+        let price = price_feed.get_price(source_key).await.ok();
+        let volume_usd = corridor_volume(&corridor_payments, price);
 
         let health_score = calculate_health_score(success_rate, total_attempts, volume_usd);
         let liquidity_trend = get_liquidity_trend(volume_usd);
@@ -929,30 +933,105 @@ pub async fn get_corridor_detail(
             latency_distribution,
             liquidity_trends,
             related_corridors,
+            latency_ewma_ms: None,
         })
-    })
-    .await
-    .map_err(|error| {
-        let message = error.to_string();
-        if message.contains("No payment data found for corridor") {
-            ApiError::not_found("CORRIDOR_NOT_FOUND", "Corridor not found")
-        } else {
-            ApiError::from(error)
-        }
-    })?;
+    };
+    let mut detail =
+        cached_query_or_stale_with_refresh(&cache, &cache_key, 300, refresh, fetch_detail)
+            .await
+            .map_err(|error| {
+                let message = error.to_string();
+                if message.contains("No payment data found for corridor") {
+                    ApiError::not_found("CORRIDOR_NOT_FOUND", "Corridor not found")
+                } else {
+                    ApiError::from(error)
+                }
+            })?;
+    // The monitor refreshes this every minute, so read it outside the detail cache
+    detail.value.latency_ewma_ms = crate::monitor::cached_latency_ewma(&cache, &corridor_key).await;
 
     // Log successful corridor fetch
     info!(
-        corridor_id = %response.corridor.id,
-        success_rate = response.corridor.success_rate,
+        corridor_id = %detail.value.corridor.id,
+        success_rate = detail.value.corridor.success_rate,
+        stale = detail.stale_age_seconds.is_some(),
         "Corridor found"
     );
 
     let ttl = cache.config.get_ttl("corridor");
-    let cached = crate::http_cache::cached_json_response(&headers, &cache_key, &response, ttl)?;
+    let mut cached =
+        crate::http_cache::cached_json_response(&headers, &cache_key, &detail.value, ttl)?;
+    detail.mark_response(&mut cached);
     Ok(cached)
 }
 
+/// Assets per heatmap axis when `top` is omitted
+const DEFAULT_HEATMAP_ASSETS: usize = 10;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CorridorHeatmapQuery {
+    /// Assets on each axis (1-50, default 10)
+    pub top: Option<usize>,
+    /// First day included (default 30 days before `end_date`)
+    pub start_date: Option<chrono::NaiveDate>,
+    /// Last day included (default today)
+    pub end_date: Option<chrono::NaiveDate>,
+}
+
+/// GET /api/corridors/heatmap - Volume matrix between the busiest assets
+///
+/// Returns an N×N matrix of daily corridor volume and success rate between
+/// the `top` assets by volume, with explicit zeros for inactive pairs.
+#[utoipa::path(
+    get,
+    path = "/api/corridors/heatmap",
+    params(CorridorHeatmapQuery),
+    responses(
+        (status = 200, description = "Corridor heatmap for the date range"),
+        (status = 400, description = "top out of range or start_date after end_date"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Corridors"
+)]
+pub async fn get_corridor_heatmap(
+    State(app_state): State<AppState>,
+    Query(params): Query<CorridorHeatmapQuery>,
+) -> ApiResult<Json<CorridorHeatmap>> {
+    let top = params.top.unwrap_or(DEFAULT_HEATMAP_ASSETS);
+    if !(1..=MAX_HEATMAP_ASSETS).contains(&top) {
+        return Err(ApiError::bad_request(
+            "INVALID_TOP",
+            format!("top must be between 1 and {MAX_HEATMAP_ASSETS} (got {top})"),
+        ));
+    }
+    let end_date = params
+        .end_date
+        .unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let start_date = params
+        .start_date
+        .unwrap_or(end_date - chrono::Duration::days(30));
+    if start_date > end_date {
+        return Err(ApiError::bad_request(
+            "INVALID_RANGE",
+            "start_date must not be after end_date",
+        ));
+    }
+
+    let heatmap = app_state
+        .db
+        .corridor_aggregates()
+        .get_corridor_heatmap(start_date, end_date, top)
+        .await
+        .map_err(|e| {
+            ApiError::internal(
+                "DATABASE_ERROR",
+                format!("Failed to build corridor heatmap: {e}"),
+            )
+        })?;
+    Ok(Json(heatmap))
+}
+
 /// POST /api/corridors - Create a new corridor
 pub async fn create_corridor(
     State(app_state): State<AppState>,
@@ -1338,4 +1417,70 @@ mod tests {
         let related_corridors = related.unwrap();
         assert!(related_corridors.len() >= 2); // At least target and one related
     }
+
+    #[test]
+    fn test_operation_volumes_count_each_payment_operation() {
+        let payment = |id: &str, operation_type: &str| crate::rpc::Payment {
+            id: id.to_string(),
+            paging_token: id.to_string(),
+            transaction_hash: "hash_batch".to_string(),
+            source_account: "GTEST".to_string(),
+            destination: "GDEST".to_string(),
+            asset_type: "native".to_string(),
+            asset_code: None,
+            asset_issuer: None,
+            amount: "10.0".to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            operation_type: Some(operation_type.to_string()),
+            source_asset_type: None,
+            source_asset_code: None,
+            source_asset_issuer: None,
+            source_amount: None,
+            from: Some("GTEST".to_string()),
+            to: Some("GDEST".to_string()),
+            asset_balance_changes: None,
+        };
+        let payments = vec![
+            payment("op_1", "payment"),
+            payment("op_2", "payment"),
+            payment("op_3", "create_account"),
+        ];
+
+        let volumes = operation_volumes(&payments);
+        let volume = &volumes["XLM:native->XLM:native"];
+        assert_eq!(volume.operation_count, 2);
+        assert_eq!(volume.transaction_count, 1);
+        assert!((operation_success_rate(volume) - 100.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_corridor_volume_sums_exactly_before_pricing() {
+        let payment = |amount: &str| crate::rpc::Payment {
+            id: amount.to_string(),
+            paging_token: amount.to_string(),
+            transaction_hash: "hash_volume".to_string(),
+            source_account: "GTEST".to_string(),
+            destination: "GDEST".to_string(),
+            asset_type: "native".to_string(),
+            asset_code: None,
+            asset_issuer: None,
+            amount: amount.to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            operation_type: Some("payment".to_string()),
+            source_asset_type: None,
+            source_asset_code: None,
+            source_asset_issuer: None,
+            source_amount: None,
+            from: Some("GTEST".to_string()),
+            to: Some("GDEST".to_string()),
+            asset_balance_changes: None,
+        };
+        let payments = [payment("0.1000000"), payment("0.2000000")];
+        let refs: Vec<&crate::rpc::Payment> = payments.iter().collect();
+
+        assert_eq!(corridor_volume(&refs, None), 0.3);
+        assert_eq!(corridor_volume(&refs, Some(2.0)), 0.6);
+        // Too large for an exact Amount, so approximated instead of zeroed
+        assert_eq!(corridor_volume(&refs, Some(1e30)), 0.3 * 1e30);
+    }
 }
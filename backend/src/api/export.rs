@@ -12,10 +12,12 @@ use axum::{
 use chrono::{DateTime, Duration, Utc};
 use csv::Writer;
 use rust_xlsxwriter::{Color, Format, Workbook};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
 
+use crate::db::aggregates::AggregatedCorridorMetrics;
 use crate::error::{ApiError, ApiResult};
-use crate::models::PaymentRow;
+use crate::models::{Anchor, PaymentRow};
 use crate::state::AppState;
 
 /// Prefix any cell that begins with a formula-trigger character so spreadsheet
@@ -28,18 +30,426 @@ fn sanitize_csv_field(value: String) -> String {
     }
 }
 
+/// Serialize rows as JSON Lines: one compact JSON object per line, so
+/// downstream consumers can parse each record independently.
+fn to_ndjson<T: Serialize>(rows: &[T]) -> serde_json::Result<Vec<u8>> {
+    let mut data = Vec::new();
+    for row in rows {
+        serde_json::to_writer(&mut data, row)?;
+        data.push(b'\n');
+    }
+    Ok(data)
+}
+
+/// Decimal places for USD amounts when `EXPORT_USD_DECIMALS` is unset
+const DEFAULT_USD_DECIMALS: usize = 2;
+
+/// Decimal places USD amounts are rounded to in every export format, from
+/// `EXPORT_USD_DECIMALS` (0-8). Storage keeps full precision.
+fn usd_decimals() -> usize {
+    static DECIMALS: OnceLock<usize> = OnceLock::new();
+    *DECIMALS.get_or_init(|| {
+        std::env::var("EXPORT_USD_DECIMALS")
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+            .filter(|d| *d <= 8)
+            .unwrap_or(DEFAULT_USD_DECIMALS)
+    })
+}
+
+fn round_usd(value: f64) -> f64 {
+    let factor = 10_f64.powi(usd_decimals() as i32);
+    (value * factor).round() / factor
+}
+
+fn invalid_format(format: &str) -> ApiError {
+    ApiError::bad_request(
+        "INVALID_FORMAT",
+        format!("Format {format} is not supported (expected csv, json, ndjson or excel)"),
+    )
+}
+
+fn export_error(e: impl std::fmt::Display) -> ApiError {
+    ApiError::internal("EXPORT_ERROR", e.to_string())
+}
+
+/// A single exported value. Keeping the type lets CSV apply its text
+/// formatting while Excel still receives real numbers.
+pub(crate) enum Cell {
+    Text(String),
+    /// Written to CSV with two decimals
+    Decimal(f64),
+    /// USD amount, rounded to [`usd_decimals`] in CSV, JSON and Excel alike
+    Usd(f64),
+    Integer(i64),
+    Number(f64),
+    /// Written as `true`/`false` to CSV and `Yes`/`No` to Excel
+    Flag(bool),
+}
+
+impl Cell {
+    fn to_csv(&self) -> String {
+        match self {
+            Self::Text(v) => sanitize_csv_field(v.clone()),
+            Self::Decimal(v) => format!("{v:.2}"),
+            Self::Usd(v) => format!("{:.*}", usd_decimals(), round_usd(*v)),
+            Self::Integer(v) => v.to_string(),
+            Self::Number(v) => v.to_string(),
+            Self::Flag(v) => v.to_string(),
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            Self::Text(v) => serde_json::Value::from(v.as_str()),
+            Self::Decimal(v) | Self::Number(v) => serde_json::Value::from(*v),
+            Self::Usd(v) => serde_json::Value::from(round_usd(*v)),
+            Self::Integer(v) => serde_json::Value::from(*v),
+            Self::Flag(v) => serde_json::Value::from(*v),
+        }
+    }
+}
+
+/// An exportable column: the name accepted by `?columns=`, the header shown
+/// in CSV/Excel, and how to read the value from a row.
+pub(crate) struct Column<T> {
+    pub(crate) name: &'static str,
+    pub(crate) header: &'static str,
+    pub(crate) value: fn(&T) -> Cell,
+}
+
+const CORRIDOR_COLUMNS: &[Column<AggregatedCorridorMetrics>] = &[
+    Column {
+        name: "corridor_key",
+        header: "Corridor ID",
+        value: |m| Cell::Text(m.corridor_key.clone()),
+    },
+    Column {
+        name: "source_asset_code",
+        header: "Source Asset",
+        value: |m| Cell::Text(m.source_asset_code.clone()),
+    },
+    Column {
+        name: "source_asset_issuer",
+        header: "Source Issuer",
+        value: |m| Cell::Text(m.source_asset_issuer.clone()),
+    },
+    Column {
+        name: "destination_asset_code",
+        header: "Destination Asset",
+        value: |m| Cell::Text(m.destination_asset_code.clone()),
+    },
+    Column {
+        name: "destination_asset_issuer",
+        header: "Destination Issuer",
+        value: |m| Cell::Text(m.destination_asset_issuer.clone()),
+    },
+    Column {
+        name: "avg_success_rate",
+        header: "Success Rate (%)",
+        value: |m| Cell::Decimal(m.avg_success_rate),
+    },
+    Column {
+        name: "total_transactions",
+        header: "Total Transactions",
+        value: |m| Cell::Integer(m.total_transactions),
+    },
+    Column {
+        name: "successful_transactions",
+        header: "Successful Transactions",
+        value: |m| Cell::Integer(m.successful_transactions),
+    },
+    Column {
+        name: "failed_transactions",
+        header: "Failed Transactions",
+        value: |m| Cell::Integer(m.failed_transactions),
+    },
+    Column {
+        name: "total_volume_usd",
+        header: "Volume (USD)",
+        value: |m| Cell::Usd(m.total_volume_usd),
+    },
+    Column {
+        name: "latest_date",
+        header: "Latest Date",
+        value: |m| Cell::Text(m.latest_date.to_string()),
+    },
+];
+
+const ANCHOR_COLUMNS: &[Column<Anchor>] = &[
+    Column {
+        name: "id",
+        header: "Anchor ID",
+        value: |a| Cell::Text(a.id.clone()),
+    },
+    Column {
+        name: "name",
+        header: "Name",
+        value: |a| Cell::Text(a.name.clone()),
+    },
+    Column {
+        name: "stellar_account",
+        header: "Stellar Account",
+        value: |a| Cell::Text(a.stellar_account.clone()),
+    },
+    Column {
+        name: "home_domain",
+        header: "Home Domain",
+        value: |a| Cell::Text(a.home_domain.clone().unwrap_or_default()),
+    },
+    Column {
+        name: "reliability_score",
+        header: "Reliability Score (%)",
+        value: |a| Cell::Decimal(a.reliability_score),
+    },
+    Column {
+        name: "total_transactions",
+        header: "Total Transactions",
+        value: |a| Cell::Integer(a.total_transactions),
+    },
+    Column {
+        name: "successful_transactions",
+        header: "Successful Transactions",
+        value: |a| Cell::Integer(a.successful_transactions),
+    },
+    Column {
+        name: "failed_transactions",
+        header: "Failed Transactions",
+        value: |a| Cell::Integer(a.failed_transactions),
+    },
+    Column {
+        name: "total_volume_usd",
+        header: "Volume (USD)",
+        value: |a| Cell::Usd(a.total_volume_usd),
+    },
+    Column {
+        name: "status",
+        header: "Status",
+        value: |a| Cell::Text(a.status.clone()),
+    },
+    Column {
+        name: "updated_at",
+        header: "Last Updated",
+        value: |a| Cell::Text(a.updated_at.to_rfc3339()),
+    },
+];
+
+const PAYMENT_COLUMNS: &[Column<PaymentRow>] = &[
+    Column {
+        name: "transaction_hash",
+        header: "Transaction Hash",
+        value: |p| Cell::Text(p.transaction_hash.clone()),
+    },
+    Column {
+        name: "source_account",
+        header: "Source Account",
+        value: |p| Cell::Text(p.source_account.clone()),
+    },
+    Column {
+        name: "destination_account",
+        header: "Destination Account",
+        value: |p| Cell::Text(p.destination_account.clone()),
+    },
+    Column {
+        name: "source_asset",
+        header: "Source Asset",
+        value: |p| Cell::Text(format!("{}:{}", p.source_asset_code, p.source_asset_issuer)),
+    },
+    Column {
+        name: "destination_asset",
+        header: "Destination Asset",
+        value: |p| {
+            Cell::Text(format!(
+                "{}:{}",
+                p.destination_asset_code, p.destination_asset_issuer
+            ))
+        },
+    },
+    Column {
+        name: "amount",
+        header: "Amount",
+        value: |p| Cell::Number(p.amount),
+    },
+    Column {
+        name: "successful",
+        header: "Successful",
+        value: |p| Cell::Flag(p.successful),
+    },
+    Column {
+        name: "created_at",
+        header: "Timestamp",
+        value: |p| Cell::Text(p.created_at.to_rfc3339()),
+    },
+];
+
+/// Resolve the `columns` query parameter against the available columns.
+///
+/// `None` (or an empty list) selects every column in its default order. The
+/// returned flag is `true` when the caller asked for an explicit subset.
+pub(crate) fn select_columns<'a, T>(
+    available: &'a [Column<T>],
+    requested: Option<&str>,
+) -> ApiResult<(Vec<&'a Column<T>>, bool)> {
+    let names: Vec<&str> = requested
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+        .collect();
+
+    if names.is_empty() {
+        return Ok((available.iter().collect(), false));
+    }
+
+    let columns = names
+        .into_iter()
+        .map(|name| {
+            available.iter().find(|c| c.name == name).ok_or_else(|| {
+                let known: Vec<&str> = available.iter().map(|c| c.name).collect();
+                ApiError::bad_request(
+                    "INVALID_COLUMN",
+                    format!(
+                        "Unknown column {name} (expected one of: {})",
+                        known.join(", ")
+                    ),
+                )
+            })
+        })
+        .collect::<ApiResult<Vec<_>>>()?;
+    Ok((columns, true))
+}
+
+pub(crate) fn write_csv<T>(rows: &[T], columns: &[&Column<T>]) -> ApiResult<Vec<u8>> {
+    let mut wtr = Writer::from_writer(vec![]);
+    wtr.write_record(columns.iter().map(|c| c.header))
+        .map_err(export_error)?;
+
+    for row in rows {
+        wtr.write_record(columns.iter().map(|c| (c.value)(row).to_csv()))
+            .map_err(export_error)?;
+    }
+
+    wtr.into_inner().map_err(export_error)
+}
+
+fn write_excel<T>(rows: &[T], columns: &[&Column<T>]) -> ApiResult<Vec<u8>> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    let header_format = Format::new()
+        .set_bold()
+        .set_background_color(Color::RGB(0x00D9_EAD3));
+    let usd_format = match usd_decimals() {
+        0 => Format::new().set_num_format("#,##0"),
+        decimals => Format::new().set_num_format(format!("#,##0.{}", "0".repeat(decimals))),
+    };
+
+    for (i, column) in columns.iter().enumerate() {
+        worksheet
+            .write_with_format(0, i as u16, column.header, &header_format)
+            .map_err(export_error)?;
+    }
+
+    for (row_idx, row) in rows.iter().enumerate() {
+        let row_idx = (row_idx + 1) as u32;
+        for (col_idx, column) in columns.iter().enumerate() {
+            let col_idx = col_idx as u16;
+            match (column.value)(row) {
+                Cell::Text(v) => worksheet.write(row_idx, col_idx, v),
+                Cell::Decimal(v) | Cell::Number(v) => worksheet.write(row_idx, col_idx, v),
+                Cell::Usd(v) => {
+                    worksheet.write_with_format(row_idx, col_idx, round_usd(v), &usd_format)
+                }
+                Cell::Integer(v) => worksheet.write(row_idx, col_idx, v as f64),
+                Cell::Flag(v) => worksheet.write(row_idx, col_idx, if v { "Yes" } else { "No" }),
+            }
+            .map_err(export_error)?;
+        }
+    }
+
+    workbook.save_to_buffer().map_err(export_error)
+}
+
+/// Build one JSON object per row containing only the selected columns.
+fn project_json<T>(
+    rows: &[T],
+    columns: &[&Column<T>],
+) -> Vec<serde_json::Map<String, serde_json::Value>> {
+    rows.iter()
+        .map(|row| {
+            columns
+                .iter()
+                .map(|c| (c.name.to_string(), (c.value)(row).to_json()))
+                .collect()
+        })
+        .collect()
+}
+
+/// Serialize full records, with USD fields rounded as in the projected output.
+fn full_json<T: Serialize>(
+    rows: &[T],
+    columns: &[&Column<T>],
+) -> serde_json::Result<Vec<serde_json::Value>> {
+    rows.iter()
+        .map(|row| {
+            let mut value = serde_json::to_value(row)?;
+            if let Some(record) = value.as_object_mut() {
+                for column in columns {
+                    let cell = (column.value)(row);
+                    if let (Cell::Usd(_), Some(field)) = (&cell, record.get_mut(column.name)) {
+                        *field = cell.to_json();
+                    }
+                }
+            }
+            Ok(value)
+        })
+        .collect()
+}
+
+/// Serialize rows as a JSON array, projected to the selected columns when the
+/// caller asked for a subset and as the full records otherwise.
+pub(crate) fn write_json<T: Serialize>(
+    rows: &[T],
+    columns: &[&Column<T>],
+    projected: bool,
+) -> ApiResult<Vec<u8>> {
+    if projected {
+        serde_json::to_vec(&project_json(rows, columns)).map_err(export_error)
+    } else {
+        let rows = full_json(rows, columns).map_err(export_error)?;
+        serde_json::to_vec(&rows).map_err(export_error)
+    }
+}
+
+fn write_ndjson<T: Serialize>(
+    rows: &[T],
+    columns: &[&Column<T>],
+    projected: bool,
+) -> ApiResult<Vec<u8>> {
+    if projected {
+        to_ndjson(&project_json(rows, columns)).map_err(export_error)
+    } else {
+        let rows = full_json(rows, columns).map_err(export_error)?;
+        to_ndjson(&rows).map_err(export_error)
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ExportQuery {
-    pub format: String, // "csv", "json", "excel"
+    pub format: String, // "csv", "json", "ndjson", "excel"
     pub start_date: Option<DateTime<Utc>>,
     pub end_date: Option<DateTime<Utc>>,
     pub corridor_id: Option<String>,
+    /// Comma-separated column names to include, e.g. `corridor_key,total_volume_usd`
+    pub columns: Option<String>,
 }
 
 pub async fn export_corridors(
     State(app_state): State<AppState>,
     Query(params): Query<ExportQuery>,
 ) -> ApiResult<impl IntoResponse> {
+    let (columns, projected) = select_columns(CORRIDOR_COLUMNS, params.columns.as_deref())?;
+
     let today = Utc::now().date_naive();
     let start_date = params
         .start_date
@@ -64,42 +474,7 @@ pub async fn export_corridors(
 
     match params.format.to_lowercase().as_str() {
         "csv" => {
-            let mut wtr = Writer::from_writer(vec![]);
-            wtr.write_record([
-                "Corridor ID",
-                "Source Asset",
-                "Source Issuer",
-                "Destination Asset",
-                "Destination Issuer",
-                "Success Rate (%)",
-                "Total Transactions",
-                "Successful Transactions",
-                "Failed Transactions",
-                "Volume (USD)",
-                "Latest Date",
-            ])
-            .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-
-            for m in corridors {
-                wtr.write_record(&[
-                    sanitize_csv_field(m.corridor_key),
-                    sanitize_csv_field(m.source_asset_code),
-                    sanitize_csv_field(m.source_asset_issuer),
-                    sanitize_csv_field(m.destination_asset_code),
-                    sanitize_csv_field(m.destination_asset_issuer),
-                    format!("{:.2}", m.avg_success_rate),
-                    m.total_transactions.to_string(),
-                    m.successful_transactions.to_string(),
-                    m.failed_transactions.to_string(),
-                    format!("{:.2}", m.total_volume_usd),
-                    m.latest_date.to_string(),
-                ])
-                .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-            }
-
-            let data = wtr
-                .into_inner()
-                .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
+            let data = write_csv(&corridors, &columns)?;
 
             let mut headers = HeaderMap::new();
             headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/csv"));
@@ -121,78 +496,25 @@ pub async fn export_corridors(
                 HeaderValue::from_static("attachment; filename=\"corridors_export.json\""),
             );
 
-            let data = serde_json::to_vec(&corridors)
-                .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
+            let data = write_json(&corridors, &columns, projected)?;
             Ok((headers, data))
         }
-        "excel" | "xlsx" => {
-            let mut workbook = Workbook::new();
-            let worksheet = workbook.add_worksheet();
-
-            let header_format = Format::new()
-                .set_bold()
-                .set_background_color(Color::RGB(0x00D9_EAD3));
-
-            let headers = [
-                "Corridor ID",
-                "Source Asset",
-                "Source Issuer",
-                "Destination Asset",
-                "Destination Issuer",
-                "Success Rate (%)",
-                "Total Transactions",
-                "Successful Transactions",
-                "Failed Transactions",
-                "Volume (USD)",
-                "Latest Date",
-            ];
-
-            for (i, h) in headers.iter().enumerate() {
-                worksheet
-                    .write_with_format(0, i as u16, *h, &header_format)
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-            }
-
-            for (row, m) in corridors.iter().enumerate() {
-                let row = (row + 1) as u32;
-                worksheet
-                    .write(row, 0, &m.corridor_key)
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(row, 1, &m.source_asset_code)
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(row, 2, &m.source_asset_issuer)
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(row, 3, &m.destination_asset_code)
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(row, 4, &m.destination_asset_issuer)
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(row, 5, m.avg_success_rate)
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(row, 6, m.total_transactions as f64)
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(row, 7, m.successful_transactions as f64)
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(row, 8, m.failed_transactions as f64)
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(row, 9, m.total_volume_usd)
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(row, 10, m.latest_date.to_string())
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-            }
+        "ndjson" | "jsonl" => {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("application/x-ndjson"),
+            );
+            headers.insert(
+                header::CONTENT_DISPOSITION,
+                HeaderValue::from_static("attachment; filename=\"corridors_export.ndjson\""),
+            );
 
-            let data = workbook
-                .save_to_buffer()
-                .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
+            let data = write_ndjson(&corridors, &columns, projected)?;
+            Ok((headers, data))
+        }
+        "excel" | "xlsx" => {
+            let data = write_excel(&corridors, &columns)?;
 
             let mut headers = HeaderMap::new();
             headers.insert(
@@ -208,10 +530,7 @@ pub async fn export_corridors(
 
             Ok((headers, data))
         }
-        _ => Err(ApiError::bad_request(
-            "INVALID_FORMAT",
-            format!("Format {} is not supported", params.format),
-        )),
+        _ => Err(invalid_format(&params.format)),
     }
 }
 
@@ -219,6 +538,8 @@ pub async fn export_anchors(
     State(app_state): State<AppState>,
     Query(params): Query<ExportQuery>,
 ) -> ApiResult<impl IntoResponse> {
+    let (columns, projected) = select_columns(ANCHOR_COLUMNS, params.columns.as_deref())?;
+
     let anchors = app_state.db.list_anchors(1000, 0).await.map_err(|e| {
         ApiError::internal(
             "DATABASE_ERROR",
@@ -228,42 +549,7 @@ pub async fn export_anchors(
 
     match params.format.to_lowercase().as_str() {
         "csv" => {
-            let mut wtr = Writer::from_writer(vec![]);
-            wtr.write_record([
-                "Anchor ID",
-                "Name",
-                "Stellar Account",
-                "Home Domain",
-                "Reliability Score (%)",
-                "Total Transactions",
-                "Successful Transactions",
-                "Failed Transactions",
-                "Volume (USD)",
-                "Status",
-                "Last Updated",
-            ])
-            .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-
-            for a in anchors {
-                wtr.write_record(&[
-                    sanitize_csv_field(a.id),
-                    sanitize_csv_field(a.name),
-                    sanitize_csv_field(a.stellar_account),
-                    sanitize_csv_field(a.home_domain.unwrap_or_default()),
-                    format!("{:.2}", a.reliability_score),
-                    a.total_transactions.to_string(),
-                    a.successful_transactions.to_string(),
-                    a.failed_transactions.to_string(),
-                    format!("{:.2}", a.total_volume_usd),
-                    sanitize_csv_field(a.status),
-                    a.updated_at.to_rfc3339(),
-                ])
-                .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-            }
-
-            let data = wtr
-                .into_inner()
-                .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
+            let data = write_csv(&anchors, &columns)?;
 
             let mut headers = HeaderMap::new();
             headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/csv"));
@@ -285,78 +571,25 @@ pub async fn export_anchors(
                 HeaderValue::from_static("attachment; filename=\"anchors_export.json\""),
             );
 
-            let data = serde_json::to_vec(&anchors)
-                .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
+            let data = write_json(&anchors, &columns, projected)?;
             Ok((headers, data))
         }
-        "excel" | "xlsx" => {
-            let mut workbook = Workbook::new();
-            let worksheet = workbook.add_worksheet();
-
-            let header_format = Format::new()
-                .set_bold()
-                .set_background_color(Color::RGB(0x00D9_EAD3));
-
-            let headers = [
-                "Anchor ID",
-                "Name",
-                "Stellar Account",
-                "Home Domain",
-                "Reliability Score (%)",
-                "Total Transactions",
-                "Successful Transactions",
-                "Failed Transactions",
-                "Volume (USD)",
-                "Status",
-                "Last Updated",
-            ];
-
-            for (i, h) in headers.iter().enumerate() {
-                worksheet
-                    .write_with_format(0, i as u16, *h, &header_format)
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-            }
-
-            for (row, a) in anchors.iter().enumerate() {
-                let row = (row + 1) as u32;
-                worksheet
-                    .write(row, 0, &a.id)
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(row, 1, &a.name)
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(row, 2, &a.stellar_account)
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(row, 3, a.home_domain.as_deref().unwrap_or(""))
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(row, 4, a.reliability_score)
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(row, 5, a.total_transactions as f64)
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(row, 6, a.successful_transactions as f64)
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(row, 7, a.failed_transactions as f64)
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(row, 8, a.total_volume_usd)
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(row, 9, &a.status)
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(row, 10, a.updated_at.to_rfc3339())
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-            }
+        "ndjson" | "jsonl" => {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("application/x-ndjson"),
+            );
+            headers.insert(
+                header::CONTENT_DISPOSITION,
+                HeaderValue::from_static("attachment; filename=\"anchors_export.ndjson\""),
+            );
 
-            let data = workbook
-                .save_to_buffer()
-                .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
+            let data = write_ndjson(&anchors, &columns, projected)?;
+            Ok((headers, data))
+        }
+        "excel" | "xlsx" => {
+            let data = write_excel(&anchors, &columns)?;
 
             let mut headers = HeaderMap::new();
             headers.insert(
@@ -372,10 +605,7 @@ pub async fn export_anchors(
 
             Ok((headers, data))
         }
-        _ => Err(ApiError::bad_request(
-            "INVALID_FORMAT",
-            format!("Format {} is not supported", params.format),
-        )),
+        _ => Err(invalid_format(&params.format)),
     }
 }
 
@@ -383,6 +613,8 @@ pub async fn export_payments(
     State(app_state): State<AppState>,
     Query(params): Query<ExportQuery>,
 ) -> ApiResult<impl IntoResponse> {
+    let (columns, projected) = select_columns(PAYMENT_COLUMNS, params.columns.as_deref())?;
+
     let start_date = params
         .start_date
         .unwrap_or_else(|| Utc::now() - Duration::days(30));
@@ -409,42 +641,7 @@ pub async fn export_payments(
 
     match params.format.to_lowercase().as_str() {
         "csv" => {
-            let mut wtr = Writer::from_writer(vec![]);
-            wtr.write_record([
-                "Transaction Hash",
-                "Source Account",
-                "Destination Account",
-                "Source Asset",
-                "Destination Asset",
-                "Amount",
-                "Successful",
-                "Timestamp",
-            ])
-            .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-
-            for p in payments {
-                wtr.write_record(&[
-                    sanitize_csv_field(p.transaction_hash),
-                    sanitize_csv_field(p.source_account),
-                    sanitize_csv_field(p.destination_account),
-                    sanitize_csv_field(format!(
-                        "{}:{}",
-                        p.source_asset_code, p.source_asset_issuer
-                    )),
-                    sanitize_csv_field(format!(
-                        "{}:{}",
-                        p.destination_asset_code, p.destination_asset_issuer
-                    )),
-                    p.amount.to_string(),
-                    p.successful.to_string(),
-                    p.created_at.to_rfc3339(),
-                ])
-                .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-            }
-
-            let data = wtr
-                .into_inner()
-                .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
+            let data = write_csv(&payments, &columns)?;
 
             let mut headers = HeaderMap::new();
             headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/csv"));
@@ -466,77 +663,25 @@ pub async fn export_payments(
                 HeaderValue::from_static("attachment; filename=\"payments_export.json\""),
             );
 
-            let data = serde_json::to_vec(&payments)
-                .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
+            let data = write_json(&payments, &columns, projected)?;
             Ok((headers, data))
         }
-        "excel" | "xlsx" => {
-            let mut workbook = Workbook::new();
-            let worksheet = workbook.add_worksheet();
-
-            let header_format = Format::new()
-                .set_bold()
-                .set_background_color(Color::RGB(0x00D9_EAD3));
-
-            let headers = [
-                "Transaction Hash",
-                "Source Account",
-                "Destination Account",
-                "Source Asset",
-                "Destination Asset",
-                "Amount",
-                "Successful",
-                "Timestamp",
-            ];
-
-            for (i, h) in headers.iter().enumerate() {
-                worksheet
-                    .write_with_format(0, i as u16, *h, &header_format)
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-            }
-
-            for (row, p) in payments.iter().enumerate() {
-                let row = (row + 1) as u32;
-                worksheet
-                    .write(row, 0, &p.transaction_hash)
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(row, 1, &p.source_account)
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(row, 2, &p.destination_account)
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(
-                        row,
-                        3,
-                        format!("{}:{}", p.source_asset_code, p.source_asset_issuer),
-                    )
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(
-                        row,
-                        4,
-                        format!(
-                            "{}:{}",
-                            p.destination_asset_code, p.destination_asset_issuer
-                        ),
-                    )
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(row, 5, p.amount)
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(row, 6, if p.successful { "Yes" } else { "No" })
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-                worksheet
-                    .write(row, 7, p.created_at.to_rfc3339())
-                    .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
-            }
+        "ndjson" | "jsonl" => {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("application/x-ndjson"),
+            );
+            headers.insert(
+                header::CONTENT_DISPOSITION,
+                HeaderValue::from_static("attachment; filename=\"payments_export.ndjson\""),
+            );
 
-            let data = workbook
-                .save_to_buffer()
-                .map_err(|e| ApiError::internal("EXPORT_ERROR", e.to_string()))?;
+            let data = write_ndjson(&payments, &columns, projected)?;
+            Ok((headers, data))
+        }
+        "excel" | "xlsx" => {
+            let data = write_excel(&payments, &columns)?;
 
             let mut headers = HeaderMap::new();
             headers.insert(
@@ -552,9 +697,158 @@ pub async fn export_payments(
 
             Ok((headers, data))
         }
-        _ => Err(ApiError::bad_request(
-            "INVALID_FORMAT",
-            format!("Format {} is not supported", params.format),
-        )),
+        _ => Err(invalid_format(&params.format)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn corridor() -> AggregatedCorridorMetrics {
+        AggregatedCorridorMetrics {
+            corridor_key: "USDC:GA->XLM:native".to_string(),
+            source_asset_code: "USDC".to_string(),
+            source_asset_issuer: "GA".to_string(),
+            destination_asset_code: "XLM".to_string(),
+            destination_asset_issuer: "native".to_string(),
+            total_transactions: 10,
+            successful_transactions: 9,
+            failed_transactions: 1,
+            avg_success_rate: 90.0,
+            total_volume_usd: 1250.5,
+            latest_date: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_select_columns_subset_projects_csv_and_json() {
+        let (columns, projected) =
+            select_columns(CORRIDOR_COLUMNS, Some("corridor_key, total_volume_usd")).unwrap();
+        assert!(projected);
+
+        let rows = vec![corridor()];
+        let csv = String::from_utf8(write_csv(&rows, &columns).unwrap()).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines,
+            vec!["Corridor ID,Volume (USD)", "USDC:GA->XLM:native,1250.50"]
+        );
+
+        let json: Value =
+            serde_json::from_slice(&write_json(&rows, &columns, projected).unwrap()).unwrap();
+        assert_eq!(
+            json,
+            json!([{"corridor_key": "USDC:GA->XLM:native", "total_volume_usd": 1250.5}])
+        );
+
+        assert!(!write_excel(&rows, &columns).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_select_columns_rejects_unknown_column() {
+        let err = select_columns(CORRIDOR_COLUMNS, Some("corridor_key,bogus"))
+            .map(|_| ())
+            .unwrap_err();
+        match err {
+            ApiError::BadRequest { code, message, .. } => {
+                assert_eq!(code, "INVALID_COLUMN");
+                assert!(message.contains("bogus"));
+            }
+            other => panic!("expected bad request, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_select_columns_default_keeps_all_columns() {
+        let (columns, projected) = select_columns(CORRIDOR_COLUMNS, None).unwrap();
+        assert!(!projected);
+        assert_eq!(columns.len(), CORRIDOR_COLUMNS.len());
+
+        let rows = vec![corridor()];
+        let csv = String::from_utf8(write_csv(&rows, &columns).unwrap()).unwrap();
+        assert!(csv.starts_with("Corridor ID,Source Asset,Source Issuer,"));
+
+        let json: Value =
+            serde_json::from_slice(&write_json(&rows, &columns, projected).unwrap()).unwrap();
+        assert_eq!(json[0]["successful_transactions"], 9);
+        assert_eq!(json[0]["source_asset_code"], "USDC");
+    }
+
+    /// Inflate `xl/worksheets/sheet1.xml` out of an xlsx archive
+    fn sheet_xml(xlsx: &[u8]) -> String {
+        use std::io::Read;
+
+        let name = b"xl/worksheets/sheet1.xml";
+        // Local file header: signature, then the name at offset 30
+        let header = (0..xlsx.len())
+            .find(|&i| {
+                xlsx[i..].starts_with(b"PK\x03\x04")
+                    && xlsx
+                        .get(i + 30..)
+                        .is_some_and(|rest| rest.starts_with(name))
+            })
+            .unwrap();
+        let field = |at: usize| usize::from(u16::from_le_bytes([xlsx[at], xlsx[at + 1]]));
+        let data = header + 30 + field(header + 26) + field(header + 28);
+
+        let mut xml = String::new();
+        flate2::read::DeflateDecoder::new(&xlsx[data..])
+            .read_to_string(&mut xml)
+            .unwrap();
+        xml
+    }
+
+    #[test]
+    fn test_usd_values_are_rounded_in_every_format() {
+        let rows = vec![AggregatedCorridorMetrics {
+            total_volume_usd: 12_345.678_900_000_001,
+            ..corridor()
+        }];
+        let (all, _) = select_columns(CORRIDOR_COLUMNS, None).unwrap();
+        let (subset, _) = select_columns(CORRIDOR_COLUMNS, Some("total_volume_usd")).unwrap();
+
+        let csv = String::from_utf8(write_csv(&rows, &subset).unwrap()).unwrap();
+        assert_eq!(csv.lines().nth(1), Some("12345.68"));
+
+        for (columns, projected) in [(&subset, true), (&all, false)] {
+            let json: Value =
+                serde_json::from_slice(&write_json(&rows, columns, projected).unwrap()).unwrap();
+            assert_eq!(json[0]["total_volume_usd"], json!(12_345.68));
+
+            let ndjson = write_ndjson(&rows, columns, projected).unwrap();
+            let line: Value = serde_json::from_slice(&ndjson).unwrap();
+            assert_eq!(line["total_volume_usd"], json!(12_345.68));
+        }
+
+        let xml = sheet_xml(&write_excel(&rows, &subset).unwrap());
+        assert!(xml.contains("<v>12345.68</v>"), "{xml}");
+        assert!(!xml.contains("12345.6789"));
+    }
+
+    #[test]
+    fn test_to_ndjson_emits_one_valid_object_per_line() {
+        let rows = vec![
+            json!({"corridor_key": "USDC:GA->XLM:native", "total_volume_usd": 1250.5}),
+            json!({"corridor_key": "EURC:GB->USDC:GA", "note": "line\nbreak"}),
+        ];
+
+        let data = to_ndjson(&rows).unwrap();
+        let text = String::from_utf8(data).unwrap();
+        assert!(text.ends_with('\n'));
+
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), rows.len());
+        for (line, expected) in lines.iter().zip(&rows) {
+            let parsed: Value = serde_json::from_str(line).unwrap();
+            assert_eq!(&parsed, expected);
+        }
+    }
+
+    #[test]
+    fn test_to_ndjson_empty_rows() {
+        let rows: Vec<Value> = Vec::new();
+        assert!(to_ndjson(&rows).unwrap().is_empty());
     }
 }
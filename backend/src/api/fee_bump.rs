@@ -6,7 +6,9 @@ use axum::{
 use serde::Deserialize;
 use std::sync::Arc;
 
+use crate::error::ApiResult;
 use crate::models::{FeeBumpStats, FeeBumpTransaction};
+use crate::pagination::validate_limit;
 use crate::services::fee_bump_tracker::FeeBumpTrackerService;
 
 #[derive(Deserialize)]
@@ -55,10 +57,11 @@ async fn get_fee_bump_stats(
     get,
     path = "/api/fee-bumps/recent",
     params(
-        ("limit" = Option<i64>, Query, description = "Maximum number of transactions to return (1-100, default 50)")
+        ("limit" = Option<i64>, Query, description = "Maximum number of transactions to return (1-MAX_PAGE_SIZE, default 50)")
     ),
     responses(
         (status = 200, description = "List of recent fee bump transactions", body = Vec<FeeBumpTransaction>),
+        (status = 400, description = "limit exceeds MAX_PAGE_SIZE"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Fee Bumps"
@@ -66,12 +69,12 @@ async fn get_fee_bump_stats(
 async fn get_recent_fee_bumps(
     State(service): State<Arc<FeeBumpTrackerService>>,
     Query(params): Query<RecentFeeBumpsParams>,
-) -> Json<Vec<FeeBumpTransaction>> {
-    let limit = params.limit.clamp(1, 100);
+) -> ApiResult<Json<Vec<FeeBumpTransaction>>> {
+    let limit = validate_limit(params.limit)?;
     // In a real app, handle error properly
     let transactions = service
         .get_recent_fee_bumps(limit)
         .await
         .unwrap_or_default();
-    Json(transactions)
+    Ok(Json(transactions))
 }
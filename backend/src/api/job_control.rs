@@ -0,0 +1,179 @@
+//! Admin endpoints for running background jobs on demand.
+//!
+//! # Endpoints
+//!
+//! | Method | Path                                   | Description                              |
+//! |--------|----------------------------------------|------------------------------------------|
+//! | GET    | `/admin/jobs`                          | Names of jobs that can be triggered      |
+//! | POST   | `/admin/jobs/{name}/run`               | Run a job now and return its summary     |
+//! | GET    | `/admin/jobs/asset_revalidation/stats` | Last revalidation run and asset backlog  |
+//!
+//! Like every admin endpoint these sit behind
+//! [`require_admin`](crate::api::admin::require_admin). A trigger for a job
+//! that is already running returns `409` with status `already_running`.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde_json::json;
+
+use crate::error::{ApiError, ApiResult};
+use crate::jobs::scheduler::{JobTriggers, TriggerOutcome};
+use crate::jobs::AssetRevalidationJob;
+
+#[derive(Clone)]
+pub struct JobControlState {
+    pub triggers: Arc<JobTriggers>,
+    pub revalidation: Option<Arc<AssetRevalidationJob>>,
+}
+
+impl JobControlState {
+    #[must_use]
+    pub fn new(triggers: Arc<JobTriggers>) -> Self {
+        Self {
+            triggers,
+            revalidation: None,
+        }
+    }
+
+    /// Serve stats for `job` at `/jobs/asset_revalidation/stats`
+    #[must_use]
+    pub fn with_revalidation_job(mut self, job: Arc<AssetRevalidationJob>) -> Self {
+        self.revalidation = Some(job);
+        self
+    }
+}
+
+/// GET /admin/jobs
+async fn list_jobs(State(state): State<JobControlState>) -> Json<Vec<String>> {
+    Json(state.triggers.names())
+}
+
+/// POST /admin/jobs/{name}/run
+async fn run_job(
+    State(state): State<JobControlState>,
+    Path(name): Path<String>,
+) -> ApiResult<Response> {
+    let outcome = state
+        .triggers
+        .trigger(&name)
+        .await
+        .map_err(|e| ApiError::internal("JOB_FAILED", format!("Job '{name}' failed: {e}")))?;
+    match outcome {
+        TriggerOutcome::Completed(result) => Ok(Json(json!({
+            "job": name,
+            "status": "completed",
+            "result": result,
+        }))
+        .into_response()),
+        TriggerOutcome::AlreadyRunning => Ok((
+            StatusCode::CONFLICT,
+            Json(json!({ "job": name, "status": "already_running" })),
+        )
+            .into_response()),
+        TriggerOutcome::UnknownJob => Err(ApiError::not_found(
+            "JOB_NOT_FOUND",
+            format!("No job named '{name}'"),
+        )),
+    }
+}
+
+/// GET /admin/jobs/asset_revalidation/stats
+async fn asset_revalidation_stats(
+    State(state): State<JobControlState>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let Some(job) = &state.revalidation else {
+        return Err(ApiError::not_found(
+            "JOB_NOT_FOUND",
+            "Asset revalidation job is not registered",
+        ));
+    };
+    let stats_error = |e: anyhow::Error| {
+        ApiError::internal(
+            "JOB_STATS_FAILED",
+            format!("Failed to read revalidation stats: {e}"),
+        )
+    };
+    let last_run = job.last_run().await.map_err(stats_error)?;
+    let assets = job.get_stats().await.map_err(stats_error)?;
+    Ok(Json(json!({ "last_run": last_run, "assets": assets })))
+}
+
+/// Build the job control router. Mount this at `/admin`.
+pub fn routes(state: JobControlState) -> Router {
+    Router::new()
+        .route("/jobs", get(list_jobs))
+        .route("/jobs/{name}/run", post(run_job))
+        .route(
+            "/jobs/asset_revalidation/stats",
+            get(asset_revalidation_stats),
+        )
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::admin::{require_admin, AdminAuth};
+    use axum::body::Body;
+    use axum::http::{header::AUTHORIZATION, Method, Request};
+    use tower::ServiceExt;
+
+    fn app(token: Option<&str>) -> Router {
+        let triggers = Arc::new(JobTriggers::default());
+        triggers.register("asset-revalidation", || {
+            Box::pin(async { Ok(json!({ "total_assets": 2 })) })
+        });
+        require_admin(
+            routes(JobControlState::new(triggers)),
+            AdminAuth::new(token),
+        )
+    }
+
+    async fn run(app: &Router, auth: Option<&str>) -> (StatusCode, serde_json::Value) {
+        let mut request = Request::builder()
+            .method(Method::POST)
+            .uri("/jobs/asset-revalidation/run");
+        if let Some(auth) = auth {
+            request = request.header(AUTHORIZATION, auth);
+        }
+        let response = app
+            .clone()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_job_trigger_requires_bearer_token() {
+        let app = app(Some("s3cret"));
+
+        let (status, _) = run(&app, None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, _) = run(&app, Some("Bearer wrong")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (status, json) = run(&app, Some("Bearer s3cret")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["status"], "completed");
+        assert_eq!(json["result"]["total_assets"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_job_control_disabled_without_token() {
+        let (status, json) = run(&app(None), Some("Bearer anything")).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(json["error"]["code"], "ADMIN_API_DISABLED");
+    }
+}
@@ -6,7 +6,9 @@ use axum::{
 use serde::Deserialize;
 use std::sync::Arc;
 
+use crate::error::ApiResult;
 use crate::models::{LiquidityPool, LiquidityPoolSnapshot, LiquidityPoolStats};
+use crate::pagination::validate_limit;
 use crate::services::liquidity_pool_analyzer::LiquidityPoolAnalyzer;
 
 #[derive(Deserialize)]
@@ -97,10 +99,11 @@ async fn get_pool_stats(
     path = "/api/liquidity-pools/rankings",
     params(
         ("sort_by" = Option<String>, Query, description = "Sort field (e.g., 'apy', default 'apy')"),
-        ("limit" = Option<i64>, Query, description = "Maximum number of pools to return (1-100, default 20)")
+        ("limit" = Option<i64>, Query, description = "Maximum number of pools to return (1-MAX_PAGE_SIZE, default 20)")
     ),
     responses(
         (status = 200, description = "Liquidity pool rankings", body = Vec<LiquidityPool>),
+        (status = 400, description = "limit exceeds MAX_PAGE_SIZE"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Liquidity Pools"
@@ -108,13 +111,13 @@ async fn get_pool_stats(
 async fn get_pool_rankings(
     State(analyzer): State<Arc<LiquidityPoolAnalyzer>>,
     Query(params): Query<RankingsParams>,
-) -> Json<Vec<LiquidityPool>> {
-    let limit = params.limit.clamp(1, 100);
+) -> ApiResult<Json<Vec<LiquidityPool>>> {
+    let limit = validate_limit(params.limit)?;
     let pools = analyzer
         .get_pool_rankings(&params.sort_by, limit)
         .await
         .unwrap_or_default();
-    Json(pools)
+    Ok(Json(pools))
 }
 
 #[derive(serde::Serialize)]
@@ -153,10 +156,11 @@ async fn get_pool_detail(
     path = "/api/liquidity-pools/{pool_id}/snapshots",
     params(
         ("pool_id" = String, Path, description = "Liquidity pool ID"),
-        ("limit" = Option<i64>, Query, description = "Maximum number of snapshots to return (1-500, default 100)")
+        ("limit" = Option<i64>, Query, description = "Maximum number of snapshots to return (1-MAX_PAGE_SIZE, default 100)")
     ),
     responses(
         (status = 200, description = "Liquidity pool snapshots", body = Vec<LiquidityPoolSnapshot>),
+        (status = 400, description = "limit exceeds MAX_PAGE_SIZE"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Liquidity Pools"
@@ -165,11 +169,11 @@ async fn get_pool_snapshots(
     State(analyzer): State<Arc<LiquidityPoolAnalyzer>>,
     Path(pool_id): Path<String>,
     Query(params): Query<SnapshotParams>,
-) -> Json<Vec<LiquidityPoolSnapshot>> {
-    let limit = params.limit.clamp(1, 500);
+) -> ApiResult<Json<Vec<LiquidityPoolSnapshot>>> {
+    let limit = validate_limit(params.limit)?;
     let snapshots = analyzer
         .get_pool_snapshots(&pool_id, limit)
         .await
         .unwrap_or_default();
-    Json(snapshots)
+    Ok(Json(snapshots))
 }
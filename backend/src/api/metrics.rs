@@ -1,5 +1,5 @@
 use axum::{
-    extract::State,
+    extract::{Query, State},
     http::HeaderMap,
     response::{IntoResponse, Response},
     routing::get,
@@ -8,7 +8,7 @@ use axum::{
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
-use crate::cache::helpers::cached_query;
+use crate::cache::helpers::{cached_query_with_refresh, RefreshQuery};
 use crate::cache::{keys, CacheManager};
 use crate::observability::metrics as obs_metrics;
 
@@ -25,6 +25,7 @@ pub struct MetricsOverview {
 #[utoipa::path(
     get,
     path = "/api/metrics/overview",
+    params(RefreshQuery),
     responses(
         (status = 200, description = "Metrics overview", body = MetricsOverview),
         (status = 500, description = "Internal server error")
@@ -33,14 +34,16 @@ pub struct MetricsOverview {
 )]
 pub async fn metrics_overview(
     State(cache): State<Arc<CacheManager>>,
+    Query(RefreshQuery { refresh }): Query<RefreshQuery>,
     headers: HeaderMap,
 ) -> Response {
     let cache_key = keys::metrics_overview();
 
-    let overview = cached_query(
+    let overview = cached_query_with_refresh(
         &cache,
         &cache_key,
         cache.config.get_ttl("dashboard"),
+        refresh,
         || async {
             // Placeholder: Replace with real data aggregation logic
             Ok(MetricsOverview {
@@ -1,8 +1,13 @@
-use axum::{extract::State, response::IntoResponse, routing::get, Json, Router};
+use axum::{
+    extract::{Query, State},
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
-use crate::cache::helpers::cached_query;
+use crate::cache::helpers::{cached_query_with_refresh, RefreshQuery};
 use crate::cache::{keys, CacheManager};
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -13,11 +18,14 @@ pub struct CachedMetricsSummary {
     pub corridor_count: u32,
 }
 
-pub async fn cached_metrics_summary(State(cache): State<Arc<CacheManager>>) -> impl IntoResponse {
+pub async fn cached_metrics_summary(
+    State(cache): State<Arc<CacheManager>>,
+    Query(RefreshQuery { refresh }): Query<RefreshQuery>,
+) -> impl IntoResponse {
     let cache_key = keys::metrics_overview();
     let ttl = cache.config.get_ttl("dashboard");
 
-    let summary = cached_query(&cache, &cache_key, ttl, || async {
+    let summary = cached_query_with_refresh(&cache, &cache_key, ttl, refresh, || async {
         Ok(CachedMetricsSummary {
             cache_hit: false,
             total_volume: 0.0,
@@ -1,14 +1,18 @@
 pub mod account_merges;
 pub mod achievements;
+pub mod admin;
 pub mod alerts;
 pub mod analytics_dashboard;
 pub mod anchors;
 pub mod api_keys;
 pub mod asset_verification;
+pub mod assets;
 pub mod backfill;
 
 pub mod auth;
 pub mod cache_stats;
+pub mod circuit_breakers;
+pub mod corridor_alert_config;
 pub mod corridors;
 pub mod cost_calculator;
 pub mod export;
@@ -17,6 +21,7 @@ pub mod api_analytics;
 pub mod contract_events;
 pub mod fee_bump;
 pub mod governance;
+pub mod job_control;
 pub mod liquidity_pools;
 pub mod metrics;
 
@@ -27,10 +32,14 @@ pub mod prediction;
 pub mod price_feed;
 pub mod replay_handlers;
 pub mod rpc;
+pub mod rpc_errors;
+pub mod runtime_config;
 pub mod sep10;
 pub mod sep24_proxy;
 pub mod sep31_proxy;
 pub mod snapshots;
+pub mod summary;
+pub mod trades;
 pub mod transactions;
 pub mod trustlines;
 pub mod v1;
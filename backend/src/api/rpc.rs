@@ -5,9 +5,15 @@ use axum::{
     Json,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
-use crate::rpc::{Asset, StellarRpcClient};
+use crate::error::ApiError;
+use crate::pagination::validate_limit;
+use crate::rpc::circuit_breaker::circuit_breaker_states;
+use crate::rpc::metrics::rpc_error_counts;
+use crate::rpc::{Asset, CircuitState, HealthResponse, StellarRpcClient};
+use crate::validation::{validate_stellar_address, StellarAddressKind};
 
 #[derive(Debug, Deserialize)]
 pub struct PaginationQuery {
@@ -35,6 +41,38 @@ pub struct OrderBookQuery {
 #[derive(Debug, Serialize)]
 pub struct ErrorResponse {
     pub error: String,
+    /// Machine-readable reason, set for rejected request parameters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+}
+
+fn bad_request(e: impl std::fmt::Display) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ErrorResponse {
+            error: e.to_string(),
+            code: None,
+        }),
+    )
+}
+
+/// Like [`bad_request`], keeping the validation error's code
+fn invalid_param(e: ApiError) -> (StatusCode, Json<ErrorResponse>) {
+    let detail = e.to_error_response(None).error;
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ErrorResponse {
+            error: detail.message,
+            code: Some(detail.code),
+        }),
+    )
+}
+
+/// Reject a `limit` above `MAX_PAGE_SIZE` before it reaches Horizon
+fn page_limit(limit: u32) -> Result<u32, (StatusCode, Json<ErrorResponse>)> {
+    validate_limit(i64::from(limit))
+        .map(|_| limit)
+        .map_err(bad_request)
 }
 
 /// Health check for Stellar RPC
@@ -57,11 +95,83 @@ pub async fn rpc_health_check(
             StatusCode::SERVICE_UNAVAILABLE,
             Json(ErrorResponse {
                 error: format!("RPC health check failed: {e}"),
+                code: None,
             }),
         )),
     }
 }
 
+/// Circuit breaker state and error counts for a single upstream endpoint
+#[derive(Debug, Serialize)]
+pub struct EndpointBreakerStatus {
+    pub endpoint: String,
+    pub state: CircuitState,
+    /// Gauge encoding of `state` (0=closed, 1=open, 2=half-open)
+    pub state_value: i64,
+    /// RPC errors recorded since startup, by error type
+    pub error_counts: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RpcBreakerHealth {
+    pub status: String,
+    /// True while any breaker is not closed, i.e. calls are being short-circuited
+    pub in_failover: bool,
+    pub network: String,
+    pub endpoints: Vec<EndpointBreakerStatus>,
+    pub rpc_health: Option<HealthResponse>,
+    pub rpc_error: Option<String>,
+}
+
+/// Operator view of RPC circuit breakers, error counts and upstream health
+#[utoipa::path(
+    get,
+    path = "/api/health/rpc",
+    responses(
+        (status = 200, description = "Circuit breaker states and RPC health")
+    ),
+    tag = "RPC"
+)]
+#[tracing::instrument(skip(client))]
+pub async fn rpc_breaker_health(
+    State(client): State<Arc<StellarRpcClient>>,
+) -> Json<RpcBreakerHealth> {
+    // Breaker states are read before the upstream call so they are reported
+    // even when the health call itself fails or is rejected by an open breaker.
+    let endpoints: Vec<EndpointBreakerStatus> = circuit_breaker_states()
+        .into_iter()
+        .map(|(endpoint, state)| EndpointBreakerStatus {
+            error_counts: rpc_error_counts(&endpoint),
+            endpoint,
+            state,
+            state_value: state.gauge_value(),
+        })
+        .collect();
+    let in_failover = endpoints.iter().any(|e| e.state != CircuitState::Closed);
+
+    let (rpc_health, rpc_error) = match client.check_health().await {
+        Ok(health) => (Some(health), None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+
+    let status = if rpc_health.is_none() {
+        "unhealthy"
+    } else if in_failover {
+        "degraded"
+    } else {
+        "healthy"
+    };
+
+    Json(RpcBreakerHealth {
+        status: status.to_string(),
+        in_failover,
+        network: client.active_network().label().to_string(),
+        endpoints,
+        rpc_health,
+        rpc_error,
+    })
+}
+
 /// Get latest ledger information
 #[utoipa::path(
     get,
@@ -82,6 +192,7 @@ pub async fn get_latest_ledger(
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ErrorResponse {
                 error: format!("Failed to fetch ledger: {e}"),
+                code: None,
             }),
         )),
     }
@@ -92,11 +203,12 @@ pub async fn get_latest_ledger(
     get,
     path = "/api/rpc/payments",
     params(
-        ("limit" = Option<u32>, Query, description = "Maximum number of payments to return (default 20)"),
+        ("limit" = Option<u32>, Query, description = "Maximum number of payments to return (1-MAX_PAGE_SIZE, default 20)"),
         ("cursor" = Option<String>, Query, description = "Pagination cursor for next page")
     ),
     responses(
         (status = 200, description = "List of recent payments"),
+        (status = 400, description = "limit exceeds MAX_PAGE_SIZE", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "RPC"
@@ -106,13 +218,15 @@ pub async fn get_payments(
     State(client): State<Arc<StellarRpcClient>>,
     Query(params): Query<PaginationQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let limit = page_limit(params.limit)?;
     let cursor = params.cursor.as_deref();
-    match client.fetch_payments(params.limit, cursor).await {
+    match client.fetch_payments(limit, cursor).await {
         Ok(payments) => Ok(Json(payments)),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ErrorResponse {
                 error: format!("Failed to fetch payments: {e}"),
+                code: None,
             }),
         )),
     }
@@ -124,10 +238,11 @@ pub async fn get_payments(
     path = "/api/rpc/accounts/{account_id}/payments",
     params(
         ("account_id" = String, Path, description = "Stellar account ID"),
-        ("limit" = Option<u32>, Query, description = "Maximum number of payments to return (default 20)")
+        ("limit" = Option<u32>, Query, description = "Maximum number of payments to return (1-MAX_PAGE_SIZE, default 20)")
     ),
     responses(
         (status = 200, description = "List of account payments"),
+        (status = 400, description = "Malformed account ID or limit exceeds MAX_PAGE_SIZE", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "RPC"
@@ -138,15 +253,15 @@ pub async fn get_account_payments(
     Path(account_id): Path<String>,
     Query(params): Query<PaginationQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
-    match client
-        .fetch_account_payments(&account_id, params.limit)
-        .await
-    {
+    let limit = page_limit(params.limit)?;
+    validate_stellar_address(&account_id, StellarAddressKind::Account).map_err(invalid_param)?;
+    match client.fetch_account_payments(&account_id, limit).await {
         Ok(payments) => Ok(Json(payments)),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ErrorResponse {
                 error: format!("Failed to fetch account payments: {e}"),
+                code: None,
             }),
         )),
     }
@@ -157,11 +272,12 @@ pub async fn get_account_payments(
     get,
     path = "/api/rpc/trades",
     params(
-        ("limit" = Option<u32>, Query, description = "Maximum number of trades to return (default 20)"),
+        ("limit" = Option<u32>, Query, description = "Maximum number of trades to return (1-MAX_PAGE_SIZE, default 20)"),
         ("cursor" = Option<String>, Query, description = "Pagination cursor for next page")
     ),
     responses(
         (status = 200, description = "List of recent trades"),
+        (status = 400, description = "limit exceeds MAX_PAGE_SIZE", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "RPC"
@@ -171,13 +287,15 @@ pub async fn get_trades(
     State(client): State<Arc<StellarRpcClient>>,
     Query(params): Query<PaginationQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let limit = page_limit(params.limit)?;
     let cursor = params.cursor.as_deref();
-    match client.fetch_trades(params.limit, cursor).await {
+    match client.fetch_trades(limit, cursor).await {
         Ok(trades) => Ok(Json(trades)),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ErrorResponse {
                 error: format!("Failed to fetch trades: {e}"),
+                code: None,
             }),
         )),
     }
@@ -194,10 +312,11 @@ pub async fn get_trades(
         ("buying_asset_type" = String, Query, description = "Buying asset type"),
         ("buying_asset_code" = Option<String>, Query, description = "Buying asset code"),
         ("buying_asset_issuer" = Option<String>, Query, description = "Buying asset issuer"),
-        ("limit" = Option<u32>, Query, description = "Maximum number of price levels to return (default 20)")
+        ("limit" = Option<u32>, Query, description = "Maximum number of price levels to return (1-MAX_PAGE_SIZE, default 20)")
     ),
     responses(
         (status = 200, description = "Order book for trading pair"),
+        (status = 400, description = "Malformed issuer or limit exceeds MAX_PAGE_SIZE", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "RPC"
@@ -207,6 +326,13 @@ pub async fn get_order_book(
     State(client): State<Arc<StellarRpcClient>>,
     Query(params): Query<OrderBookQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let limit = page_limit(params.limit)?;
+    for issuer in [&params.selling_asset_issuer, &params.buying_asset_issuer]
+        .into_iter()
+        .flatten()
+    {
+        validate_stellar_address(issuer, StellarAddressKind::Issuer).map_err(invalid_param)?;
+    }
     let selling_asset = Asset {
         asset_type: params.selling_asset_type,
         asset_code: params.selling_asset_code,
@@ -220,7 +346,7 @@ pub async fn get_order_book(
     };
 
     match client
-        .fetch_order_book(&selling_asset, &buying_asset, params.limit)
+        .fetch_order_book(&selling_asset, &buying_asset, limit)
         .await
     {
         Ok(order_book) => Ok(Json(order_book)),
@@ -228,7 +354,141 @@ pub async fn get_order_book(
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ErrorResponse {
                 error: format!("Failed to fetch order book: {e}"),
+                code: None,
             }),
         )),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_rpc_breaker_health_json_shape_in_mock_mode() {
+        let client = Arc::new(StellarRpcClient::new_with_defaults(true));
+
+        let Json(body) = rpc_breaker_health(State(client)).await;
+        let json = serde_json::to_value(&body).unwrap();
+
+        // The "stellar" breaker is process-global, so other tests may have
+        // tripped it; only the shape and internal consistency are asserted.
+        assert!(json["in_failover"].is_boolean());
+        assert_eq!(json["network"], "testnet");
+        assert!(json["rpc_error"].is_null());
+        assert_eq!(json["rpc_health"]["status"], "healthy");
+        assert!(json["rpc_health"]["latestLedger"].is_u64());
+
+        let stellar = json["endpoints"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|e| e["endpoint"] == "stellar")
+            .expect("stellar breaker is registered");
+        let state = stellar["state"].as_str().unwrap();
+        let expected_value = match state {
+            "closed" => 0,
+            "open" => 1,
+            "half_open" => 2,
+            other => panic!("unexpected breaker state {other}"),
+        };
+        assert_eq!(stellar["state_value"], expected_value);
+        assert!(stellar["error_counts"].is_object());
+
+        let expected_status = if json["in_failover"] == true {
+            "degraded"
+        } else {
+            "healthy"
+        };
+        assert_eq!(json["status"], expected_status);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_limit_is_rejected_before_calling_horizon() {
+        let client = Arc::new(StellarRpcClient::new_with_defaults(true));
+        let limit = u32::try_from(crate::pagination::max_page_size() + 1).unwrap();
+        let query = || PaginationQuery {
+            limit,
+            cursor: None,
+        };
+
+        let Err((status, Json(body))) = get_payments(State(client.clone()), Query(query())).await
+        else {
+            panic!("oversized payments limit was accepted");
+        };
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.error.contains("limit must be between 1 and"));
+
+        let Err((status, _)) = get_trades(State(client.clone()), Query(query())).await else {
+            panic!("oversized trades limit was accepted");
+        };
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        assert!(get_payments(
+            State(client),
+            Query(PaginationQuery {
+                limit: 20,
+                cursor: None
+            })
+        )
+        .await
+        .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_malformed_issuer_and_account_are_rejected() {
+        const ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+        const BAD_CHECKSUM: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVA";
+        const MUXED: &str = "MA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJUAAAAAAAAAAE2JUG6";
+        let client = Arc::new(StellarRpcClient::new_with_defaults(true));
+        let query = |issuer: &str| OrderBookQuery {
+            selling_asset_type: "credit_alphanum4".to_string(),
+            selling_asset_code: Some("USDC".to_string()),
+            selling_asset_issuer: Some(issuer.to_string()),
+            buying_asset_type: "native".to_string(),
+            buying_asset_code: None,
+            buying_asset_issuer: None,
+            limit: 20,
+        };
+
+        let Err((status, Json(body))) =
+            get_order_book(State(client.clone()), Query(query(BAD_CHECKSUM))).await
+        else {
+            panic!("malformed issuer was accepted");
+        };
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.error.contains("not a valid Stellar public key"));
+        assert_eq!(body.code.as_deref(), Some("INVALID_ISSUER"));
+
+        let Err((status, Json(body))) = get_account_payments(
+            State(client.clone()),
+            Path("GTYPO".to_string()),
+            Query(PaginationQuery {
+                limit: 20,
+                cursor: None,
+            }),
+        )
+        .await
+        else {
+            panic!("malformed account was accepted");
+        };
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body.code.as_deref(), Some("INVALID_ACCOUNT"));
+
+        // Muxed accounts are valid on the account endpoint
+        assert!(get_account_payments(
+            State(client.clone()),
+            Path(MUXED.to_string()),
+            Query(PaginationQuery {
+                limit: 20,
+                cursor: None,
+            }),
+        )
+        .await
+        .is_ok());
+
+        assert!(get_order_book(State(client), Query(query(ISSUER)))
+            .await
+            .is_ok());
+    }
+}
@@ -0,0 +1,159 @@
+//! Admin endpoint for browsing recent RPC errors.
+//!
+//! # Endpoints
+//!
+//! | Method | Path                 | Description                                  |
+//! |--------|----------------------|----------------------------------------------|
+//! | GET    | `/admin/rpc-errors`  | Recent RPC errors, newest first              |
+//!
+//! Both query parameters are optional: `endpoint` keeps errors from one client
+//! method (e.g. `fetch_ledger`) and `error_type` keeps one error class (e.g.
+//! `timeout_error`).
+//!
+//! Messages carry upstream URLs and error bodies, so the endpoint is only
+//! served behind [`require_admin`](crate::api::admin::require_admin).
+
+use axum::{
+    extract::{Query, State},
+    response::Json,
+    routing::get,
+    Router,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::rpc::error_log::{rpc_error_log, RpcErrorEvent, RpcErrorLog};
+
+#[derive(Debug, Default, Deserialize)]
+pub struct RpcErrorsQuery {
+    pub endpoint: Option<String>,
+    pub error_type: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RpcErrorsResponse {
+    /// Most events the log keeps before evicting the oldest
+    pub capacity: usize,
+    pub events: Vec<RpcErrorEvent>,
+}
+
+/// GET /admin/rpc-errors
+async fn list_rpc_errors(
+    State(log): State<&'static RpcErrorLog>,
+    Query(query): Query<RpcErrorsQuery>,
+) -> Json<RpcErrorsResponse> {
+    Json(RpcErrorsResponse {
+        capacity: log.capacity(),
+        events: log.recent(query.endpoint.as_deref(), query.error_type.as_deref()),
+    })
+}
+
+fn router(log: &'static RpcErrorLog) -> Router {
+    Router::new()
+        .route("/rpc-errors", get(list_rpc_errors))
+        .with_state(log)
+}
+
+/// Build the RPC error log router. Mount this at `/admin` behind
+/// [`require_admin`](crate::api::admin::require_admin).
+pub fn routes() -> Router {
+    router(rpc_error_log())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::admin::{require_admin, AdminAuth};
+    use crate::rpc::error::RpcError;
+    use axum::body::Body;
+    use axum::http::{header::AUTHORIZATION, Request, StatusCode};
+    use tower::ServiceExt;
+
+    fn app(log: &'static RpcErrorLog) -> Router {
+        require_admin(router(log), AdminAuth::new(Some("s3cret")))
+    }
+
+    async fn get_json(app: &Router, uri: &str) -> serde_json::Value {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(uri)
+                    .header(AUTHORIZATION, "Bearer s3cret")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_lists_newest_first_and_filters() {
+        let log: &'static RpcErrorLog = Box::leak(Box::new(RpcErrorLog::new(3)));
+        log.record(
+            "fetch_ledger",
+            &RpcError::TimeoutError("oldest".to_string()),
+        );
+        log.record(
+            "fetch_ledger",
+            &RpcError::ParseError("bad json".to_string()),
+        );
+        log.record("fetch_account", &RpcError::NotFound("account".to_string()));
+        log.record(
+            "fetch_ledger",
+            &RpcError::TimeoutError("newest".to_string()),
+        );
+        let app = app(log);
+
+        let json = get_json(&app, "/rpc-errors").await;
+        assert_eq!(json["capacity"], 3);
+        let events = json["events"].as_array().unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0]["message"], "Timeout error: newest");
+        assert_eq!(events[1]["endpoint"], "fetch_account");
+        assert_eq!(events[2]["error_type"], "parse_error");
+        assert!(events
+            .iter()
+            .all(|e| e["message"] != "Timeout error: oldest"));
+
+        let json = get_json(&app, "/rpc-errors?endpoint=fetch_ledger").await;
+        assert_eq!(json["events"].as_array().unwrap().len(), 2);
+
+        let json = get_json(
+            &app,
+            "/rpc-errors?endpoint=fetch_ledger&error_type=timeout_error",
+        )
+        .await;
+        let events = json["events"].as_array().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["message"], "Timeout error: newest");
+    }
+
+    #[tokio::test]
+    async fn test_rpc_errors_require_admin_token() {
+        let log: &'static RpcErrorLog = Box::leak(Box::new(RpcErrorLog::new(3)));
+        log.record(
+            "fetch_ledger",
+            &RpcError::TimeoutError("upstream detail".to_string()),
+        );
+
+        let response = app(log)
+            .oneshot(
+                Request::builder()
+                    .uri("/rpc-errors")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(!String::from_utf8_lossy(&body).contains("upstream detail"));
+    }
+}
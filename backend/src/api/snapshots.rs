@@ -1,19 +1,39 @@
 //! HTTP handlers for snapshot generation and submission
 
-use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use axum::{
+    extract::{Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    middleware,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tracing::{error, info};
 use utoipa::ToSchema;
 
+use crate::api::export::{select_columns, write_csv, write_json, Cell, Column};
 use crate::database::Database;
+use crate::error::{ApiError, ApiResult};
+use crate::idempotency::{
+    idempotency_key, request_fingerprint, Claim, IdempotencyStore, StoredResponse,
+};
 use crate::services::contract::ContractService;
 use crate::services::snapshot::SnapshotService;
+use crate::snapshot::{AnalyticsSnapshot, SnapshotDiff, SnapshotValidationError, SCHEMA_VERSION};
+use crate::snapshot_auth_middleware::{require_snapshot_signature, SnapshotSubmitters};
+
+/// Idempotency scope for snapshot generation requests
+const GENERATE_SCOPE: &str = "snapshots.generate";
+/// Most epochs a single history request may span
+const MAX_HISTORY_EPOCHS: u64 = 500;
 
 /// Response for snapshot generation
 #[derive(Debug, Serialize, ToSchema)]
 pub struct SnapshotResponse {
+    pub snapshot_id: String,
     pub epoch: u64,
     pub timestamp: String,
     pub hash: String,
@@ -22,6 +42,9 @@ pub struct SnapshotResponse {
     pub corridor_count: usize,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub submission: Option<SubmissionInfo>,
+    /// Queued on-chain submission, when submission is asynchronous
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub submission_id: Option<String>,
 }
 
 /// Submission information
@@ -33,38 +56,159 @@ pub struct SubmissionInfo {
 }
 
 /// Request for snapshot generation
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct GenerateSnapshotRequest {
     pub epoch: u64,
     #[serde(default)]
     pub submit_to_contract: bool,
 }
 
+/// Query parameters for snapshot generation
+#[derive(Debug, Default, Deserialize)]
+pub struct GenerateSnapshotQuery {
+    /// Compute the snapshot and hash without storing or submitting it
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Response for a dry-run snapshot generation
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SnapshotPreviewResponse {
+    pub epoch: u64,
+    pub timestamp: String,
+    pub hash: String,
+    pub schema_version: u32,
+    pub anchor_count: usize,
+    pub corridor_count: usize,
+    /// Canonical JSON that would be hashed and submitted
+    pub canonical_json: String,
+    pub dry_run: bool,
+}
+
 /// Shared application state for snapshot handlers
 #[derive(Clone)]
 pub struct SnapshotAppState {
     pub db: Arc<Database>,
     pub contract_service: Option<Arc<ContractService>>,
     pub snapshot_service: Arc<SnapshotService>,
+    pub idempotency: Arc<IdempotencyStore>,
 }
 
 /// Generate a snapshot (optionally submit to contract)
 ///
 /// POST /api/snapshots/generate
+///
+/// With an `Idempotency-Key` header, a repeat of the same key within the
+/// configured window returns the original response without generating or
+/// submitting again. Failed attempts are not recorded, so the key can be
+/// retried; reusing a key with a different body is rejected with 422.
+///
+/// With `?dry_run=true` the snapshot is aggregated and hashed but nothing is
+/// stored or submitted; idempotency keys are ignored.
+///
+/// Requests must be signed by an allowlisted submitter key, see
+/// [`crate::snapshot_auth_middleware`].
 #[utoipa::path(
     post,
     path = "/api/snapshots/generate",
     request_body = GenerateSnapshotRequest,
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Replay-safe request key"),
+        ("dry_run" = Option<bool>, Query, description = "Preview the snapshot without persisting or submitting it"),
+        ("X-Stellar-Account" = String, Header, description = "Submitter public key"),
+        ("X-Timestamp" = String, Header, description = "Unix seconds the request was signed at"),
+        ("X-Nonce" = String, Header, description = "Unique per request; reuse is rejected"),
+        ("X-Signature" = String, Header, description = "Base64 ed25519 signature of the request")
+    ),
     responses(
         (status = 200, description = "Snapshot generated successfully", body = SnapshotResponse),
+        (status = 400, description = "Snapshot failed validation (epoch, metrics, corridor keys or hash)"),
+        (status = 401, description = "Missing or invalid submitter signature, or reused nonce"),
+        (status = 403, description = "Submitter key is not allowlisted"),
+        (status = 409, description = "A request with this Idempotency-Key is still in progress"),
+        (status = 422, description = "Idempotency-Key was already used with a different request body"),
         (status = 500, description = "Snapshot generation failed")
     ),
     tag = "Snapshots"
 )]
 pub async fn generate_snapshot(
     State(state): State<SnapshotAppState>,
+    Query(query): Query<GenerateSnapshotQuery>,
+    headers: HeaderMap,
     Json(request): Json<GenerateSnapshotRequest>,
-) -> Result<Json<SnapshotResponse>, SnapshotError> {
+) -> Response {
+    if query.dry_run {
+        return match run_preview(&state, request.epoch).await {
+            Ok(response) => Json(response).into_response(),
+            Err(e) => e.into_response(),
+        };
+    }
+
+    let Some(key) = idempotency_key(&headers) else {
+        return match run_generation(&state, &request).await {
+            Ok(response) => Json(response).into_response(),
+            Err(e) => e.into_response(),
+        };
+    };
+
+    let request_hash = request_fingerprint(&serde_json::to_vec(&request).unwrap_or_default());
+    match state
+        .idempotency
+        .claim(GENERATE_SCOPE, &key, &request_hash)
+        .await
+    {
+        Ok(Claim::Fresh) => {}
+        Ok(Claim::Replay(stored)) => {
+            info!("Replaying snapshot response for idempotency key {}", key);
+            return stored.into_response();
+        }
+        Ok(Claim::InProgress) => {
+            return SnapshotError::Conflict(
+                "A request with this Idempotency-Key is still in progress".to_string(),
+            )
+            .into_response();
+        }
+        Ok(Claim::Mismatch) => {
+            return SnapshotError::Unprocessable(
+                "Idempotency-Key was already used with a different request body".to_string(),
+            )
+            .into_response();
+        }
+        Err(e) => {
+            error!("Failed to claim idempotency key {}: {}", key, e);
+            return SnapshotError::GenerationFailed("Idempotency store unavailable".to_string())
+                .into_response();
+        }
+    }
+
+    let stored = match run_generation(&state, &request).await {
+        Ok(response) => StoredResponse {
+            status: StatusCode::OK,
+            resource_id: Some(response.snapshot_id.clone()),
+            body: serde_json::to_value(&response).unwrap_or_default(),
+        },
+        Err(e) => {
+            // Leave the key free so the client can retry once the cause is fixed
+            if let Err(release_err) = state.idempotency.release(GENERATE_SCOPE, &key).await {
+                error!("Failed to release idempotency key {}: {}", key, release_err);
+            }
+            return e.into_response();
+        }
+    };
+    if let Err(e) = state
+        .idempotency
+        .complete(GENERATE_SCOPE, &key, &stored)
+        .await
+    {
+        error!("Failed to store idempotent response for key {}: {}", key, e);
+    }
+    stored.into_response()
+}
+
+async fn run_generation(
+    state: &SnapshotAppState,
+    request: &GenerateSnapshotRequest,
+) -> Result<SnapshotResponse, SnapshotError> {
     info!(
         "Generating snapshot for epoch {} (submit: {})",
         request.epoch, request.submit_to_contract
@@ -79,10 +223,11 @@ pub async fn generate_snapshot(
         Ok(result) => {
             let hash = result.hash.clone();
             let response = SnapshotResponse {
+                snapshot_id: result.snapshot_id,
                 epoch: result.epoch,
                 timestamp: result.timestamp.to_rfc3339(),
                 hash: result.hash,
-                schema_version: 1, // From SCHEMA_VERSION
+                schema_version: SCHEMA_VERSION,
                 anchor_count: result.anchor_count,
                 corridor_count: result.corridor_count,
                 submission: result.submission_result.map(|sr| SubmissionInfo {
@@ -90,6 +235,7 @@ pub async fn generate_snapshot(
                     ledger: sr.ledger,
                     contract_timestamp: sr.timestamp,
                 }),
+                submission_id: result.submission_id,
             };
 
             info!(
@@ -101,18 +247,285 @@ pub async fn generate_snapshot(
                 result.verification_successful
             );
 
-            Ok(Json(response))
+            Ok(response)
         }
         Err(e) => {
             error!(
                 "Failed to generate snapshot for epoch {}: {}",
                 request.epoch, e
             );
-            Err(SnapshotError::GenerationFailed(e.to_string()))
+            match e.downcast::<SnapshotValidationError>() {
+                Ok(invalid) => Err(SnapshotError::Invalid(invalid)),
+                Err(e) => Err(SnapshotError::GenerationFailed(e.to_string())),
+            }
         }
     }
 }
 
+async fn run_preview(
+    state: &SnapshotAppState,
+    epoch: u64,
+) -> Result<SnapshotPreviewResponse, SnapshotError> {
+    info!("Computing dry-run snapshot for epoch {}", epoch);
+
+    let preview = state
+        .snapshot_service
+        .compute_snapshot_preview(epoch)
+        .await
+        .map_err(|e| {
+            error!(
+                "Failed to compute snapshot preview for epoch {}: {}",
+                epoch, e
+            );
+            SnapshotError::GenerationFailed(e.to_string())
+        })?;
+
+    Ok(SnapshotPreviewResponse {
+        epoch: preview.snapshot.epoch,
+        timestamp: preview.snapshot.timestamp.to_rfc3339(),
+        hash: preview.hash,
+        schema_version: preview.snapshot.schema_version,
+        anchor_count: preview.snapshot.anchor_metrics.len(),
+        corridor_count: preview.snapshot.corridor_metrics.len(),
+        canonical_json: preview.canonical_json,
+        dry_run: true,
+    })
+}
+
+/// Query parameters for comparing two snapshots
+#[derive(Debug, Deserialize)]
+pub struct SnapshotDiffQuery {
+    pub from: u64,
+    pub to: u64,
+}
+
+/// Compare the stored snapshots of two epochs
+///
+/// GET /api/snapshots/diff?from=N&to=M
+#[utoipa::path(
+    get,
+    path = "/api/snapshots/diff",
+    params(
+        ("from" = u64, Query, description = "Epoch to compare from"),
+        ("to" = u64, Query, description = "Epoch to compare to")
+    ),
+    responses(
+        (status = 200, description = "Differences between the two snapshots", body = SnapshotDiff),
+        (status = 400, description = "`from` and `to` are the same epoch"),
+        (status = 404, description = "No snapshot stored for one of the epochs"),
+        (status = 500, description = "Failed to load snapshots")
+    ),
+    tag = "Snapshots"
+)]
+pub async fn diff_snapshots(
+    State(state): State<SnapshotAppState>,
+    Query(query): Query<SnapshotDiffQuery>,
+) -> Result<Json<SnapshotDiff>, SnapshotError> {
+    if query.from == query.to {
+        return Err(SnapshotError::BadRequest(
+            "`from` and `to` must be different epochs".to_string(),
+        ));
+    }
+
+    let load = |epoch: u64| {
+        let service = state.snapshot_service.clone();
+        async move {
+            service
+                .load_snapshot(epoch)
+                .await
+                .map_err(|e| SnapshotError::GenerationError(e.to_string()))?
+                .ok_or_else(|| SnapshotError::NotFound(format!("No snapshot for epoch {epoch}")))
+        }
+    };
+    let from = load(query.from).await?;
+    let to = load(query.to).await?;
+
+    Ok(Json(SnapshotDiff::between(&from, &to)))
+}
+
+/// Query parameters for the snapshot history
+#[derive(Debug, Deserialize)]
+pub struct SnapshotHistoryQuery {
+    pub from: u64,
+    pub to: u64,
+    /// Comma-separated field names to include, e.g. `epoch,total_volume_usd`
+    pub fields: Option<String>,
+    /// `json` (default) or `csv`
+    pub format: Option<String>,
+}
+
+/// Per-epoch totals from one stored snapshot
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SnapshotHistoryPoint {
+    pub epoch: u64,
+    pub timestamp: String,
+    pub anchor_count: usize,
+    pub corridor_count: usize,
+    pub total_volume_usd: f64,
+    pub total_transactions: i64,
+    pub successful_transactions: i64,
+    pub failed_transactions: i64,
+    /// Successful over total corridor transactions, `0` when there were none
+    pub success_rate: f64,
+}
+
+impl From<&AnalyticsSnapshot> for SnapshotHistoryPoint {
+    fn from(snapshot: &AnalyticsSnapshot) -> Self {
+        let corridors = &snapshot.corridor_metrics;
+        let total_transactions = corridors.iter().map(|c| c.total_transactions).sum::<i64>();
+        let successful_transactions = corridors
+            .iter()
+            .map(|c| c.successful_transactions)
+            .sum::<i64>();
+        Self {
+            epoch: snapshot.epoch,
+            timestamp: snapshot.timestamp.to_rfc3339(),
+            anchor_count: snapshot.anchor_metrics.len(),
+            corridor_count: corridors.len(),
+            total_volume_usd: corridors.iter().map(|c| c.volume_usd).sum(),
+            total_transactions,
+            successful_transactions,
+            failed_transactions: corridors.iter().map(|c| c.failed_transactions).sum(),
+            success_rate: if total_transactions > 0 {
+                successful_transactions as f64 / total_transactions as f64
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+const HISTORY_FIELDS: &[Column<SnapshotHistoryPoint>] = &[
+    Column {
+        name: "epoch",
+        header: "Epoch",
+        value: |p| Cell::Integer(p.epoch as i64),
+    },
+    Column {
+        name: "timestamp",
+        header: "Timestamp",
+        value: |p| Cell::Text(p.timestamp.clone()),
+    },
+    Column {
+        name: "anchor_count",
+        header: "Anchors",
+        value: |p| Cell::Integer(p.anchor_count as i64),
+    },
+    Column {
+        name: "corridor_count",
+        header: "Corridors",
+        value: |p| Cell::Integer(p.corridor_count as i64),
+    },
+    Column {
+        name: "total_volume_usd",
+        header: "Total Volume (USD)",
+        value: |p| Cell::Usd(p.total_volume_usd),
+    },
+    Column {
+        name: "total_transactions",
+        header: "Total Transactions",
+        value: |p| Cell::Integer(p.total_transactions),
+    },
+    Column {
+        name: "successful_transactions",
+        header: "Successful Transactions",
+        value: |p| Cell::Integer(p.successful_transactions),
+    },
+    Column {
+        name: "failed_transactions",
+        header: "Failed Transactions",
+        value: |p| Cell::Integer(p.failed_transactions),
+    },
+    Column {
+        name: "success_rate",
+        header: "Success Rate",
+        value: |p| Cell::Number(p.success_rate),
+    },
+];
+
+/// Time series of per-epoch totals across stored snapshots
+///
+/// GET /api/snapshots?from=N&to=M&fields=epoch,total_volume_usd&format=csv
+///
+/// Epochs without a stored snapshot are skipped. The range may span at most
+/// [`MAX_HISTORY_EPOCHS`] epochs.
+#[utoipa::path(
+    get,
+    path = "/api/snapshots",
+    params(
+        ("from" = u64, Query, description = "First epoch, inclusive"),
+        ("to" = u64, Query, description = "Last epoch, inclusive"),
+        ("fields" = Option<String>, Query, description = "Comma-separated fields to include; all when omitted"),
+        ("format" = Option<String>, Query, description = "`json` (default) or `csv`")
+    ),
+    responses(
+        (status = 200, description = "One entry per stored epoch in the range", body = [SnapshotHistoryPoint]),
+        (status = 400, description = "Invalid range, unknown field or unsupported format"),
+        (status = 500, description = "Failed to load snapshots")
+    ),
+    tag = "Snapshots"
+)]
+pub async fn get_snapshot_history(
+    State(state): State<SnapshotAppState>,
+    Query(query): Query<SnapshotHistoryQuery>,
+) -> ApiResult<Response> {
+    if query.from > query.to {
+        return Err(ApiError::bad_request(
+            "INVALID_RANGE",
+            "`from` must not be after `to`",
+        ));
+    }
+    if query.to - query.from >= MAX_HISTORY_EPOCHS {
+        return Err(ApiError::bad_request(
+            "RANGE_TOO_LARGE",
+            format!("At most {MAX_HISTORY_EPOCHS} epochs can be requested at once"),
+        ));
+    }
+    let (fields, projected) = select_columns(HISTORY_FIELDS, query.fields.as_deref())?;
+    let format = query.format.as_deref().unwrap_or("json").to_lowercase();
+
+    let points: Vec<SnapshotHistoryPoint> = state
+        .snapshot_service
+        .load_snapshot_range(query.from, query.to)
+        .await
+        .map_err(|e| {
+            error!(
+                "Failed to load snapshots {}..={}: {}",
+                query.from, query.to, e
+            );
+            ApiError::internal("SNAPSHOT_HISTORY_FAILED", e.to_string())
+        })?
+        .iter()
+        .map(SnapshotHistoryPoint::from)
+        .collect();
+
+    let mut headers = HeaderMap::new();
+    let data = match format.as_str() {
+        "json" => {
+            headers.insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("application/json"),
+            );
+            write_json(&points, &fields, projected)?
+        }
+        "csv" => {
+            headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/csv"));
+            headers.insert(
+                header::CONTENT_DISPOSITION,
+                HeaderValue::from_static("attachment; filename=\"snapshot_history.csv\""),
+            );
+            write_csv(&points, &fields)?
+        }
+        other => {
+            return Err(ApiError::bad_request(
+                "INVALID_FORMAT",
+                format!("Format {other} is not supported (expected csv or json)"),
+            ))
+        }
+    };
+    Ok((headers, data).into_response())
+}
+
 /// Health check for contract service
 ///
 /// GET /api/snapshots/contract/health
@@ -160,26 +573,445 @@ pub enum SnapshotError {
     SubmissionError(String),
     ConnectionError(String),
     ConfigError(String),
+    Conflict(String),
+    Unprocessable(String),
+    BadRequest(String),
+    NotFound(String),
+    /// Rejected by [`crate::snapshot::validate_snapshot`]; rendered as an
+    /// [`ApiError`] so clients get the specific failure code
+    Invalid(SnapshotValidationError),
 }
 
-impl IntoResponse for SnapshotError {
-    fn into_response(self) -> axum::response::Response {
+impl SnapshotError {
+    fn status_and_body(self) -> (StatusCode, serde_json::Value) {
         let (status, message) = match self {
+            Self::Invalid(err) => {
+                let err = ApiError::from(err);
+                let body = serde_json::to_value(err.to_error_response(None)).unwrap_or_default();
+                return (StatusCode::BAD_REQUEST, body);
+            }
             Self::GenerationFailed(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
             Self::GenerationError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
             Self::HashingError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
             Self::SubmissionError(msg) => (StatusCode::BAD_GATEWAY, msg),
             Self::ConnectionError(msg) => (StatusCode::SERVICE_UNAVAILABLE, msg),
             Self::ConfigError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
+            Self::Conflict(msg) => (StatusCode::CONFLICT, msg),
+            Self::Unprocessable(msg) => (StatusCode::UNPROCESSABLE_ENTITY, msg),
+            Self::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
+            Self::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
         };
 
         (
             status,
-            Json(serde_json::json!({
+            serde_json::json!({
                 "error": message,
                 "timestamp": Utc::now().to_rfc3339()
-            })),
+            }),
+        )
+    }
+}
+
+impl IntoResponse for SnapshotError {
+    fn into_response(self) -> axum::response::Response {
+        let (status, body) = self.status_and_body();
+        (status, Json(body)).into_response()
+    }
+}
+
+/// Build the snapshot router. Mount this at `/api/snapshots`.
+///
+/// `POST /generate` only accepts requests signed by one of `submitters`.
+pub fn routes(state: SnapshotAppState, submitters: SnapshotSubmitters) -> Router {
+    let submission = Router::new()
+        .route("/generate", post(generate_snapshot))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::new(submitters),
+            require_snapshot_signature,
+        ));
+    Router::new()
+        .route("/", get(get_snapshot_history))
+        .route("/diff", get(diff_snapshots))
+        .route("/contract/health", get(contract_health_check))
+        .merge(submission)
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::StellarRpcClient;
+    use crate::snapshot::{SnapshotAnchorMetrics, SnapshotCorridorMetrics};
+    use uuid::Uuid;
+
+    async fn state_with_snapshots(snapshots: &[AnalyticsSnapshot]) -> SnapshotAppState {
+        let pool = sqlx::SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::query(
+            "CREATE TABLE snapshots (
+                id TEXT PRIMARY KEY,
+                entity_id TEXT,
+                entity_type TEXT,
+                data TEXT,
+                hash TEXT,
+                epoch INTEGER UNIQUE,
+                timestamp TEXT NOT NULL,
+                created_at TEXT DEFAULT CURRENT_TIMESTAMP
+            )",
         )
-            .into_response()
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(include_str!(
+            "../../migrations/037_create_idempotency_keys.sql"
+        ))
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let db = Arc::new(Database::new(pool.clone()));
+        let snapshot_service = Arc::new(SnapshotService::new(
+            db.clone(),
+            Arc::new(StellarRpcClient::new_with_defaults(true)),
+            None,
+            None,
+        ));
+        for snapshot in snapshots {
+            let json = SnapshotService::serialize_deterministically(snapshot.clone()).unwrap();
+            let hash = SnapshotService::hash_snapshot_hex(snapshot.clone()).unwrap();
+            snapshot_service
+                .store_snapshot_in_database(snapshot, &hash, &json)
+                .await
+                .unwrap();
+        }
+
+        SnapshotAppState {
+            db,
+            contract_service: None,
+            snapshot_service,
+            idempotency: Arc::new(IdempotencyStore::new(pool)),
+        }
+    }
+
+    fn anchor(id: u128, status: &str) -> SnapshotAnchorMetrics {
+        SnapshotAnchorMetrics {
+            id: Uuid::from_u128(id),
+            name: format!("Anchor {id}"),
+            stellar_account: format!("GANCHOR{id}"),
+            success_rate: 0.99,
+            failure_rate: 0.01,
+            reliability_score: 0.99,
+            total_transactions: 100,
+            successful_transactions: 99,
+            failed_transactions: 1,
+            avg_settlement_time_ms: Some(500),
+            volume_usd: Some(1_000.0),
+            status: status.to_string(),
+        }
+    }
+
+    fn corridor(key: &str, volume_usd: f64, total_transactions: i64) -> SnapshotCorridorMetrics {
+        SnapshotCorridorMetrics {
+            id: Uuid::new_v4(),
+            corridor_key: key.to_string(),
+            source_asset_code: "USDC".to_string(),
+            source_asset_issuer: "issuer1".to_string(),
+            destination_asset_code: "EURC".to_string(),
+            destination_asset_issuer: "issuer2".to_string(),
+            total_transactions,
+            successful_transactions: total_transactions,
+            failed_transactions: 0,
+            success_rate: 1.0,
+            volume_usd,
+            avg_settlement_latency_ms: Some(250),
+            liquidity_depth_usd: 10_000.0,
+        }
+    }
+
+    fn snapshot(
+        epoch: u64,
+        anchors: Vec<SnapshotAnchorMetrics>,
+        corridors: Vec<SnapshotCorridorMetrics>,
+    ) -> AnalyticsSnapshot {
+        let mut snapshot = AnalyticsSnapshot::new(epoch, Utc::now());
+        anchors
+            .into_iter()
+            .for_each(|a| snapshot.add_anchor_metrics(a));
+        corridors
+            .into_iter()
+            .for_each(|c| snapshot.add_corridor_metrics(c));
+        snapshot
+    }
+
+    async fn generate(
+        state: &SnapshotAppState,
+        key: &str,
+        request: GenerateSnapshotRequest,
+    ) -> StatusCode {
+        let mut headers = HeaderMap::new();
+        headers.insert("idempotency-key", HeaderValue::from_str(key).unwrap());
+        generate_snapshot(
+            State(state.clone()),
+            Query(GenerateSnapshotQuery::default()),
+            headers,
+            Json(request),
+        )
+        .await
+        .status()
+    }
+
+    #[tokio::test]
+    async fn test_failed_generation_releases_idempotency_key() {
+        // No anchor or corridor tables, so generation fails
+        let state = state_with_snapshots(&[]).await;
+        let request = || GenerateSnapshotRequest {
+            epoch: 7,
+            submit_to_contract: false,
+        };
+
+        let status = generate(&state, "key-1", request()).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        let status = generate(&state, "key-1", request()).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+
+        let hash = request_fingerprint(&serde_json::to_vec(&request()).unwrap());
+        let claim = state
+            .idempotency
+            .claim(GENERATE_SCOPE, "key-1", &hash)
+            .await
+            .unwrap();
+        assert_eq!(claim, Claim::Fresh);
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_reused_with_different_body_is_rejected() {
+        let state = state_with_snapshots(&[]).await;
+        let first = GenerateSnapshotRequest {
+            epoch: 7,
+            submit_to_contract: false,
+        };
+        let hash = request_fingerprint(&serde_json::to_vec(&first).unwrap());
+        state
+            .idempotency
+            .claim(GENERATE_SCOPE, "key-1", &hash)
+            .await
+            .unwrap();
+
+        let status = generate(
+            &state,
+            "key-1",
+            GenerateSnapshotRequest {
+                epoch: 8,
+                submit_to_contract: false,
+            },
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_diff_reports_corridor_and_anchor_changes() {
+        let state = state_with_snapshots(&[
+            snapshot(
+                1,
+                vec![anchor(1, "green"), anchor(2, "green")],
+                vec![
+                    corridor("USDC->EURC", 1_000.0, 10),
+                    corridor("USDC->NGN", 50.0, 2),
+                ],
+            ),
+            snapshot(
+                2,
+                vec![anchor(1, "green"), anchor(2, "red")],
+                vec![
+                    corridor("USDC->EURC", 1_500.0, 12),
+                    corridor("XLM->USDC", 75.0, 3),
+                ],
+            ),
+        ])
+        .await;
+
+        let Json(diff) = diff_snapshots(State(state), Query(SnapshotDiffQuery { from: 1, to: 2 }))
+            .await
+            .unwrap();
+
+        assert_eq!(diff.from_epoch, 1);
+        assert_eq!(diff.to_epoch, 2);
+        assert_eq!(diff.added_corridors, vec!["XLM->USDC".to_string()]);
+        assert_eq!(diff.removed_corridors, vec!["USDC->NGN".to_string()]);
+        assert_eq!(diff.corridor_changes.len(), 1);
+        assert_eq!(diff.corridor_changes[0].corridor_key, "USDC->EURC");
+        assert_eq!(diff.corridor_changes[0].volume_usd, 500.0);
+        assert_eq!(diff.corridor_changes[0].total_transactions, 2);
+        assert_eq!(diff.anchor_status_changes.len(), 1);
+        assert_eq!(diff.anchor_status_changes[0].anchor_id, Uuid::from_u128(2));
+        assert_eq!(diff.anchor_status_changes[0].from_status, "green");
+        assert_eq!(diff.anchor_status_changes[0].to_status, "red");
+    }
+
+    #[tokio::test]
+    async fn test_diff_with_missing_epoch_is_not_found() {
+        let state = state_with_snapshots(&[snapshot(1, vec![], vec![])]).await;
+
+        let err = diff_snapshots(State(state), Query(SnapshotDiffQuery { from: 1, to: 2 }))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.into_response().status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_validation_failure_carries_its_code() {
+        let err = SnapshotError::Invalid(SnapshotValidationError::DuplicateCorridorKey(
+            "USDC->EURC".to_string(),
+        ));
+
+        let (status, body) = err.status_and_body();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["code"], "SNAPSHOT_DUPLICATE_CORRIDOR");
+    }
+
+    async fn history(
+        state: SnapshotAppState,
+        from: u64,
+        to: u64,
+        fields: Option<&str>,
+        format: Option<&str>,
+    ) -> ApiResult<(HeaderMap, String)> {
+        let query = SnapshotHistoryQuery {
+            from,
+            to,
+            fields: fields.map(str::to_string),
+            format: format.map(str::to_string),
+        };
+        let response = get_snapshot_history(State(state), Query(query)).await?;
+        let headers = response.headers().clone();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        Ok((headers, String::from_utf8(body.to_vec()).unwrap()))
+    }
+
+    async fn seeded_history_state() -> SnapshotAppState {
+        state_with_snapshots(&[
+            snapshot(1, vec![anchor(1, "green")], vec![corridor("A", 100.0, 1)]),
+            snapshot(
+                2,
+                vec![anchor(1, "green")],
+                vec![corridor("A", 200.0, 2), corridor("B", 50.0, 3)],
+            ),
+            snapshot(3, vec![], vec![corridor("A", 300.0, 4)]),
+            snapshot(5, vec![], vec![corridor("A", 500.0, 5)]),
+        ])
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_history_selects_range_and_projects_fields() {
+        let state = seeded_history_state().await;
+
+        let (_, body) = history(state, 2, 5, Some("epoch,total_volume_usd"), None)
+            .await
+            .unwrap();
+        let points: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+        assert_eq!(
+            points,
+            serde_json::json!([
+                {"epoch": 2, "total_volume_usd": 250.0},
+                {"epoch": 3, "total_volume_usd": 300.0},
+                {"epoch": 5, "total_volume_usd": 500.0},
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_history_without_fields_returns_every_field() {
+        let state = seeded_history_state().await;
+
+        let (_, body) = history(state, 2, 2, None, Some("json")).await.unwrap();
+        let points: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+        assert_eq!(points.as_array().unwrap().len(), 1);
+        assert_eq!(points[0]["anchor_count"], 1);
+        assert_eq!(points[0]["corridor_count"], 2);
+        assert_eq!(points[0]["total_transactions"], 5);
+        assert_eq!(points[0]["success_rate"], 1.0);
+        assert!(points[0]["timestamp"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_history_as_csv() {
+        let state = seeded_history_state().await;
+
+        let (headers, body) = history(state, 1, 2, Some("epoch,corridor_count"), Some("csv"))
+            .await
+            .unwrap();
+
+        assert_eq!(headers[header::CONTENT_TYPE], "text/csv");
+        assert_eq!(body, "Epoch,Corridors\n1,1\n2,2\n");
+    }
+
+    #[tokio::test]
+    async fn test_history_rejects_bad_ranges_fields_and_formats() {
+        let state = seeded_history_state().await;
+
+        for (from, to, fields, format) in [
+            (3, 2, None, None),
+            (0, MAX_HISTORY_EPOCHS, None, None),
+            (1, 2, Some("epoch,bogus"), None),
+            (1, 2, None, Some("excel")),
+        ] {
+            let err = history(state.clone(), from, to, fields, format)
+                .await
+                .unwrap_err();
+            assert_eq!(err.into_response().status(), StatusCode::BAD_REQUEST);
+        }
+
+        // The cap is inclusive of both ends
+        assert!(history(state, 1, MAX_HISTORY_EPOCHS, None, None)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_diff_of_identical_epochs_is_bad_request() {
+        let state = state_with_snapshots(&[snapshot(1, vec![], vec![])]).await;
+
+        let err = diff_snapshots(State(state), Query(SnapshotDiffQuery { from: 1, to: 1 }))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.into_response().status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_routes_mount_history_diff_and_signed_generate() {
+        use axum::body::Body;
+        use axum::http::{Method, Request};
+        use tower::ServiceExt;
+
+        let state = seeded_history_state().await;
+        let app = routes(state, SnapshotSubmitters::new(Vec::new()));
+        let status = |method: Method, uri: &str| {
+            let app = app.clone();
+            let request = Request::builder()
+                .method(method)
+                .uri(uri)
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(r#"{"epoch":9}"#))
+                .unwrap();
+            async move { app.oneshot(request).await.unwrap().status() }
+        };
+
+        assert_eq!(status(Method::GET, "/?from=1&to=5").await, StatusCode::OK);
+        assert_eq!(
+            status(Method::GET, "/diff?from=1&to=2").await,
+            StatusCode::OK
+        );
+        // Generation, including dry runs, is behind the submitter signature check
+        assert_eq!(
+            status(Method::POST, "/generate?dry_run=true").await,
+            StatusCode::UNAUTHORIZED
+        );
     }
 }
@@ -0,0 +1,72 @@
+//! Network-wide daily summary.
+//!
+//! `GET /api/summary/daily?date=YYYY-MM-DD` returns the totals for one UTC
+//! day, defaulting to today. Responses are cached per date and carry
+//! `Cache-Control`/`ETag` headers.
+
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    response::Response,
+    routing::get,
+    Router,
+};
+use chrono::{NaiveDate, Utc};
+use serde::Deserialize;
+
+use crate::cache::helpers::{cached_query_with_refresh, RefreshQuery};
+use crate::cache::keys;
+use crate::error::{ApiError, ApiResult};
+use crate::services::analytics::daily_summary;
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct DailySummaryQuery {
+    /// UTC day to summarise; defaults to today
+    pub date: Option<NaiveDate>,
+}
+
+/// GET /api/summary/daily
+#[utoipa::path(
+    get,
+    path = "/api/summary/daily",
+    params(DailySummaryQuery, RefreshQuery),
+    responses(
+        (status = 200, description = "Totals for the requested day", body = crate::services::analytics::DailySummary),
+        (status = 304, description = "Not modified (ETag or Last-Modified match)"),
+        (status = 400, description = "Malformed date"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Analytics"
+)]
+pub async fn get_daily_summary(
+    State(app_state): State<AppState>,
+    Query(query): Query<DailySummaryQuery>,
+    Query(RefreshQuery { refresh }): Query<RefreshQuery>,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    let date = query.date.unwrap_or_else(|| Utc::now().date_naive());
+    let cache_key = keys::daily_summary(&date.to_string());
+    let ttl = app_state.cache.config.get_ttl("dashboard");
+
+    let summary = cached_query_with_refresh(&app_state.cache, &cache_key, ttl, refresh, || {
+        daily_summary(app_state.db.pool(), date)
+    })
+    .await
+    .map_err(|e| {
+        ApiError::internal(
+            "DAILY_SUMMARY_FAILED",
+            format!("Failed to build daily summary for {date}: {e}"),
+        )
+    })?;
+
+    crate::http_cache::cached_json_response(&headers, &cache_key, &summary, ttl)
+        .map_err(|e| ApiError::internal("DAILY_SUMMARY_FAILED", e.to_string()))
+}
+
+/// Build the summary router. Mount this at `/api/summary`.
+pub fn routes(app_state: AppState) -> Router {
+    Router::new()
+        .route("/daily", get(get_daily_summary))
+        .with_state(app_state)
+}
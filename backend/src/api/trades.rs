@@ -0,0 +1,249 @@
+use axum::{
+    extract::{Query, State},
+    routing::get,
+    Json, Router,
+};
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::error::{ApiError, ApiResult};
+use crate::models::trades::{Candle, CandleInterval};
+use crate::pagination::validate_limit;
+use crate::rpc::{Asset, StellarRpcClient, Trade};
+use crate::services::aggregation::{AggregationService, MAX_CANDLES};
+use crate::services::price_feed::PriceFeedClient;
+use crate::services::trade_pricing::Quote;
+use crate::validation::{validate_stellar_address, StellarAddressKind};
+
+/// Number of candles returned when `from` is omitted
+const DEFAULT_CANDLE_COUNT: i32 = 100;
+
+/// Number of trades returned when `limit` is omitted
+const DEFAULT_TRADE_LIMIT: u32 = 20;
+
+#[derive(Debug, Deserialize)]
+pub struct CandleParams {
+    /// Base asset, `native` or `CODE:ISSUER`
+    pub base: String,
+    /// Counter asset, `native` or `CODE:ISSUER`
+    pub counter: String,
+    /// One of `1m`, `5m`, `1h`, `1d` (default `1h`)
+    pub interval: Option<String>,
+    /// Range start (RFC 3339); defaults to 100 intervals before `to`, or the
+    /// start of the trades retention window if that is later
+    pub from: Option<DateTime<Utc>>,
+    /// Exclusive range end (RFC 3339); defaults to now
+    pub to: Option<DateTime<Utc>>,
+    /// Express prices and volume in this asset instead of the counter asset:
+    /// `USD` or an asset key, valued through the price feed
+    pub quote: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PairTradesParams {
+    /// Base asset, `native` or `CODE:ISSUER`
+    pub base: String,
+    /// Counter asset, `native` or `CODE:ISSUER`
+    pub counter: String,
+    /// Page size (default 20, at most `MAX_PAGE_SIZE`)
+    pub limit: Option<u32>,
+    /// Horizon paging token of the last trade already seen
+    pub cursor: Option<String>,
+}
+
+/// `trade_retention_days` is how long raw trades are kept (see
+/// [`crate::jobs::RetentionConfig::retention_days`]); candles are built from
+/// them, so older ranges are rejected.
+pub fn routes(
+    service: Arc<AggregationService>,
+    rpc_client: Arc<StellarRpcClient>,
+    price_feed: Arc<PriceFeedClient>,
+    trade_retention_days: Option<u32>,
+) -> Router {
+    Router::new()
+        .route("/candles", get(get_candles))
+        .with_state((service, price_feed, trade_retention_days))
+        .merge(
+            Router::new()
+                .route("/", get(get_pair_trades))
+                .with_state(rpc_client),
+        )
+}
+
+/// GET /api/trades?base=...&counter=... - recent trades for a trading pair
+async fn get_pair_trades(
+    State(rpc_client): State<Arc<StellarRpcClient>>,
+    Query(params): Query<PairTradesParams>,
+) -> ApiResult<Json<Vec<Trade>>> {
+    let parse = |field: &str, id: &str| {
+        Asset::parse(id).ok_or_else(|| {
+            ApiError::bad_request(
+                "INVALID_ASSET",
+                format!("{field} must be `native` or `CODE:ISSUER`, got '{id}'"),
+            )
+        })
+    };
+    let base = parse("base", &params.base)?;
+    let counter = parse("counter", &params.counter)?;
+    for issuer in [&base.asset_issuer, &counter.asset_issuer]
+        .into_iter()
+        .flatten()
+    {
+        validate_stellar_address(issuer, StellarAddressKind::Issuer)?;
+    }
+    let limit = params.limit.unwrap_or(DEFAULT_TRADE_LIMIT);
+    validate_limit(i64::from(limit))?;
+
+    let trades = rpc_client
+        .fetch_trades_for_pair(&base, &counter, limit, params.cursor.as_deref())
+        .await?;
+    Ok(Json(trades))
+}
+
+/// GET /api/trades/candles - OHLCV candles for a trading pair
+///
+/// Buckets with no trades are included with `null` prices and zero volume.
+/// Candles are built from raw trades, so a range starting before the trades
+/// retention window is rejected with `RANGE_BEYOND_RETENTION`.
+async fn get_candles(
+    State((service, price_feed, trade_retention_days)): State<(
+        Arc<AggregationService>,
+        Arc<PriceFeedClient>,
+        Option<u32>,
+    )>,
+    Query(params): Query<CandleParams>,
+) -> ApiResult<Json<Vec<Candle>>> {
+    let interval = params
+        .interval
+        .as_deref()
+        .map_or(Ok(CandleInterval::OneHour), str::parse)
+        .map_err(|e: String| ApiError::bad_request("INVALID_INTERVAL", e))?;
+    let to = params.to.unwrap_or_else(Utc::now);
+    let oldest = trade_retention_days.map(|days| Utc::now() - Duration::days(i64::from(days)));
+    let from = params.from.unwrap_or_else(|| {
+        let from = to - interval.duration() * DEFAULT_CANDLE_COUNT;
+        oldest.map_or(from, |oldest| from.max(oldest))
+    });
+
+    if let (Some(days), Some(oldest)) = (trade_retention_days, oldest) {
+        if from < oldest || to <= oldest {
+            return Err(ApiError::bad_request(
+                "RANGE_BEYOND_RETENTION",
+                format!(
+                    "Trades are kept for {days} days; request candles from {} onwards",
+                    oldest.to_rfc3339_opts(SecondsFormat::Secs, true)
+                ),
+            ));
+        }
+    }
+
+    if from >= to {
+        return Err(ApiError::bad_request(
+            "INVALID_RANGE",
+            "from must be earlier than to",
+        ));
+    }
+    if (to - from).num_seconds() / interval.seconds() > MAX_CANDLES {
+        return Err(ApiError::bad_request(
+            "RANGE_TOO_LARGE",
+            format!("At most {MAX_CANDLES} candles can be requested at once"),
+        ));
+    }
+
+    let quote = match params.quote.as_deref() {
+        Some(quote_asset) => {
+            let assets =
+                [params.base.as_str(), params.counter.as_str(), quote_asset].map(String::from);
+            let quote = Quote::from_usd_prices(quote_asset, price_feed.get_prices(&assets).await);
+            if !quote.has_path(&params.base, &params.counter) {
+                return Err(ApiError::bad_request(
+                    "NO_PRICE_PATH",
+                    format!(
+                        "No price path from {}/{} to {quote_asset}",
+                        params.base, params.counter
+                    ),
+                ));
+            }
+            Some(quote)
+        }
+        None => None,
+    };
+
+    let candles = service
+        .aggregate_trades_to_candles(
+            &params.base,
+            &params.counter,
+            interval,
+            from,
+            to,
+            quote.as_ref(),
+        )
+        .await?;
+    Ok(Json(candles))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use crate::services::aggregation::AggregationConfig;
+    use crate::services::price_feed::{default_asset_mapping, PriceFeedConfig};
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    const USDC: &str = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZAA";
+
+    async fn get_json(app: &Router, uri: &str) -> (StatusCode, serde_json::Value) {
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_candles_before_trade_retention_are_rejected() {
+        let pool = sqlx::SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::query(include_str!("../../migrations/039_create_trades.sql"))
+            .execute(&pool)
+            .await
+            .unwrap();
+        let app = routes(
+            Arc::new(AggregationService::new(
+                Arc::new(Database::new(pool)),
+                AggregationConfig::default(),
+            )),
+            Arc::new(StellarRpcClient::new_with_defaults(true)),
+            Arc::new(PriceFeedClient::new(
+                PriceFeedConfig::default(),
+                default_asset_mapping(),
+            )),
+            Some(90),
+        );
+
+        let from = (Utc::now() - Duration::days(100)).to_rfc3339_opts(SecondsFormat::Secs, true);
+        let (status, body) = get_json(
+            &app,
+            &format!("/candles?base=native&counter={USDC}&interval=1d&from={from}"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["code"], "RANGE_BEYOND_RETENTION");
+
+        // The default 100-day range is cut to the retention window instead
+        let (status, body) = get_json(
+            &app,
+            &format!("/candles?base=native&counter={USDC}&interval=1d"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.as_array().unwrap().len() <= 91);
+    }
+}
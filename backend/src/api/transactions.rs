@@ -10,11 +10,11 @@ use uuid::Uuid;
 
 use crate::{
     models::{PendingTransaction, PendingTransactionWithSignatures, TransactionResult},
+    pagination::validate_limit,
     state::AppState,
 };
 
 const DEFAULT_PAGE_LIMIT: i64 = 20;
-const MAX_PAGE_LIMIT: i64 = 100;
 
 // Request/Response DTOs
 #[derive(Debug, Deserialize)]
@@ -36,7 +36,7 @@ pub struct ListTransactionsQuery {
     pub account: Option<String>,
     /// Opaque cursor returned by a previous page response.
     pub cursor: Option<String>,
-    /// Maximum number of results (1–100, default 20).
+    /// Maximum number of results (1–MAX_PAGE_SIZE, default 20).
     pub limit: Option<i64>,
 }
 
@@ -97,11 +97,11 @@ pub fn routes() -> Router<AppState> {
     params(
         ("account" = Option<String>, Query, description = "Filter by source account"),
         ("cursor" = Option<String>, Query, description = "Opaque pagination cursor from a previous response"),
-        ("limit" = Option<i64>, Query, description = "Maximum results (1-100, default 20)")
+        ("limit" = Option<i64>, Query, description = "Maximum results (1-MAX_PAGE_SIZE, default 20)")
     ),
     responses(
         (status = 200, description = "Paginated list of pending transactions", body = ListTransactionsResponse),
-        (status = 400, description = "Cursor/filter mismatch or invalid cursor"),
+        (status = 400, description = "Cursor/filter mismatch, invalid cursor or limit exceeds MAX_PAGE_SIZE"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Transactions"
@@ -110,10 +110,8 @@ pub async fn list_transactions(
     State(state): State<AppState>,
     Query(query): Query<ListTransactionsQuery>,
 ) -> Result<Json<ListTransactionsResponse>, (StatusCode, String)> {
-    let limit = query
-        .limit
-        .unwrap_or(DEFAULT_PAGE_LIMIT)
-        .clamp(1, MAX_PAGE_LIMIT);
+    let limit = validate_limit(query.limit.unwrap_or(DEFAULT_PAGE_LIMIT))
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
 
     // Decode cursor and validate that the embedded filter matches this request.
     let after_id: Option<String> = match query.cursor.as_deref() {
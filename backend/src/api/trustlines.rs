@@ -1,38 +1,16 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::{IntoResponse, Response},
     routing::get,
     Json, Router,
 };
 use serde::Deserialize;
 use std::sync::Arc;
 
+use crate::error::ApiResult;
 use crate::models::{TrustlineMetrics, TrustlineSnapshot, TrustlineStat};
+use crate::pagination::validate_limit;
 use crate::services::trustline_analyzer::TrustlineAnalyzer;
 
-pub type ApiResult<T> = Result<T, ApiError>;
-
-#[derive(Debug)]
-pub enum ApiError {
-    Internal(String),
-}
-
-impl IntoResponse for ApiError {
-    fn into_response(self) -> Response {
-        let (status, message) = match self {
-            Self::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-        };
-        (status, message).into_response()
-    }
-}
-
-impl From<anyhow::Error> for ApiError {
-    fn from(err: anyhow::Error) -> Self {
-        Self::Internal(err.to_string())
-    }
-}
-
 #[derive(Deserialize)]
 pub struct RankingsParams {
     #[serde(default = "default_limit")]
@@ -90,10 +68,11 @@ async fn get_trustline_metrics(
     get,
     path = "/api/trustlines/rankings",
     params(
-        ("limit" = Option<i64>, Query, description = "Maximum number of rankings to return (1-200, default 50)")
+        ("limit" = Option<i64>, Query, description = "Maximum number of rankings to return (1-MAX_PAGE_SIZE, default 50)")
     ),
     responses(
         (status = 200, description = "Trustline rankings", body = Vec<TrustlineStat>),
+        (status = 400, description = "limit exceeds MAX_PAGE_SIZE"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Trustlines"
@@ -102,7 +81,7 @@ async fn get_trustline_rankings(
     State(analyzer): State<Arc<TrustlineAnalyzer>>,
     Query(params): Query<RankingsParams>,
 ) -> ApiResult<Json<Vec<TrustlineStat>>> {
-    let limit = params.limit.clamp(1, 200);
+    let limit = validate_limit(params.limit)?;
     let rankings = analyzer
         .get_trustline_rankings(limit)
         .await
@@ -117,10 +96,11 @@ async fn get_trustline_rankings(
     params(
         ("asset_code" = String, Path, description = "Asset code (e.g., 'USDC')"),
         ("asset_issuer" = String, Path, description = "Asset issuer account ID"),
-        ("limit" = Option<i64>, Query, description = "Maximum number of history entries to return (1-365, default 30)")
+        ("limit" = Option<i64>, Query, description = "Maximum number of history entries to return (1-MAX_PAGE_SIZE, default 30)")
     ),
     responses(
         (status = 200, description = "Trustline history for asset", body = Vec<TrustlineSnapshot>),
+        (status = 400, description = "limit exceeds MAX_PAGE_SIZE"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Trustlines"
@@ -130,7 +110,7 @@ async fn get_trustline_history(
     Path((asset_code, asset_issuer)): Path<(String, String)>,
     Query(params): Query<HistoryParams>,
 ) -> ApiResult<Json<Vec<TrustlineSnapshot>>> {
-    let limit = params.limit.clamp(1, 365);
+    let limit = validate_limit(params.limit)?;
     let history = analyzer
         .get_asset_history(&asset_code, &asset_issuer, limit)
         .await
@@ -1,15 +1,17 @@
 use crate::api::{
-    account_merges, anchors, cache_stats, corridors, cost_calculator, fee_bump, liquidity_pools,
-    metrics, oauth, price_feed as price_feed_api, rpc, sep24_proxy, webhooks,
+    account_merges, anchors, assets, cache_stats, corridors, cost_calculator, fee_bump, liquidity_pools,
+    metrics, oauth, price_feed as price_feed_api, rpc, sep24_proxy, summary, trades, webhooks,
 };
 use crate::auth_middleware::auth_middleware;
 use crate::cache::CacheManager;
 use crate::database::Database;
 use crate::deprecation_middleware::{default_deprecation_map, deprecation_middleware};
 use crate::handlers::job_monitoring;
+use crate::jobs::RetentionConfig;
 use crate::rate_limit::{api_key_rate_limit_middleware, rate_limit_middleware, RateLimiter};
 use crate::rpc::StellarRpcClient;
 use crate::services::account_merge_detector::AccountMergeDetector;
+use crate::services::aggregation::{AggregationConfig, AggregationService};
 use crate::services::fee_bump_tracker::FeeBumpTrackerService;
 use crate::services::liquidity_pool_analyzer::LiquidityPoolAnalyzer;
 use crate::services::price_feed::PriceFeedClient;
@@ -22,7 +24,6 @@ use axum::{
 use serde::Serialize;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tower_http::cors::CorsLayer;
 
 /// Job monitoring routes
 fn job_monitoring_routes(pool: sqlx::SqlitePool) -> Router {
@@ -78,7 +79,6 @@ pub fn routes(
     lp_analyzer: Arc<LiquidityPoolAnalyzer>,
     price_feed: Arc<PriceFeedClient>,
     rate_limiter: Arc<RateLimiter>,
-    cors: CorsLayer,
     pool: sqlx::SqlitePool,
     cache: Arc<CacheManager>,
 ) -> Router {
@@ -103,6 +103,7 @@ pub fn routes(
         )
         .route("/anchors/{id}/assets", get(anchors::get_anchor_assets))
         .route("/analytics/muxed", get(anchors::get_muxed_analytics))
+        .route("/corridors/heatmap", get(corridors::get_corridor_heatmap))
         .with_state(app_state.clone());
 
     // 2b. Export routes (#1784) — handlers already existed but were never
@@ -143,6 +144,7 @@ pub fn routes(
     // 4. RPC routes
     let rpc_routes = Router::new()
         .route("/rpc/health", get(rpc::rpc_health_check))
+        .route("/health/rpc", get(rpc::rpc_breaker_health))
         .route("/rpc/ledger/latest", get(rpc::get_latest_ledger))
         .route("/rpc/payments", get(rpc::get_payments))
         .route(
@@ -151,7 +153,7 @@ pub fn routes(
         )
         .route("/rpc/trades", get(rpc::get_trades))
         .route("/rpc/orderbook", get(rpc::get_order_book))
-        .with_state(rpc_client);
+        .with_state(rpc_client.clone());
 
     // 5. Special service routes
     let service_routes = Router::new()
@@ -161,6 +163,19 @@ pub fn routes(
             account_merges::routes(account_merge_detector),
         )
         .nest("/liquidity-pools", liquidity_pools::routes(lp_analyzer))
+        .nest("/assets", assets::routes(pool.clone()))
+        .nest(
+            "/trades",
+            trades::routes(
+                Arc::new(AggregationService::new(
+                    app_state.db.clone(),
+                    AggregationConfig::default(),
+                )),
+                rpc_client,
+                price_feed.clone(),
+                RetentionConfig::from_env().retention_days("trades"),
+            ),
+        )
         .nest("/prices", price_feed_api::routes(price_feed.clone()))
         .nest("/cost-calculator", cost_calculator::routes(price_feed))
         .nest("/cache/stats", cache_stats::routes(cache.clone()))
@@ -181,19 +196,18 @@ pub fn routes(
         .merge(rpc_routes)
         .merge(service_routes)
         .merge(oauth_routes);
-
     // Combine all routes
     Router::new()
         .nest("/api/v1", v1_router.clone())
         .nest("/api/v2", v2_routes())
         .route("/api/version", get(get_api_version))
+        .nest("/api/summary", summary::routes(app_state))
         // Preserve existing unversioned endpoints for backward compatibility.
         .merge(v1_router)
         // SEP-24 proxy routes are mounted separately because the callback
         // endpoint manages its own per-origin CORS headers dynamically
         // (the anchor home_domain is not known at startup).
         .merge(sep24_proxy::routes())
-        .layer(cors)
         .layer(middleware::from_fn(
             crate::request_id::request_id_middleware,
         ))
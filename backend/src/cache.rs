@@ -141,6 +141,11 @@ impl CacheManager {
         self.redis_connection.clone()
     }
 
+    /// Whether entries go to Redis rather than the in-memory fallback store
+    pub async fn is_redis_connected(&self) -> bool {
+        self.redis_connection.read().await.is_some()
+    }
+
     /// Health check for the cache dependency
     pub async fn health_check(&self) -> anyhow::Result<()> {
         self.ping().await
@@ -225,6 +230,26 @@ impl CacheManager {
         key: &str,
         value: &T,
         ttl_seconds: usize,
+    ) -> anyhow::Result<()> {
+        self.write(key, value, ttl_seconds, true).await
+    }
+
+    /// Set value in cache with TTL, overwriting any existing entry
+    pub async fn replace<T: Serialize>(
+        &self,
+        key: &str,
+        value: &T,
+        ttl_seconds: usize,
+    ) -> anyhow::Result<()> {
+        self.write(key, value, ttl_seconds, false).await
+    }
+
+    async fn write<T: Serialize>(
+        &self,
+        key: &str,
+        value: &T,
+        ttl_seconds: usize,
+        only_if_absent: bool,
     ) -> anyhow::Result<()> {
         if self.redis_connection.read().await.is_none() {
             match serde_json::to_string(value) {
@@ -250,12 +275,14 @@ impl CacheManager {
             let mut conn = conn.clone();
             match serde_json::to_string(value) {
                 Ok(serialized) => {
+                    let mut cmd = redis::cmd("SET");
+                    cmd.arg(key).arg(&serialized);
                     // SET NX prevents concurrent miss-then-write races: the first writer wins
                     // and subsequent concurrent writers silently skip rather than overwriting.
-                    match redis::cmd("SET")
-                        .arg(key)
-                        .arg(&serialized)
-                        .arg("NX")
+                    if only_if_absent {
+                        cmd.arg("NX");
+                    }
+                    match cmd
                         .arg("PX")
                         .arg(ttl_seconds * 1000)
                         .query_async::<_, Option<String>>(&mut conn)
@@ -266,7 +293,7 @@ impl CacheManager {
                             Ok(())
                         }
                         Err(e) => {
-                            tracing::warn!("Redis SET NX error for {}: {}", key, e);
+                            tracing::warn!("Redis SET error for {}: {}", key, e);
                             Ok(())
                         }
                     }
@@ -303,6 +330,9 @@ impl CacheManager {
                 }
             }
         } else {
+            if self.in_memory_store.write().await.remove(key).is_some() {
+                self.invalidations.fetch_add(1, Ordering::Relaxed);
+            }
             Ok(())
         }
     }
@@ -360,7 +390,13 @@ impl CacheManager {
 
             Ok(deleted_count)
         } else {
-            Ok(0)
+            let mut store = self.in_memory_store.write().await;
+            let before = store.len();
+            store.retain(|key, _| !matches_pattern(pattern, key));
+            let deleted_count = before - store.len();
+            self.invalidations
+                .fetch_add(deleted_count as u64, Ordering::Relaxed);
+            Ok(deleted_count)
         }
     }
 
@@ -434,6 +470,14 @@ impl CacheManager {
     }
 }
 
+/// Glob match for the in-memory fallback; only the trailing `*` used by
+/// [`keys`] patterns is supported
+fn matches_pattern(pattern: &str, key: &str) -> bool {
+    pattern
+        .strip_suffix('*')
+        .map_or(pattern == key, |prefix| key.starts_with(prefix))
+}
+
 /// Cache key builders for consistency
 pub mod keys {
     #[must_use]
@@ -481,6 +525,11 @@ pub mod keys {
         "analytics:dashboard".to_string()
     }
 
+    #[must_use]
+    pub fn daily_summary(date: &str) -> String {
+        format!("dashboard:daily_summary:{date}")
+    }
+
     /// Pattern for invalidating all anchor-related caches
     #[must_use]
     pub fn anchor_pattern() -> String {
@@ -493,6 +542,12 @@ pub mod keys {
         "corridor:*".to_string()
     }
 
+    /// Pattern for invalidating every corridor list page and filter variant
+    #[must_use]
+    pub fn corridor_list_pattern() -> String {
+        "corridor:list:*".to_string()
+    }
+
     /// Pattern for invalidating all dashboard caches
     #[must_use]
     pub fn dashboard_pattern() -> String {
@@ -10,13 +10,14 @@ use axum::{
     response::Response,
 };
 use chrono::{DateTime, Utc};
-use serde::{de::DeserializeOwned, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::future::Future;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::OnceLock;
+use std::time::{Duration, Instant};
 
 #[derive(Clone)]
 struct CacheEntry {
@@ -178,6 +179,84 @@ pub fn cached_json_response<T: Serialize>(
     Ok(response)
 }
 
+/// `?refresh=true` on a cached GET handler: skip the cache read, recompute
+/// and repopulate the cache.
+///
+/// Forced refreshes of one key are limited to one per
+/// `CACHE_REFRESH_MIN_INTERVAL_SECONDS` (default 30); a refresh inside that
+/// window is served from the cache as usual, so the flag can't be used to
+/// defeat caching.
+#[derive(Debug, Clone, Copy, Default, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RefreshQuery {
+    /// Bypass the cache and recompute this response
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+const DEFAULT_REFRESH_MIN_INTERVAL_SECONDS: u64 = 30;
+
+fn refresh_min_interval() -> Duration {
+    static INTERVAL: OnceLock<Duration> = OnceLock::new();
+    *INTERVAL.get_or_init(|| {
+        Duration::from_secs(
+            std::env::var("CACHE_REFRESH_MIN_INTERVAL_SECONDS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_REFRESH_MIN_INTERVAL_SECONDS),
+        )
+    })
+}
+
+static LAST_REFRESH: OnceLock<Mutex<HashMap<String, Instant>>> = OnceLock::new();
+
+/// Whether a forced refresh of `key` may run now, claiming the slot if so
+fn claim_refresh(key: &str) -> bool {
+    let Ok(mut last_refresh) = LAST_REFRESH
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+    else {
+        return false;
+    };
+    claim_refresh_at(
+        &mut last_refresh,
+        key,
+        Instant::now(),
+        refresh_min_interval(),
+    )
+}
+
+/// Claim logic behind [`claim_refresh`]. Entries whose window has passed no
+/// longer throttle anything and are dropped on each successful claim, so the
+/// map only holds keys refreshed within the last `interval`.
+fn claim_refresh_at(
+    last_refresh: &mut HashMap<String, Instant>,
+    key: &str,
+    now: Instant,
+    interval: Duration,
+) -> bool {
+    if matches!(last_refresh.get(key), Some(last) if now.duration_since(*last) < interval) {
+        return false;
+    }
+    last_refresh.retain(|_, last| now.duration_since(*last) < interval);
+    last_refresh.insert(key.to_string(), now);
+    true
+}
+
+/// Whether to skip the cache read for `key`
+fn bypass_cache(key: &str, refresh: bool) -> bool {
+    if !refresh {
+        return false;
+    }
+    if claim_refresh(key) {
+        tracing::debug!("Forced refresh for key: {}", key);
+        true
+    } else {
+        tracing::debug!("Forced refresh for key {} throttled, reading cache", key);
+        false
+    }
+}
+
 /// Executes a query using a cache-aside strategy.
 ///
 /// Checks the cache for `key` first. On a hit the cached value is returned
@@ -204,23 +283,183 @@ where
     F: FnOnce() -> Fut,
     Fut: Future<Output = anyhow::Result<T>>,
 {
-    if let Some(cached) = cache.get::<T>(key).await? {
-        tracing::debug!("Cache hit for key: {}", key);
-        return Ok(cached);
-    }
+    cached_query_with_refresh(cache, key, ttl, false, query_fn).await
+}
 
-    tracing::debug!("Cache miss for key: {}", key);
+/// Like [`cached_query`], but with `refresh` set the cached value is ignored
+/// and overwritten with a fresh result (see [`RefreshQuery`]).
+pub async fn cached_query_with_refresh<T, F, Fut>(
+    cache: &Arc<CacheManager>,
+    key: &str,
+    ttl: usize,
+    refresh: bool,
+    query_fn: F,
+) -> anyhow::Result<T>
+where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = anyhow::Result<T>>,
+{
+    let refresh = bypass_cache(key, refresh);
+    if !refresh {
+        if let Some(cached) = cache.get::<T>(key).await? {
+            tracing::debug!("Cache hit for key: {}", key);
+            return Ok(cached);
+        }
+        tracing::debug!("Cache miss for key: {}", key);
+    }
 
     let result = query_fn().await?;
 
     // Cache write is best-effort so reads are never blocked by cache backend issues.
-    if let Err(error) = cache.set(key, &result, ttl).await {
+    let written = if refresh {
+        cache.replace(key, &result, ttl).await
+    } else {
+        cache.set(key, &result, ttl).await
+    };
+    if let Err(error) = written {
         tracing::warn!("Failed to cache result for key {}: {}", key, error);
     }
 
     Ok(result)
 }
 
+/// Set to `true` on responses served from a stale copy after a failed fetch
+pub const DATA_STALE_HEADER: &str = "x-data-stale";
+/// Age in seconds of a stale copy served under [`DATA_STALE_HEADER`]
+pub const DATA_AGE_HEADER: &str = "x-data-age";
+
+/// How long the fallback copy kept by [`cached_query_or_stale`] survives
+const STALE_RETENTION_SECONDS: usize = 24 * 60 * 60;
+
+#[derive(Serialize, Deserialize)]
+struct StaleEntry<T> {
+    stored_at: DateTime<Utc>,
+    value: T,
+}
+
+fn stale_key(key: &str) -> String {
+    format!("stale:{key}")
+}
+
+/// Value returned by [`cached_query_or_stale`]
+#[derive(Debug)]
+pub struct MaybeStale<T> {
+    pub value: T,
+    /// Age in seconds of the fallback copy when the fresh fetch failed
+    pub stale_age_seconds: Option<i64>,
+}
+
+impl<T> MaybeStale<T> {
+    /// Flag `response` as stale with [`DATA_STALE_HEADER`] and [`DATA_AGE_HEADER`].
+    ///
+    /// Stale responses are also marked `no-cache` so clients and CDNs don't
+    /// keep serving outage data once the upstream recovers.
+    pub fn mark_response(&self, response: &mut Response) {
+        let Some(age) = self.stale_age_seconds else {
+            return;
+        };
+        let headers = response.headers_mut();
+        headers.insert(DATA_STALE_HEADER, HeaderValue::from_static("true"));
+        headers.insert(DATA_AGE_HEADER, HeaderValue::from(age));
+        headers.insert(CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+    }
+}
+
+/// Like [`cached_query`], but falls back to the last good value when
+/// `query_fn` fails.
+///
+/// Every successful fetch is also kept under a `stale:` key for
+/// `STALE_RETENTION_SECONDS`, outliving the fresh entry's `ttl`. When a fetch
+/// fails and such a copy exists it is returned with its age instead of the
+/// error, so endpoints keep serving data through upstream outages. Without a
+/// copy the error is returned as before.
+pub async fn cached_query_or_stale<T, F, Fut>(
+    cache: &Arc<CacheManager>,
+    key: &str,
+    ttl: usize,
+    query_fn: F,
+) -> anyhow::Result<MaybeStale<T>>
+where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = anyhow::Result<T>>,
+{
+    cached_query_or_stale_with_refresh(cache, key, ttl, false, query_fn).await
+}
+
+/// Like [`cached_query_or_stale`], but with `refresh` set the fresh cached
+/// value is ignored and overwritten (see [`RefreshQuery`]). A failed refresh
+/// still falls back to the stale copy.
+pub async fn cached_query_or_stale_with_refresh<T, F, Fut>(
+    cache: &Arc<CacheManager>,
+    key: &str,
+    ttl: usize,
+    refresh: bool,
+    query_fn: F,
+) -> anyhow::Result<MaybeStale<T>>
+where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = anyhow::Result<T>>,
+{
+    let refresh = bypass_cache(key, refresh);
+    if !refresh {
+        if let Some(cached) = cache.get::<T>(key).await? {
+            tracing::debug!("Cache hit for key: {}", key);
+            return Ok(MaybeStale {
+                value: cached,
+                stale_age_seconds: None,
+            });
+        }
+        tracing::debug!("Cache miss for key: {}", key);
+    }
+
+    let result = match query_fn().await {
+        Ok(result) => result,
+        Err(error) => {
+            let Some(stale) = cache.get::<StaleEntry<T>>(&stale_key(key)).await? else {
+                return Err(error);
+            };
+            let age = (Utc::now() - stale.stored_at).num_seconds().max(0);
+            tracing::warn!(
+                "Fetch for key {} failed, serving {}s old stale copy: {}",
+                key,
+                age,
+                error
+            );
+            return Ok(MaybeStale {
+                value: stale.value,
+                stale_age_seconds: Some(age),
+            });
+        }
+    };
+
+    let written = if refresh {
+        cache.replace(key, &result, ttl).await
+    } else {
+        cache.set(key, &result, ttl).await
+    };
+    if let Err(error) = written {
+        tracing::warn!("Failed to cache result for key {}: {}", key, error);
+    }
+    let stale = StaleEntry {
+        stored_at: Utc::now(),
+        value: &result,
+    };
+    if let Err(error) = cache
+        .replace(&stale_key(key), &stale, STALE_RETENTION_SECONDS)
+        .await
+    {
+        tracing::warn!("Failed to keep stale copy for key {}: {}", key, error);
+    }
+
+    Ok(MaybeStale {
+        value: result,
+        stale_age_seconds: None,
+    })
+}
+
 /// Executes a query with a cache key derived from a prefix and serialized params.
 ///
 /// Combines [`build_param_cache_key`] with [`cached_query`]: the cache key is
@@ -305,6 +544,111 @@ mod tests {
         assert_eq!(key_a, key_b);
         assert!(key_a.starts_with("corridor:list:"));
     }
+
+    #[tokio::test]
+    async fn test_refresh_recomputes_while_normal_reads_hit_cache() {
+        let cache = Arc::new(CacheManager::new_in_memory_for_tests(
+            crate::cache::CacheConfig::default(),
+        ));
+        let key = "dashboard:refresh:test";
+        let fetch = |limit: i64| async move { Ok(TestParams { limit, offset: 0 }) };
+
+        let first = cached_query(&cache, key, 60, || fetch(1)).await.unwrap();
+        assert_eq!(first.limit, 1);
+        let cached = cached_query(&cache, key, 60, || fetch(2)).await.unwrap();
+        assert_eq!(cached.limit, 1);
+
+        let refreshed = cached_query_with_refresh(&cache, key, 60, true, || fetch(3))
+            .await
+            .unwrap();
+        assert_eq!(refreshed.limit, 3);
+        let cached = cached_query(&cache, key, 60, || fetch(4)).await.unwrap();
+        assert_eq!(cached.limit, 3);
+
+        // A second refresh inside the cooldown is served from the cache
+        let throttled = cached_query_with_refresh(&cache, key, 60, true, || fetch(5))
+            .await
+            .unwrap();
+        assert_eq!(throttled.limit, 3);
+    }
+
+    #[test]
+    fn test_refresh_claims_drop_expired_keys() {
+        let interval = Duration::from_secs(30);
+        let start = Instant::now();
+        let mut last_refresh = HashMap::new();
+
+        assert!(claim_refresh_at(&mut last_refresh, "a", start, interval));
+        assert!(claim_refresh_at(&mut last_refresh, "b", start, interval));
+        assert!(!claim_refresh_at(&mut last_refresh, "a", start, interval));
+        assert_eq!(last_refresh.len(), 2);
+
+        let later = start + interval;
+        assert!(claim_refresh_at(&mut last_refresh, "c", later, interval));
+        assert_eq!(last_refresh.keys().collect::<Vec<_>>(), ["c"]);
+    }
+
+    #[tokio::test]
+    async fn test_failed_fetch_serves_stale_copy_with_flag() {
+        let cache = Arc::new(CacheManager::new_in_memory_for_tests(
+            crate::cache::CacheConfig::default(),
+        ));
+        let params = || TestParams {
+            limit: 10,
+            offset: 0,
+        };
+
+        let fresh =
+            cached_query_or_stale(&cache, "corridor:list:test", 60, || async { Ok(params()) })
+                .await
+                .unwrap();
+        assert_eq!(fresh.value, params());
+        assert_eq!(fresh.stale_age_seconds, None);
+
+        // Expire the fresh entry, then fail the upstream fetch
+        cache.delete("corridor:list:test").await.unwrap();
+        let stale =
+            cached_query_or_stale::<TestParams, _, _>(&cache, "corridor:list:test", 60, || async {
+                Err(anyhow::anyhow!("circuit breaker open"))
+            })
+            .await
+            .unwrap();
+        assert_eq!(stale.value, params());
+        assert!(stale.stale_age_seconds.is_some());
+
+        let mut response = Response::new(Body::empty());
+        stale.mark_response(&mut response);
+        assert_eq!(response.headers()[DATA_STALE_HEADER], "true");
+        assert!(response.headers()[DATA_AGE_HEADER]
+            .to_str()
+            .unwrap()
+            .parse::<i64>()
+            .is_ok());
+        assert_eq!(response.headers()[CACHE_CONTROL], "no-cache");
+    }
+
+    #[tokio::test]
+    async fn test_failed_fetch_without_stale_copy_returns_error() {
+        let cache = Arc::new(CacheManager::new_in_memory_for_tests(
+            crate::cache::CacheConfig::default(),
+        ));
+        let result = cached_query_or_stale::<(i64, i64), _, _>(
+            &cache,
+            "corridor:list:missing",
+            60,
+            || async { Err(anyhow::anyhow!("circuit breaker open")) },
+        )
+        .await;
+        assert!(result.is_err());
+
+        let mut response = Response::new(Body::empty());
+        MaybeStale {
+            value: (),
+            stale_age_seconds: None,
+        }
+        .mark_response(&mut response);
+        assert!(response.headers().get(DATA_STALE_HEADER).is_none());
+    }
 }
 
 #[cfg(test)]
@@ -62,6 +62,33 @@ impl CacheInvalidationService {
         Ok(())
     }
 
+    /// Invalidate caches for corridors touched by newly ingested payments.
+    ///
+    /// Only the touched corridors' detail entries and the corridor list views
+    /// (which aggregate every corridor) are dropped; other corridors keep
+    /// their cached detail until their TTL expires.
+    pub async fn invalidate_ingested_corridors(
+        &self,
+        corridor_keys: &[String],
+    ) -> anyhow::Result<()> {
+        if corridor_keys.is_empty() {
+            return Ok(());
+        }
+        tracing::debug!(
+            "Invalidating cache for {} ingested corridors",
+            corridor_keys.len()
+        );
+        for corridor_key in corridor_keys {
+            self.cache
+                .delete(&keys::corridor_detail(corridor_key))
+                .await?;
+        }
+        self.cache
+            .delete_pattern(&keys::corridor_list_pattern())
+            .await?;
+        Ok(())
+    }
+
     /// Invalidate dashboard caches
     pub async fn invalidate_dashboard(&self) -> anyhow::Result<()> {
         tracing::info!("Invalidating dashboard caches");
@@ -135,6 +162,26 @@ mod tests {
         assert!(svc.invalidate_corridor("USD-EUR").await.is_ok());
     }
 
+    #[tokio::test]
+    async fn invalidate_ingested_corridors_is_scoped_to_touched_corridors() {
+        let cache = Arc::new(CacheManager::new_in_memory_for_tests(CacheConfig::default()));
+        let svc = CacheInvalidationService::new(Arc::clone(&cache));
+        let touched = keys::corridor_detail("USDC:GA->XLM:native");
+        let untouched = keys::corridor_detail("EURC:GB->XLM:native");
+        let list = keys::corridor_list(50, 0, "");
+        for key in [&touched, &untouched, &list] {
+            cache.set(key, &1, 60).await.unwrap();
+        }
+
+        svc.invalidate_ingested_corridors(&["USDC:GA->XLM:native".to_string()])
+            .await
+            .unwrap();
+
+        assert_eq!(cache.get::<i32>(&touched).await.unwrap(), None);
+        assert_eq!(cache.get::<i32>(&list).await.unwrap(), None);
+        assert_eq!(cache.get::<i32>(&untouched).await.unwrap(), Some(1));
+    }
+
     #[tokio::test]
     async fn invalidate_dashboard_succeeds() {
         let svc = make_service();
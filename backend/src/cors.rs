@@ -0,0 +1,330 @@
+//! Cross-origin request policy for the API.
+//!
+//! Origins come from `CORS_ALLOWED_ORIGINS`. `*` mirrors any request origin,
+//! without credentials, and is only honoured outside production; a production
+//! deployment with `*` allows no cross-origin requests at all. Preflights from
+//! origins that aren't allowed are answered with `403 CORS_ORIGIN_NOT_ALLOWED`
+//! rather than a header-less `200`, so misconfigured frontends fail loudly.
+
+use std::time::Duration;
+
+use axum::{
+    extract::{Request, State},
+    http::{
+        header::{ACCESS_CONTROL_REQUEST_METHOD, AUTHORIZATION, CONTENT_TYPE, ORIGIN},
+        HeaderName, HeaderValue, Method,
+    },
+    middleware::{self, Next},
+    response::Response,
+    Router,
+};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+use crate::error::{error_response_with_request_id, ApiError};
+
+/// Origin allowed when `CORS_ALLOWED_ORIGINS` is unset
+pub const DEFAULT_ALLOWED_ORIGIN: &str = "http://localhost:3000";
+
+/// How long browsers may cache a preflight when `CORS_MAX_AGE_SECONDS` is unset
+const DEFAULT_MAX_AGE_SECONDS: u64 = 3600;
+
+/// Which cross-origin requests the API accepts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorsConfig {
+    pub allowed_origins: Vec<HeaderValue>,
+    /// Mirror any origin back (`CORS_ALLOWED_ORIGINS=*`); never set in production
+    pub allow_any_origin: bool,
+    pub allowed_methods: Vec<Method>,
+    pub allowed_headers: Vec<HeaderName>,
+    /// Ignored with `allow_any_origin`, so no arbitrary site can make
+    /// credentialed requests
+    pub allow_credentials: bool,
+    pub max_age: Duration,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_origins: vec![HeaderValue::from_static(DEFAULT_ALLOWED_ORIGIN)],
+            allow_any_origin: false,
+            allowed_methods: vec![Method::GET, Method::POST, Method::PUT, Method::DELETE],
+            allowed_headers: vec![CONTENT_TYPE, AUTHORIZATION],
+            allow_credentials: true,
+            max_age: Duration::from_secs(DEFAULT_MAX_AGE_SECONDS),
+        }
+    }
+}
+
+/// Comma-separated `name`, parsed with `parse`; invalid entries are logged and
+/// skipped. `None` when the variable is unset.
+fn parse_list<T>(name: &str, parse: impl Fn(&str) -> Option<T>) -> Option<Vec<T>> {
+    let raw = std::env::var(name).ok()?;
+    Some(
+        raw.split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .filter_map(|entry| {
+                let parsed = parse(entry);
+                if parsed.is_none() {
+                    tracing::warn!("CORS: skipping invalid entry '{}' in {}", entry, name);
+                }
+                parsed
+            })
+            .collect(),
+    )
+}
+
+impl CorsConfig {
+    /// Load from `CORS_ALLOWED_ORIGINS` (default
+    /// [`DEFAULT_ALLOWED_ORIGIN`]), `CORS_ALLOWED_METHODS` (default
+    /// `GET,POST,PUT,DELETE`), `CORS_ALLOWED_HEADERS` (default
+    /// `content-type,authorization`), `CORS_ALLOW_CREDENTIALS` (default `true`)
+    /// and `CORS_MAX_AGE_SECONDS` (default 3600).
+    #[must_use]
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
+        let mut allowed_origins = parse_list("CORS_ALLOWED_ORIGINS", |origin| {
+            origin.parse::<HeaderValue>().ok()
+        })
+        .unwrap_or(defaults.allowed_origins);
+        let mut allow_any_origin = allowed_origins.iter().any(|origin| *origin == "*");
+        allowed_origins.retain(|origin| *origin != "*");
+
+        if allow_any_origin && crate::env_config::is_production_env() {
+            tracing::error!(
+                "CORS: ignoring wildcard in CORS_ALLOWED_ORIGINS in production; \
+                 list the frontend origins explicitly"
+            );
+            allow_any_origin = false;
+        }
+        if allow_any_origin {
+            tracing::warn!("CORS: wildcard origin configured; mirroring request origin");
+        } else if allowed_origins.is_empty() {
+            tracing::warn!(
+                "CORS: no valid origins in CORS_ALLOWED_ORIGINS. \
+                 All cross-origin requests will be rejected."
+            );
+        }
+        for origin in &allowed_origins {
+            tracing::info!(
+                "CORS: allowing origin '{}'",
+                origin.to_str().unwrap_or_default()
+            );
+        }
+
+        let allowed_methods = parse_list("CORS_ALLOWED_METHODS", |method| {
+            Method::from_bytes(method.to_ascii_uppercase().as_bytes()).ok()
+        })
+        .unwrap_or(defaults.allowed_methods);
+        let allowed_headers = parse_list("CORS_ALLOWED_HEADERS", |header| {
+            HeaderName::from_bytes(header.to_ascii_lowercase().as_bytes()).ok()
+        })
+        .unwrap_or(defaults.allowed_headers);
+        let allow_credentials = std::env::var("CORS_ALLOW_CREDENTIALS")
+            .ok()
+            .and_then(|s| s.parse::<bool>().ok())
+            .unwrap_or(defaults.allow_credentials);
+        let max_age = std::env::var("CORS_MAX_AGE_SECONDS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .map_or(defaults.max_age, Duration::from_secs);
+
+        Self {
+            allowed_origins,
+            allow_any_origin,
+            allowed_methods,
+            allowed_headers,
+            allow_credentials,
+            max_age,
+        }
+    }
+
+    #[must_use]
+    pub fn is_origin_allowed(&self, origin: &HeaderValue) -> bool {
+        self.allow_any_origin || self.allowed_origins.contains(origin)
+    }
+
+    /// Layer adding CORS headers for allowed origins and answering their preflights
+    #[must_use]
+    pub fn layer(&self) -> CorsLayer {
+        let allow_origin = if self.allow_any_origin {
+            AllowOrigin::mirror_request()
+        } else {
+            AllowOrigin::list(self.allowed_origins.clone())
+        };
+        CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods(self.allowed_methods.clone())
+            .allow_headers(self.allowed_headers.clone())
+            .allow_credentials(self.allow_credentials && !self.allow_any_origin)
+            .max_age(self.max_age)
+    }
+}
+
+/// Apply `config` to `router`: CORS headers for allowed origins and a `403`
+/// for preflights from any other origin.
+pub fn apply_cors<S>(router: Router<S>, config: CorsConfig) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    router
+        .layer(config.layer())
+        .layer(middleware::from_fn_with_state(
+            config,
+            reject_disallowed_preflight,
+        ))
+}
+
+async fn reject_disallowed_preflight(
+    State(config): State<CorsConfig>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let is_preflight = req.method() == Method::OPTIONS
+        && req.headers().contains_key(ACCESS_CONTROL_REQUEST_METHOD);
+    match req.headers().get(ORIGIN) {
+        Some(origin) if is_preflight && !config.is_origin_allowed(origin) => {
+            tracing::info!(origin = ?origin, "CORS: rejecting preflight from disallowed origin");
+            let error = ApiError::forbidden(
+                "CORS_ORIGIN_NOT_ALLOWED",
+                "Cross-origin requests from this origin are not allowed",
+            );
+            error_response_with_request_id(error, &req)
+        }
+        _ => next.run(req).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{
+        header::{ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_ORIGIN},
+        StatusCode,
+    };
+    use axum::routing::get;
+    use tower::ServiceExt;
+
+    const FRONTEND: &str = "https://app.stellar-insights.com";
+
+    fn app(config: CorsConfig) -> Router {
+        apply_cors(
+            Router::new().route("/anchors", get(|| async { "ok" })),
+            config,
+        )
+    }
+
+    fn config() -> CorsConfig {
+        CorsConfig {
+            allowed_origins: vec![HeaderValue::from_static(FRONTEND)],
+            ..CorsConfig::default()
+        }
+    }
+
+    async fn send(app: &Router, method: Method, origin: &str) -> Response {
+        let mut request = axum::http::Request::builder()
+            .method(method.clone())
+            .uri("/anchors")
+            .header(ORIGIN, origin);
+        if method == Method::OPTIONS {
+            request = request.header(ACCESS_CONTROL_REQUEST_METHOD, "GET");
+        }
+        app.clone()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_allowed_origin_gets_cors_headers() {
+        let app = app(config());
+
+        let response = send(&app, Method::GET, FRONTEND).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(ACCESS_CONTROL_ALLOW_ORIGIN).unwrap(),
+            FRONTEND
+        );
+
+        let preflight = send(&app, Method::OPTIONS, FRONTEND).await;
+        assert_eq!(preflight.status(), StatusCode::OK);
+        assert_eq!(
+            preflight
+                .headers()
+                .get(ACCESS_CONTROL_ALLOW_ORIGIN)
+                .unwrap(),
+            FRONTEND
+        );
+    }
+
+    #[tokio::test]
+    async fn test_disallowed_origin_gets_no_cors_headers() {
+        let app = app(config());
+
+        let response = send(&app, Method::GET, "https://evil.example").await;
+        assert!(response
+            .headers()
+            .get(ACCESS_CONTROL_ALLOW_ORIGIN)
+            .is_none());
+
+        let preflight = send(&app, Method::OPTIONS, "https://evil.example").await;
+        assert_eq!(preflight.status(), StatusCode::FORBIDDEN);
+        assert!(preflight
+            .headers()
+            .get(ACCESS_CONTROL_ALLOW_ORIGIN)
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_wildcard_origin_drops_credentials() {
+        let listed = send(&app(config()), Method::GET, FRONTEND).await;
+        assert_eq!(
+            listed
+                .headers()
+                .get(ACCESS_CONTROL_ALLOW_CREDENTIALS)
+                .unwrap(),
+            "true"
+        );
+
+        let wildcard = app(CorsConfig {
+            allow_any_origin: true,
+            ..config()
+        });
+        let response = send(&wildcard, Method::GET, "https://other.example").await;
+        assert_eq!(
+            response.headers().get(ACCESS_CONTROL_ALLOW_ORIGIN).unwrap(),
+            "https://other.example"
+        );
+        assert!(response
+            .headers()
+            .get(ACCESS_CONTROL_ALLOW_CREDENTIALS)
+            .is_none());
+    }
+
+    #[test]
+    fn test_wildcard_is_ignored_in_production() {
+        let _guard = crate::lock_env_test();
+        std::env::remove_var("RUST_ENV");
+        std::env::set_var("CORS_ALLOWED_ORIGINS", format!("*, {FRONTEND}"));
+        std::env::set_var("CORS_ALLOWED_METHODS", "get, patch");
+
+        std::env::set_var("ENVIRONMENT", "development");
+        let development = CorsConfig::from_env();
+        std::env::set_var("ENVIRONMENT", "production");
+        let production = CorsConfig::from_env();
+        std::env::remove_var("ENVIRONMENT");
+        std::env::remove_var("CORS_ALLOWED_ORIGINS");
+        std::env::remove_var("CORS_ALLOWED_METHODS");
+
+        assert!(development.allow_any_origin);
+        assert!(development.is_origin_allowed(&HeaderValue::from_static("https://other.example")));
+        assert!(!production.allow_any_origin);
+        assert_eq!(
+            production.allowed_origins,
+            vec![HeaderValue::from_static(FRONTEND)]
+        );
+        assert_eq!(production.allowed_methods, vec![Method::GET, Method::PATCH]);
+    }
+}
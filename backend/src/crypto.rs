@@ -85,6 +85,13 @@ pub fn is_encrypted(data: &str) -> bool {
     data.contains(':') && data.split(':').count() == 2
 }
 
+/// Compare without short-circuiting so timing does not leak how much of a
+/// secret matched
+#[must_use]
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
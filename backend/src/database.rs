@@ -1340,20 +1340,24 @@ impl Database {
     }
 
     /// Saves a batch of payment records to the database using a transaction.
+    ///
+    /// Payments whose id is already stored are skipped; returns how many
+    /// rows were new.
     #[tracing::instrument(skip(self, payments), fields(payment_count = payments.len()))]
-    pub async fn save_payments(&self, payments: Vec<crate::models::PaymentRow>) -> Result<()> {
+    pub async fn save_payments(&self, payments: Vec<crate::models::PaymentRow>) -> Result<usize> {
         self.execute_with_timing("save_payments", async {
             let payment_count = payments.len();
             if payments.is_empty() {
-                return Ok(());
+                return Ok(0);
             }
 
             let mut tx = self.pool.begin().await.with_context(|| {
                 format!("Failed to begin transaction for save_payments ({payment_count} payments)")
             })?;
 
+            let mut inserted = 0;
             for payment in &payments {
-                sqlx::query(
+                let result = sqlx::query(
                     r"
                     INSERT INTO payments (
                         id, transaction_hash, source_account, destination_account,
@@ -1375,12 +1379,13 @@ impl Database {
                 .execute(&mut *tx)
                 .await
                 .with_context(|| format!("Failed to save payment id: {}", payment.id))?;
+                inserted += result.rows_affected() as usize;
             }
 
             tx.commit().await.with_context(|| {
                 format!("Failed to commit transaction for save_payments ({payment_count} payments)")
             })?;
-            Ok(())
+            Ok(inserted)
         })
         .await
     }
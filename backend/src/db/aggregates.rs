@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use anyhow::{anyhow, Context, Result};
 use chrono::NaiveDate;
 use serde::Serialize;
@@ -5,6 +7,35 @@ use sqlx::SqlitePool;
 
 use crate::models::corridor::{Corridor, CorridorAnalytics, CorridorMetrics};
 
+/// Most assets a corridor heatmap can span
+pub const MAX_HEATMAP_ASSETS: usize = 50;
+
+/// Daily corridor rows in `?1..=?2` keyed by `CODE:ISSUER` on both sides, and
+/// each asset's volume summed over the corridors it appears in
+const HEATMAP_CTE: &str = r"
+    WITH corridors AS (
+        SELECT
+            asset_a_code || ':' || asset_a_issuer AS source_asset,
+            asset_b_code || ':' || asset_b_issuer AS destination_asset,
+            total_transactions,
+            successful_transactions,
+            volume_usd
+        FROM corridor_metrics
+        WHERE date >= ?1 AND date <= ?2
+    ),
+    top_assets AS (
+        SELECT asset, SUM(volume_usd) AS volume_usd
+        FROM (
+            SELECT source_asset AS asset, volume_usd FROM corridors
+            UNION ALL
+            SELECT destination_asset AS asset, volume_usd FROM corridors
+        )
+        GROUP BY asset
+        ORDER BY volume_usd DESC, asset ASC
+        LIMIT ?3
+    )
+";
+
 pub struct CorridorAggregates {
     pool: SqlitePool,
 }
@@ -278,6 +309,64 @@ impl CorridorAggregates {
         Ok(stats)
     }
 
+    /// Volume and success rate between the `top` busiest assets from
+    /// `start_date` to `end_date`, as a dense matrix.
+    ///
+    /// Assets are ranked by the volume of every corridor they take part in,
+    /// on either side. Two queries cover the whole matrix: one for the ranked
+    /// assets and one for every corridor between them.
+    pub async fn get_corridor_heatmap(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        top: usize,
+    ) -> Result<CorridorHeatmap> {
+        let start_datetime = start_of_day_utc(start_date)?;
+        let end_datetime = end_of_day_utc(end_date)?;
+        let limit = i64::try_from(top.min(MAX_HEATMAP_ASSETS))?;
+
+        let assets: Vec<String> = sqlx::query_scalar(&format!(
+            "{HEATMAP_CTE} SELECT asset FROM top_assets ORDER BY volume_usd DESC, asset ASC"
+        ))
+        .bind(start_datetime)
+        .bind(end_datetime)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context(format!(
+            "Failed to rank heatmap assets from {} to {}",
+            start_date, end_date
+        ))?;
+
+        let cells = sqlx::query_as::<_, HeatmapCellRow>(&format!(
+            r"{HEATMAP_CTE}
+            SELECT
+                source_asset,
+                destination_asset,
+                SUM(total_transactions) AS total_transactions,
+                SUM(successful_transactions) AS successful_transactions,
+                SUM(volume_usd) AS volume_usd
+            FROM corridors
+            WHERE source_asset IN (SELECT asset FROM top_assets)
+              AND destination_asset IN (SELECT asset FROM top_assets)
+            GROUP BY source_asset, destination_asset
+            "
+        ))
+        .bind(start_datetime)
+        .bind(end_datetime)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context(format!(
+            "Failed to get heatmap corridors from {} to {}",
+            start_date, end_date
+        ))?;
+
+        Ok(CorridorHeatmap::from_cells(
+            start_date, end_date, assets, cells,
+        ))
+    }
+
     pub async fn delete_old_metrics(&self, cutoff_date: NaiveDate) -> Result<u64> {
         let cutoff_datetime = start_of_day_utc(cutoff_date)?;
 
@@ -340,5 +429,169 @@ pub struct CorridorSummaryStats {
     pub avg_success_rate: Option<f64>,
 }
 
+/// Pairwise corridor activity between the busiest assets over a date range.
+///
+/// Rows are source assets and columns destination assets, both in the order
+/// of `assets`, so `volume_usd[i][j]` is the volume sent from `assets[i]` to
+/// `assets[j]`. Corridors are directional, so the matrix is generally not
+/// symmetric. Pairs with no activity, including the diagonal, are zero.
+#[derive(Debug, Clone, Serialize)]
+pub struct CorridorHeatmap {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    /// `CODE:ISSUER` of each row and column, busiest first
+    pub assets: Vec<String>,
+    pub volume_usd: Vec<Vec<f64>>,
+    /// Percentage of successful transactions; zero when there were none
+    pub success_rate: Vec<Vec<f64>>,
+}
+
+impl CorridorHeatmap {
+    fn from_cells(
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        assets: Vec<String>,
+        cells: Vec<HeatmapCellRow>,
+    ) -> Self {
+        let index: HashMap<&str, usize> = assets
+            .iter()
+            .enumerate()
+            .map(|(i, asset)| (asset.as_str(), i))
+            .collect();
+        let n = assets.len();
+        let mut volume_usd = vec![vec![0.0; n]; n];
+        let mut success_rate = vec![vec![0.0; n]; n];
+        for cell in cells {
+            let (Some(&i), Some(&j)) = (
+                index.get(cell.source_asset.as_str()),
+                index.get(cell.destination_asset.as_str()),
+            ) else {
+                continue;
+            };
+            volume_usd[i][j] = cell.volume_usd;
+            if cell.total_transactions > 0 {
+                success_rate[i][j] =
+                    cell.successful_transactions as f64 * 100.0 / cell.total_transactions as f64;
+            }
+        }
+        Self {
+            start_date,
+            end_date,
+            assets,
+            volume_usd,
+            success_rate,
+        }
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct HeatmapCellRow {
+    source_asset: String,
+    destination_asset: String,
+    total_transactions: i64,
+    successful_transactions: i64,
+    volume_usd: f64,
+}
+
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+
+    async fn seeded_pool() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::query(include_str!(
+            "../../migrations/003_create_ingestion_and_payments.sql"
+        ))
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let day = |d: u32| start_of_day_utc(NaiveDate::from_ymd_opt(2026, 3, d).unwrap()).unwrap();
+        for (source, dest, date, total, successful, volume) in [
+            ("USDC", "XLM", day(2), 100, 90, 1000.0),
+            ("XLM", "USDC", day(3), 50, 50, 400.0),
+            ("EURC", "USDC", day(4), 10, 5, 300.0),
+            ("NGN", "XLM", day(5), 4, 4, 10.0),
+            // Outside the requested range
+            ("USDC", "EURC", day(20), 1, 1, 99_999.0),
+        ] {
+            let issuer = |code: &str| {
+                if code == "XLM" {
+                    "native".to_string()
+                } else {
+                    format!("G{code}ISSUER")
+                }
+            };
+            sqlx::query(
+                "INSERT INTO corridor_metrics (corridor_key, asset_a_code, asset_a_issuer,
+                     asset_b_code, asset_b_issuer, date, total_transactions,
+                     successful_transactions, volume_usd)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(format!("{source}->{dest}"))
+            .bind(source)
+            .bind(issuer(source))
+            .bind(dest)
+            .bind(issuer(dest))
+            .bind(date)
+            .bind(total)
+            .bind(successful)
+            .bind(volume)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_heatmap_selects_top_assets_and_fills_directional_matrix() {
+        let aggregates = CorridorAggregates::new(seeded_pool().await);
+        let heatmap = aggregates
+            .get_corridor_heatmap(
+                NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2026, 3, 10).unwrap(),
+                3,
+            )
+            .await
+            .unwrap();
+
+        // USDC 1700, XLM 1410, EURC 300; NGN (10) falls outside the top 3
+        assert_eq!(
+            heatmap.assets,
+            ["USDC:GUSDCISSUER", "XLM:native", "EURC:GEURCISSUER"]
+        );
+        let (usdc, xlm, eurc) = (0, 1, 2);
+        assert!(heatmap.volume_usd.iter().all(|row| row.len() == 3));
+        assert!(heatmap.success_rate.iter().all(|row| row.len() == 3));
+
+        // Each direction keeps its own volume and success rate
+        assert_eq!(heatmap.volume_usd[usdc][xlm], 1000.0);
+        assert_eq!(heatmap.volume_usd[xlm][usdc], 400.0);
+        assert_eq!(heatmap.success_rate[usdc][xlm], 90.0);
+        assert_eq!(heatmap.success_rate[xlm][usdc], 100.0);
+        assert_eq!(heatmap.volume_usd[eurc][usdc], 300.0);
+        assert_eq!(heatmap.success_rate[eurc][usdc], 50.0);
+
+        // One-way and out-of-range pairs stay explicit zeros, as does the diagonal
+        assert_eq!(heatmap.volume_usd[usdc][eurc], 0.0);
+        assert_eq!(heatmap.volume_usd[xlm][eurc], 0.0);
+        assert_eq!(heatmap.success_rate[xlm][eurc], 0.0);
+        assert!((0..3).all(|i| heatmap.volume_usd[i][i] == 0.0));
+    }
+
+    #[tokio::test]
+    async fn test_heatmap_without_activity_is_empty() {
+        let aggregates = CorridorAggregates::new(seeded_pool().await);
+        let heatmap = aggregates
+            .get_corridor_heatmap(
+                NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+                10,
+            )
+            .await
+            .unwrap();
+        assert!(heatmap.assets.is_empty());
+        assert!(heatmap.volume_usd.is_empty());
+    }
+}
@@ -6,6 +6,7 @@ pub struct EmailService {
     smtp_host: String,
     smtp_user: String,
     smtp_pass: String,
+    smtp_port: Option<u16>,
 }
 
 impl EmailService {
@@ -15,9 +16,17 @@ impl EmailService {
             smtp_host,
             smtp_user,
             smtp_pass,
+            smtp_port: None,
         }
     }
 
+    /// Override the SMTP port instead of the relay default (465).
+    #[must_use]
+    pub const fn with_port(mut self, port: u16) -> Self {
+        self.smtp_port = Some(port);
+        self
+    }
+
     pub fn send_html(&self, to: &str, subject: &str, html: &str) -> anyhow::Result<()> {
         let email = Message::builder()
             .from(self.smtp_user.parse()?)
@@ -27,9 +36,11 @@ impl EmailService {
             .body(html.to_string())?;
 
         let creds = Credentials::new(self.smtp_user.clone(), self.smtp_pass.clone());
-        let mailer = SmtpTransport::relay(&self.smtp_host)?
-            .credentials(creds)
-            .build();
+        let mut builder = SmtpTransport::relay(&self.smtp_host)?.credentials(creds);
+        if let Some(port) = self.smtp_port {
+            builder = builder.port(port);
+        }
+        let mailer = builder.build();
 
         mailer.send(&email)?;
         Ok(())
@@ -23,6 +23,17 @@ const VALIDATED_VARS: &[(&str, fn(&str) -> bool)] = &[
     ("JWT_SECRET", validate_jwt_secret),
 ];
 
+/// Whether `RUST_ENV`/`ENVIRONMENT` names a production deployment
+/// (`production` or `prod`)
+#[must_use]
+pub fn is_production_env() -> bool {
+    let env_mode = env::var("RUST_ENV")
+        .or_else(|_| env::var("ENVIRONMENT"))
+        .unwrap_or_default()
+        .to_lowercase();
+    env_mode == "production" || env_mode == "prod"
+}
+
 /// Validates all required environment variables are set
 pub fn validate_env() -> Result<()> {
     // STELLAR_NETWORK is a fatal startup guard: a missing or unknown value would
@@ -121,6 +132,7 @@ pub fn log_env_config() {
     // Network
     log_var("STELLAR_NETWORK");
     log_var("RPC_MOCK_MODE");
+    log_var("ALLOW_MOCK_IN_PROD");
 
     // Pool config
     log_var("DB_POOL_MAX_CONNECTIONS");
@@ -134,6 +146,17 @@ pub fn log_env_config() {
 
     // CORS
     log_var("CORS_ALLOWED_ORIGINS");
+    log_var("CORS_ALLOWED_METHODS");
+    log_var("CORS_ALLOWED_HEADERS");
+    log_var("CORS_ALLOW_CREDENTIALS");
+    log_var("CORS_MAX_AGE_SECONDS");
+
+    // Snapshot submission queue
+    log_var("SNAPSHOT_SUBMIT_MAX_ATTEMPTS");
+    log_var("SNAPSHOT_SUBMIT_FEE_BUMP_PERCENT");
+    log_var("SNAPSHOT_SUBMIT_MAX_FEE_STROOPS");
+    log_var("SNAPSHOT_SUBMIT_RETRY_DELAY_SECONDS");
+    log_var("SNAPSHOT_SUBMIT_POLL_INTERVAL_SECONDS");
 
     // Slack Bot
     if let Ok(slack_url) = env::var("SLACK_WEBHOOK_URL") {
@@ -74,11 +74,21 @@ pub enum ApiError {
         message: String,
         details: Option<HashMap<String, serde_json::Value>>,
     },
+    Forbidden {
+        code: String,
+        message: String,
+        details: Option<HashMap<String, serde_json::Value>>,
+    },
     ServiceUnavailable {
         code: String,
         message: String,
         details: Option<HashMap<String, serde_json::Value>>,
     },
+    PayloadTooLarge {
+        code: String,
+        message: String,
+        details: Option<HashMap<String, serde_json::Value>>,
+    },
 }
 
 pub type AppError = ApiError;
@@ -147,6 +157,15 @@ impl ApiError {
         }
     }
 
+    /// Create a Forbidden error
+    pub fn forbidden(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::Forbidden {
+            code: code.into(),
+            message: message.into(),
+            details: None,
+        }
+    }
+
     /// Create a ServiceUnavailable error
     pub fn service_unavailable(code: impl Into<String>, message: impl Into<String>) -> Self {
         Self::ServiceUnavailable {
@@ -156,6 +175,15 @@ impl ApiError {
         }
     }
 
+    /// Create a PayloadTooLarge error
+    pub fn payload_too_large(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::PayloadTooLarge {
+            code: code.into(),
+            message: message.into(),
+            details: None,
+        }
+    }
+
     /// Add details to any error variant
     #[must_use]
     pub fn with_details(mut self, details: HashMap<String, serde_json::Value>) -> Self {
@@ -164,7 +192,9 @@ impl ApiError {
             | Self::BadRequest { details: d, .. }
             | Self::InternalError { details: d, .. }
             | Self::Unauthorized { details: d, .. }
-            | Self::ServiceUnavailable { details: d, .. } => {
+            | Self::Forbidden { details: d, .. }
+            | Self::ServiceUnavailable { details: d, .. }
+            | Self::PayloadTooLarge { details: d, .. } => {
                 *d = Some(details);
             }
         }
@@ -178,7 +208,9 @@ impl ApiError {
             Self::BadRequest { .. } => StatusCode::BAD_REQUEST,
             Self::InternalError { .. } => StatusCode::INTERNAL_SERVER_ERROR,
             Self::Unauthorized { .. } => StatusCode::UNAUTHORIZED,
+            Self::Forbidden { .. } => StatusCode::FORBIDDEN,
             Self::ServiceUnavailable { .. } => StatusCode::SERVICE_UNAVAILABLE,
+            Self::PayloadTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
         }
     }
 
@@ -214,11 +246,21 @@ impl ApiError {
                 message,
                 details,
             } => (code.clone(), message.clone(), details.clone(), None),
+            Self::Forbidden {
+                code,
+                message,
+                details,
+            } => (code.clone(), message.clone(), details.clone(), None),
             Self::ServiceUnavailable {
                 code,
                 message,
                 details,
             } => (code.clone(), message.clone(), details.clone(), None),
+            Self::PayloadTooLarge {
+                code,
+                message,
+                details,
+            } => (code.clone(), message.clone(), details.clone(), None),
         };
 
         ErrorResponse {
@@ -240,7 +282,9 @@ impl std::fmt::Display for ApiError {
             Self::BadRequest { message, .. } => write!(f, "{}", message),
             Self::InternalError { message, .. } => write!(f, "{}", message),
             Self::Unauthorized { message, .. } => write!(f, "{}", message),
+            Self::Forbidden { message, .. } => write!(f, "{}", message),
             Self::ServiceUnavailable { message, .. } => write!(f, "{}", message),
+            Self::PayloadTooLarge { message, .. } => write!(f, "{}", message),
         }
     }
 }
@@ -355,6 +399,15 @@ impl From<DomainError> for ApiError {
 /// Convert RPC errors into API errors so handlers can use `?` consistently.
 impl From<crate::rpc::error::RpcError> for ApiError {
     fn from(err: crate::rpc::error::RpcError) -> Self {
+        if let Some(codes) = err.result_codes() {
+            let mut details = HashMap::new();
+            details.insert("result_codes".to_string(), serde_json::json!(codes));
+            return Self::bad_request_with_details(
+                "TRANSACTION_FAILED",
+                format!("Transaction failed: {codes}"),
+                details,
+            );
+        }
         Self::InternalError {
             code: "RPC_ERROR".to_string(),
             message: "External service error".to_string(),
@@ -482,4 +535,27 @@ mod tests {
             _ => panic!("Expected InternalError"),
         }
     }
+
+    #[test]
+    fn test_from_transaction_failed_rpc_error() {
+        let rpc_err =
+            crate::rpc::error::RpcError::TransactionFailed(crate::rpc::error::HorizonResultCodes {
+                transaction: Some("tx_failed".to_string()),
+                operations: vec!["op_no_trust".to_string()],
+            });
+        let api_error: ApiError = rpc_err.into();
+
+        assert_eq!(api_error.status_code(), StatusCode::BAD_REQUEST);
+        match api_error {
+            ApiError::BadRequest {
+                code,
+                details: Some(details),
+                ..
+            } => {
+                assert_eq!(code, "TRANSACTION_FAILED");
+                assert_eq!(details["result_codes"]["operations"][0], "op_no_trust");
+            }
+            _ => panic!("Expected BadRequest with result codes"),
+        }
+    }
 }
@@ -120,34 +120,77 @@ fn set_common_headers(
     }
 }
 
-pub fn cached_json_response<T: Serialize>(
+fn body_etag(body: &[u8]) -> String {
+    let digest = Sha256::digest(body);
+    format!("\"{}\"", hex::encode(digest))
+}
+
+fn conditional_response(
     request_headers: &HeaderMap,
-    resource_key: &str,
-    payload: &T,
+    body: Vec<u8>,
+    etag: &str,
+    last_modified: DateTime<Utc>,
     ttl_seconds: usize,
-) -> anyhow::Result<Response> {
-    let body = serde_json::to_vec(payload)?;
-    let digest = Sha256::digest(&body);
-    let etag = format!("\"{}\"", hex::encode(digest));
-    let last_modified = resolve_last_modified(resource_key, &etag);
+) -> Response {
     let cache_control = format!("public, max-age={ttl_seconds}");
 
-    let not_modified = if_none_match_matches(request_headers, &etag)
-        || if_modified_since_matches(request_headers, last_modified);
+    // If-None-Match takes precedence; If-Modified-Since is only consulted without it
+    let not_modified = if request_headers.contains_key(IF_NONE_MATCH) {
+        if_none_match_matches(request_headers, etag)
+    } else {
+        if_modified_since_matches(request_headers, last_modified)
+    };
 
     if not_modified {
         let mut response = Response::new(Body::empty());
         *response.status_mut() = StatusCode::NOT_MODIFIED;
-        set_common_headers(response.headers_mut(), &cache_control, &etag, last_modified);
-        return Ok(response);
+        set_common_headers(response.headers_mut(), &cache_control, etag, last_modified);
+        return response;
     }
 
     let mut response = Response::new(Body::from(body));
     response
         .headers_mut()
         .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-    set_common_headers(response.headers_mut(), &cache_control, &etag, last_modified);
-    Ok(response)
+    set_common_headers(response.headers_mut(), &cache_control, etag, last_modified);
+    response
+}
+
+pub fn cached_json_response<T: Serialize>(
+    request_headers: &HeaderMap,
+    resource_key: &str,
+    payload: &T,
+    ttl_seconds: usize,
+) -> anyhow::Result<Response> {
+    let body = serde_json::to_vec(payload)?;
+    let etag = body_etag(&body);
+    let last_modified = resolve_last_modified(resource_key, &etag);
+    Ok(conditional_response(
+        request_headers,
+        body,
+        &etag,
+        last_modified,
+        ttl_seconds,
+    ))
+}
+
+/// Like [`cached_json_response`], but `Last-Modified` comes from the data
+/// itself (e.g. the newest ingested ledger) rather than when the body changed.
+pub fn cached_json_response_with_last_modified<T: Serialize>(
+    request_headers: &HeaderMap,
+    payload: &T,
+    last_modified: DateTime<Utc>,
+    ttl_seconds: usize,
+) -> anyhow::Result<Response> {
+    let body = serde_json::to_vec(payload)?;
+    let etag = body_etag(&body);
+    Ok(conditional_response(
+        request_headers,
+        body,
+        &etag,
+        last_modified,
+        ttl_seconds,
+    ))
 }
 
 #[cfg(test)]
@@ -229,4 +272,70 @@ mod tests {
         .unwrap();
         assert_eq!(second.status(), StatusCode::NOT_MODIFIED);
     }
+
+    #[tokio::test]
+    async fn uses_supplied_last_modified() {
+        let ledger_closed = DateTime::parse_from_rfc3339("2024-03-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let headers = HeaderMap::new();
+        let first = cached_json_response_with_last_modified(
+            &headers,
+            &Payload { value: "d" },
+            ledger_closed,
+            60,
+        )
+        .unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(
+            first.headers().get(LAST_MODIFIED).unwrap(),
+            "Fri, 01 Mar 2024 12:00:00 GMT"
+        );
+
+        let mut since_headers = HeaderMap::new();
+        since_headers.insert(
+            IF_MODIFIED_SINCE,
+            HeaderValue::from_static("Fri, 01 Mar 2024 12:00:00 GMT"),
+        );
+        let unchanged = cached_json_response_with_last_modified(
+            &since_headers,
+            &Payload { value: "d" },
+            ledger_closed,
+            60,
+        )
+        .unwrap();
+        assert_eq!(unchanged.status(), StatusCode::NOT_MODIFIED);
+
+        let newer_ledger = ledger_closed + chrono::Duration::seconds(5);
+        let changed = cached_json_response_with_last_modified(
+            &since_headers,
+            &Payload { value: "d" },
+            newer_ledger,
+            60,
+        )
+        .unwrap();
+        assert_eq!(changed.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn if_none_match_takes_precedence_over_if_modified_since() {
+        let mut headers = HeaderMap::new();
+        headers.insert(IF_NONE_MATCH, HeaderValue::from_static("\"stale\""));
+        headers.insert(
+            IF_MODIFIED_SINCE,
+            HeaderValue::from_static("Fri, 01 Mar 2024 12:00:00 GMT"),
+        );
+        let last_modified = DateTime::parse_from_rfc3339("2024-03-01T11:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let response = cached_json_response_with_last_modified(
+            &headers,
+            &Payload { value: "e" },
+            last_modified,
+            60,
+        )
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
 }
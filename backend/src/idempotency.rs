@@ -0,0 +1,416 @@
+//! `Idempotency-Key` support for expensive, non-idempotent POST endpoints.
+//!
+//! The first request with a given key claims it and its response is stored;
+//! repeats within the TTL get the stored status code and body back unchanged.
+//! Only successful responses are stored: a failed request releases its claim
+//! so the client can retry with the same key. A claim that never completes
+//! (the process died mid-request) expires after a short timeout instead of
+//! the full TTL, and a key reused with a different body is refused.
+
+use std::time::Duration;
+
+use axum::{
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+const DEFAULT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+/// How long an unfinished claim blocks its key before it is presumed dead
+const DEFAULT_IN_PROGRESS_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+const MAX_KEY_LEN: usize = 255;
+
+/// Read a usable `Idempotency-Key` header value, if present.
+#[must_use]
+pub fn idempotency_key(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|k| !k.is_empty() && k.len() <= MAX_KEY_LEN)
+        .map(str::to_string)
+}
+
+/// Hex SHA-256 of a request body, stored with a claim to detect key reuse
+#[must_use]
+pub fn request_fingerprint(body: &[u8]) -> String {
+    hex::encode(Sha256::digest(body))
+}
+
+/// Response recorded against an idempotency key
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredResponse {
+    pub status: StatusCode,
+    pub body: serde_json::Value,
+    /// Id of the resource the original request created, if any
+    pub resource_id: Option<String>,
+}
+
+impl IntoResponse for StoredResponse {
+    fn into_response(self) -> Response {
+        (self.status, Json(self.body)).into_response()
+    }
+}
+
+/// Outcome of claiming a key
+#[derive(Debug, Clone, PartialEq)]
+pub enum Claim {
+    /// First use of the key; the caller should process the request
+    Fresh,
+    /// The key was already used; return this response instead
+    Replay(StoredResponse),
+    /// Another request with this key has not finished yet
+    InProgress,
+    /// The key was first used with a different request body
+    Mismatch,
+}
+
+pub struct IdempotencyStore {
+    pool: SqlitePool,
+    ttl: Duration,
+    in_progress_timeout: Duration,
+}
+
+impl IdempotencyStore {
+    #[must_use]
+    pub const fn new(pool: SqlitePool) -> Self {
+        Self {
+            pool,
+            ttl: DEFAULT_TTL,
+            in_progress_timeout: DEFAULT_IN_PROGRESS_TIMEOUT,
+        }
+    }
+
+    /// Honour `IDEMPOTENCY_KEY_TTL_SECONDS` (default 24 hours) and
+    /// `IDEMPOTENCY_IN_PROGRESS_TIMEOUT_SECONDS` (default 300)
+    #[must_use]
+    pub fn from_env(pool: SqlitePool) -> Self {
+        let seconds = |name: &str, default: Duration| {
+            std::env::var(name)
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .map_or(default, Duration::from_secs)
+        };
+        Self::new(pool)
+            .with_ttl(seconds("IDEMPOTENCY_KEY_TTL_SECONDS", DEFAULT_TTL))
+            .with_in_progress_timeout(seconds(
+                "IDEMPOTENCY_IN_PROGRESS_TIMEOUT_SECONDS",
+                DEFAULT_IN_PROGRESS_TIMEOUT,
+            ))
+    }
+
+    #[must_use]
+    pub const fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_in_progress_timeout(mut self, timeout: Duration) -> Self {
+        self.in_progress_timeout = timeout;
+        self
+    }
+
+    /// Claim `key` within `scope` for a request whose body has
+    /// [`request_fingerprint`] `request_hash`.
+    ///
+    /// Records older than the TTL, and unfinished claims older than the
+    /// in-progress timeout, are expired first.
+    pub async fn claim(
+        &self,
+        scope: &str,
+        key: &str,
+        request_hash: &str,
+    ) -> Result<Claim, sqlx::Error> {
+        sqlx::query(
+            "DELETE FROM idempotency_keys
+             WHERE scope = ? AND idempotency_key = ?
+               AND (created_at <= datetime('now', ?)
+                    OR (status_code IS NULL AND created_at <= datetime('now', ?)))",
+        )
+        .bind(scope)
+        .bind(key)
+        .bind(format!("-{} seconds", self.ttl.as_secs()))
+        .bind(format!("-{} seconds", self.in_progress_timeout.as_secs()))
+        .execute(&self.pool)
+        .await?;
+
+        let inserted = sqlx::query(
+            "INSERT OR IGNORE INTO idempotency_keys
+                 (scope, idempotency_key, request_hash, created_at)
+             VALUES (?, ?, ?, datetime('now'))",
+        )
+        .bind(scope)
+        .bind(key)
+        .bind(request_hash)
+        .execute(&self.pool)
+        .await?;
+        if inserted.rows_affected() == 1 {
+            return Ok(Claim::Fresh);
+        }
+
+        let row: Option<(Option<i64>, Option<String>, Option<String>, String)> = sqlx::query_as(
+            "SELECT status_code, response_body, resource_id, request_hash
+             FROM idempotency_keys
+             WHERE scope = ? AND idempotency_key = ?",
+        )
+        .bind(scope)
+        .bind(key)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        if row
+            .as_ref()
+            .is_some_and(|(_, _, _, stored_hash)| stored_hash != request_hash)
+        {
+            return Ok(Claim::Mismatch);
+        }
+
+        Ok(match row {
+            Some((Some(status), Some(body), resource_id, _)) => Claim::Replay(StoredResponse {
+                status: u16::try_from(status)
+                    .ok()
+                    .and_then(|s| StatusCode::from_u16(s).ok())
+                    .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+                body: serde_json::from_str(&body).unwrap_or(serde_json::Value::Null),
+                resource_id,
+            }),
+            _ => Claim::InProgress,
+        })
+    }
+
+    /// Record the response for a key previously returned as [`Claim::Fresh`].
+    pub async fn complete(
+        &self,
+        scope: &str,
+        key: &str,
+        response: &StoredResponse,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE idempotency_keys
+             SET status_code = ?, response_body = ?, resource_id = ?
+             WHERE scope = ? AND idempotency_key = ?",
+        )
+        .bind(i64::from(response.status.as_u16()))
+        .bind(response.body.to_string())
+        .bind(&response.resource_id)
+        .bind(scope)
+        .bind(key)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Give up a [`Claim::Fresh`] key without recording a response, so a
+    /// retry with the same key is processed again
+    pub async fn release(&self, scope: &str, key: &str) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "DELETE FROM idempotency_keys
+             WHERE scope = ? AND idempotency_key = ? AND status_code IS NULL",
+        )
+        .bind(scope)
+        .bind(key)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    const BODY_HASH: &str = "body-a";
+
+    async fn create_table(pool: &SqlitePool) {
+        sqlx::query(include_str!(
+            "../migrations/037_create_idempotency_keys.sql"
+        ))
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    async fn store() -> IdempotencyStore {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        create_table(&pool).await;
+        IdempotencyStore::new(pool)
+    }
+
+    fn created(id: &str) -> StoredResponse {
+        StoredResponse {
+            status: StatusCode::OK,
+            body: serde_json::json!({ "snapshot_id": id }),
+            resource_id: Some(id.to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_repeat_key_replays_original_response() {
+        let store = store().await;
+
+        assert_eq!(
+            store.claim("snapshots", "key-1", BODY_HASH).await.unwrap(),
+            Claim::Fresh
+        );
+        assert_eq!(
+            store.claim("snapshots", "key-1", BODY_HASH).await.unwrap(),
+            Claim::InProgress
+        );
+
+        store
+            .complete("snapshots", "key-1", &created("snap-a"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            store.claim("snapshots", "key-1", BODY_HASH).await.unwrap(),
+            Claim::Replay(created("snap-a"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_different_keys_are_processed_independently() {
+        let store = store().await;
+
+        assert_eq!(
+            store.claim("snapshots", "key-1", BODY_HASH).await.unwrap(),
+            Claim::Fresh
+        );
+        assert_eq!(
+            store.claim("snapshots", "key-2", BODY_HASH).await.unwrap(),
+            Claim::Fresh
+        );
+        store
+            .complete("snapshots", "key-1", &created("snap-a"))
+            .await
+            .unwrap();
+        store
+            .complete("snapshots", "key-2", &created("snap-b"))
+            .await
+            .unwrap();
+
+        let Claim::Replay(first) = store.claim("snapshots", "key-1", BODY_HASH).await.unwrap()
+        else {
+            panic!("expected replay");
+        };
+        let Claim::Replay(second) = store.claim("snapshots", "key-2", BODY_HASH).await.unwrap()
+        else {
+            panic!("expected replay");
+        };
+        assert_eq!(first.resource_id.as_deref(), Some("snap-a"));
+        assert_eq!(second.resource_id.as_deref(), Some("snap-b"));
+    }
+
+    #[tokio::test]
+    async fn test_expired_key_can_be_reused() {
+        let store = store().await.with_ttl(Duration::ZERO);
+
+        assert_eq!(
+            store.claim("snapshots", "key-1", BODY_HASH).await.unwrap(),
+            Claim::Fresh
+        );
+        store
+            .complete("snapshots", "key-1", &created("snap-a"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            store.claim("snapshots", "key-1", BODY_HASH).await.unwrap(),
+            Claim::Fresh
+        );
+    }
+
+    #[tokio::test]
+    async fn test_released_claim_can_be_retried() {
+        let store = store().await;
+
+        assert_eq!(
+            store.claim("snapshots", "key-1", BODY_HASH).await.unwrap(),
+            Claim::Fresh
+        );
+        store.release("snapshots", "key-1").await.unwrap();
+        assert_eq!(
+            store.claim("snapshots", "key-1", BODY_HASH).await.unwrap(),
+            Claim::Fresh
+        );
+
+        // A completed response is never released
+        store
+            .complete("snapshots", "key-1", &created("snap-a"))
+            .await
+            .unwrap();
+        store.release("snapshots", "key-1").await.unwrap();
+        assert_eq!(
+            store.claim("snapshots", "key-1", BODY_HASH).await.unwrap(),
+            Claim::Replay(created("snap-a"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stale_in_progress_claim_expires() {
+        let store = store().await.with_in_progress_timeout(Duration::ZERO);
+
+        assert_eq!(
+            store.claim("snapshots", "key-1", BODY_HASH).await.unwrap(),
+            Claim::Fresh
+        );
+        assert_eq!(
+            store.claim("snapshots", "key-1", BODY_HASH).await.unwrap(),
+            Claim::Fresh
+        );
+
+        // Completed responses still last for the full TTL
+        store
+            .complete("snapshots", "key-1", &created("snap-a"))
+            .await
+            .unwrap();
+        assert_eq!(
+            store.claim("snapshots", "key-1", BODY_HASH).await.unwrap(),
+            Claim::Replay(created("snap-a"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_key_reused_with_different_body_is_mismatch() {
+        let store = store().await;
+
+        assert_eq!(
+            store.claim("snapshots", "key-1", BODY_HASH).await.unwrap(),
+            Claim::Fresh
+        );
+        assert_eq!(
+            store.claim("snapshots", "key-1", "body-b").await.unwrap(),
+            Claim::Mismatch
+        );
+        store
+            .complete("snapshots", "key-1", &created("snap-a"))
+            .await
+            .unwrap();
+        assert_eq!(
+            store.claim("snapshots", "key-1", "body-b").await.unwrap(),
+            Claim::Mismatch
+        );
+        assert_ne!(request_fingerprint(b"{}"), request_fingerprint(b"{ }"));
+    }
+
+    #[test]
+    fn test_idempotency_key_header_parsing() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(idempotency_key(&headers), None);
+
+        headers.insert(
+            IDEMPOTENCY_KEY_HEADER,
+            HeaderValue::from_static("  abc-123 "),
+        );
+        assert_eq!(idempotency_key(&headers).as_deref(), Some("abc-123"));
+
+        headers.insert(IDEMPOTENCY_KEY_HEADER, HeaderValue::from_static(" "));
+        assert_eq!(idempotency_key(&headers), None);
+    }
+}
@@ -0,0 +1,567 @@
+//! Resumable Ledger Backfill
+//!
+//! Walks ledgers page by page from the RPC and records the last durably
+//! written sequence and pagination cursor as a `backfill_state` row, so a
+//! restarted process picks up exactly where the previous one stopped.
+//!
+//! # Design
+//!
+//! - **Checkpointing**: each page of ledgers and its checkpoint are written in
+//!   a single transaction, so the checkpoint never runs ahead of the data.
+//!   The next page is requested with the stored cursor.
+//! - **Retention boundary**: RPC nodes only keep a window of recent ledgers. A
+//!   checkpoint that has fallen behind `oldest_ledger` is moved forward to the
+//!   oldest ledger still available instead of failing the run, and its
+//!   cursor is dropped.
+//! - **Progress tracking**: the latest [`BackfillProgress`] snapshot is shared
+//!   behind an `Arc<RwLock<_>>` so `GET /admin/backfill/ledgers` can read it
+//!   without touching the database or the RPC.
+//! - **Cancellation**: [`CheckpointedBackfill::cancel`] trips a
+//!   [`CancellationToken`], the same mechanism the contract event backfill
+//!   uses. It is only checked between pages, so the last page and its
+//!   checkpoint are always committed before the loop exits.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, RwLock};
+use tokio_retry::RetryIf;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+use super::retry_strategy;
+use crate::rpc::error::RpcError;
+use crate::rpc::{HealthResponse, RpcLedger, StellarRpcClient};
+
+/// Checkpoint id used when a caller does not need several independent backfills.
+pub const DEFAULT_CHECKPOINT_ID: &str = "ledgers";
+
+// ── Public types ─────────────────────────────────────────────────────────────
+
+/// How often and in what pages the background backfill walks ledgers
+#[derive(Debug, Clone)]
+pub struct LedgerBackfillConfig {
+    pub enabled: bool,
+    /// Pause between passes once the backfill has caught up
+    pub interval_seconds: u64,
+    pub page_size: u32,
+}
+
+impl Default for LedgerBackfillConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_seconds: 60,
+            page_size: 100,
+        }
+    }
+}
+
+impl LedgerBackfillConfig {
+    /// Read `LEDGER_BACKFILL_ENABLED`, `LEDGER_BACKFILL_INTERVAL_SECONDS` and
+    /// `LEDGER_BACKFILL_PAGE_SIZE`, keeping defaults for unset values
+    #[must_use]
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        config.enabled = std::env::var("LEDGER_BACKFILL_ENABLED")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(config.enabled);
+        config.interval_seconds = std::env::var("LEDGER_BACKFILL_INTERVAL_SECONDS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .map_or(config.interval_seconds, |s| s.max(1));
+        config.page_size = std::env::var("LEDGER_BACKFILL_PAGE_SIZE")
+            .ok()
+            .and_then(|s| s.parse::<u32>().ok())
+            .map_or(config.page_size, |s| s.clamp(1, 200));
+        config
+    }
+}
+
+/// Persisted position of a backfill.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BackfillCheckpoint {
+    pub id: String,
+    /// Last ledger whose page was committed.
+    pub last_ledger_sequence: u64,
+    /// RPC pagination cursor returned with that page.
+    pub cursor: Option<String>,
+}
+
+/// Snapshot of backfill progress exposed by the status endpoint.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BackfillProgress {
+    /// Last ledger committed, `None` until the first page is written.
+    pub last_ledger_sequence: Option<u64>,
+    /// Oldest ledger the RPC node still retains.
+    pub oldest_ledger: u64,
+    /// Newest ledger known to the RPC node.
+    pub latest_ledger: u64,
+    /// Ledgers still to be processed before the backfill is caught up.
+    pub remaining_ledgers: u64,
+    /// Ledgers written by this process since it started.
+    pub ledgers_processed: u64,
+    /// Whether the backfill was stopped through [`CheckpointedBackfill::cancel`].
+    pub cancelled: bool,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Shared handle that the status endpoint reads from.
+pub type BackfillProgressRef = Arc<RwLock<BackfillProgress>>;
+
+/// Outcome of a single [`CheckpointedBackfill::run_page`] call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackfillPage {
+    /// First ledger requested for this page.
+    pub start_ledger: u64,
+    /// Number of ledgers written.
+    pub ledgers_written: u64,
+    /// `true` when the checkpoint has reached the latest ledger.
+    pub caught_up: bool,
+}
+
+// ── Backfill ─────────────────────────────────────────────────────────────────
+
+/// Ledger backfill that resumes from its stored checkpoint.
+pub struct CheckpointedBackfill {
+    rpc_client: Arc<StellarRpcClient>,
+    pool: SqlitePool,
+    checkpoint_id: String,
+    progress: BackfillProgressRef,
+    cancel: CancellationToken,
+}
+
+impl CheckpointedBackfill {
+    #[must_use]
+    pub fn new(rpc_client: Arc<StellarRpcClient>, pool: SqlitePool) -> Self {
+        Self::with_checkpoint_id(rpc_client, pool, DEFAULT_CHECKPOINT_ID)
+    }
+
+    #[must_use]
+    pub fn with_checkpoint_id(
+        rpc_client: Arc<StellarRpcClient>,
+        pool: SqlitePool,
+        checkpoint_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            rpc_client,
+            pool,
+            checkpoint_id: checkpoint_id.into(),
+            progress: Arc::new(RwLock::new(BackfillProgress::default())),
+            cancel: CancellationToken::new(),
+        }
+    }
+
+    /// Handle to the live progress snapshot.
+    #[must_use]
+    pub fn progress(&self) -> BackfillProgressRef {
+        Arc::clone(&self.progress)
+    }
+
+    /// Stop [`Self::run`] and [`Self::run_to_latest`] after the page in
+    /// flight is committed. Fails if the backfill was already cancelled.
+    pub fn cancel(&self) -> Result<()> {
+        if self.cancel.is_cancelled() {
+            anyhow::bail!("Ledger backfill is already cancelled");
+        }
+        self.cancel.cancel();
+        info!("Ledger backfill cancellation requested");
+        Ok(())
+    }
+
+    /// Load the stored checkpoint, if any.
+    pub async fn load_checkpoint(&self) -> Result<Option<BackfillCheckpoint>> {
+        let row: Option<(i64, Option<String>)> =
+            sqlx::query_as("SELECT current_ledger, cursor FROM backfill_state WHERE id = $1")
+                .bind(&self.checkpoint_id)
+                .fetch_optional(&self.pool)
+                .await
+                .context("Failed to load backfill checkpoint")?;
+
+        Ok(row.map(|(seq, cursor)| BackfillCheckpoint {
+            id: self.checkpoint_id.clone(),
+            last_ledger_sequence: seq as u64,
+            cursor,
+        }))
+    }
+
+    /// Process one page of ledgers starting after the stored checkpoint.
+    pub async fn run_page(&self, page_size: u32) -> Result<BackfillPage> {
+        let checkpoint = self.load_checkpoint().await?;
+
+        let client = &self.rpc_client;
+        let health = RetryIf::start(
+            retry_strategy(),
+            || client.check_health(),
+            RpcError::is_retryable,
+        )
+        .await
+        .context("Failed to check health")?;
+
+        let resume_from = checkpoint
+            .as_ref()
+            .map_or(health.oldest_ledger, |c| c.last_ledger_sequence + 1);
+        let (start_ledger, cursor) = if resume_from < health.oldest_ledger {
+            warn!(
+                "Backfill checkpoint {} is behind the RPC retention window, skipping to ledger {}",
+                resume_from, health.oldest_ledger
+            );
+            (health.oldest_ledger, None)
+        } else {
+            (
+                resume_from,
+                checkpoint.as_ref().and_then(|c| c.cursor.clone()),
+            )
+        };
+
+        if start_ledger > health.latest_ledger {
+            self.update_progress(checkpoint.map(|c| c.last_ledger_sequence), &health, 0)
+                .await;
+            return Ok(BackfillPage {
+                start_ledger,
+                ledgers_written: 0,
+                caught_up: true,
+            });
+        }
+
+        let result = RetryIf::start(
+            retry_strategy(),
+            || client.fetch_ledgers(Some(start_ledger), page_size, cursor.as_deref()),
+            RpcError::is_retryable,
+        )
+        .await
+        .context("Failed to fetch ledgers")?;
+
+        let Some(last) = result.ledgers.iter().map(|l| l.sequence).max() else {
+            self.update_progress(checkpoint.map(|c| c.last_ledger_sequence), &health, 0)
+                .await;
+            return Ok(BackfillPage {
+                start_ledger,
+                ledgers_written: 0,
+                caught_up: true,
+            });
+        };
+
+        self.commit_page(&result.ledgers, last, result.cursor.as_deref())
+            .await?;
+
+        let written = result.ledgers.len() as u64;
+        self.update_progress(Some(last), &health, written).await;
+        info!(
+            "Backfilled ledgers {}..={} (checkpoint {})",
+            start_ledger, last, self.checkpoint_id
+        );
+
+        Ok(BackfillPage {
+            start_ledger,
+            ledgers_written: written,
+            caught_up: last >= result.latest_ledger,
+        })
+    }
+
+    /// Run pages until the checkpoint reaches the latest ledger or the
+    /// backfill is cancelled.
+    pub async fn run_to_latest(&self, page_size: u32) -> Result<u64> {
+        let mut total = 0;
+        loop {
+            if self.cancel.is_cancelled() {
+                self.progress.write().await.cancelled = true;
+                return Ok(total);
+            }
+            let page = self.run_page(page_size).await?;
+            total += page.ledgers_written;
+            if page.caught_up {
+                return Ok(total);
+            }
+        }
+    }
+
+    /// Walk pages until caught up, then check for new ledgers every
+    /// `interval_seconds` until shutdown or cancellation. A page that was not
+    /// the last one is followed immediately by the next.
+    pub async fn run(
+        self: Arc<Self>,
+        config: LedgerBackfillConfig,
+        mut shutdown_rx: broadcast::Receiver<()>,
+    ) {
+        if !config.enabled {
+            info!("Ledger backfill is disabled");
+            return;
+        }
+
+        let mut interval = tokio::time::interval(Duration::from_secs(config.interval_seconds));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    match self.run_page(config.page_size).await {
+                        Ok(page) if !page.caught_up => interval.reset_immediately(),
+                        Ok(_) => {}
+                        Err(e) => error!("Ledger backfill failed: {}", e),
+                    }
+                }
+                () = self.cancel.cancelled() => {
+                    info!("Ledger backfill cancelled");
+                    self.progress.write().await.cancelled = true;
+                    break;
+                }
+                _ = shutdown_rx.recv() => {
+                    info!("Ledger backfill shutting down");
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Write a page of ledgers and advance the checkpoint in one transaction.
+    async fn commit_page(
+        &self,
+        ledgers: &[RpcLedger],
+        last_ledger: u64,
+        cursor: Option<&str>,
+    ) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+
+        for ledger in ledgers {
+            let ts: i64 = ledger.ledger_close_time.parse().unwrap_or(0);
+            let close_time = Utc.timestamp_opt(ts, 0).single().unwrap_or_else(Utc::now);
+            sqlx::query(
+                r"
+                INSERT INTO ledgers (sequence, hash, close_time, transaction_count, operation_count)
+                VALUES ($1, $2, $3, 0, 0)
+                ON CONFLICT (sequence) DO NOTHING
+                ",
+            )
+            .bind(ledger.sequence as i64)
+            .bind(&ledger.hash)
+            .bind(close_time)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to insert ledger")?;
+        }
+
+        sqlx::query(
+            r"
+            INSERT INTO backfill_state (id, current_ledger, cursor, updated_at)
+            VALUES ($1, $2, $3, CURRENT_TIMESTAMP)
+            ON CONFLICT (id) DO UPDATE SET
+                current_ledger = EXCLUDED.current_ledger,
+                cursor = EXCLUDED.cursor,
+                updated_at = CURRENT_TIMESTAMP
+            ",
+        )
+        .bind(&self.checkpoint_id)
+        .bind(last_ledger as i64)
+        .bind(cursor)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to update backfill checkpoint")?;
+
+        tx.commit().await.context("Failed to commit transaction")?;
+        Ok(())
+    }
+
+    async fn update_progress(
+        &self,
+        last_ledger: Option<u64>,
+        health: &HealthResponse,
+        written: u64,
+    ) {
+        let mut progress = self.progress.write().await;
+        progress.last_ledger_sequence = last_ledger;
+        progress.oldest_ledger = health.oldest_ledger;
+        progress.latest_ledger = health.latest_ledger;
+        progress.remaining_ledgers = match last_ledger {
+            Some(seq) => health
+                .latest_ledger
+                .saturating_sub(seq.max(health.oldest_ledger)),
+            None => health.latest_ledger.saturating_sub(health.oldest_ledger) + 1,
+        };
+        progress.ledgers_processed += written;
+        progress.updated_at = Some(Utc::now());
+    }
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_pool() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        for migration in [
+            include_str!("../../migrations/033_create_backfill_state.sql"),
+            include_str!("../../migrations/036_add_backfill_state_cursor.sql"),
+        ] {
+            sqlx::query(migration).execute(&pool).await.unwrap();
+        }
+        sqlx::query(
+            r"
+            CREATE TABLE ledgers (
+                sequence INTEGER PRIMARY KEY,
+                hash TEXT NOT NULL,
+                close_time TEXT NOT NULL,
+                transaction_count INTEGER DEFAULT 0,
+                operation_count INTEGER DEFAULT 0,
+                created_at TEXT DEFAULT CURRENT_TIMESTAMP
+            )
+            ",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_second_run_resumes_from_checkpoint() {
+        let pool = setup_pool().await;
+        let client = Arc::new(StellarRpcClient::new_with_defaults(true));
+        let oldest = crate::rpc::mock_stellar::mock_health_response().oldest_ledger;
+
+        // First process writes a single page and then "stops".
+        let first = CheckpointedBackfill::new(Arc::clone(&client), pool.clone());
+        let page = first.run_page(10).await.unwrap();
+        assert_eq!(page.start_ledger, oldest);
+        assert_eq!(page.ledgers_written, 10);
+        assert!(!page.caught_up);
+        drop(first);
+
+        // A fresh instance picks up after the stored checkpoint.
+        let second = CheckpointedBackfill::new(client, pool.clone());
+        let checkpoint = second.load_checkpoint().await.unwrap().unwrap();
+        assert_eq!(checkpoint.last_ledger_sequence, oldest + 9);
+
+        let page = second.run_page(10).await.unwrap();
+        assert_eq!(page.start_ledger, oldest + 10);
+
+        let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM ledgers")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 20);
+
+        let progress = second.progress().read().await.clone();
+        assert_eq!(progress.last_ledger_sequence, Some(oldest + 19));
+        assert_eq!(progress.ledgers_processed, 10);
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_behind_retention_window_skips_to_oldest() {
+        let pool = setup_pool().await;
+        let health = crate::rpc::mock_stellar::mock_health_response();
+        sqlx::query(
+            "INSERT INTO backfill_state (id, current_ledger, cursor) VALUES ($1, $2, '42')",
+        )
+        .bind(DEFAULT_CHECKPOINT_ID)
+        .bind(42i64)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let client = Arc::new(StellarRpcClient::new_with_defaults(true));
+        let backfill = CheckpointedBackfill::new(client, pool);
+        let page = backfill.run_page(5).await.unwrap();
+        assert_eq!(page.start_ledger, health.oldest_ledger);
+        assert_eq!(page.ledgers_written, 5);
+        // The stale cursor was not used for the skipped-ahead page
+        let checkpoint = backfill.load_checkpoint().await.unwrap().unwrap();
+        assert_eq!(checkpoint.last_ledger_sequence, health.oldest_ledger + 4);
+    }
+
+    #[tokio::test]
+    async fn test_next_page_is_requested_with_stored_cursor() {
+        use crate::rpc::circuit_breaker::CircuitBreakerConfig;
+        use crate::rpc::test_server::{ScriptedResponse, TestServer};
+        use axum::http::StatusCode;
+
+        let pool = setup_pool().await;
+        sqlx::query("INSERT INTO backfill_state (id, current_ledger, cursor) VALUES ($1, $2, $3)")
+            .bind(DEFAULT_CHECKPOINT_ID)
+            .bind(1010i64)
+            .bind("cursor_1010")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let server = TestServer::start([
+            ScriptedResponse::healthy(),
+            ScriptedResponse::json(
+                StatusCode::OK,
+                r#"{"jsonrpc":"2.0","id":1,"result":{"ledgers":[{"hash":"hash_1011","sequence":1011,"ledgerCloseTime":"1734032457"}],"latestLedger":1100,"oldestLedger":1001,"cursor":"cursor_1011"}}"#,
+            ),
+        ])
+        .await;
+        let client = Arc::new(server.client(&CircuitBreakerConfig::default()));
+        let backfill = CheckpointedBackfill::new(client, pool);
+
+        let page = backfill.run_page(10).await.unwrap();
+        assert_eq!(page.start_ledger, 1011);
+        assert_eq!(page.ledgers_written, 1);
+
+        let request: serde_json::Value =
+            serde_json::from_slice(&server.request_bodies()[1]).unwrap();
+        assert_eq!(request["method"], "getLedgers");
+        assert_eq!(request["params"]["pagination"]["cursor"], "cursor_1010");
+
+        let checkpoint = backfill.load_checkpoint().await.unwrap().unwrap();
+        assert_eq!(checkpoint.last_ledger_sequence, 1011);
+        assert_eq!(checkpoint.cursor.as_deref(), Some("cursor_1011"));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_stops_run_with_checkpoint_committed() {
+        let pool = setup_pool().await;
+        let client = Arc::new(StellarRpcClient::new_with_defaults(true));
+        let backfill = Arc::new(CheckpointedBackfill::new(client, pool));
+
+        backfill.cancel().unwrap();
+        assert!(backfill.cancel().is_err());
+
+        let (_shutdown_tx, shutdown_rx) = broadcast::channel(1);
+        let config = LedgerBackfillConfig {
+            page_size: 10,
+            ..LedgerBackfillConfig::default()
+        };
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            Arc::clone(&backfill).run(config, shutdown_rx),
+        )
+        .await
+        .expect("cancelled backfill should stop");
+
+        let progress = backfill.progress().read().await.clone();
+        assert!(progress.cancelled);
+        // Any page written before the cancel was seen has its checkpoint
+        let checkpoint = backfill.load_checkpoint().await.unwrap();
+        assert_eq!(
+            checkpoint.map(|c| c.last_ledger_sequence),
+            progress.last_ledger_sequence
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_to_latest_reports_caught_up() {
+        let pool = setup_pool().await;
+        let health = crate::rpc::mock_stellar::mock_health_response();
+        let client = Arc::new(StellarRpcClient::new_with_defaults(true));
+        let backfill = CheckpointedBackfill::new(client, pool);
+
+        let total = backfill.run_to_latest(25).await.unwrap();
+        assert_eq!(total, health.latest_ledger - health.oldest_ledger + 1);
+
+        let page = backfill.run_page(25).await.unwrap();
+        assert!(page.caught_up);
+        assert_eq!(page.ledgers_written, 0);
+        assert_eq!(backfill.progress().read().await.remaining_ledgers, 0);
+    }
+}
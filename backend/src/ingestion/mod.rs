@@ -1,3 +1,4 @@
+pub mod backfill_checkpoint;
 pub mod ledger;
 
 use anyhow::Result;
@@ -5,10 +6,13 @@ use serde::Serialize;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio_retry::strategy::{jitter, ExponentialBackoff};
-use tokio_retry::Retry;
+use tokio_retry::RetryIf;
 use tracing::{info, warn};
 
+use crate::alerts::{ingestion_lag_safety_margin_from_env, AlertManager};
+use crate::analytics::operations::compute_anchor_operation_volume;
 use crate::database::Database;
+use crate::rpc::error::RpcError;
 use crate::rpc::StellarRpcClient;
 
 fn retry_strategy() -> impl Iterator<Item = Duration> {
@@ -21,12 +25,26 @@ fn retry_strategy() -> impl Iterator<Item = Duration> {
 pub struct DataIngestionService {
     rpc_client: Arc<StellarRpcClient>,
     db: Arc<Database>,
+    /// Receives ingestion lag alerts raised by status checks
+    alert_manager: Option<Arc<AlertManager>>,
 }
 
 impl DataIngestionService {
     #[must_use]
     pub const fn new(rpc_client: Arc<StellarRpcClient>, db: Arc<Database>) -> Self {
-        Self { rpc_client, db }
+        Self {
+            rpc_client,
+            db,
+            alert_manager: None,
+        }
+    }
+
+    /// Raise [`crate::alerts::AlertType::IngestionLag`] alerts on `alert_manager`
+    /// whenever the ingestion status is checked.
+    #[must_use]
+    pub fn with_alert_manager(mut self, alert_manager: Arc<AlertManager>) -> Self {
+        self.alert_manager = Some(alert_manager);
+        self
     }
 
     /// Sync all metrics from Stellar network
@@ -58,38 +76,40 @@ impl DataIngestionService {
     /// Process metrics for a single anchor
     async fn process_anchor_metrics(&self, account_id: &str) -> Result<()> {
         let client = &self.rpc_client;
-        let payments = Retry::spawn(retry_strategy(), || async {
-            client
-                .fetch_account_payments(account_id, 100)
-                .await
-                .map_err(|e| anyhow::anyhow!("{e}"))
-        })
+        let payments = RetryIf::start(
+            retry_strategy(),
+            || client.fetch_account_payments(account_id, 100),
+            RpcError::is_retryable,
+        )
         .await?;
 
         if payments.is_empty() {
             return Ok(());
         }
 
-        let mut successful = 0;
-        let failed = 0;
-        let mut total_volume = 0.0;
-        let settlement_times = Vec::new(); // Removed mut as it's never pushed to
-
-        for payment in &payments {
-            let amount: f64 = payment.get_amount().parse().unwrap_or(0.0);
-            total_volume += amount;
-
-            successful += 1;
-        }
+        // Counted per payment operation, so a multi-operation transaction
+        // issuing this anchor's asset contributes each of its payments
+        let volume = compute_anchor_operation_volume(&payments, &[])
+            .into_iter()
+            .find(|volume| volume.anchor_account == account_id);
+        let (total_transactions, successful, failed, total_volume) =
+            volume.map_or((0, 0, 0, 0.0), |volume| {
+                (
+                    volume.operation_count,
+                    volume.successful_operations,
+                    volume.failed_operations,
+                    volume.volume,
+                )
+            });
+        let settlement_times: Vec<i32> = Vec::new();
 
-        let total_transactions = i64::from(successful + failed);
         let success_rate = if total_transactions > 0 {
-            (f64::from(successful) / total_transactions as f64) * 100.0
+            (successful as f64 / total_transactions as f64) * 100.0
         } else {
             0.0
         };
 
-        let reliability_score = self.calculate_reliability_score(success_rate, i64::from(failed));
+        let reliability_score = self.calculate_reliability_score(success_rate, failed);
 
         let avg_settlement_time = if settlement_times.is_empty() {
             1000
@@ -109,8 +129,8 @@ impl DataIngestionService {
             .update_anchor_from_rpc(crate::database::AnchorRpcUpdate {
                 stellar_account: account_id.to_string(),
                 total_transactions,
-                successful_transactions: i64::from(successful),
-                failed_transactions: i64::from(failed),
+                successful_transactions: successful,
+                failed_transactions: failed,
                 total_volume_usd: total_volume,
                 avg_settlement_time_ms: avg_settlement_time,
                 reliability_score,
@@ -130,9 +150,11 @@ impl DataIngestionService {
     /// Get current network health status
     pub async fn get_network_health(&self) -> Result<NetworkHealth> {
         let client = &self.rpc_client;
-        let health = Retry::spawn(retry_strategy(), || async {
-            client.check_health().await.map_err(|e| anyhow::anyhow!("{e}"))
-        })
+        let health = RetryIf::start(
+            retry_strategy(),
+            || client.check_health(),
+            RpcError::is_retryable,
+        )
         .await?;
 
         Ok(NetworkHealth {
@@ -170,11 +192,27 @@ impl DataIngestionService {
 
         // We get network state
         let client = &self.rpc_client;
-        let health = Retry::spawn(retry_strategy(), || async {
-            client.check_health().await.map_err(|e| anyhow::anyhow!("{e}"))
-        })
+        let health = RetryIf::start(
+            retry_strategy(),
+            || client.check_health(),
+            RpcError::is_retryable,
+        )
         .await?;
 
+        if last_ingested > 0 {
+            crate::observability::metrics::set_ingestion_lag(health.latest_ledger, last_ingested);
+        }
+
+        if let Some(alert_manager) = &self.alert_manager {
+            if last_ingested > 0 {
+                alert_manager.check_ingestion_lag(
+                    &health,
+                    last_ingested,
+                    ingestion_lag_safety_margin_from_env(),
+                );
+            }
+        }
+
         Ok(IngestionStatus {
             last_ingested_ledger: last_ingested,
             network_latest_ledger: health.latest_ledger,
@@ -1,14 +1,15 @@
 use anyhow::Result;
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
 use sqlx::SqlitePool;
 use std::sync::Arc;
-use tokio::time::{interval, Duration as TokioDuration};
+use tokio::time::{interval, Duration as TokioDuration, Instant};
 use tracing::{info, warn};
 
 use crate::observability::job_metrics::JobMetricsCollector;
 
 use crate::models::asset_verification::VerifiedAsset;
-use crate::services::asset_verifier::AssetVerifier;
+use crate::services::asset_verifier::{AssetVerification, AssetVerifier};
 
 /// Configuration for asset revalidation job
 #[derive(Debug, Clone)]
@@ -34,17 +35,67 @@ impl Default for RevalidationConfig {
     }
 }
 
+impl RevalidationConfig {
+    /// Defaults overridden by `ASSET_REVALIDATION_ENABLED`,
+    /// `ASSET_REVALIDATION_INTERVAL_HOURS`, `ASSET_REVALIDATION_BATCH_SIZE`
+    /// and `ASSET_REVALIDATION_MAX_AGE_DAYS`.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        config.enabled = std::env::var("ASSET_REVALIDATION_ENABLED")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(config.enabled);
+        config.interval_hours = std::env::var("ASSET_REVALIDATION_INTERVAL_HOURS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .map_or(config.interval_hours, |h| h.max(1));
+        config.batch_size = std::env::var("ASSET_REVALIDATION_BATCH_SIZE")
+            .ok()
+            .and_then(|s| s.parse::<usize>().ok())
+            .map_or(config.batch_size, |b| b.clamp(1, 10_000));
+        config.max_age_days = std::env::var("ASSET_REVALIDATION_MAX_AGE_DAYS")
+            .ok()
+            .and_then(|s| s.parse::<i64>().ok())
+            .map_or(config.max_age_days, |d| d.max(0));
+        config
+    }
+}
+
+/// Outcome of one revalidation cycle, persisted to `asset_revalidation_runs`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, sqlx::FromRow)]
+pub struct RevalidationRun {
+    pub started_at: DateTime<Utc>,
+    pub assets_checked: i64,
+    pub revalidated: i64,
+    pub failures: i64,
+    pub duration_ms: i64,
+}
+
 /// Asset revalidation job
 pub struct AssetRevalidationJob {
     pool: SqlitePool,
     config: RevalidationConfig,
+    verifier: Option<Arc<dyn AssetVerification>>,
 }
 
 impl AssetRevalidationJob {
     /// Create a new asset revalidation job
     #[must_use]
     pub const fn new(pool: SqlitePool, config: RevalidationConfig) -> Self {
-        Self { pool, config }
+        Self {
+            pool,
+            config,
+            verifier: None,
+        }
+    }
+
+    /// Verify assets with `verifier` instead of the external sources used by
+    /// [`AssetVerifier`]
+    #[must_use]
+    pub fn with_verifier(mut self, verifier: Arc<dyn AssetVerification>) -> Self {
+        self.verifier = Some(verifier);
+        self
     }
 
     /// Start the revalidation job
@@ -76,9 +127,11 @@ impl AssetRevalidationJob {
         }
     }
 
-    /// Run a single revalidation cycle
-    async fn run_revalidation(&self) -> Result<()> {
+    /// Run a single revalidation cycle and record its outcome
+    async fn run_revalidation(&self) -> Result<RevalidationRun> {
         info!("Starting asset revalidation cycle");
+        let started_at = Utc::now();
+        let timer = Instant::now();
 
         let cutoff_date = Utc::now() - Duration::days(self.config.max_age_days);
 
@@ -96,14 +149,17 @@ impl AssetRevalidationJob {
         .fetch_all(&self.pool)
         .await?;
 
+        let assets_checked = assets.len() as i64;
         if assets.is_empty() {
             info!("No assets need revalidation");
-            return Ok(());
+        } else {
+            info!("Revalidating {} assets", assets.len());
         }
 
-        info!("Revalidating {} assets", assets.len());
-
-        let verifier = AssetVerifier::new(self.pool.clone())?;
+        let verifier = match &self.verifier {
+            Some(verifier) => verifier.clone(),
+            None => Arc::new(AssetVerifier::new(self.pool.clone())?),
+        };
         let mut success_count = 0;
         let mut failure_count = 0;
 
@@ -137,7 +193,48 @@ impl AssetRevalidationJob {
             success_count, failure_count
         );
 
-        Ok(())
+        let run = RevalidationRun {
+            started_at,
+            assets_checked,
+            revalidated: success_count,
+            failures: failure_count,
+            duration_ms: i64::try_from(timer.elapsed().as_millis()).unwrap_or(i64::MAX),
+        };
+        sqlx::query(
+            r"
+            INSERT INTO asset_revalidation_runs
+                (started_at, assets_checked, revalidated, failures, duration_ms)
+            VALUES (?, ?, ?, ?, ?)
+            ",
+        )
+        .bind(run.started_at)
+        .bind(run.assets_checked)
+        .bind(run.revalidated)
+        .bind(run.failures)
+        .bind(run.duration_ms)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(run)
+    }
+
+    /// Run one revalidation cycle now and report its outcome
+    pub async fn run_once(&self) -> Result<RevalidationRun> {
+        self.run_revalidation().await
+    }
+
+    /// Outcome of the most recent cycle, including runs from before a restart
+    pub async fn last_run(&self) -> Result<Option<RevalidationRun>> {
+        Ok(sqlx::query_as::<_, RevalidationRun>(
+            r"
+            SELECT started_at, assets_checked, revalidated, failures, duration_ms
+            FROM asset_revalidation_runs
+            ORDER BY id DESC
+            LIMIT 1
+            ",
+        )
+        .fetch_optional(&self.pool)
+        .await?)
     }
 
     /// Manually trigger revalidation for a specific asset
@@ -197,7 +294,7 @@ impl AssetRevalidationJob {
 }
 
 /// Statistics about asset revalidation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct RevalidationStats {
     pub total_assets: i64,
     pub needs_revalidation: i64,
@@ -209,6 +306,8 @@ pub struct RevalidationStats {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::asset_verification::VerificationResult;
+    use async_trait::async_trait;
 
     #[test]
     fn test_default_config() {
@@ -219,6 +318,76 @@ mod tests {
         assert_eq!(config.max_age_days, 7);
     }
 
+    #[test]
+    fn test_config_from_env_overrides() {
+        let _guard = crate::lock_env_test();
+        std::env::set_var("ASSET_REVALIDATION_INTERVAL_HOURS", "6");
+        std::env::set_var("ASSET_REVALIDATION_BATCH_SIZE", "25");
+        let config = RevalidationConfig::from_env();
+        std::env::remove_var("ASSET_REVALIDATION_INTERVAL_HOURS");
+        std::env::remove_var("ASSET_REVALIDATION_BATCH_SIZE");
+
+        assert_eq!(config.interval_hours, 6);
+        assert_eq!(config.batch_size, 25);
+        assert_eq!(config.max_age_days, 7);
+    }
+
+    #[tokio::test]
+    async fn test_run_once_persists_last_run() {
+        struct ScriptedVerifier;
+
+        #[async_trait]
+        impl AssetVerification for ScriptedVerifier {
+            async fn verify_asset(
+                &self,
+                asset_code: &str,
+                _asset_issuer: &str,
+            ) -> Result<VerificationResult> {
+                anyhow::ensure!(asset_code != "SCAM", "issuer unreachable");
+                Ok(VerificationResult {
+                    stellar_expert_verified: true,
+                    stellar_toml_verified: true,
+                    stellar_toml_data: None,
+                    anchor_registry_verified: false,
+                    trustline_count: 10,
+                    transaction_count: 0,
+                    total_volume_usd: 0.0,
+                })
+            }
+        }
+
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        for migration in [
+            include_str!("../../migrations/026_create_verified_assets.sql"),
+            include_str!("../../migrations/041_create_asset_revalidation_runs.sql"),
+        ] {
+            sqlx::query(migration).execute(&pool).await.unwrap();
+        }
+        for code in ["USDC", "EURC", "SCAM"] {
+            sqlx::query(
+                "INSERT INTO verified_assets (id, asset_code, asset_issuer, verification_status)
+                 VALUES (?, ?, 'GISSUER', 'unverified')",
+            )
+            .bind(code)
+            .bind(code)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let job = AssetRevalidationJob::new(pool.clone(), RevalidationConfig::default())
+            .with_verifier(Arc::new(ScriptedVerifier));
+        assert!(job.last_run().await.unwrap().is_none());
+        let run = job.run_once().await.unwrap();
+        assert_eq!(run.assets_checked, 3);
+        assert_eq!(run.revalidated, 2);
+        assert_eq!(run.failures, 1);
+
+        // A fresh job over the same database, as after a restart
+        let restarted = AssetRevalidationJob::new(pool, RevalidationConfig::default());
+        assert_eq!(restarted.last_run().await.unwrap(), Some(run));
+    }
+
     #[test]
     fn test_custom_config() {
         let config = RevalidationConfig {
@@ -15,15 +15,19 @@
 //!   overwhelming the upstream node.
 //! - **Idempotency**: events are inserted with `INSERT OR REPLACE` so re-running
 //!   a range is safe.
+//! - **Cancellation**: [`BackfillJob::cancel`] trips a [`CancellationToken`]
+//!   checked between pages; the worker commits its buffered events with a
+//!   checkpoint at the last processed ledger before exiting.
 
 use crate::rpc::StellarRpcClient;
 use crate::services::event_indexer::{EventIndexer, IndexedEvent};
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, PoisonError};
 use tokio::sync::RwLock;
 use tokio::time::{sleep, Duration};
+use tokio_util::sync::CancellationToken;
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
@@ -63,6 +67,10 @@ pub enum BackfillStatus {
     Completed,
     /// The last backfill was aborted due to an error.
     Failed,
+    /// A stop was requested; the worker is checkpointing before it exits.
+    Cancelling,
+    /// The last backfill was stopped by an operator.
+    Cancelled,
 }
 
 /// Snapshot of backfill progress exposed by the status endpoint.
@@ -84,7 +92,7 @@ pub struct BackfillState {
     pub gaps_detected: u64,
     /// When the current (or last) job started.
     pub started_at: Option<DateTime<Utc>>,
-    /// When the current (or last) job finished (success or failure).
+    /// When the current (or last) job finished (success, failure or cancellation).
     pub finished_at: Option<DateTime<Utc>>,
     /// Error message if status is `Failed`.
     pub error: Option<String>,
@@ -118,6 +126,8 @@ pub struct BackfillJob {
     event_indexer: Arc<EventIndexer>,
     rpc_client: Arc<StellarRpcClient>,
     state: BackfillStateRef,
+    /// Token for the current run; replaced on every start
+    cancel: Mutex<CancellationToken>,
 }
 
 impl BackfillJob {
@@ -132,6 +142,7 @@ impl BackfillJob {
             event_indexer,
             rpc_client,
             state,
+            cancel: Mutex::new(CancellationToken::new()),
         }
     }
 
@@ -160,7 +171,10 @@ impl BackfillJob {
         // Reject if already running
         {
             let state = self.state.read().await;
-            if state.status == BackfillStatus::Running {
+            if matches!(
+                state.status,
+                BackfillStatus::Running | BackfillStatus::Cancelling
+            ) {
                 anyhow::bail!("A backfill is already in progress");
             }
         }
@@ -211,14 +225,17 @@ impl BackfillJob {
         let rpc = Arc::clone(&self.rpc_client);
         let state_ref = Arc::clone(&self.state);
         let delay_ms = req.delay_ms.unwrap_or(DEFAULT_BACKFILL_DELAY_MS);
+        let cancel = CancellationToken::new();
+        *self.cancel.lock().unwrap_or_else(PoisonError::into_inner) = cancel.clone();
 
         tokio::spawn(async move {
-            let result = run_backfill(indexer, rpc, state_ref.clone(), req, gaps, delay_ms).await;
+            let result =
+                run_backfill(indexer, rpc, state_ref.clone(), req, gaps, delay_ms, cancel).await;
 
             let mut state = state_ref.write().await;
             state.finished_at = Some(Utc::now());
             match result {
-                Ok(()) => {
+                Ok(BackfillExit::Completed) => {
                     state.status = BackfillStatus::Completed;
                     info!(
                         events = state.events_indexed,
@@ -226,6 +243,14 @@ impl BackfillJob {
                         "Backfill completed"
                     );
                 }
+                Ok(BackfillExit::Cancelled) => {
+                    state.status = BackfillStatus::Cancelled;
+                    info!(
+                        checkpoint = state.current_ledger,
+                        ledgers = state.ledgers_processed,
+                        "Backfill cancelled"
+                    );
+                }
                 Err(e) => {
                     state.status = BackfillStatus::Failed;
                     state.error = Some(e.to_string());
@@ -237,6 +262,27 @@ impl BackfillJob {
         Ok(())
     }
 
+    /// Ask the running backfill to stop after its current page.
+    ///
+    /// Returns once the request is recorded; the status moves from
+    /// `cancelling` to `cancelled` when the worker has checkpointed and exited.
+    pub async fn cancel(&self) -> Result<()> {
+        let mut state = self.state.write().await;
+        if state.status != BackfillStatus::Running {
+            anyhow::bail!("No backfill is running");
+        }
+        state.status = BackfillStatus::Cancelling;
+        self.cancel
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .cancel();
+        info!(
+            current_ledger = state.current_ledger,
+            "Backfill cancellation requested"
+        );
+        Ok(())
+    }
+
     /// Return a snapshot of the current backfill state.
     pub async fn status(&self) -> BackfillState {
         self.state.read().await.clone()
@@ -245,6 +291,13 @@ impl BackfillJob {
 
 // ── Worker ────────────────────────────────────────────────────────────────────
 
+/// How the worker loop ended when it did not fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BackfillExit {
+    Completed,
+    Cancelled,
+}
+
 /// A contiguous ledger range with no indexed events.
 #[derive(Debug, Clone)]
 pub struct LedgerGap {
@@ -253,7 +306,8 @@ pub struct LedgerGap {
 }
 
 /// Core backfill loop. Iterates over detected gaps, fetches ledgers from the
-/// RPC in pages, and indexes any contract events found.
+/// RPC in pages, and indexes any contract events found. Stops before the next
+/// page once `cancel` is tripped.
 async fn run_backfill(
     indexer: Arc<EventIndexer>,
     rpc: Arc<StellarRpcClient>,
@@ -261,7 +315,8 @@ async fn run_backfill(
     req: BackfillRequest,
     gaps: Vec<LedgerGap>,
     delay_ms: u64,
-) -> Result<()> {
+    cancel: CancellationToken,
+) -> Result<BackfillExit> {
     // If no gaps were detected, still walk the full range to catch any events
     // that may have been missed (e.g. the table was empty).
     let ranges_to_process: Vec<(u64, u64)> = if gaps.is_empty() {
@@ -279,6 +334,10 @@ async fn run_backfill(
         let mut uncommitted_events = 0;
 
         loop {
+            if cancel.is_cancelled() {
+                break;
+            }
+
             // Fetch a page of ledgers
             let result = rpc
                 .fetch_ledgers(Some(current), BACKFILL_PAGE_SIZE, cursor.as_deref())
@@ -338,9 +397,12 @@ async fn run_backfill(
             cursor = result.cursor;
             current = last_ledger + 1;
 
-            // Rate-limit between pages
+            // Rate-limit between pages; a cancel request cuts the wait short
             if delay_ms > 0 {
-                sleep(Duration::from_millis(delay_ms)).await;
+                tokio::select! {
+                    () = sleep(Duration::from_millis(delay_ms)) => {}
+                    () = cancel.cancelled() => {}
+                }
             }
         }
 
@@ -354,9 +416,13 @@ async fn run_backfill(
                 return Err(e);
             }
         }
+
+        if cancel.is_cancelled() {
+            return Ok(BackfillExit::Cancelled);
+        }
     }
 
-    Ok(())
+    Ok(BackfillExit::Completed)
 }
 
 /// Extract [`IndexedEvent`]s from a single [`crate::rpc::stellar::RpcLedger`].
@@ -527,4 +593,103 @@ mod tests {
             .to_string()
             .contains("already in progress"));
     }
+
+    fn ledgers_page(sequences: std::ops::RangeInclusive<u64>) -> String {
+        let ledgers: Vec<_> = sequences
+            .clone()
+            .map(|seq| {
+                serde_json::json!({
+                    "hash": format!("hash_{seq}"),
+                    "sequence": seq,
+                    "ledgerCloseTime": "2026-01-01T00:00:00Z"
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {
+                "ledgers": ledgers,
+                "latestLedger": 2000,
+                "oldestLedger": 1000,
+                "cursor": sequences.end().to_string()
+            }
+        })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn cancel_checkpoints_and_stops_the_loop() {
+        use crate::database::Database;
+        use crate::db::schema::Schema;
+        use crate::rpc::circuit_breaker::CircuitBreakerConfig;
+        use crate::rpc::test_server::{ScriptedResponse, TestServer};
+        use axum::http::StatusCode;
+
+        let pool = sqlx::SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::query(Schema::CREATE_CONTRACT_EVENTS)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(include_str!(
+            "../../migrations/033_create_backfill_state.sql"
+        ))
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let server = TestServer::start([
+            ScriptedResponse::json(StatusCode::OK, &ledgers_page(1000..=1001)),
+            ScriptedResponse::json(StatusCode::OK, &ledgers_page(1002..=1003)),
+        ])
+        .await;
+        let indexer = Arc::new(EventIndexer::new(Arc::new(Database::new(pool.clone()))));
+        let rpc = Arc::new(server.client(&CircuitBreakerConfig::default()));
+        let state = Arc::new(RwLock::new(BackfillState::default()));
+        let job = BackfillJob::new(indexer, rpc, Arc::clone(&state));
+
+        job.start(BackfillRequest {
+            from_ledger: 1000,
+            to_ledger: 1010,
+            contract_id: None,
+            // Long enough that the worker is parked between pages when cancelled
+            delay_ms: Some(60_000),
+        })
+        .await
+        .unwrap();
+
+        let wait_for = |done: fn(&BackfillState) -> bool| {
+            let state = Arc::clone(&state);
+            async move {
+                tokio::time::timeout(Duration::from_secs(5), async {
+                    while !done(&*state.read().await) {
+                        sleep(Duration::from_millis(10)).await;
+                    }
+                })
+                .await
+                .expect("backfill did not reach the expected state");
+            }
+        };
+
+        wait_for(|s| s.ledgers_processed == 2).await;
+        job.cancel().await.unwrap();
+        assert_eq!(job.status().await.status, BackfillStatus::Cancelling);
+
+        wait_for(|s| s.status == BackfillStatus::Cancelled).await;
+        let status = job.status().await;
+        assert_eq!(status.current_ledger, 1001);
+        assert!(status.finished_at.is_some());
+        // The worker exited instead of fetching the second page
+        assert_eq!(server.hits(), 1);
+
+        let checkpoint: i64 =
+            sqlx::query_scalar("SELECT current_ledger FROM backfill_state WHERE id = 'default'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(checkpoint, 1001);
+
+        // Nothing is running any more, so a second cancel is rejected
+        assert!(job.cancel().await.is_err());
+    }
 }
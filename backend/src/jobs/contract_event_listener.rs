@@ -1,12 +1,15 @@
 use anyhow::Result;
-use std::sync::Arc;
-use tokio::time::{interval, Duration as TokioDuration};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use tokio::time::Duration as TokioDuration;
 use tracing::{debug, error, info};
 
 use crate::observability::job_metrics::JobMetricsCollector;
 
 use crate::database::Database;
-use crate::services::contract_listener::ListenerConfig;
+use crate::services::alert_service::AlertService;
+use crate::services::contract_listener::{
+    ContractEventListener, ContractEventSource, ListenerConfig,
+};
 use crate::services::event_indexer::EventIndexer;
 
 /// Configuration for contract event listener job
@@ -22,6 +25,8 @@ pub struct ContractEventListenerConfig {
     pub rpc_url: String,
     /// Start ledger number (optional)
     pub start_ledger: Option<u64>,
+    /// Upper bound on the exponential backoff after RPC errors, in seconds
+    pub max_backoff_seconds: u64,
 }
 
 impl Default for ContractEventListenerConfig {
@@ -36,21 +41,52 @@ impl Default for ContractEventListenerConfig {
             start_ledger: std::env::var("CONTRACT_EVENT_START_LEDGER")
                 .ok()
                 .and_then(|s| s.parse().ok()),
+            max_backoff_seconds: 300,
         }
     }
 }
 
+/// Polling progress, kept across failed polls
+#[derive(Debug)]
+struct ListenerProgress {
+    /// Last ledger whose events have been indexed
+    cursor: Option<u64>,
+    events_processed: u64,
+    last_error: Option<String>,
+    consecutive_failures: u32,
+}
+
 /// Contract event listener background job
 pub struct ContractEventListenerJob {
     db: Arc<Database>,
     config: ContractEventListenerConfig,
+    source: Option<Arc<dyn ContractEventSource>>,
+    progress: Mutex<ListenerProgress>,
 }
 
 impl ContractEventListenerJob {
     /// Create a new contract event listener job
     #[must_use]
     pub const fn new(db: Arc<Database>, config: ContractEventListenerConfig) -> Self {
-        Self { db, config }
+        Self {
+            db,
+            config,
+            source: None,
+            progress: Mutex::new(ListenerProgress {
+                cursor: None,
+                events_processed: 0,
+                last_error: None,
+                consecutive_failures: 0,
+            }),
+        }
+    }
+
+    /// Read events from `source` instead of the Soroban RPC configured in
+    /// [`ContractEventListenerConfig`]
+    #[must_use]
+    pub fn with_event_source(mut self, source: Arc<dyn ContractEventSource>) -> Self {
+        self.source = Some(source);
+        self
     }
 
     /// Start the event listener job
@@ -65,84 +101,122 @@ impl ContractEventListenerJob {
         info!("RPC URL: {}", self.config.rpc_url);
         info!("Interval: {} seconds", self.config.interval_seconds);
 
-        let mut interval = interval(TokioDuration::from_secs(self.config.interval_seconds));
-        interval.tick().await; // Skip first immediate tick
-
-        // Create services
-        let event_indexer = Arc::new(EventIndexer::new(self.db.clone()));
-
-        let listener_config = ListenerConfig {
-            rpc_url: self.config.rpc_url.clone(),
-            contract_id: self.config.contract_id.clone(),
-            poll_interval_secs: self.config.interval_seconds,
-            start_ledger: self.config.start_ledger,
+        let source = if let Some(source) = &self.source {
+            source.clone()
+        } else {
+            let listener_config = ListenerConfig {
+                rpc_url: self.config.rpc_url.clone(),
+                contract_id: self.config.contract_id.clone(),
+                poll_interval_secs: self.config.interval_seconds,
+                start_ledger: self.config.start_ledger,
+            };
+            match ContractEventListener::new(
+                listener_config,
+                self.db.clone(),
+                Arc::new(AlertService::default()),
+            ) {
+                Ok(listener) => Arc::new(listener) as Arc<dyn ContractEventSource>,
+                Err(e) => {
+                    error!("Failed to create contract event listener: {}", e);
+                    return;
+                }
+            }
         };
+        let event_indexer = EventIndexer::new(self.db.clone());
+        let poll_interval = TokioDuration::from_secs(self.config.interval_seconds);
 
-        // Note: In a real implementation, the ContractEventListener would run continuously
-        // For this background job, we'll periodically check for missed events
+        let mut delay = poll_interval;
         loop {
-            interval.tick().await;
+            tokio::time::sleep(delay).await;
 
-            let _metrics = JobMetricsCollector::new("contract-event-listener");
-            let result = match self
-                .check_for_missed_events(&event_indexer, &listener_config)
-                .await
-            {
+            let metrics = JobMetricsCollector::new("contract-event-listener");
+            match self.poll_once(source.as_ref(), &event_indexer).await {
                 Ok(events_processed) => {
                     if events_processed > 0 {
-                        info!("Processed {} missed contract events", events_processed);
+                        info!("Processed {} contract events", events_processed);
                     }
-                    Ok(())
-                }
-                Err(e) => {
-                    error!("Error checking for missed events: {}", e);
-                    // Continue running despite errors
-                    Err(e)
-                }
-            };
-
-            match result {
-                Ok(_) => {
-                    _metrics.complete_success();
+                    metrics.complete_success();
+                    delay = poll_interval;
                 }
                 Err(e) => {
-                    _metrics.complete_failure(&e.to_string());
+                    // Keep running, but back off so an RPC outage isn't hammered
+                    delay = self.backoff_delay(self.progress().consecutive_failures);
+                    error!(
+                        "Error polling contract events, retrying in {:?}: {}",
+                        delay, e
+                    );
+                    metrics.complete_failure(&e.to_string());
                 }
             }
         }
     }
 
-    /// Check for missed events and process them
-    async fn check_for_missed_events(
+    /// Fetch and index events after the stored cursor, advancing the cursor
+    /// only once they have been indexed.
+    ///
+    /// On a fresh job the cursor is resumed from the indexer checkpoint, so a
+    /// restart picks up where the previous process stopped.
+    pub async fn poll_once(
         &self,
-        event_indexer: &Arc<EventIndexer>,
-        listener_config: &ListenerConfig,
+        source: &dyn ContractEventSource,
+        event_indexer: &EventIndexer,
     ) -> Result<usize> {
-        // Get the latest event from the database
-        let recent_events = event_indexer.get_event_stats().await?;
+        let result = self.fetch_and_index(source, event_indexer).await;
 
-        let start_ledger = if recent_events.total_events > 0 {
-            // Get the latest ledger from the database
-            recent_events.latest_ledger.unwrap_or(0) + 1
-        } else {
-            listener_config.start_ledger.unwrap_or(0)
+        let mut progress = self.progress();
+        match &result {
+            Ok(indexed) => {
+                progress.events_processed += *indexed as u64;
+                progress.consecutive_failures = 0;
+            }
+            Err(e) => {
+                progress.consecutive_failures = progress.consecutive_failures.saturating_add(1);
+                progress.last_error = Some(e.to_string());
+            }
+        }
+        result
+    }
+
+    async fn fetch_and_index(
+        &self,
+        source: &dyn ContractEventSource,
+        event_indexer: &EventIndexer,
+    ) -> Result<usize> {
+        let cursor = self.progress().cursor;
+        let cursor = match cursor {
+            Some(cursor) => Some(cursor),
+            None => event_indexer.get_last_processed_ledger().await?,
         };
+        let start_ledger = cursor.map_or(self.config.start_ledger.unwrap_or(0), |c| c + 1);
 
-        // In a real implementation, this would:
-        // 1. Query the Stellar RPC for events since start_ledger
-        // 2. Process each event through the event indexer
-        // 3. Update verification status for snapshots
-        // Current behavior uses: listener_config.rpc_url and listener_config.poll_interval_secs
+        debug!("Checking for contract events since ledger {}", start_ledger);
 
-        // For now, we'll just log that we're checking
-        debug!(
-            "Checking for events since ledger {} with poll interval {} seconds",
-            start_ledger, listener_config.poll_interval_secs
-        );
+        let page = source.fetch_contract_events(start_ledger).await?;
+        let indexed = event_indexer
+            .process_events(&page.events, page.protocol_version)
+            .await?;
+
+        let new_cursor = cursor.map_or(page.latest_ledger, |c| c.max(page.latest_ledger));
+        event_indexer.persist_checkpoint(new_cursor).await?;
+        self.progress().cursor = Some(new_cursor);
 
-        // Return 0 events processed for now
-        // In a real implementation, this would return the actual count
-        Ok(0)
+        Ok(indexed)
+    }
+
+    /// Delay before the next poll after `failures` consecutive errors:
+    /// the poll interval doubled per failure, capped at `max_backoff_seconds`
+    #[must_use]
+    pub fn backoff_delay(&self, failures: u32) -> TokioDuration {
+        let secs = self
+            .config
+            .interval_seconds
+            .saturating_mul(2u64.saturating_pow(failures))
+            .min(self.config.max_backoff_seconds);
+        TokioDuration::from_secs(secs)
+    }
+
+    fn progress(&self) -> MutexGuard<'_, ListenerProgress> {
+        self.progress.lock().unwrap_or_else(PoisonError::into_inner)
     }
 
     /// Get job statistics
@@ -150,6 +224,19 @@ impl ContractEventListenerJob {
         let event_indexer = Arc::new(EventIndexer::new(self.db.clone()));
         let event_stats = event_indexer.get_event_stats().await?;
 
+        let (cursor, events_processed, last_error) = {
+            let progress = self.progress();
+            (
+                progress.cursor,
+                progress.events_processed,
+                progress.last_error.clone(),
+            )
+        };
+        let cursor = match cursor {
+            Some(cursor) => Some(cursor),
+            None => event_indexer.get_last_processed_ledger().await?,
+        };
+
         Ok(ContractEventListenerStats {
             enabled: self.config.enabled,
             interval_seconds: self.config.interval_seconds,
@@ -161,6 +248,9 @@ impl ContractEventListenerJob {
             latest_epoch: event_stats.latest_epoch,
             latest_ledger: event_stats.latest_ledger,
             events_last_24h: event_stats.events_last_24h,
+            cursor,
+            events_processed,
+            last_error,
         })
     }
 }
@@ -178,6 +268,12 @@ pub struct ContractEventListenerStats {
     pub latest_epoch: Option<u64>,
     pub latest_ledger: Option<u64>,
     pub events_last_24h: i64,
+    /// Last ledger whose events have been indexed
+    pub cursor: Option<u64>,
+    /// Events indexed by this process since it started
+    pub events_processed: u64,
+    /// Most recent polling error, if any
+    pub last_error: Option<String>,
 }
 
 /// Create and start the contract event listener job
@@ -201,6 +297,57 @@ mod tests {
     use super::*;
     use crate::database::Database;
     use crate::db::schema::Schema;
+    use crate::services::contract_listener::ContractEventPage;
+    use crate::services::event_indexer::IndexedEvent;
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use std::collections::VecDeque;
+
+    /// Event source replaying scripted responses and recording each request
+    struct ScriptedSource {
+        responses: Mutex<VecDeque<Result<ContractEventPage>>>,
+        requested_from: Mutex<Vec<u64>>,
+    }
+
+    impl ScriptedSource {
+        fn new(responses: Vec<Result<ContractEventPage>>) -> Self {
+            Self {
+                responses: Mutex::new(responses.into()),
+                requested_from: Mutex::default(),
+            }
+        }
+
+        fn requested_from(&self) -> Vec<u64> {
+            self.requested_from.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl ContractEventSource for ScriptedSource {
+        async fn fetch_contract_events(&self, start_ledger: u64) -> Result<ContractEventPage> {
+            self.requested_from.lock().unwrap().push(start_ledger);
+            self.responses
+                .lock()
+                .unwrap()
+                .pop_front()
+                .unwrap_or_else(|| Err(anyhow::anyhow!("no scripted response")))
+        }
+    }
+
+    fn snapshot_event(id: &str, ledger: u64) -> IndexedEvent {
+        IndexedEvent {
+            id: id.to_string(),
+            contract_id: "test-contract".to_string(),
+            event_type: "SNAP_SUB".to_string(),
+            epoch: Some(1),
+            hash: Some("ab".repeat(32)),
+            timestamp: Some(1_700_000_000),
+            ledger,
+            transaction_hash: id.to_string(),
+            created_at: Utc::now(),
+            verification_status: None,
+        }
+    }
 
     async fn setup_contract_event_db() -> Arc<Database> {
         let pool = sqlx::SqlitePool::connect(":memory:").await.unwrap();
@@ -212,6 +359,10 @@ mod tests {
             .execute(&pool)
             .await
             .unwrap();
+        sqlx::query(Schema::CREATE_INDEXER_STATE)
+            .execute(&pool)
+            .await
+            .unwrap();
 
         Arc::new(Database::new(pool))
     }
@@ -249,4 +400,62 @@ mod tests {
         assert_eq!(stats.interval_seconds, 10);
         assert_eq!(stats.total_events, 0); // No events in empty database
     }
+
+    #[tokio::test]
+    async fn test_rpc_failure_then_recovery_advances_and_resumes_cursor() {
+        let db = setup_contract_event_db().await;
+        let indexer = EventIndexer::new(db.clone());
+        let config = ContractEventListenerConfig {
+            start_ledger: Some(100),
+            ..ContractEventListenerConfig::default()
+        };
+
+        let source = ScriptedSource::new(vec![
+            Err(anyhow::anyhow!("rpc unavailable")),
+            Ok(ContractEventPage {
+                events: vec![snapshot_event("evt-1", 105)],
+                latest_ledger: 110,
+                protocol_version: 21,
+            }),
+        ]);
+        let job = ContractEventListenerJob::new(db.clone(), config.clone());
+
+        assert!(job.poll_once(&source, &indexer).await.is_err());
+        let stats = job.get_stats().await.unwrap();
+        assert_eq!(stats.cursor, None);
+        assert_eq!(stats.last_error.as_deref(), Some("rpc unavailable"));
+        assert!(
+            job.backoff_delay(job.progress().consecutive_failures) > TokioDuration::from_secs(10)
+        );
+
+        assert_eq!(job.poll_once(&source, &indexer).await.unwrap(), 1);
+        let stats = job.get_stats().await.unwrap();
+        assert_eq!(stats.cursor, Some(110));
+        assert_eq!(stats.events_processed, 1);
+        assert_eq!(stats.total_events, 1);
+        assert_eq!(job.progress().consecutive_failures, 0);
+        // The failed poll is retried from the same ledger
+        assert_eq!(source.requested_from(), vec![100, 100]);
+
+        // A restarted job resumes after the stored cursor
+        let restarted = ContractEventListenerJob::new(db, config);
+        let source = ScriptedSource::new(vec![Ok(ContractEventPage {
+            latest_ledger: 115,
+            ..ContractEventPage::default()
+        })]);
+        assert_eq!(restarted.poll_once(&source, &indexer).await.unwrap(), 0);
+        assert_eq!(source.requested_from(), vec![111]);
+        assert_eq!(restarted.get_stats().await.unwrap().cursor, Some(115));
+    }
+
+    #[tokio::test]
+    async fn test_backoff_delay_doubles_up_to_cap() {
+        let db = setup_contract_event_db().await;
+        let job = ContractEventListenerJob::new(db, ContractEventListenerConfig::default());
+
+        assert_eq!(job.backoff_delay(0), TokioDuration::from_secs(10));
+        assert_eq!(job.backoff_delay(1), TokioDuration::from_secs(20));
+        assert_eq!(job.backoff_delay(3), TokioDuration::from_secs(80));
+        assert_eq!(job.backoff_delay(20), TokioDuration::from_secs(300));
+    }
 }
@@ -1,9 +1,13 @@
 pub mod asset_revalidation;
 pub mod backfill;
 pub mod contract_event_listener;
+pub mod price_refresh;
+pub mod retention;
 pub mod scheduler;
 
-pub use asset_revalidation::{AssetRevalidationJob, RevalidationConfig, RevalidationStats};
+pub use asset_revalidation::{
+    AssetRevalidationJob, RevalidationConfig, RevalidationRun, RevalidationStats,
+};
 pub use backfill::{
     BackfillJob, BackfillRequest, BackfillState, BackfillStateRef, BackfillStatus, LedgerGap,
 };
@@ -11,4 +15,8 @@ pub use contract_event_listener::{
     start_contract_event_listener_job, ContractEventListenerConfig, ContractEventListenerJob,
     ContractEventListenerStats,
 };
+pub use price_refresh::{
+    CachedPrice, PriceCache, PriceRefreshConfig, PriceRefreshJob, PriceRefreshStats, PriceSource,
+};
+pub use retention::{PrunedRows, RetentionConfig, RetentionJob, RetentionPolicy};
 pub use scheduler::{JobConfig, JobScheduler};
@@ -0,0 +1,381 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+
+use crate::observability::job_metrics::JobMetricsCollector;
+use crate::rpc::{Asset, StellarRpcClient, Trade};
+
+/// Circle's USDC on mainnet
+const DEFAULT_QUOTE_ASSET: &str = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+/// Recent trades scanned for a last-trade fallback price
+const TRADE_LOOKBACK: u32 = 200;
+
+/// Configuration for the price cache refresher
+#[derive(Debug, Clone)]
+pub struct PriceRefreshConfig {
+    pub enabled: bool,
+    pub interval_seconds: u64,
+    /// Age after which a cached price is reported as stale
+    pub ttl_seconds: u64,
+    /// Asset prices are quoted in, as `native` or `CODE:ISSUER`
+    pub quote_asset: String,
+    /// Assets to price, as `native` or `CODE:ISSUER`
+    pub assets: Vec<String>,
+}
+
+impl Default for PriceRefreshConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_seconds: 60,
+            ttl_seconds: 300,
+            quote_asset: DEFAULT_QUOTE_ASSET.to_string(),
+            assets: vec!["native".to_string()],
+        }
+    }
+}
+
+impl PriceRefreshConfig {
+    /// Defaults overridden by `PRICE_REFRESH_ENABLED`,
+    /// `PRICE_REFRESH_INTERVAL_SECONDS`, `PRICE_REFRESH_TTL_SECONDS`,
+    /// `PRICE_REFRESH_QUOTE_ASSET` and the comma-separated `PRICE_REFRESH_ASSETS`.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        config.enabled = std::env::var("PRICE_REFRESH_ENABLED")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(config.enabled);
+        config.interval_seconds = std::env::var("PRICE_REFRESH_INTERVAL_SECONDS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .map_or(config.interval_seconds, |s| s.max(10));
+        config.ttl_seconds = std::env::var("PRICE_REFRESH_TTL_SECONDS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(config.ttl_seconds);
+        if let Ok(quote) = std::env::var("PRICE_REFRESH_QUOTE_ASSET") {
+            config.quote_asset = quote.trim().to_string();
+        }
+        if let Ok(assets) = std::env::var("PRICE_REFRESH_ASSETS") {
+            config.assets = assets
+                .split(',')
+                .map(str::trim)
+                .filter(|a| !a.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+        config
+    }
+}
+
+/// Where a cached price came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceSource {
+    OrderBook,
+    LastTrade,
+}
+
+/// A cached price of one asset in the quote asset
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CachedPrice {
+    pub asset: String,
+    pub quote_asset: String,
+    pub price: f64,
+    pub source: PriceSource,
+    pub updated_at: DateTime<Utc>,
+    /// Older than the cache TTL; callers decide whether to trust it
+    pub stale: bool,
+}
+
+/// Latest known prices, written by [`PriceRefreshJob`] and read by request
+/// handlers without touching Horizon.
+#[derive(Debug)]
+pub struct PriceCache {
+    ttl: Duration,
+    prices: RwLock<HashMap<String, CachedPrice>>,
+}
+
+impl PriceCache {
+    #[must_use]
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            prices: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Cached price for `asset` (`native` or `CODE:ISSUER`), flagged stale
+    /// when older than the TTL.
+    #[must_use]
+    pub fn get_price(&self, asset: &str) -> Option<CachedPrice> {
+        let mut price = self
+            .prices
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(asset)
+            .cloned()?;
+        price.stale = self.is_stale(price.updated_at);
+        Some(price)
+    }
+
+    /// Every cached price, staleness included.
+    #[must_use]
+    pub fn all_prices(&self) -> Vec<CachedPrice> {
+        let prices = self.prices.read().unwrap_or_else(PoisonError::into_inner);
+        let mut all: Vec<CachedPrice> = prices
+            .values()
+            .cloned()
+            .map(|mut p| {
+                p.stale = self.is_stale(p.updated_at);
+                p
+            })
+            .collect();
+        all.sort_by(|a, b| a.asset.cmp(&b.asset));
+        all
+    }
+
+    pub(crate) fn insert(&self, price: CachedPrice) {
+        self.prices
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(price.asset.clone(), price);
+    }
+
+    fn is_stale(&self, updated_at: DateTime<Utc>) -> bool {
+        (Utc::now() - updated_at)
+            .to_std()
+            .is_ok_and(|age| age > self.ttl)
+    }
+}
+
+/// Outcome of one refresh cycle
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PriceRefreshStats {
+    pub from_order_book: usize,
+    pub from_last_trade: usize,
+    /// Assets with neither a two-sided book nor a recent trade
+    pub unpriced: usize,
+}
+
+/// Periodically refreshes [`PriceCache`] from Horizon order books, falling
+/// back to the most recent trade for pairs with an empty side.
+pub struct PriceRefreshJob {
+    rpc_client: Arc<StellarRpcClient>,
+    cache: Arc<PriceCache>,
+    config: PriceRefreshConfig,
+}
+
+impl PriceRefreshJob {
+    #[must_use]
+    pub fn new(rpc_client: Arc<StellarRpcClient>, config: PriceRefreshConfig) -> Self {
+        let cache = Arc::new(PriceCache::new(Duration::from_secs(config.ttl_seconds)));
+        Self {
+            rpc_client,
+            cache,
+            config,
+        }
+    }
+
+    /// Cache this job writes to, for handing to readers
+    #[must_use]
+    pub fn cache(&self) -> Arc<PriceCache> {
+        Arc::clone(&self.cache)
+    }
+
+    /// Run every `interval_seconds` until shutdown is signalled.
+    pub async fn run(self: Arc<Self>, mut shutdown_rx: broadcast::Receiver<()>) {
+        if !self.config.enabled {
+            info!("Price refresh job is disabled");
+            return;
+        }
+
+        let mut interval = tokio::time::interval(Duration::from_secs(self.config.interval_seconds));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown_rx.recv() => break,
+            }
+
+            let metrics = JobMetricsCollector::new("price-refresh");
+            match self.refresh_once().await {
+                Ok(_) => metrics.complete_success(),
+                Err(e) => metrics.complete_failure(&e.to_string()),
+            }
+        }
+    }
+
+    /// Price every configured asset once.
+    pub async fn refresh_once(&self) -> Result<PriceRefreshStats> {
+        let quote = Asset::parse(&self.config.quote_asset).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Invalid PRICE_REFRESH_QUOTE_ASSET '{}'",
+                self.config.quote_asset
+            )
+        })?;
+        let mut stats = PriceRefreshStats::default();
+        // Fetched at most once per cycle, and only if some book is one-sided
+        let mut recent_trades: Option<Vec<Trade>> = None;
+
+        for asset_id in &self.config.assets {
+            let Some(asset) = Asset::parse(asset_id) else {
+                warn!(asset = %asset_id, "Skipping unparseable asset in PRICE_REFRESH_ASSETS");
+                stats.unpriced += 1;
+                continue;
+            };
+
+            let mid = match self.rpc_client.fetch_order_book(&asset, &quote, 20).await {
+                Ok(book) => book.mid_price(),
+                Err(e) => {
+                    debug!(asset = %asset_id, error = %e, "Order book fetch failed");
+                    None
+                }
+            };
+            let (price, source) = if let Some(mid) = mid {
+                (mid, PriceSource::OrderBook)
+            } else {
+                if recent_trades.is_none() {
+                    recent_trades = Some(self.rpc_client.fetch_trades(TRADE_LOOKBACK, None).await?);
+                }
+                let trades = recent_trades.as_deref().unwrap_or_default();
+                match last_trade_price(trades, asset_id, &self.config.quote_asset) {
+                    Some(price) => (price, PriceSource::LastTrade),
+                    None => {
+                        stats.unpriced += 1;
+                        continue;
+                    }
+                }
+            };
+
+            match source {
+                PriceSource::OrderBook => stats.from_order_book += 1,
+                PriceSource::LastTrade => stats.from_last_trade += 1,
+            }
+            self.cache.insert(CachedPrice {
+                asset: asset_id.clone(),
+                quote_asset: self.config.quote_asset.clone(),
+                price,
+                source,
+                updated_at: Utc::now(),
+                stale: false,
+            });
+        }
+
+        info!(
+            from_order_book = stats.from_order_book,
+            from_last_trade = stats.from_last_trade,
+            unpriced = stats.unpriced,
+            "Refreshed price cache"
+        );
+        Ok(stats)
+    }
+}
+
+fn asset_id(asset_type: &str, code: Option<&str>, issuer: Option<&str>) -> String {
+    match (asset_type, code, issuer) {
+        ("native", _, _) => "native".to_string(),
+        (_, Some(code), Some(issuer)) => format!("{code}:{issuer}"),
+        _ => String::new(),
+    }
+}
+
+/// Price of `asset` in `quote` from the newest matching trade in a
+/// newest-first page, in either orientation.
+fn last_trade_price(trades: &[Trade], asset: &str, quote: &str) -> Option<f64> {
+    trades.iter().find_map(|trade| {
+        let base = asset_id(
+            &trade.base_asset_type,
+            trade.base_asset_code.as_deref(),
+            trade.base_asset_issuer.as_deref(),
+        );
+        let counter = asset_id(
+            &trade.counter_asset_type,
+            trade.counter_asset_code.as_deref(),
+            trade.counter_asset_issuer.as_deref(),
+        );
+        let (n, d) = (trade.price.n as f64, trade.price.d as f64);
+        if n <= 0.0 || d <= 0.0 {
+            return None;
+        }
+        if base == asset && counter == quote {
+            Some(n / d)
+        } else if base == quote && counter == asset {
+            Some(d / n)
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::{MockFixtures, OrderBook};
+
+    fn job(fixtures: MockFixtures, ttl_seconds: u64) -> PriceRefreshJob {
+        let client = StellarRpcClient::new_with_defaults(true).with_mock_fixtures(fixtures);
+        PriceRefreshJob::new(
+            Arc::new(client),
+            PriceRefreshConfig {
+                ttl_seconds,
+                quote_asset: "USDC:GBXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX".to_string(),
+                assets: vec!["native".to_string(), "EURC:GEURISSUER".to_string()],
+                ..PriceRefreshConfig::default()
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_refresh_populates_cache_from_order_books() {
+        let job = job(MockFixtures::default(), 300);
+        assert!(job.cache().get_price("native").is_none());
+
+        let stats = job.refresh_once().await.unwrap();
+        assert_eq!(stats.from_order_book, 2);
+
+        let cache = job.cache();
+        let xlm = cache.get_price("native").unwrap();
+        assert_eq!(xlm.source, PriceSource::OrderBook);
+        assert!((xlm.price - 1.0).abs() < 1e-9);
+        assert!(!xlm.stale);
+        assert!(cache.get_price("EURC:GEURISSUER").is_some());
+        assert_eq!(cache.all_prices().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_one_sided_book_falls_back_to_last_trade() {
+        let one_sided = OrderBook {
+            bids: Vec::new(),
+            ..crate::rpc::mock_stellar::mock_order_book(
+                &Asset::parse("native").unwrap(),
+                &Asset::parse("USDC:GB").unwrap(),
+            )
+        };
+        let job = job(
+            MockFixtures {
+                order_book: Some(one_sided),
+                ..MockFixtures::default()
+            },
+            0,
+        );
+
+        let stats = job.refresh_once().await.unwrap();
+        // Mock trades are native/USDC only, so EURC has no price
+        assert_eq!(stats.from_last_trade, 1);
+        assert_eq!(stats.unpriced, 1);
+
+        let xlm = job.cache().get_price("native").unwrap();
+        assert_eq!(xlm.source, PriceSource::LastTrade);
+        assert!((xlm.price - 2.0).abs() < 1e-9);
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert!(job.cache().get_price("native").unwrap().stale);
+    }
+}
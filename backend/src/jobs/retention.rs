@@ -0,0 +1,315 @@
+use anyhow::Result;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::info;
+
+use crate::observability::job_metrics::JobMetricsCollector;
+
+/// Age-based pruning rule for one raw-data table.
+///
+/// Aggregates and snapshots are deliberately never listed here.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    pub table: &'static str,
+    /// Column holding the row's timestamp
+    pub timestamp_column: &'static str,
+    /// Extra filter a row must match to be pruned
+    pub condition: Option<&'static str>,
+    pub retention_days: u32,
+}
+
+/// Configuration for the data retention job
+#[derive(Debug, Clone)]
+pub struct RetentionConfig {
+    pub enabled: bool,
+    pub interval_seconds: u64,
+    /// Rows deleted per statement, so no single delete holds the lock for long
+    pub batch_size: i64,
+    pub policies: Vec<RetentionPolicy>,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_seconds: 3600,
+            batch_size: 1000,
+            policies: vec![
+                RetentionPolicy {
+                    table: "payments",
+                    timestamp_column: "created_at",
+                    condition: None,
+                    retention_days: 90,
+                },
+                RetentionPolicy {
+                    table: "trades",
+                    timestamp_column: "ledger_close_time",
+                    condition: None,
+                    retention_days: 90,
+                },
+                RetentionPolicy {
+                    table: "webhook_events",
+                    timestamp_column: "created_at",
+                    condition: Some("status = 'delivered'"),
+                    retention_days: 30,
+                },
+            ],
+        }
+    }
+}
+
+impl RetentionConfig {
+    /// Defaults overridden by `RETENTION_ENABLED`, `RETENTION_INTERVAL_SECONDS`,
+    /// `RETENTION_BATCH_SIZE` and `RETENTION_<TABLE>_DAYS`; a window of 0
+    /// disables pruning for that table.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        config.enabled = std::env::var("RETENTION_ENABLED")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(config.enabled);
+        config.interval_seconds = std::env::var("RETENTION_INTERVAL_SECONDS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .map_or(config.interval_seconds, |s| s.max(60));
+        config.batch_size = std::env::var("RETENTION_BATCH_SIZE")
+            .ok()
+            .and_then(|s| s.parse::<i64>().ok())
+            .map_or(config.batch_size, |s| s.clamp(1, 100_000));
+        for policy in &mut config.policies {
+            let var = format!("RETENTION_{}_DAYS", policy.table.to_uppercase());
+            if let Some(days) = std::env::var(var).ok().and_then(|s| s.parse().ok()) {
+                policy.retention_days = days;
+            }
+        }
+        config
+    }
+
+    /// Days of rows `table` keeps, or `None` when it is never pruned
+    #[must_use]
+    pub fn retention_days(&self, table: &str) -> Option<u32> {
+        if !self.enabled {
+            return None;
+        }
+        self.policies
+            .iter()
+            .find(|policy| policy.table == table)
+            .map(|policy| policy.retention_days)
+            .filter(|&days| days > 0)
+    }
+}
+
+/// Rows pruned from one table in a single run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PrunedRows {
+    pub table: &'static str,
+    pub rows: u64,
+}
+
+/// Deletes raw rows that have aged out of their retention window
+pub struct RetentionJob {
+    pool: SqlitePool,
+    config: RetentionConfig,
+}
+
+impl RetentionJob {
+    #[must_use]
+    pub const fn new(pool: SqlitePool, config: RetentionConfig) -> Self {
+        Self { pool, config }
+    }
+
+    /// Run every `interval_seconds` until shutdown is signalled.
+    pub async fn run(self: Arc<Self>, mut shutdown_rx: broadcast::Receiver<()>) {
+        if !self.config.enabled {
+            info!("Data retention job is disabled");
+            return;
+        }
+
+        let mut interval = tokio::time::interval(Duration::from_secs(self.config.interval_seconds));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown_rx.recv() => break,
+            }
+
+            let metrics = JobMetricsCollector::new("data-retention");
+            match self.prune_once().await {
+                Ok(_) => metrics.complete_success(),
+                Err(e) => metrics.complete_failure(&e.to_string()),
+            }
+        }
+    }
+
+    /// Apply every enabled policy once, returning the rows pruned per table.
+    pub async fn prune_once(&self) -> Result<Vec<PrunedRows>> {
+        let mut pruned = Vec::new();
+        for policy in &self.config.policies {
+            if policy.retention_days == 0 {
+                continue;
+            }
+            let rows = self.prune_table(policy).await?;
+            info!(
+                table = policy.table,
+                rows,
+                retention_days = policy.retention_days,
+                "Pruned expired rows"
+            );
+            pruned.push(PrunedRows {
+                table: policy.table,
+                rows,
+            });
+        }
+        Ok(pruned)
+    }
+
+    async fn prune_table(&self, policy: &RetentionPolicy) -> Result<u64> {
+        let condition = policy
+            .condition
+            .map_or_else(String::new, |c| format!(" AND {c}"));
+        let sql = format!(
+            "DELETE FROM {table} WHERE rowid IN (
+                 SELECT rowid FROM {table}
+                 WHERE datetime({column}) < datetime('now', ?){condition}
+                 LIMIT ?
+             )",
+            table = policy.table,
+            column = policy.timestamp_column,
+        );
+        let max_age = format!("-{} days", policy.retention_days);
+
+        let mut total = 0;
+        loop {
+            let deleted = sqlx::query(&sql)
+                .bind(&max_age)
+                .bind(self.config.batch_size)
+                .execute(&self.pool)
+                .await?
+                .rows_affected();
+            total += deleted;
+            if deleted < self.config.batch_size.unsigned_abs() {
+                return Ok(total);
+            }
+            // Let other writers in between batches
+            tokio::task::yield_now().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_prune_once_only_removes_expired_rows() {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        for migration in [
+            include_str!("../../migrations/003_create_ingestion_and_payments.sql"),
+            include_str!("../../migrations/039_create_trades.sql"),
+        ] {
+            sqlx::query(migration).execute(&pool).await.unwrap();
+        }
+        sqlx::query(
+            "CREATE TABLE webhook_events (
+                id TEXT PRIMARY KEY,
+                status TEXT NOT NULL,
+                created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+            )",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        for (id, age_days) in [("old-1", 200), ("old-2", 91), ("old-3", 120), ("new", 1)] {
+            sqlx::query(
+                "INSERT INTO payments (id, transaction_hash, source_account, destination_account,
+                                       asset_type, amount, created_at)
+                 VALUES (?, 'tx', 'GA', 'GB', 'native', 1.0,
+                         strftime('%Y-%m-%dT%H:%M:%SZ', 'now', ?))",
+            )
+            .bind(id)
+            .bind(format!("-{age_days} days"))
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        sqlx::query(
+            "INSERT INTO corridor_metrics (corridor_key, asset_a_code, asset_a_issuer,
+                                           asset_b_code, asset_b_issuer, date)
+             VALUES ('USDC->XLM', 'USDC', 'GA', 'XLM', 'native', date('now', '-400 days'))",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO webhook_events (id, status, created_at) VALUES
+                 ('delivered-old', 'delivered', datetime('now', '-31 days')),
+                 ('failed-old', 'failed', datetime('now', '-31 days')),
+                 ('delivered-new', 'delivered', datetime('now', '-1 days'))",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let job = RetentionJob::new(
+            pool.clone(),
+            RetentionConfig {
+                batch_size: 2,
+                ..RetentionConfig::default()
+            },
+        );
+        let pruned = job.prune_once().await.unwrap();
+        assert_eq!(
+            pruned,
+            vec![
+                PrunedRows {
+                    table: "payments",
+                    rows: 3
+                },
+                PrunedRows {
+                    table: "trades",
+                    rows: 0
+                },
+                PrunedRows {
+                    table: "webhook_events",
+                    rows: 1
+                },
+            ]
+        );
+
+        let payments: Vec<(String,)> = sqlx::query_as("SELECT id FROM payments")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(payments, vec![("new".to_string(),)]);
+        let events: Vec<(String,)> = sqlx::query_as("SELECT id FROM webhook_events ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(
+            events,
+            vec![("delivered-new".to_string(),), ("failed-old".to_string(),)]
+        );
+        let (aggregates,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM corridor_metrics")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(aggregates, 1);
+    }
+
+    #[test]
+    fn test_retention_days_is_none_for_unpruned_tables() {
+        let mut config = RetentionConfig::default();
+        assert_eq!(config.retention_days("trades"), Some(90));
+        assert_eq!(config.retention_days("corridor_metrics"), None);
+
+        config.policies[1].retention_days = 0;
+        assert_eq!(config.retention_days("trades"), None);
+        config.enabled = false;
+        assert_eq!(config.retention_days("payments"), None);
+    }
+}
@@ -1,6 +1,10 @@
 use anyhow::Result;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, PoisonError};
 use std::time::Duration;
+use tokio::sync::broadcast;
 use tokio::task::JoinHandle;
 use tracing::info;
 
@@ -41,8 +45,113 @@ impl JobConfig {
     }
 }
 
+/// Future returned by an on-demand job, resolving to its JSON summary
+pub type TriggerFuture = Pin<Box<dyn Future<Output = Result<serde_json::Value>> + Send>>;
+type TriggerFn = Arc<dyn Fn() -> TriggerFuture + Send + Sync>;
+
+/// Result of triggering a job by name
+#[derive(Debug)]
+pub enum TriggerOutcome {
+    /// The job ran to completion; carries its summary (stats, counts)
+    Completed(serde_json::Value),
+    /// A previous trigger of the same job has not finished yet
+    AlreadyRunning,
+    UnknownJob,
+}
+
+/// Jobs that can be run on demand, e.g. from the admin API.
+///
+/// At most one manual run per job is in flight; concurrent triggers of the
+/// same job are coalesced into [`TriggerOutcome::AlreadyRunning`].
+#[derive(Default)]
+pub struct JobTriggers {
+    jobs: Mutex<HashMap<String, TriggerFn>>,
+    running: Arc<Mutex<HashSet<String>>>,
+}
+
+/// Clears a job's running flag when its run ends, even if it panics
+struct RunningGuard {
+    name: String,
+    running: Arc<Mutex<HashSet<String>>>,
+}
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        self.running
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&self.name);
+    }
+}
+
+impl JobTriggers {
+    pub fn register<F>(&self, name: &str, job_fn: F)
+    where
+        F: Fn() -> TriggerFuture + Send + Sync + 'static,
+    {
+        self.jobs
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(name.to_string(), Arc::new(job_fn));
+    }
+
+    /// Registered job names, sorted
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<_> = self
+            .jobs
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .keys()
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Run `name` now and wait for it, unless it is unknown or already running.
+    pub async fn trigger(&self, name: &str) -> Result<TriggerOutcome> {
+        let Some(job_fn) = self
+            .jobs
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(name)
+            .cloned()
+        else {
+            return Ok(TriggerOutcome::UnknownJob);
+        };
+
+        if !self
+            .running
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(name.to_string())
+        {
+            return Ok(TriggerOutcome::AlreadyRunning);
+        }
+        let _guard = RunningGuard {
+            name: name.to_string(),
+            running: Arc::clone(&self.running),
+        };
+
+        info!("Job '{}' triggered manually", name);
+        let metrics = JobMetricsCollector::new(name);
+        match job_fn().await {
+            Ok(summary) => {
+                metrics.complete_success();
+                Ok(TriggerOutcome::Completed(summary))
+            }
+            Err(e) => {
+                metrics.complete_failure(&e.to_string());
+                Err(e)
+            }
+        }
+    }
+}
+
 pub struct JobScheduler {
     handles: Vec<JoinHandle<()>>,
+    stop_tx: broadcast::Sender<()>,
+    triggers: Arc<JobTriggers>,
 }
 
 impl Default for JobScheduler {
@@ -53,12 +162,29 @@ impl Default for JobScheduler {
 
 impl JobScheduler {
     #[must_use]
-    pub const fn new() -> Self {
+    pub fn new() -> Self {
+        let (stop_tx, _) = broadcast::channel(1);
         Self {
             handles: Vec::new(),
+            stop_tx,
+            triggers: Arc::new(JobTriggers::default()),
         }
     }
 
+    /// Make a job runnable on demand through [`Self::triggers`].
+    pub fn register_trigger<F>(&self, name: &str, job_fn: F)
+    where
+        F: Fn() -> TriggerFuture + Send + Sync + 'static,
+    {
+        self.triggers.register(name, job_fn);
+    }
+
+    /// Shared handle for running registered jobs by name
+    #[must_use]
+    pub fn triggers(&self) -> Arc<JobTriggers> {
+        Arc::clone(&self.triggers)
+    }
+
     pub fn add_job<F>(&mut self, config: JobConfig, job_fn: F)
     where
         F: Fn() -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<()>> + Send>>
@@ -78,12 +204,20 @@ impl JobScheduler {
         let redis_url =
             std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
 
+        let mut stop_rx = self.stop_tx.subscribe();
         let handle = tokio::spawn(async move {
             let mut interval = tokio::time::interval(Duration::from_secs(config.interval_seconds));
             interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
 
             loop {
-                interval.tick().await;
+                // Only checked between runs, so an in-flight job always finishes.
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = stop_rx.recv() => {
+                        info!("Job '{}' stopped", config.name);
+                        break;
+                    }
+                }
                 let lock_key = format!("job-lock:{}", config.name);
                 // TTL is slightly shorter than the interval so the lock expires before
                 // the next tick, allowing any instance to acquire it next round.
@@ -179,4 +313,66 @@ impl JobScheduler {
             handle.abort();
         }
     }
+
+    /// Stop scheduling new runs and wait up to `timeout` for in-flight jobs.
+    pub async fn shutdown_gracefully(self, timeout: Duration) {
+        info!("Stopping job scheduler");
+        let _ = self.stop_tx.send(());
+        crate::shutdown::shutdown_background_tasks(self.handles, timeout).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::Notify;
+
+    #[tokio::test]
+    async fn test_concurrent_triggers_of_a_job_are_coalesced() {
+        let scheduler = JobScheduler::new();
+        let release = Arc::new(Notify::new());
+        let started = Arc::new(Notify::new());
+        {
+            let release = Arc::clone(&release);
+            let started = Arc::clone(&started);
+            scheduler.register_trigger("slow-job", move || {
+                let release = Arc::clone(&release);
+                let started = Arc::clone(&started);
+                Box::pin(async move {
+                    started.notify_one();
+                    release.notified().await;
+                    Ok(serde_json::json!({ "processed": 3 }))
+                })
+            });
+        }
+        let triggers = scheduler.triggers();
+
+        let first = tokio::spawn({
+            let triggers = Arc::clone(&triggers);
+            async move { triggers.trigger("slow-job").await }
+        });
+        started.notified().await;
+
+        assert!(matches!(
+            triggers.trigger("slow-job").await.unwrap(),
+            TriggerOutcome::AlreadyRunning
+        ));
+        assert!(matches!(
+            triggers.trigger("no-such-job").await.unwrap(),
+            TriggerOutcome::UnknownJob
+        ));
+
+        release.notify_one();
+        let TriggerOutcome::Completed(summary) = first.await.unwrap().unwrap() else {
+            panic!("first trigger should complete");
+        };
+        assert_eq!(summary["processed"], 3);
+
+        // Once finished the job can be triggered again
+        release.notify_one();
+        assert!(matches!(
+            triggers.trigger("slow-job").await.unwrap(),
+            TriggerOutcome::Completed(_)
+        ));
+    }
 }
@@ -1,5 +1,6 @@
 pub mod admin_audit_log;
 pub mod alerts;
+pub mod amount;
 pub mod analytics;
 pub mod api;
 pub mod api_analytics_middleware;
@@ -16,6 +17,7 @@ pub mod broadcast;
 pub mod cache;
 pub mod cache_invalidation;
 // cache_middleware removed in favor of cache helper APIs
+pub mod cors;
 pub mod crypto;
 pub mod database;
 
@@ -27,6 +29,7 @@ pub mod features;
 pub mod handlers; // Core handlers (pool_metrics, health_check, ingestion_status)
 pub mod health_check_enhanced; // Enhanced health check with mobile support
 pub mod http_cache; // HTTP caching layer (ETag/conditional responses)
+pub mod idempotency;
 pub mod ingestion;
 pub mod ip_whitelist_middleware;
 pub mod jobs;
@@ -48,6 +51,7 @@ pub mod request_id;
 pub mod services;
 pub mod shutdown;
 pub mod snapshot;
+pub mod snapshot_auth_middleware;
 pub mod state;
 pub mod validation;
 pub mod vault;
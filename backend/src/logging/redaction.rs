@@ -125,6 +125,35 @@ pub fn redact_token(token: &str) -> String {
     }
 }
 
+/// Redact transaction memo (show only memo type and length)
+///
+/// Accepts either a bare memo (treated as `text`) or a `type:value` pair
+/// using Horizon's memo types (`text`, `id`, `hash`, `return`).
+///
+/// Example: `text:hello world!` becomes `text:len=12`
+#[must_use]
+pub fn redact_memo(memo: &str) -> String {
+    let (memo_type, value) = match memo.split_once(':') {
+        Some((kind, value)) if matches!(kind, "text" | "id" | "hash" | "return") => (kind, value),
+        _ => ("text", memo),
+    };
+    if value.is_empty() {
+        return "[REDACTED]".to_string();
+    }
+    format!("{memo_type}:len={}", value.len())
+}
+
+/// Redact base64 XDR blob (show only first 8 chars and byte length)
+///
+/// Example: `AAAAAgAAAAB...` becomes `AAAAAgAA...(len=152)`
+#[must_use]
+pub fn redact_xdr(xdr: &str) -> String {
+    if xdr.len() <= 8 {
+        return "[REDACTED]".to_string();
+    }
+    format!("{}...(len={})", &xdr[..8], xdr.len())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -181,6 +210,22 @@ mod tests {
         assert_eq!(redact_token("abc"), "[REDACTED]");
     }
 
+    #[test]
+    fn test_redact_memo() {
+        assert_eq!(redact_memo("hello world!"), "text:len=12");
+        assert_eq!(redact_memo("text:hello world!"), "text:len=12");
+        assert_eq!(redact_memo("id:1234567890"), "id:len=10");
+        assert_eq!(redact_memo(""), "[REDACTED]");
+        assert_eq!(redact_memo("hash:"), "[REDACTED]");
+    }
+
+    #[test]
+    fn test_redact_xdr() {
+        let xdr = "AAAAAgAAAABkZWFkYmVlZmRlYWRiZWVmZGVhZGJlZWY=";
+        assert_eq!(redact_xdr(xdr), "AAAAAgAA...(len=44)");
+        assert_eq!(redact_xdr("AAAA"), "[REDACTED]");
+    }
+
     #[test]
     fn test_redacted_wrapper() {
         let secret = "my_secret_value";
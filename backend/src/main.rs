@@ -1,13 +1,10 @@
 use anyhow::Context;
 use axum::{
-    http::{
-        header::{AUTHORIZATION, CONTENT_TYPE},
-        HeaderValue, Method,
-    },
     middleware,
     routing::{get, post},
     Router,
 };
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::task::JoinHandle;
@@ -16,45 +13,72 @@ use tower_http::{
         predicate::{NotForContentType, Predicate, SizeAbove},
         CompressionLayer, CompressionLevel,
     },
-    cors::{AllowOrigin, CorsLayer},
     timeout::TimeoutLayer,
     trace::TraceLayer,
 };
 
 use stellar_insights_backend::{
-    api::v1::routes,
+    alerts::AlertManager,
+    api::{
+        admin::{require_admin, AdminAuth},
+        corridor_alert_config::{load_overrides, CorridorAlertConfigState},
+        snapshots::SnapshotAppState,
+        v1::routes,
+    },
     backup::{BackupConfig, BackupManager},
     cache::{CacheConfig, CacheManager},
+    cache_invalidation::CacheInvalidationService,
+    cors::{apply_cors, CorsConfig},
     database::{Database, PoolConfig},
     env_config,
     features::graphql_api::{
         graphql_handler, graphql_health_handler, GraphQLAPI, GraphQLAPIConfig,
     },
-    ingestion::DataIngestionService,
-    jobs::backfill::{BackfillJob, BackfillState},
+    idempotency::IdempotencyStore,
+    ingestion::{
+        backfill_checkpoint::{CheckpointedBackfill, LedgerBackfillConfig},
+        ledger::{LedgerIngestionConfig, LedgerIngestionService},
+        DataIngestionService,
+    },
+    jobs::{
+        backfill::{BackfillJob, BackfillState},
+        AssetRevalidationJob, JobScheduler, PriceRefreshConfig, PriceRefreshJob, RetentionConfig,
+        RetentionJob, RevalidationConfig,
+    },
     middleware::{
         concurrency_limit_middleware, panic_recovery_middleware, ApiVersioning, BatchEndpoints,
         ConcurrencyLimitState, DatabaseSchemaSeparation, DeprecationWarnings, ETagCachingSupport,
         FieldSelectionParameter, MobilePaginationEndpoints, MobileRequestLogging,
         NetworkAwareRpcClient, NetworkContextMiddleware, PushNotificationService,
         ResponseCompression, WebSocketRealTimeUpdates, PushNotificationRegistration,
-        Sep10ForMobile,
+        Sep10ForMobile, read_only_middleware, ReadOnlyMode,
     },
-    network::StellarNetwork,
+    network::{NetworkConfig, StellarNetwork},
     observability::logging::request_response_logging_middleware,
     observability::metrics as obs_metrics,
     observability::tracing::trace_propagation_middleware,
+    payload_limit::{limit_mutating_bodies, BodyLimitConfig},
     rate_limit::RateLimiter,
     request_id::request_id_middleware,
     rpc::StellarRpcClient,
     services::{
-        event_indexer::EventIndexer, service_container::ServiceContainer,
+        aggregation::{AggregationConfig, AggregationService},
+        anchor_monitor::AnchorMonitor,
+        claimable_balance_tracker::ClaimableBalanceTracker,
+        contract::ContractService,
+        event_indexer::EventIndexer,
+        service_container::ServiceContainer,
+        snapshot::SnapshotService,
+        snapshot_submission::{SnapshotSubmissionQueue, SubmissionQueueConfig},
+        stellar_toml::StellarTomlClient,
+        transfer_server_probe::{TransferProbeConfig, TransferServerProbe},
         webhook_dispatcher::WebhookDispatcher,
     },
     shutdown::{
-        flush_cache, log_shutdown_summary, shutdown_background_tasks, shutdown_database,
-        shutdown_signal, shutdown_websockets, wait_for_signal, ShutdownConfig, ShutdownCoordinator,
+        flush_cache, log_shutdown_summary, shutdown_signal, shutdown_websockets, ShutdownConfig,
+        ShutdownCoordinator,
     },
+    snapshot_auth_middleware::SnapshotSubmitters,
     state::AppState,
     websocket::WsState,
 };
@@ -103,13 +127,20 @@ async fn main() -> anyhow::Result<()> {
 
     let db = Arc::new(Database::new(pool.clone()));
 
+    // Shutdown coordinator; background loops subscribe so they stop ticking on SIGTERM/SIGINT
+    let shutdown_coordinator = Arc::new(ShutdownCoordinator::new(ShutdownConfig::from_env()));
+
     // Database pool metrics logger
     let pool_metrics_handle: JoinHandle<()> = {
         let pool_metrics_db = Arc::clone(&db);
+        let mut shutdown_rx = shutdown_coordinator.subscribe();
         tokio::spawn(async move {
             let mut interval = tokio::time::interval(DB_POOL_LOG_INTERVAL);
             loop {
-                interval.tick().await;
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = shutdown_rx.recv() => break,
+                }
                 let metrics = pool_metrics_db.pool_metrics();
                 tracing::info!(
                     pool_size = metrics.size,
@@ -133,10 +164,14 @@ async fn main() -> anyhow::Result<()> {
     // Pool exhaustion monitoring: warn at >90% utilization, update Prometheus gauges
     let pool_exhaustion_handle: JoinHandle<()> = {
         let monitor_pool = pool.clone();
+        let mut shutdown_rx = shutdown_coordinator.subscribe();
         tokio::spawn(async move {
             let mut interval = tokio::time::interval(Duration::from_secs(30));
             loop {
-                interval.tick().await;
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = shutdown_rx.recv() => break,
+                }
                 let size = monitor_pool.size();
                 let idle = monitor_pool.num_idle() as u32;
                 let active = size.saturating_sub(idle);
@@ -177,6 +212,7 @@ async fn main() -> anyhow::Result<()> {
         .unwrap_or_else(|_| "false".to_string())
         .parse::<bool>()
         .unwrap_or(false);
+    stellar_insights_backend::rpc::config::ensure_mock_mode_allowed(mock_mode)?;
 
     // Respect STELLAR_NETWORK rather than hardcoding mainnet — a testnet
     // deployment must not silently issue RPC calls against mainnet.
@@ -195,7 +231,12 @@ async fn main() -> anyhow::Result<()> {
 
     let ws_state = Arc::new(WsState::new());
     ws_state.spawn_redis_subscriber();
-    let ingestion = Arc::new(DataIngestionService::new(rpc_client.clone(), db.clone()));
+    let (alert_manager, _) = AlertManager::new();
+    let alert_manager = Arc::new(alert_manager);
+    let ingestion = Arc::new(
+        DataIngestionService::new(rpc_client.clone(), db.clone())
+            .with_alert_manager(Arc::clone(&alert_manager)),
+    );
 
     let app_state = AppState::new(
         db.clone(),
@@ -269,6 +310,56 @@ async fn main() -> anyhow::Result<()> {
         backfill_state,
     ));
 
+    // Snapshot generation and history under /api/snapshots. On-chain submission
+    // needs SNAPSHOT_CONTRACT_ID and STELLAR_SOURCE_SECRET_KEY.
+    let contract_service = match ContractService::from_env() {
+        Ok(service) => Some(Arc::new(service)),
+        Err(e) => {
+            tracing::warn!(
+                "Snapshot contract not configured, skipping submission: {}",
+                e
+            );
+            None
+        }
+    };
+    let snapshot_service = SnapshotService::new(
+        db.clone(),
+        rpc_client.clone(),
+        contract_service.clone(),
+        Some(Arc::new(EventIndexer::new(db.clone()))),
+    );
+    // Hashes are queued and sent by a background worker instead of inline
+    let (snapshot_service, submission_worker_handle) = match contract_service.clone() {
+        Some(contract) => {
+            let queue = Arc::new(SnapshotSubmissionQueue::new(
+                pool.clone(),
+                contract,
+                SubmissionQueueConfig::from_env(),
+            ));
+            let handle: JoinHandle<()> =
+                tokio::spawn(Arc::clone(&queue).start_worker(shutdown_coordinator.subscribe()));
+            (snapshot_service.with_submission_queue(queue), Some(handle))
+        }
+        None => (snapshot_service, None),
+    };
+    let snapshot_service = Arc::new(snapshot_service);
+    let snapshot_routes = stellar_insights_backend::api::snapshots::routes(
+        SnapshotAppState {
+            db: db.clone(),
+            contract_service,
+            snapshot_service,
+            idempotency: Arc::new(IdempotencyStore::from_env(pool.clone())),
+        },
+        SnapshotSubmitters::from_env(),
+    );
+
+    // Corridor alert thresholds, restored from corridor_alert_config and
+    // edited under /admin/alerts/corridors
+    match load_overrides(&pool, &alert_manager).await {
+        Ok(count) => tracing::info!("Loaded {} corridor alert overrides", count),
+        Err(e) => tracing::warn!("Failed to load corridor alert overrides: {}", e),
+    }
+
     let rate_limiter = Arc::new(
         RateLimiter::new_with_db(Some(pool.clone()))
             .await
@@ -282,6 +373,12 @@ async fn main() -> anyhow::Result<()> {
         "Concurrency limit initialized (MAX_IN_FLIGHT_REQUESTS env var, default 500)"
     );
 
+    // Read-only maintenance mode — rejects writes with 503 while enabled
+    let read_only_mode = ReadOnlyMode::from_env();
+    if read_only_mode.is_enabled() {
+        tracing::warn!("Starting in read-only mode (READ_ONLY=true)");
+    }
+
     // Configure rate limits for expensive operations
     use stellar_insights_backend::rate_limit::{ClientRateLimits, RateLimitConfig};
 
@@ -354,14 +451,14 @@ async fn main() -> anyhow::Result<()> {
             .ok()
             .and_then(|s| s.parse().ok())
             .unwrap_or(10);
+        let mut shutdown_rx = shutdown_coordinator.subscribe();
         tokio::spawn(async move {
             let mut restarts: u32 = 0;
             loop {
                 let dispatcher = WebhookDispatcher::new(webhook_pool.clone());
-                match dispatcher.run().await {
-                    Ok(()) => {
-                        tracing::warn!("Webhook dispatcher exited cleanly; restarting");
-                    }
+                match dispatcher.run(shutdown_rx.resubscribe()).await {
+                    // The dispatcher only returns Ok once shutdown has been signalled
+                    Ok(()) => break,
                     Err(e) => {
                         restarts += 1;
                         tracing::error!(
@@ -382,59 +479,100 @@ async fn main() -> anyhow::Result<()> {
                 // Exponential back-off capped at 60 s before restarting.
                 let backoff_secs = std::cmp::min(2u64.saturating_pow(restarts), 60);
                 tracing::info!("Restarting webhook dispatcher in {}s", backoff_secs);
-                tokio::time::sleep(std::time::Duration::from_secs(backoff_secs)).await;
+                tokio::select! {
+                    () = tokio::time::sleep(std::time::Duration::from_secs(backoff_secs)) => {}
+                    _ = shutdown_rx.recv() => break,
+                }
             }
         })
     };
 
-    // CORS configuration
-    let allowed_origins = std::env::var("CORS_ALLOWED_ORIGINS")
-        .unwrap_or_else(|_| "http://localhost:3000".to_string());
-    let wildcard_origins = allowed_origins.trim() == "*";
-    let origins: Vec<HeaderValue> = allowed_origins
-        .split(',')
-        .filter_map(|origin| {
-            let trimmed = origin.trim();
-            if trimmed == "*" {
-                return None;
-            }
-            match trimmed.parse::<HeaderValue>() {
-                Ok(value) => {
-                    tracing::info!("CORS: allowing origin '{}'", trimmed);
-                    Some(value)
-                }
-                Err(_) => {
-                    tracing::warn!(
-                        "CORS: skipping invalid origin '{}' — check CORS_ALLOWED_ORIGINS",
-                        trimmed
-                    );
-                    None
-                }
-            }
-        })
-        .collect();
+    // Prune raw payments, trades and delivered webhook events past their retention window
+    let retention_job = Arc::new(RetentionJob::new(pool.clone(), RetentionConfig::from_env()));
+    let retention_handle: JoinHandle<()> =
+        tokio::spawn(Arc::clone(&retention_job).run(shutdown_coordinator.subscribe()));
+
+    // Follow the ledger stream, storing polled trades for candles and claimable
+    // balances, dropping cached corridor aggregates as payments land and raising lag alerts as the cursor nears the retention edge
+    let ledger_ingestion = Arc::new(
+        LedgerIngestionService::new(
+            rpc_client.clone(),
+            Arc::clone(&fee_bump_tracker),
+            Arc::clone(&account_merge_detector),
+            pool.clone(),
+        )
+        .with_cache_invalidation(Arc::new(CacheInvalidationService::new(cache.clone())))
+        .with_claimable_balances(Arc::new(ClaimableBalanceTracker::new(
+            pool.clone(),
+            rpc_client.clone(),
+        )))
+        .with_aggregation(Arc::new(AggregationService::new(
+            db.clone(),
+            AggregationConfig::default(),
+        )))
+        .with_alert_manager(Arc::clone(&alert_manager)),
+    );
+    let ledger_ingestion_handle: JoinHandle<()> = tokio::spawn(ledger_ingestion.run(
+        LedgerIngestionConfig::from_env(),
+        shutdown_coordinator.subscribe(),
+    ));
 
-    if origins.is_empty() && !wildcard_origins {
-        tracing::warn!(
-            "CORS: no valid origins parsed from CORS_ALLOWED_ORIGINS='{}'. \
-             All cross-origin requests will be rejected.",
-            allowed_origins
-        );
-    }
+    // Fill in ledgers from the oldest one the RPC still retains, resuming
+    // from the checkpoint left by the previous process
+    let ledger_backfill = Arc::new(CheckpointedBackfill::new(rpc_client.clone(), pool.clone()));
+    let ledger_backfill_handle: JoinHandle<()> = tokio::spawn(Arc::clone(&ledger_backfill).run(
+        LedgerBackfillConfig::from_env(),
+        shutdown_coordinator.subscribe(),
+    ));
 
-    let allow_origin = if wildcard_origins {
-        tracing::info!("CORS: wildcard origin configured; mirroring request origin");
-        AllowOrigin::mirror_request()
-    } else {
-        AllowOrigin::list(origins)
-    };
+    // Alert on anchor metric changes and SEP-6/SEP-24 transfer server outages
+    let mut anchor_monitor =
+        AnchorMonitor::new(db.clone(), Arc::clone(&alert_manager), cache.clone());
+    let passphrase = NetworkConfig::for_network(stellar_network).network_passphrase;
+    let transfer_probe =
+        StellarTomlClient::new(Arc::new(tokio::sync::RwLock::new(None)), Some(passphrase))
+            .and_then(|toml_client| {
+                TransferServerProbe::new(Arc::new(toml_client), TransferProbeConfig::from_env())
+            });
+    match transfer_probe {
+        Ok(probe) => anchor_monitor = anchor_monitor.with_transfer_probe(Arc::new(probe)),
+        Err(e) => tracing::warn!("Transfer server probe disabled: {}", e),
+    }
+    let anchor_monitor_handle: JoinHandle<()> =
+        tokio::spawn(anchor_monitor.start(shutdown_coordinator.subscribe()));
 
-    let cors = CorsLayer::new()
-        .allow_origin(allow_origin)
-        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
-        .allow_headers([CONTENT_TYPE, AUTHORIZATION])
-        .allow_credentials(true)
-        .max_age(Duration::from_secs(3600));
+    // Cache a mid-price per configured asset so handlers skip per-request order book calls
+    let price_refresh_job = Arc::new(PriceRefreshJob::new(
+        rpc_client.clone(),
+        PriceRefreshConfig::from_env(),
+    ));
+    // Assets the price feed has no provider price for are valued from these books
+    price_feed.use_onchain_prices(price_refresh_job.cache());
+    let price_refresh_handle: JoinHandle<()> =
+        tokio::spawn(Arc::clone(&price_refresh_job).run(shutdown_coordinator.subscribe()));
+
+    // Jobs operators can run on demand via POST /admin/jobs/{name}/run
+    let job_scheduler = JobScheduler::new();
+    let revalidation_job = Arc::new(AssetRevalidationJob::new(
+        pool.clone(),
+        RevalidationConfig::from_env(),
+    ));
+    let revalidation_trigger_job = Arc::clone(&revalidation_job);
+    job_scheduler.register_trigger("asset-revalidation", move || {
+        let job = Arc::clone(&revalidation_trigger_job);
+        Box::pin(async move { Ok(serde_json::to_value(job.run_once().await?)?) })
+    });
+    job_scheduler.register_trigger("data-retention", move || {
+        let job = Arc::clone(&retention_job);
+        Box::pin(async move { Ok(serde_json::to_value(job.prune_once().await?)?) })
+    });
+    job_scheduler.register_trigger("price-refresh", move || {
+        let job = Arc::clone(&price_refresh_job);
+        Box::pin(async move { Ok(serde_json::to_value(job.refresh_once().await?)?) })
+    });
+
+    // CORS configuration (CORS_ALLOWED_ORIGINS etc.; `*` is dev-only)
+    let cors = CorsConfig::from_env();
 
     // Compression configuration
     let compression_min_size: u16 = std::env::var("COMPRESSION_MIN_SIZE")
@@ -488,7 +626,9 @@ async fn main() -> anyhow::Result<()> {
     let ws_routes = Router::new()
         .route("/ws", stellar_insights_backend::websocket::ws_route())
         .with_state(Arc::clone(&ws_state))
-        .layer(cors.clone());
+        .merge(stellar_insights_backend::api::alerts::ws_routes(
+            Arc::clone(&alert_manager),
+        ));
 
     let base_routes = routes(
         app_state.clone(),
@@ -499,13 +639,36 @@ async fn main() -> anyhow::Result<()> {
         lp_analyzer,
         price_feed,
         rate_limiter,
-        cors,
         pool.clone(),
         cache.clone(),
     );
 
-    // Admin routes (backfill, etc.) — mounted at /admin
-    let admin_routes = stellar_insights_backend::api::backfill::routes(backfill_job);
+    let corridor_alert_state = CorridorAlertConfigState {
+        pool: pool.clone(),
+        alert_manager: Arc::clone(&alert_manager),
+    };
+    // Admin routes (backfill, etc.) — mounted at /admin, all behind the admin bearer token
+    let admin_routes = require_admin(
+        stellar_insights_backend::api::backfill::routes(backfill_job)
+            .merge(stellar_insights_backend::api::backfill::ledger_routes(
+                ledger_backfill,
+            ))
+            .merge(stellar_insights_backend::middleware::read_only::routes(
+                read_only_mode.clone(),
+            ))
+            .merge(stellar_insights_backend::api::job_control::routes(
+                stellar_insights_backend::api::job_control::JobControlState::new(
+                    job_scheduler.triggers(),
+                )
+                .with_revalidation_job(revalidation_job),
+            ))
+            .merge(stellar_insights_backend::api::circuit_breakers::routes())
+            .merge(stellar_insights_backend::api::rpc_errors::routes())
+            .merge(
+                stellar_insights_backend::api::corridor_alert_config::routes(corridor_alert_state),
+            ),
+        AdminAuth::from_env(),
+    );
 
     let graphql_api = Arc::new(GraphQLAPI::new(GraphQLAPIConfig::default(), 0));
     let graphql_routes = Router::new()
@@ -513,14 +676,37 @@ async fn main() -> anyhow::Result<()> {
         .route("/graphql/health", get(graphql_health_handler))
         .layer(axum::Extension(Arc::clone(&graphql_api)));
 
+    let config_routes = stellar_insights_backend::api::runtime_config::routes(
+        stellar_insights_backend::api::runtime_config::RuntimeConfigState {
+            rpc_client: rpc_client.clone(),
+            cache: cache.clone(),
+            read_only: read_only_mode.clone(),
+        },
+    );
+
+    // Indexed contract events and snapshot reconciliation under /api/analytics
+    let contract_event_routes = stellar_insights_backend::api::contract_events::routes(Arc::new(
+        EventIndexer::new(db.clone()),
+    ));
+
     let app = base_routes
         .nest("/admin", admin_routes)
+        .nest("/api/snapshots", snapshot_routes)
+        .merge(contract_event_routes)
+        .merge(config_routes)
         .merge(graphql_routes)
         .merge(ws_routes)
-        .route("/swagger-ui/*path", get(|| async { "Swagger UI documentation" }))
+        .route("/swagger-ui/*path", get(|| async { "Swagger UI documentation" }));
+    // Bodies on mutating routes are capped well below the global payload limit,
+    // including snapshot submission and /admin
+    let app = limit_mutating_bodies(app, BodyLimitConfig::from_env())
         .layer(middleware::from_fn(
             stellar_insights_backend::payload_limit::payload_limit_middleware,
         ))
+        .layer(middleware::from_fn_with_state(
+            read_only_mode,
+            read_only_middleware,
+        ))
         .layer(middleware::from_fn(
             stellar_insights_backend::api_deprecation_middleware::deprecation_middleware,
         ))
@@ -542,7 +728,10 @@ async fn main() -> anyhow::Result<()> {
         .layer(middleware::from_fn(trace_propagation_middleware))
         .layer(TraceLayer::new_for_http())
         .layer(middleware::from_fn(obs_metrics::http_metrics_middleware))
-        .layer(middleware::from_fn(request_response_logging_middleware))
+        .layer(middleware::from_fn(request_response_logging_middleware));
+    // One CORS policy for every route, including /admin, /graphql and /ws. It
+    // sits outside the rejecting middleware so browsers can read their errors.
+    let app = apply_cors(app, cors)
         .layer(middleware::from_fn(request_id_middleware))
         .layer(timeout_layer)
         .layer(
@@ -562,58 +751,50 @@ async fn main() -> anyhow::Result<()> {
     tracing::info!("Server listening on {}", addr);
 
     let listener = tokio::net::TcpListener::bind(&addr).await?;
-    let start_shutdown = std::time::Instant::now();
-
-    // Shutdown coordinator
-    let shutdown_coordinator = Arc::new(ShutdownCoordinator::new(ShutdownConfig::from_env()));
 
     let mut background_tasks: Vec<JoinHandle<()>> = vec![
         pool_metrics_handle,
         pool_exhaustion_handle,
         webhook_dispatcher_handle,
+        retention_handle,
+        price_refresh_handle,
+        ledger_ingestion_handle,
+        ledger_backfill_handle,
+        anchor_monitor_handle,
     ];
+    background_tasks.extend(submission_worker_handle);
 
-    // Graceful shutdown handler
-    let shutdown_handler: JoinHandle<()> = {
-        let shutdown_pool = pool.clone();
-        let shutdown_cache = cache.clone();
-        let shutdown_ws_state = ws_state.clone();
-        let coordinator = shutdown_coordinator.clone();
-        tokio::spawn(async move {
-            wait_for_signal().await;
-            coordinator.trigger_shutdown();
-            shutdown_websockets(shutdown_ws_state, coordinator.background_task_timeout()).await;
-            flush_cache(shutdown_cache, coordinator.background_task_timeout()).await;
-            shutdown_database(shutdown_pool, coordinator.db_close_timeout()).await;
-        })
-    };
-
-    background_tasks.push(shutdown_handler);
-    // Clone references needed inside the graceful shutdown future
-    let shutdown_pool = pool.clone();
-    let shutdown_cache = cache.clone();
+    // One signal drives both axum's connection draining and background teardown
     let shutdown_ws_state = ws_state.clone();
+    let coordinator = shutdown_coordinator.clone();
+    // Peer addresses feed IP rate limiting and the bypass CIDR allowlist
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(async move {
+        shutdown_signal().await;
+        coordinator.trigger_shutdown();
+        shutdown_websockets(shutdown_ws_state, coordinator.background_task_timeout()).await;
+    })
+    .await?;
 
-    axum::serve(listener, app)
-        .with_graceful_shutdown(async move {
-            shutdown_signal().await;
-            let coordinator = shutdown_coordinator.clone();
-            coordinator.trigger_shutdown();
-            shutdown_websockets(shutdown_ws_state, coordinator.background_task_timeout()).await;
-            flush_cache(shutdown_cache, coordinator.background_task_timeout()).await;
-            shutdown_database(shutdown_pool, coordinator.db_close_timeout()).await;
-        })
-        .await?;
-
-    shutdown_background_tasks(
-        background_tasks,
-        ShutdownConfig::from_env().background_task_timeout,
+    let start_shutdown = std::time::Instant::now();
+    flush_cache(
+        cache.clone(),
+        shutdown_coordinator.background_task_timeout(),
     )
     .await;
+    shutdown_coordinator
+        .drain(
+            background_tasks,
+            pool.clone(),
+            stellar_insights_backend::observability::tracing::shutdown_tracing,
+        )
+        .await;
 
     log_shutdown_summary(start_shutdown);
     tracing::info!("Server shutdown complete");
-    stellar_insights_backend::observability::tracing::shutdown_tracing();
 
     Ok(())
 }
@@ -15,6 +15,7 @@ pub mod push_notification_service;
 pub mod push_notification_registration;
 pub mod sep10_for_mobile;
 pub mod rate_limiting_by_client;
+pub mod read_only;
 pub mod response_compression;
 pub mod websocket_real_time_updates;
 
@@ -37,5 +38,6 @@ pub use push_notification_service::PushNotificationService;
 pub use push_notification_registration::PushNotificationRegistration;
 pub use sep10_for_mobile::Sep10ForMobile;
 pub use rate_limiting_by_client::RateLimitingByClient;
+pub use read_only::{read_only_middleware, ReadOnlyMode};
 pub use response_compression::ResponseCompression;
 pub use websocket_real_time_updates::WebSocketRealTimeUpdates;
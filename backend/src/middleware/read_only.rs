@@ -0,0 +1,242 @@
+/// Maintenance (read-only) mode.
+///
+/// While enabled, safe methods (`GET`, `HEAD`, `OPTIONS`) are served as usual
+/// and every mutating request is rejected with `503 SERVICE_READ_ONLY`. The
+/// flag starts from `READ_ONLY` and can be flipped at runtime through
+/// `PUT /admin/read-only`, which is itself exempt from the check. Like the
+/// rest of `/admin` the toggle needs the admin bearer token (see
+/// [`require_admin`](crate::api::admin::require_admin)).
+use axum::{
+    extract::{Request, State},
+    http::Method,
+    middleware::Next,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use crate::error::{error_response_with_request_id, ApiError};
+
+/// Path of the toggle endpoint once mounted under `/admin`
+pub const READ_ONLY_ADMIN_PATH: &str = "/admin/read-only";
+
+/// Shared, runtime-changeable read-only flag.
+#[derive(Clone, Default)]
+pub struct ReadOnlyMode {
+    enabled: Arc<AtomicBool>,
+}
+
+impl ReadOnlyMode {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled: Arc::new(AtomicBool::new(enabled)),
+        }
+    }
+
+    /// Load from `READ_ONLY` env var (`true`/`1`); defaults to off.
+    pub fn from_env() -> Self {
+        let enabled = std::env::var("READ_ONLY")
+            .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "true" | "1"))
+            .unwrap_or(false);
+        Self::new(enabled)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+}
+
+/// Axum middleware function — call via `middleware::from_fn_with_state`.
+pub async fn read_only_middleware(
+    State(mode): State<ReadOnlyMode>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let is_safe = matches!(*req.method(), Method::GET | Method::HEAD | Method::OPTIONS);
+    if !mode.is_enabled() || is_safe || req.uri().path() == READ_ONLY_ADMIN_PATH {
+        return next.run(req).await;
+    }
+
+    tracing::info!(
+        method = %req.method(),
+        path = req.uri().path(),
+        "Read-only mode — rejecting write request"
+    );
+    let error = ApiError::service_unavailable(
+        "SERVICE_READ_ONLY",
+        "The service is in read-only maintenance mode. Please retry later.",
+    );
+    error_response_with_request_id(error, &req)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReadOnlyStatus {
+    pub enabled: bool,
+}
+
+/// GET /admin/read-only
+async fn get_read_only(State(mode): State<ReadOnlyMode>) -> Json<ReadOnlyStatus> {
+    Json(ReadOnlyStatus {
+        enabled: mode.is_enabled(),
+    })
+}
+
+/// PUT /admin/read-only
+async fn set_read_only(
+    State(mode): State<ReadOnlyMode>,
+    Json(status): Json<ReadOnlyStatus>,
+) -> impl IntoResponse {
+    mode.set_enabled(status.enabled);
+    tracing::warn!(enabled = status.enabled, "Read-only mode toggled");
+    Json(status)
+}
+
+/// Build the admin toggle router. Mount this at `/admin` behind
+/// [`require_admin`](crate::api::admin::require_admin).
+pub fn routes(mode: ReadOnlyMode) -> Router {
+    Router::new()
+        .route("/read-only", get(get_read_only).put(set_read_only))
+        .with_state(mode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::admin::{require_admin, AdminAuth};
+    use axum::{
+        body::Body,
+        http::{header::AUTHORIZATION, Request, StatusCode},
+        middleware,
+        routing::post,
+    };
+    use tower::ServiceExt;
+
+    const TOKEN: &str = "Bearer s3cret";
+
+    fn app(mode: ReadOnlyMode) -> Router {
+        Router::new()
+            .route(
+                "/api/snapshots",
+                get(|| async { StatusCode::OK }).post(|| async { StatusCode::CREATED }),
+            )
+            .route("/api/webhooks", post(|| async { StatusCode::CREATED }))
+            .nest(
+                "/admin",
+                require_admin(routes(mode.clone()), AdminAuth::new(Some("s3cret"))),
+            )
+            .layer(middleware::from_fn_with_state(mode, read_only_middleware))
+    }
+
+    async fn send_with(
+        app: &Router,
+        method: Method,
+        uri: &str,
+        body: Body,
+        auth: Option<&str>,
+    ) -> Response {
+        let mut request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json");
+        if let Some(auth) = auth {
+            request = request.header(AUTHORIZATION, auth);
+        }
+        app.clone()
+            .oneshot(request.body(body).unwrap())
+            .await
+            .unwrap()
+    }
+
+    async fn send(app: &Router, method: Method, uri: &str, body: Body) -> Response {
+        send_with(app, method, uri, body, Some(TOKEN)).await
+    }
+
+    #[tokio::test]
+    async fn test_read_only_rejects_post_but_serves_get() {
+        let app = app(ReadOnlyMode::new(true));
+
+        let response = send(&app, Method::GET, "/api/snapshots", Body::empty()).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = send(&app, Method::POST, "/api/webhooks", Body::empty()).await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["error"]["code"], "SERVICE_READ_ONLY");
+    }
+
+    #[tokio::test]
+    async fn test_read_only_can_be_toggled_at_runtime() {
+        let mode = ReadOnlyMode::default();
+        let app = app(mode.clone());
+
+        let response = send(&app, Method::POST, "/api/snapshots", Body::empty()).await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let response = send(
+            &app,
+            Method::PUT,
+            READ_ONLY_ADMIN_PATH,
+            Body::from(r#"{"enabled": true}"#),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(mode.is_enabled());
+
+        let response = send(&app, Method::POST, "/api/snapshots", Body::empty()).await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        // The toggle stays reachable while writes are blocked
+        let response = send(
+            &app,
+            Method::PUT,
+            READ_ONLY_ADMIN_PATH,
+            Body::from(r#"{"enabled": false}"#),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = send(&app, Method::POST, "/api/snapshots", Body::empty()).await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_toggle_requires_admin_token() {
+        let mode = ReadOnlyMode::default();
+        let app = app(mode.clone());
+
+        let response = send_with(
+            &app,
+            Method::PUT,
+            READ_ONLY_ADMIN_PATH,
+            Body::from(r#"{"enabled": true}"#),
+            None,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response =
+            send_with(&app, Method::GET, READ_ONLY_ADMIN_PATH, Body::empty(), None).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(!mode.is_enabled());
+    }
+
+    #[test]
+    fn test_read_only_from_env() {
+        let _guard = crate::lock_env_test();
+        std::env::set_var("READ_ONLY", "true");
+        assert!(ReadOnlyMode::from_env().is_enabled());
+        std::env::remove_var("READ_ONLY");
+        assert!(!ReadOnlyMode::from_env().is_enabled());
+    }
+}
@@ -31,6 +31,7 @@ pub mod rate_limiting_by_client;
 pub mod redis_caching_models;
 pub mod response_compression;
 pub mod service_mesh;
+pub mod trades;
 pub mod websocket_real_time_updates;
 pub mod websocket_streaming_models;
 
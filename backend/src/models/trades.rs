@@ -0,0 +1,96 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Supported candle widths
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CandleInterval {
+    #[serde(rename = "1m")]
+    OneMinute,
+    #[serde(rename = "5m")]
+    FiveMinutes,
+    #[serde(rename = "1h")]
+    OneHour,
+    #[serde(rename = "1d")]
+    OneDay,
+}
+
+impl CandleInterval {
+    #[must_use]
+    pub const fn seconds(self) -> i64 {
+        match self {
+            Self::OneMinute => 60,
+            Self::FiveMinutes => 5 * 60,
+            Self::OneHour => 60 * 60,
+            Self::OneDay => 24 * 60 * 60,
+        }
+    }
+
+    #[must_use]
+    pub fn duration(self) -> Duration {
+        Duration::seconds(self.seconds())
+    }
+
+    /// Start of the bucket containing `time` (buckets are aligned to the Unix epoch).
+    #[must_use]
+    pub fn bucket_start(self, time: DateTime<Utc>) -> DateTime<Utc> {
+        let secs = time.timestamp();
+        let start = secs - secs.rem_euclid(self.seconds());
+        Utc.timestamp_opt(start, 0).single().unwrap_or(time)
+    }
+}
+
+impl std::str::FromStr for CandleInterval {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "1m" => Ok(Self::OneMinute),
+            "5m" => Ok(Self::FiveMinutes),
+            "1h" => Ok(Self::OneHour),
+            "1d" => Ok(Self::OneDay),
+            other => Err(format!("unsupported candle interval: {other}")),
+        }
+    }
+}
+
+/// One OHLCV bucket for a trading pair.
+///
+/// Buckets without trades are still emitted so charts keep a regular time
+/// axis: their `open`/`high`/`low`/`close` are `null` and `volume` is 0.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Candle {
+    pub open_time: DateTime<Utc>,
+    pub open: Option<f64>,
+    pub high: Option<f64>,
+    pub low: Option<f64>,
+    pub close: Option<f64>,
+    /// Sum of `base_amount` over the bucket, or of its value in the quote
+    /// asset when the candles are quoted
+    pub volume: f64,
+    pub trade_count: i64,
+}
+
+impl Candle {
+    #[must_use]
+    pub const fn empty(open_time: DateTime<Utc>) -> Self {
+        Self {
+            open_time,
+            open: None,
+            high: None,
+            low: None,
+            close: None,
+            volume: 0.0,
+            trade_count: 0,
+        }
+    }
+
+    /// Fold a trade into the candle; trades must arrive in time order.
+    pub fn push(&mut self, price: f64, volume: f64) {
+        self.open.get_or_insert(price);
+        self.high = Some(self.high.map_or(price, |h| h.max(price)));
+        self.low = Some(self.low.map_or(price, |l| l.min(price)));
+        self.close = Some(price);
+        self.volume += volume;
+        self.trade_count += 1;
+    }
+}
@@ -1,36 +1,207 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
+use tokio::sync::broadcast;
 use tokio::time::{interval, Duration};
 
 #[cfg(test)]
 use std::sync::atomic::{AtomicU64, Ordering};
 
 use crate::alerts::AlertManager;
+use crate::amount::Amount;
 use crate::cache::CacheManager;
-use crate::rpc::StellarRpcClient;
+use crate::rpc::{Payment, StellarRpcClient};
 use crate::webhooks::events::CorridorMetrics;
 
+const DEFAULT_LIQUIDITY_WINDOW: usize = 15;
+const DEFAULT_LATENCY_EWMA_ALPHA: f64 = 0.3;
+
+/// Rolling-window settings for corridor liquidity drop detection. How far
+/// below the window's median counts as a drop is the corridor's
+/// `liquidity_decrease_factor` alert threshold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LiquidityDropConfig {
+    /// Number of recent liquidity samples kept per corridor
+    pub window: usize,
+}
+
+impl Default for LiquidityDropConfig {
+    fn default() -> Self {
+        Self {
+            window: DEFAULT_LIQUIDITY_WINDOW,
+        }
+    }
+}
+
+impl LiquidityDropConfig {
+    /// Load from `LIQUIDITY_WINDOW_SAMPLES`, falling back to the default for
+    /// missing or invalid values.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let window = std::env::var("LIQUIDITY_WINDOW_SAMPLES")
+            .ok()
+            .and_then(|s| s.parse::<usize>().ok())
+            .filter(|w| *w > 0)
+            .unwrap_or(DEFAULT_LIQUIDITY_WINDOW);
+        Self { window }
+    }
+}
+
+/// Smoothing for the per-corridor latency estimate used by latency alerts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatencyEwmaConfig {
+    /// Weight of the newest sample, in `(0, 1]`; 1.0 disables smoothing
+    pub alpha: f64,
+}
+
+impl Default for LatencyEwmaConfig {
+    fn default() -> Self {
+        Self {
+            alpha: DEFAULT_LATENCY_EWMA_ALPHA,
+        }
+    }
+}
+
+impl LatencyEwmaConfig {
+    /// Load from `LATENCY_EWMA_ALPHA`, falling back to the default for
+    /// missing or out-of-range values.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let alpha = std::env::var("LATENCY_EWMA_ALPHA")
+            .ok()
+            .and_then(|s| s.parse::<f64>().ok())
+            .filter(|a| *a > 0.0 && *a <= 1.0)
+            .unwrap_or(DEFAULT_LATENCY_EWMA_ALPHA);
+        Self { alpha }
+    }
+}
+
 pub struct CorridorMonitor {
     alert_manager: Arc<AlertManager>,
     cache: Arc<CacheManager>,
     rpc_client: Arc<StellarRpcClient>,
     previous_state: tokio::sync::RwLock<HashMap<String, CorridorState>>,
     webhook_event_service: Option<Arc<crate::services::webhook_event_service::WebhookEventService>>,
+    liquidity_config: LiquidityDropConfig,
+    latency_config: LatencyEwmaConfig,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
 struct CorridorState {
     success_rate: f64,
     latency: f64,
-    liquidity: f64,
+    liquidity: Amount,
+    /// Recent liquidity samples, oldest first, including `liquidity`
+    #[serde(default)]
+    liquidity_history: VecDeque<Amount>,
+    /// Exponentially weighted moving average of `latency`
+    #[serde(default)]
+    latency_ewma: Option<f64>,
+}
+
+impl CorridorState {
+    /// Median of the recent liquidity samples, or the last sample when no
+    /// history has been recorded yet.
+    fn liquidity_baseline(&self) -> Amount {
+        median(&self.liquidity_history).unwrap_or(self.liquidity)
+    }
+
+    /// Smoothed latency, or the last sample for states cached before the
+    /// average was tracked.
+    fn latency_ewma(&self) -> f64 {
+        self.latency_ewma.unwrap_or(self.latency)
+    }
+
+    /// Build the next state, appending `liquidity` to the rolling window and
+    /// folding `latency` into the moving average with weight `alpha`.
+    fn next(
+        previous: Option<&Self>,
+        success_rate: f64,
+        latency: f64,
+        liquidity: Amount,
+        window: usize,
+        alpha: f64,
+    ) -> Self {
+        let mut liquidity_history = previous
+            .map(|state| state.liquidity_history.clone())
+            .unwrap_or_default();
+        liquidity_history.push_back(liquidity);
+        while liquidity_history.len() > window.max(1) {
+            liquidity_history.pop_front();
+        }
+        let latency_ewma = previous.map_or(latency, |state| {
+            alpha * latency + (1.0 - alpha) * state.latency_ewma()
+        });
+
+        Self {
+            success_rate,
+            latency,
+            liquidity,
+            liquidity_history,
+            latency_ewma: Some(latency_ewma),
+        }
+    }
+}
+
+/// Smoothed latency for `corridor_key` as last recorded by the monitor, if
+/// the corridor has been observed recently.
+pub async fn cached_latency_ewma(cache: &CacheManager, corridor_key: &str) -> Option<f64> {
+    let cache_key = format!("corridor_health:{corridor_key}");
+    cache
+        .get::<CorridorState>(&cache_key)
+        .await
+        .ok()
+        .flatten()
+        .as_ref()
+        .map(CorridorState::latency_ewma)
+}
+
+fn median(samples: &VecDeque<Amount>) -> Option<Amount> {
+    if samples.is_empty() {
+        return None;
+    }
+    let mut sorted: Vec<Amount> = samples.iter().copied().collect();
+    sorted.sort_unstable();
+    let mid = sorted.len() / 2;
+    if sorted.len() % 2 == 0 {
+        (sorted[mid - 1] + sorted[mid]).scaled(0.5)
+    } else {
+        Some(sorted[mid])
+    }
+}
+
+/// Group payments by the corridor their actual source and destination assets form.
+///
+/// Path payments missing a source asset cannot be attributed and are skipped.
+fn group_by_corridor(payments: &[Payment]) -> HashMap<String, Vec<&Payment>> {
+    let mut corridor_map: HashMap<String, Vec<&Payment>> = HashMap::new();
+    for payment in payments {
+        match payment.corridor_key() {
+            Some(key) => corridor_map.entry(key).or_default().push(payment),
+            None => {
+                tracing::debug!(payment_id = %payment.id, "Skipping payment without a source asset")
+            }
+        }
+    }
+    corridor_map
+}
+
+/// The floor `current` fell below when it is more than `threshold` under
+/// `baseline`, or `None` when there is no drop.
+fn liquidity_drop_floor(baseline: Amount, current: Amount, threshold: f64) -> Option<Amount> {
+    if baseline <= Amount::ZERO {
+        return None;
+    }
+    baseline
+        .scaled(1.0 - threshold)
+        .filter(|&floor| current < floor)
 }
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct HealthStatus {
     pub success_rate: f64,
     pub latency: f64,
-    pub liquidity: f64,
+    pub liquidity: Amount,
 }
 
 #[cfg(test)]
@@ -49,6 +220,8 @@ impl CorridorMonitor {
             rpc_client,
             previous_state: tokio::sync::RwLock::new(HashMap::new()),
             webhook_event_service: None,
+            liquidity_config: LiquidityDropConfig::from_env(),
+            latency_config: LatencyEwmaConfig::from_env(),
         }
     }
 
@@ -65,14 +238,36 @@ impl CorridorMonitor {
             rpc_client,
             previous_state: tokio::sync::RwLock::new(HashMap::new()),
             webhook_event_service: Some(webhook_event_service),
+            liquidity_config: LiquidityDropConfig::from_env(),
+            latency_config: LatencyEwmaConfig::from_env(),
         }
     }
 
-    pub async fn start(self: Arc<Self>) {
+    /// Override the liquidity drop window
+    #[must_use]
+    pub const fn with_liquidity_config(mut self, liquidity_config: LiquidityDropConfig) -> Self {
+        self.liquidity_config = liquidity_config;
+        self
+    }
+
+    /// Override the latency smoothing factor
+    #[must_use]
+    pub const fn with_latency_config(mut self, latency_config: LatencyEwmaConfig) -> Self {
+        self.latency_config = latency_config;
+        self
+    }
+
+    pub async fn start(self: Arc<Self>, mut shutdown_rx: broadcast::Receiver<()>) {
         let mut ticker = interval(Duration::from_secs(60));
 
         loop {
-            ticker.tick().await;
+            tokio::select! {
+                _ = ticker.tick() => {}
+                _ = shutdown_rx.recv() => {
+                    tracing::info!("Corridor monitor stopped");
+                    break;
+                }
+            }
             if let Err(e) = self.check_corridors().await {
                 tracing::error!("Error checking corridors: {}", e);
             }
@@ -86,25 +281,14 @@ impl CorridorMonitor {
             .await
             .map_err(|e| anyhow::anyhow!("{e}"))?;
 
-        let mut corridor_map: HashMap<String, Vec<&crate::rpc::Payment>> = HashMap::new();
-        for payment in &payments {
-            let key = format!(
-                "{}:{}->XLM:native",
-                payment.get_asset_code().as_deref().unwrap_or("XLM"),
-                payment.get_asset_issuer().as_deref().unwrap_or("native")
-            );
-            corridor_map.entry(key).or_default().push(payment);
-        }
+        let corridor_map = group_by_corridor(&payments);
 
         let mut prev_state = self.previous_state.write().await;
 
         for (corridor_id, payments) in corridor_map {
             let success_rate = 100.0;
             let latency = 400.0 + (success_rate * 2.0);
-            let liquidity: f64 = payments
-                .iter()
-                .filter_map(|p| p.get_amount().parse::<f64>().ok())
-                .sum();
+            let liquidity = Amount::sum_parsed(payments.iter().map(|p| p.get_amount()));
 
             let cache_key = format!("corridor_health:{}", corridor_id);
             let cached_state: Option<CorridorState> =
@@ -112,27 +296,42 @@ impl CorridorMonitor {
             let effective_old = cached_state
                 .as_ref()
                 .or_else(|| prev_state.get(&corridor_id));
+            let new_state = CorridorState::next(
+                effective_old,
+                success_rate,
+                latency,
+                liquidity,
+                self.liquidity_config.window,
+                self.latency_config.alpha,
+            );
 
             if let Some(old_state) = effective_old {
+                let liquidity_baseline = old_state.liquidity_baseline();
+                // Compare smoothed latencies so a single slow sample does not alert
                 self.alert_manager.check_and_alert(
                     &corridor_id,
                     old_state.success_rate,
                     success_rate,
-                    old_state.latency,
-                    latency,
-                    old_state.liquidity,
-                    liquidity,
+                    old_state.latency_ewma(),
+                    new_state.latency_ewma(),
+                    liquidity_baseline.to_f64(),
+                    liquidity.to_f64(),
                 );
 
-                // Trigger webhook events for corridor changes
-                if let Some(webhook_service) = &self.webhook_event_service {
+                // Webhooks honour the same per-corridor thresholds and mute as alerts
+                let thresholds = self.alert_manager.thresholds_for(&corridor_id);
+                let webhook_service = self
+                    .webhook_event_service
+                    .as_ref()
+                    .filter(|_| !thresholds.muted);
+                if let Some(webhook_service) = webhook_service {
                     let old_metrics = CorridorMetrics {
                         success_rate: old_state.success_rate / 100.0,
                         avg_latency_ms: old_state.latency,
                         p95_latency_ms: old_state.latency * 1.5,
                         p99_latency_ms: old_state.latency * 2.0,
-                        liquidity_depth_usd: old_state.liquidity,
-                        liquidity_volume_24h_usd: old_state.liquidity * 10.0,
+                        liquidity_depth_usd: old_state.liquidity.to_f64(),
+                        liquidity_volume_24h_usd: old_state.liquidity.to_f64() * 10.0,
                         total_attempts: 100,
                         successful_payments: (old_state.success_rate / 100.0 * 100.0) as i64,
                         failed_payments: (100.0 - old_state.success_rate) as i64,
@@ -143,17 +342,19 @@ impl CorridorMonitor {
                         avg_latency_ms: latency,
                         p95_latency_ms: latency * 1.5,
                         p99_latency_ms: latency * 2.0,
-                        liquidity_depth_usd: liquidity,
-                        liquidity_volume_24h_usd: liquidity * 10.0,
+                        liquidity_depth_usd: liquidity.to_f64(),
+                        liquidity_volume_24h_usd: liquidity.to_f64() * 10.0,
                         total_attempts: 100,
                         successful_payments: (success_rate / 100.0 * 100.0) as i64,
                         failed_payments: (100.0 - success_rate) as i64,
                     };
 
                     // Check for corridor health degradation
-                    use crate::webhooks::events::{check_corridor_degradation, determine_severity};
+                    use crate::webhooks::events::{
+                        check_corridor_degradation_with, determine_severity,
+                    };
                     let (degraded, changes) =
-                        check_corridor_degradation(&old_metrics, &new_metrics);
+                        check_corridor_degradation_with(&old_metrics, &new_metrics, &thresholds);
 
                     if degraded {
                         let severity = determine_severity(&old_metrics, &new_metrics);
@@ -178,19 +379,20 @@ impl CorridorMonitor {
                         });
                     }
 
-                    // Check for liquidity drops
-                    if old_state.liquidity > 0.0
-                        && (old_state.liquidity - liquidity) / old_state.liquidity > 0.30
+                    // Check for liquidity drops against the rolling median
+                    let drop_threshold = 1.0 - thresholds.liquidity_decrease_factor;
+                    if let Some(floor) =
+                        liquidity_drop_floor(liquidity_baseline, liquidity, drop_threshold)
                     {
                         let webhook_service = webhook_service.clone();
                         let corridor_id_clone = corridor_id.clone();
-                        let threshold = old_state.liquidity * 0.7; // 30% drop threshold
+                        let threshold = floor.to_f64();
 
                         tokio::spawn(async move {
                             if let Err(e) = webhook_service
                                 .trigger_corridor_liquidity_dropped(
                                     &corridor_id_clone,
-                                    liquidity,
+                                    liquidity.to_f64(),
                                     threshold,
                                     "decreasing",
                                     "warning",
@@ -207,11 +409,6 @@ impl CorridorMonitor {
                 }
             }
 
-            let new_state = CorridorState {
-                success_rate,
-                latency,
-                liquidity,
-            };
             let _ = self.cache.set(&cache_key, &new_state, 60).await;
             prev_state.insert(corridor_id, new_state);
         }
@@ -243,18 +440,11 @@ impl CorridorMonitor {
             .await
             .map_err(|e| anyhow::anyhow!("{e}"))?;
 
-        let liquidity: f64 = payments
+        let corridor_amounts = payments
             .iter()
-            .filter(|payment| {
-                let key = format!(
-                    "{}:{}->XLM:native",
-                    payment.get_asset_code().as_deref().unwrap_or("XLM"),
-                    payment.get_asset_issuer().as_deref().unwrap_or("native")
-                );
-                key == corridor_key
-            })
-            .filter_map(|p| p.get_amount().parse::<f64>().ok())
-            .sum();
+            .filter(|payment| payment.corridor_key().as_deref() == Some(corridor_key))
+            .map(|p| p.get_amount());
+        let liquidity = Amount::sum_parsed(corridor_amounts);
 
         Ok(HealthStatus {
             success_rate: 100.0,
@@ -267,6 +457,7 @@ impl CorridorMonitor {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::alerts::AlertThresholds;
     use crate::cache::{CacheConfig, CacheManager};
 
     #[tokio::test]
@@ -295,4 +486,240 @@ mod tests {
             "second call should use cached metrics"
         );
     }
+
+    /// `(asset_type, code, issuer)` from `native` or `CODE:ISSUER`
+    fn asset_fields(asset: &str) -> (String, Option<String>, Option<String>) {
+        match asset.split_once(':') {
+            None => ("native".to_string(), None, None),
+            Some((code, issuer)) => (
+                "credit_alphanum4".to_string(),
+                Some(code.to_string()),
+                Some(issuer.to_string()),
+            ),
+        }
+    }
+
+    /// Synthetic payment delivering `dest_asset`; `source_asset` makes it a path payment.
+    fn payment(id: &str, dest_asset: &str, source_asset: Option<&str>) -> Payment {
+        let (asset_type, asset_code, asset_issuer) = asset_fields(dest_asset);
+        let source = source_asset.map(asset_fields);
+        Payment {
+            id: id.to_string(),
+            paging_token: id.to_string(),
+            transaction_hash: format!("hash-{id}"),
+            source_account: "GSOURCE".to_string(),
+            destination: "GDEST".to_string(),
+            asset_type,
+            asset_code,
+            asset_issuer,
+            amount: "10.0000000".to_string(),
+            created_at: "2026-01-22T10:00:00Z".to_string(),
+            operation_type: Some(
+                if source.is_some() {
+                    "path_payment_strict_send"
+                } else {
+                    "payment"
+                }
+                .to_string(),
+            ),
+            source_asset_type: source.as_ref().map(|s| s.0.clone()),
+            source_asset_code: source.as_ref().and_then(|s| s.1.clone()),
+            source_asset_issuer: source.as_ref().and_then(|s| s.2.clone()),
+            source_amount: source.as_ref().map(|_| "9.5000000".to_string()),
+            from: None,
+            to: None,
+            asset_balance_changes: None,
+        }
+    }
+
+    #[test]
+    fn test_payments_are_keyed_by_actual_source_and_destination_assets() {
+        let payments = vec![
+            payment("direct-usdc", "USDC:GUSDC", None),
+            payment("direct-eurc", "EURC:GEURC", None),
+            payment("path-usdc-eurc", "EURC:GEURC", Some("USDC:GUSDC")),
+            payment("path-xlm-usdc", "USDC:GUSDC", Some("native")),
+            payment("path-usdc-eurc-2", "EURC:GEURC", Some("USDC:GUSDC")),
+        ];
+
+        let corridors = group_by_corridor(&payments);
+        let ids =
+            |key: &str| -> Vec<&str> { corridors[key].iter().map(|p| p.id.as_str()).collect() };
+
+        assert_eq!(corridors.len(), 4);
+        assert_eq!(ids("USDC:GUSDC->USDC:GUSDC"), ["direct-usdc"]);
+        assert_eq!(ids("EURC:GEURC->EURC:GEURC"), ["direct-eurc"]);
+        assert_eq!(
+            ids("USDC:GUSDC->EURC:GEURC"),
+            ["path-usdc-eurc", "path-usdc-eurc-2"]
+        );
+        assert_eq!(ids("XLM:native->USDC:GUSDC"), ["path-xlm-usdc"]);
+        assert!(!corridors.keys().any(|k| k.ends_with("->XLM:native")));
+    }
+
+    #[test]
+    fn test_path_payment_without_source_asset_is_skipped() {
+        let mut orphan = payment("orphan", "EURC:GEURC", Some("USDC:GUSDC"));
+        orphan.source_asset_type = None;
+
+        assert_eq!(orphan.corridor_key(), None);
+        assert!(group_by_corridor(&[orphan]).is_empty());
+    }
+
+    fn amount(value: f64) -> Amount {
+        Amount::parse(&format!("{value:.7}")).unwrap()
+    }
+
+    /// Feed liquidity samples through the rolling window and return the ticks
+    /// at which a drop would be reported.
+    fn drop_ticks(samples: &[f64], config: LiquidityDropConfig) -> Vec<usize> {
+        let drop_threshold = 1.0 - AlertThresholds::default().liquidity_decrease_factor;
+        let mut state: Option<CorridorState> = None;
+        let mut ticks = Vec::new();
+        for (tick, liquidity) in samples.iter().copied().map(amount).enumerate() {
+            if let Some(old) = &state {
+                if liquidity_drop_floor(old.liquidity_baseline(), liquidity, drop_threshold)
+                    .is_some()
+                {
+                    ticks.push(tick);
+                }
+            }
+            state = Some(CorridorState::next(
+                state.as_ref(),
+                100.0,
+                400.0,
+                liquidity,
+                config.window,
+                DEFAULT_LATENCY_EWMA_ALPHA,
+            ));
+        }
+        ticks
+    }
+
+    #[test]
+    fn test_slow_bleed_trips_liquidity_drop() {
+        // 5% per tick never exceeds 30% tick-over-tick
+        let samples: Vec<f64> = (0..20).map(|i| 1_000.0 * 0.95_f64.powi(i)).collect();
+
+        let ticks = drop_ticks(&samples, LiquidityDropConfig::default());
+
+        assert!(!ticks.is_empty(), "slow bleed should be detected");
+        assert!(ticks[0] < 15, "detected too late at tick {}", ticks[0]);
+    }
+
+    #[test]
+    fn test_single_spike_does_not_trip_liquidity_drop() {
+        let mut samples = vec![1_000.0; 10];
+        samples.push(2_500.0);
+        samples.extend([1_000.0; 5]);
+
+        // Compared against the spike alone, the next tick is a 60% drop
+        assert!(liquidity_drop_floor(amount(2_500.0), amount(1_000.0), 0.30).is_some());
+        assert!(drop_ticks(&samples, LiquidityDropConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn test_liquidity_window_is_bounded() {
+        let mut state = CorridorState::next(None, 100.0, 400.0, amount(1.0), 3, 1.0);
+        for liquidity in 2..=5 {
+            let liquidity = amount(f64::from(liquidity));
+            state = CorridorState::next(Some(&state), 100.0, 400.0, liquidity, 3, 1.0);
+        }
+
+        assert_eq!(
+            state.liquidity_history.iter().copied().collect::<Vec<_>>(),
+            vec![amount(3.0), amount(4.0), amount(5.0)]
+        );
+        assert_eq!(state.liquidity_baseline(), amount(4.0));
+    }
+
+    #[test]
+    fn test_liquidity_config_from_env() {
+        let _guard = crate::lock_env_test();
+        std::env::set_var("LIQUIDITY_WINDOW_SAMPLES", "30");
+        assert_eq!(LiquidityDropConfig::from_env().window, 30);
+
+        std::env::set_var("LIQUIDITY_WINDOW_SAMPLES", "0");
+        assert_eq!(
+            LiquidityDropConfig::from_env(),
+            LiquidityDropConfig::default()
+        );
+
+        std::env::remove_var("LIQUIDITY_WINDOW_SAMPLES");
+    }
+
+    /// Feed latency samples through the monitor state and return the ticks at
+    /// which a latency-increase alert would fire, plus the final state.
+    fn latency_alert_ticks(samples: &[f64], alpha: f64) -> (Vec<usize>, CorridorState) {
+        let (alert_manager, mut rx) = AlertManager::with_capacity(64);
+        let mut state: Option<CorridorState> = None;
+        let mut ticks = Vec::new();
+        for (tick, &latency) in samples.iter().enumerate() {
+            let next =
+                CorridorState::next(state.as_ref(), 100.0, latency, amount(1_000.0), 15, alpha);
+            if let Some(old) = &state {
+                alert_manager.check_and_alert(
+                    "USDC->EURC",
+                    100.0,
+                    100.0,
+                    old.latency_ewma(),
+                    next.latency_ewma(),
+                    1_000.0,
+                    1_000.0,
+                );
+                while rx.try_recv().is_ok() {
+                    ticks.push(tick);
+                }
+            }
+            state = Some(next);
+        }
+        (ticks, state.unwrap())
+    }
+
+    #[test]
+    fn test_latency_ewma_smooths_noisy_series() {
+        // Steady 400ms with a 1s spike every fifth tick
+        let samples: Vec<f64> = (0..20)
+            .map(|i| if i % 5 == 4 { 1_000.0 } else { 400.0 })
+            .collect();
+
+        // Without smoothing every spike is a 2.5x increase
+        let (raw_ticks, _) = latency_alert_ticks(&samples, 1.0);
+        assert_eq!(raw_ticks, vec![4, 9, 14, 19]);
+
+        let (smoothed_ticks, state) = latency_alert_ticks(&samples, 0.3);
+        assert!(smoothed_ticks.len() < raw_ticks.len());
+        assert!(smoothed_ticks.is_empty());
+
+        // The last tick is a spike: 0.3 * 1000 + 0.7 * previous average
+        let mut expected = 400.0;
+        for &latency in &samples[1..] {
+            expected = 0.3 * latency + 0.7 * expected;
+        }
+        assert!((state.latency_ewma() - expected).abs() < 1e-9);
+        assert!(state.latency_ewma() > 400.0 && state.latency_ewma() < 1_000.0);
+    }
+
+    #[test]
+    fn test_latency_ewma_sustained_increase_still_alerts() {
+        let mut samples = vec![400.0; 5];
+        samples.extend([1_200.0; 5]);
+
+        let (ticks, _) = latency_alert_ticks(&samples, 0.5);
+        assert_eq!(ticks, vec![5]);
+    }
+
+    #[test]
+    fn test_latency_ewma_config_from_env() {
+        let _guard = crate::lock_env_test();
+        std::env::set_var("LATENCY_EWMA_ALPHA", "0.5");
+        assert!((LatencyEwmaConfig::from_env().alpha - 0.5).abs() < f64::EPSILON);
+
+        for invalid in ["0", "1.5", "abc"] {
+            std::env::set_var("LATENCY_EWMA_ALPHA", invalid);
+            assert_eq!(LatencyEwmaConfig::from_env(), LatencyEwmaConfig::default());
+        }
+
+        std::env::remove_var("LATENCY_EWMA_ALPHA");
+    }
 }
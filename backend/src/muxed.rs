@@ -4,7 +4,7 @@
 //! sub-accounts via a 64-bit muxed ID. M-addresses are 69 characters and start with 'M'.
 //! See SEP-0023 and [Stellar Muxed Accounts FAQ](https://stellar.org/blog/developers/muxed-accounts-faq).
 
-use data_encoding::BASE32;
+use data_encoding::{BASE32, BASE32_NOPAD};
 use serde::{Deserialize, Serialize};
 
 /// Stellar strkey version bytes
@@ -35,6 +35,40 @@ fn crc16(data: &[u8]) -> u16 {
     crc
 }
 
+/// True if `key` is unpadded base32 that decodes to `version`, a
+/// `payload_len`-byte payload and a matching CRC16 checksum.
+fn is_valid_strkey(key: &str, version: u8, payload_len: usize) -> bool {
+    let Ok(decoded) = BASE32_NOPAD.decode(key.as_bytes()) else {
+        return false;
+    };
+    // version(1) + payload + checksum(2); the version byte carries the
+    // strkey type in its top five bits
+    if decoded.len() != payload_len + 3 || decoded[0] != version << 3 {
+        return false;
+    }
+    // The checksum is stored little-endian
+    let (body, checksum) = decoded.split_at(payload_len + 1);
+    crc16(body) == u16::from_le_bytes([checksum[0], checksum[1]])
+}
+
+/// Returns true if `key` is a well-formed Stellar public key (G-address):
+/// base32 that decodes to the `ACCOUNT_ID` version byte, a 32-byte Ed25519
+/// key and a matching CRC16 checksum.
+#[must_use]
+pub fn is_valid_stellar_public_key(key: &str) -> bool {
+    key.len() == G_ADDRESS_LEN
+        && key.starts_with('G')
+        && is_valid_strkey(key, VERSION_ACCOUNT_ID, 32)
+}
+
+/// Returns true if `addr` is a well-formed muxed account (M-address): the
+/// `MUXED_ACCOUNT` version byte, a 32-byte Ed25519 key, a 64-bit id and a
+/// matching CRC16 checksum.
+#[must_use]
+pub fn is_valid_muxed_address(addr: &str) -> bool {
+    is_muxed_address(addr) && is_valid_strkey(addr, VERSION_MUXED_ACCOUNT, 40)
+}
+
 /// Result of parsing a muxed account address.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MuxedAccountInfo {
@@ -153,6 +187,41 @@ mod tests {
         assert!(!is_stellar_account_address("invalid"));
     }
 
+    #[test]
+    fn test_is_valid_stellar_public_key() {
+        assert!(is_valid_stellar_public_key(
+            "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN"
+        ));
+        // Last character changed, so the checksum no longer matches
+        assert!(!is_valid_stellar_public_key(
+            "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVA"
+        ));
+        assert!(!is_valid_stellar_public_key(
+            "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZV"
+        ));
+        assert!(!is_valid_stellar_public_key(
+            "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVNA"
+        ));
+        // Secret seed: valid strkey, wrong version byte
+        assert!(!is_valid_stellar_public_key(
+            "SA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN"
+        ));
+    }
+
+    #[test]
+    fn test_is_valid_muxed_address() {
+        assert!(is_valid_muxed_address(
+            "MA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJUAAAAAAAAAAE2JUG6"
+        ));
+        // Last character changed, so the checksum no longer matches
+        assert!(!is_valid_muxed_address(
+            "MA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJUAAAAAAAAAAE2JUGA"
+        ));
+        assert!(!is_valid_muxed_address(
+            "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ"
+        ));
+    }
+
     #[test]
     fn test_parse_muxed_address() {
         // Invalid: G-address returns None
@@ -32,6 +32,62 @@ impl std::str::FromStr for StellarNetwork {
     }
 }
 
+/// Network selection for constructing RPC clients.
+///
+/// `Mainnet` and `Testnet` resolve to the URLs in [`NetworkConfig::for_network`];
+/// `Custom` points at explicit endpoints (local quickstart, private Horizon).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Network {
+    Mainnet,
+    Testnet,
+    Custom { rpc: String, horizon: String },
+}
+
+impl Network {
+    /// The underlying Stellar network, inferring testnet for custom
+    /// endpoints whose Horizon URL mentions it.
+    #[must_use]
+    pub fn stellar_network(&self) -> StellarNetwork {
+        match self {
+            Self::Mainnet => StellarNetwork::Mainnet,
+            Self::Testnet => StellarNetwork::Testnet,
+            Self::Custom { horizon, .. } => {
+                if horizon.contains("testnet") {
+                    StellarNetwork::Testnet
+                } else {
+                    StellarNetwork::Mainnet
+                }
+            }
+        }
+    }
+
+    /// Short label used to tag responses ("mainnet", "testnet", "custom").
+    #[must_use]
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::Mainnet => "mainnet",
+            Self::Testnet => "testnet",
+            Self::Custom { .. } => "custom",
+        }
+    }
+}
+
+impl From<StellarNetwork> for Network {
+    fn from(network: StellarNetwork) -> Self {
+        match network {
+            StellarNetwork::Mainnet => Self::Mainnet,
+            StellarNetwork::Testnet => Self::Testnet,
+        }
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct NetworkConfig {
     pub network: StellarNetwork,
@@ -94,6 +150,29 @@ impl NetworkConfig {
         }
     }
 
+    /// Create network configuration for explicit endpoints, inferring the
+    /// passphrase from the Horizon URL.
+    #[must_use]
+    pub fn for_custom(rpc_url: String, horizon_url: String) -> Self {
+        let network = Network::Custom {
+            rpc: rpc_url.clone(),
+            horizon: horizon_url.clone(),
+        }
+        .stellar_network();
+        let network_passphrase = match network {
+            StellarNetwork::Mainnet => "Public Global Stellar Network ; September 2015",
+            StellarNetwork::Testnet => "Test SDF Network ; September 2015",
+        }
+        .to_string();
+
+        Self {
+            network,
+            rpc_url,
+            horizon_url,
+            network_passphrase,
+        }
+    }
+
     /// Get the network passphrase for transaction signing
     #[must_use]
     pub fn network_passphrase(&self) -> &str {
@@ -183,6 +262,20 @@ mod tests {
         assert_eq!(StellarNetwork::Testnet.to_string(), "testnet");
     }
 
+    #[test]
+    fn test_network_label_and_inference() {
+        assert_eq!(Network::Mainnet.label(), "mainnet");
+        assert_eq!(Network::Testnet.to_string(), "testnet");
+
+        let custom = Network::Custom {
+            rpc: "http://localhost:8000/soroban/rpc".to_string(),
+            horizon: "https://horizon-testnet.example.org".to_string(),
+        };
+        assert_eq!(custom.label(), "custom");
+        assert_eq!(custom.stellar_network(), StellarNetwork::Testnet);
+        assert_eq!(Network::from(StellarNetwork::Mainnet), Network::Mainnet);
+    }
+
     #[test]
     fn test_network_config_creation() {
         // Mainnet now requires explicit env vars — set them for test isolation.
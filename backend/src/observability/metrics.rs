@@ -83,6 +83,22 @@ lazy_static! {
         &["method", "error_type"]
     )
     .expect("Failed to register rpc_errors_total counter");
+    pub static ref RATE_LIMIT_REJECTIONS_TOTAL: IntCounterVec = IntCounterVec::new(
+        Opts::new(
+            "rate_limit_rejections_total",
+            "Total number of requests rejected by the rate limiter"
+        ),
+        &["route", "bucket"]
+    )
+    .expect("Failed to register rate_limit_rejections_total counter");
+    pub static ref RATE_LIMIT_BYPASSES_TOTAL: IntCounterVec = IntCounterVec::new(
+        Opts::new(
+            "rate_limit_bypasses_total",
+            "Total number of allowlisted requests that skipped the rate limiter"
+        ),
+        &["route", "matched"]
+    )
+    .expect("Failed to register rate_limit_bypasses_total counter");
     pub static ref BACKGROUND_JOBS_TOTAL: IntCounter = IntCounter::new(
         "background_jobs_total",
         "Total number of background jobs executed"
@@ -194,6 +210,27 @@ lazy_static! {
         "Stellar ledger lag in seconds"
     )
     .expect("Failed to register stellar_ledger_lag_seconds gauge");
+    // Ingestion pipeline throughput and lag
+    pub static ref LEDGERS_INGESTED_TOTAL: IntCounter = IntCounter::new(
+        "ledgers_ingested_total",
+        "Total number of ledgers persisted by ingestion"
+    )
+    .expect("Failed to register ledgers_ingested_total counter");
+    pub static ref PAYMENTS_INGESTED_TOTAL: IntCounter = IntCounter::new(
+        "payments_ingested_total",
+        "Total number of payments persisted by ingestion"
+    )
+    .expect("Failed to register payments_ingested_total counter");
+    pub static ref TRADES_INGESTED_TOTAL: IntCounter = IntCounter::new(
+        "trades_ingested_total",
+        "Total number of new trades seen by ingestion"
+    )
+    .expect("Failed to register trades_ingested_total counter");
+    pub static ref INGESTION_LAG_LEDGERS: IntGauge = IntGauge::new(
+        "ingestion_lag_ledgers",
+        "Ledgers between the network's latest ledger and the last ingested one"
+    )
+    .expect("Failed to register ingestion_lag_ledgers gauge");
     pub static ref STELLAR_TRANSACTION_SUCCESS_RATE: Histogram = Histogram::with_opts(
         HistogramOpts::new(
             "stellar_transaction_success_rate",
@@ -253,6 +290,8 @@ pub fn init_metrics() {
         HTTP_ERRORS_TOTAL,
         DB_ERRORS_TOTAL,
         RPC_ERRORS_TOTAL,
+        RATE_LIMIT_REJECTIONS_TOTAL,
+        RATE_LIMIT_BYPASSES_TOTAL,
         BACKGROUND_JOBS_TOTAL,
         ACTIVE_CONNECTIONS,
         CORRIDORS_TRACKED,
@@ -272,6 +311,10 @@ pub fn init_metrics() {
         BACKUP_VERIFICATIONS_TOTAL,
         BACKUP_SIZE_BYTES,
         STELLAR_LEDGER_LAG_SECONDS,
+        LEDGERS_INGESTED_TOTAL,
+        PAYMENTS_INGESTED_TOTAL,
+        TRADES_INGESTED_TOTAL,
+        INGESTION_LAG_LEDGERS,
         STELLAR_TRANSACTION_SUCCESS_RATE,
         STELLAR_ANCHOR_HEALTH,
         STELLAR_CORRIDOR_RELIABILITY,
@@ -415,6 +458,20 @@ pub fn record_rpc_error(method: &str, error_type: &str) {
     ERRORS_TOTAL.inc();
 }
 
+/// `bucket` must already be redacted; it ends up in the exported label set.
+pub fn record_rate_limit_rejection(route: &str, bucket: &str) {
+    RATE_LIMIT_REJECTIONS_TOTAL
+        .with_label_values(&[&normalize_endpoint(route), bucket])
+        .inc();
+}
+
+/// Count a request that skipped rate limiting; `matched` is `api_key` or `cidr`
+pub fn record_rate_limit_bypass(route: &str, matched: &str) {
+    RATE_LIMIT_BYPASSES_TOTAL
+        .with_label_values(&[&normalize_endpoint(route), matched])
+        .inc();
+}
+
 pub fn set_active_connections(count: i64) {
     ACTIVE_CONNECTIONS.set(count);
 }
@@ -490,6 +547,32 @@ pub fn record_pool_error(kind: &str) {
     DB_POOL_ERRORS_TOTAL.with_label_values(&[kind]).inc();
 }
 
+pub fn record_ledger_ingested() {
+    LEDGERS_INGESTED_TOTAL.inc();
+}
+
+pub fn record_payments_ingested(count: u64) {
+    PAYMENTS_INGESTED_TOTAL.inc_by(count);
+}
+
+pub fn record_trades_ingested(count: u64) {
+    TRADES_INGESTED_TOTAL.inc_by(count);
+}
+
+/// Ledgers between `latest_ledger` and `last_ingested`; a last ingested
+/// ledger ahead of `latest` counts as no lag
+#[must_use]
+pub fn ingestion_lag(latest_ledger: u64, last_ingested: u64) -> i64 {
+    i64::try_from(latest_ledger.saturating_sub(last_ingested)).unwrap_or(i64::MAX)
+}
+
+/// Set `ingestion_lag_ledgers` to [`ingestion_lag`]
+pub fn set_ingestion_lag(latest_ledger: u64, last_ingested: u64) -> i64 {
+    let lag = ingestion_lag(latest_ledger, last_ingested);
+    INGESTION_LAG_LEDGERS.set(lag);
+    lag
+}
+
 /// Check if request duration violates SLO (p95 < 500ms)
 pub fn check_slo_violation(endpoint: &str, duration_ms: f64) {
     const SLO_TARGET_MS: f64 = 500.0;
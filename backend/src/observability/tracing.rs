@@ -1,11 +1,13 @@
 use anyhow::Result;
 use axum::{body::Body, extract::Request, middleware::Next, response::Response};
 use opentelemetry::global;
-use opentelemetry::trace::TracerProvider;
-use opentelemetry::KeyValue;
+use opentelemetry::trace::{Link, SpanKind, TraceId, TracerProvider};
+use opentelemetry::{Context, KeyValue};
 use opentelemetry_otlp::WithExportConfig;
 use opentelemetry_sdk::propagation::TraceContextPropagator;
 use opentelemetry_sdk::resource::Resource;
+use opentelemetry_sdk::trace::{Sampler, SamplingResult, ShouldSample};
+use std::collections::HashMap;
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
@@ -35,6 +37,7 @@ fn init_otel_tracer(service_name: &str) -> Result<opentelemetry_sdk::trace::Trac
     let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder()
         .with_batch_exporter(exporter)
         .with_resource(resource)
+        .with_sampler(SpanNameSampler::from_env())
         .build();
 
     global::set_tracer_provider(provider.clone());
@@ -42,9 +45,167 @@ fn init_otel_tracer(service_name: &str) -> Result<opentelemetry_sdk::trace::Trac
     Ok(provider.tracer("stellar-insights-backend"))
 }
 
+/// Head sampler with per-span-name ratios layered over a global ratio.
+///
+/// A span whose name has an override is sampled at that rate even inside an
+/// unsampled trace, so critical write paths can stay at 100% while noisy
+/// ingestion spans are cut down. Any other span follows its parent's
+/// decision, or the global rate when it starts a trace.
+#[derive(Debug, Clone)]
+pub struct SpanNameSampler {
+    default: Sampler,
+    overrides: HashMap<String, Sampler>,
+}
+
+impl SpanNameSampler {
+    #[must_use]
+    pub fn new(default_rate: f64) -> Self {
+        Self {
+            default: Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(default_rate))),
+            overrides: HashMap::new(),
+        }
+    }
+
+    /// Sample spans named `name` at `rate` instead of the global rate
+    #[must_use]
+    pub fn with_rate(mut self, name: impl Into<String>, rate: f64) -> Self {
+        self.overrides
+            .insert(name.into(), Sampler::TraceIdRatioBased(rate));
+        self
+    }
+
+    /// Global rate from `OTEL_SAMPLE_RATE` (default 1.0) and overrides from
+    /// `OTEL_SPAN_SAMPLE_RATES`, a comma-separated `span_name=rate` list.
+    /// Malformed entries are skipped with a warning.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let default_rate = std::env::var("OTEL_SAMPLE_RATE")
+            .ok()
+            .and_then(|v| v.trim().parse::<f64>().ok())
+            .unwrap_or(1.0);
+        let mut sampler = Self::new(default_rate);
+        for entry in std::env::var("OTEL_SPAN_SAMPLE_RATES")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|e| !e.is_empty())
+        {
+            match entry
+                .split_once('=')
+                .and_then(|(name, rate)| Some((name.trim(), rate.trim().parse::<f64>().ok()?)))
+            {
+                Some((name, rate)) if !name.is_empty() => sampler = sampler.with_rate(name, rate),
+                _ => eprintln!("Ignoring invalid OTEL_SPAN_SAMPLE_RATES entry '{entry}'"),
+            }
+        }
+        sampler
+    }
+}
+
+impl ShouldSample for SpanNameSampler {
+    fn should_sample(
+        &self,
+        parent_context: Option<&Context>,
+        trace_id: TraceId,
+        name: &str,
+        span_kind: &SpanKind,
+        attributes: &[KeyValue],
+        links: &[Link],
+    ) -> SamplingResult {
+        self.overrides
+            .get(name)
+            .unwrap_or(&self.default)
+            .should_sample(parent_context, trace_id, name, span_kind, attributes, links)
+    }
+}
+
+/// Output flavour of the fmt layers, from `LOG_FORMAT`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// One JSON object per line, for log shippers
+    Json,
+    /// Multi-line, human-readable output for local development
+    Pretty,
+    /// Single-line plain text
+    Compact,
+}
+
+impl LogFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "pretty" => Some(Self::Pretty),
+            "compact" | "plain" | "text" => Some(Self::Compact),
+            _ => None,
+        }
+    }
+
+    /// `LOG_FORMAT=json|pretty|compact`. When unset or unrecognised, development
+    /// (`RUST_ENV`/`ENVIRONMENT` = `development`/`dev`) gets `pretty` and every
+    /// other environment, including an unset one, gets `json`.
+    #[must_use]
+    pub fn from_env() -> Self {
+        if let Some(format) = std::env::var("LOG_FORMAT")
+            .ok()
+            .and_then(|v| Self::parse(&v))
+        {
+            return format;
+        }
+        let env_mode = std::env::var("RUST_ENV")
+            .or_else(|_| std::env::var("ENVIRONMENT"))
+            .unwrap_or_default()
+            .to_lowercase();
+        if env_mode == "development" || env_mode == "dev" {
+            Self::Pretty
+        } else {
+            Self::Json
+        }
+    }
+}
+
+const DEFAULT_LOG_FILTER: &str = "stellar_insights_backend=info,tower_http=info";
+
+/// Build the filter from `RUST_LOG`, falling back to [`DEFAULT_LOG_FILTER`]
+/// when it is unset or fails to parse.
+fn env_filter() -> tracing_subscriber::EnvFilter {
+    match std::env::var(tracing_subscriber::EnvFilter::DEFAULT_ENV) {
+        Ok(directives) => tracing_subscriber::EnvFilter::try_new(&directives).unwrap_or_else(|e| {
+            eprintln!("Ignoring invalid RUST_LOG '{directives}': {e}");
+            tracing_subscriber::EnvFilter::new(DEFAULT_LOG_FILTER)
+        }),
+        Err(_) => tracing_subscriber::EnvFilter::new(DEFAULT_LOG_FILTER),
+    }
+}
+
+fn fmt_layer<S, W>(format: LogFormat, writer: W, ansi: bool) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+    W: for<'w> tracing_subscriber::fmt::MakeWriter<'w> + Send + Sync + 'static,
+{
+    let layer = tracing_subscriber::fmt::layer()
+        .with_writer(writer)
+        .with_ansi(ansi)
+        .with_target(true)
+        .with_level(true);
+    match format {
+        LogFormat::Json => layer.json().boxed(),
+        LogFormat::Pretty => layer.pretty().boxed(),
+        LogFormat::Compact => layer.compact().boxed(),
+    }
+}
+
 /// Initialize tracing. When `LOG_DIR` is set, logs are also written to a rotating file
 /// (daily rotation, up to 30 files retained). The returned guard must be held for the
 /// process lifetime so that file logs are flushed; drop it only at shutdown.
+/// Whether spans are exported over OTLP: `OTEL_ENABLED`, on unless set to
+/// something other than `true`
+#[must_use]
+pub fn otel_enabled() -> bool {
+    std::env::var("OTEL_ENABLED")
+        .map(|v| v.eq_ignore_ascii_case("true"))
+        .unwrap_or(true)
+}
+
 pub fn init_tracing(service_name: &str) -> Result<Option<WorkerGuard>> {
     // Register W3C TraceContext as the global propagator so that
     // `traceparent` / `tracestate` headers are used for context propagation.
@@ -52,15 +213,10 @@ pub fn init_tracing(service_name: &str) -> Result<Option<WorkerGuard>> {
 
     let _ = tracing_log::LogTracer::init();
 
-    let env_filter = tracing_subscriber::EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| "backend=info,tower_http=info".into());
-
-    let log_format = std::env::var("LOG_FORMAT").unwrap_or_else(|_| "json".to_string());
-    let use_json = log_format.eq_ignore_ascii_case("json");
+    let env_filter = env_filter();
+    let log_format = LogFormat::from_env();
 
-    let otel_enabled = std::env::var("OTEL_ENABLED")
-        .map(|v| v.eq_ignore_ascii_case("true"))
-        .unwrap_or(true);
+    let otel_enabled = otel_enabled();
 
     // Optional rotating file appender
     let log_dir = std::env::var("LOG_DIR").ok();
@@ -79,113 +235,23 @@ pub fn init_tracing(service_name: &str) -> Result<Option<WorkerGuard>> {
     };
 
     // OTel layer must be registered on `registry()` first so `LookupSpan` bounds are satisfied.
-    let stdout = std::io::stdout;
-
     if otel_enabled {
         let otel_tracer = init_otel_tracer(service_name)?;
         let otel_layer = tracing_opentelemetry::layer().with_tracer(otel_tracer);
-        let base = tracing_subscriber::registry()
+        tracing_subscriber::registry()
             .with(otel_layer)
             .with(TraceIdLayer)
-            .with(env_filter);
-
-        match (use_json, file_writer) {
-            (true, Some(w)) => {
-                let stdout_layer = tracing_subscriber::fmt::layer()
-                    .json()
-                    .with_writer(stdout)
-                    .with_target(true)
-                    .with_level(true)
-                    .boxed();
-                let file_layer = tracing_subscriber::fmt::layer()
-                    .json()
-                    .with_writer(w)
-                    .with_target(true)
-                    .with_level(true)
-                    .boxed();
-                base.with(stdout_layer).with(file_layer).init();
-            }
-            (true, None) => {
-                let stdout_layer = tracing_subscriber::fmt::layer()
-                    .json()
-                    .with_writer(stdout)
-                    .with_target(true)
-                    .with_level(true)
-                    .boxed();
-                base.with(stdout_layer).init();
-            }
-            (false, Some(w)) => {
-                let stdout_layer = tracing_subscriber::fmt::layer()
-                    .with_writer(stdout)
-                    .with_target(true)
-                    .with_level(true)
-                    .boxed();
-                let file_layer = tracing_subscriber::fmt::layer()
-                    .with_writer(w)
-                    .with_target(true)
-                    .with_level(true)
-                    .boxed();
-                base.with(stdout_layer).with(file_layer).init();
-            }
-            (false, None) => {
-                let stdout_layer = tracing_subscriber::fmt::layer()
-                    .with_writer(stdout)
-                    .with_target(true)
-                    .with_level(true)
-                    .boxed();
-                base.with(stdout_layer).init();
-            }
-        }
+            .with(env_filter)
+            .with(fmt_layer(log_format, std::io::stdout, true))
+            .with(file_writer.map(|w| fmt_layer(log_format, w, false)))
+            .init();
         tracing::info!("OpenTelemetry tracing enabled");
     } else {
-        let base = tracing_subscriber::registry().with(env_filter);
-        match (use_json, file_writer) {
-            (true, Some(w)) => {
-                let stdout_layer = tracing_subscriber::fmt::layer()
-                    .json()
-                    .with_writer(stdout)
-                    .with_target(true)
-                    .with_level(true)
-                    .boxed();
-                let file_layer = tracing_subscriber::fmt::layer()
-                    .json()
-                    .with_writer(w)
-                    .with_target(true)
-                    .with_level(true)
-                    .boxed();
-                base.with(stdout_layer).with(file_layer).init();
-            }
-            (true, None) => {
-                let stdout_layer = tracing_subscriber::fmt::layer()
-                    .json()
-                    .with_writer(stdout)
-                    .with_target(true)
-                    .with_level(true)
-                    .boxed();
-                base.with(stdout_layer).init();
-            }
-            (false, Some(w)) => {
-                let stdout_layer = tracing_subscriber::fmt::layer()
-                    .with_writer(stdout)
-                    .with_target(true)
-                    .with_level(true)
-                    .boxed();
-                let file_layer = tracing_subscriber::fmt::layer()
-                    .with_writer(w)
-                    .with_target(true)
-                    .with_level(true)
-                    .boxed();
-                base.with(stdout_layer).with(file_layer).init();
-            }
-            (false, None) => {
-                let stdout_layer = tracing_subscriber::fmt::layer()
-                    .with_writer(stdout)
-                    .with_target(true)
-                    .with_level(true)
-                    .boxed();
-                base.with(stdout_layer).init();
-            }
-        }
+        tracing_subscriber::registry()
+            .with(env_filter)
+            .with(fmt_layer(log_format, std::io::stdout, true))
+            .with(file_writer.map(|w| fmt_layer(log_format, w, false)))
+            .init();
     }
 
     Ok(file_guard)
@@ -323,7 +389,9 @@ pub async fn trace_propagation_middleware(req: Request<Body>, next: Next) -> Res
 /// ```
 pub fn inject_trace_context(builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
     let mut carrier = std::collections::HashMap::new();
-    let cx = opentelemetry::Context::current();
+    // The tracing span's context, so the outbound call is parented to the
+    // span it was made from rather than only to the OTel thread context
+    let cx = tracing::Span::current().context();
     global::get_text_map_propagator(|propagator| {
         propagator.inject_context(&cx, &mut carrier);
     });
@@ -372,6 +440,79 @@ mod tests {
         let _ = inject_trace_context(builder);
     }
 
+    #[test]
+    fn log_format_parses_each_value() {
+        assert_eq!(LogFormat::parse("json"), Some(LogFormat::Json));
+        assert_eq!(LogFormat::parse("PRETTY"), Some(LogFormat::Pretty));
+        assert_eq!(LogFormat::parse(" compact "), Some(LogFormat::Compact));
+        assert_eq!(LogFormat::parse("xml"), None);
+    }
+
+    #[test]
+    fn log_format_defaults_by_environment() {
+        let _guard = crate::lock_env_test();
+        std::env::remove_var("LOG_FORMAT");
+        std::env::remove_var("RUST_ENV");
+
+        std::env::set_var("ENVIRONMENT", "development");
+        assert_eq!(LogFormat::from_env(), LogFormat::Pretty);
+        std::env::set_var("ENVIRONMENT", "production");
+        assert_eq!(LogFormat::from_env(), LogFormat::Json);
+        std::env::remove_var("ENVIRONMENT");
+        assert_eq!(LogFormat::from_env(), LogFormat::Json);
+
+        std::env::set_var("LOG_FORMAT", "compact");
+        assert_eq!(LogFormat::from_env(), LogFormat::Compact);
+        std::env::set_var("LOG_FORMAT", "bogus");
+        assert_eq!(LogFormat::from_env(), LogFormat::Json);
+        std::env::remove_var("LOG_FORMAT");
+    }
+
+    /// Fraction of spans sampled across trace ids spread evenly over the
+    /// low 64 bits that `TraceIdRatioBased` decides on
+    fn sampled_fraction(sampler: &SpanNameSampler, name: &str) -> f64 {
+        const TRIALS: u32 = 10_000;
+        let step = u64::MAX / u64::from(TRIALS);
+        let sampled = (0..TRIALS)
+            .filter(|&i| {
+                let id = (1u128 << 64) | u128::from(u64::from(i) * step);
+                let trace_id = TraceId::from_bytes(id.to_be_bytes());
+                let result =
+                    sampler.should_sample(None, trace_id, name, &SpanKind::Internal, &[], &[]);
+                result.decision == opentelemetry_sdk::trace::SamplingDecision::RecordAndSample
+            })
+            .count();
+        f64::from(u32::try_from(sampled).unwrap()) / f64::from(TRIALS)
+    }
+
+    #[test]
+    fn span_name_sampler_applies_overrides_before_default() {
+        let sampler = SpanNameSampler::new(0.5)
+            .with_rate("process_ledgers", 0.01)
+            .with_rate("submit_snapshot", 1.0);
+
+        assert!((sampled_fraction(&sampler, "process_ledgers") - 0.01).abs() < 0.001);
+        assert!((sampled_fraction(&sampler, "get_anchors") - 0.5).abs() < 0.001);
+        assert!((sampled_fraction(&sampler, "submit_snapshot") - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn span_name_sampler_reads_overrides_from_env() {
+        let _guard = crate::lock_env_test();
+        std::env::set_var("OTEL_SAMPLE_RATE", "0");
+        std::env::set_var(
+            "OTEL_SPAN_SAMPLE_RATES",
+            "submit_snapshot=1.0, bogus, =0.5, process_ledgers=abc",
+        );
+        let sampler = SpanNameSampler::from_env();
+        std::env::remove_var("OTEL_SAMPLE_RATE");
+        std::env::remove_var("OTEL_SPAN_SAMPLE_RATES");
+
+        assert_eq!(sampler.overrides.len(), 1);
+        assert!((sampled_fraction(&sampler, "submit_snapshot") - 1.0).abs() < f64::EPSILON);
+        assert!(sampled_fraction(&sampler, "process_ledgers") < f64::EPSILON);
+    }
+
     #[tokio::test]
     async fn propagation_middleware_accepts_traceparent_header() {
         let app = Router::new()
@@ -28,6 +28,7 @@ use utoipa::OpenApi;
         // Corridors
         crate::api::corridors::list_corridors,
         crate::api::corridors::get_corridor_detail,
+        crate::api::corridors::get_corridor_heatmap,
         // Price Feed
         crate::api::price_feed::get_price,
         crate::api::price_feed::get_prices,
@@ -58,6 +59,7 @@ use utoipa::OpenApi;
         crate::api::contract_events::get_contract_event,
         crate::api::contract_events::get_events_for_epoch,
         crate::api::contract_events::get_event_stats,
+        crate::api::contract_events::get_snapshot_reconciliation,
         // Fee Bumps
         crate::api::fee_bump::get_fee_bump_stats,
         crate::api::fee_bump::get_recent_fee_bumps,
@@ -77,10 +79,12 @@ use utoipa::OpenApi;
         crate::api::network::get_network_info,
         crate::api::network::get_available_networks,
         crate::api::network::switch_network,
+        crate::api::runtime_config::get_runtime_config,
         // Prediction
         crate::api::prediction::predict_success,
         // RPC
         crate::api::rpc::rpc_health_check,
+        crate::api::rpc::rpc_breaker_health,
         crate::api::rpc::get_latest_ledger,
         crate::api::rpc::get_payments,
         crate::api::rpc::get_account_payments,
@@ -166,12 +170,18 @@ use utoipa::OpenApi;
         // Snapshots
         crate::api::snapshots::generate_snapshot,
         crate::api::snapshots::contract_health_check,
+        crate::api::snapshots::diff_snapshots,
+        crate::api::snapshots::get_snapshot_history,
+        // Summary
+        crate::api::summary::get_daily_summary,
     ),
     components(
         schemas(
             crate::api::anchors::AnchorsResponse,
             crate::api::anchors::AnchorMetricsResponse,
             crate::api::corridors::CorridorResponse,
+            crate::api::runtime_config::RuntimeConfig,
+            crate::api::runtime_config::CacheBackend,
             crate::api::corridors::CorridorDetailResponse,
             crate::api::corridors::SuccessRateDataPoint,
             crate::api::corridors::LatencyDataPoint,
@@ -187,9 +197,15 @@ use utoipa::OpenApi;
             crate::api::cost_calculator::CostCalculationResponse,
             crate::api::cost_calculator::ErrorResponse,
             crate::api::snapshots::SnapshotResponse,
+            crate::api::snapshots::SnapshotPreviewResponse,
             crate::api::snapshots::SubmissionInfo,
             crate::api::snapshots::GenerateSnapshotRequest,
             crate::api::snapshots::ContractHealthResponse,
+            crate::api::snapshots::SnapshotHistoryPoint,
+            crate::snapshot::diff::SnapshotDiff,
+            crate::snapshot::diff::CorridorDelta,
+            crate::snapshot::diff::AnchorStatusChange,
+            crate::services::analytics::DailySummary,
         )
     ),
     tags(
@@ -31,9 +31,61 @@
 //! Ok(Json(response))
 //! ```
 
+use std::sync::OnceLock;
+
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+use crate::error::ApiError;
+
+/// Default cap on `limit`; matches Horizon's own per-request maximum.
+pub const DEFAULT_MAX_PAGE_SIZE: i64 = 200;
+
+/// Largest `limit` any list endpoint accepts, from `MAX_PAGE_SIZE`.
+///
+/// Read once per process; invalid or non-positive values fall back to
+/// [`DEFAULT_MAX_PAGE_SIZE`].
+pub fn max_page_size() -> i64 {
+    static MAX_PAGE_SIZE: OnceLock<i64> = OnceLock::new();
+    *MAX_PAGE_SIZE.get_or_init(|| {
+        std::env::var("MAX_PAGE_SIZE")
+            .ok()
+            .and_then(|v| v.parse::<i64>().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(DEFAULT_MAX_PAGE_SIZE)
+    })
+}
+
+/// A `limit` query parameter outside `1..=max`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("limit must be between 1 and {max}, got {limit}")]
+pub struct InvalidLimit {
+    pub limit: i64,
+    pub max: i64,
+}
+
+impl From<InvalidLimit> for ApiError {
+    fn from(err: InvalidLimit) -> Self {
+        Self::bad_request("INVALID_LIMIT", err.to_string())
+    }
+}
+
+/// Check a requested page size against [`max_page_size`].
+///
+/// Oversized limits are rejected rather than clamped so clients learn about
+/// the cap instead of silently receiving short pages.
+pub fn validate_limit(limit: i64) -> Result<i64, InvalidLimit> {
+    check_limit(limit, max_page_size())
+}
+
+fn check_limit(limit: i64, max: i64) -> Result<i64, InvalidLimit> {
+    if (1..=max).contains(&limit) {
+        Ok(limit)
+    } else {
+        Err(InvalidLimit { limit, max })
+    }
+}
+
 /// Pagination metadata included in every list response.
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PageMeta {
@@ -156,6 +208,118 @@ mod tests {
         assert_eq!(meta.prev_offset, Some(0));
     }
 
+    #[test]
+    fn limit_outside_page_size_is_rejected() {
+        assert_eq!(check_limit(200, 200), Ok(200));
+        assert_eq!(
+            check_limit(201, 200),
+            Err(InvalidLimit {
+                limit: 201,
+                max: 200
+            })
+        );
+        assert!(check_limit(0, 200).is_err());
+        assert!(check_limit(-5, 200).is_err());
+    }
+
+    #[tokio::test]
+    async fn limit_is_rejected_consistently_across_endpoints() {
+        use crate::api::{anchors, assets, contract_events, liquidity_pools, trustlines};
+        use crate::cache::{CacheConfig, CacheManager};
+        use crate::database::Database;
+        use crate::rpc::StellarRpcClient;
+        use axum::{body::Body, http::Request, http::StatusCode, routing::get, Router};
+        use std::sync::Arc;
+        use tower::ServiceExt;
+
+        let _guard = crate::lock_env_test();
+        // AppState resolves mainnet URLs even in mock mode
+        for (name, url) in [
+            ("STELLAR_RPC_URL_MAINNET", "https://rpc.example.com"),
+            ("STELLAR_HORIZON_URL_MAINNET", "https://horizon.example.com"),
+        ] {
+            if std::env::var(name).is_err() {
+                std::env::set_var(name, url);
+            }
+        }
+
+        // Limits are checked before any query runs, so no tables are needed
+        let pool = sqlx::SqlitePool::connect(":memory:").await.unwrap();
+        let db = Arc::new(Database::new(pool.clone()));
+        let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+        let cache = Arc::new(CacheManager::new_in_memory_for_tests(CacheConfig::default()));
+        let price_feed = Arc::new(crate::services::price_feed::PriceFeedClient::new(
+            crate::services::price_feed::PriceFeedConfig::default(),
+            crate::services::price_feed::default_asset_mapping(),
+        ));
+        let app_state = crate::state::AppState::new(
+            Arc::clone(&db),
+            Arc::clone(&cache),
+            Arc::new(crate::websocket::WsState::new()),
+            Arc::new(crate::ingestion::DataIngestionService::new(
+                Arc::clone(&rpc_client),
+                Arc::clone(&db),
+            )),
+            Arc::clone(&rpc_client),
+        );
+
+        let app = Router::new()
+            .route("/anchors", get(anchors::get_anchors))
+            .with_state((Arc::clone(&db), cache, Arc::clone(&rpc_client), price_feed))
+            .merge(
+                Router::new()
+                    .route("/muxed", get(anchors::get_muxed_analytics))
+                    .with_state(app_state),
+            )
+            .nest("/assets", assets::routes(pool.clone()))
+            .nest(
+                "/pools",
+                liquidity_pools::routes(Arc::new(
+                    crate::services::liquidity_pool_analyzer::LiquidityPoolAnalyzer::new(
+                        pool.clone(),
+                        rpc_client.clone(),
+                    ),
+                )),
+            )
+            .nest(
+                "/trustlines",
+                trustlines::routes(Arc::new(
+                    crate::services::trustline_analyzer::TrustlineAnalyzer::new(pool, rpc_client),
+                )),
+            )
+            .merge(contract_events::routes(Arc::new(
+                crate::services::event_indexer::EventIndexer::new(db),
+            )));
+
+        let endpoints = [
+            "/anchors",
+            "/muxed",
+            "/assets",
+            "/pools/rankings",
+            "/pools/p1/snapshots",
+            "/trustlines/rankings",
+            "/trustlines/USDC/GABC/history",
+            "/api/analytics/contract-events",
+        ];
+        let oversized = max_page_size() + 1;
+        for endpoint in endpoints {
+            for limit in [0, -1, oversized] {
+                let uri = format!("{endpoint}?limit={limit}");
+                let response = app
+                    .clone()
+                    .oneshot(Request::builder().uri(&uri).body(Body::empty()).unwrap())
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{uri}");
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                assert_eq!(json["error"]["code"], "INVALID_LIMIT", "{uri}");
+            }
+        }
+    }
+
     #[test]
     fn paginated_response_wraps_data() {
         let items = vec![1u32, 2, 3];
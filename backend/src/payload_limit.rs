@@ -1,15 +1,106 @@
 //! Middleware to limit request payload size and prevent DoS attacks
 
 use axum::{
-    extract::Request,
-    http::StatusCode,
-    middleware::Next,
+    extract::{DefaultBodyLimit, Request, State},
+    http::{header::CONTENT_LENGTH, Method, StatusCode},
+    middleware::{self, Next},
     response::{IntoResponse, Response},
+    Router,
 };
 
+use crate::error::ApiError;
+
 /// Maximum payload size in bytes (10MB)
 const MAX_PAYLOAD_SIZE: usize = 10 * 1024 * 1024;
 
+/// Default body limit for mutating routes (2 MiB)
+const DEFAULT_MUTATING_BODY_LIMIT: usize = 2 * 1024 * 1024;
+
+/// Body size limit applied to routes that accept request bodies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BodyLimitConfig {
+    /// Largest body, in bytes, a mutating request may carry
+    pub max_bytes: usize,
+}
+
+impl Default for BodyLimitConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes: DEFAULT_MUTATING_BODY_LIMIT,
+        }
+    }
+}
+
+impl BodyLimitConfig {
+    /// Load from `MAX_MUTATING_BODY_BYTES`, falling back to 2 MiB for missing
+    /// or invalid values.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let max_bytes = std::env::var("MAX_MUTATING_BODY_BYTES")
+            .ok()
+            .and_then(|s| s.parse::<usize>().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(DEFAULT_MUTATING_BODY_LIMIT);
+        Self { max_bytes }
+    }
+}
+
+/// Cap request bodies on `router` at `config.max_bytes`.
+///
+/// Body extractors stop buffering once the limit is reached, and oversized
+/// POST/PUT/PATCH/DELETE requests are answered with a JSON `413` instead of
+/// axum's plain-text rejection. Requests declaring a larger `Content-Length`
+/// are refused before the body is read.
+pub fn limit_mutating_bodies<S>(router: Router<S>, config: BodyLimitConfig) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    router
+        .layer(DefaultBodyLimit::max(config.max_bytes))
+        .layer(middleware::from_fn_with_state(
+            config,
+            body_limit_middleware,
+        ))
+}
+
+fn body_too_large(max_bytes: usize) -> Response {
+    ApiError::payload_too_large(
+        "PAYLOAD_TOO_LARGE",
+        format!("Request body exceeds maximum of {max_bytes} bytes"),
+    )
+    .into_response()
+}
+
+async fn body_limit_middleware(
+    State(config): State<BodyLimitConfig>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let mutating = matches!(
+        *req.method(),
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    );
+    if !mutating {
+        return next.run(req).await;
+    }
+
+    let declared = req
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok());
+    if declared.is_some_and(|length| length > config.max_bytes) {
+        return body_too_large(config.max_bytes);
+    }
+
+    // Streamed bodies are only caught once an extractor hits DefaultBodyLimit
+    let response = next.run(req).await;
+    if response.status() == StatusCode::PAYLOAD_TOO_LARGE {
+        return body_too_large(config.max_bytes);
+    }
+    response
+}
+
 /// Middleware to enforce maximum payload size
 pub async fn payload_limit_middleware(req: Request, next: Next) -> Response {
     // Check Content-Length header if present
@@ -41,7 +132,7 @@ mod tests {
         http::{Request, StatusCode},
         middleware,
         routing::post,
-        Router,
+        Json, Router,
     };
     use tower::ServiceExt;
 
@@ -82,4 +173,84 @@ mod tests {
         let response = app.oneshot(request).await.unwrap();
         assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
     }
+
+    async fn echo_len(Json(body): Json<serde_json::Value>) -> String {
+        body.to_string().len().to_string()
+    }
+
+    fn limited_app(max_bytes: usize) -> Router {
+        limit_mutating_bodies(
+            Router::new().route("/submit", post(echo_len)),
+            BodyLimitConfig { max_bytes },
+        )
+    }
+
+    async fn error_code(response: Response) -> String {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        json["error"]["code"].as_str().unwrap().to_string()
+    }
+
+    fn json_body(len: usize) -> String {
+        format!("{{\"data\":\"{}\"}}", "x".repeat(len))
+    }
+
+    #[tokio::test]
+    async fn test_mutating_body_over_limit_returns_413() {
+        let body = json_body(2048);
+        let request = Request::builder()
+            .method("POST")
+            .uri("/submit")
+            .header("content-type", "application/json")
+            .header("content-length", body.len())
+            .body(Body::from(body))
+            .unwrap();
+
+        let response = limited_app(1024).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(error_code(response).await, "PAYLOAD_TOO_LARGE");
+    }
+
+    #[tokio::test]
+    async fn test_streamed_body_over_limit_returns_413() {
+        // No Content-Length, so only DefaultBodyLimit can catch it
+        let chunks = vec![Ok::<_, std::io::Error>(json_body(2048))];
+        let request = Request::builder()
+            .method("POST")
+            .uri("/submit")
+            .header("content-type", "application/json")
+            .body(Body::from_stream(futures::stream::iter(chunks)))
+            .unwrap();
+
+        let response = limited_app(1024).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(error_code(response).await, "PAYLOAD_TOO_LARGE");
+    }
+
+    #[tokio::test]
+    async fn test_mutating_body_within_limit_is_accepted() {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/submit")
+            .header("content-type", "application/json")
+            .body(Body::from(json_body(100)))
+            .unwrap();
+
+        let response = limited_app(1024).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn test_body_limit_config_from_env() {
+        let _guard = crate::lock_env_test();
+        std::env::set_var("MAX_MUTATING_BODY_BYTES", "4096");
+        assert_eq!(BodyLimitConfig::from_env().max_bytes, 4096);
+
+        std::env::set_var("MAX_MUTATING_BODY_BYTES", "0");
+        assert_eq!(BodyLimitConfig::from_env(), BodyLimitConfig::default());
+
+        std::env::remove_var("MAX_MUTATING_BODY_BYTES");
+    }
 }
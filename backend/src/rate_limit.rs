@@ -5,13 +5,18 @@ use axum::{
     middleware::Next,
     response::{IntoResponse, Response},
 };
+use ipnetwork::IpNetwork;
 use redis::aio::MultiplexedConnection;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::net::IpAddr;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+use crate::crypto::constant_time_eq;
+use crate::logging::redaction::{redact_ip, redact_token, redact_user_id};
 use crate::models::api_key::hash_api_key;
+use crate::observability::metrics;
 
 /// Rate limit configuration for an endpoint
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,6 +83,118 @@ impl ClientIdentifier {
             Self::IpAddress(ip) => format!("ip:{ip}"),
         }
     }
+
+    /// Bucket key safe for logs and metric labels
+    #[must_use]
+    pub fn redacted_key(&self) -> String {
+        match self {
+            Self::ApiKey(key) => format!("apikey:{}", redact_token(key)),
+            Self::User(id) => format!("user:{}", redact_user_id(id)),
+            Self::IpAddress(ip) => format!("ip:{}", redact_ip(ip)),
+        }
+    }
+}
+
+/// Count and log a rejected request.
+fn record_throttle(route: &str, bucket: &str, limit: u32) {
+    metrics::record_rate_limit_rejection(route, bucket);
+    tracing::info!(route, bucket, limit, "Rate limit exceeded");
+}
+
+/// Which allowlist entry let a request skip rate limiting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BypassMatch {
+    ApiKey,
+    Cidr,
+}
+
+impl BypassMatch {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::ApiKey => "api_key",
+            Self::Cidr => "cidr",
+        }
+    }
+}
+
+/// Trusted internal callers (ingestion, monitors) exempt from rate limiting
+#[derive(Debug, Clone, Default)]
+pub struct RateLimitBypass {
+    api_keys: Vec<String>,
+    networks: Vec<IpNetwork>,
+}
+
+impl RateLimitBypass {
+    /// Allow `api_keys` and the IPs or CIDR ranges in `networks`; entries that
+    /// don't parse are dropped
+    #[must_use]
+    pub fn new(
+        api_keys: impl IntoIterator<Item = String>,
+        networks: impl IntoIterator<Item = String>,
+    ) -> Self {
+        let api_keys = api_keys
+            .into_iter()
+            .map(|key| key.trim().to_string())
+            .filter(|key| !key.is_empty())
+            .collect();
+        let networks = networks
+            .into_iter()
+            .map(|entry| entry.trim().to_string())
+            .filter(|entry| !entry.is_empty())
+            .filter_map(|entry| match entry.parse::<IpNetwork>() {
+                Ok(network) => Some(network),
+                Err(e) => {
+                    tracing::warn!("Ignoring invalid rate limit bypass CIDR {}: {}", entry, e);
+                    None
+                }
+            })
+            .collect();
+        Self { api_keys, networks }
+    }
+
+    /// Read comma-separated lists from `RATE_LIMIT_BYPASS_API_KEYS` and
+    /// `RATE_LIMIT_BYPASS_CIDRS`
+    #[must_use]
+    pub fn from_env() -> Self {
+        let list = |name: &str| -> Vec<String> {
+            std::env::var(name)
+                .unwrap_or_default()
+                .split(',')
+                .map(str::to_string)
+                .collect()
+        };
+        Self::new(
+            list("RATE_LIMIT_BYPASS_API_KEYS"),
+            list("RATE_LIMIT_BYPASS_CIDRS"),
+        )
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.api_keys.is_empty() && self.networks.is_empty()
+    }
+
+    /// Whether `bearer_token` or `ip` is allowlisted.
+    ///
+    /// Every configured key is compared in constant time, so response timing
+    /// reveals neither which key nor how much of it matched.
+    #[must_use]
+    pub fn matches(&self, bearer_token: Option<&str>, ip: &str) -> Option<BypassMatch> {
+        if let Some(token) = bearer_token {
+            let key_matched = self.api_keys.iter().fold(false, |matched, key| {
+                matched | constant_time_eq(token.as_bytes(), key.as_bytes())
+            });
+            if key_matched {
+                return Some(BypassMatch::ApiKey);
+            }
+        }
+        let ip = ip.parse::<IpAddr>().ok()?;
+        self.networks
+            .iter()
+            .any(|network| network.contains(ip))
+            .then_some(BypassMatch::Cidr)
+    }
 }
 
 /// Client tier for rate limiting
@@ -118,6 +235,7 @@ pub struct RateLimiter {
     endpoint_configs: Arc<RwLock<HashMap<String, RateLimitConfig>>>,
     fallback_memory_store: Arc<RwLock<HashMap<String, (u32, i64)>>>,
     db_pool: Option<sqlx::SqlitePool>,
+    bypass: RateLimitBypass,
 }
 
 impl RateLimiter {
@@ -153,9 +271,30 @@ impl RateLimiter {
             endpoint_configs: Arc::new(RwLock::new(HashMap::new())),
             fallback_memory_store: Arc::new(RwLock::new(HashMap::new())),
             db_pool,
+            bypass: RateLimitBypass::from_env(),
         })
     }
 
+    /// Replace the bypass allowlist loaded from the environment
+    #[must_use]
+    pub fn with_bypass(mut self, bypass: RateLimitBypass) -> Self {
+        self.bypass = bypass;
+        self
+    }
+
+    /// Check the bypass allowlist, counting a match in metrics
+    fn bypass_match(&self, bearer_token: Option<&str>, ip: &str, route: &str) -> bool {
+        if self.bypass.is_empty() {
+            return false;
+        }
+        let Some(matched) = self.bypass.matches(bearer_token, ip) else {
+            return false;
+        };
+        metrics::record_rate_limit_bypass(route, matched.as_str());
+        tracing::debug!(route, matched = matched.as_str(), "Rate limit bypassed");
+        true
+    }
+
     /// Register a rate limit config for an endpoint
     pub async fn register_endpoint(&self, path: String, config: RateLimitConfig) {
         self.endpoint_configs.write().await.insert(path, config);
@@ -362,6 +501,9 @@ impl RateLimiter {
             if let Ok((allowed, remaining, reset)) =
                 self.check_redis_limit(&mut conn, &key, limit).await
             {
+                if !allowed {
+                    record_throttle(endpoint, &client.redacted_key(), limit);
+                }
                 return (
                     allowed,
                     RateLimitInfo {
@@ -383,6 +525,9 @@ impl RateLimiter {
 
         // Fall back to memory store
         let (allowed, remaining, reset) = self.check_memory_limit(&key, limit).await;
+        if !allowed {
+            record_throttle(endpoint, &client.redacted_key(), limit);
+        }
         (
             allowed,
             RateLimitInfo {
@@ -677,6 +822,17 @@ pub async fn api_key_rate_limit_middleware(
         return next.run(req).await;
     };
 
+    let ip = req
+        .extensions()
+        .get::<ConnectInfo<std::net::SocketAddr>>()
+        .map_or_else(
+            || "unknown".to_string(),
+            |connect_info| connect_info.0.ip().to_string(),
+        );
+    if limiter.bypass_match(Some(token), &ip, req.uri().path()) {
+        return next.run(req).await;
+    }
+
     let Some(api_key_id) = limiter.resolve_api_key_id(token).await else {
         return next.run(req).await;
     };
@@ -685,6 +841,8 @@ pub async fn api_key_rate_limit_middleware(
     let (allowed, info) = limiter.rate_limit_api_key(&api_key_id, limit).await;
 
     if !allowed {
+        let bucket = ClientIdentifier::ApiKey(api_key_id).redacted_key();
+        record_throttle(req.uri().path(), &bucket, limit);
         return RateLimitError { info }.into_response();
     }
 
@@ -730,6 +888,10 @@ pub async fn rate_limit_middleware(
         );
     let path = req.uri().path().to_string();
 
+    if limiter.bypass_match(bearer_token.as_deref(), &ip, &path) {
+        return next.run(req).await;
+    }
+
     // Resolve client identifier from copied request metadata.
     let client = limiter
         .resolve_client_identifier(bearer_token, auth_user_id, ip.clone())
@@ -873,6 +1035,186 @@ mod tests {
         assert!(allowed);
     }
 
+    #[tokio::test]
+    async fn test_rejections_are_counted_per_route_and_bucket() {
+        let limiter = RateLimiter::new_with_db(None).await.unwrap();
+        // Short enough that the route label is not normalized to `:id`
+        let endpoint = format!(
+            "/api/throttled-{}",
+            &uuid::Uuid::new_v4().simple().to_string()[..8]
+        );
+        limiter
+            .register_endpoint(
+                endpoint.clone(),
+                RateLimitConfig {
+                    requests_per_minute: 2,
+                    whitelist_ips: vec![],
+                    client_limits: None,
+                },
+            )
+            .await;
+
+        let counter =
+            metrics::RATE_LIMIT_REJECTIONS_TOTAL.with_label_values(&[&endpoint, "ip:203.0.*.*"]);
+        let before = counter.get();
+
+        for _ in 0..2 {
+            let (allowed, _) = limiter.check_rate_limit("203.0.113.7", &endpoint).await;
+            assert!(allowed);
+        }
+        for _ in 0..3 {
+            let (allowed, _) = limiter.check_rate_limit("203.0.113.7", &endpoint).await;
+            assert!(!allowed);
+        }
+
+        assert_eq!(counter.get() - before, 3);
+    }
+
+    async fn get_status(app: &axum::Router, uri: &str, token: &str) -> StatusCode {
+        use tower::ServiceExt;
+
+        let request = Request::builder()
+            .uri(uri)
+            .header(header::AUTHORIZATION, format!("Bearer {token}"))
+            .body(axum::body::Body::empty())
+            .unwrap();
+        app.clone().oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_allowlisted_key_is_never_throttled() {
+        let endpoint = format!(
+            "/api/bypass-{}",
+            &uuid::Uuid::new_v4().simple().to_string()[..8]
+        );
+        let bypass = RateLimitBypass::new(["si_live_ingestion".to_string()], Vec::new());
+        let limiter = RateLimiter::new_with_db(None)
+            .await
+            .unwrap()
+            .with_bypass(bypass);
+        limiter
+            .register_endpoint(
+                endpoint.clone(),
+                RateLimitConfig {
+                    requests_per_minute: 2,
+                    whitelist_ips: vec![],
+                    client_limits: None,
+                },
+            )
+            .await;
+        let app = axum::Router::new()
+            .route(&endpoint, axum::routing::get(|| async { "ok" }))
+            .layer(axum::middleware::from_fn_with_state(
+                Arc::new(limiter),
+                rate_limit_middleware,
+            ));
+
+        let bypassed =
+            metrics::RATE_LIMIT_BYPASSES_TOTAL.with_label_values(&[&endpoint, "api_key"]);
+        let before = bypassed.get();
+
+        for _ in 0..5 {
+            assert_eq!(
+                get_status(&app, &endpoint, "si_live_ingestion").await,
+                StatusCode::OK
+            );
+        }
+        assert_eq!(bypassed.get() - before, 5);
+
+        for _ in 0..2 {
+            assert_eq!(
+                get_status(&app, &endpoint, "si_live_public").await,
+                StatusCode::OK
+            );
+        }
+        assert_eq!(
+            get_status(&app, &endpoint, "si_live_public").await,
+            StatusCode::TOO_MANY_REQUESTS
+        );
+        assert_eq!(bypassed.get() - before, 5);
+    }
+
+    #[tokio::test]
+    async fn test_allowlisted_cidr_is_never_throttled_when_served() {
+        let endpoint = format!(
+            "/api/cidr-bypass-{}",
+            &uuid::Uuid::new_v4().simple().to_string()[..8]
+        );
+        let bypass = RateLimitBypass::new(Vec::new(), ["127.0.0.0/8".to_string()]);
+        let limiter = RateLimiter::new_with_db(None)
+            .await
+            .unwrap()
+            .with_bypass(bypass);
+        limiter
+            .register_endpoint(
+                endpoint.clone(),
+                RateLimitConfig {
+                    requests_per_minute: 2,
+                    whitelist_ips: vec![],
+                    client_limits: None,
+                },
+            )
+            .await;
+        let app = axum::Router::new()
+            .route(&endpoint, axum::routing::get(|| async { "ok" }))
+            .layer(axum::middleware::from_fn_with_state(
+                Arc::new(limiter),
+                rate_limit_middleware,
+            ));
+
+        // Served like main.rs so the middleware sees the real peer address
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+            )
+            .await
+            .unwrap();
+        });
+
+        let bypassed = metrics::RATE_LIMIT_BYPASSES_TOTAL.with_label_values(&[&endpoint, "cidr"]);
+        let before = bypassed.get();
+
+        let client = reqwest::Client::new();
+        for _ in 0..5 {
+            let response = client
+                .get(format!("http://{addr}{endpoint}"))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), reqwest::StatusCode::OK);
+        }
+        assert_eq!(bypassed.get() - before, 5);
+    }
+
+    #[test]
+    fn test_bypass_matches_keys_and_cidrs() {
+        let bypass = RateLimitBypass::new(
+            ["internal-key".to_string()],
+            [
+                "10.0.0.0/8".to_string(),
+                "192.168.1.7".to_string(),
+                "not-a-cidr".to_string(),
+            ],
+        );
+        assert_eq!(bypass.networks.len(), 2);
+
+        assert_eq!(
+            bypass.matches(Some("internal-key"), "203.0.113.7"),
+            Some(BypassMatch::ApiKey)
+        );
+        assert_eq!(bypass.matches(Some("internal-ke"), "203.0.113.7"), None);
+        assert_eq!(bypass.matches(None, "10.20.30.40"), Some(BypassMatch::Cidr));
+        assert_eq!(
+            bypass.matches(Some("other"), "192.168.1.7"),
+            Some(BypassMatch::Cidr)
+        );
+        assert_eq!(bypass.matches(None, "192.168.1.8"), None);
+        assert_eq!(bypass.matches(None, "unknown"), None);
+    }
+
     #[tokio::test]
     async fn test_api_key_rate_limit_reads_config_table() {
         let db = setup_api_key_rate_limit_db().await;
@@ -1,28 +1,415 @@
 //! Circuit breaker to avoid hammering failing RPC/Horizon endpoints.
 //! Uses the failsafe crate for battle-tested reliability.
 
-use failsafe::{backoff, failure_policy, Config, StateMachine};
-use std::sync::{Arc, OnceLock};
+use failsafe::failure_policy::{ConsecutiveFailures, FailurePolicy};
+use failsafe::{backoff, failure_policy, Config, Instrument, StateMachine};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU8, Ordering};
+use std::sync::{Arc, OnceLock, PoisonError, RwLock, Weak};
 use std::time::Duration;
 
-/// Concrete circuit breaker type using a fixed backoff and consecutive-failure policy.
-pub type CircuitBreaker =
-    StateMachine<failure_policy::ConsecutiveFailures<std::iter::Repeat<Duration>>, ()>;
+use super::config::circuit_breaker_config_for_endpoint;
+use super::metrics;
+
+/// Underlying failsafe state machine wrapped by [`CircuitBreaker`].
+pub type CircuitBreakerMachine = StateMachine<ForceablePolicy, CircuitBreakerInstrument>;
+
+/// Circuit breaker using a fixed backoff and consecutive-failure policy that
+/// an operator can pin open or closed.
+///
+/// Derefs to the failsafe state machine, so `call`, `on_error` and
+/// `is_call_permitted` work as before.
+pub struct CircuitBreaker {
+    machine: CircuitBreakerMachine,
+    endpoint: String,
+    forced: Arc<AtomicU8>,
+    probation: Arc<Probation>,
+}
 pub type SharedCircuitBreaker = Arc<CircuitBreaker>;
 
+/// How long a forced-open breaker stays open; effectively until cleared.
+const FORCED_OPEN_DURATION: Duration = Duration::from_secs(10 * 365 * 24 * 60 * 60);
+
+const AUTOMATIC: u8 = 0;
+const FORCED_OPEN: u8 = 1;
+const FORCED_CLOSED: u8 = 2;
+
+/// Operator override applied to a breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForcedState {
+    Open,
+    Closed,
+}
+
+impl ForcedState {
+    /// Value reported on the `circuit_breaker_forced` gauge.
+    #[must_use]
+    pub const fn gauge_value(forced: Option<Self>) -> i64 {
+        match forced {
+            None => 0,
+            Some(Self::Open) => 1,
+            Some(Self::Closed) => 2,
+        }
+    }
+}
+
+impl CircuitBreaker {
+    #[must_use]
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Current operator override, if any.
+    #[must_use]
+    pub fn forced_state(&self) -> Option<ForcedState> {
+        match self.forced.load(Ordering::SeqCst) {
+            FORCED_OPEN => Some(ForcedState::Open),
+            FORCED_CLOSED => Some(ForcedState::Closed),
+            _ => None,
+        }
+    }
+
+    /// Reject every call until [`Self::force_reset`] is called.
+    pub fn force_open(&self) {
+        self.set_forced(FORCED_OPEN);
+        // Re-open from closed so the open period is the forced one, not
+        // whatever backoff the breaker may already be serving.
+        self.machine.reset();
+        self.machine.on_error();
+        tracing::warn!(endpoint = %self.endpoint, "Circuit breaker forced open");
+    }
+
+    /// Admit every call, regardless of failures, until [`Self::force_reset`]
+    /// is called.
+    pub fn force_close(&self) {
+        self.set_forced(FORCED_CLOSED);
+        self.machine.reset();
+        tracing::warn!(endpoint = %self.endpoint, "Circuit breaker forced closed");
+    }
+
+    /// Clear any override and return to automatic behaviour from closed.
+    pub fn force_reset(&self) {
+        self.set_forced(AUTOMATIC);
+        self.machine.reset();
+        tracing::info!(endpoint = %self.endpoint, "Circuit breaker override cleared");
+    }
+
+    fn set_forced(&self, value: u8) {
+        self.forced.store(value, Ordering::SeqCst);
+        self.probation.clear();
+        metrics::set_circuit_breaker_forced(
+            &self.endpoint,
+            ForcedState::gauge_value(self.forced_state()),
+        );
+    }
+}
+
+impl Deref for CircuitBreaker {
+    type Target = CircuitBreakerMachine;
+
+    fn deref(&self) -> &Self::Target {
+        &self.machine
+    }
+}
+
+impl fmt::Debug for CircuitBreaker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CircuitBreaker")
+            .field("endpoint", &self.endpoint)
+            .field("machine", &self.machine)
+            .field("forced", &self.forced_state())
+            .finish()
+    }
+}
+
+/// Successes still owed after a half-open probe before the breaker counts as
+/// closed again. Failsafe recloses on the first probe success, so a failure
+/// inside this window re-opens the breaker straight away instead.
+#[derive(Debug, Default)]
+struct Probation {
+    remaining: AtomicU32,
+    /// Set between half-opening and the probe's outcome
+    probing: AtomicBool,
+}
+
+impl Probation {
+    fn start(&self, success_threshold: u32) {
+        self.remaining
+            .store(success_threshold.max(1), Ordering::SeqCst);
+        self.probing.store(true, Ordering::SeqCst);
+    }
+
+    fn clear(&self) {
+        self.remaining.store(0, Ordering::SeqCst);
+        self.probing.store(false, Ordering::SeqCst);
+    }
+
+    fn active(&self) -> bool {
+        self.probing.load(Ordering::SeqCst) || self.remaining.load(Ordering::SeqCst) > 0
+    }
+
+    /// Count one success, returning the successes still owed.
+    fn record_success(&self) -> u32 {
+        let previous = self
+            .remaining
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+                Some(n.saturating_sub(1))
+            })
+            .unwrap_or_default();
+        previous.saturating_sub(1)
+    }
+}
+
+/// Consecutive-failure policy that defers to the breaker's forced state and
+/// enforces the half-open success threshold.
+#[derive(Debug)]
+pub struct ForceablePolicy {
+    inner: ConsecutiveFailures<std::iter::Repeat<Duration>>,
+    forced: Arc<AtomicU8>,
+    probation: Arc<Probation>,
+    reopen_delay: Duration,
+    instrument: CircuitBreakerInstrument,
+}
+
+impl FailurePolicy for ForceablePolicy {
+    fn record_success(&mut self) {
+        self.inner.record_success();
+        // The probe's own success is reported by `on_closed`; later ones
+        // have no transition of their own, so report the reclose here.
+        let probe = self.probation.probing.swap(false, Ordering::SeqCst);
+        if self.probation.remaining.load(Ordering::SeqCst) > 0
+            && self.probation.record_success() == 0
+            && !probe
+        {
+            self.instrument.transition(CircuitState::Closed);
+        }
+    }
+
+    fn mark_dead_on_failure(&mut self) -> Option<Duration> {
+        match self.forced.load(Ordering::SeqCst) {
+            FORCED_OPEN => Some(FORCED_OPEN_DURATION),
+            FORCED_CLOSED => None,
+            _ if self.probation.active() => Some(self.reopen_delay),
+            _ => self.inner.mark_dead_on_failure(),
+        }
+    }
+
+    fn revived(&mut self) {
+        self.inner.revived();
+    }
+}
+
+/// Callback invoked with the endpoint label and new state on every transition.
+pub type StateChangeCallback = Arc<dyn Fn(&str, CircuitState) + Send + Sync>;
+
 pub fn rpc_circuit_breaker() -> SharedCircuitBreaker {
     static BREAKER: OnceLock<SharedCircuitBreaker> = OnceLock::new();
     BREAKER
         .get_or_init(|| {
-            let config = CircuitBreakerConfig::default();
-            let backoff = backoff::constant(config.timeout_duration);
-            let policy = failure_policy::consecutive_failures(config.failure_threshold, backoff);
-            let cb: CircuitBreaker = Config::new().failure_policy(policy).build();
-            Arc::new(cb)
+            build_circuit_breaker(
+                "stellar",
+                &circuit_breaker_config_for_endpoint("stellar"),
+                None,
+            )
         })
         .clone()
 }
 
+/// Build a circuit breaker for `endpoint`, optionally notifying `on_state_change`
+/// whenever the breaker opens, half-opens or closes.
+///
+/// The state gauge is always updated; the callback is for push-style
+/// notifications (alerts, Slack) on top of that.
+#[must_use]
+pub fn build_circuit_breaker(
+    endpoint: &str,
+    config: &CircuitBreakerConfig,
+    on_state_change: Option<StateChangeCallback>,
+) -> SharedCircuitBreaker {
+    let backoff = backoff::constant(config.timeout_duration);
+    let forced = Arc::new(AtomicU8::new(AUTOMATIC));
+    let probation = Arc::new(Probation::default());
+    let instrument = CircuitBreakerInstrument::new(endpoint, on_state_change)
+        .with_probation(config.success_threshold, probation.clone());
+    let policy = ForceablePolicy {
+        inner: failure_policy::consecutive_failures(config.failure_threshold, backoff),
+        forced: forced.clone(),
+        probation: probation.clone(),
+        reopen_delay: config.timeout_duration,
+        instrument: instrument.clone(),
+    };
+    let machine: CircuitBreakerMachine = Config::new()
+        .failure_policy(policy)
+        .instrument(instrument)
+        .build();
+    let cb = Arc::new(CircuitBreaker {
+        machine,
+        endpoint: endpoint.to_string(),
+        forced,
+        probation,
+    });
+    metrics::set_circuit_breaker_forced(endpoint, ForcedState::gauge_value(None));
+    breaker_registry()
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(endpoint.to_string(), Arc::downgrade(&cb));
+    cb
+}
+
+/// Live breakers built through [`build_circuit_breaker`], keyed by endpoint.
+fn breaker_registry() -> &'static RwLock<BTreeMap<String, Weak<CircuitBreaker>>> {
+    static BREAKERS: OnceLock<RwLock<BTreeMap<String, Weak<CircuitBreaker>>>> = OnceLock::new();
+    BREAKERS.get_or_init(|| RwLock::new(BTreeMap::new()))
+}
+
+/// Look up the most recently built breaker for `endpoint`, if it is still alive.
+#[must_use]
+pub fn find_circuit_breaker(endpoint: &str) -> Option<SharedCircuitBreaker> {
+    breaker_registry()
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(endpoint)
+        .and_then(Weak::upgrade)
+}
+
+/// Every live breaker, keyed by endpoint.
+#[must_use]
+pub fn circuit_breakers() -> BTreeMap<String, SharedCircuitBreaker> {
+    breaker_registry()
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .filter_map(|(endpoint, breaker)| Some((endpoint.clone(), breaker.upgrade()?)))
+        .collect()
+}
+
+/// Last observed state of every breaker built through [`build_circuit_breaker`].
+fn state_registry() -> &'static RwLock<BTreeMap<String, CircuitState>> {
+    static STATES: OnceLock<RwLock<BTreeMap<String, CircuitState>>> = OnceLock::new();
+    STATES.get_or_init(|| RwLock::new(BTreeMap::new()))
+}
+
+/// Snapshot of the current state of each known breaker, keyed by endpoint.
+#[must_use]
+pub fn circuit_breaker_states() -> BTreeMap<String, CircuitState> {
+    state_registry()
+        .read()
+        .map(|states| states.clone())
+        .unwrap_or_default()
+}
+
+/// Breaker state as observed by transition callbacks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+impl CircuitState {
+    /// Value reported on the `circuit_breaker_state` gauge.
+    #[must_use]
+    pub const fn gauge_value(self) -> i64 {
+        match self {
+            Self::Closed => 0,
+            Self::Open => 1,
+            Self::HalfOpen => 2,
+        }
+    }
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half_open",
+        }
+    }
+}
+
+impl fmt::Display for CircuitState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Failsafe instrument that records state transitions as metrics and
+/// forwards them to an optional callback.
+#[derive(Clone)]
+pub struct CircuitBreakerInstrument {
+    endpoint: String,
+    on_state_change: Option<StateChangeCallback>,
+    success_threshold: u32,
+    probation: Arc<Probation>,
+}
+
+impl CircuitBreakerInstrument {
+    #[must_use]
+    pub fn new(endpoint: &str, on_state_change: Option<StateChangeCallback>) -> Self {
+        Self {
+            endpoint: endpoint.to_string(),
+            on_state_change,
+            success_threshold: 1,
+            probation: Arc::default(),
+        }
+    }
+
+    fn with_probation(mut self, success_threshold: u32, probation: Arc<Probation>) -> Self {
+        self.success_threshold = success_threshold;
+        self.probation = probation;
+        self
+    }
+
+    fn transition(&self, state: CircuitState) {
+        metrics::set_circuit_breaker_state(&self.endpoint, state.gauge_value());
+        if let Ok(mut states) = state_registry().write() {
+            states.insert(self.endpoint.clone(), state);
+        }
+        if let Some(callback) = &self.on_state_change {
+            callback(&self.endpoint, state);
+        }
+    }
+}
+
+impl fmt::Debug for CircuitBreakerInstrument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CircuitBreakerInstrument")
+            .field("endpoint", &self.endpoint)
+            .field("has_callback", &self.on_state_change.is_some())
+            .field("success_threshold", &self.success_threshold)
+            .finish()
+    }
+}
+
+impl Instrument for CircuitBreakerInstrument {
+    fn on_call_rejected(&self) {}
+
+    fn on_open(&self) {
+        tracing::warn!(endpoint = %self.endpoint, "Circuit breaker opened");
+        self.probation.clear();
+        self.transition(CircuitState::Open);
+    }
+
+    fn on_half_open(&self) {
+        tracing::info!(endpoint = %self.endpoint, "Circuit breaker half-open");
+        self.probation.start(self.success_threshold);
+        self.transition(CircuitState::HalfOpen);
+    }
+
+    fn on_closed(&self) {
+        // Still owed successes: keep reporting half-open until they arrive
+        if self.probation.remaining.load(Ordering::SeqCst) > 0 {
+            return;
+        }
+        self.transition(CircuitState::Closed);
+    }
+}
+
 /// Configuration for the circuit breaker.
 ///
 /// The circuit breaker protects against cascading failures by automatically opening
@@ -37,9 +424,13 @@ pub fn rpc_circuit_breaker() -> SharedCircuitBreaker {
 /// # Configuration Parameters
 ///
 /// * `failure_threshold` - Number of consecutive failures before opening
-/// * `success_threshold` - Successes needed to close circuit (legacy, unused)
+/// * `success_threshold` - Consecutive successes after half-opening before the
+///   circuit counts as closed; a failure before then re-opens it immediately
 /// * `timeout_duration` - How long circuit stays open before testing recovery
 ///
+/// Each endpoint can override these, see
+/// [`circuit_breaker_config_for_endpoint`].
+///
 /// # Example
 ///
 /// ```rust,no_run
@@ -48,7 +439,7 @@ pub fn rpc_circuit_breaker() -> SharedCircuitBreaker {
 ///
 /// let config = CircuitBreakerConfig {
 ///     failure_threshold: 5,           // Open after 5 consecutive failures
-///     success_threshold: 2,           // (unused in failsafe 1.3)
+///     success_threshold: 2,           // Reclose after 2 successes
 ///     timeout_duration: Duration::from_secs(30), // Stay open for 30 seconds
 /// };
 ///
@@ -59,12 +450,36 @@ pub fn rpc_circuit_breaker() -> SharedCircuitBreaker {
 pub struct CircuitBreakerConfig {
     /// Consecutive retryable failures required to trip the circuit open.
     pub failure_threshold: u32,
-    /// Retained for config compatibility; not supported by failsafe 1.3.
+    /// Consecutive successes after half-opening required to reclose.
     pub success_threshold: u32,
     /// How long the circuit stays open before attempting recovery.
     pub timeout_duration: Duration,
 }
 
+/// Per-endpoint replacement for some [`CircuitBreakerConfig`] fields.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CircuitBreakerOverride {
+    pub failure_threshold: Option<u32>,
+    pub success_threshold: Option<u32>,
+    pub timeout_duration: Option<Duration>,
+}
+
+impl CircuitBreakerConfig {
+    /// This config with every field set in `overrides` replaced.
+    #[must_use]
+    pub fn with_override(&self, overrides: &CircuitBreakerOverride) -> Self {
+        Self {
+            failure_threshold: overrides
+                .failure_threshold
+                .unwrap_or(self.failure_threshold),
+            success_threshold: overrides
+                .success_threshold
+                .unwrap_or(self.success_threshold),
+            timeout_duration: overrides.timeout_duration.unwrap_or(self.timeout_duration),
+        }
+    }
+}
+
 impl Default for CircuitBreakerConfig {
     fn default() -> Self {
         Self {
@@ -74,3 +489,159 @@ impl Default for CircuitBreakerConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_callback_observes_state_transitions() {
+        let observed: Arc<Mutex<Vec<(String, CircuitState)>>> = Arc::new(Mutex::new(Vec::new()));
+        let sink = observed.clone();
+        let callback: StateChangeCallback = Arc::new(move |endpoint, state| {
+            sink.lock().unwrap().push((endpoint.to_string(), state));
+        });
+
+        let config = CircuitBreakerConfig {
+            failure_threshold: 2,
+            success_threshold: 1,
+            timeout_duration: Duration::from_millis(20),
+        };
+        let breaker = build_circuit_breaker("test_endpoint", &config, Some(callback));
+
+        breaker.on_error();
+        breaker.on_error();
+        assert!(!breaker.is_call_permitted());
+
+        std::thread::sleep(Duration::from_millis(40));
+        assert!(breaker.is_call_permitted());
+
+        let states: Vec<CircuitState> = observed.lock().unwrap().iter().map(|(_, s)| *s).collect();
+        assert_eq!(
+            states,
+            vec![
+                CircuitState::Closed,
+                CircuitState::Open,
+                CircuitState::HalfOpen
+            ]
+        );
+        assert!(observed
+            .lock()
+            .unwrap()
+            .iter()
+            .all(|(endpoint, _)| endpoint == "test_endpoint"));
+    }
+
+    #[test]
+    fn test_state_registry_tracks_latest_state() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 1,
+            success_threshold: 1,
+            timeout_duration: Duration::from_secs(60),
+        };
+        let breaker = build_circuit_breaker("registry_endpoint", &config, None);
+        assert_eq!(
+            circuit_breaker_states().get("registry_endpoint"),
+            Some(&CircuitState::Closed)
+        );
+
+        breaker.on_error();
+        assert_eq!(
+            circuit_breaker_states().get("registry_endpoint"),
+            Some(&CircuitState::Open)
+        );
+    }
+
+    #[test]
+    fn test_success_threshold_is_independent_per_endpoint() {
+        let defaults = CircuitBreakerConfig {
+            failure_threshold: 2,
+            success_threshold: 1,
+            timeout_duration: Duration::from_millis(20),
+        };
+        let write_config = defaults.with_override(&CircuitBreakerOverride {
+            success_threshold: Some(3),
+            ..CircuitBreakerOverride::default()
+        });
+        let read = build_circuit_breaker("probation_read", &defaults, None);
+        let write = build_circuit_breaker("probation_write", &write_config, None);
+
+        for breaker in [&read, &write] {
+            breaker.on_error();
+            breaker.on_error();
+            assert!(!breaker.is_call_permitted());
+        }
+        std::thread::sleep(Duration::from_millis(40));
+        for breaker in [&read, &write] {
+            assert!(breaker.is_call_permitted());
+            breaker.on_success();
+        }
+
+        let states = circuit_breaker_states();
+        assert_eq!(states.get("probation_read"), Some(&CircuitState::Closed));
+        assert_eq!(states.get("probation_write"), Some(&CircuitState::HalfOpen));
+
+        // Read is fully closed and tolerates a failure; write re-opens at once
+        read.on_error();
+        assert!(read.is_call_permitted());
+        write.on_error();
+        assert!(!write.is_call_permitted());
+
+        std::thread::sleep(Duration::from_millis(40));
+        assert!(write.is_call_permitted());
+        for _ in 0..3 {
+            write.on_success();
+        }
+        assert_eq!(
+            circuit_breaker_states().get("probation_write"),
+            Some(&CircuitState::Closed)
+        );
+        write.on_error();
+        assert!(write.is_call_permitted());
+    }
+
+    #[test]
+    fn test_forced_open_rejects_calls_until_reset() {
+        let breaker = build_circuit_breaker(
+            "forced_open_endpoint",
+            &CircuitBreakerConfig::default(),
+            None,
+        );
+        let found = find_circuit_breaker("forced_open_endpoint").unwrap();
+        assert!(Arc::ptr_eq(&breaker, &found));
+
+        breaker.force_open();
+        assert_eq!(breaker.forced_state(), Some(ForcedState::Open));
+        assert!(!breaker.is_call_permitted());
+        breaker.on_success();
+        assert!(!breaker.is_call_permitted());
+
+        breaker.force_reset();
+        assert_eq!(breaker.forced_state(), None);
+        assert!(breaker.is_call_permitted());
+    }
+
+    #[test]
+    fn test_forced_close_admits_calls_despite_failures() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 1,
+            success_threshold: 1,
+            timeout_duration: Duration::from_secs(60),
+        };
+        let breaker = build_circuit_breaker("forced_close_endpoint", &config, None);
+        breaker.on_error();
+        assert!(!breaker.is_call_permitted());
+
+        breaker.force_close();
+        assert!(breaker.is_call_permitted());
+        for _ in 0..10 {
+            breaker.on_error();
+        }
+        assert!(breaker.is_call_permitted());
+
+        breaker.force_reset();
+        breaker.on_error();
+        assert!(!breaker.is_call_permitted());
+    }
+}
@@ -27,7 +27,13 @@ pub trait StellarRpcClientTrait: Send + Sync {
     async fn fetch_latest_ledger(&self) -> Result<LedgerInfo, RpcError>;
 
     /// Fetch ledger by sequence number
-    async fn fetch_ledger_by_sequence(&self, sequence: u64) -> Result<LedgerInfo, RpcError>;
+    async fn fetch_ledger(&self, sequence: u64) -> Result<LedgerInfo, RpcError>;
+
+    /// Former name of [`Self::fetch_ledger`]
+    #[deprecated(note = "renamed to `fetch_ledger`")]
+    async fn fetch_ledger_by_sequence(&self, sequence: u64) -> Result<LedgerInfo, RpcError> {
+        self.fetch_ledger(sequence).await
+    }
 
     /// Fetch multiple ledgers with pagination
     async fn fetch_ledgers(
@@ -72,6 +78,15 @@ pub trait StellarRpcClientTrait: Send + Sync {
     /// Fetch trades from the order book
     async fn fetch_trades(&self, limit: u32, cursor: Option<&str>) -> Result<Vec<Trade>, RpcError>;
 
+    /// Fetch trades between a base and counter asset
+    async fn fetch_trades_for_pair(
+        &self,
+        base: &crate::rpc::stellar::Asset,
+        counter: &crate::rpc::stellar::Asset,
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> Result<Vec<Trade>, RpcError>;
+
     /// Fetch order book for a trading pair
     async fn fetch_order_book(
         &self,
@@ -113,8 +128,8 @@ impl StellarRpcClientTrait for StellarRpcClient {
         StellarRpcClient::fetch_latest_ledger(self).await
     }
 
-    async fn fetch_ledger_by_sequence(&self, sequence: u64) -> Result<LedgerInfo, RpcError> {
-        StellarRpcClient::fetch_ledger_by_sequence(self, sequence).await
+    async fn fetch_ledger(&self, sequence: u64) -> Result<LedgerInfo, RpcError> {
+        StellarRpcClient::fetch_ledger(self, sequence).await
     }
 
     async fn fetch_ledgers(
@@ -169,6 +184,16 @@ impl StellarRpcClientTrait for StellarRpcClient {
         StellarRpcClient::fetch_trades(self, limit, cursor).await
     }
 
+    async fn fetch_trades_for_pair(
+        &self,
+        base: &crate::rpc::stellar::Asset,
+        counter: &crate::rpc::stellar::Asset,
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> Result<Vec<Trade>, RpcError> {
+        StellarRpcClient::fetch_trades_for_pair(self, base, counter, limit, cursor).await
+    }
+
     async fn fetch_order_book(
         &self,
         selling_asset: &crate::rpc::stellar::Asset,
@@ -256,7 +281,7 @@ impl StellarRpcClientTrait for MockStellarRpcClient {
         })
     }
 
-    async fn fetch_ledger_by_sequence(&self, sequence: u64) -> Result<LedgerInfo, RpcError> {
+    async fn fetch_ledger(&self, sequence: u64) -> Result<LedgerInfo, RpcError> {
         Ok(LedgerInfo {
             sequence,
             hash: format!("mock-ledger-hash-{sequence}"),
@@ -345,6 +370,16 @@ impl StellarRpcClientTrait for MockStellarRpcClient {
         Ok(vec![])
     }
 
+    async fn fetch_trades_for_pair(
+        &self,
+        _base: &crate::rpc::stellar::Asset,
+        _counter: &crate::rpc::stellar::Asset,
+        _limit: u32,
+        _cursor: Option<&str>,
+    ) -> Result<Vec<Trade>, RpcError> {
+        Ok(vec![])
+    }
+
     async fn fetch_order_book(
         &self,
         _selling_asset: &crate::rpc::stellar::Asset,
@@ -0,0 +1,178 @@
+//! Single-flight coalescing of identical concurrent RPC requests.
+//!
+//! While a request for a key is in flight, later callers with the same key
+//! wait for its result instead of issuing their own HTTP call. Nothing is kept
+//! once the call finishes, so this dedupes a stampede even with no response
+//! cache in front of the client.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Mutex, PoisonError};
+
+use tokio::sync::watch;
+
+use super::error::RpcError;
+
+type Outcome<T> = Option<Result<T, RpcError>>;
+
+/// In-flight requests keyed by method and parameters
+#[derive(Default)]
+pub struct RequestCoalescer {
+    in_flight: Mutex<HashMap<String, Box<dyn Any + Send + Sync>>>,
+}
+
+enum Role<T> {
+    Leader(watch::Sender<Outcome<T>>),
+    Follower(watch::Receiver<Outcome<T>>),
+    /// The key is in flight with a different result type; run uncoalesced
+    Alone,
+}
+
+/// Removes the leader's entry even if its future is dropped mid-flight, so
+/// followers stop waiting and fetch for themselves
+struct InFlightGuard<'a> {
+    coalescer: &'a RequestCoalescer,
+    key: &'a str,
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.coalescer
+            .in_flight
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(self.key);
+    }
+}
+
+impl RequestCoalescer {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `fetch` for `key`, or share the result of the call already in
+    /// flight for it.
+    pub async fn run<T, F, Fut>(&self, key: String, fetch: F) -> Result<T, RpcError>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, RpcError>>,
+    {
+        let role = {
+            let mut in_flight = self
+                .in_flight
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            match in_flight.get(&key) {
+                Some(entry) => entry
+                    .downcast_ref::<watch::Receiver<Outcome<T>>>()
+                    .map_or(Role::Alone, |rx| Role::Follower(rx.clone())),
+                None => {
+                    let (tx, rx) = watch::channel(None);
+                    in_flight.insert(key.clone(), Box::new(rx));
+                    Role::Leader(tx)
+                }
+            }
+        };
+
+        match role {
+            Role::Leader(tx) => {
+                let guard = InFlightGuard {
+                    coalescer: self,
+                    key: &key,
+                };
+                let result = fetch().await;
+                drop(guard);
+                tx.send_replace(Some(result.clone()));
+                result
+            }
+            Role::Follower(mut rx) => {
+                if let Ok(outcome) = rx.wait_for(Option::is_some).await {
+                    if let Some(result) = outcome.clone() {
+                        return result;
+                    }
+                }
+                // The leader was cancelled before finishing
+                fetch().await
+            }
+            Role::Alone => fetch().await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_concurrent_identical_keys_share_one_call() {
+        let coalescer = Arc::new(RequestCoalescer::new());
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..10)
+            .map(|_| {
+                let coalescer = coalescer.clone();
+                let calls = calls.clone();
+                tokio::spawn(async move {
+                    coalescer
+                        .run("ledger".to_string(), || async {
+                            calls.fetch_add(1, Ordering::SeqCst);
+                            tokio::time::sleep(Duration::from_millis(50)).await;
+                            Ok::<_, RpcError>(7_u64)
+                        })
+                        .await
+                })
+            })
+            .collect();
+        for task in tasks {
+            assert_eq!(task.await.unwrap().unwrap(), 7);
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        // Finished calls are forgotten; the next one goes upstream again
+        coalescer
+            .run("ledger".to_string(), || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Ok::<_, RpcError>(8_u64)
+            })
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_followers_fetch_themselves_when_leader_is_cancelled() {
+        let coalescer = Arc::new(RequestCoalescer::new());
+
+        let leader = {
+            let coalescer = coalescer.clone();
+            tokio::spawn(async move {
+                coalescer
+                    .run("ledger".to_string(), || async {
+                        tokio::time::sleep(Duration::from_secs(60)).await;
+                        Ok::<_, RpcError>(1_u64)
+                    })
+                    .await
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let follower = {
+            let coalescer = coalescer.clone();
+            tokio::spawn(async move {
+                coalescer
+                    .run("ledger".to_string(), || async { Ok::<_, RpcError>(2_u64) })
+                    .await
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        leader.abort();
+
+        assert_eq!(follower.await.unwrap().unwrap(), 2);
+    }
+}
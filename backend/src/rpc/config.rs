@@ -2,7 +2,7 @@
 
 use std::time::Duration;
 
-use super::circuit_breaker::CircuitBreakerConfig;
+use super::circuit_breaker::{CircuitBreakerConfig, CircuitBreakerOverride};
 
 /// Load circuit breaker and retry config from environment with defaults.
 #[must_use]
@@ -29,6 +29,39 @@ pub fn circuit_breaker_config_from_env() -> CircuitBreakerConfig {
     }
 }
 
+/// Overrides for `endpoint` from `RPC_CIRCUIT_BREAKER_<ENDPOINT>_FAILURE_THRESHOLD`,
+/// `..._SUCCESS_THRESHOLD` and `..._TIMEOUT_SECONDS`, where `<ENDPOINT>` is the
+/// upper-cased endpoint label with other characters replaced by `_`.
+#[must_use]
+pub fn circuit_breaker_override_from_env(endpoint: &str) -> CircuitBreakerOverride {
+    let prefix: String = endpoint
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let var = |suffix: &str| {
+        std::env::var(format!("RPC_CIRCUIT_BREAKER_{prefix}_{suffix}"))
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+    };
+    CircuitBreakerOverride {
+        failure_threshold: var("FAILURE_THRESHOLD").map(|n| n.clamp(1, 100) as u32),
+        success_threshold: var("SUCCESS_THRESHOLD").map(|n| n.clamp(1, 100) as u32),
+        timeout_duration: var("TIMEOUT_SECONDS").map(|s| Duration::from_secs(s.clamp(1, 3600))),
+    }
+}
+
+/// Global breaker config with any overrides for `endpoint` applied.
+#[must_use]
+pub fn circuit_breaker_config_for_endpoint(endpoint: &str) -> CircuitBreakerConfig {
+    circuit_breaker_config_from_env().with_override(&circuit_breaker_override_from_env(endpoint))
+}
+
 /// Max retries for `retry_with_backoff` (from `RPC_MAX_RETRIES`, default 3).
 #[must_use]
 pub fn max_retries_from_env() -> u32 {
@@ -60,3 +93,190 @@ pub fn max_backoff_from_env() -> Duration {
         .clamp(100, 60_000);
     Duration::from_millis(ms)
 }
+
+/// Whether identical concurrent requests share one upstream call
+/// (from `RPC_COALESCE_REQUESTS`, default false).
+#[must_use]
+pub fn coalesce_requests_from_env() -> bool {
+    std::env::var("RPC_COALESCE_REQUESTS")
+        .ok()
+        .and_then(|s| s.parse::<bool>().ok())
+        .unwrap_or(false)
+}
+
+/// HTTP request timeout for RPC/Horizon calls (from `RPC_HTTP_TIMEOUT_MS`, default 30000).
+#[must_use]
+pub fn http_timeout_from_env() -> Duration {
+    let ms = std::env::var("RPC_HTTP_TIMEOUT_MS")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(30_000)
+        .clamp(100, 600_000);
+    Duration::from_millis(ms)
+}
+
+/// Refuse to run the RPC client in mock mode in production.
+///
+/// Mock mode serves fabricated balances, payments and ledgers, so a
+/// production instance started with `RPC_MOCK_MODE=true` by mistake would
+/// publish fake data. Outside production this always passes; in production it
+/// fails unless `ALLOW_MOCK_IN_PROD=true`, and logs an error either way.
+pub fn ensure_mock_mode_allowed(mock_mode: bool) -> anyhow::Result<()> {
+    if !mock_mode || !crate::env_config::is_production_env() {
+        return Ok(());
+    }
+    let allowed = std::env::var("ALLOW_MOCK_IN_PROD")
+        .ok()
+        .and_then(|s| s.parse::<bool>().ok())
+        .unwrap_or(false);
+    if allowed {
+        tracing::error!(
+            "RPC mock mode is enabled in production (ALLOW_MOCK_IN_PROD=true); \
+             every RPC response is fabricated"
+        );
+        Ok(())
+    } else {
+        tracing::error!(
+            "Refusing to start in RPC mock mode in production; \
+             unset RPC_MOCK_MODE or set ALLOW_MOCK_IN_PROD=true"
+        );
+        Err(anyhow::anyhow!(
+            "RPC_MOCK_MODE=true is not allowed in production without ALLOW_MOCK_IN_PROD=true"
+        ))
+    }
+}
+
+/// HTTP client settings shared by RPC and Horizon requests.
+///
+/// Interactive deployments do well with the defaults. Backfills that fan out
+/// many concurrent requests to one host benefit from a larger idle pool
+/// (e.g. `RPC_POOL_MAX_IDLE_PER_HOST=64`) so connections are reused instead of
+/// re-established; a short idle timeout keeps quiet periods from holding
+/// sockets open.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcClientConfig {
+    pub timeout: Duration,
+    /// Idle keep-alive connections retained per host
+    pub pool_max_idle_per_host: usize,
+    /// How long an idle connection is kept before being closed
+    pub pool_idle_timeout: Duration,
+    /// `User-Agent` sent with every request so upstream providers can attribute traffic
+    pub user_agent: String,
+}
+
+/// `User-Agent` used when `RPC_USER_AGENT` is unset
+pub const DEFAULT_USER_AGENT: &str = concat!("stellar-insights/", env!("CARGO_PKG_VERSION"));
+
+impl RpcClientConfig {
+    /// Load from `RPC_HTTP_TIMEOUT_MS`, `RPC_POOL_MAX_IDLE_PER_HOST`
+    /// (default 16), `RPC_POOL_IDLE_TIMEOUT_SECONDS` (default 90) and
+    /// `RPC_USER_AGENT` (default [`DEFAULT_USER_AGENT`]).
+    #[must_use]
+    pub fn from_env() -> Self {
+        let pool_max_idle_per_host = std::env::var("RPC_POOL_MAX_IDLE_PER_HOST")
+            .ok()
+            .and_then(|s| s.parse::<usize>().ok())
+            .unwrap_or(16)
+            .clamp(0, 1024);
+        let pool_idle_timeout_secs = std::env::var("RPC_POOL_IDLE_TIMEOUT_SECONDS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(90)
+            .clamp(1, 3600);
+        let user_agent = std::env::var("RPC_USER_AGENT")
+            .ok()
+            .filter(|s| !s.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_USER_AGENT.to_string());
+        Self {
+            timeout: http_timeout_from_env(),
+            pool_max_idle_per_host,
+            pool_idle_timeout: Duration::from_secs(pool_idle_timeout_secs),
+            user_agent,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rpc_client_config_from_env() {
+        let _guard = crate::lock_env_test();
+        std::env::set_var("RPC_HTTP_TIMEOUT_MS", "5000");
+        std::env::set_var("RPC_POOL_MAX_IDLE_PER_HOST", "64");
+        std::env::set_var("RPC_POOL_IDLE_TIMEOUT_SECONDS", "15");
+        std::env::set_var("RPC_USER_AGENT", "insights-backfill/1.0");
+
+        let config = RpcClientConfig::from_env();
+
+        std::env::remove_var("RPC_HTTP_TIMEOUT_MS");
+        std::env::remove_var("RPC_POOL_MAX_IDLE_PER_HOST");
+        std::env::remove_var("RPC_POOL_IDLE_TIMEOUT_SECONDS");
+        std::env::remove_var("RPC_USER_AGENT");
+
+        assert_eq!(
+            config,
+            RpcClientConfig {
+                timeout: Duration::from_millis(5000),
+                pool_max_idle_per_host: 64,
+                pool_idle_timeout: Duration::from_secs(15),
+                user_agent: "insights-backfill/1.0".to_string(),
+            }
+        );
+        assert_eq!(RpcClientConfig::from_env().user_agent, DEFAULT_USER_AGENT);
+        assert_eq!(RpcClientConfig::from_env().pool_max_idle_per_host, 16);
+        assert_eq!(
+            RpcClientConfig::from_env().pool_idle_timeout,
+            Duration::from_secs(90)
+        );
+    }
+
+    #[test]
+    fn test_mock_mode_is_refused_in_production_without_override() {
+        let _guard = crate::lock_env_test();
+        std::env::remove_var("RUST_ENV");
+        std::env::set_var("ENVIRONMENT", "production");
+
+        let refused = ensure_mock_mode_allowed(true);
+        let real_client = ensure_mock_mode_allowed(false);
+        std::env::set_var("ALLOW_MOCK_IN_PROD", "true");
+        let overridden = ensure_mock_mode_allowed(true);
+        std::env::remove_var("ALLOW_MOCK_IN_PROD");
+        std::env::set_var("ENVIRONMENT", "development");
+        let development = ensure_mock_mode_allowed(true);
+        std::env::remove_var("ENVIRONMENT");
+
+        assert!(refused.is_err());
+        assert!(real_client.is_ok());
+        assert!(overridden.is_ok());
+        assert!(development.is_ok());
+    }
+
+    #[test]
+    fn test_circuit_breaker_override_falls_back_to_global() {
+        let _guard = crate::lock_env_test();
+        std::env::set_var("RPC_CIRCUIT_BREAKER_SUCCESS_THRESHOLD", "2");
+        std::env::set_var(
+            "RPC_CIRCUIT_BREAKER_SEND_TRANSACTION_SUCCESS_THRESHOLD",
+            "5",
+        );
+        std::env::set_var(
+            "RPC_CIRCUIT_BREAKER_SEND_TRANSACTION_TIMEOUT_SECONDS",
+            "120",
+        );
+
+        let write = circuit_breaker_config_for_endpoint("send-transaction");
+        let read = circuit_breaker_config_for_endpoint("stellar");
+
+        std::env::remove_var("RPC_CIRCUIT_BREAKER_SUCCESS_THRESHOLD");
+        std::env::remove_var("RPC_CIRCUIT_BREAKER_SEND_TRANSACTION_SUCCESS_THRESHOLD");
+        std::env::remove_var("RPC_CIRCUIT_BREAKER_SEND_TRANSACTION_TIMEOUT_SECONDS");
+
+        assert_eq!(write.success_threshold, 5);
+        assert_eq!(write.timeout_duration, Duration::from_secs(120));
+        assert_eq!(write.failure_threshold, read.failure_threshold);
+        assert_eq!(read.success_threshold, 2);
+        assert_eq!(read.timeout_duration, Duration::from_secs(30));
+    }
+}
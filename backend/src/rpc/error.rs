@@ -1,17 +1,114 @@
 use failsafe::futures::CircuitBreaker as _;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::time::Duration;
 
+/// What failed below the HTTP layer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkErrorKind {
+    /// The host name could not be resolved, almost always a misconfigured URL
+    Dns,
+    /// Refused or reset connections, gateway errors and anything else
+    Other,
+}
+
+impl NetworkErrorKind {
+    /// Classify a transport error message, including its source chain
+    #[must_use]
+    pub fn classify(message: &str) -> Self {
+        let lowered = message.to_ascii_lowercase();
+        let dns_markers = [
+            "dns error",
+            "failed to lookup address",
+            "name or service not known",
+            "no such host",
+            "nodename nor servname",
+        ];
+        if dns_markers.iter().any(|marker| lowered.contains(marker)) {
+            Self::Dns
+        } else {
+            Self::Other
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum RpcError {
     NetworkError(String),
-    RateLimitError { retry_after: Option<Duration> },
-    ServerError { status: u16, message: String },
+    RateLimitError {
+        retry_after: Option<Duration>,
+    },
+    ServerError {
+        status: u16,
+        message: String,
+    },
+    TransactionFailed(HorizonResultCodes),
     ParseError(String),
     TimeoutError(String),
+    NotFound(String),
+    /// Error object returned in a JSON-RPC response body
+    JsonRpcError {
+        code: i32,
+        message: String,
+    },
     CircuitBreakerOpen,
 }
 
+/// Invalid JSON was received by the server
+pub const JSON_RPC_PARSE_ERROR: i32 = -32700;
+/// The request is not a valid JSON-RPC request object
+pub const JSON_RPC_INVALID_REQUEST: i32 = -32600;
+pub const JSON_RPC_METHOD_NOT_FOUND: i32 = -32601;
+pub const JSON_RPC_INVALID_PARAMS: i32 = -32602;
+pub const JSON_RPC_INTERNAL_ERROR: i32 = -32603;
+
+/// Whether a JSON-RPC error code is worth retrying.
+///
+/// Internal errors and the implementation-defined server range
+/// (`-32099..=-32000`), which Soroban RPC uses for transient conditions such
+/// as an overloaded or still-syncing node, are retryable. Parse, invalid
+/// request, unknown method and invalid params errors will fail the same way
+/// every time.
+#[must_use]
+pub const fn is_retryable_json_rpc_code(code: i32) -> bool {
+    matches!(code, JSON_RPC_INTERNAL_ERROR | -32099..=-32000)
+}
+
+/// Result codes from a Horizon transaction error, e.g. `tx_failed` with
+/// `["op_no_trust"]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HorizonResultCodes {
+    pub transaction: Option<String>,
+    #[serde(default)]
+    pub operations: Vec<String>,
+}
+
+impl HorizonResultCodes {
+    /// Parse `extras.result_codes` from a Horizon problem+json error body.
+    ///
+    /// Returns `None` if the body isn't JSON or carries no result codes.
+    #[must_use]
+    pub fn from_error_body(body: &str) -> Option<Self> {
+        let value: serde_json::Value = serde_json::from_str(body).ok()?;
+        let codes = value.get("extras")?.get("result_codes")?;
+        let codes: Self = serde_json::from_value(codes.clone()).ok()?;
+        if codes.transaction.is_none() && codes.operations.is_empty() {
+            return None;
+        }
+        Some(codes)
+    }
+}
+
+impl fmt::Display for HorizonResultCodes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.transaction.as_deref().unwrap_or("unknown"))?;
+        if !self.operations.is_empty() {
+            write!(f, " [{}]", self.operations.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
 impl fmt::Display for RpcError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -26,8 +123,13 @@ impl fmt::Display for RpcError {
             Self::ServerError { status, message } => {
                 write!(f, "Server error ({status}): {message}")
             }
+            Self::TransactionFailed(codes) => write!(f, "Transaction failed: {codes}"),
             Self::ParseError(msg) => write!(f, "Parse error: {msg}"),
             Self::TimeoutError(msg) => write!(f, "Timeout error: {msg}"),
+            Self::NotFound(msg) => write!(f, "Not found: {msg}"),
+            Self::JsonRpcError { code, message } => {
+                write!(f, "RPC error: {message} (code: {code})")
+            }
             Self::CircuitBreakerOpen => write!(f, "Circuit breaker is open"),
         }
     }
@@ -36,11 +138,23 @@ impl fmt::Display for RpcError {
 impl std::error::Error for RpcError {}
 
 impl RpcError {
+    /// Whether a failed call is worth retrying; this is the predicate
+    /// [`with_retry`] uses.
+    ///
+    /// Covers every [`is_transient`](Self::is_transient) error plus 5xx
+    /// responses and retryable JSON-RPC codes.
     #[must_use]
     pub const fn is_retryable(&self) -> bool {
-        self.is_transient() || matches!(self, Self::ServerError { status, .. } if *status >= 500)
+        match self {
+            Self::ServerError { status, .. } => *status >= 500,
+            Self::JsonRpcError { code, .. } => is_retryable_json_rpc_code(*code),
+            _ => self.is_transient(),
+        }
     }
 
+    /// Whether the request never got a usable response: network errors,
+    /// timeouts and rate limiting. Use [`is_retryable`](Self::is_retryable)
+    /// to decide on retries.
     #[must_use]
     pub const fn is_transient(&self) -> bool {
         matches!(
@@ -49,6 +163,27 @@ impl RpcError {
         )
     }
 
+    /// Network error carrying `message`
+    #[must_use]
+    pub fn network(message: impl Into<String>) -> Self {
+        Self::NetworkError(message.into())
+    }
+
+    /// What kind of network failure this is, if it is one
+    #[must_use]
+    pub fn network_error_kind(&self) -> Option<NetworkErrorKind> {
+        match self {
+            Self::NetworkError(msg) => Some(NetworkErrorKind::classify(msg)),
+            _ => None,
+        }
+    }
+
+    /// Whether this is a host name resolution failure
+    #[must_use]
+    pub fn is_dns_failure(&self) -> bool {
+        self.network_error_kind() == Some(NetworkErrorKind::Dns)
+    }
+
     #[must_use]
     pub fn categorize(err: &str) -> Self {
         let lowered = err.to_ascii_lowercase();
@@ -61,8 +196,9 @@ impl RpcError {
         } else if lowered.contains("network")
             || lowered.contains("connection")
             || lowered.contains("dns")
+            || NetworkErrorKind::classify(err) == NetworkErrorKind::Dns
         {
-            Self::NetworkError(err.to_string())
+            Self::network(err)
         } else {
             Self::ServerError {
                 status: 500,
@@ -77,20 +213,58 @@ impl RpcError {
             Self::NetworkError(_) => "network_error",
             Self::RateLimitError { .. } => "rate_limit_error",
             Self::ServerError { .. } => "server_error",
+            Self::TransactionFailed(_) => "transaction_failed",
             Self::ParseError(_) => "parse_error",
             Self::TimeoutError(_) => "timeout_error",
+            Self::NotFound(_) => "not_found",
+            Self::JsonRpcError { .. } => "json_rpc_error",
             Self::CircuitBreakerOpen => "circuit_breaker_open",
         }
     }
+
+    /// Horizon result codes, if this error came from a rejected transaction
+    #[must_use]
+    pub const fn result_codes(&self) -> Option<&HorizonResultCodes> {
+        match self {
+            Self::TransactionFailed(codes) => Some(codes),
+            _ => None,
+        }
+    }
 }
 
 use crate::rpc::circuit_breaker::SharedCircuitBreaker;
+use crate::rpc::retry_budget::RetryBudget;
 
 #[derive(Debug, Clone)]
 pub struct RetryConfig {
     pub max_attempts: u32,
     pub base_delay_ms: u64,
     pub max_delay_ms: u64,
+    /// Attempts allowed while the host name fails to resolve, counted
+    /// separately from `max_attempts` since DNS failures rarely clear up
+    pub max_dns_attempts: u32,
+    /// Randomize each delay between zero and its backoff value, the same
+    /// full jitter the ingestion retries use, so clients that failed
+    /// together don't retry in lockstep
+    pub jitter: bool,
+}
+
+impl RetryConfig {
+    /// Delay before retrying after failed attempt number `attempt` (1-based):
+    /// `base_delay_ms * 2^(attempt - 1)`, capped at `max_delay_ms`, then jittered
+    #[must_use]
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        let backoff = Duration::from_millis(std::cmp::min(
+            self.base_delay_ms
+                .saturating_mul(2u64.saturating_pow(attempt.saturating_sub(1))),
+            self.max_delay_ms,
+        ));
+        if self.jitter {
+            tokio_retry::strategy::jitter(backoff)
+        } else {
+            backoff
+        }
+    }
 }
 
 impl Default for RetryConfig {
@@ -99,20 +273,26 @@ impl Default for RetryConfig {
             max_attempts: 3,
             base_delay_ms: 100,
             max_delay_ms: 5_000,
+            max_dns_attempts: 2,
+            jitter: true,
         }
     }
 }
 
+/// Run `operation` through `circuit_breaker`, retrying errors that are
+/// [`RpcError::is_retryable`] with backoff from [`RetryConfig::delay_for`].
 pub async fn with_retry<F, Fut, T>(
     operation: F,
     config: RetryConfig,
     circuit_breaker: SharedCircuitBreaker,
+    retry_budget: &RetryBudget,
 ) -> Result<T, RpcError>
 where
     F: Fn() -> Fut,
     Fut: std::future::Future<Output = Result<T, RpcError>>,
 {
     let mut attempt = 0;
+    let mut dns_failures = 0;
 
     loop {
         attempt += 1;
@@ -122,22 +302,308 @@ where
         let result: Result<T, failsafe::Error<RpcError>> = circuit_breaker.call(operation()).await;
 
         match result {
-            Ok(val) => return Ok(val),
+            Ok(val) => {
+                retry_budget.record_success();
+                return Ok(val);
+            }
             Err(failsafe::Error::Rejected) => return Err(RpcError::CircuitBreakerOpen),
             Err(failsafe::Error::Inner(e)) => {
-                if !e.is_transient() || attempt >= config.max_attempts {
+                if !e.is_retryable() {
+                    return Err(e);
+                }
+                retry_budget.record_failure();
+                if attempt >= config.max_attempts {
+                    return Err(e);
+                }
+                if e.is_dns_failure() {
+                    dns_failures += 1;
+                    if dns_failures >= config.max_dns_attempts {
+                        tracing::warn!(attempt, error = %e, "Host name does not resolve, not retrying");
+                        return Err(e);
+                    }
+                }
+                // Under sustained failure, give up after one attempt so the
+                // breaker sees each request instead of each retry chain.
+                if !retry_budget.allow_retry() {
+                    crate::rpc::metrics::record_retry_suppressed("stellar");
+                    tracing::debug!(attempt, "Retry budget exhausted, not retrying");
                     return Err(e);
                 }
 
-                let delay = std::cmp::min(
-                    config
-                        .base_delay_ms
-                        .saturating_mul(2u64.saturating_pow(attempt.saturating_sub(1))),
-                    config.max_delay_ms,
-                );
+                tokio::time::sleep(config.delay_for(attempt)).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-                tokio::time::sleep(Duration::from_millis(delay)).await;
+    #[test]
+    fn test_parse_operation_result_codes() {
+        let body = r#"{
+            "type": "https://stellar.org/horizon-errors/transaction_failed",
+            "title": "Transaction Failed",
+            "status": 400,
+            "detail": "The transaction failed when submitted to the stellar network.",
+            "extras": {
+                "envelope_xdr": "AAAAAgAAAAA=",
+                "result_codes": {
+                    "transaction": "tx_failed",
+                    "operations": ["op_success", "op_no_trust"]
+                },
+                "result_xdr": "AAAAAAAAAGT/////AAAAAQAAAAAAAAAB////+gAAAAA="
             }
+        }"#;
+
+        let codes = HorizonResultCodes::from_error_body(body).unwrap();
+        assert_eq!(codes.transaction.as_deref(), Some("tx_failed"));
+        assert_eq!(codes.operations, vec!["op_success", "op_no_trust"]);
+        assert_eq!(codes.to_string(), "tx_failed [op_success, op_no_trust]");
+    }
+
+    #[test]
+    fn test_parse_transaction_only_result_codes() {
+        let body = r#"{
+            "title": "Transaction Failed",
+            "status": 400,
+            "extras": {"result_codes": {"transaction": "tx_bad_seq"}}
+        }"#;
+
+        let codes = HorizonResultCodes::from_error_body(body).unwrap();
+        assert_eq!(codes.transaction.as_deref(), Some("tx_bad_seq"));
+        assert!(codes.operations.is_empty());
+        assert_eq!(codes.to_string(), "tx_bad_seq");
+    }
+
+    #[test]
+    fn test_bodies_without_result_codes() {
+        assert!(HorizonResultCodes::from_error_body("Bad Gateway").is_none());
+        assert!(HorizonResultCodes::from_error_body(
+            r#"{"title": "Resource Missing", "status": 404}"#
+        )
+        .is_none());
+        assert!(
+            HorizonResultCodes::from_error_body(r#"{"extras": {"result_codes": {}}}"#).is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_budget_suppresses_retries_under_sustained_failure() {
+        use crate::rpc::circuit_breaker::{build_circuit_breaker, CircuitBreakerConfig};
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let config = CircuitBreakerConfig {
+            failure_threshold: 1_000,
+            ..CircuitBreakerConfig::default()
+        };
+        let breaker = build_circuit_breaker("retry-budget-test", &config, None);
+        let budget = RetryBudget::new(4.0, 0.1);
+        let retry = RetryConfig {
+            max_attempts: 4,
+            base_delay_ms: 1,
+            max_delay_ms: 1,
+            ..RetryConfig::default()
+        };
+        let calls = AtomicU32::new(0);
+        let failing = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(RpcError::network("connection refused"))
+        };
+
+        // The first request may retry once before the budget is at half
+        assert!(with_retry(failing, retry.clone(), breaker.clone(), &budget)
+            .await
+            .is_err());
+        assert_eq!(calls.swap(0, Ordering::SeqCst), 2);
+
+        // After that every request makes a single attempt instead of four
+        for _ in 0..5 {
+            assert!(with_retry(failing, retry.clone(), breaker.clone(), &budget)
+                .await
+                .is_err());
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+    }
+
+    #[test]
+    fn test_json_rpc_error_retryability_by_code() {
+        let error = |code| RpcError::JsonRpcError {
+            code,
+            message: "boom".to_string(),
+        };
+
+        for code in [JSON_RPC_INTERNAL_ERROR, -32000, -32099] {
+            assert!(
+                error(code).is_retryable(),
+                "code {code} should be retryable"
+            );
         }
+        for code in [
+            JSON_RPC_PARSE_ERROR,
+            JSON_RPC_INVALID_REQUEST,
+            JSON_RPC_METHOD_NOT_FOUND,
+            JSON_RPC_INVALID_PARAMS,
+            -32100,
+            1,
+        ] {
+            assert!(
+                !error(code).is_retryable(),
+                "code {code} should not be retryable"
+            );
+        }
+
+        let err = error(JSON_RPC_INVALID_PARAMS);
+        assert_eq!(err.error_type_label(), "json_rpc_error");
+        assert_eq!(err.to_string(), "RPC error: boom (code: -32602)");
+    }
+
+    #[test]
+    fn test_network_error_kind_classification() {
+        let dns = RpcError::network(
+            "error sending request: client error (Connect): dns error: failed to lookup address information",
+        );
+        assert!(dns.is_dns_failure());
+        assert!(dns.is_transient());
+        assert!(RpcError::categorize("no such host: horizon.example").is_dns_failure());
+
+        assert!(!RpcError::network("connection refused").is_dns_failure());
+        assert!(!RpcError::categorize("connection reset by peer").is_dns_failure());
+    }
+
+    #[tokio::test]
+    async fn test_dns_failures_give_up_before_the_retry_budget() {
+        use crate::rpc::circuit_breaker::{build_circuit_breaker, CircuitBreakerConfig};
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let config = CircuitBreakerConfig {
+            failure_threshold: 1_000,
+            ..CircuitBreakerConfig::default()
+        };
+        let breaker = build_circuit_breaker("dns-retry-test", &config, None);
+        let budget = RetryBudget::new(100.0, 0.1);
+        let retry = RetryConfig {
+            max_attempts: 10,
+            base_delay_ms: 1,
+            max_delay_ms: 1,
+            max_dns_attempts: 2,
+            jitter: false,
+        };
+        let calls = AtomicU32::new(0);
+
+        let dns_failure = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(RpcError::network("dns error: failed to lookup address"))
+        };
+        let err = with_retry(dns_failure, retry.clone(), breaker.clone(), &budget)
+            .await
+            .unwrap_err();
+        assert!(err.is_dns_failure());
+        assert_eq!(calls.swap(0, Ordering::SeqCst), 2);
+
+        // Other network errors still use the full attempt budget
+        let refused = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(RpcError::network("connection refused"))
+        };
+        assert!(with_retry(refused, retry, breaker, &budget).await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 10);
+    }
+
+    #[tokio::test]
+    async fn test_with_retry_retries_exactly_the_retryable_errors() {
+        use crate::rpc::circuit_breaker::{build_circuit_breaker, CircuitBreakerConfig};
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let config = CircuitBreakerConfig {
+            failure_threshold: 1_000,
+            ..CircuitBreakerConfig::default()
+        };
+        let breaker = build_circuit_breaker("retry-policy-test", &config, None);
+        let retry = RetryConfig {
+            max_attempts: 3,
+            base_delay_ms: 0,
+            max_delay_ms: 0,
+            max_dns_attempts: 3,
+            jitter: false,
+        };
+        let server_error = |status| RpcError::ServerError {
+            status,
+            message: "boom".to_string(),
+        };
+        let json_rpc_error = |code| RpcError::JsonRpcError {
+            code,
+            message: "boom".to_string(),
+        };
+        // Calls made for each error: 3 when retried, 1 when not
+        let errors = [
+            (RpcError::network("connection refused"), 3),
+            (RpcError::TimeoutError("slow".to_string()), 3),
+            (RpcError::RateLimitError { retry_after: None }, 3),
+            (server_error(503), 3),
+            (server_error(404), 1),
+            (json_rpc_error(JSON_RPC_INTERNAL_ERROR), 3),
+            (json_rpc_error(JSON_RPC_INVALID_PARAMS), 1),
+            (RpcError::ParseError("bad json".to_string()), 1),
+            (RpcError::NotFound("ledger".to_string()), 1),
+            (
+                RpcError::TransactionFailed(HorizonResultCodes {
+                    transaction: Some("tx_failed".to_string()),
+                    operations: Vec::new(),
+                }),
+                1,
+            ),
+        ];
+
+        for (error, expected) in errors {
+            // A fresh budget per case so earlier failures don't suppress retries
+            let budget = RetryBudget::new(100.0, 0.1);
+            let calls = AtomicU32::new(0);
+            let failing = || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err::<(), _>(error.clone())
+            };
+            assert!(with_retry(failing, retry.clone(), breaker.clone(), &budget)
+                .await
+                .is_err());
+
+            assert_eq!(calls.load(Ordering::SeqCst), expected, "{error}");
+        }
+    }
+
+    #[test]
+    fn test_delay_for_backs_off_and_jitters_within_the_cap() {
+        let config = RetryConfig {
+            base_delay_ms: 100,
+            max_delay_ms: 1_000,
+            jitter: false,
+            ..RetryConfig::default()
+        };
+        let delays: Vec<u128> = (1..=6).map(|a| config.delay_for(a).as_millis()).collect();
+        assert_eq!(delays, vec![100, 200, 400, 800, 1_000, 1_000]);
+
+        let jittered = RetryConfig {
+            jitter: true,
+            ..config
+        };
+        for attempt in 1..=6 {
+            assert!(jittered.delay_for(attempt) <= config.delay_for(attempt));
+        }
+    }
+
+    #[test]
+    fn test_transaction_failed_is_not_retryable() {
+        let err = RpcError::TransactionFailed(HorizonResultCodes {
+            transaction: Some("tx_insufficient_fee".to_string()),
+            operations: Vec::new(),
+        });
+
+        assert!(!err.is_retryable());
+        assert_eq!(err.error_type_label(), "transaction_failed");
+        assert_eq!(
+            err.result_codes().and_then(|c| c.transaction.as_deref()),
+            Some("tx_insufficient_fee")
+        );
     }
 }
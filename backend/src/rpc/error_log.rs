@@ -0,0 +1,192 @@
+//! Bounded log of recent RPC errors.
+//!
+//! `rpc_errors_total` says how many calls failed; this keeps the last few
+//! failures themselves so an operator can see which calls broke and why.
+//! Stellar account ids in error messages are redacted before they are stored.
+
+use std::collections::VecDeque;
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::error::RpcError;
+use crate::logging::redaction::redact_account;
+
+/// Events kept when `RPC_ERROR_LOG_CAPACITY` is unset
+pub const DEFAULT_ERROR_LOG_CAPACITY: usize = 200;
+
+/// One failed RPC call
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RpcErrorEvent {
+    pub timestamp: DateTime<Utc>,
+    /// Client method that failed, e.g. `fetch_ledger`
+    pub endpoint: String,
+    /// Same label as `rpc_errors_total`, e.g. `timeout_error`
+    pub error_type: String,
+    pub message: String,
+}
+
+/// Ring buffer of the most recent RPC errors; the oldest is evicted when full
+#[derive(Debug)]
+pub struct RpcErrorLog {
+    capacity: usize,
+    events: Mutex<VecDeque<RpcErrorEvent>>,
+}
+
+impl RpcErrorLog {
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            capacity,
+            events: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Capacity from `RPC_ERROR_LOG_CAPACITY` (default 200)
+    #[must_use]
+    pub fn from_env() -> Self {
+        let capacity = std::env::var("RPC_ERROR_LOG_CAPACITY")
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+            .unwrap_or(DEFAULT_ERROR_LOG_CAPACITY);
+        Self::new(capacity)
+    }
+
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Record `error` from a call to `endpoint`
+    pub fn record(&self, endpoint: &str, error: &RpcError) {
+        self.push(RpcErrorEvent {
+            timestamp: Utc::now(),
+            endpoint: endpoint.to_string(),
+            error_type: error.error_type_label().to_string(),
+            message: redact_accounts(&error.to_string()),
+        });
+    }
+
+    fn push(&self, event: RpcErrorEvent) {
+        let mut events = self.events.lock().unwrap_or_else(PoisonError::into_inner);
+        if events.len() == self.capacity {
+            events.pop_front();
+        }
+        events.push_back(event);
+    }
+
+    /// Recorded events, newest first, optionally narrowed to one endpoint
+    /// and/or error type
+    #[must_use]
+    pub fn recent(&self, endpoint: Option<&str>, error_type: Option<&str>) -> Vec<RpcErrorEvent> {
+        self.events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .rev()
+            .filter(|e| endpoint.is_none_or(|endpoint| e.endpoint == endpoint))
+            .filter(|e| error_type.is_none_or(|error_type| e.error_type == error_type))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Process-wide error log every [`StellarRpcClient`](super::StellarRpcClient) records into
+pub fn rpc_error_log() -> &'static RpcErrorLog {
+    static LOG: OnceLock<RpcErrorLog> = OnceLock::new();
+    LOG.get_or_init(RpcErrorLog::from_env)
+}
+
+/// Whether `word` looks like a Stellar account (`G...`), muxed account
+/// (`M...`) or contract (`C...`) strkey
+fn is_strkey(word: &str) -> bool {
+    let expected_len = match word.as_bytes().first() {
+        Some(b'G' | b'C') => 56,
+        Some(b'M') => 69,
+        _ => return false,
+    };
+    word.len() == expected_len
+        && word
+            .bytes()
+            .all(|b| b.is_ascii_uppercase() || (b'2'..=b'7').contains(&b))
+}
+
+/// Replace every strkey in `message` with its redacted form
+fn redact_accounts(message: &str) -> String {
+    fn flush(word: &mut String, redacted: &mut String) {
+        if is_strkey(word) {
+            redacted.push_str(&redact_account(word));
+        } else {
+            redacted.push_str(word);
+        }
+        word.clear();
+    }
+
+    let mut redacted = String::with_capacity(message.len());
+    let mut word = String::new();
+    for c in message.chars() {
+        if c.is_ascii_alphanumeric() {
+            word.push(c);
+        } else {
+            flush(&mut word, &mut redacted);
+            redacted.push(c);
+        }
+    }
+    flush(&mut word, &mut redacted);
+    redacted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACCOUNT: &str = "GBRPYHIL2CI3FNQ4BXLFMNDLFJUNPU2HY3ZMFSHONUCEOASW7QC7OX2H";
+
+    #[test]
+    fn test_oldest_event_is_evicted_and_filters_apply() {
+        let log = RpcErrorLog::new(3);
+        log.record("fetch_ledger", &RpcError::TimeoutError("first".to_string()));
+        log.record("fetch_ledger", &RpcError::ParseError("second".to_string()));
+        log.record(
+            "fetch_account",
+            &RpcError::TimeoutError("third".to_string()),
+        );
+        log.record(
+            "fetch_ledger",
+            &RpcError::TimeoutError("fourth".to_string()),
+        );
+
+        let messages: Vec<String> = log
+            .recent(None, None)
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+        assert_eq!(messages.len(), 3);
+        assert!(messages[0].contains("fourth"));
+        assert!(messages[2].contains("second"));
+
+        let timeouts = log.recent(Some("fetch_ledger"), Some("timeout_error"));
+        assert_eq!(timeouts.len(), 1);
+        assert!(timeouts[0].message.contains("fourth"));
+    }
+
+    #[test]
+    fn test_accounts_are_redacted_from_messages() {
+        let log = RpcErrorLog::new(10);
+        log.record(
+            "fetch_account",
+            &RpcError::NotFound(format!("/accounts/{ACCOUNT}/offers")),
+        );
+
+        let event = &log.recent(None, None)[0];
+        assert!(!event.message.contains(ACCOUNT));
+        assert!(event.message.contains("/accounts/GBRP...OX2H/offers"));
+        assert_eq!(event.error_type, "not_found");
+        assert_eq!(
+            redact_accounts("ledger 12345 not found"),
+            "ledger 12345 not found"
+        );
+    }
+}
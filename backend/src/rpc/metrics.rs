@@ -1,7 +1,12 @@
-//! Prometheus metrics for RPC error rates and circuit breaker state.
+//! Prometheus metrics for RPC error rates, latency and circuit breaker state.
 
 use lazy_static::lazy_static;
-use prometheus::{register_int_counter_vec, register_int_gauge_vec, IntCounterVec, IntGaugeVec};
+use prometheus::core::Collector;
+use prometheus::{
+    register_histogram_vec, register_int_counter_vec, register_int_gauge_vec, HistogramVec,
+    IntCounterVec, IntGaugeVec,
+};
+use std::collections::BTreeMap;
 
 lazy_static! {
     static ref RPC_ERRORS: IntCounterVec = register_int_counter_vec!(
@@ -16,6 +21,30 @@ lazy_static! {
         &["endpoint"]
     )
     .expect("circuit_breaker_state metric");
+    static ref CIRCUIT_BREAKER_FORCED: IntGaugeVec = register_int_gauge_vec!(
+        "circuit_breaker_forced",
+        "Circuit breaker operator override (0=automatic, 1=forced open, 2=forced closed)",
+        &["endpoint"]
+    )
+    .expect("circuit_breaker_forced metric");
+    static ref RPC_REQUEST_DURATION: HistogramVec = register_histogram_vec!(
+        "rpc_request_duration_seconds",
+        "Upstream RPC call duration including retries, successful or not",
+        &["method"],
+        vec![0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0]
+    )
+    .expect("rpc_request_duration_seconds metric");
+    static ref RPC_RETRIES_SUPPRESSED: IntCounterVec = register_int_counter_vec!(
+        "rpc_retries_suppressed_total",
+        "Retries skipped because the retry budget was exhausted",
+        &["endpoint"]
+    )
+    .expect("rpc_retries_suppressed_total metric");
+}
+
+/// Count a retry skipped by the retry budget.
+pub fn record_retry_suppressed(endpoint: &str) {
+    RPC_RETRIES_SUPPRESSED.with_label_values(&[endpoint]).inc();
 }
 
 /// Record an RPC error for metrics.
@@ -23,9 +52,55 @@ pub fn record_rpc_error(error_type: &str, endpoint: &str) {
     RPC_ERRORS.with_label_values(&[error_type, endpoint]).inc();
 }
 
+/// Total RPC errors recorded for `endpoint` since startup, keyed by error type.
+#[must_use]
+pub fn rpc_error_counts(endpoint: &str) -> BTreeMap<String, u64> {
+    let mut counts = BTreeMap::new();
+    for family in RPC_ERRORS.collect() {
+        for metric in family.get_metric() {
+            let label = |name: &str| {
+                metric
+                    .get_label()
+                    .iter()
+                    .find(|l| l.get_name() == name)
+                    .map(|l| l.get_value().to_string())
+            };
+            if label("endpoint").as_deref() != Some(endpoint) {
+                continue;
+            }
+            if let Some(error_type) = label("error_type") {
+                counts.insert(error_type, metric.get_counter().get_value() as u64);
+            }
+        }
+    }
+    counts
+}
+
+/// Record how long a call to client `method` took.
+pub fn observe_rpc_duration(method: &str, seconds: f64) {
+    RPC_REQUEST_DURATION
+        .with_label_values(&[method])
+        .observe(seconds);
+}
+
+/// Number of RPC durations observed for `method` since startup.
+#[must_use]
+pub fn rpc_duration_sample_count(method: &str) -> u64 {
+    RPC_REQUEST_DURATION
+        .with_label_values(&[method])
+        .get_sample_count()
+}
+
 /// Set circuit breaker state gauge (0=closed, 1=open, 2=half-open).
 pub fn set_circuit_breaker_state(endpoint: &str, state: i64) {
     CIRCUIT_BREAKER_STATE
         .with_label_values(&[endpoint])
         .set(state);
 }
+
+/// Set circuit breaker override gauge (0=automatic, 1=forced open, 2=forced closed).
+pub fn set_circuit_breaker_forced(endpoint: &str, forced: i64) {
+    CIRCUIT_BREAKER_FORCED
+        .with_label_values(&[endpoint])
+        .set(forced);
+}
@@ -1,14 +1,94 @@
 //! Deterministic Stellar Horizon/RPC fixtures for tests and mock-mode clients.
 
 use super::stellar::{
-    Asset, AssetAccounts, AssetBalanceChange, AssetBalances, AssetFlags, FeeBumpTransactionInfo,
-    GetLedgersResult, HealthResponse, HorizonAsset, HorizonEffect, HorizonLiquidityPool,
-    HorizonOperation, HorizonPoolReserve, HorizonTransaction, InnerTransaction, LedgerInfo,
-    OrderBook, OrderBookEntry, Payment, Price, RpcLedger, Trade,
+    AccountInfo, Asset, AssetAccounts, AssetBalanceChange, AssetBalances, AssetFlags, Balance,
+    FeeBumpTransactionInfo, GetLedgersResult, GetTransactionsResult, HealthResponse, HorizonAsset,
+    HorizonEffect, HorizonLiquidityPool, HorizonOperation, HorizonPoolReserve, HorizonTransaction,
+    InnerTransaction, JsonRpcCall, JsonRpcError, JsonRpcResponse, LedgerInfo, Offer, OrderBook,
+    OrderBookEntry, Payment, Price, RpcLatestLedger, RpcLedger, RpcTransaction, Trade,
 };
 
 pub const MOCK_OLDEST_LEDGER: u64 = 51_565_760;
 pub const MOCK_LATEST_LEDGER: u64 = 51_565_820;
+/// Asset of the claimable balance created in every mock ledger
+pub const MOCK_CLAIMABLE_BALANCE_ASSET: &str =
+    "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+/// Per-method overrides for a mock-mode `StellarRpcClient`.
+///
+/// Any field left as `None` falls back to the generated fixture below.
+#[derive(Debug, Clone, Default)]
+pub struct MockFixtures {
+    pub health: Option<HealthResponse>,
+    pub ledger: Option<LedgerInfo>,
+    pub payments: Option<Vec<Payment>>,
+    pub trades: Option<Vec<Trade>>,
+    pub order_book: Option<OrderBook>,
+    pub account: Option<AccountInfo>,
+    pub transactions: Option<Vec<HorizonTransaction>>,
+}
+
+fn take_limit<T: Clone>(items: &[T], limit: u32) -> Vec<T> {
+    items.iter().take(limit as usize).cloned().collect()
+}
+
+impl MockFixtures {
+    pub fn health(&self) -> HealthResponse {
+        self.health.clone().unwrap_or_else(mock_health_response)
+    }
+
+    pub fn ledger_info(&self) -> LedgerInfo {
+        self.ledger.clone().unwrap_or_else(mock_ledger_info)
+    }
+
+    pub fn ledger(&self, sequence: u64) -> LedgerInfo {
+        self.ledger.clone().unwrap_or_else(|| mock_ledger(sequence))
+    }
+
+    pub fn payments(&self, limit: u32) -> Vec<Payment> {
+        self.payments
+            .as_deref()
+            .map_or_else(|| mock_payments(limit), |p| take_limit(p, limit))
+    }
+
+    pub fn trades(&self, limit: u32) -> Vec<Trade> {
+        self.trades
+            .as_deref()
+            .map_or_else(|| mock_trades(limit), |t| take_limit(t, limit))
+    }
+
+    /// Fixture trades on the `base`/`counter` pair, or generated ones
+    pub fn trades_for_pair(&self, base: &Asset, counter: &Asset, limit: u32) -> Vec<Trade> {
+        match self.trades.as_deref() {
+            Some(trades) => trades
+                .iter()
+                .filter(|t| trade_matches_pair(t, base, counter))
+                .take(limit as usize)
+                .cloned()
+                .collect(),
+            None => mock_trades_for_pair(base, counter, limit),
+        }
+    }
+
+    pub fn order_book(&self, selling_asset: &Asset, buying_asset: &Asset) -> OrderBook {
+        self.order_book
+            .clone()
+            .unwrap_or_else(|| mock_order_book(selling_asset, buying_asset))
+    }
+
+    pub fn account(&self, account_id: &str) -> AccountInfo {
+        self.account
+            .clone()
+            .unwrap_or_else(|| mock_account(account_id))
+    }
+
+    pub fn transactions(&self, limit: u32, ledger_sequence: u64) -> Vec<HorizonTransaction> {
+        self.transactions.as_deref().map_or_else(
+            || mock_transactions(limit, ledger_sequence),
+            |t| take_limit(t, limit),
+        )
+    }
+}
 pub fn mock_health_response() -> HealthResponse {
     HealthResponse {
         status: "healthy".to_string(),
@@ -34,6 +114,29 @@ pub fn mock_ledger_info() -> LedgerInfo {
     }
 }
 
+/// Agrees with `mock_ledger_info` at its own sequence, so re-fetching the
+/// latest ledger by sequence yields the same hash.
+pub fn mock_ledger(sequence: u64) -> LedgerInfo {
+    let latest = mock_ledger_info();
+    if sequence == latest.sequence {
+        return latest;
+    }
+    LedgerInfo {
+        sequence,
+        hash: format!("hash_{sequence}"),
+        previous_hash: format!("hash_{}", sequence.saturating_sub(1)),
+        ..latest
+    }
+}
+
+pub fn mock_latest_rpc_ledger() -> RpcLatestLedger {
+    RpcLatestLedger {
+        hash: format!("hash_{MOCK_LATEST_LEDGER}"),
+        protocol_version: 21,
+        sequence: MOCK_LATEST_LEDGER,
+    }
+}
+
 // I'm mocking getLedgers response for testing
 pub fn mock_get_ledgers(start: u64, limit: u32) -> GetLedgersResult {
     if start > MOCK_LATEST_LEDGER {
@@ -65,6 +168,80 @@ pub fn mock_get_ledgers(start: u64, limit: u32) -> GetLedgersResult {
     }
 }
 
+// Mocked getTransactions page: one transaction per ledger, with the cursor
+// holding the last ledger returned so the next page starts after it.
+pub fn mock_get_transactions(start: u64, limit: u32) -> GetTransactionsResult {
+    if start > MOCK_LATEST_LEDGER {
+        return GetTransactionsResult {
+            transactions: Vec::new(),
+            latest_ledger: MOCK_LATEST_LEDGER,
+            oldest_ledger: MOCK_OLDEST_LEDGER,
+            cursor: Some(MOCK_LATEST_LEDGER.to_string()),
+        };
+    }
+
+    let end = (start.saturating_add(u64::from(limit)).saturating_sub(1)).min(MOCK_LATEST_LEDGER);
+    let transactions = (start..=end)
+        .enumerate()
+        .map(|(i, seq)| RpcTransaction {
+            tx_hash: format!("tx_hash_{seq}"),
+            status: if seq % 10 == 0 { "FAILED" } else { "SUCCESS" }.to_string(),
+            application_order: 1,
+            fee_bump: false,
+            ledger: seq,
+            created_at: Some(1_734_032_457 + i as i64 * 5),
+            envelope_xdr: Some("mock_envelope".to_string()),
+            result_xdr: Some("mock_result".to_string()),
+            result_meta_xdr: Some("mock_result_meta".to_string()),
+        })
+        .collect();
+
+    GetTransactionsResult {
+        transactions,
+        latest_ledger: MOCK_LATEST_LEDGER,
+        oldest_ledger: MOCK_OLDEST_LEDGER,
+        cursor: Some(end.to_string()),
+    }
+}
+
+/// Answer a JSON-RPC batch from the fixtures above; unknown methods get a
+/// "method not found" error on their own item.
+pub fn mock_rpc_batch(calls: &[JsonRpcCall]) -> Vec<JsonRpcResponse<serde_json::Value>> {
+    calls
+        .iter()
+        .map(|call| {
+            let params = call.params.as_ref();
+            let start = params
+                .and_then(|p| p.get("startLedger"))
+                .and_then(serde_json::Value::as_u64)
+                .unwrap_or(MOCK_OLDEST_LEDGER);
+            let limit = params
+                .and_then(|p| p.pointer("/pagination/limit"))
+                .and_then(serde_json::Value::as_u64)
+                .map_or(10, |l| u32::try_from(l).unwrap_or(u32::MAX));
+
+            let result = match call.method.as_str() {
+                "getHealth" => serde_json::to_value(mock_health_response()).ok(),
+                "getLatestLedger" => serde_json::to_value(mock_latest_rpc_ledger()).ok(),
+                "getLedgers" => serde_json::to_value(mock_get_ledgers(start, limit)).ok(),
+                "getTransactions" => serde_json::to_value(mock_get_transactions(start, limit)).ok(),
+                _ => None,
+            };
+            let error = result.is_none().then(|| JsonRpcError {
+                code: -32601,
+                message: format!("method not found: {}", call.method),
+            });
+
+            JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id: call.id,
+                result,
+                error,
+            }
+        })
+        .collect()
+}
+
 pub fn mock_payments(limit: u32) -> Vec<Payment> {
     (0..limit)
         .map(|i| {
@@ -216,6 +393,30 @@ pub fn mock_trades(limit: u32) -> Vec<Trade> {
         .collect()
 }
 
+fn trade_matches_pair(trade: &Trade, base: &Asset, counter: &Asset) -> bool {
+    trade.base_asset_type == base.asset_type
+        && trade.base_asset_code == base.asset_code
+        && trade.base_asset_issuer == base.asset_issuer
+        && trade.counter_asset_type == counter.asset_type
+        && trade.counter_asset_code == counter.asset_code
+        && trade.counter_asset_issuer == counter.asset_issuer
+}
+
+pub fn mock_trades_for_pair(base: &Asset, counter: &Asset, limit: u32) -> Vec<Trade> {
+    mock_trades(limit)
+        .into_iter()
+        .map(|trade| Trade {
+            base_asset_type: base.asset_type.clone(),
+            base_asset_code: base.asset_code.clone(),
+            base_asset_issuer: base.asset_issuer.clone(),
+            counter_asset_type: counter.asset_type.clone(),
+            counter_asset_code: counter.asset_code.clone(),
+            counter_asset_issuer: counter.asset_issuer.clone(),
+            ..trade
+        })
+        .collect()
+}
+
 pub fn mock_order_book(selling_asset: &Asset, buying_asset: &Asset) -> OrderBook {
     let bids = vec![
         OrderBookEntry {
@@ -255,6 +456,77 @@ pub fn mock_order_book(selling_asset: &Asset, buying_asset: &Asset) -> OrderBook
     }
 }
 
+pub fn mock_account(account_id: &str) -> AccountInfo {
+    AccountInfo {
+        account_id: account_id.to_string(),
+        sequence: "221360043892735".to_string(),
+        subentry_count: 2,
+        last_modified_ledger: MOCK_LATEST_LEDGER - 5,
+        home_domain: None,
+        balances: vec![
+            Balance {
+                asset_type: "credit_alphanum4".to_string(),
+                asset_code: Some("USDC".to_string()),
+                asset_issuer: Some(
+                    "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN".to_string(),
+                ),
+                balance: "1520.4500000".to_string(),
+                buying_liabilities: "0.0000000".to_string(),
+                selling_liabilities: "100.0000000".to_string(),
+                limit: Some("922337203685.4775807".to_string()),
+            },
+            Balance {
+                asset_type: "credit_alphanum4".to_string(),
+                asset_code: Some("EURC".to_string()),
+                asset_issuer: Some(
+                    "GDHU6WRG4IEQXM5NZ4BMPKOXHW76MZM4Y2IEMFDVXBSDP6SJY4ITNPP2".to_string(),
+                ),
+                balance: "310.0000000".to_string(),
+                buying_liabilities: "25.0000000".to_string(),
+                selling_liabilities: "0.0000000".to_string(),
+                limit: Some("10000.0000000".to_string()),
+            },
+            Balance {
+                asset_type: "native".to_string(),
+                asset_code: None,
+                asset_issuer: None,
+                balance: "10245.1234567".to_string(),
+                buying_liabilities: "0.0000000".to_string(),
+                selling_liabilities: "0.0000000".to_string(),
+                limit: None,
+            },
+        ],
+    }
+}
+
+pub fn mock_offers(account_id: &str, limit: u32) -> Vec<Offer> {
+    (0..limit)
+        .map(|i| Offer {
+            id: format!("{}", 165_561_423 + u64::from(i)),
+            paging_token: format!("{}", 165_561_423 + u64::from(i)),
+            seller: account_id.to_string(),
+            selling: Asset {
+                asset_type: "native".to_string(),
+                asset_code: None,
+                asset_issuer: None,
+            },
+            buying: Asset {
+                asset_type: "credit_alphanum4".to_string(),
+                asset_code: Some("USDC".to_string()),
+                asset_issuer: Some(
+                    "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN".to_string(),
+                ),
+            },
+            amount: format!("{}.0000000", 100 + i * 25),
+            price: Price {
+                n: 10 + i64::from(i),
+                d: 100,
+            },
+            last_modified_ledger: 51_583_040 - u64::from(i),
+        })
+        .collect()
+}
+
 pub fn mock_transactions(limit: u32, ledger_sequence: u64) -> Vec<HorizonTransaction> {
     (0..limit)
         .map(|i| {
@@ -310,6 +582,8 @@ pub fn mock_operations_for_ledger(sequence: u64) -> Vec<HorizonOperation> {
             account: Some(source_a),
             into: Some(dest_a),
             amount: None,
+            asset: None,
+            balance_id: None,
         },
         HorizonOperation {
             id: format!("op_{sequence}_1"),
@@ -321,6 +595,8 @@ pub fn mock_operations_for_ledger(sequence: u64) -> Vec<HorizonOperation> {
             account: None,
             into: None,
             amount: Some("25.0000000".to_string()),
+            asset: None,
+            balance_id: None,
         },
         HorizonOperation {
             id: format!("op_{sequence}_2"),
@@ -329,9 +605,24 @@ pub fn mock_operations_for_ledger(sequence: u64) -> Vec<HorizonOperation> {
             source_account: source_b.clone(),
             operation_type: "account_merge".to_string(),
             created_at: "2026-01-22T10:32:00Z".to_string(),
-            account: Some(source_b),
+            account: Some(source_b.clone()),
             into: Some(dest_b),
             amount: None,
+            asset: None,
+            balance_id: None,
+        },
+        HorizonOperation {
+            id: format!("op_{sequence}_3"),
+            paging_token: format!("pt_{sequence}_3"),
+            transaction_hash: format!("txhash_{sequence}_3"),
+            source_account: source_b,
+            operation_type: "create_claimable_balance".to_string(),
+            created_at: "2026-01-22T10:33:00Z".to_string(),
+            account: None,
+            into: None,
+            amount: Some("40.0000000".to_string()),
+            asset: Some(MOCK_CLAIMABLE_BALANCE_ASSET.to_string()),
+            balance_id: None,
         },
     ]
 }
@@ -344,6 +635,7 @@ pub fn mock_effects_for_operation(operation_id: &str) -> Vec<HorizonEffect> {
             account: Some("GDESTAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA".to_string()),
             amount: Some("125.5000000".to_string()),
             asset_type: Some("native".to_string()),
+            balance_id: None,
         }];
     }
 
@@ -357,6 +649,7 @@ pub fn mock_effects_for_operation(operation_id: &str) -> Vec<HorizonEffect> {
                 ),
                 amount: Some("10.0000000".to_string()),
                 asset_type: Some("native".to_string()),
+                balance_id: None,
             },
             HorizonEffect {
                 id: format!("effect_{operation_id}_1"),
@@ -366,10 +659,22 @@ pub fn mock_effects_for_operation(operation_id: &str) -> Vec<HorizonEffect> {
                 ),
                 amount: Some("0.5000000".to_string()),
                 asset_type: Some("native".to_string()),
+                balance_id: None,
             },
         ];
     }
 
+    if operation_id.ends_with("_3") {
+        return vec![HorizonEffect {
+            id: format!("effect_{operation_id}_0"),
+            effect_type: "claimable_balance_created".to_string(),
+            account: Some("GBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBB".to_string()),
+            amount: Some("40.0000000".to_string()),
+            asset_type: None,
+            balance_id: Some(format!("00000000{operation_id}")),
+        }];
+    }
+
     Vec::new()
 }
 pub fn mock_liquidity_pools(limit: u32) -> Vec<HorizonLiquidityPool> {
@@ -1,19 +1,32 @@
 pub mod circuit_breaker;
 pub mod client_trait;
+pub mod coalesce;
 pub mod config;
 pub mod error;
+pub mod error_log;
 pub mod metrics;
 pub mod mock_stellar;
 pub mod rate_limiter;
+pub mod retry_budget;
 pub mod stellar;
+#[cfg(test)]
+pub(crate) mod test_server;
 
-pub use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
+pub use circuit_breaker::{
+    CircuitBreaker, CircuitBreakerConfig, CircuitBreakerOverride, CircuitState, ForcedState,
+    StateChangeCallback,
+};
 pub use client_trait::{MockStellarRpcClient, StellarRpcClientTrait};
+pub use coalesce::RequestCoalescer;
+pub use error_log::{rpc_error_log, RpcErrorEvent, RpcErrorLog};
 pub use failsafe::futures::CircuitBreaker as FailsafeCircuitBreaker;
+pub use mock_stellar::MockFixtures;
 pub use rate_limiter::{RpcRateLimitConfig, RpcRateLimitMetrics, RpcRateLimiter};
+pub use retry_budget::RetryBudget;
 pub use stellar::{
-    Asset, FeeBumpTransactionInfo, GetLedgersResult, HealthResponse, HorizonAsset, HorizonEffect,
-    HorizonLiquidityPool, HorizonOperation, HorizonPoolReserve, HorizonTransaction,
-    InnerTransaction, LedgerInfo, OrderBook, OrderBookEntry, Payment, Price, RpcLedger,
-    StellarRpcClient, Trade,
+    AccountInfo, Asset, Balance, FeeBumpTransactionInfo, GetLedgersResult, GetTransactionsResult,
+    HealthResponse, HorizonAsset, HorizonEffect, HorizonLiquidityPool, HorizonOperation,
+    HorizonPoolReserve, HorizonTransaction, InnerTransaction, LedgerInfo, LedgerPoll, Offer,
+    OrderBook, OrderBookDepth, OrderBookEntry, OrderBookSpread, Payment, Price, RpcLatestLedger,
+    RpcLedger, RpcTransaction, StellarRpcClient, Trade,
 };
@@ -0,0 +1,124 @@
+//! Client-side retry throttling, modelled on gRPC's retry throttle.
+//!
+//! Every failed attempt spends a token and every success earns back
+//! `token_ratio` of one. Retries are only allowed while more than half of
+//! `max_tokens` remain, so when the upstream is broadly failing the budget
+//! drains within a few requests and each call makes a single attempt. The
+//! circuit breaker then sees one failure per request and trips at its
+//! configured threshold instead of being fed whole retry chains.
+
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+
+const DEFAULT_MAX_TOKENS: f64 = 10.0;
+const DEFAULT_TOKEN_RATIO: f64 = 0.1;
+
+#[derive(Debug)]
+pub struct RetryBudget {
+    max_tokens: f64,
+    token_ratio: f64,
+    tokens: Mutex<f64>,
+}
+
+impl RetryBudget {
+    #[must_use]
+    pub fn new(max_tokens: f64, token_ratio: f64) -> Self {
+        Self {
+            max_tokens,
+            token_ratio,
+            tokens: Mutex::new(max_tokens),
+        }
+    }
+
+    /// `RPC_RETRY_BUDGET_MAX_TOKENS` (default 10) and
+    /// `RPC_RETRY_BUDGET_TOKEN_RATIO` (default 0.1).
+    #[must_use]
+    pub fn from_env() -> Self {
+        let max_tokens = std::env::var("RPC_RETRY_BUDGET_MAX_TOKENS")
+            .ok()
+            .and_then(|s| s.parse::<f64>().ok())
+            .unwrap_or(DEFAULT_MAX_TOKENS)
+            .clamp(1.0, 1000.0);
+        let token_ratio = std::env::var("RPC_RETRY_BUDGET_TOKEN_RATIO")
+            .ok()
+            .and_then(|s| s.parse::<f64>().ok())
+            .unwrap_or(DEFAULT_TOKEN_RATIO)
+            .clamp(0.01, 1.0);
+        Self::new(max_tokens, token_ratio)
+    }
+
+    /// Whether a failed attempt may be retried right now.
+    #[must_use]
+    pub fn allow_retry(&self) -> bool {
+        self.tokens() > self.max_tokens / 2.0
+    }
+
+    pub fn record_success(&self) {
+        let mut tokens = self.tokens.lock().unwrap_or_else(PoisonError::into_inner);
+        *tokens = (*tokens + self.token_ratio).min(self.max_tokens);
+    }
+
+    pub fn record_failure(&self) {
+        let mut tokens = self.tokens.lock().unwrap_or_else(PoisonError::into_inner);
+        *tokens = (*tokens - 1.0).max(0.0);
+    }
+
+    #[must_use]
+    pub fn tokens(&self) -> f64 {
+        *self.tokens.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Process-wide budget shared by every `StellarRpcClient`, alongside
+/// [`rpc_circuit_breaker`](crate::rpc::circuit_breaker::rpc_circuit_breaker).
+pub fn rpc_retry_budget() -> Arc<RetryBudget> {
+    static BUDGET: OnceLock<Arc<RetryBudget>> = OnceLock::new();
+    BUDGET
+        .get_or_init(|| Arc::new(RetryBudget::from_env()))
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget_drains_on_failure_and_refills_on_success() {
+        let budget = RetryBudget::new(4.0, 0.5);
+        assert!(budget.allow_retry());
+
+        budget.record_failure();
+        assert!(budget.allow_retry());
+        budget.record_failure();
+        // 2 of 4 tokens left: at the threshold, retries stop
+        assert!(!budget.allow_retry());
+
+        for _ in 0..10 {
+            budget.record_failure();
+        }
+        assert!(budget.tokens().abs() < f64::EPSILON);
+
+        // Needs 2.5 tokens (5 successes at 0.5) before retrying again
+        for _ in 0..5 {
+            assert!(!budget.allow_retry());
+            budget.record_success();
+        }
+        assert!(budget.allow_retry());
+
+        for _ in 0..10 {
+            budget.record_success();
+        }
+        assert!((budget.tokens() - 4.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_retry_budget_from_env() {
+        let _guard = crate::lock_env_test();
+        std::env::set_var("RPC_RETRY_BUDGET_MAX_TOKENS", "20");
+        std::env::set_var("RPC_RETRY_BUDGET_TOKEN_RATIO", "5");
+        let budget = RetryBudget::from_env();
+        assert!((budget.max_tokens - 20.0).abs() < f64::EPSILON);
+        assert!((budget.token_ratio - 1.0).abs() < f64::EPSILON);
+        std::env::remove_var("RPC_RETRY_BUDGET_MAX_TOKENS");
+        std::env::remove_var("RPC_RETRY_BUDGET_TOKEN_RATIO");
+    }
+}
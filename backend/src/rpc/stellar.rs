@@ -1,18 +1,29 @@
-use crate::network::{NetworkConfig, StellarNetwork};
+use crate::network::{Network, NetworkConfig, StellarNetwork};
 use crate::observability::tracing::inject_trace_context;
-use crate::rpc::circuit_breaker::{rpc_circuit_breaker, CircuitBreaker};
-use crate::rpc::config::{initial_backoff_from_env, max_backoff_from_env, max_retries_from_env};
-use crate::rpc::error::{with_retry, RetryConfig, RpcError};
+use crate::rpc::circuit_breaker::{rpc_circuit_breaker, CircuitBreaker, SharedCircuitBreaker};
+use crate::rpc::coalesce::RequestCoalescer;
+use crate::rpc::config::{
+    coalesce_requests_from_env, initial_backoff_from_env, max_backoff_from_env,
+    max_retries_from_env, RpcClientConfig,
+};
+use crate::rpc::error::{
+    with_retry, HorizonResultCodes, RetryConfig, RpcError, JSON_RPC_INTERNAL_ERROR,
+    JSON_RPC_INVALID_PARAMS, JSON_RPC_INVALID_REQUEST,
+};
+use crate::rpc::error_log::rpc_error_log;
 use crate::rpc::metrics;
+use crate::rpc::mock_stellar::MockFixtures;
 use crate::rpc::rate_limiter::{RpcRateLimitConfig, RpcRateLimitMetrics, RpcRateLimiter};
+use crate::rpc::retry_budget::{rpc_retry_budget, RetryBudget};
 use anyhow::{anyhow, Context, Result};
 use reqwest::Client;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::json;
+use std::collections::HashMap;
 use std::fmt::Write;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tracing::{debug, info, warn};
+use tracing::{debug, info, warn, Instrument};
 use uuid::Uuid;
 
 // ============================================================================
@@ -31,6 +42,9 @@ const MIN_PAGINATION_DELAY_MS: u64 = 50;
 /// Default delay between pagination requests
 const DEFAULT_PAGINATION_DELAY_MS: u64 = 100;
 
+/// Correlation id header attached to every outbound RPC/Horizon request
+const REQUEST_ID_HEADER: &str = "x-request-id";
+
 /// Stellar RPC Client for interacting with Stellar network via RPC and Horizon API
 // Asset Models (Horizon API)
 // ==========================================
@@ -78,9 +92,13 @@ pub struct StellarRpcClient {
     rpc_url: String,
     horizon_url: String,
     network_config: NetworkConfig,
+    /// Network selection this client was built for (may be `Custom`)
+    active_network: Network,
     mock_mode: bool,
     rate_limiter: RpcRateLimiter,
     circuit_breaker: Arc<CircuitBreaker>,
+    /// Shared cap on retries across requests, see [`RetryBudget`]
+    retry_budget: Arc<RetryBudget>,
     /// Maximum records per single request (default: 200)
     max_records_per_request: u32,
     /// Maximum total records across all paginated requests (default: 10_000)
@@ -93,6 +111,12 @@ pub struct StellarRpcClient {
     initial_backoff: Duration,
     /// Maximum backoff duration
     max_backoff: Duration,
+    /// Per-request HTTP timeout, also reported in `RpcError::TimeoutError`
+    http_timeout: Duration,
+    /// Fixture overrides served in mock mode
+    mock_fixtures: Arc<MockFixtures>,
+    /// Shares in-flight calls between identical concurrent requests when set
+    coalescer: Option<Arc<RequestCoalescer>>,
 }
 
 // ============================================================================
@@ -110,6 +134,17 @@ pub struct HealthResponse {
     pub ledger_retention_window: u64,
 }
 
+/// Result of the Soroban RPC `getLatestLedger` method.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcLatestLedger {
+    /// Ledger hash (returned as `id` by the RPC)
+    #[serde(rename = "id")]
+    pub hash: String,
+    #[serde(rename = "protocolVersion")]
+    pub protocol_version: u32,
+    pub sequence: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JsonRpcResponse<T> {
     pub jsonrpc: String,
@@ -124,6 +159,65 @@ pub struct JsonRpcError {
     pub message: String,
 }
 
+impl From<JsonRpcError> for RpcError {
+    fn from(error: JsonRpcError) -> Self {
+        Self::JsonRpcError {
+            code: error.code,
+            message: error.message,
+        }
+    }
+}
+
+/// A single call within a JSON-RPC batch. `id` must be unique within the batch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRpcCall {
+    pub jsonrpc: String,
+    pub id: u64,
+    pub method: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<serde_json::Value>,
+}
+
+impl JsonRpcCall {
+    #[must_use]
+    pub fn new(id: u64, method: impl Into<String>, params: Option<serde_json::Value>) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id,
+            method: method.into(),
+            params,
+        }
+    }
+}
+
+/// Match batch response items back to `calls` by id, in call order. Calls the
+/// server didn't answer get a per-item error rather than failing the batch.
+fn correlate_batch_responses(
+    calls: &[JsonRpcCall],
+    items: Vec<serde_json::Value>,
+) -> Vec<JsonRpcResponse<serde_json::Value>> {
+    let mut by_id: HashMap<u64, JsonRpcResponse<serde_json::Value>> = items
+        .into_iter()
+        .filter_map(|item| serde_json::from_value::<JsonRpcResponse<serde_json::Value>>(item).ok())
+        .map(|response| (response.id, response))
+        .collect();
+
+    calls
+        .iter()
+        .map(|call| {
+            by_id.remove(&call.id).unwrap_or_else(|| JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id: call.id,
+                result: None,
+                error: Some(JsonRpcError {
+                    code: JSON_RPC_INTERNAL_ERROR,
+                    message: format!("No response for request id {}", call.id),
+                }),
+            })
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LedgerInfo {
     pub sequence: u64,
@@ -259,10 +353,105 @@ impl Payment {
         }
         self.asset_issuer.clone()
     }
+
+    /// Returns the asset type, preferring `asset_balance_changes`.
+    #[must_use]
+    pub fn get_asset_type(&self) -> String {
+        if let Some(ref changes) = self.asset_balance_changes {
+            if let Some(change) = changes.first() {
+                return change.asset_type.clone();
+            }
+        }
+        self.asset_type.clone()
+    }
+
+    #[must_use]
+    pub fn is_path_payment(&self) -> bool {
+        matches!(
+            self.operation_type.as_deref(),
+            Some("path_payment_strict_send" | "path_payment_strict_receive")
+        )
+    }
+
+    /// Asset credited to the destination, as `CODE:ISSUER` or `XLM:native`.
+    #[must_use]
+    pub fn destination_asset_key(&self) -> String {
+        corridor_asset_key(
+            &self.get_asset_type(),
+            self.get_asset_code().as_deref(),
+            self.get_asset_issuer().as_deref(),
+        )
+    }
+
+    /// Asset debited from the sender. Path payments carry it in `source_asset_*`;
+    /// plain payments send and receive the same asset. `None` for a path
+    /// payment missing its source asset.
+    #[must_use]
+    pub fn source_asset_key(&self) -> Option<String> {
+        if !self.is_path_payment() {
+            return Some(self.destination_asset_key());
+        }
+        let asset_type = self.source_asset_type.as_deref()?;
+        Some(corridor_asset_key(
+            asset_type,
+            self.source_asset_code.as_deref(),
+            self.source_asset_issuer.as_deref(),
+        ))
+    }
+
+    /// Corridor this payment belongs to: `SOURCE_ASSET->DESTINATION_ASSET`.
+    #[must_use]
+    pub fn corridor_key(&self) -> Option<String> {
+        Some(format!(
+            "{}->{}",
+            self.source_asset_key()?,
+            self.destination_asset_key()
+        ))
+    }
+}
+
+/// Count a failed call in `rpc_errors_total` and keep it in the RPC error log
+fn record_error(method: &str, error: &RpcError) {
+    metrics::record_rpc_error(error.error_type_label(), "stellar");
+    rpc_error_log().record(method, error);
+}
+
+fn corridor_asset_key(asset_type: &str, code: Option<&str>, issuer: Option<&str>) -> String {
+    if asset_type == "native" {
+        "XLM:native".to_string()
+    } else {
+        format!(
+            "{}:{}",
+            code.unwrap_or("UNKNOWN"),
+            issuer.unwrap_or("unknown")
+        )
+    }
 }
 
 // Horizon API Response Structures
 // ==========================================
+
+/// Accept a stroop amount Horizon may encode as either a JSON string or a
+/// number; older Horizon versions and some providers send fees as numbers.
+fn deserialize_string_or_number<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringOrNumber {
+        String(String),
+        Number(serde_json::Number),
+    }
+
+    Ok(
+        Option::<StringOrNumber>::deserialize(deserializer)?.map(|value| match value {
+            StringOrNumber::String(s) => s,
+            StringOrNumber::Number(n) => n.to_string(),
+        }),
+    )
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HorizonOperation {
     pub id: String,
@@ -275,6 +464,11 @@ pub struct HorizonOperation {
     pub account: Option<String>,
     pub into: Option<String>,
     pub amount: Option<String>,
+    /// Asset of a `create_claimable_balance`, `native` or `CODE:ISSUER`
+    pub asset: Option<String>,
+    /// Balance claimed or clawed back by a `claim_claimable_balance` or
+    /// `clawback_claimable_balance`
+    pub balance_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -285,6 +479,8 @@ pub struct HorizonEffect {
     pub account: Option<String>,
     pub amount: Option<String>,
     pub asset_type: Option<String>,
+    /// Set on `claimable_balance_*` effects
+    pub balance_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -296,9 +492,17 @@ pub struct HorizonTransaction {
     pub source_account: String,
     #[serde(rename = "fee_account")]
     pub fee_account: Option<String>,
-    #[serde(rename = "fee_charged")]
+    #[serde(
+        rename = "fee_charged",
+        default,
+        deserialize_with = "deserialize_string_or_number"
+    )]
     pub fee_charged: Option<String>,
-    #[serde(rename = "max_fee")]
+    #[serde(
+        rename = "max_fee",
+        default,
+        deserialize_with = "deserialize_string_or_number"
+    )]
     pub max_fee: Option<String>,
     pub operation_count: u32,
     pub successful: bool,
@@ -318,7 +522,11 @@ pub struct FeeBumpTransactionInfo {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InnerTransaction {
     pub hash: String,
-    #[serde(rename = "max_fee")]
+    #[serde(
+        rename = "max_fee",
+        default,
+        deserialize_with = "deserialize_string_or_number"
+    )]
     pub max_fee: Option<String>,
     pub signatures: Vec<String>,
 }
@@ -347,6 +555,28 @@ pub struct Price {
     pub d: i64,
 }
 
+impl Trade {
+    /// Base asset as `CODE:ISSUER` (`XLM:native` for lumens), matching corridor keys
+    #[must_use]
+    pub fn base_asset_key(&self) -> String {
+        corridor_asset_key(
+            &self.base_asset_type,
+            self.base_asset_code.as_deref(),
+            self.base_asset_issuer.as_deref(),
+        )
+    }
+
+    /// Counter asset as `CODE:ISSUER` (`XLM:native` for lumens), matching corridor keys
+    #[must_use]
+    pub fn counter_asset_key(&self) -> String {
+        corridor_asset_key(
+            &self.counter_asset_type,
+            self.counter_asset_code.as_deref(),
+            self.counter_asset_issuer.as_deref(),
+        )
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderBook {
     pub bids: Vec<OrderBookEntry>,
@@ -361,6 +591,113 @@ pub struct OrderBookEntry {
     pub amount: String,
 }
 
+/// Gap between the best bid and best ask of an order book.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct OrderBookSpread {
+    pub best_bid: f64,
+    pub best_ask: f64,
+    /// `best_ask - best_bid`
+    pub absolute: f64,
+    /// Spread as a percentage of the mid-price
+    pub percent: f64,
+}
+
+/// Amount resting on each side of the book within a band around the mid-price.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct OrderBookDepth {
+    pub bid_amount: f64,
+    pub ask_amount: f64,
+}
+
+impl OrderBookDepth {
+    #[must_use]
+    pub fn total(&self) -> f64 {
+        self.bid_amount + self.ask_amount
+    }
+}
+
+impl OrderBookEntry {
+    fn parsed(&self) -> Option<(f64, f64)> {
+        let price = self.price.parse::<f64>().ok()?;
+        let amount = self.amount.parse::<f64>().ok()?;
+        Some((price, amount))
+    }
+}
+
+impl OrderBook {
+    fn best_bid(&self) -> Option<f64> {
+        self.bids
+            .iter()
+            .filter_map(OrderBookEntry::parsed)
+            .map(|(price, _)| price)
+            .reduce(f64::max)
+    }
+
+    fn best_ask(&self) -> Option<f64> {
+        self.asks
+            .iter()
+            .filter_map(OrderBookEntry::parsed)
+            .map(|(price, _)| price)
+            .reduce(f64::min)
+    }
+
+    /// Midpoint between the best bid and best ask, if both sides are quoted.
+    #[must_use]
+    pub fn mid_price(&self) -> Option<f64> {
+        Some(f64::midpoint(self.best_bid()?, self.best_ask()?))
+    }
+
+    /// Returns the best bid/ask spread, or `None` if either side is empty.
+    #[must_use]
+    pub fn spread(&self) -> Option<OrderBookSpread> {
+        let best_bid = self.best_bid()?;
+        let best_ask = self.best_ask()?;
+        let mid = f64::midpoint(best_bid, best_ask);
+        if mid <= 0.0 {
+            return None;
+        }
+        let absolute = best_ask - best_bid;
+
+        Some(OrderBookSpread {
+            best_bid,
+            best_ask,
+            absolute,
+            percent: absolute / mid * 100.0,
+        })
+    }
+
+    /// Sums bid and ask amounts priced within `pct` percent of the mid-price
+    /// (e.g. `1.0` = 1%). Entries with unparseable price or amount are skipped.
+    #[must_use]
+    pub fn depth_within(&self, pct: f64) -> OrderBookDepth {
+        let Some(mid) = self.mid_price() else {
+            return OrderBookDepth::default();
+        };
+        let min_bid = mid * (1.0 - pct / 100.0);
+        let max_ask = mid * (1.0 + pct / 100.0);
+
+        let bid_amount = self
+            .bids
+            .iter()
+            .filter_map(OrderBookEntry::parsed)
+            .filter(|(price, _)| *price >= min_bid)
+            .map(|(_, amount)| amount)
+            .sum();
+        let ask_amount = self
+            .asks
+            .iter()
+            .filter_map(OrderBookEntry::parsed)
+            .filter(|(price, _)| *price <= max_ask)
+            .map(|(_, amount)| amount)
+            .sum();
+
+        OrderBookDepth {
+            bid_amount,
+            ask_amount,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Asset {
     pub asset_type: String,
@@ -368,6 +705,79 @@ pub struct Asset {
     pub asset_issuer: Option<String>,
 }
 
+impl Asset {
+    /// Parse `native` or `CODE:ISSUER` into a Horizon asset.
+    #[must_use]
+    pub fn parse(id: &str) -> Option<Self> {
+        if id.eq_ignore_ascii_case("native") {
+            return Some(Self {
+                asset_type: "native".to_string(),
+                asset_code: None,
+                asset_issuer: None,
+            });
+        }
+        let (code, issuer) = id.split_once(':')?;
+        if code.is_empty() || code.len() > 12 || issuer.is_empty() {
+            return None;
+        }
+        let asset_type = if code.len() <= 4 {
+            "credit_alphanum4"
+        } else {
+            "credit_alphanum12"
+        };
+        Some(Self {
+            asset_type: asset_type.to_string(),
+            asset_code: Some(code.to_string()),
+            asset_issuer: Some(issuer.to_string()),
+        })
+    }
+}
+
+/// Account state as returned by Horizon's `/accounts/{id}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountInfo {
+    pub account_id: String,
+    pub sequence: String,
+    pub subentry_count: u32,
+    #[serde(default)]
+    pub last_modified_ledger: u64,
+    pub balances: Vec<Balance>,
+    /// Domain hosting the account's stellar.toml (SEP-1), if set
+    #[serde(default)]
+    pub home_domain: Option<String>,
+}
+
+/// A single trustline or native balance held by an account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Balance {
+    pub asset_type: String,
+    pub asset_code: Option<String>,
+    pub asset_issuer: Option<String>,
+    pub balance: String,
+    #[serde(default)]
+    pub buying_liabilities: String,
+    #[serde(default)]
+    pub selling_liabilities: String,
+    /// Trustline limit; absent for the native balance
+    pub limit: Option<String>,
+}
+
+/// A resting offer on the DEX as returned by Horizon's `/accounts/{id}/offers`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Offer {
+    pub id: String,
+    pub paging_token: String,
+    pub seller: String,
+    pub selling: Asset,
+    pub buying: Asset,
+    pub amount: String,
+    /// Offer price as a rational (`price_r` in Horizon)
+    #[serde(rename = "price_r")]
+    pub price: Price,
+    #[serde(default)]
+    pub last_modified_ledger: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HorizonResponse<T> {
     #[serde(rename = "_embedded")]
@@ -403,6 +813,49 @@ pub struct GetLedgersResult {
     pub cursor: Option<String>,
 }
 
+/// Outcome of polling `getLedgers` forward from a ledger sequence.
+#[derive(Debug, Clone)]
+pub enum LedgerPoll {
+    /// At least one ledger at or after the requested start.
+    Ledgers(GetLedgersResult),
+    /// The requested start is past the node's latest ledger; nothing to do
+    /// until the network closes another ledger.
+    CaughtUp { latest_ledger: u64 },
+}
+
+/// Transaction as returned by the Soroban RPC `getTransactions` method.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcTransaction {
+    #[serde(rename = "txHash")]
+    pub tx_hash: String,
+    /// `SUCCESS` or `FAILED`
+    pub status: String,
+    /// Position of the transaction within its ledger, starting at 1
+    #[serde(rename = "applicationOrder")]
+    pub application_order: u32,
+    #[serde(rename = "feeBump", default)]
+    pub fee_bump: bool,
+    pub ledger: u64,
+    #[serde(rename = "createdAt", default)]
+    pub created_at: Option<i64>,
+    #[serde(rename = "envelopeXdr")]
+    pub envelope_xdr: Option<String>,
+    #[serde(rename = "resultXdr")]
+    pub result_xdr: Option<String>,
+    #[serde(rename = "resultMetaXdr")]
+    pub result_meta_xdr: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetTransactionsResult {
+    pub transactions: Vec<RpcTransaction>,
+    #[serde(rename = "latestLedger")]
+    pub latest_ledger: u64,
+    #[serde(rename = "oldestLedger")]
+    pub oldest_ledger: u64,
+    pub cursor: Option<String>,
+}
+
 // ============================================================================
 // Liquidity Pool Models (Horizon API)
 // ============================================================================
@@ -448,6 +901,9 @@ fn status_to_rpc_error(
             message: body,
         };
     }
+    if let Some(codes) = HorizonResultCodes::from_error_body(&body) {
+        return RpcError::TransactionFailed(codes);
+    }
     RpcError::ServerError {
         status: status.as_u16(),
         message: body,
@@ -468,6 +924,55 @@ async fn map_response_error(response: reqwest::Response) -> RpcError {
     status_to_rpc_error(status, body, retry_after)
 }
 
+/// Decode a JSON response body.
+///
+/// reqwest strips `Content-Encoding` once it has decompressed gzip or br, so
+/// a header still present here is an encoding it could not decode; that is
+/// reported as such rather than as a confusing JSON syntax error.
+async fn parse_json<T: serde::de::DeserializeOwned>(
+    response: reqwest::Response,
+) -> Result<T, RpcError> {
+    if let Some(encoding) = response
+        .headers()
+        .get(reqwest::header::CONTENT_ENCODING)
+        .and_then(|v| v.to_str().ok())
+        .filter(|e| !e.eq_ignore_ascii_case("identity"))
+    {
+        return Err(RpcError::ParseError(format!(
+            "unsupported response content-encoding '{encoding}'"
+        )));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| RpcError::ParseError(e.to_string()))
+}
+
+fn build_http_client(config: &RpcClientConfig) -> Client {
+    Client::builder()
+        .timeout(config.timeout)
+        .pool_max_idle_per_host(config.pool_max_idle_per_host)
+        .pool_idle_timeout(config.pool_idle_timeout)
+        .user_agent(config.user_agent.clone())
+        .gzip(true)
+        .brotli(true)
+        .build()
+        .expect("Failed to build HTTP client")
+}
+
+fn coalescer_from_env() -> Option<Arc<RequestCoalescer>> {
+    coalesce_requests_from_env().then(|| Arc::new(RequestCoalescer::new()))
+}
+
+/// `native` or `CODE:ISSUER`, for request coalescing keys
+fn coalesce_key(asset: &Asset) -> String {
+    match (&asset.asset_code, &asset.asset_issuer) {
+        (Some(code), Some(issuer)) => format!("{code}:{issuer}"),
+        (Some(code), None) => code.clone(),
+        _ => asset.asset_type.clone(),
+    }
+}
+
 // ============================================================================
 // Implementation
 // ============================================================================
@@ -480,20 +985,17 @@ impl StellarRpcClient {
     /// * `horizon_url` - The Horizon API endpoint URL
     /// * `mock_mode` - If true, returns mock data instead of making real API calls
     pub fn new(rpc_url: String, horizon_url: String, mock_mode: bool) -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(30))
-            .build()
-            .expect("Failed to build HTTP client");
+        let client_config = RpcClientConfig::from_env();
+        let http_timeout = client_config.timeout;
+        let client = build_http_client(&client_config);
         let rate_limiter = RpcRateLimiter::new(RpcRateLimitConfig::from_env());
 
-        // Determine network based on URLs
-        let network = if horizon_url.contains("testnet") {
-            StellarNetwork::Testnet
-        } else {
-            StellarNetwork::Mainnet
+        // Explicit URLs are a custom network; the passphrase is inferred from them
+        let network_config = NetworkConfig::for_custom(rpc_url.clone(), horizon_url.clone());
+        let active_network = Network::Custom {
+            rpc: rpc_url.clone(),
+            horizon: horizon_url.clone(),
         };
-
-        let network_config = NetworkConfig::for_network(network);
         let circuit_breaker = rpc_circuit_breaker();
 
         // Load pagination config from environment or use defaults with security limits
@@ -555,15 +1057,20 @@ impl StellarRpcClient {
             rpc_url,
             horizon_url,
             network_config,
+            active_network,
             mock_mode,
             rate_limiter,
             circuit_breaker,
+            retry_budget: rpc_retry_budget(),
             max_records_per_request,
             max_total_records,
             pagination_delay_ms,
             max_retries: max_retries_from_env(),
             initial_backoff: initial_backoff_from_env(),
             max_backoff: max_backoff_from_env(),
+            http_timeout,
+            mock_fixtures: Arc::default(),
+            coalescer: coalescer_from_env(),
         }
     }
 
@@ -572,10 +1079,9 @@ impl StellarRpcClient {
     pub fn new_with_network(network: StellarNetwork, mock_mode: bool) -> Self {
         let network_config = NetworkConfig::for_network(network);
 
-        let client = Client::builder()
-            .timeout(Duration::from_secs(30))
-            .build()
-            .expect("Failed to build HTTP client");
+        let client_config = RpcClientConfig::from_env();
+        let http_timeout = client_config.timeout;
+        let client = build_http_client(&client_config);
         let rate_limiter = RpcRateLimiter::new(RpcRateLimitConfig::from_env());
         let circuit_breaker = rpc_circuit_breaker();
 
@@ -603,15 +1109,20 @@ impl StellarRpcClient {
             rpc_url: network_config.rpc_url.clone(),
             horizon_url: network_config.horizon_url.clone(),
             network_config,
+            active_network: Network::from(network),
             mock_mode,
             rate_limiter,
             circuit_breaker,
+            retry_budget: rpc_retry_budget(),
             max_records_per_request,
             max_total_records,
             pagination_delay_ms,
             max_retries: max_retries_from_env(),
             initial_backoff: initial_backoff_from_env(),
             max_backoff: max_backoff_from_env(),
+            http_timeout,
+            mock_fixtures: Arc::default(),
+            coalescer: coalescer_from_env(),
         }
     }
 
@@ -631,6 +1142,175 @@ impl StellarRpcClient {
         Self::new_with_network(network, mock_mode)
     }
 
+    /// Override the HTTP timeout (e.g. for long-running backfill jobs).
+    #[must_use]
+    pub fn with_http_timeout(self, timeout: Duration) -> Self {
+        self.with_client_config(RpcClientConfig {
+            timeout,
+            ..RpcClientConfig::from_env()
+        })
+    }
+
+    /// Rebuild the HTTP client with explicit timeout and connection pool settings.
+    #[must_use]
+    pub fn with_client_config(mut self, config: RpcClientConfig) -> Self {
+        self.client = build_http_client(&config);
+        self.http_timeout = config.timeout;
+        self
+    }
+
+    /// Serve the given fixtures in mock mode instead of the generated defaults.
+    ///
+    /// Methods without an override keep returning the `mock_stellar` data.
+    #[must_use]
+    pub fn with_mock_fixtures(mut self, fixtures: MockFixtures) -> Self {
+        self.mock_fixtures = Arc::new(fixtures);
+        self
+    }
+
+    /// Use a dedicated circuit breaker instead of the process-wide one.
+    #[must_use]
+    pub fn with_circuit_breaker(mut self, circuit_breaker: SharedCircuitBreaker) -> Self {
+        self.circuit_breaker = circuit_breaker;
+        self
+    }
+
+    /// Use a dedicated retry budget instead of the process-wide one.
+    #[must_use]
+    pub fn with_retry_budget(mut self, retry_budget: Arc<RetryBudget>) -> Self {
+        self.retry_budget = retry_budget;
+        self
+    }
+
+    /// Turn request coalescing on or off, overriding `RPC_COALESCE_REQUESTS`.
+    #[must_use]
+    pub fn with_request_coalescing(mut self, enabled: bool) -> Self {
+        self.coalescer = enabled.then(|| Arc::new(RequestCoalescer::new()));
+        self
+    }
+
+    /// Override the retry count and backoff bounds read from the environment.
+    #[must_use]
+    pub const fn with_retry_policy(
+        mut self,
+        max_retries: u32,
+        initial_backoff: Duration,
+        max_backoff: Duration,
+    ) -> Self {
+        self.max_retries = max_retries;
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Configured per-request HTTP timeout
+    #[must_use]
+    pub const fn http_timeout(&self) -> Duration {
+        self.http_timeout
+    }
+
+    /// Map a transport-level reqwest error, reporting the configured
+    /// timeout when the request timed out.
+    ///
+    /// reqwest's message omits the underlying cause, so the source chain is
+    /// appended to tell DNS failures apart from other connection errors.
+    fn send_error(&self, err: &reqwest::Error) -> RpcError {
+        if err.is_timeout() {
+            return RpcError::TimeoutError(format!(
+                "request timed out after {}ms",
+                self.http_timeout.as_millis()
+            ));
+        }
+        let mut message = err.to_string();
+        let mut source = std::error::Error::source(err);
+        while let Some(cause) = source {
+            message.push_str(": ");
+            message.push_str(&cause.to_string());
+            source = cause.source();
+        }
+        RpcError::network(message)
+    }
+
+    /// Send one request attempt, mapping transport errors to [`RpcError`].
+    async fn send_once(
+        &self,
+        builder: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, RpcError> {
+        self.send_tagged(builder)
+            .await
+            .map_err(|e| self.send_error(&e))
+    }
+
+    /// Send one request attempt tagged with a fresh `X-Request-Id`.
+    ///
+    /// The id is recorded on the `rpc_request` span, which is exported to APM
+    /// and propagated upstream via the trace context, and logged with any
+    /// transport error or error status so a failing call can be traced
+    /// end-to-end.
+    async fn send_tagged(
+        &self,
+        builder: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, reqwest::Error> {
+        let request_id = Uuid::new_v4().to_string();
+        let span = tracing::info_span!("rpc_request", request_id = %request_id);
+        async {
+            let result = inject_trace_context(builder.header(REQUEST_ID_HEADER, &request_id))
+                .send()
+                .await;
+            match &result {
+                Ok(response) if !response.status().is_success() => warn!(
+                    request_id = %request_id,
+                    "RPC request failed with status {}",
+                    response.status()
+                ),
+                Ok(_) => {}
+                Err(e) => warn!(request_id = %request_id, "RPC request failed: {}", e),
+            }
+            result
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// Create a client for a [`Network`] selection.
+    ///
+    /// `Mainnet`/`Testnet` use the canonical URLs from [`NetworkConfig`]
+    /// (testnet defaults to `https://horizon-testnet.stellar.org`; mainnet
+    /// requires the `STELLAR_*_URL_MAINNET` variables), `Custom` uses the
+    /// given endpoints as-is.
+    #[must_use]
+    pub fn for_network(network: Network, mock_mode: bool) -> Self {
+        match network {
+            Network::Mainnet => Self::new_with_network(StellarNetwork::Mainnet, mock_mode),
+            Network::Testnet => Self::new_with_network(StellarNetwork::Testnet, mock_mode),
+            Network::Custom { rpc, horizon } => Self::new(rpc, horizon, mock_mode),
+        }
+    }
+
+    /// Network selection this client was built for, for tagging responses
+    #[must_use]
+    pub const fn active_network(&self) -> &Network {
+        &self.active_network
+    }
+
+    /// Whether calls return mock data instead of reaching the network
+    #[must_use]
+    pub const fn is_mock_mode(&self) -> bool {
+        self.mock_mode
+    }
+
+    /// Soroban RPC endpoint; may embed a provider API key
+    #[must_use]
+    pub fn rpc_url(&self) -> &str {
+        &self.rpc_url
+    }
+
+    /// Horizon endpoint; may embed a provider API key
+    #[must_use]
+    pub fn horizon_url(&self) -> &str {
+        &self.horizon_url
+    }
+
     /// Get the current network configuration
     #[must_use]
     pub const fn network_config(&self) -> &NetworkConfig {
@@ -661,7 +1341,12 @@ impl StellarRpcClient {
         self.rate_limiter.metrics()
     }
 
-    async fn execute_with_retry<F, Fut, T>(&self, operation: F) -> Result<T, RpcError>
+    /// Run `operation` with retries, recording its duration under `method`
+    async fn execute_with_retry<F, Fut, T>(
+        &self,
+        method: &'static str,
+        operation: F,
+    ) -> Result<T, RpcError>
     where
         F: Fn() -> Fut,
         Fut: std::future::Future<Output = Result<T, RpcError>>,
@@ -670,26 +1355,47 @@ impl StellarRpcClient {
             max_attempts: self.max_retries + 1,
             base_delay_ms: self.initial_backoff.as_millis() as u64,
             max_delay_ms: self.max_backoff.as_millis() as u64,
+            ..RetryConfig::default()
         };
 
-        with_retry(operation, retry_config, self.circuit_breaker.clone()).await
+        let started = Instant::now();
+        let result = with_retry(
+            operation,
+            retry_config,
+            self.circuit_breaker.clone(),
+            &self.retry_budget,
+        )
+        .await;
+        metrics::observe_rpc_duration(method, started.elapsed().as_secs_f64());
+        result
+    }
+
+    /// Run `fetch` through the request coalescer when coalescing is enabled
+    async fn coalesced<T, F, Fut>(&self, key: String, fetch: F) -> Result<T, RpcError>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<T, RpcError>>,
+    {
+        match &self.coalescer {
+            Some(coalescer) => coalescer.run(key, fetch).await,
+            None => fetch().await,
+        }
     }
 
     /// Check the health of the RPC endpoint
     pub async fn check_health(&self) -> Result<HealthResponse, RpcError> {
         if self.mock_mode {
-            return Ok(super::mock_stellar::mock_health_response());
+            return Ok(self.mock_fixtures.health());
         }
 
         info!("Checking RPC health at {}", self.rpc_url);
 
         let result = self
-            .execute_with_retry(|| self.check_health_internal())
+            .execute_with_retry("check_health", || self.check_health_internal())
             .await;
 
-        result.inspect_err(|e| {
-            metrics::record_rpc_error(e.error_type_label(), "stellar");
-        })
+        result.inspect_err(|e| record_error("check_health", e))
     }
 
     async fn check_health_internal(&self) -> Result<HealthResponse, RpcError> {
@@ -699,29 +1405,18 @@ impl StellarRpcClient {
             "id": 1
         });
 
-        let response = inject_trace_context(
-            self.client
-                .post(&self.rpc_url)
-                .json(&payload)
-        )
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self
+            .send_once(self.client.post(&self.rpc_url).json(&payload))
+            .await?;
 
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
 
-        let json_response: JsonRpcResponse<HealthResponse> = response
-            .json()
-            .await
-            .map_err(|e| RpcError::ParseError(e.to_string()))?;
+        let json_response: JsonRpcResponse<HealthResponse> = parse_json(response).await?;
 
         if let Some(error) = json_response.error {
-            return Err(RpcError::ServerError {
-                status: 500,
-                message: format!("RPC error: {} (code: {})", error.message, error.code),
-            });
+            return Err(error.into());
         }
 
         json_response
@@ -732,74 +1427,111 @@ impl StellarRpcClient {
     /// Fetch latest ledger information
     pub async fn fetch_latest_ledger(&self) -> Result<LedgerInfo, RpcError> {
         if self.mock_mode {
-            return Ok(super::mock_stellar::mock_ledger_info());
+            return Ok(self.mock_fixtures.ledger_info());
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_latest_ledger_internal())
+            .coalesced("fetch_latest_ledger".to_string(), || {
+                self.execute_with_retry("fetch_latest_ledger", || {
+                    self.fetch_latest_ledger_internal()
+                })
+            })
             .await;
 
-        result.inspect_err(|e| {
-            metrics::record_rpc_error(e.error_type_label(), "stellar");
-        })
+        result.inspect_err(|e| record_error("fetch_latest_ledger", e))
     }
 
     async fn fetch_latest_ledger_internal(&self) -> Result<LedgerInfo, RpcError> {
         let url = format!("{}/ledgers?order=desc&limit=1", self.horizon_url);
-        let response = inject_trace_context(
-            self.client
-                .get(&url)
-        )
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send_once(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
-        let horizon_response: HorizonResponse<LedgerInfo> = response
-            .json()
-            .await
-            .map_err(|e| RpcError::ParseError(e.to_string()))?;
+        let horizon_response: HorizonResponse<LedgerInfo> = parse_json(response).await?;
         horizon_response
             .embedded
             .and_then(|e| e.records.into_iter().next())
             .ok_or_else(|| RpcError::ParseError("No ledger data found".to_string()))
     }
 
-    /// Fetch a specific ledger by its sequence number and return its info.
-    /// Used to verify ledger hashes during snapshot generation (issue #1631).
-    pub async fn fetch_ledger_by_sequence(&self, sequence: u64) -> Result<LedgerInfo, RpcError> {
+    /// Fetch the latest ledger known to the Soroban RPC via `getLatestLedger`.
+    ///
+    /// Cheaper than the Horizon `/ledgers` query and reflects RPC-side sync
+    /// state, so it suits liveness checks and choosing a backfill start point.
+    pub async fn fetch_latest_rpc_ledger(&self) -> Result<RpcLatestLedger, RpcError> {
         if self.mock_mode {
-            return Ok(super::mock_stellar::mock_ledger_info());
+            return Ok(super::mock_stellar::mock_latest_rpc_ledger());
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_ledger_by_sequence_internal(sequence))
+            .execute_with_retry("fetch_latest_rpc_ledger", || {
+                self.fetch_latest_rpc_ledger_internal()
+            })
             .await;
 
-        result.inspect_err(|e| {
-            metrics::record_rpc_error(e.error_type_label(), "stellar");
-        })
+        result.inspect_err(|e| record_error("fetch_latest_rpc_ledger", e))
     }
 
-    async fn fetch_ledger_by_sequence_internal(&self, sequence: u64) -> Result<LedgerInfo, RpcError> {
-        let url = format!("{}/ledgers/{}", self.horizon_url, sequence);
-        let response = inject_trace_context(
-            self.client
-                .get(&url)
-        )
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
-        if !response.status().is_success() {
+    async fn fetch_latest_rpc_ledger_internal(&self) -> Result<RpcLatestLedger, RpcError> {
+        let payload = json!({
+            "jsonrpc": "2.0",
+            "method": "getLatestLedger",
+            "id": 1
+        });
+
+        let response = self
+            .send_once(self.client.post(&self.rpc_url).json(&payload))
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(map_response_error(response).await);
+        }
+
+        let json_response: JsonRpcResponse<RpcLatestLedger> = parse_json(response).await?;
+
+        if let Some(error) = json_response.error {
+            return Err(error.into());
+        }
+
+        json_response.result.ok_or_else(|| {
+            RpcError::ParseError("No result in getLatestLedger response".to_string())
+        })
+    }
+
+    /// Fetch a specific ledger by its sequence number and return its info.
+    /// Used to verify ledger hashes during snapshot generation (issue #1631).
+    ///
+    /// Returns `RpcError::NotFound` when Horizon has no ledger at `sequence`.
+    pub async fn fetch_ledger(&self, sequence: u64) -> Result<LedgerInfo, RpcError> {
+        if self.mock_mode {
+            return Ok(self.mock_fixtures.ledger(sequence));
+        }
+
+        let result = self
+            .execute_with_retry("fetch_ledger", || self.fetch_ledger_internal(sequence))
+            .await;
+
+        result.inspect_err(|e| record_error("fetch_ledger", e))
+    }
+
+    /// Former name of [`Self::fetch_ledger`]
+    #[deprecated(note = "renamed to `fetch_ledger`")]
+    pub async fn fetch_ledger_by_sequence(&self, sequence: u64) -> Result<LedgerInfo, RpcError> {
+        self.fetch_ledger(sequence).await
+    }
+
+    async fn fetch_ledger_internal(&self, sequence: u64) -> Result<LedgerInfo, RpcError> {
+        let url = format!("{}/ledgers/{}", self.horizon_url, sequence);
+        let response = self.send_once(self.client.get(&url)).await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(RpcError::NotFound(format!("ledger {sequence}")));
+        }
+        if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
         // Horizon returns a single ledger object (not wrapped in _embedded)
         // when querying by sequence directly.
-        response
-            .json::<LedgerInfo>()
-            .await
-            .map_err(|e| RpcError::ParseError(e.to_string()))
+        parse_json::<LedgerInfo>(response).await
     }
 
     /// I'm fetching ledgers via RPC getLedgers for sequential ingestion (issue #2)
@@ -822,12 +1554,54 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_ledgers_internal(start_ledger, limit, cursor))
+            .execute_with_retry("fetch_ledgers", || {
+                self.fetch_ledgers_internal(start_ledger, limit, cursor)
+            })
             .await;
 
-        result.inspect_err(|e| {
-            metrics::record_rpc_error(e.error_type_label(), "stellar");
-        })
+        result.inspect_err(|e| record_error("fetch_ledgers", e))
+    }
+
+    /// Fetch ledgers from `start_ledger` (or `cursor`), telling "caught up"
+    /// apart from a failed or unexpectedly empty response.
+    ///
+    /// Soroban RPC rejects a start past its latest ledger with an invalid
+    /// request error, so such errors are checked against `getHealth` before
+    /// being reported. An empty page for a start the node should have is
+    /// returned as [`RpcError::NotFound`].
+    pub async fn poll_ledgers(
+        &self,
+        start_ledger: u64,
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> Result<LedgerPoll, RpcError> {
+        match self.fetch_ledgers(Some(start_ledger), limit, cursor).await {
+            Ok(result) if result.ledgers.is_empty() => {
+                if start_ledger > result.latest_ledger {
+                    Ok(LedgerPoll::CaughtUp {
+                        latest_ledger: result.latest_ledger,
+                    })
+                } else {
+                    Err(RpcError::NotFound(format!(
+                        "getLedgers returned no ledgers from {start_ledger} (latest {})",
+                        result.latest_ledger
+                    )))
+                }
+            }
+            Ok(result) => Ok(LedgerPoll::Ledgers(result)),
+            Err(
+                err @ RpcError::JsonRpcError {
+                    code: JSON_RPC_INVALID_REQUEST | JSON_RPC_INVALID_PARAMS,
+                    ..
+                },
+            ) => match self.check_health().await {
+                Ok(health) if start_ledger > health.latest_ledger => Ok(LedgerPoll::CaughtUp {
+                    latest_ledger: health.latest_ledger,
+                }),
+                _ => Err(err),
+            },
+            Err(err) => Err(err),
+        }
     }
 
     async fn fetch_ledgers_internal(
@@ -854,32 +1628,137 @@ impl StellarRpcClient {
             "id": 1,
             "params": params
         });
-        let response = inject_trace_context(
-            self.client
-                .post(&self.rpc_url)
-                .json(&payload)
-        )
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self
+            .send_once(self.client.post(&self.rpc_url).json(&payload))
+            .await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
-        let json_response: JsonRpcResponse<GetLedgersResult> = response
-            .json()
-            .await
-            .map_err(|e| RpcError::ParseError(e.to_string()))?;
+        let json_response: JsonRpcResponse<GetLedgersResult> = parse_json(response).await?;
         if let Some(error) = json_response.error {
-            return Err(RpcError::ServerError {
-                status: 500,
-                message: format!("RPC error: {} (code: {})", error.message, error.code),
-            });
+            return Err(error.into());
         }
         json_response
             .result
             .ok_or_else(|| RpcError::ParseError("No result in getLedgers response".to_string()))
     }
 
+    /// Fetch transactions with result metadata via RPC `getTransactions`.
+    ///
+    /// Pages forward from `start_ledger`, or from `cursor` when continuing a
+    /// previous page, which makes it suited to high-throughput backfill.
+    pub async fn fetch_transactions(
+        &self,
+        start_ledger: Option<u64>,
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> Result<GetTransactionsResult, RpcError> {
+        if self.mock_mode {
+            let start = if let Some(c) = cursor {
+                c.parse::<u64>().ok().map_or_else(
+                    || start_ledger.unwrap_or(super::mock_stellar::MOCK_OLDEST_LEDGER),
+                    |v| v.saturating_add(1),
+                )
+            } else {
+                start_ledger.unwrap_or(super::mock_stellar::MOCK_OLDEST_LEDGER)
+            };
+            return Ok(super::mock_stellar::mock_get_transactions(start, limit));
+        }
+
+        let result = self
+            .execute_with_retry("fetch_transactions", || {
+                self.fetch_transactions_internal(start_ledger, limit, cursor)
+            })
+            .await;
+
+        result.inspect_err(|e| record_error("fetch_transactions", e))
+    }
+
+    async fn fetch_transactions_internal(
+        &self,
+        start_ledger: Option<u64>,
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> Result<GetTransactionsResult, RpcError> {
+        let mut pagination = serde_json::Map::new();
+        pagination.insert("limit".to_string(), json!(limit));
+        let mut params = serde_json::Map::new();
+        if let Some(c) = cursor {
+            pagination.insert("cursor".to_string(), json!(c));
+        } else if let Some(start) = start_ledger {
+            params.insert("startLedger".to_string(), json!(start));
+        }
+        params.insert("pagination".to_string(), json!(pagination));
+        let payload = json!({
+            "jsonrpc": "2.0",
+            "method": "getTransactions",
+            "id": 1,
+            "params": params
+        });
+        let response = self
+            .send_once(self.client.post(&self.rpc_url).json(&payload))
+            .await?;
+        if !response.status().is_success() {
+            return Err(map_response_error(response).await);
+        }
+        let json_response: JsonRpcResponse<GetTransactionsResult> = parse_json(response).await?;
+        if let Some(error) = json_response.error {
+            return Err(error.into());
+        }
+        json_response.result.ok_or_else(|| {
+            RpcError::ParseError("No result in getTransactions response".to_string())
+        })
+    }
+
+    /// Send several JSON-RPC calls in one batch request.
+    ///
+    /// Responses are returned in the order of `calls`. An error from one
+    /// call is reported on its own item and does not fail the batch.
+    pub async fn rpc_batch(
+        &self,
+        calls: Vec<JsonRpcCall>,
+    ) -> Result<Vec<JsonRpcResponse<serde_json::Value>>, RpcError> {
+        if calls.is_empty() {
+            return Ok(Vec::new());
+        }
+        if self.mock_mode {
+            return Ok(super::mock_stellar::mock_rpc_batch(&calls));
+        }
+
+        let result = self
+            .execute_with_retry("rpc_batch", || self.rpc_batch_internal(&calls))
+            .await;
+
+        result.inspect_err(|e| record_error("rpc_batch", e))
+    }
+
+    async fn rpc_batch_internal(
+        &self,
+        calls: &[JsonRpcCall],
+    ) -> Result<Vec<JsonRpcResponse<serde_json::Value>>, RpcError> {
+        let response = self
+            .send_once(self.client.post(&self.rpc_url).json(calls))
+            .await?;
+        if !response.status().is_success() {
+            return Err(map_response_error(response).await);
+        }
+        let body: serde_json::Value = parse_json(response).await?;
+
+        match body {
+            serde_json::Value::Array(items) => Ok(correlate_batch_responses(calls, items)),
+            // A batch the server rejects as a whole comes back as a single error object
+            other => match other
+                .get("error")
+                .and_then(|e| serde_json::from_value::<JsonRpcError>(e.clone()).ok())
+            {
+                Some(error) => Err(error.into()),
+                None => Err(RpcError::ParseError(
+                    "Expected an array in batch response".to_string(),
+                )),
+            },
+        }
+    }
+
     /// Fetch recent payments
     pub async fn fetch_payments(
         &self,
@@ -887,17 +1766,17 @@ impl StellarRpcClient {
         cursor: Option<&str>,
     ) -> Result<Vec<Payment>, RpcError> {
         if self.mock_mode {
-            return Ok(super::mock_stellar::mock_payments(limit));
+            return Ok(self.mock_fixtures.payments(limit));
         }
 
         info!("Fetching {} payments from Horizon API", limit);
 
         let result = self
-            .execute_with_retry(|| self.fetch_payments_internal(limit, cursor))
+            .execute_with_retry("fetch_payments", || {
+                self.fetch_payments_internal(limit, cursor)
+            })
             .await;
-        result.inspect_err(|e| {
-            metrics::record_rpc_error(e.error_type_label(), "stellar");
-        })
+        result.inspect_err(|e| record_error("fetch_payments", e))
     }
 
     async fn fetch_payments_internal(
@@ -907,22 +1786,13 @@ impl StellarRpcClient {
     ) -> Result<Vec<Payment>, RpcError> {
         let mut url = format!("{}/payments?order=desc&limit={}", self.horizon_url, limit);
         if let Some(c) = cursor {
-            let _ = write!(url, "&cursor={c}");
+            let _ = write!(url, "&cursor={}", urlencoding::encode(c));
         }
-        let response = inject_trace_context(
-            self.client
-                .get(&url)
-        )
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send_once(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
-        let horizon_response: HorizonResponse<Payment> = response
-            .json()
-            .await
-            .map_err(|e| RpcError::ParseError(e.to_string()))?;
+        let horizon_response: HorizonResponse<Payment> = parse_json(response).await?;
         Ok(horizon_response
             .embedded
             .map(|e| e.records)
@@ -936,16 +1806,14 @@ impl StellarRpcClient {
         cursor: Option<&str>,
     ) -> Result<Vec<Trade>, RpcError> {
         if self.mock_mode {
-            return Ok(super::mock_stellar::mock_trades(limit));
+            return Ok(self.mock_fixtures.trades(limit));
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_trades_internal(limit, cursor))
+            .execute_with_retry("fetch_trades", || self.fetch_trades_internal(limit, cursor))
             .await;
 
-        result.inspect_err(|e| {
-            metrics::record_rpc_error(e.error_type_label(), "stellar");
-        })
+        result.inspect_err(|e| record_error("fetch_trades", e))
     }
 
     async fn fetch_trades_internal(
@@ -955,22 +1823,63 @@ impl StellarRpcClient {
     ) -> Result<Vec<Trade>, RpcError> {
         let mut url = format!("{}/trades?order=desc&limit={}", self.horizon_url, limit);
         if let Some(c) = cursor {
-            let _ = write!(url, "&cursor={c}");
+            let _ = write!(url, "&cursor={}", urlencoding::encode(c));
         }
-        let response = inject_trace_context(
-            self.client
-                .get(&url)
-        )
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send_once(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
-        let horizon_response: HorizonResponse<Trade> = response
-            .json()
-            .await
+        let horizon_response: HorizonResponse<Trade> = parse_json(response).await?;
+        Ok(horizon_response
+            .embedded
+            .map(|e| e.records)
+            .unwrap_or_default())
+    }
+
+    /// Fetch recent trades between `base` and `counter`, newest first
+    pub async fn fetch_trades_for_pair(
+        &self,
+        base: &Asset,
+        counter: &Asset,
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> Result<Vec<Trade>, RpcError> {
+        if self.mock_mode {
+            return Ok(self.mock_fixtures.trades_for_pair(base, counter, limit));
+        }
+
+        let result = self
+            .execute_with_retry("fetch_trades_for_pair", || {
+                self.fetch_trades_for_pair_internal(base, counter, limit, cursor)
+            })
+            .await;
+
+        result.inspect_err(|e| record_error("fetch_trades_for_pair", e))
+    }
+
+    async fn fetch_trades_for_pair_internal(
+        &self,
+        base: &Asset,
+        counter: &Asset,
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> Result<Vec<Trade>, RpcError> {
+        let base_params = Self::asset_to_query_params("base", base)
             .map_err(|e| RpcError::ParseError(e.to_string()))?;
+        let counter_params = Self::asset_to_query_params("counter", counter)
+            .map_err(|e| RpcError::ParseError(e.to_string()))?;
+        let mut url = format!(
+            "{}/trades?{}&{}&order=desc&limit={}",
+            self.horizon_url, base_params, counter_params, limit
+        );
+        if let Some(c) = cursor {
+            let _ = write!(url, "&cursor={}", urlencoding::encode(c));
+        }
+        let response = self.send_once(self.client.get(&url)).await?;
+        if !response.status().is_success() {
+            return Err(map_response_error(response).await);
+        }
+        let horizon_response: HorizonResponse<Trade> = parse_json(response).await?;
         Ok(horizon_response
             .embedded
             .map(|e| e.records)
@@ -985,21 +1894,23 @@ impl StellarRpcClient {
         limit: u32,
     ) -> Result<OrderBook, RpcError> {
         if self.mock_mode {
-            return Ok(super::mock_stellar::mock_order_book(
-                selling_asset,
-                buying_asset,
-            ));
+            return Ok(self.mock_fixtures.order_book(selling_asset, buying_asset));
         }
 
+        let key = format!(
+            "fetch_order_book:{}:{}:{limit}",
+            coalesce_key(selling_asset),
+            coalesce_key(buying_asset)
+        );
         let result = self
-            .execute_with_retry(|| {
-                self.fetch_order_book_internal(selling_asset, buying_asset, limit)
+            .coalesced(key, || {
+                self.execute_with_retry("fetch_order_book", || {
+                    self.fetch_order_book_internal(selling_asset, buying_asset, limit)
+                })
             })
             .await;
 
-        result.inspect_err(|e| {
-            metrics::record_rpc_error(e.error_type_label(), "stellar");
-        })
+        result.inspect_err(|e| record_error("fetch_order_book", e))
     }
 
     async fn fetch_order_book_internal(
@@ -1016,34 +1927,96 @@ impl StellarRpcClient {
             "{}/order_book?{}&{}&limit={}",
             self.horizon_url, selling_params, buying_params, limit
         );
-        let response = inject_trace_context(
-            self.client
-                .get(&url)
-        )
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send_once(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
-        response
-            .json()
-            .await
-            .map_err(|e| RpcError::ParseError(e.to_string()))
+        parse_json(response).await
+    }
+
+    /// Fetch resting offers for a specific account
+    pub async fn fetch_offers_for_account(
+        &self,
+        account_id: &str,
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> Result<Vec<Offer>, RpcError> {
+        if self.mock_mode {
+            return Ok(super::mock_stellar::mock_offers(account_id, limit));
+        }
+
+        let result = self
+            .execute_with_retry("fetch_offers_for_account", || {
+                self.fetch_offers_for_account_internal(account_id, limit, cursor)
+            })
+            .await;
+
+        result.inspect_err(|e| record_error("fetch_offers_for_account", e))
+    }
+
+    async fn fetch_offers_for_account_internal(
+        &self,
+        account_id: &str,
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> Result<Vec<Offer>, RpcError> {
+        let mut url = format!(
+            "{}/accounts/{}/offers?order=desc&limit={}",
+            self.horizon_url, account_id, limit
+        );
+        if let Some(c) = cursor {
+            let _ = write!(url, "&cursor={}", urlencoding::encode(c));
+        }
+        let response = self.send_once(self.client.get(&url)).await?;
+        if !response.status().is_success() {
+            return Err(map_response_error(response).await);
+        }
+        let horizon_response: HorizonResponse<Offer> = parse_json(response).await?;
+        Ok(horizon_response
+            .embedded
+            .map(|e| e.records)
+            .unwrap_or_default())
+    }
+
+    /// Fetch an account's sequence, subentry count and balances
+    pub async fn fetch_account(&self, account_id: &str) -> Result<AccountInfo, RpcError> {
+        if self.mock_mode {
+            return Ok(self.mock_fixtures.account(account_id));
+        }
+
+        info!("Fetching account {} from Horizon API", account_id);
+
+        let result = self
+            .execute_with_retry("fetch_account", || self.fetch_account_internal(account_id))
+            .await;
+
+        result.inspect_err(|e| record_error("fetch_account", e))
+    }
+
+    async fn fetch_account_internal(&self, account_id: &str) -> Result<AccountInfo, RpcError> {
+        let url = format!("{}/accounts/{}", self.horizon_url, account_id);
+        let response = self.send_once(self.client.get(&url)).await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(RpcError::NotFound(format!("account {account_id}")));
+        }
+        if !response.status().is_success() {
+            return Err(map_response_error(response).await);
+        }
+        parse_json::<AccountInfo>(response).await
     }
 
     pub async fn fetch_payments_for_ledger(&self, sequence: u64) -> Result<Vec<Payment>, RpcError> {
         if self.mock_mode {
-            return Ok(super::mock_stellar::mock_payments(5));
+            return Ok(self.mock_fixtures.payments(5));
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_payments_for_ledger_internal(sequence))
+            .execute_with_retry("fetch_payments_for_ledger", || {
+                self.fetch_payments_for_ledger_internal(sequence)
+            })
             .await;
 
-        result.inspect_err(|e| {
-            metrics::record_rpc_error(e.error_type_label(), "stellar");
-        })
+        result.inspect_err(|e| record_error("fetch_payments_for_ledger", e))
     }
 
     async fn fetch_payments_for_ledger_internal(
@@ -1054,20 +2027,11 @@ impl StellarRpcClient {
             "{}/ledgers/{}/payments?limit=200",
             self.horizon_url, sequence
         );
-        let response = inject_trace_context(
-            self.client
-                .get(&url)
-        )
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send_once(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
-        let horizon_response: HorizonResponse<Payment> = response
-            .json()
-            .await
-            .map_err(|e| RpcError::ParseError(e.to_string()))?;
+        let horizon_response: HorizonResponse<Payment> = parse_json(response).await?;
         Ok(horizon_response
             .embedded
             .map(|e| e.records)
@@ -1080,16 +2044,16 @@ impl StellarRpcClient {
         sequence: u64,
     ) -> Result<Vec<HorizonTransaction>, RpcError> {
         if self.mock_mode {
-            return Ok(super::mock_stellar::mock_transactions(5, sequence));
+            return Ok(self.mock_fixtures.transactions(5, sequence));
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_transactions_for_ledger_internal(sequence))
+            .execute_with_retry("fetch_transactions_for_ledger", || {
+                self.fetch_transactions_for_ledger_internal(sequence)
+            })
             .await;
 
-        result.inspect_err(|e| {
-            metrics::record_rpc_error(e.error_type_label(), "stellar");
-        })
+        result.inspect_err(|e| record_error("fetch_transactions_for_ledger", e))
     }
 
     async fn fetch_transactions_for_ledger_internal(
@@ -1100,20 +2064,11 @@ impl StellarRpcClient {
             "{}/ledgers/{}/transactions?limit=200&include_failed=true",
             self.horizon_url, sequence
         );
-        let response = inject_trace_context(
-            self.client
-                .get(&url)
-        )
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send_once(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
-        let horizon_response: HorizonResponse<HorizonTransaction> = response
-            .json()
-            .await
-            .map_err(|e| RpcError::ParseError(e.to_string()))?;
+        let horizon_response: HorizonResponse<HorizonTransaction> = parse_json(response).await?;
         Ok(horizon_response
             .embedded
             .map(|e| e.records)
@@ -1130,12 +2085,12 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_operations_for_ledger_internal(sequence))
+            .execute_with_retry("fetch_operations_for_ledger", || {
+                self.fetch_operations_for_ledger_internal(sequence)
+            })
             .await;
 
-        result.inspect_err(|e| {
-            metrics::record_rpc_error(e.error_type_label(), "stellar");
-        })
+        result.inspect_err(|e| record_error("fetch_operations_for_ledger", e))
     }
 
     async fn fetch_operations_for_ledger_internal(
@@ -1146,20 +2101,11 @@ impl StellarRpcClient {
             "{}/ledgers/{}/operations?limit=200",
             self.horizon_url, sequence
         );
-        let response = inject_trace_context(
-            self.client
-                .get(&url)
-        )
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send_once(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
-        let horizon_response: HorizonResponse<HorizonOperation> = response
-            .json()
-            .await
-            .map_err(|e| RpcError::ParseError(e.to_string()))?;
+        let horizon_response: HorizonResponse<HorizonOperation> = parse_json(response).await?;
         Ok(horizon_response
             .embedded
             .map(|e| e.records)
@@ -1178,12 +2124,12 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_operation_effects_internal(operation_id))
+            .execute_with_retry("fetch_operation_effects", || {
+                self.fetch_operation_effects_internal(operation_id)
+            })
             .await;
 
-        result.inspect_err(|e| {
-            metrics::record_rpc_error(e.error_type_label(), "stellar");
-        })
+        result.inspect_err(|e| record_error("fetch_operation_effects", e))
     }
 
     async fn fetch_operation_effects_internal(
@@ -1194,26 +2140,40 @@ impl StellarRpcClient {
             "{}/operations/{}/effects?limit=200",
             self.horizon_url, operation_id
         );
-        let response = inject_trace_context(
-            self.client
-                .get(&url)
-        )
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send_once(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
-        let horizon_response: HorizonResponse<HorizonEffect> = response
-            .json()
-            .await
-            .map_err(|e| RpcError::ParseError(e.to_string()))?;
+        let horizon_response: HorizonResponse<HorizonEffect> = parse_json(response).await?;
         Ok(horizon_response
             .embedded
             .map(|e| e.records)
             .unwrap_or_default())
     }
 
+    /// Fetch payments for several accounts, at most `max_concurrency` at a time.
+    ///
+    /// Each account gets its own result so partial failures stay visible. Every
+    /// call goes through the shared circuit breaker, so once it trips the
+    /// remaining accounts fail fast with `RpcError::CircuitBreakerOpen`.
+    pub async fn fetch_account_payments_bulk(
+        &self,
+        account_ids: &[String],
+        limit: u32,
+        max_concurrency: usize,
+    ) -> HashMap<String, Result<Vec<Payment>, RpcError>> {
+        use futures::stream::{self, StreamExt};
+
+        stream::iter(account_ids)
+            .map(|account_id| async move {
+                let result = self.fetch_account_payments(account_id, limit).await;
+                (account_id.clone(), result)
+            })
+            .buffer_unordered(max_concurrency.max(1))
+            .collect()
+            .await
+    }
+
     /// Fetch payments for a specific account
     pub async fn fetch_account_payments(
         &self,
@@ -1221,16 +2181,16 @@ impl StellarRpcClient {
         limit: u32,
     ) -> Result<Vec<Payment>, RpcError> {
         if self.mock_mode {
-            return Ok(super::mock_stellar::mock_payments(limit));
+            return Ok(self.mock_fixtures.payments(limit));
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_account_payments_internal(account_id, limit))
+            .execute_with_retry("fetch_account_payments", || {
+                self.fetch_account_payments_internal(account_id, limit)
+            })
             .await;
 
-        result.inspect_err(|e| {
-            metrics::record_rpc_error(e.error_type_label(), "stellar");
-        })
+        result.inspect_err(|e| record_error("fetch_account_payments", e))
     }
 
     async fn fetch_account_payments_internal(
@@ -1242,20 +2202,11 @@ impl StellarRpcClient {
             "{}/accounts/{}/payments?order=desc&limit={}",
             self.horizon_url, account_id, limit
         );
-        let response = inject_trace_context(
-            self.client
-                .get(&url)
-        )
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send_once(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
-        let horizon_response: HorizonResponse<Payment> = response
-            .json()
-            .await
-            .map_err(|e| RpcError::ParseError(e.to_string()))?;
+        let horizon_response: HorizonResponse<Payment> = parse_json(response).await?;
         Ok(horizon_response
             .embedded
             .map(|e| e.records)
@@ -1276,7 +2227,7 @@ impl StellarRpcClient {
     pub async fn fetch_all_payments(&self, max_records: Option<u32>) -> Result<Vec<Payment>> {
         if self.mock_mode {
             let limit = self.resolve_max_records(max_records);
-            return Ok(super::mock_stellar::mock_payments(limit));
+            return Ok(self.mock_fixtures.payments(limit));
         }
 
         let max_records = self.resolve_max_records(max_records);
@@ -1358,7 +2309,7 @@ impl StellarRpcClient {
             let limit = max_records
                 .unwrap_or(self.max_total_records)
                 .min(ABSOLUTE_MAX_TOTAL_RECORDS);
-            return Ok(super::mock_stellar::mock_trades(limit));
+            return Ok(self.mock_fixtures.trades(limit));
         }
 
         let max_records = max_records
@@ -1437,7 +2388,7 @@ impl StellarRpcClient {
             let limit = max_records
                 .unwrap_or(self.max_total_records)
                 .min(ABSOLUTE_MAX_TOTAL_RECORDS);
-            return Ok(super::mock_stellar::mock_payments(limit));
+            return Ok(self.mock_fixtures.payments(limit));
         }
 
         let max_records = max_records
@@ -1461,11 +2412,11 @@ impl StellarRpcClient {
             );
 
             if let Some(ref cursor_val) = cursor {
-                let _ = write!(url, "&cursor={cursor_val}");
+                let _ = write!(url, "&cursor={}", urlencoding::encode(cursor_val));
             }
 
             let response = self
-                .retry_request(|| async { inject_trace_context(self.client.get(&url)).send().await })
+                .retry_request(|| self.send_tagged(self.client.get(&url)))
                 .await
                 .context("Failed to fetch account payments page")?;
 
@@ -1555,6 +2506,7 @@ impl StellarRpcClient {
             max_attempts: self.max_retries + 1,
             base_delay_ms: self.initial_backoff.as_millis() as u64,
             max_delay_ms: self.max_backoff.as_millis() as u64,
+            ..RetryConfig::default()
         };
 
         with_retry(
@@ -1566,9 +2518,13 @@ impl StellarRpcClient {
                     .map_err(|_| RpcError::RateLimitError { retry_after: None })?;
 
                 let start_time = Instant::now();
-                let response = request_fn()
-                    .await
-                    .map_err(|e| RpcError::categorize(&e.to_string()))?;
+                let response = request_fn().await.map_err(|e| {
+                    if e.is_timeout() || e.is_connect() {
+                        self.send_error(&e)
+                    } else {
+                        RpcError::categorize(&e.to_string())
+                    }
+                })?;
                 let elapsed = start_time.elapsed().as_millis();
                 let status = response.status();
                 let headers = response.headers().clone();
@@ -1617,6 +2573,7 @@ impl StellarRpcClient {
             },
             retry_config,
             self.circuit_breaker.clone(),
+            &self.retry_budget,
         )
         .await
         .map_err(|e| {
@@ -1640,12 +2597,12 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_liquidity_pools_internal(limit, cursor))
+            .execute_with_retry("fetch_liquidity_pools", || {
+                self.fetch_liquidity_pools_internal(limit, cursor)
+            })
             .await;
 
-        result.inspect_err(|e| {
-            metrics::record_rpc_error(e.error_type_label(), "stellar");
-        })
+        result.inspect_err(|e| record_error("fetch_liquidity_pools", e))
     }
 
     async fn fetch_liquidity_pools_internal(
@@ -1659,22 +2616,13 @@ impl StellarRpcClient {
         );
 
         if let Some(c) = cursor {
-            let _ = write!(url, "&cursor={c}");
+            let _ = write!(url, "&cursor={}", urlencoding::encode(c));
         }
-        let response = inject_trace_context(
-            self.client
-                .get(&url)
-        )
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send_once(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
-        let horizon_response: HorizonResponse<HorizonLiquidityPool> = response
-            .json()
-            .await
-            .map_err(|e| RpcError::ParseError(e.to_string()))?;
+        let horizon_response: HorizonResponse<HorizonLiquidityPool> = parse_json(response).await?;
         Ok(horizon_response
             .embedded
             .map(|e| e.records)
@@ -1696,12 +2644,12 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_liquidity_pool_internal(pool_id))
+            .execute_with_retry("fetch_liquidity_pool", || {
+                self.fetch_liquidity_pool_internal(pool_id)
+            })
             .await;
 
-        result.inspect_err(|e| {
-            metrics::record_rpc_error(e.error_type_label(), "stellar");
-        })
+        result.inspect_err(|e| record_error("fetch_liquidity_pool", e))
     }
 
     async fn fetch_liquidity_pool_internal(
@@ -1709,20 +2657,11 @@ impl StellarRpcClient {
         pool_id: &str,
     ) -> Result<HorizonLiquidityPool, RpcError> {
         let url = format!("{}/liquidity_pools/{}", self.horizon_url, pool_id);
-        let response = inject_trace_context(
-            self.client
-                .get(&url)
-        )
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send_once(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
-        response
-            .json()
-            .await
-            .map_err(|e| RpcError::ParseError(e.to_string()))
+        parse_json(response).await
     }
 
     /// Fetch trades for a specific liquidity pool
@@ -1736,12 +2675,12 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_pool_trades_internal(pool_id, limit))
+            .execute_with_retry("fetch_pool_trades", || {
+                self.fetch_pool_trades_internal(pool_id, limit)
+            })
             .await;
 
-        result.inspect_err(|e| {
-            metrics::record_rpc_error(e.error_type_label(), "stellar");
-        })
+        result.inspect_err(|e| record_error("fetch_pool_trades", e))
     }
 
     async fn fetch_pool_trades_internal(
@@ -1753,20 +2692,11 @@ impl StellarRpcClient {
             "{}/liquidity_pools/{}/trades?order=desc&limit={}",
             self.horizon_url, pool_id, limit
         );
-        let response = inject_trace_context(
-            self.client
-                .get(&url)
-        )
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send_once(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
-        let horizon_response: HorizonResponse<Trade> = response
-            .json()
-            .await
-            .map_err(|e| RpcError::ParseError(e.to_string()))?;
+        let horizon_response: HorizonResponse<Trade> = parse_json(response).await?;
         Ok(horizon_response
             .embedded
             .map(|e| e.records)
@@ -1784,12 +2714,12 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_assets_internal(limit, rating_sort))
+            .execute_with_retry("fetch_assets", || {
+                self.fetch_assets_internal(limit, rating_sort)
+            })
             .await;
 
-        result.inspect_err(|e| {
-            metrics::record_rpc_error(e.error_type_label(), "stellar");
-        })
+        result.inspect_err(|e| record_error("fetch_assets", e))
     }
 
     async fn fetch_assets_internal(
@@ -1803,20 +2733,11 @@ impl StellarRpcClient {
         } else {
             url.push_str("&order=desc");
         }
-        let response = inject_trace_context(
-            self.client
-                .get(&url)
-        )
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send_once(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
-        let horizon_response: HorizonResponse<HorizonAsset> = response
-            .json()
-            .await
-            .map_err(|e| RpcError::ParseError(e.to_string()))?;
+        let horizon_response: HorizonResponse<HorizonAsset> = parse_json(response).await?;
         Ok(horizon_response
             .embedded
             .map(|e| e.records)
@@ -1867,6 +2788,41 @@ mod tests {
         assert!(health.latest_ledger > 0);
     }
 
+    #[tokio::test]
+    async fn test_mock_fixtures_override_payments() {
+        let mut payment = mock_stellar::mock_payments(1).remove(0);
+        payment.id = "fixture-payment".to_string();
+        payment.amount = "42.0000000".to_string();
+        let fixtures = MockFixtures {
+            payments: Some(vec![payment]),
+            ..MockFixtures::default()
+        };
+        let client = StellarRpcClient::new_with_defaults(true).with_mock_fixtures(fixtures);
+
+        let payments = client.fetch_payments(10, None).await.unwrap();
+        assert_eq!(payments.len(), 1);
+        assert_eq!(payments[0].id, "fixture-payment");
+        assert_eq!(payments[0].amount, "42.0000000");
+
+        // Methods without an override keep the generated defaults
+        let trades = client.fetch_trades(3, None).await.unwrap();
+        assert_eq!(trades.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_mock_fetch_account_payments_bulk() {
+        let client = StellarRpcClient::new_with_defaults(true);
+        let accounts: Vec<String> = (0..5).map(|i| format!("GACCOUNT{i}")).collect();
+
+        let results = client.fetch_account_payments_bulk(&accounts, 3, 2).await;
+
+        assert_eq!(results.len(), accounts.len());
+        for account in &accounts {
+            let payments = results[account].as_ref().unwrap();
+            assert_eq!(payments.len(), 3);
+        }
+    }
+
     #[tokio::test]
     async fn test_mock_fetch_ledger() {
         let client = StellarRpcClient::new_with_defaults(true);
@@ -1876,6 +2832,157 @@ mod tests {
         assert!(!ledger.hash.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_mock_fetch_latest_rpc_ledger() {
+        let client = StellarRpcClient::new_with_defaults(true);
+        let ledger = client.fetch_latest_rpc_ledger().await.unwrap();
+
+        assert_eq!(ledger.sequence, mock_stellar::MOCK_LATEST_LEDGER);
+        assert!(!ledger.hash.is_empty());
+        assert!(ledger.protocol_version > 0);
+    }
+
+    #[test]
+    fn test_rpc_latest_ledger_deserialization() {
+        let json = r#"{
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {
+                "id": "c73c5eac58a441d4eb733c35253ae85f783e018f7be5ef974258fed067aabb36",
+                "protocolVersion": 21,
+                "sequence": 2539605
+            }
+        }"#;
+
+        let response: JsonRpcResponse<RpcLatestLedger> = serde_json::from_str(json).unwrap();
+        let ledger = response.result.unwrap();
+        assert_eq!(ledger.sequence, 2_539_605);
+        assert_eq!(ledger.protocol_version, 21);
+        assert!(ledger.hash.starts_with("c73c5eac"));
+    }
+
+    #[tokio::test]
+    async fn test_mock_fetch_transactions_pages_with_cursor() {
+        let client = StellarRpcClient::new_with_defaults(true);
+
+        let first = client
+            .fetch_transactions(Some(mock_stellar::MOCK_OLDEST_LEDGER), 5, None)
+            .await
+            .unwrap();
+        assert_eq!(first.transactions.len(), 5);
+        assert_eq!(
+            first.transactions[0].ledger,
+            mock_stellar::MOCK_OLDEST_LEDGER
+        );
+        assert!(first
+            .transactions
+            .iter()
+            .all(|tx| tx.result_meta_xdr.is_some() && tx.application_order >= 1));
+
+        let second = client
+            .fetch_transactions(None, 5, first.cursor.as_deref())
+            .await
+            .unwrap();
+        assert_eq!(
+            second.transactions[0].ledger,
+            first.transactions.last().unwrap().ledger + 1
+        );
+    }
+
+    #[test]
+    fn test_get_transactions_deserialization() {
+        let json = r#"{
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {
+                "transactions": [{
+                    "status": "SUCCESS",
+                    "applicationOrder": 1,
+                    "feeBump": false,
+                    "envelopeXdr": "AAAAAgAAAAA=",
+                    "resultXdr": "AAAAAAAAAGQ=",
+                    "resultMetaXdr": "AAAAAwAAAAA=",
+                    "ledger": 1888539,
+                    "createdAt": 1717166042,
+                    "txHash": "d2e3b1f0"
+                }],
+                "latestLedger": 1888542,
+                "oldestLedger": 1871263,
+                "cursor": "8111217537191937"
+            }
+        }"#;
+
+        let response: JsonRpcResponse<GetTransactionsResult> = serde_json::from_str(json).unwrap();
+        let result = response.result.unwrap();
+        assert_eq!(result.transactions.len(), 1);
+        let tx = &result.transactions[0];
+        assert_eq!(tx.tx_hash, "d2e3b1f0");
+        assert_eq!(tx.status, "SUCCESS");
+        assert_eq!(tx.application_order, 1);
+        assert_eq!(tx.ledger, 1_888_539);
+        assert_eq!(result.cursor.as_deref(), Some("8111217537191937"));
+    }
+
+    #[tokio::test]
+    async fn test_mock_rpc_batch_reports_errors_per_call() {
+        let client = StellarRpcClient::new_with_defaults(true);
+
+        let responses = client
+            .rpc_batch(vec![
+                JsonRpcCall::new(1, "getHealth", None),
+                JsonRpcCall::new(2, "getNoSuchMethod", None),
+                JsonRpcCall::new(
+                    3,
+                    "getLedgers",
+                    Some(json!({
+                        "startLedger": mock_stellar::MOCK_OLDEST_LEDGER,
+                        "pagination": { "limit": 2 }
+                    })),
+                ),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            responses.iter().map(|r| r.id).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(responses[0].result.as_ref().unwrap()["status"], "healthy");
+        assert!(responses[0].error.is_none());
+        assert_eq!(responses[1].error.as_ref().unwrap().code, -32601);
+        assert!(responses[1].result.is_none());
+        let ledgers: GetLedgersResult =
+            serde_json::from_value(responses[2].result.clone().unwrap()).unwrap();
+        assert_eq!(ledgers.ledgers.len(), 2);
+    }
+
+    #[test]
+    fn test_correlate_batch_responses_by_id() {
+        let calls = vec![
+            JsonRpcCall::new(7, "getHealth", None),
+            JsonRpcCall::new(8, "getLatestLedger", None),
+            JsonRpcCall::new(9, "getLedgers", None),
+        ];
+        // Out of order, with one error and one call missing entirely
+        let items = vec![
+            json!({"jsonrpc": "2.0", "id": 8, "result": {"sequence": 42}}),
+            json!({"jsonrpc": "2.0", "id": 7, "error": {"code": -32600, "message": "bad"}}),
+        ];
+
+        let responses = correlate_batch_responses(&calls, items);
+
+        assert_eq!(
+            responses.iter().map(|r| r.id).collect::<Vec<_>>(),
+            vec![7, 8, 9]
+        );
+        assert_eq!(responses[0].error.as_ref().unwrap().code, -32600);
+        assert_eq!(responses[1].result.as_ref().unwrap()["sequence"], 42);
+        assert_eq!(
+            responses[2].error.as_ref().unwrap().code,
+            JSON_RPC_INTERNAL_ERROR
+        );
+    }
+
     #[tokio::test]
     async fn test_mock_fetch_payments() {
         let client = StellarRpcClient::new_with_defaults(true);
@@ -1894,6 +3001,124 @@ mod tests {
         assert!(!trades[0].id.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_mock_fetch_trades_for_pair() {
+        let client = StellarRpcClient::new_with_defaults(true);
+        let base = Asset::parse("USDC:GBISSUER").unwrap();
+        let counter = Asset::parse("native").unwrap();
+
+        let trades = client
+            .fetch_trades_for_pair(&base, &counter, 4, None)
+            .await
+            .unwrap();
+
+        assert_eq!(trades.len(), 4);
+        for trade in &trades {
+            assert_eq!(trade.base_asset_code.as_deref(), Some("USDC"));
+            assert_eq!(trade.base_asset_issuer.as_deref(), Some("GBISSUER"));
+            assert_eq!(trade.counter_asset_type, "native");
+            assert_eq!(trade.counter_asset_code, None);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_coalescing_shares_one_upstream_call_for_concurrent_ledger_fetches() {
+        use crate::rpc::test_server::{ScriptedResponse, TestServer};
+        use axum::http::StatusCode;
+
+        let server = TestServer::start([ScriptedResponse::json(
+            StatusCode::OK,
+            r#"{"_embedded": {"records": [{
+                "sequence": 2539605, "hash": "abc", "previous_hash": "def",
+                "transaction_count": 1, "operation_count": 2,
+                "closed_at": "2026-01-01T00:00:00Z", "total_coins": "100",
+                "fee_pool": "1", "base_fee": 100, "base_reserve": "0.5"
+            }]}}"#,
+        )
+        .with_delay(Duration::from_millis(100))])
+        .await;
+        let client = Arc::new(
+            server
+                .client(&crate::rpc::CircuitBreakerConfig::default())
+                .with_request_coalescing(true),
+        );
+
+        let fetches: Vec<_> = (0..20)
+            .map(|_| {
+                let client = client.clone();
+                tokio::spawn(async move { client.fetch_latest_ledger().await })
+            })
+            .collect();
+        for fetch in fetches {
+            assert_eq!(fetch.await.unwrap().unwrap().sequence, 2539605);
+        }
+        assert_eq!(server.hits(), 1);
+
+        // Without coalescing every call goes upstream
+        let client = server
+            .client(&crate::rpc::CircuitBreakerConfig::default())
+            .with_request_coalescing(false);
+        let (a, b) = tokio::join!(client.fetch_latest_ledger(), client.fetch_latest_ledger());
+        assert!(a.is_ok() && b.is_ok());
+        assert_eq!(server.hits(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_trades_for_pair_sends_both_assets() {
+        use crate::rpc::test_server::{ScriptedResponse, TestServer};
+        use axum::http::StatusCode;
+
+        let server = TestServer::start([ScriptedResponse::json(
+            StatusCode::OK,
+            r#"{"_embedded": {"records": []}}"#,
+        )])
+        .await;
+        let client = server.client(&crate::rpc::CircuitBreakerConfig::default());
+        let base = Asset::parse("USDC:GBISSUER").unwrap();
+        let counter = Asset::parse("native").unwrap();
+
+        let trades = client
+            .fetch_trades_for_pair(&base, &counter, 10, Some("123-1&limit=200"))
+            .await
+            .unwrap();
+        assert!(trades.is_empty());
+        assert_eq!(server.hits(), 1);
+        let uri = &server.request_uris()[0];
+        assert_eq!(uri.path(), "/trades");
+        assert_eq!(
+            uri.query(),
+            Some(
+                "base_asset_type=credit_alphanum4&base_asset_code=USDC&base_asset_issuer=GBISSUER\
+                 &counter_asset_type=native&order=desc&limit=10&cursor=123-1%26limit%3D200"
+            )
+        );
+
+        let missing_issuer = Asset {
+            asset_type: "credit_alphanum4".to_string(),
+            asset_code: Some("USDC".to_string()),
+            asset_issuer: None,
+        };
+        let err = client
+            .fetch_trades_for_pair(&missing_issuer, &counter, 10, None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, RpcError::ParseError(_)));
+        assert_eq!(server.hits(), 1);
+    }
+
+    #[test]
+    fn test_parse_asset_ids() {
+        let usdc = Asset::parse("USDC:GBISSUER").unwrap();
+        assert_eq!(usdc.asset_type, "credit_alphanum4");
+        assert_eq!(
+            Asset::parse("LONGCODE:GBISSUER").unwrap().asset_type,
+            "credit_alphanum12"
+        );
+        assert_eq!(Asset::parse("NATIVE").unwrap().asset_type, "native");
+        assert!(Asset::parse("USDC").is_none());
+        assert!(Asset::parse(":GBISSUER").is_none());
+    }
+
     #[tokio::test]
     async fn test_mock_fetch_order_book() {
         let client = StellarRpcClient::new_with_defaults(true);
@@ -1919,6 +3144,142 @@ mod tests {
         assert!(!order_book.asks.is_empty());
     }
 
+    fn mock_usdc_book() -> OrderBook {
+        let selling = Asset {
+            asset_type: "native".to_string(),
+            asset_code: None,
+            asset_issuer: None,
+        };
+        let buying = Asset {
+            asset_type: "credit_alphanum4".to_string(),
+            asset_code: Some("USDC".to_string()),
+            asset_issuer: Some("GBXXXXXXX".to_string()),
+        };
+        mock_stellar::mock_order_book(&selling, &buying)
+    }
+
+    #[test]
+    fn test_order_book_spread() {
+        let spread = mock_usdc_book().spread().unwrap();
+
+        assert!((spread.best_bid - 0.995).abs() < 1e-9);
+        assert!((spread.best_ask - 1.005).abs() < 1e-9);
+        assert!((spread.absolute - 0.01).abs() < 1e-9);
+        assert!((spread.percent - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_order_book_depth_within() {
+        let book = mock_usdc_book();
+
+        let tight = book.depth_within(0.75);
+        assert!((tight.bid_amount - 1000.0).abs() < 1e-9);
+        assert!((tight.ask_amount - 1200.0).abs() < 1e-9);
+
+        let wide = book.depth_within(2.0);
+        assert!((wide.bid_amount - 8500.0).abs() < 1e-9);
+        assert!((wide.ask_amount - 8700.0).abs() < 1e-9);
+        assert!((wide.total() - 17_200.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_order_book_one_sided_has_no_spread_or_depth() {
+        let mut book = mock_usdc_book();
+        book.asks.clear();
+
+        assert!(book.spread().is_none());
+        assert_eq!(book.depth_within(5.0), OrderBookDepth::default());
+    }
+
+    #[tokio::test]
+    async fn test_mock_fetch_offers_for_account() {
+        let client = StellarRpcClient::new_with_defaults(true);
+        let account_id = "GXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX";
+        let offers = client
+            .fetch_offers_for_account(account_id, 4, None)
+            .await
+            .unwrap();
+
+        assert_eq!(offers.len(), 4);
+        assert!(offers.iter().all(|o| o.seller == account_id));
+        assert_eq!(offers[0].selling.asset_type, "native");
+        assert_eq!(offers[0].buying.asset_code.as_deref(), Some("USDC"));
+        assert!(offers[0].price.d > 0);
+    }
+
+    #[test]
+    fn test_offer_deserialization_uses_price_r() {
+        let json = r#"{
+            "id": "165561423",
+            "paging_token": "165561423",
+            "seller": "GSELLER",
+            "selling": { "asset_type": "native" },
+            "buying": {
+                "asset_type": "credit_alphanum4",
+                "asset_code": "USDC",
+                "asset_issuer": "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN"
+            },
+            "amount": "150.0000000",
+            "price_r": { "n": 1, "d": 10 },
+            "price": "0.1000000",
+            "last_modified_ledger": 51583040
+        }"#;
+
+        let offer: Offer = serde_json::from_str(json).unwrap();
+        assert_eq!(offer.price.n, 1);
+        assert_eq!(offer.price.d, 10);
+        assert!(offer.selling.asset_code.is_none());
+        assert_eq!(offer.last_modified_ledger, 51_583_040);
+    }
+
+    fn horizon_transaction_json(fee_charged: &str, max_fee: &str) -> String {
+        format!(
+            r#"{{
+                "id": "tx1",
+                "hash": "tx1",
+                "ledger": 51583040,
+                "created_at": "2026-01-01T00:00:00Z",
+                "source_account": "GSOURCE",
+                "fee_charged": {fee_charged},
+                "max_fee": {max_fee},
+                "operation_count": 1,
+                "successful": true,
+                "paging_token": "221371890778112",
+                "inner_transaction": {{
+                    "hash": "inner1",
+                    "max_fee": {max_fee},
+                    "signatures": []
+                }}
+            }}"#
+        )
+    }
+
+    #[test]
+    fn test_horizon_transaction_accepts_numeric_fees() {
+        let tx: HorizonTransaction =
+            serde_json::from_str(&horizon_transaction_json("100", "1000")).unwrap();
+        assert_eq!(tx.fee_charged.as_deref(), Some("100"));
+        assert_eq!(tx.max_fee.as_deref(), Some("1000"));
+        assert_eq!(
+            tx.inner_transaction.unwrap().max_fee.as_deref(),
+            Some("1000")
+        );
+    }
+
+    #[test]
+    fn test_horizon_transaction_accepts_string_fees() {
+        let tx: HorizonTransaction =
+            serde_json::from_str(&horizon_transaction_json(r#""100""#, r#""1000""#)).unwrap();
+        assert_eq!(tx.fee_charged.as_deref(), Some("100"));
+        assert_eq!(tx.max_fee.as_deref(), Some("1000"));
+
+        // Missing and null fees still deserialize as absent
+        let tx: HorizonTransaction =
+            serde_json::from_str(&horizon_transaction_json("null", "null")).unwrap();
+        assert_eq!(tx.fee_charged, None);
+        assert_eq!(tx.max_fee, None);
+    }
+
     #[tokio::test]
     async fn test_mock_fetch_liquidity_pools() {
         let client = StellarRpcClient::new_with_defaults(true);
@@ -1953,8 +3314,9 @@ mod tests {
         let client = StellarRpcClient::new_with_defaults(true);
         let operations = client.fetch_operations_for_ledger(123).await.unwrap();
 
-        assert_eq!(operations.len(), 3);
+        assert_eq!(operations.len(), 4);
         assert_eq!(operations[0].operation_type, "account_merge");
+        assert_eq!(operations[3].operation_type, "create_claimable_balance");
     }
 
     #[tokio::test]
@@ -1985,6 +3347,204 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_mock_poll_ledgers_reports_caught_up_past_latest() {
+        let client = StellarRpcClient::new_with_defaults(true);
+
+        let poll = client
+            .poll_ledgers(mock_stellar::MOCK_LATEST_LEDGER + 1, 5, None)
+            .await
+            .unwrap();
+        let LedgerPoll::CaughtUp { latest_ledger } = poll else {
+            panic!("expected caught up, got {poll:?}");
+        };
+        assert_eq!(latest_ledger, mock_stellar::MOCK_LATEST_LEDGER);
+
+        let poll = client
+            .poll_ledgers(mock_stellar::MOCK_LATEST_LEDGER, 5, None)
+            .await
+            .unwrap();
+        let LedgerPoll::Ledgers(result) = poll else {
+            panic!("expected the latest ledger, got {poll:?}");
+        };
+        assert_eq!(result.ledgers.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_configured_http_timeout_reported_in_error() {
+        use crate::rpc::test_server::{ScriptedResponse, TestServer};
+
+        // A response slower than the client timeout forces a timeout.
+        let server =
+            TestServer::start([ScriptedResponse::healthy().with_delay(Duration::from_secs(5))])
+                .await;
+        let client = server
+            .client(&crate::rpc::CircuitBreakerConfig::default())
+            .with_http_timeout(Duration::from_millis(150));
+        assert_eq!(client.http_timeout(), Duration::from_millis(150));
+
+        let err = client.check_health_internal().await.unwrap_err();
+        match err {
+            RpcError::TimeoutError(msg) => assert!(msg.contains("150ms"), "{msg}"),
+            other => panic!("expected timeout error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_status_to_rpc_error_extracts_horizon_result_codes() {
+        let body = r#"{"title":"Transaction Failed","status":400,"extras":{"result_codes":{"transaction":"tx_failed","operations":["op_no_trust"]}}}"#;
+
+        let err = status_to_rpc_error(reqwest::StatusCode::BAD_REQUEST, body.to_string(), None);
+        let codes = err.result_codes().expect("result codes");
+        assert_eq!(codes.transaction.as_deref(), Some("tx_failed"));
+        assert_eq!(codes.operations, vec!["op_no_trust"]);
+
+        let err = status_to_rpc_error(
+            reqwest::StatusCode::BAD_REQUEST,
+            r#"{"title":"Bad Request","status":400}"#.to_string(),
+            None,
+        );
+        assert!(matches!(err, RpcError::ServerError { status: 400, .. }));
+    }
+
+    #[tokio::test]
+    async fn test_mock_fetch_account() {
+        let client = StellarRpcClient::new_with_defaults(true);
+        let account_id = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+        let account = client.fetch_account(account_id).await.unwrap();
+
+        assert_eq!(account.account_id, account_id);
+        assert!(!account.balances.is_empty());
+        let native = account
+            .balances
+            .iter()
+            .find(|b| b.asset_type == "native")
+            .expect("native balance");
+        assert!(native.limit.is_none());
+        assert!(account
+            .balances
+            .iter()
+            .filter(|b| b.asset_type != "native")
+            .all(|b| b.asset_code.is_some() && b.limit.is_some()));
+    }
+
+    #[tokio::test]
+    async fn test_mock_fetch_ledger_returns_requested_sequence() {
+        let client = StellarRpcClient::new_with_defaults(true);
+        let ledger = client.fetch_ledger(51_565_800).await.unwrap();
+
+        assert_eq!(ledger.sequence, 51_565_800);
+        assert!(!ledger.hash.is_empty());
+
+        #[allow(deprecated)]
+        let renamed = client.fetch_ledger_by_sequence(51_565_800).await.unwrap();
+        assert_eq!(renamed.hash, ledger.hash);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_ledger_maps_404_to_not_found() {
+        use crate::rpc::test_server::{ScriptedResponse, TestServer};
+        use axum::http::StatusCode;
+
+        let server = TestServer::start([ScriptedResponse::json(
+            StatusCode::NOT_FOUND,
+            r#"{"status":404,"title":"Resource Missing"}"#,
+        )])
+        .await;
+        let client = server.client(&crate::rpc::CircuitBreakerConfig::default());
+
+        let err = client.fetch_ledger_internal(999_999_999).await.unwrap_err();
+        match err {
+            RpcError::NotFound(msg) => assert!(msg.contains("999999999"), "{msg}"),
+            other => panic!("expected not found error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_health_records_request_duration() {
+        use crate::rpc::test_server::{ScriptedResponse, TestServer};
+
+        let server = TestServer::start([ScriptedResponse::healthy()]).await;
+        let client = server.client(&crate::rpc::CircuitBreakerConfig::default());
+        let before = metrics::rpc_duration_sample_count("check_health");
+
+        let health = client.check_health().await.unwrap();
+
+        assert_eq!(health.status, "healthy");
+        assert!(metrics::rpc_duration_sample_count("check_health") > before);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_account_maps_404_to_not_found() {
+        use crate::rpc::test_server::{ScriptedResponse, TestServer};
+        use axum::http::StatusCode;
+
+        let server = TestServer::start([ScriptedResponse::json(
+            StatusCode::NOT_FOUND,
+            r#"{"status":404,"title":"Resource Missing"}"#,
+        )])
+        .await;
+        let client = server.client(&crate::rpc::CircuitBreakerConfig::default());
+
+        let err = client.fetch_account_internal("GMISSING").await.unwrap_err();
+        match err {
+            RpcError::NotFound(msg) => assert!(msg.contains("GMISSING"), "{msg}"),
+            other => panic!("expected not found error, got {other:?}"),
+        }
+        assert!(!RpcError::NotFound(String::new()).is_retryable());
+    }
+
+    #[test]
+    fn test_for_network_testnet_urls() {
+        let _guard = crate::lock_env_test();
+        let client = StellarRpcClient::for_network(Network::Testnet, true);
+        if std::env::var("STELLAR_HORIZON_URL_TESTNET").is_err() {
+            assert_eq!(client.horizon_url, "https://horizon-testnet.stellar.org");
+        }
+        if std::env::var("STELLAR_RPC_URL_TESTNET").is_err() {
+            assert_eq!(client.rpc_url, "https://soroban-testnet.stellar.org");
+        }
+        assert_eq!(client.active_network(), &Network::Testnet);
+        assert!(client.is_testnet());
+    }
+
+    #[test]
+    fn test_for_network_mainnet_urls() {
+        let _guard = crate::lock_env_test();
+        if std::env::var("STELLAR_RPC_URL_MAINNET").is_err() {
+            std::env::set_var("STELLAR_RPC_URL_MAINNET", "https://rpc.example.com");
+        }
+        if std::env::var("STELLAR_HORIZON_URL_MAINNET").is_err() {
+            std::env::set_var("STELLAR_HORIZON_URL_MAINNET", "https://horizon.example.com");
+        }
+        let client = StellarRpcClient::for_network(Network::Mainnet, true);
+
+        assert_eq!(
+            client.horizon_url,
+            std::env::var("STELLAR_HORIZON_URL_MAINNET").unwrap()
+        );
+        assert_eq!(
+            client.rpc_url,
+            std::env::var("STELLAR_RPC_URL_MAINNET").unwrap()
+        );
+        assert_eq!(client.active_network().label(), "mainnet");
+        assert!(client.is_mainnet());
+    }
+
+    #[test]
+    fn test_for_network_custom_urls() {
+        let network = Network::Custom {
+            rpc: "http://localhost:8000/soroban/rpc".to_string(),
+            horizon: "http://localhost:8000".to_string(),
+        };
+        let client = StellarRpcClient::for_network(network.clone(), true);
+
+        assert_eq!(client.rpc_url, "http://localhost:8000/soroban/rpc");
+        assert_eq!(client.horizon_url, "http://localhost:8000");
+        assert_eq!(client.active_network(), &network);
+        assert_eq!(client.network_config().horizon_url, "http://localhost:8000");
+    }
+
     #[tokio::test]
     async fn test_pagination_config_defaults() {
         let client = StellarRpcClient::new_with_defaults(true);
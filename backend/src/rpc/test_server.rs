@@ -0,0 +1,386 @@
+//! Scripted local HTTP server standing in for Horizon and Soroban RPC.
+//!
+//! Unlike `mock_mode`, a client pointed at a [`TestServer`] goes through the
+//! real request, retry, error-mapping and circuit breaker paths. Responses are
+//! served in the order they were queued; once the queue is drained the last
+//! response is repeated.
+
+use axum::{
+    body::{Body, Bytes},
+    http::{HeaderMap, HeaderName, HeaderValue, StatusCode, Uri},
+    response::Response,
+    Router,
+};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use super::circuit_breaker::{build_circuit_breaker, CircuitBreakerConfig, SharedCircuitBreaker};
+use super::retry_budget::RetryBudget;
+use super::stellar::StellarRpcClient;
+
+/// One canned HTTP response.
+#[derive(Debug, Clone)]
+pub struct ScriptedResponse {
+    pub status: StatusCode,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: Vec<u8>,
+    /// Wait this long before responding, to provoke client timeouts
+    pub delay: Duration,
+}
+
+impl ScriptedResponse {
+    #[must_use]
+    pub fn json(status: StatusCode, body: &str) -> Self {
+        Self {
+            status,
+            headers: vec![("content-type", "application/json".to_string())],
+            body: body.as_bytes().to_vec(),
+            delay: Duration::ZERO,
+        }
+    }
+
+    /// JSON response whose body is already compressed with `content_encoding`.
+    #[must_use]
+    pub fn encoded_json(status: StatusCode, content_encoding: &str, body: Vec<u8>) -> Self {
+        Self {
+            body,
+            ..Self::json(status, "")
+        }
+        .with_header("content-encoding", content_encoding)
+    }
+
+    /// Successful `getHealth` JSON-RPC response.
+    #[must_use]
+    pub fn healthy() -> Self {
+        Self::json(
+            StatusCode::OK,
+            r#"{"jsonrpc":"2.0","id":1,"result":{"status":"healthy","latestLedger":1100,"oldestLedger":1001,"ledgerRetentionWindow":100}}"#,
+        )
+    }
+
+    #[must_use]
+    pub fn with_header(mut self, name: &'static str, value: &str) -> Self {
+        self.headers.push((name, value.to_string()));
+        self
+    }
+
+    #[must_use]
+    pub const fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+#[derive(Default)]
+struct Script {
+    queue: Mutex<VecDeque<ScriptedResponse>>,
+    last: Mutex<Option<ScriptedResponse>>,
+    hits: AtomicUsize,
+    uris: Mutex<Vec<Uri>>,
+    headers: Mutex<Vec<HeaderMap>>,
+    bodies: Mutex<Vec<Bytes>>,
+}
+
+impl Script {
+    fn next(&self, uri: Uri, headers: HeaderMap, body: Bytes) -> ScriptedResponse {
+        self.hits.fetch_add(1, Ordering::SeqCst);
+        self.uris
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(uri);
+        self.headers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(headers);
+        self.bodies
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(body);
+        let mut last = self.last.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(next) = self
+            .queue
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pop_front()
+        {
+            *last = Some(next);
+        }
+        last.clone().unwrap_or_else(|| {
+            ScriptedResponse::json(
+                StatusCode::NOT_IMPLEMENTED,
+                r#"{"error":"no response scripted"}"#,
+            )
+        })
+    }
+}
+
+/// Fake upstream bound to an ephemeral localhost port.
+pub struct TestServer {
+    url: String,
+    script: Arc<Script>,
+    server: tokio::task::JoinHandle<()>,
+}
+
+impl TestServer {
+    /// Start serving `responses` for every method and path.
+    pub async fn start(responses: impl IntoIterator<Item = ScriptedResponse>) -> Self {
+        let script = Arc::new(Script {
+            queue: Mutex::new(responses.into_iter().collect()),
+            ..Script::default()
+        });
+        let handler_script = script.clone();
+        let app = Router::new().fallback(move |uri: Uri, headers: HeaderMap, body: Bytes| {
+            let script = handler_script.clone();
+            async move {
+                let scripted = script.next(uri, headers, body);
+                tokio::time::sleep(scripted.delay).await;
+                let mut response = Response::new(Body::from(scripted.body));
+                *response.status_mut() = scripted.status;
+                for (name, value) in scripted.headers {
+                    if let Ok(value) = HeaderValue::from_str(&value) {
+                        response
+                            .headers_mut()
+                            .insert(HeaderName::from_static(name), value);
+                    }
+                }
+                response
+            }
+        });
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind test server");
+        let url = format!("http://{}", listener.local_addr().expect("local addr"));
+        let server = tokio::spawn(async move {
+            let _ = axum::serve(listener, app).await;
+        });
+        Self {
+            url,
+            script,
+            server,
+        }
+    }
+
+    #[must_use]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Requests received so far.
+    #[must_use]
+    pub fn hits(&self) -> usize {
+        self.script.hits.load(Ordering::SeqCst)
+    }
+
+    /// Path and query of each request received so far, oldest first.
+    #[must_use]
+    pub fn request_uris(&self) -> Vec<Uri> {
+        self.script
+            .uris
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Headers of each request received so far, oldest first.
+    #[must_use]
+    pub fn request_headers(&self) -> Vec<HeaderMap> {
+        self.script
+            .headers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Bodies of each request received so far, oldest first.
+    #[must_use]
+    pub fn request_bodies(&self) -> Vec<Bytes> {
+        self.script
+            .bodies
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Real-mode client using this server as both RPC and Horizon, with its
+    /// own breaker and retry budget so tests cannot trip each other's state.
+    #[must_use]
+    pub fn client(&self, breaker: &CircuitBreakerConfig) -> StellarRpcClient {
+        StellarRpcClient::new(self.url.clone(), self.url.clone(), false)
+            .with_circuit_breaker(Self::breaker(breaker))
+            .with_retry_budget(Arc::new(RetryBudget::new(100.0, 1.0)))
+            .with_retry_policy(2, Duration::from_millis(10), Duration::from_millis(20))
+    }
+
+    fn breaker(config: &CircuitBreakerConfig) -> SharedCircuitBreaker {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        let endpoint = format!("test_server_{}", NEXT_ID.fetch_add(1, Ordering::SeqCst));
+        build_circuit_breaker(&endpoint, config, None)
+    }
+}
+
+impl Drop for TestServer {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::config::DEFAULT_USER_AGENT;
+    use crate::rpc::error::RpcError;
+    use crate::rpc::stellar::LedgerPoll;
+    use std::io::Write;
+
+    #[tokio::test]
+    async fn test_rate_limit_carries_retry_after_and_retries() {
+        let server = TestServer::start([ScriptedResponse::json(
+            StatusCode::TOO_MANY_REQUESTS,
+            r#"{"title":"Rate Limit Exceeded"}"#,
+        )
+        .with_header("retry-after", "7")])
+        .await;
+        let client = server.client(&CircuitBreakerConfig::default());
+
+        let err = client.check_health().await.unwrap_err();
+        assert!(matches!(
+            err,
+            RpcError::RateLimitError {
+                retry_after: Some(d)
+            } if d == Duration::from_secs(7)
+        ));
+        // One attempt plus two retries
+        assert_eq!(server.hits(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_server_errors_trip_the_breaker() {
+        let server = TestServer::start([ScriptedResponse::json(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "upstream unavailable",
+        )])
+        .await;
+        let client = server.client(&CircuitBreakerConfig {
+            failure_threshold: 2,
+            success_threshold: 1,
+            timeout_duration: Duration::from_secs(60),
+        });
+
+        for _ in 0..2 {
+            let err = client.check_health().await.unwrap_err();
+            assert!(matches!(err, RpcError::ServerError { status: 503, .. }));
+        }
+        let err = client.check_health().await.unwrap_err();
+        assert!(matches!(err, RpcError::CircuitBreakerOpen));
+        assert_eq!(server.hits(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_requests_carry_user_agent_and_request_id() {
+        let server = TestServer::start([ScriptedResponse::healthy()]).await;
+        // The User-Agent is read from the environment when the client is built
+        let client = {
+            let _guard = crate::lock_env_test();
+            server.client(&CircuitBreakerConfig::default())
+        };
+
+        client.check_health().await.unwrap();
+        client.check_health().await.unwrap();
+
+        let headers = server.request_headers();
+        assert_eq!(headers.len(), 2);
+        assert_eq!(headers[0]["user-agent"], DEFAULT_USER_AGENT);
+        assert!(DEFAULT_USER_AGENT.starts_with("stellar-insights/"));
+        let request_ids: Vec<_> = headers
+            .iter()
+            .map(|h| h["x-request-id"].to_str().unwrap().to_string())
+            .collect();
+        for id in &request_ids {
+            assert!(uuid::Uuid::parse_str(id).is_ok());
+        }
+        assert_ne!(request_ids[0], request_ids[1]);
+    }
+
+    #[tokio::test]
+    async fn test_gzip_encoded_body_is_decoded() {
+        let healthy = ScriptedResponse::healthy();
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&healthy.body).unwrap();
+        let server = TestServer::start([ScriptedResponse::encoded_json(
+            StatusCode::OK,
+            "gzip",
+            encoder.finish().unwrap(),
+        )])
+        .await;
+        let client = server.client(&CircuitBreakerConfig::default());
+
+        let health = client.check_health().await.unwrap();
+        assert_eq!(health.latest_ledger, 1100);
+
+        let accept_encoding = server.request_headers()[0]["accept-encoding"]
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert!(accept_encoding.contains("gzip"));
+        assert!(accept_encoding.contains("br"));
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_encoding_is_a_parse_error() {
+        let server = TestServer::start([ScriptedResponse::encoded_json(
+            StatusCode::OK,
+            "zstd",
+            vec![0x28, 0xb5, 0x2f, 0xfd],
+        )])
+        .await;
+        let client = server.client(&CircuitBreakerConfig::default());
+
+        let err = client.check_health().await.unwrap_err();
+        assert!(matches!(err, RpcError::ParseError(ref msg) if msg.contains("zstd")));
+    }
+
+    #[tokio::test]
+    async fn test_timeout_is_retried() {
+        let server = TestServer::start([
+            ScriptedResponse::healthy().with_delay(Duration::from_secs(2)),
+            ScriptedResponse::healthy(),
+        ])
+        .await;
+        let client = server
+            .client(&CircuitBreakerConfig::default())
+            .with_http_timeout(Duration::from_millis(200));
+
+        let health = client.check_health().await.unwrap();
+        assert_eq!(health.latest_ledger, 1100);
+        assert_eq!(server.hits(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_poll_ledgers_past_latest_is_caught_up() {
+        let start_out_of_range = ScriptedResponse::json(
+            StatusCode::OK,
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32600,"message":"start ledger must be between the oldest ledger: 1001 and the latest ledger: 1100"}}"#,
+        );
+
+        let server =
+            TestServer::start([start_out_of_range.clone(), ScriptedResponse::healthy()]).await;
+        let client = server.client(&CircuitBreakerConfig::default());
+        let poll = client.poll_ledgers(1101, 10, None).await.unwrap();
+        assert!(matches!(
+            poll,
+            LedgerPoll::CaughtUp {
+                latest_ledger: 1100
+            }
+        ));
+        assert_eq!(server.hits(), 2);
+
+        // The same error for a start the node should have is a real failure
+        let server = TestServer::start([start_out_of_range, ScriptedResponse::healthy()]).await;
+        let client = server.client(&CircuitBreakerConfig::default());
+        let err = client.poll_ledgers(1050, 10, None).await.unwrap_err();
+        assert!(matches!(err, RpcError::JsonRpcError { code: -32600, .. }));
+    }
+}
@@ -47,6 +47,15 @@ impl AccountMergeDetector {
 
     /// Fetches operations for a ledger, extracts account merges, and persists merge events.
     pub async fn process_ledger_operations(&self, ledger_sequence: u64) -> Result<u64> {
+        let operations = self.fetch_ledger_operations(ledger_sequence).await?;
+        self.process_operations(ledger_sequence, &operations).await
+    }
+
+    /// Fetches a ledger's operations through the shared RPC circuit breaker.
+    pub async fn fetch_ledger_operations(
+        &self,
+        ledger_sequence: u64,
+    ) -> Result<Vec<HorizonOperation>> {
         let circuit_breaker = rpc_circuit_breaker();
         let operations: Vec<HorizonOperation> = circuit_breaker
             .call(async {
@@ -60,7 +69,15 @@ impl AccountMergeDetector {
                 failsafe::Error::Rejected => anyhow::anyhow!("Circuit breaker open"),
                 failsafe::Error::Inner(err) => err,
             })?;
+        Ok(operations)
+    }
 
+    /// Persists the account merges among an already fetched ledger's operations.
+    pub async fn process_operations(
+        &self,
+        ledger_sequence: u64,
+        operations: &[HorizonOperation],
+    ) -> Result<u64> {
         let mut inserted = 0_u64;
 
         for operation in operations
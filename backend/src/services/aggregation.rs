@@ -1,7 +1,7 @@
 #![allow(clippy::needless_raw_string_hashes)]
 
 use anyhow::{Context, Result};
-use chrono::{DateTime, Duration, Timelike, Utc};
+use chrono::{DateTime, Duration, SecondsFormat, Timelike, Utc};
 use std::sync::Arc;
 use tokio::time::{interval, Duration as TokioDuration};
 use tracing::{error, info, warn};
@@ -9,11 +9,26 @@ use uuid::Uuid;
 
 use crate::database::Database;
 use crate::models::corridor::{CorridorMetrics, HourlyCorridorMetrics, VolumeTrend};
+use crate::models::trades::{Candle, CandleInterval};
+use crate::rpc::{Price, Trade};
 use crate::services::analytics::compute_metrics_from_payments;
+use crate::services::trade_pricing::Quote;
 
 const MAX_RETRIES: i32 = 3;
 const RETRY_DELAY_SECS: u64 = 60;
 
+/// Upper bound on buckets returned by a single candle query
+pub const MAX_CANDLES: i64 = 1500;
+
+/// Asset identifier used by the `trades` table: `native` or `CODE:ISSUER`
+#[must_use]
+pub fn asset_key(asset_type: &str, code: Option<&str>, issuer: Option<&str>) -> String {
+    match (asset_type, code, issuer) {
+        ("native", _, _) | (_, None, _) => "native".to_string(),
+        (_, Some(code), issuer) => format!("{code}:{}", issuer.unwrap_or_default()),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct AggregationConfig {
     pub interval_hours: u64,
@@ -429,6 +444,128 @@ impl AggregationService {
             })
             .collect()
     }
+
+    /// Persist raw trades for later candle aggregation; already-stored ids are skipped.
+    pub async fn record_trades(&self, trades: &[Trade]) -> Result<usize> {
+        let mut inserted = 0;
+        for trade in trades {
+            let result = sqlx::query(
+                r#"
+                INSERT OR IGNORE INTO trades (
+                    id, ledger_close_time, base_asset, counter_asset,
+                    base_amount, counter_amount, price_n, price_d, trade_type
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&trade.id)
+            .bind(&trade.ledger_close_time)
+            .bind(asset_key(
+                &trade.base_asset_type,
+                trade.base_asset_code.as_deref(),
+                trade.base_asset_issuer.as_deref(),
+            ))
+            .bind(asset_key(
+                &trade.counter_asset_type,
+                trade.counter_asset_code.as_deref(),
+                trade.counter_asset_issuer.as_deref(),
+            ))
+            .bind(&trade.base_amount)
+            .bind(&trade.counter_amount)
+            .bind(trade.price.n)
+            .bind(trade.price.d)
+            .bind(&trade.trade_type)
+            .execute(self.db.pool())
+            .await
+            .context("Failed to store trade")?;
+            inserted += result.rows_affected() as usize;
+        }
+        Ok(inserted)
+    }
+
+    /// Build OHLCV candles for `base`/`counter` over `[from, to)`.
+    ///
+    /// Price is `price.n / price.d` and volume is the summed `base_amount`.
+    /// With a `quote` both are instead normalized into the quote asset (see
+    /// [`Quote::normalize`]), and trades that can't be valued are skipped.
+    /// Every bucket in the range is returned; empty ones carry `null` prices
+    /// and zero volume rather than a carried-forward close.
+    pub async fn aggregate_trades_to_candles(
+        &self,
+        base: &str,
+        counter: &str,
+        interval: CandleInterval,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        quote: Option<&Quote>,
+    ) -> Result<Vec<Candle>> {
+        let rows: Vec<(String, String, i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT ledger_close_time, base_amount, price_n, price_d
+            FROM trades
+            WHERE base_asset = ? AND counter_asset = ?
+              AND ledger_close_time >= ? AND ledger_close_time < ?
+            ORDER BY ledger_close_time ASC, id ASC
+            "#,
+        )
+        .bind(base)
+        .bind(counter)
+        .bind(
+            interval
+                .bucket_start(from)
+                .to_rfc3339_opts(SecondsFormat::Secs, true),
+        )
+        .bind(to.to_rfc3339_opts(SecondsFormat::Secs, true))
+        .fetch_all(self.db.pool())
+        .await
+        .context("Failed to fetch trades for candle aggregation")?;
+
+        let trades: Vec<_> = rows
+            .into_iter()
+            .filter_map(|(time, amount, n, d)| {
+                let time = DateTime::parse_from_rfc3339(&time)
+                    .ok()?
+                    .with_timezone(&Utc);
+                if d == 0 {
+                    warn!("Skipping trade with zero price denominator at {}", time);
+                    return None;
+                }
+                let amount = amount.parse::<f64>().unwrap_or(0.0);
+                match quote {
+                    Some(quote) => {
+                        let quoted = quote.normalize(base, counter, &Price { n, d }, amount)?;
+                        Some((time, quoted.price_in_quote, quoted.volume_in_quote))
+                    }
+                    None => Some((time, n as f64 / d as f64, amount)),
+                }
+            })
+            .collect();
+
+        Ok(build_candles(&trades, interval, from, to))
+    }
+}
+
+/// Bucket time-ordered `(time, price, volume)` trades into candles covering `[from, to)`.
+fn build_candles(
+    trades: &[(DateTime<Utc>, f64, f64)],
+    interval: CandleInterval,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Vec<Candle> {
+    let mut candles = Vec::new();
+    let mut open_time = interval.bucket_start(from);
+    while open_time < to && (candles.len() as i64) < MAX_CANDLES {
+        candles.push(Candle::empty(open_time));
+        open_time += interval.duration();
+    }
+
+    let first = interval.bucket_start(from);
+    for &(time, price, volume) in trades {
+        let index = (time - first).num_seconds().div_euclid(interval.seconds());
+        if let Some(candle) = usize::try_from(index).ok().and_then(|i| candles.get_mut(i)) {
+            candle.push(price, volume);
+        }
+    }
+    candles
 }
 
 impl Clone for AggregationService {
@@ -639,6 +776,128 @@ mod tests {
             .is_some());
     }
 
+    fn trade(id: &str, time: &str, n: i64, d: i64, base_amount: &str) -> Trade {
+        let mut trade = crate::rpc::mock_stellar::mock_trades(1).remove(0);
+        trade.id = id.to_string();
+        trade.ledger_close_time = time.to_string();
+        trade.price = crate::rpc::Price { n, d };
+        trade.base_amount = base_amount.to_string();
+        trade
+    }
+
+    fn at(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(time)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[tokio::test]
+    async fn test_trades_aggregate_into_ohlcv_candles() {
+        let (db, _temp_dir) = setup_test_db().await;
+        db.pool()
+            .execute(include_str!("../../migrations/039_create_trades.sql"))
+            .await
+            .unwrap();
+        let service = AggregationService::new(Arc::clone(&db), AggregationConfig::default());
+
+        let stored = service
+            .record_trades(&[
+                // 10:00 bucket: 0.10 -> 0.15 -> 0.08 -> 0.12
+                trade("t1", "2026-01-22T10:00:05Z", 1, 10, "100.0000000"),
+                trade("t2", "2026-01-22T10:01:00Z", 3, 20, "50.0000000"),
+                trade("t3", "2026-01-22T10:02:30Z", 2, 25, "25.0000000"),
+                trade("t4", "2026-01-22T10:04:59Z", 3, 25, "25.0000000"),
+                // 10:05 bucket left empty; 10:10 bucket has a single trade
+                trade("t5", "2026-01-22T10:12:00Z", 1, 5, "10.0000000"),
+                // Outside the requested range
+                trade("t6", "2026-01-22T10:15:00Z", 1, 1, "999.0000000"),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(stored, 6);
+        // Re-recording the same trade is a no-op
+        let again = service
+            .record_trades(&[trade("t1", "2026-01-22T10:00:05Z", 1, 10, "100.0000000")])
+            .await
+            .unwrap();
+        assert_eq!(again, 0);
+
+        let candles = service
+            .aggregate_trades_to_candles(
+                "native",
+                "USDC:GBXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX",
+                CandleInterval::FiveMinutes,
+                at("2026-01-22T10:00:00Z"),
+                at("2026-01-22T10:15:00Z"),
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(candles.len(), 3);
+
+        let first = &candles[0];
+        assert_eq!(first.open_time, at("2026-01-22T10:00:00Z"));
+        assert_eq!(first.open, Some(0.1));
+        assert_eq!(first.high, Some(0.15));
+        assert_eq!(first.low, Some(0.08));
+        assert_eq!(first.close, Some(0.12));
+        assert!((first.volume - 200.0).abs() < f64::EPSILON);
+        assert_eq!(first.trade_count, 4);
+
+        assert_eq!(candles[1], Candle::empty(at("2026-01-22T10:05:00Z")));
+
+        let third = &candles[2];
+        assert_eq!(third.open, Some(0.2));
+        assert_eq!(third.close, Some(0.2));
+        assert!((third.volume - 10.0).abs() < f64::EPSILON);
+        assert_eq!(third.trade_count, 1);
+
+        // With USDC at $2.00 every price doubles and volume becomes
+        // 100 * 0.2 + 50 * 0.3 + 25 * 0.16 + 25 * 0.24 = $45
+        let usdc = "USDC:GBXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX";
+        let quote = Quote::new("USD", [(usdc.to_string(), 2.0)].into_iter().collect());
+        let quoted = service
+            .aggregate_trades_to_candles(
+                "native",
+                usdc,
+                CandleInterval::FiveMinutes,
+                at("2026-01-22T10:00:00Z"),
+                at("2026-01-22T10:15:00Z"),
+                Some(&quote),
+            )
+            .await
+            .unwrap();
+        assert_eq!(quoted[0].open, Some(0.2));
+        assert_eq!(quoted[0].high, Some(0.3));
+        assert_eq!(quoted[0].close, Some(0.24));
+        assert!((quoted[0].volume - 45.0).abs() < 1e-9);
+        assert_eq!(quoted[2].close, Some(0.4));
+    }
+
+    #[test]
+    fn test_candle_buckets_align_to_interval() {
+        let trades = [(at("2026-01-22T13:59:59Z"), 2.0, 1.0)];
+        let candles = build_candles(
+            &trades,
+            CandleInterval::OneHour,
+            at("2026-01-22T12:30:00Z"),
+            at("2026-01-22T14:00:00Z"),
+        );
+
+        assert_eq!(candles.len(), 2);
+        assert_eq!(candles[0].open_time, at("2026-01-22T12:00:00Z"));
+        assert_eq!(candles[0].close, None);
+        assert_eq!(candles[1].open_time, at("2026-01-22T13:00:00Z"));
+        assert_eq!(candles[1].close, Some(2.0));
+
+        assert_eq!(
+            "1d".parse::<CandleInterval>().unwrap(),
+            CandleInterval::OneDay
+        );
+        assert!("2h".parse::<CandleInterval>().is_err());
+    }
+
     #[tokio::test]
     async fn test_aggregate_with_no_data() {
         let (db, _temp_dir) = setup_test_db().await;
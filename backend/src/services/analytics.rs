@@ -1,4 +1,7 @@
 use crate::models::corridor::{compute_median, CorridorMetrics, PaymentRecord};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
 use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
@@ -238,6 +241,54 @@ pub fn compute_metrics_by_window(
     compute_metrics_from_payments(&filtered)
 }
 
+/// Network-wide totals for one UTC day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct DailySummary {
+    pub date: NaiveDate,
+    pub total_payments: i64,
+    pub total_volume_usd: f64,
+    pub active_corridors: i64,
+    pub new_claimable_balances: i64,
+    pub ledger_count: i64,
+}
+
+/// Summarise `date` in a single round trip.
+///
+/// Volume and active corridors come from the daily `corridor_metrics`
+/// rollup; payments, claimable balances and ledgers are counted from their
+/// raw tables by their UTC timestamp. Timestamps are compared as text
+/// against `[date, date + 1)` so their indexes apply; every stored
+/// format (`2026-03-04T00:00:01Z`, `2026-03-04 08:00:00`) starts with the day.
+pub async fn daily_summary(pool: &SqlitePool, date: NaiveDate) -> anyhow::Result<DailySummary> {
+    let day = date.format("%Y-%m-%d").to_string();
+    let next_day = date
+        .succ_opt()
+        .ok_or_else(|| anyhow::anyhow!("No day after {date}"))?
+        .format("%Y-%m-%d")
+        .to_string();
+    let summary = sqlx::query_as::<_, DailySummary>(
+        r"
+        SELECT
+            ?1 AS date,
+            (SELECT COUNT(*) FROM payments WHERE created_at >= ?1 AND created_at < ?2)
+                AS total_payments,
+            (SELECT COALESCE(SUM(volume_usd), 0.0) FROM corridor_metrics WHERE date = ?1)
+                AS total_volume_usd,
+            (SELECT COUNT(*) FROM corridor_metrics WHERE date = ?1 AND total_transactions > 0)
+                AS active_corridors,
+            (SELECT COUNT(*) FROM claimable_balances WHERE created_at >= ?1 AND created_at < ?2)
+                AS new_claimable_balances,
+            (SELECT COUNT(*) FROM ledgers WHERE close_time >= ?1 AND close_time < ?2)
+                AS ledger_count
+        ",
+    )
+    .bind(day)
+    .bind(next_day)
+    .fetch_one(pool)
+    .await?;
+    Ok(summary)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -471,4 +522,65 @@ mod tests {
         assert_eq!(m.avg_settlement_latency_ms, Some(2000)); // (1000 + 3000) / 2
         assert_eq!(m.median_settlement_latency_ms, Some(2000)); // Median of [1000, 3000]
     }
+
+    #[tokio::test]
+    async fn test_daily_summary_counts_only_the_requested_day() {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        for migration in [
+            include_str!("../../migrations/003_create_ingestion_and_payments.sql"),
+            include_str!("../../migrations/007_create_ledger_ingestion_tables.sql"),
+            include_str!("../../migrations/040_create_claimable_balances.sql"),
+            include_str!("../../migrations/049_add_daily_summary_indexes.sql"),
+        ] {
+            sqlx::query(migration).execute(&pool).await.unwrap();
+        }
+        sqlx::query(
+            r"
+            INSERT INTO payments (id, transaction_hash, source_account, destination_account,
+                                  asset_type, amount, created_at) VALUES
+                ('p1', 't1', 'GA', 'GB', 'native', 10.0, '2026-03-04T00:00:01Z'),
+                ('p2', 't2', 'GA', 'GB', 'native', 20.0, '2026-03-04T12:30:00Z'),
+                ('p3', 't3', 'GA', 'GB', 'native', 30.0, '2026-03-04T23:59:59Z'),
+                ('p4', 't4', 'GA', 'GB', 'native', 40.0, '2026-03-05T00:00:00Z');
+            INSERT INTO corridor_metrics (corridor_key, asset_a_code, asset_a_issuer, asset_b_code,
+                                          asset_b_issuer, date, total_transactions, volume_usd) VALUES
+                ('USDC->XLM', 'USDC', 'GA', 'XLM', 'native', '2026-03-04', 2, 150.5),
+                ('EURC->XLM', 'EURC', 'GA', 'XLM', 'native', '2026-03-04', 1, 49.5),
+                ('NGNT->XLM', 'NGNT', 'GA', 'XLM', 'native', '2026-03-04', 0, 0.0),
+                ('USDC->XLM', 'USDC', 'GA', 'XLM', 'native', '2026-03-03', 9, 999.0);
+            INSERT INTO claimable_balances (id, asset, amount, created_at) VALUES
+                ('cb1', 'native', '5', '2026-03-04 08:00:00'),
+                ('cb2', 'native', '5', '2026-03-03 08:00:00');
+            INSERT INTO ledgers (sequence, hash, close_time) VALUES
+                (100, 'h100', '2026-03-03T23:59:58Z'),
+                (101, 'h101', '2026-03-04T00:00:03Z'),
+                (102, 'h102', '2026-03-04T00:00:08Z');
+            ",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2026, 3, 4).unwrap();
+        let summary = daily_summary(&pool, date).await.unwrap();
+        assert_eq!(
+            summary,
+            DailySummary {
+                date,
+                total_payments: 3,
+                total_volume_usd: 200.0,
+                active_corridors: 2,
+                new_claimable_balances: 1,
+                ledger_count: 2,
+            }
+        );
+
+        // A day with no data reports zeros rather than failing
+        let empty = daily_summary(&pool, NaiveDate::from_ymd_opt(2026, 1, 1).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(empty.total_payments, 0);
+        assert_eq!(empty.total_volume_usd, 0.0);
+        assert_eq!(empty.ledger_count, 0);
+    }
 }
@@ -1,10 +1,12 @@
 use crate::alerts::{AlertManager, AlertType};
 use crate::cache::CacheManager;
 use crate::database::Database;
+use crate::services::transfer_server_probe::TransferServerProbe;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
+use tokio::sync::broadcast;
 use tokio::time::{interval, Duration};
 
 use crate::models::{AnchorMetrics, AnchorStatus};
@@ -65,6 +67,7 @@ pub struct AnchorMonitor {
     alert_manager: Arc<AlertManager>,
     cache: Arc<CacheManager>,
     last_metrics: Arc<tokio::sync::RwLock<HashMap<String, AnchorMetrics>>>,
+    transfer_probe: Option<Arc<TransferServerProbe>>,
 }
 
 impl AnchorMonitor {
@@ -79,15 +82,27 @@ impl AnchorMonitor {
             alert_manager,
             cache,
             last_metrics: Arc::new(tokio::sync::RwLock::new(HashMap::new())),
+            transfer_probe: None,
         }
     }
 
-    pub async fn start(self) {
+    /// Also probe each anchor's SEP-6/SEP-24 transfer server on every check.
+    #[must_use]
+    pub fn with_transfer_probe(mut self, probe: Arc<TransferServerProbe>) -> Self {
+        self.transfer_probe = Some(probe);
+        self
+    }
+
+    /// Check anchors every 5 minutes until shutdown is signalled.
+    pub async fn start(self, mut shutdown_rx: broadcast::Receiver<()>) {
         let mut check_interval = interval(Duration::from_secs(300)); // Check every 5 minutes
         tracing::info!("Anchor monitor started");
 
         loop {
-            check_interval.tick().await;
+            tokio::select! {
+                _ = check_interval.tick() => {}
+                _ = shutdown_rx.recv() => break,
+            }
             if let Err(e) = self.check_anchors().await {
                 tracing::error!("Anchor monitoring failed: {}", e);
             }
@@ -98,6 +113,10 @@ impl AnchorMonitor {
         let anchors = self.db.list_anchors(1000, 0).await?;
 
         for anchor in anchors {
+            if let Some(probe) = &self.transfer_probe {
+                probe.check_anchor(&self.alert_manager, &anchor).await;
+            }
+
             let cache_key = format!("anchor_metrics:{}", anchor.id);
 
             // Try the cache first (1-minute TTL reduces repeated expensive DB/RPC calls)
@@ -201,3 +220,28 @@ impl AnchorMonitor {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::CacheConfig;
+
+    #[tokio::test]
+    async fn test_start_returns_on_shutdown() {
+        let pool = sqlx::SqlitePool::connect(":memory:").await.unwrap();
+        let (alert_manager, _rx) = AlertManager::new();
+        let monitor = AnchorMonitor::new(
+            Arc::new(Database::new(pool)),
+            Arc::new(alert_manager),
+            Arc::new(CacheManager::new_in_memory_for_tests(CacheConfig::default())),
+        );
+        let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
+
+        let handle = tokio::spawn(monitor.start(shutdown_rx));
+        shutdown_tx.send(()).unwrap();
+        tokio::time::timeout(Duration::from_secs(5), handle)
+            .await
+            .expect("monitor kept running after shutdown")
+            .unwrap();
+    }
+}
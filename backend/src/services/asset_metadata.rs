@@ -0,0 +1,220 @@
+//! Asset metadata enrichment from issuer stellar.toml files (SEP-1)
+//!
+//! Resolves an issuer's `home_domain` from Horizon, loads the domain's
+//! stellar.toml and exposes the `[[CURRENCIES]]` entries issued by that account.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+use crate::rpc::StellarRpcClient;
+use crate::services::stellar_toml::{StellarToml, StellarTomlClient};
+
+/// stellar.toml files rarely change, so resolved metadata is kept for a day
+const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Display metadata for a single issued asset
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetMetadata {
+    pub code: String,
+    pub issuer: String,
+    pub home_domain: String,
+    pub name: Option<String>,
+    pub image: Option<String>,
+    pub desc: Option<String>,
+    pub is_asset_anchored: Option<bool>,
+}
+
+#[derive(Debug, Clone)]
+struct CachedMetadata {
+    assets: Vec<AssetMetadata>,
+    timestamp: Instant,
+}
+
+/// Looks up and caches per-issuer asset metadata
+pub struct AssetMetadataService {
+    rpc_client: Arc<StellarRpcClient>,
+    toml_client: Arc<StellarTomlClient>,
+    cache: RwLock<HashMap<String, CachedMetadata>>,
+    cache_ttl: Duration,
+}
+
+impl AssetMetadataService {
+    #[must_use]
+    pub fn new(rpc_client: Arc<StellarRpcClient>, toml_client: Arc<StellarTomlClient>) -> Self {
+        Self {
+            rpc_client,
+            toml_client,
+            cache: RwLock::new(HashMap::new()),
+            cache_ttl: DEFAULT_CACHE_TTL,
+        }
+    }
+
+    /// Override how long resolved metadata is reused
+    #[must_use]
+    pub const fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = ttl;
+        self
+    }
+
+    /// Metadata for every currency `issuer` publishes in its stellar.toml.
+    ///
+    /// An issuer without a `home_domain`, or whose stellar.toml is missing or
+    /// invalid, yields an empty list; that outcome is cached like a success.
+    pub async fn metadata_for_issuer(&self, issuer: &str) -> Result<Vec<AssetMetadata>> {
+        if let Some(cached) = self.cache.read().await.get(issuer) {
+            if cached.timestamp.elapsed() < self.cache_ttl {
+                return Ok(cached.assets.clone());
+            }
+        }
+
+        let account = self
+            .rpc_client
+            .fetch_account(issuer)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to fetch issuer account {issuer}: {e}"))?;
+
+        let assets = match account.home_domain.as_deref().filter(|d| !d.is_empty()) {
+            None => Vec::new(),
+            Some(domain) => match self.toml_client.fetch_toml(domain).await {
+                Ok(toml) => metadata_from_toml(&toml, issuer),
+                Err(e) => {
+                    tracing::warn!("No usable stellar.toml for issuer {}: {}", issuer, e);
+                    Vec::new()
+                }
+            },
+        };
+
+        self.cache.write().await.insert(
+            issuer.to_string(),
+            CachedMetadata {
+                assets: assets.clone(),
+                timestamp: Instant::now(),
+            },
+        );
+        Ok(assets)
+    }
+
+    /// Metadata for a single `code:issuer` asset, if its issuer publishes any
+    pub async fn metadata_for_asset(
+        &self,
+        code: &str,
+        issuer: &str,
+    ) -> Result<Option<AssetMetadata>> {
+        Ok(self
+            .metadata_for_issuer(issuer)
+            .await?
+            .into_iter()
+            .find(|asset| asset.code == code))
+    }
+}
+
+/// Extract the `[[CURRENCIES]]` entries issued by `issuer`
+#[must_use]
+pub fn metadata_from_toml(toml: &StellarToml, issuer: &str) -> Vec<AssetMetadata> {
+    toml.currencies
+        .iter()
+        .flatten()
+        .filter(|currency| currency.issuer.as_deref() == Some(issuer))
+        .map(|currency| AssetMetadata {
+            code: currency.code.clone(),
+            issuer: issuer.to_string(),
+            home_domain: toml.domain.clone(),
+            name: currency.name.clone(),
+            image: currency.image.clone(),
+            desc: currency.desc.clone(),
+            is_asset_anchored: currency.is_asset_anchored,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+    fn toml_client() -> StellarTomlClient {
+        StellarTomlClient::new(Arc::new(RwLock::new(None)), None).unwrap()
+    }
+
+    #[test]
+    fn test_metadata_from_representative_toml() {
+        let content = format!(
+            r#"
+ORGANIZATION_NAME = "Example Anchor"
+NETWORK_PASSPHRASE = "Public Global Stellar Network ; September 2015"
+
+[[CURRENCIES]]
+code = "USDC"
+issuer = "{ISSUER}"
+display_decimals = 2
+name = "USD Coin"
+desc = "Fully reserved digital dollar"
+image = "https://example.com/usdc.png"
+is_asset_anchored = true
+anchor_asset_type = "fiat"
+anchor_asset = "USD"
+
+[[CURRENCIES]]
+code = "EURT"
+issuer = "GDIFFERENTISSUERXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX"
+name = "Euro Token"
+"#
+        );
+        let toml = toml_client().parse_toml(&content, "example.com").unwrap();
+
+        let assets = metadata_from_toml(&toml, ISSUER);
+
+        assert_eq!(
+            assets,
+            vec![AssetMetadata {
+                code: "USDC".to_string(),
+                issuer: ISSUER.to_string(),
+                home_domain: "example.com".to_string(),
+                name: Some("USD Coin".to_string()),
+                image: Some("https://example.com/usdc.png".to_string()),
+                desc: Some("Fully reserved digital dollar".to_string()),
+                is_asset_anchored: Some(true),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_toml_without_currencies_yields_no_metadata() {
+        let toml = toml_client()
+            .parse_toml(r#"ORGANIZATION_NAME = "No Assets""#, "example.com")
+            .unwrap();
+        assert!(metadata_from_toml(&toml, ISSUER).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_toml_is_rejected() {
+        assert!(toml_client()
+            .parse_toml("[[CURRENCIES]\ncode = ", "example.com")
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_issuer_without_home_domain_is_cached_as_empty() {
+        let service = AssetMetadataService::new(
+            Arc::new(StellarRpcClient::new_with_defaults(true)),
+            Arc::new(toml_client()),
+        );
+
+        assert!(service
+            .metadata_for_issuer(ISSUER)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(service
+            .metadata_for_asset("USDC", ISSUER)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(service.cache.read().await.contains_key(ISSUER));
+    }
+}
@@ -1,4 +1,5 @@
 use anyhow::{Context, Result};
+use async_trait::async_trait;
 use chrono::Utc;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -32,6 +33,16 @@ struct TomlInfo {
     image: Option<String>,
 }
 
+/// Checks an asset against external verification sources
+#[async_trait]
+pub trait AssetVerification: Send + Sync {
+    async fn verify_asset(
+        &self,
+        asset_code: &str,
+        asset_issuer: &str,
+    ) -> Result<VerificationResult>;
+}
+
 pub struct AssetVerifier {
     http_client: Client,
     pool: SqlitePool,
@@ -594,6 +605,17 @@ impl AssetVerifier {
     }
 }
 
+#[async_trait]
+impl AssetVerification for AssetVerifier {
+    async fn verify_asset(
+        &self,
+        asset_code: &str,
+        asset_issuer: &str,
+    ) -> Result<VerificationResult> {
+        Self::verify_asset(self, asset_code, asset_issuer).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
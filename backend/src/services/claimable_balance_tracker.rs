@@ -0,0 +1,176 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Sqlite};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::rpc::{HorizonOperation, StellarRpcClient};
+
+/// Keeps `claimable_balances` in step with the operations of each ingested
+/// ledger: created balances are inserted, claimed or clawed back ones closed.
+pub struct ClaimableBalanceTracker {
+    pool: Pool<Sqlite>,
+    rpc_client: Arc<StellarRpcClient>,
+}
+
+impl ClaimableBalanceTracker {
+    #[must_use]
+    pub const fn new(pool: Pool<Sqlite>, rpc_client: Arc<StellarRpcClient>) -> Self {
+        Self { pool, rpc_client }
+    }
+
+    /// Apply the claimable balance operations of one ledger, returning how
+    /// many rows were inserted or closed.
+    pub async fn process_operations(&self, operations: &[HorizonOperation]) -> Result<u64> {
+        let mut changed = 0_u64;
+        for operation in operations {
+            let applied = match operation.operation_type.as_str() {
+                "create_claimable_balance" => self.record_created(operation).await?,
+                "claim_claimable_balance" | "clawback_claimable_balance" => {
+                    self.record_closed(operation).await?
+                }
+                _ => false,
+            };
+            if applied {
+                changed += 1;
+            }
+        }
+
+        if changed > 0 {
+            info!("Applied {} claimable balance changes", changed);
+        }
+        Ok(changed)
+    }
+
+    async fn record_created(&self, operation: &HorizonOperation) -> Result<bool> {
+        let (Some(asset), Some(amount)) = (&operation.asset, &operation.amount) else {
+            warn!(
+                "Skipping create_claimable_balance operation {} without asset or amount",
+                operation.id
+            );
+            return Ok(false);
+        };
+        // The balance id is only reported on the operation's effects
+        let Some(balance_id) = self.created_balance_id(&operation.id).await else {
+            return Ok(false);
+        };
+
+        let result = sqlx::query(
+            r"
+            INSERT INTO claimable_balances (id, asset, amount, sponsor, created_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (id) DO NOTHING
+            ",
+        )
+        .bind(&balance_id)
+        .bind(asset)
+        .bind(amount)
+        .bind(&operation.source_account)
+        .bind(timestamp(&operation.created_at))
+        .execute(&self.pool)
+        .await
+        .context("Failed to persist claimable balance")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn record_closed(&self, operation: &HorizonOperation) -> Result<bool> {
+        let Some(balance_id) = &operation.balance_id else {
+            warn!(
+                "Skipping {} operation {} without balance id",
+                operation.operation_type, operation.id
+            );
+            return Ok(false);
+        };
+
+        let result = sqlx::query(
+            "UPDATE claimable_balances SET claimed_at = $1 WHERE id = $2 AND claimed_at IS NULL",
+        )
+        .bind(timestamp(&operation.created_at))
+        .bind(balance_id)
+        .execute(&self.pool)
+        .await
+        .context("Failed to close claimable balance")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn created_balance_id(&self, operation_id: &str) -> Option<String> {
+        match self.rpc_client.fetch_operation_effects(operation_id).await {
+            Ok(effects) => effects
+                .into_iter()
+                .find(|effect| effect.effect_type == "claimable_balance_created")
+                .and_then(|effect| effect.balance_id),
+            Err(error) => {
+                warn!(
+                    "Failed to fetch effects for claimable balance operation {}: {}",
+                    operation_id, error
+                );
+                None
+            }
+        }
+    }
+}
+
+/// Horizon RFC 3339 time in the `CURRENT_TIMESTAMP` format the table defaults to
+fn timestamp(created_at: &str) -> String {
+    DateTime::parse_from_rfc3339(created_at)
+        .map_or_else(|_| Utc::now(), |dt| dt.with_timezone(&Utc))
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::mock_stellar;
+
+    async fn test_pool() -> Pool<Sqlite> {
+        let pool = sqlx::SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::query(include_str!(
+            "../../migrations/040_create_claimable_balances.sql"
+        ))
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_created_balances_are_stored_and_claims_close_them() {
+        let pool = test_pool().await;
+        let tracker = ClaimableBalanceTracker::new(
+            pool.clone(),
+            Arc::new(StellarRpcClient::new_with_defaults(true)),
+        );
+
+        let operations = mock_stellar::mock_operations_for_ledger(7);
+        assert_eq!(tracker.process_operations(&operations).await.unwrap(), 1);
+        // Replaying the same ledger does not duplicate the balance
+        assert_eq!(tracker.process_operations(&operations).await.unwrap(), 0);
+
+        let (id, asset, claimed_at): (String, String, Option<String>) =
+            sqlx::query_as("SELECT id, asset, claimed_at FROM claimable_balances")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(asset, mock_stellar::MOCK_CLAIMABLE_BALANCE_ASSET);
+        assert_eq!(claimed_at, None);
+
+        let claim = HorizonOperation {
+            id: "op_8_0".to_string(),
+            operation_type: "claim_claimable_balance".to_string(),
+            created_at: "2026-01-23T08:00:00Z".to_string(),
+            balance_id: Some(id),
+            ..operations[3].clone()
+        };
+        assert_eq!(tracker.process_operations(&[claim]).await.unwrap(), 1);
+
+        let claimed_at: Option<String> =
+            sqlx::query_scalar("SELECT claimed_at FROM claimable_balances")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(claimed_at.as_deref(), Some("2026-01-23 08:00:00"));
+    }
+}
@@ -2,10 +2,12 @@
 Temporarily disabled due to stellar_sdk 0.1 dependency issues.
 */
 use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::time::Duration;
+use stellar_xdr::curr::{Limits, ReadXdr, TransactionEnvelope, WriteXdr};
 use tracing::{debug, error, info, warn};
 
 // Stellar XDR signing types are referenced in the commented signing block below.
@@ -86,6 +88,79 @@ pub struct SubmissionResult {
     pub timestamp: u64,
 }
 
+/// Soroban inclusion fee percentiles, in stroops, over recent ledgers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeStats {
+    pub p50: u64,
+    pub p90: u64,
+    pub p99: u64,
+}
+
+impl FeeStats {
+    /// Parse the `sorobanInclusionFee` block of a `getFeeStats` result
+    fn from_rpc(result: &serde_json::Value) -> Result<Self> {
+        let fees = result
+            .get("sorobanInclusionFee")
+            .context("getFeeStats result missing sorobanInclusionFee")?;
+        let percentile = |name: &str| {
+            fees.get(name)
+                .and_then(|v| v.as_u64().or_else(|| v.as_str()?.parse().ok()))
+                .with_context(|| format!("getFeeStats result missing {name}"))
+        };
+        Ok(Self {
+            p50: percentile("p50")?,
+            p90: percentile("p90")?,
+            p99: percentile("p99")?,
+        })
+    }
+}
+
+/// Where a sent transaction stands, per `getTransaction`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionStatus {
+    /// Not yet included in a ledger
+    Pending,
+    Success {
+        ledger: u64,
+        timestamp: u64,
+    },
+    /// Included but failed, e.g. because the fee was too low
+    Failed {
+        reason: String,
+    },
+}
+
+impl TransactionStatus {
+    fn from_rpc(result: &serde_json::Value) -> Self {
+        let status = result.get("status").and_then(|s| s.as_str()).unwrap_or("");
+        if status.eq_ignore_ascii_case("success") {
+            let ledger = result
+                .get("ledger")
+                .and_then(serde_json::Value::as_u64)
+                .unwrap_or(0);
+            let timestamp = result
+                .get("createdAt")
+                .and_then(|s| s.as_str())
+                .and_then(|s| {
+                    chrono::DateTime::parse_from_rfc3339(s)
+                        .ok()
+                        .map(|d| d.timestamp() as u64)
+                })
+                .unwrap_or(0);
+            Self::Success { ledger, timestamp }
+        } else if status.eq_ignore_ascii_case("failed") {
+            let reason = result
+                .get("resultXdr")
+                .and_then(|r| r.as_str())
+                .unwrap_or("transaction failed")
+                .to_string();
+            Self::Failed { reason }
+        } else {
+            Self::Pending
+        }
+    }
+}
+
 impl ContractService {
     #[must_use]
     pub fn new(config: ContractConfig) -> Self {
@@ -181,6 +256,24 @@ impl ContractService {
 
     /// Single attempt to submit snapshot (without retry logic)
     async fn try_submit_snapshot(&self, hash: [u8; 32], epoch: u64) -> Result<SubmissionResult> {
+        let tx_hash = self.send_snapshot_transaction(hash, epoch, None).await?;
+
+        // Step 5: Wait for transaction confirmation
+        debug!("Waiting for transaction confirmation: {}", tx_hash);
+        self.wait_for_transaction(&tx_hash, epoch).await
+    }
+
+    /// Build, simulate, sign and send the `submit_snapshot` invocation, returning
+    /// the transaction hash without waiting for it to land.
+    ///
+    /// `max_fee` (stroops) is the inclusion fee written into the envelope, on
+    /// top of the simulated resource fee; `None` keeps the simulated fee.
+    pub async fn send_snapshot_transaction(
+        &self,
+        hash: [u8; 32],
+        epoch: u64,
+        max_fee: Option<u64>,
+    ) -> Result<String> {
         // Step 1: Build the contract invocation
         debug!("Building contract invocation for epoch {}", epoch);
         let invoke_args = self.build_invoke_args(hash, epoch)?;
@@ -191,17 +284,11 @@ impl ContractService {
 
         // Step 3: Prepare and sign the transaction
         debug!("Preparing and signing transaction");
-        let signed_xdr = self.prepare_and_sign_transaction(&simulated)?;
+        let signed_xdr = self.prepare_and_sign_transaction(&simulated, max_fee)?;
 
         // Step 4: Send the transaction
         debug!("Sending transaction to network");
-        let tx_hash = self.send_transaction(&signed_xdr).await?;
-
-        // Step 5: Wait for transaction confirmation
-        debug!("Waiting for transaction confirmation: {}", tx_hash);
-        let result = self.wait_for_transaction(&tx_hash, epoch).await?;
-
-        Ok(result)
+        self.send_transaction(&signed_xdr).await
     }
 
     /// Build contract invocation arguments
@@ -211,7 +298,7 @@ impl ContractService {
 
         // Build Soroban contract invocation parameters
         // Format: invoke contract_id submit_snapshot [hash_bytes, epoch_u64]
-        Ok(json!({
+        let args = json!({
             "contractId": self.config.contract_id,
             "function": "submit_snapshot",
             "args": [
@@ -224,7 +311,8 @@ impl ContractService {
                     "value": epoch.to_string()
                 }
             ]
-        }))
+        });
+        Ok(args)
     }
 
     /// Simulate the transaction to get resource estimates
@@ -274,7 +362,14 @@ impl ContractService {
     /// handles authorization via the source account configured in the node;
     /// full client-side keypair signing can be layered on top once a
     /// Soroban-compatible Rust SDK is stabilised.
-    fn prepare_and_sign_transaction(&self, simulated: &serde_json::Value) -> Result<String> {
+    ///
+    /// With `max_fee`, the envelope fee is set to it plus the simulation's
+    /// `minResourceFee`.
+    fn prepare_and_sign_transaction(
+        &self,
+        simulated: &serde_json::Value,
+        max_fee: Option<u64>,
+    ) -> Result<String> {
         let transaction_xdr = simulated
             .get("transactionData")
             .and_then(|t| t.as_str())
@@ -332,11 +427,24 @@ impl ContractService {
             return Err(anyhow::anyhow!("Simulation returned empty transactionData"));
         }
 
+        let Some(max_fee) = max_fee else {
+            debug!(
+                "Using simulation-provided transaction XDR ({} chars)",
+                transaction_xdr.len()
+            );
+            return Ok(transaction_xdr.to_string());
+        };
+        let resource_fee = simulated
+            .get("minResourceFee")
+            .and_then(|f| f.as_str())
+            .and_then(|f| f.parse::<u64>().ok())
+            .unwrap_or(0);
+        let fee = max_fee.saturating_add(resource_fee);
         debug!(
-            "Using simulation-provided transaction XDR ({} chars)",
-            transaction_xdr.len()
+            "Setting transaction fee to {} stroops ({} inclusion + {} resource)",
+            fee, max_fee, resource_fee
         );
-        Ok(transaction_xdr.to_string())
+        set_envelope_fee(transaction_xdr, fee)
     }
 
     async fn send_transaction(&self, signed_xdr: &str) -> Result<String> {
@@ -380,54 +488,11 @@ impl ContractService {
             .context("sendTransaction result missing transaction hash")
     }
 
+    /// Poll `getTransaction` until the transaction lands, for up to ~15 seconds
     async fn wait_for_transaction(&self, tx_hash: &str, epoch: u64) -> Result<SubmissionResult> {
-        let request = JsonRpcRequest {
-            jsonrpc: "2.0".to_string(),
-            id: 1,
-            method: "getTransaction".to_string(),
-            params: json!({ "hash": tx_hash }),
-        };
-
         for _ in 0..60 {
-            let response = self
-                .client
-                .post(&self.config.rpc_url)
-                .json(&request)
-                .send()
-                .await
-                .context("Failed to send getTransaction RPC request")?;
-
-            let body: JsonRpcResponse<serde_json::Value> = response
-                .json()
-                .await
-                .context("Failed to parse getTransaction RPC response")?;
-
-            if let Some(error) = &body.error {
-                let transient = error.message.to_ascii_lowercase().contains("not found");
-                if !transient {
-                    return Err(anyhow::anyhow!(
-                        "getTransaction failed: {} (code: {})",
-                        error.message,
-                        error.code
-                    ));
-                }
-            } else if let Some(result) = body.result {
-                let status = result.get("status").and_then(|s| s.as_str()).unwrap_or("");
-                if status.eq_ignore_ascii_case("success") || status.eq_ignore_ascii_case("failed") {
-                    let ledger = result
-                        .get("ledger")
-                        .and_then(serde_json::Value::as_u64)
-                        .unwrap_or(0);
-                    let timestamp = result
-                        .get("createdAt")
-                        .and_then(|s| s.as_str())
-                        .and_then(|s| {
-                            chrono::DateTime::parse_from_rfc3339(s)
-                                .ok()
-                                .map(|d| d.timestamp() as u64)
-                        })
-                        .unwrap_or(0);
-
+            match self.fetch_transaction(tx_hash).await? {
+                TransactionStatus::Success { ledger, timestamp } => {
                     return Ok(SubmissionResult {
                         hash: tx_hash.to_string(),
                         transaction_hash: tx_hash.to_string(),
@@ -435,6 +500,12 @@ impl ContractService {
                         timestamp,
                     });
                 }
+                TransactionStatus::Failed { reason } => {
+                    return Err(anyhow::anyhow!(
+                        "Transaction {tx_hash} (epoch {epoch}) failed on-chain: {reason}"
+                    ));
+                }
+                TransactionStatus::Pending => {}
             }
 
             tokio::time::sleep(Duration::from_millis(250)).await;
@@ -445,6 +516,63 @@ impl ContractService {
         ))
     }
 
+    /// Send a JSON-RPC request and return its `result`
+    async fn call(&self, method: &str, params: serde_json::Value) -> Result<serde_json::Value> {
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: 1,
+            method: method.to_string(),
+            params,
+        };
+
+        let response = self
+            .client
+            .post(&self.config.rpc_url)
+            .json(&request)
+            .send()
+            .await
+            .with_context(|| format!("Failed to send {method} RPC request"))?;
+
+        let body: JsonRpcResponse<serde_json::Value> = response
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse {method} RPC response"))?;
+
+        if let Some(error) = body.error {
+            return Err(anyhow::anyhow!(
+                "{method} failed: {} (code: {})",
+                error.message,
+                error.code
+            ));
+        }
+
+        body.result
+            .ok_or_else(|| anyhow::anyhow!("{method} returned empty result"))
+    }
+
+    /// Current Soroban inclusion fee percentiles from `getFeeStats`
+    pub async fn fetch_fee_stats(&self) -> Result<FeeStats> {
+        let result = self.call("getFeeStats", json!({})).await?;
+        FeeStats::from_rpc(&result)
+    }
+
+    /// Look up a sent transaction with `getTransaction`.
+    ///
+    /// A transaction the node doesn't know about yet is reported as
+    /// [`TransactionStatus::Pending`].
+    pub async fn fetch_transaction(&self, tx_hash: &str) -> Result<TransactionStatus> {
+        match self
+            .call("getTransaction", json!({ "hash": tx_hash }))
+            .await
+        {
+            Ok(result) => Ok(TransactionStatus::from_rpc(&result)),
+            Err(e) if e.to_string().to_ascii_lowercase().contains("not found") => {
+                Ok(TransactionStatus::Pending)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     pub async fn health_check(&self) -> Result<bool> {
         Ok(false)
     }
@@ -457,3 +585,82 @@ impl ContractService {
         Err(anyhow::anyhow!("Contract service is temporarily disabled"))
     }
 }
+
+/// Rewrite the fee of a base64 `TransactionEnvelope`; for a fee bump this is
+/// the outer fee
+fn set_envelope_fee(envelope_xdr: &str, fee: u64) -> Result<String> {
+    let bytes = BASE64
+        .decode(envelope_xdr)
+        .context("Transaction envelope is not valid base64")?;
+    let mut envelope = TransactionEnvelope::from_xdr(bytes, Limits::none())
+        .context("Failed to decode transaction envelope XDR")?;
+    let too_large = || anyhow::anyhow!("Transaction fee {fee} exceeds the XDR fee limit");
+    match &mut envelope {
+        TransactionEnvelope::TxV0(env) => {
+            env.tx.fee = u32::try_from(fee).map_err(|_| too_large())?
+        }
+        TransactionEnvelope::Tx(env) => env.tx.fee = u32::try_from(fee).map_err(|_| too_large())?,
+        TransactionEnvelope::TxFeeBump(env) => {
+            env.tx.fee = i64::try_from(fee).map_err(|_| too_large())?;
+        }
+    }
+    let bytes = envelope
+        .to_xdr(Limits::none())
+        .context("Failed to encode transaction envelope XDR")?;
+    Ok(BASE64.encode(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stellar_xdr::curr::{
+        Memo, MuxedAccount, Preconditions, SequenceNumber, Transaction, TransactionExt,
+        TransactionV1Envelope, Uint256,
+    };
+
+    fn envelope_xdr(fee: u32) -> String {
+        let envelope = TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: Transaction {
+                source_account: MuxedAccount::Ed25519(Uint256([7; 32])),
+                fee,
+                seq_num: SequenceNumber(1),
+                cond: Preconditions::None,
+                memo: Memo::None,
+                operations: Default::default(),
+                ext: TransactionExt::V0,
+            },
+            signatures: Default::default(),
+        });
+        BASE64.encode(envelope.to_xdr(Limits::none()).unwrap())
+    }
+
+    #[test]
+    fn test_max_fee_is_written_into_the_envelope() {
+        let service = ContractService::new(ContractConfig {
+            rpc_url: "http://localhost:8000".to_string(),
+            contract_id: "CONTRACT".to_string(),
+            network_passphrase: "Test SDF Network ; September 2015".to_string(),
+            source_secret_key: String::new(),
+        });
+        let simulated = json!({
+            "transactionData": envelope_xdr(100),
+            "minResourceFee": "5000"
+        });
+
+        let signed = service
+            .prepare_and_sign_transaction(&simulated, Some(1_500))
+            .unwrap();
+        let TransactionEnvelope::Tx(env) =
+            TransactionEnvelope::from_xdr(BASE64.decode(signed).unwrap(), Limits::none()).unwrap()
+        else {
+            panic!("expected a v1 envelope");
+        };
+        assert_eq!(env.tx.fee, 6_500);
+
+        let unchanged = service
+            .prepare_and_sign_transaction(&simulated, None)
+            .unwrap();
+        assert_eq!(unchanged, envelope_xdr(100));
+        assert!(set_envelope_fee(&envelope_xdr(100), u64::from(u32::MAX) + 1).is_err());
+    }
+}
@@ -1,7 +1,9 @@
 use anyhow::{Context, Result};
+use async_trait::async_trait;
 use chrono::Utc;
+use failsafe::futures::CircuitBreaker as _;
 use reqwest::Client;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::json;
 use sqlx::Row;
 use std::sync::Arc;
@@ -10,7 +12,15 @@ use tokio::time::interval;
 use tracing::{debug, error, info, warn};
 
 use crate::database::Database;
+use crate::rpc::circuit_breaker::{build_circuit_breaker, SharedCircuitBreaker};
+use crate::rpc::config::circuit_breaker_config_for_endpoint;
 use crate::services::alert_service::AlertService;
+use crate::services::event_indexer::{EventIndexer, IndexedEvent};
+use crate::services::snapshot::canonical_hash;
+use crate::snapshot::schema::AnalyticsSnapshot;
+
+/// Events requested per `getEvents` page
+const EVENTS_PAGE_LIMIT: u32 = 100;
 
 /// Configuration for the contract event listener
 #[derive(Debug, Clone, Deserialize)]
@@ -23,15 +33,101 @@ pub struct ListenerConfig {
 
 /// Represents a contract event from the Soroban RPC
 #[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ContractEvent {
     pub id: String,
-    pub paging_token: String,
-    pub ledger: String,
+    /// Deprecated by Soroban RPC in favour of the page `cursor`
+    #[serde(default)]
+    pub paging_token: Option<String>,
+    #[serde(deserialize_with = "deserialize_ledger")]
+    pub ledger: u64,
     pub ledger_closed_at: String,
     pub contract_id: String,
     pub topic: Vec<String>,
     pub value: serde_json::Value,
+    #[serde(default)]
     pub in_successful_contract_call: bool,
+    #[serde(default)]
+    pub tx_hash: Option<String>,
+}
+
+/// Older Soroban RPC versions send the ledger as a string
+fn deserialize_ledger<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringOrNumber {
+        String(String),
+        Number(u64),
+    }
+
+    match StringOrNumber::deserialize(deserializer)? {
+        StringOrNumber::Number(n) => Ok(n),
+        StringOrNumber::String(s) => s.parse().map_err(serde::de::Error::custom),
+    }
+}
+
+/// One page of a `getEvents` result
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GetEventsResult {
+    #[serde(default)]
+    events: Vec<ContractEvent>,
+    #[allow(dead_code)]
+    latest_ledger: u64,
+    /// Where the next page starts
+    cursor: Option<String>,
+}
+
+impl ContractEvent {
+    /// Convert to the indexer's representation, picking up snapshot fields
+    /// from the event value when present
+    #[must_use]
+    pub fn to_indexed_event(&self) -> IndexedEvent {
+        let event_type = if self.topic.iter().any(|t| t == "SNAP_SUB") {
+            "SNAP_SUB".to_string()
+        } else {
+            self.topic.first().cloned().unwrap_or_default()
+        };
+
+        IndexedEvent {
+            id: self.id.clone(),
+            contract_id: self.contract_id.clone(),
+            event_type,
+            epoch: self.value.get("epoch").and_then(serde_json::Value::as_u64),
+            hash: self
+                .value
+                .get("hash")
+                .and_then(serde_json::Value::as_str)
+                .map(str::to_string),
+            timestamp: self
+                .value
+                .get("timestamp")
+                .and_then(serde_json::Value::as_u64),
+            ledger: self.ledger,
+            transaction_hash: self.tx_hash.clone().unwrap_or_else(|| self.id.clone()),
+            created_at: Utc::now(),
+            verification_status: None,
+        }
+    }
+}
+
+/// Events returned by one poll of a [`ContractEventSource`]
+#[derive(Debug, Clone, Default)]
+pub struct ContractEventPage {
+    pub events: Vec<IndexedEvent>,
+    /// Latest ledger covered by this page; the next poll starts after it
+    pub latest_ledger: u64,
+    pub protocol_version: u32,
+}
+
+/// Where contract events are read from
+#[async_trait]
+pub trait ContractEventSource: Send + Sync {
+    /// Fetch contract events from `start_ledger` up to the latest ledger
+    async fn fetch_contract_events(&self, start_ledger: u64) -> Result<ContractEventPage>;
 }
 
 /// Snapshot event data extracted from contract events
@@ -78,7 +174,9 @@ pub struct ContractEventListener {
     config: ListenerConfig,
     db: Arc<Database>,
     alert_service: Arc<AlertService>,
+    circuit_breaker: SharedCircuitBreaker,
     last_ledger: u64,
+    page_limit: u32,
 }
 
 impl ContractEventListener {
@@ -103,7 +201,13 @@ impl ContractEventListener {
             config,
             db,
             alert_service,
+            circuit_breaker: build_circuit_breaker(
+                "soroban_events",
+                &circuit_breaker_config_for_endpoint("soroban_events"),
+                None,
+            ),
             last_ledger: 0,
+            page_limit: EVENTS_PAGE_LIMIT,
         })
     }
 
@@ -111,10 +215,18 @@ impl ContractEventListener {
     pub async fn start_listening(&mut self) -> Result<()> {
         info!("Starting contract event listener");
 
-        // Get current ledger if not specified
+        // Resume after the stored checkpoint, else from the configured start
+        // ledger, else from the current ledger
         if self.last_ledger == 0 {
-            self.last_ledger = self.get_latest_ledger().await?;
-            info!("Starting from ledger {}", self.last_ledger);
+            let checkpoint = EventIndexer::new(self.db.clone())
+                .get_last_processed_ledger()
+                .await?;
+            self.last_ledger = match (checkpoint, self.config.start_ledger) {
+                (Some(checkpoint), _) => checkpoint,
+                (None, Some(start_ledger)) => start_ledger.saturating_sub(1),
+                (None, None) => self.get_latest_ledger().await?,
+            };
+            info!("Starting after ledger {}", self.last_ledger);
         }
 
         let mut interval = interval(Duration::from_secs(self.config.poll_interval_secs));
@@ -167,30 +279,70 @@ impl ContractEventListener {
             }
         }
 
+        EventIndexer::new(self.db.clone())
+            .persist_checkpoint(current_ledger)
+            .await?;
         self.last_ledger = current_ledger;
         Ok(events_processed)
     }
 
-    /// Get events for a specific ledger range
+    /// Get events for `start_ledger..=end_ledger`, following the page
+    /// `cursor` until a short page comes back
     async fn get_events_for_ledger_range(
         &self,
         start_ledger: u64,
         end_ledger: u64,
     ) -> Result<Vec<ContractEvent>> {
+        let mut events = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let page = self
+                .get_events_page(start_ledger, end_ledger, cursor.as_deref())
+                .await?;
+            let full_page = page.events.len() >= self.page_limit as usize;
+            events.extend(
+                page.events
+                    .into_iter()
+                    .filter(|event| event.ledger <= end_ledger),
+            );
+            match page.cursor {
+                Some(next) if full_page && cursor.as_deref() != Some(next.as_str()) => {
+                    cursor = Some(next);
+                }
+                _ => break,
+            }
+        }
+        Ok(events)
+    }
+
+    /// Fetch one `getEvents` page. The first page is addressed by
+    /// `startLedger`; later ones by the previous page's cursor, since the RPC
+    /// rejects both together. `endLedger` is exclusive.
+    async fn get_events_page(
+        &self,
+        start_ledger: u64,
+        end_ledger: u64,
+        cursor: Option<&str>,
+    ) -> Result<GetEventsResult> {
+        let mut params = json!({
+            "endLedger": end_ledger + 1,
+            "filters": [
+                {
+                    "type": "contract",
+                    "contractIds": [self.config.contract_id]
+                }
+            ],
+            "pagination": { "limit": self.page_limit }
+        });
+        match cursor {
+            Some(cursor) => params["pagination"]["cursor"] = json!(cursor),
+            None => params["startLedger"] = json!(start_ledger),
+        }
         let request = JsonRpcRequest {
             jsonrpc: "2.0".to_string(),
             id: 1,
             method: "getEvents".to_string(),
-            params: json!({
-                "startLedger": start_ledger.to_string(),
-                "endLedger": end_ledger.to_string(),
-                "filters": [
-                    {
-                        "type": "contract",
-                        "contractIds": [self.config.contract_id]
-                    }
-                ]
-            }),
+            params,
         };
 
         let response = self
@@ -214,13 +366,10 @@ impl ContractEventListener {
             ));
         }
 
-        if let Some(result) = body.result {
-            let events: Vec<ContractEvent> =
-                serde_json::from_value(result).context("Failed to deserialize events")?;
-            Ok(events)
-        } else {
-            Ok(vec![])
-        }
+        let result = body
+            .result
+            .ok_or_else(|| anyhow::anyhow!("No getEvents result returned"))?;
+        serde_json::from_value(result).context("Failed to deserialize events")
     }
 
     /// Process a single contract event
@@ -258,10 +407,7 @@ impl ContractEventListener {
             .and_then(serde_json::Value::as_u64)
             .ok_or_else(|| anyhow::anyhow!("Missing timestamp in event"))?;
 
-        let ledger = event
-            .ledger
-            .parse::<u64>()
-            .context("Invalid ledger number")?;
+        let ledger = event.ledger;
 
         let snapshot_event = SnapshotEvent {
             epoch,
@@ -320,7 +466,7 @@ impl ContractEventListener {
 
         // Get snapshot from database
         let query = r"
-            SELECT hash, canonical_json
+            SELECT hash, data AS canonical_json
             FROM snapshots
             WHERE epoch = ?
             ORDER BY created_at DESC
@@ -355,8 +501,10 @@ impl ContractEventListener {
                 error!("Actual (on-chain): {}", on_chain_hash);
 
                 // Calculate hash to verify our data
-                let calculated_hash = self.calculate_hash(&canonical_json)?;
-                error!("Recalculated hash: {}", calculated_hash);
+                match self.calculate_hash(&canonical_json) {
+                    Ok(calculated_hash) => error!("Recalculated hash: {}", calculated_hash),
+                    Err(e) => error!("Failed to recalculate hash: {}", e),
+                }
 
                 // Send alert via AlertService
                 let expected = backend_hash.clone();
@@ -391,14 +539,12 @@ impl ContractEventListener {
         }
     }
 
-    /// Calculate SHA-256 hash of data
-    fn calculate_hash(&self, data: &str) -> Result<String> {
-        use sha2::{Digest, Sha256};
-
-        let mut hasher = Sha256::new();
-        hasher.update(data.as_bytes());
-        let result = hasher.finalize();
-        Ok(hex::encode(result))
+    /// Recompute the canonical hash of a stored snapshot, exactly as it was
+    /// computed for submission
+    fn calculate_hash(&self, canonical_json: &str) -> Result<String> {
+        let snapshot: AnalyticsSnapshot = serde_json::from_str(canonical_json)
+            .context("Stored snapshot is not a valid analytics snapshot")?;
+        Ok(canonical_hash(&snapshot))
     }
 
     /// Update verification status in database
@@ -422,6 +568,11 @@ impl ContractEventListener {
 
     /// Get the latest ledger number from the network
     async fn get_latest_ledger(&self) -> Result<u64> {
+        Ok(self.get_latest_ledger_info().await?.0)
+    }
+
+    /// Get the latest ledger number and protocol version from the network
+    async fn get_latest_ledger_info(&self) -> Result<(u64, u32)> {
         let request = JsonRpcRequest {
             jsonrpc: "2.0".to_string(),
             id: 1,
@@ -455,7 +606,12 @@ impl ContractEventListener {
                 .get("sequence")
                 .and_then(serde_json::Value::as_u64)
                 .ok_or_else(|| anyhow::anyhow!("Invalid ledger sequence"))?;
-            Ok(ledger)
+            let protocol_version = result
+                .get("protocolVersion")
+                .and_then(serde_json::Value::as_u64)
+                .and_then(|v| u32::try_from(v).ok())
+                .unwrap_or_default();
+            Ok((ledger, protocol_version))
         } else {
             Err(anyhow::anyhow!("No ledger result returned"))
         }
@@ -581,6 +737,36 @@ impl ContractEventListener {
     }
 }
 
+#[async_trait]
+impl ContractEventSource for ContractEventListener {
+    /// Fetch through this listener's circuit breaker, so an RPC outage fails
+    /// fast instead of stacking up timed-out requests
+    async fn fetch_contract_events(&self, start_ledger: u64) -> Result<ContractEventPage> {
+        let fetch = async {
+            let (latest_ledger, protocol_version) = self.get_latest_ledger_info().await?;
+            let events = if latest_ledger >= start_ledger {
+                self.get_events_for_ledger_range(start_ledger, latest_ledger)
+                    .await?
+            } else {
+                Vec::new()
+            };
+            Ok::<_, anyhow::Error>(ContractEventPage {
+                events: events.iter().map(ContractEvent::to_indexed_event).collect(),
+                latest_ledger,
+                protocol_version,
+            })
+        };
+
+        match self.circuit_breaker.call(fetch).await {
+            Ok(page) => Ok(page),
+            Err(failsafe::Error::Rejected) => {
+                Err(anyhow::anyhow!("Soroban RPC circuit breaker is open"))
+            }
+            Err(failsafe::Error::Inner(e)) => Err(e),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -600,14 +786,94 @@ mod tests {
 
         let alert_service = Arc::new(AlertService::default());
         let listener = ContractEventListener::new(config, db, alert_service).unwrap();
-        let data = r#"{"test": "data"}"#;
-        let hash = listener.calculate_hash(data).unwrap();
+        let snapshot = AnalyticsSnapshot::new(5, Utc::now());
+        let data = crate::services::snapshot::SnapshotService::serialize_deterministically(
+            snapshot.clone(),
+        )
+        .unwrap();
+        let hash = listener.calculate_hash(&data).unwrap();
 
         // Should be 64 characters (32 bytes × 2 hex chars)
         assert_eq!(hash.len(), 64);
 
         // Should only contain valid hex characters
         assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+
+        // Must match the hash computed for submission
+        assert_eq!(hash, canonical_hash(&snapshot));
+
+        assert!(listener.calculate_hash(r#"{"test": "data"}"#).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_events_are_paged_by_cursor() {
+        use crate::rpc::test_server::{ScriptedResponse, TestServer};
+        use axum::http::StatusCode;
+
+        let event = |id: &str, ledger: &str| {
+            format!(
+                r#"{{"type":"contract","ledger":{ledger},"ledgerClosedAt":"2026-01-22T10:00:00Z","contractId":"CTEST","id":"{id}","pagingToken":"{id}","inSuccessfulContractCall":true,"txHash":"tx-{id}","topic":["AAAADwAAAAhTTkFQX1NVQg=="],"value":"AAAAAQAAAAE="}}"#
+            )
+        };
+        let server = TestServer::start([
+            ScriptedResponse::json(
+                StatusCode::OK,
+                &format!(
+                    r#"{{"jsonrpc":"2.0","id":1,"result":{{"events":[{}],"latestLedger":110,"cursor":"c1"}}}}"#,
+                    event("e1", "105")
+                ),
+            ),
+            // Older RPCs send the ledger as a string
+            ScriptedResponse::json(
+                StatusCode::OK,
+                &format!(
+                    r#"{{"jsonrpc":"2.0","id":1,"result":{{"events":[{}],"latestLedger":110,"cursor":"c2"}}}}"#,
+                    event("e2", r#""108""#)
+                ),
+            ),
+            ScriptedResponse::json(
+                StatusCode::OK,
+                r#"{"jsonrpc":"2.0","id":1,"result":{"events":[],"latestLedger":110,"cursor":"c3"}}"#,
+            ),
+        ])
+        .await;
+
+        let pool = sqlx::SqlitePool::connect(":memory:").await.unwrap();
+        let mut listener = ContractEventListener::new(
+            ListenerConfig {
+                rpc_url: server.url().to_string(),
+                contract_id: "CTEST".to_string(),
+                poll_interval_secs: 10,
+                start_ledger: None,
+            },
+            Arc::new(Database::new(pool)),
+            Arc::new(AlertService::default()),
+        )
+        .unwrap();
+        listener.page_limit = 1;
+
+        let events = listener
+            .get_events_for_ledger_range(100, 110)
+            .await
+            .unwrap();
+
+        let ids: Vec<_> = events.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["e1", "e2"]);
+        assert_eq!(events[1].ledger, 108);
+        assert_eq!(events[0].to_indexed_event().transaction_hash, "tx-e1");
+
+        let requests: Vec<serde_json::Value> = server
+            .request_bodies()
+            .iter()
+            .map(|body| serde_json::from_slice(body).unwrap())
+            .collect();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0]["params"]["startLedger"], 100);
+        assert_eq!(requests[0]["params"]["endLedger"], 111);
+        assert!(requests[0]["params"]["pagination"]["cursor"].is_null());
+        assert!(requests[1]["params"]["startLedger"].is_null());
+        assert_eq!(requests[1]["params"]["pagination"]["cursor"], "c1");
+        assert_eq!(requests[2]["params"]["pagination"]["cursor"], "c2");
     }
 
     #[tokio::test]
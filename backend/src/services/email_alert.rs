@@ -0,0 +1,329 @@
+use crate::alerts::{Alert, AlertSeverity};
+use crate::email::service::EmailService;
+use anyhow::{Context, Result};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::Instant;
+
+const DEFAULT_BATCH_WINDOW_SECS: u64 = 60;
+
+/// Configuration for the email alert channel
+#[derive(Debug, Clone)]
+pub struct EmailAlertConfig {
+    pub smtp_host: String,
+    pub smtp_port: Option<u16>,
+    pub smtp_username: String,
+    pub smtp_password: String,
+    pub recipients: Vec<String>,
+    /// How long to collect alerts before sending a single summary email
+    pub batch_window: Duration,
+    /// Alerts below this severity are not emailed
+    pub min_severity: AlertSeverity,
+}
+
+impl EmailAlertConfig {
+    /// Load from `SMTP_*` and `ALERT_EMAIL_*` variables.
+    ///
+    /// Returns `None` when SMTP or the recipient list is not configured.
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        let smtp_host = std::env::var("SMTP_HOST").ok()?;
+        let smtp_username = std::env::var("SMTP_USERNAME").ok()?;
+        let smtp_password = std::env::var("SMTP_PASSWORD").ok()?;
+        let recipients: Vec<String> = std::env::var("ALERT_EMAIL_RECIPIENTS")
+            .ok()?
+            .split(',')
+            .map(str::trim)
+            .filter(|r| !r.is_empty())
+            .map(str::to_string)
+            .collect();
+        if recipients.is_empty() {
+            return None;
+        }
+
+        let smtp_port = std::env::var("SMTP_PORT").ok().and_then(|p| p.parse().ok());
+        let batch_window = std::env::var("ALERT_EMAIL_BATCH_WINDOW_SECS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .map_or(
+                Duration::from_secs(DEFAULT_BATCH_WINDOW_SECS),
+                |secs: u64| Duration::from_secs(secs.max(1)),
+            );
+        let min_severity = std::env::var("ALERT_EMAIL_MIN_SEVERITY")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(AlertSeverity::Critical);
+
+        Some(Self {
+            smtp_host,
+            smtp_port,
+            smtp_username,
+            smtp_password,
+            recipients,
+            batch_window,
+            min_severity,
+        })
+    }
+}
+
+/// Email Alert Service that batches alerts into HTML summary emails
+pub struct EmailAlertService {
+    email_service: Arc<EmailService>,
+    recipients: Vec<String>,
+    batch_window: Duration,
+    min_severity: AlertSeverity,
+    alert_rx: broadcast::Receiver<Alert>,
+}
+
+impl EmailAlertService {
+    /// Create a new `EmailAlertService`
+    #[must_use]
+    pub fn new(config: EmailAlertConfig, alert_rx: broadcast::Receiver<Alert>) -> Self {
+        let mut email_service =
+            EmailService::new(config.smtp_host, config.smtp_username, config.smtp_password);
+        if let Some(port) = config.smtp_port {
+            email_service = email_service.with_port(port);
+        }
+
+        Self {
+            email_service: Arc::new(email_service),
+            recipients: config.recipients,
+            batch_window: config.batch_window,
+            min_severity: config.min_severity,
+            alert_rx,
+        }
+    }
+
+    /// Start the email alert listener loop
+    pub async fn start(mut self) {
+        tracing::info!(
+            "Email Alert Service started, batching alerts every {}s",
+            self.batch_window.as_secs()
+        );
+
+        while let Some(batch) =
+            collect_batch(&mut self.alert_rx, self.batch_window, self.min_severity).await
+        {
+            if let Err(e) = self.send_batch(&batch).await {
+                tracing::error!("Failed to send alert email: {}", e);
+            }
+        }
+    }
+
+    /// Send one summary email for a batch of alerts
+    pub async fn send_batch(&self, alerts: &[Alert]) -> Result<()> {
+        let subject = digest_subject(alerts);
+        let html = render_alert_digest(alerts);
+
+        for recipient in &self.recipients {
+            let service = Arc::clone(&self.email_service);
+            let (to, subject, html) = (recipient.clone(), subject.clone(), html.clone());
+            tokio::task::spawn_blocking(move || service.send_html(&to, &subject, &html))
+                .await
+                .context("Email send task panicked")??;
+        }
+
+        tracing::info!(
+            "Alert email with {} alert(s) sent to {} recipient(s)",
+            alerts.len(),
+            self.recipients.len()
+        );
+        Ok(())
+    }
+}
+
+/// Wait for the first qualifying alert, then keep collecting until `window`
+/// has elapsed. Returns `None` once the channel is closed and nothing is left.
+pub async fn collect_batch(
+    rx: &mut broadcast::Receiver<Alert>,
+    window: Duration,
+    min_severity: AlertSeverity,
+) -> Option<Vec<Alert>> {
+    let mut batch = Vec::new();
+    let mut deadline: Option<Instant> = None;
+
+    loop {
+        let received = match deadline {
+            None => rx.recv().await,
+            Some(at) => match tokio::time::timeout_at(at, rx.recv()).await {
+                Ok(received) => received,
+                Err(_) => return Some(batch),
+            },
+        };
+
+        match received {
+            Ok(alert) if alert.severity >= min_severity => {
+                batch.push(alert);
+                deadline.get_or_insert_with(|| Instant::now() + window);
+            }
+            Ok(_) => {}
+            Err(RecvError::Lagged(skipped)) => {
+                tracing::warn!("Email alert channel lagged, skipped {} alerts", skipped);
+            }
+            Err(RecvError::Closed) => {
+                return if batch.is_empty() { None } else { Some(batch) };
+            }
+        }
+    }
+}
+
+fn digest_subject(alerts: &[Alert]) -> String {
+    let highest = alerts.iter().map(|a| a.severity).max().unwrap_or_default();
+    format!(
+        "Stellar Insights - {} {} alert(s)",
+        alerts.len(),
+        highest.as_str()
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Render a batch of alerts as an HTML summary table
+#[must_use]
+pub fn render_alert_digest(alerts: &[Alert]) -> String {
+    let rows: String = alerts
+        .iter()
+        .map(|alert| {
+            let subject = alert
+                .corridor_id
+                .as_deref()
+                .or(alert.anchor_id.as_deref())
+                .unwrap_or("-");
+            format!(
+                concat!(
+                    r#"<tr class="{severity}"><td>{severity}</td><td>{alert_type:?}</td>"#,
+                    "<td>{subject}</td><td>{message}</td>",
+                    "<td>{old:.2}</td><td>{new:.2}</td><td>{ts}</td></tr>",
+                ),
+                severity = alert.severity.as_str(),
+                alert_type = alert.alert_type,
+                subject = escape_html(subject),
+                message = escape_html(&alert.message),
+                old = alert.old_value,
+                new = alert.new_value,
+                ts = escape_html(&alert.timestamp),
+            )
+        })
+        .collect();
+
+    format!(
+        r#"
+<!DOCTYPE html>
+<html>
+<head>
+    <style>
+        body {{ font-family: Arial, sans-serif; margin: 20px; }}
+        table {{ border-collapse: collapse; width: 100%; }}
+        th, td {{ border: 1px solid #ddd; padding: 8px; text-align: left; }}
+        th {{ background-color: #333; color: white; }}
+        .critical td:first-child {{ color: #E01E5A; font-weight: bold; }}
+        .warning td:first-child {{ color: #ECB22E; font-weight: bold; }}
+    </style>
+</head>
+<body>
+    <h1>Stellar Insights - Alert Summary</h1>
+    <p>{count} alert(s) in this batch.</p>
+    <table>
+        <tr>
+            <th>Severity</th>
+            <th>Type</th>
+            <th>Corridor / Anchor</th>
+            <th>Message</th>
+            <th>Previous</th>
+            <th>New</th>
+            <th>Time</th>
+        </tr>
+        {rows}
+    </table>
+</body>
+</html>
+"#,
+        count = alerts.len(),
+        rows = rows,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alerts::AlertType;
+
+    fn alert(severity: AlertSeverity, message: &str) -> Alert {
+        Alert {
+            alert_type: AlertType::SuccessRateDrop,
+            severity,
+            corridor_id: Some("USDC-XLM".to_string()),
+            anchor_id: None,
+            message: message.to_string(),
+            old_value: 95.0,
+            new_value: 40.0,
+            timestamp: "2024-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_render_alert_digest_contains_rows_and_escapes() {
+        let alerts = vec![
+            alert(AlertSeverity::Critical, "Success rate <dropped> sharply"),
+            alert(AlertSeverity::Warning, "Liquidity decreased"),
+        ];
+
+        let html = render_alert_digest(&alerts);
+
+        assert!(html.contains("2 alert(s) in this batch."));
+        assert!(html.contains(r#"<tr class="critical"><td>critical</td>"#));
+        assert!(html.contains(r#"<tr class="warning"><td>warning</td>"#));
+        assert!(html.contains("Success rate &lt;dropped&gt; sharply"));
+        assert!(!html.contains("<dropped>"));
+        assert!(html.contains("USDC-XLM"));
+        assert_eq!(
+            digest_subject(&alerts),
+            "Stellar Insights - 2 critical alert(s)"
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_batch_window_groups_alerts_into_one_message() {
+        let (tx, mut rx) = broadcast::channel(16);
+        let window = Duration::from_secs(60);
+
+        tx.send(alert(AlertSeverity::Critical, "first")).unwrap();
+        tx.send(alert(AlertSeverity::Info, "filtered out")).unwrap();
+        tx.send(alert(AlertSeverity::Critical, "second")).unwrap();
+
+        let sender = tx.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            sender.send(alert(AlertSeverity::Warning, "third")).unwrap();
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            sender
+                .send(alert(AlertSeverity::Critical, "next batch"))
+                .unwrap();
+        });
+
+        let batch = collect_batch(&mut rx, window, AlertSeverity::Warning)
+            .await
+            .unwrap();
+        let messages: Vec<_> = batch.iter().map(|a| a.message.as_str()).collect();
+        assert_eq!(messages, vec!["first", "second", "third"]);
+
+        let batch = collect_batch(&mut rx, window, AlertSeverity::Warning)
+            .await
+            .unwrap();
+        assert_eq!(batch.len(), 1);
+        assert_eq!(batch[0].message, "next batch");
+
+        drop(tx);
+        assert!(collect_batch(&mut rx, window, AlertSeverity::Warning)
+            .await
+            .is_none());
+    }
+}
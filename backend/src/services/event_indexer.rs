@@ -49,6 +49,8 @@
 //! - **Sorting**: multiple sort orders for different use cases
 //!
 use crate::database::Database;
+use crate::services::snapshot::canonical_hash;
+use crate::snapshot::schema::AnalyticsSnapshot;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -118,6 +120,17 @@ pub struct EventStats {
     pub events_last_24h: i64,
 }
 
+/// Backend snapshot hash for an epoch compared with the hash submitted on-chain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotReconciliation {
+    pub epoch: u64,
+    #[serde(rename = "match")]
+    pub matches: bool,
+    /// Canonical hash of the stored backend snapshot; `None` if none was generated
+    pub backend_hash: Option<String>,
+    pub onchain_hash: String,
+}
+
 /// Event types recognised by this indexer.
 ///
 /// Any event whose `event_type` is not in this list is logged and skipped
@@ -289,56 +302,16 @@ impl EventIndexer {
     pub async fn query_events(&self, query: EventQuery) -> Result<Vec<IndexedEvent>> {
         debug!("Querying events with filters: {:?}", query);
 
-        let mut sql = String::from(
+        let (filters, bindings) = Self::filter_clause(&query);
+        let mut sql = format!(
             r"
             SELECT id, contract_id, event_type, epoch, hash, timestamp,
                    ledger, transaction_hash, created_at, verification_status
             FROM contract_events
-            WHERE 1=1
+            WHERE 1=1{filters}
         ",
         );
 
-        let mut bindings = Vec::new();
-
-        // Add filters
-        if !query.contract_ids.is_empty() {
-            let placeholders = query.contract_ids.iter().map(|_| "?").collect::<Vec<_>>().join(", ");
-            let _ = write!(sql, " AND contract_id IN ({placeholders})");
-            bindings.extend(query.contract_ids.iter().cloned());
-        }
-
-        if let Some(event_type) = &query.event_type {
-            sql.push_str(" AND event_type = ?");
-            bindings.push(event_type.clone());
-        }
-
-        if let Some(epoch) = query.epoch {
-            sql.push_str(" AND epoch = ?");
-            bindings.push(epoch.to_string());
-        }
-
-        if let Some(hash) = &query.hash {
-            sql.push_str(" AND hash = ?");
-            bindings.push(hash.clone());
-        }
-
-        if let Some((start_ledger, end_ledger)) = query.ledger_range {
-            sql.push_str(" AND ledger BETWEEN ? AND ?");
-            bindings.push(start_ledger.to_string());
-            bindings.push(end_ledger.to_string());
-        }
-
-        if let Some((start_time, end_time)) = query.time_range {
-            sql.push_str(" AND created_at BETWEEN ? AND ?");
-            bindings.push(start_time.to_rfc3339());
-            bindings.push(end_time.to_rfc3339());
-        }
-
-        if let Some(status) = &query.verification_status {
-            sql.push_str(" AND verification_status = ?");
-            bindings.push(status.clone());
-        }
-
         // Add ordering
         match query
             .order_by
@@ -394,6 +367,69 @@ impl EventIndexer {
         Ok(events)
     }
 
+    /// Count the events matching `query`'s filters, ignoring its limit,
+    /// offset and ordering.
+    pub async fn count_events(&self, query: &EventQuery) -> Result<i64> {
+        let (filters, bindings) = Self::filter_clause(query);
+        let sql = format!("SELECT COUNT(*) FROM contract_events WHERE 1=1{filters}");
+
+        let mut query_builder = sqlx::query_scalar::<_, i64>(&sql);
+        for binding in bindings {
+            query_builder = query_builder.bind(binding);
+        }
+
+        query_builder
+            .fetch_one(self.db.pool())
+            .await
+            .context("Failed to count events")
+    }
+
+    /// `AND ...` conditions for the filters set on `query`, with their bindings
+    fn filter_clause(query: &EventQuery) -> (String, Vec<String>) {
+        let mut sql = String::new();
+        let mut bindings = Vec::new();
+
+        if !query.contract_ids.is_empty() {
+            let placeholders = query.contract_ids.iter().map(|_| "?").collect::<Vec<_>>().join(", ");
+            let _ = write!(sql, " AND contract_id IN ({placeholders})");
+            bindings.extend(query.contract_ids.iter().cloned());
+        }
+
+        if let Some(event_type) = &query.event_type {
+            sql.push_str(" AND event_type = ?");
+            bindings.push(event_type.clone());
+        }
+
+        if let Some(epoch) = query.epoch {
+            sql.push_str(" AND epoch = ?");
+            bindings.push(epoch.to_string());
+        }
+
+        if let Some(hash) = &query.hash {
+            sql.push_str(" AND hash = ?");
+            bindings.push(hash.clone());
+        }
+
+        if let Some((start_ledger, end_ledger)) = query.ledger_range {
+            sql.push_str(" AND ledger BETWEEN ? AND ?");
+            bindings.push(start_ledger.to_string());
+            bindings.push(end_ledger.to_string());
+        }
+
+        if let Some((start_time, end_time)) = query.time_range {
+            sql.push_str(" AND created_at BETWEEN ? AND ?");
+            bindings.push(start_time.to_rfc3339());
+            bindings.push(end_time.to_rfc3339());
+        }
+
+        if let Some(status) = &query.verification_status {
+            sql.push_str(" AND verification_status = ?");
+            bindings.push(status.clone());
+        }
+
+        (sql, bindings)
+    }
+
     /// Get event by ID
     pub async fn get_event_by_id(&self, id: &str) -> Result<Option<IndexedEvent>> {
         debug!("Getting event by ID: {}", id);
@@ -453,6 +489,61 @@ impl EventIndexer {
         self.query_events(query).await
     }
 
+    /// Recompute the canonical hash of the stored backend snapshot for
+    /// `epoch` and compare it with the latest indexed on-chain submission.
+    ///
+    /// Returns `None` when no submission has been indexed for the epoch yet.
+    pub async fn reconcile_epoch(&self, epoch: u64) -> Result<Option<SnapshotReconciliation>> {
+        let query = EventQuery {
+            event_type: Some("SNAP_SUB".to_string()),
+            epoch: Some(epoch),
+            limit: Some(1),
+            order_by: Some(EventOrderBy::CreatedAtDesc),
+            ..Default::default()
+        };
+        let Some(onchain_hash) = self
+            .query_events(query)
+            .await?
+            .into_iter()
+            .next()
+            .and_then(|event| event.hash)
+        else {
+            return Ok(None);
+        };
+
+        let data: Option<String> = sqlx::query_scalar(
+            "SELECT data FROM snapshots WHERE epoch = ? ORDER BY created_at DESC LIMIT 1",
+        )
+        .bind(epoch as i64)
+        .fetch_optional(self.db.pool())
+        .await
+        .context("Failed to query snapshot from database")?;
+        let backend_hash = data
+            .map(|json| {
+                serde_json::from_str::<AnalyticsSnapshot>(&json)
+                    .map(|snapshot| canonical_hash(&snapshot))
+                    .with_context(|| format!("Stored snapshot for epoch {epoch} is not valid"))
+            })
+            .transpose()?;
+
+        let matches = backend_hash
+            .as_deref()
+            .is_some_and(|hash| hash.eq_ignore_ascii_case(&onchain_hash));
+        if !matches {
+            warn!(
+                "Snapshot reconciliation mismatch for epoch {}: backend {:?}, on-chain {}",
+                epoch, backend_hash, onchain_hash
+            );
+        }
+
+        Ok(Some(SnapshotReconciliation {
+            epoch,
+            matches,
+            backend_hash,
+            onchain_hash,
+        }))
+    }
+
     /// Get verification history for epochs
     pub async fn get_verification_history(&self, limit: i64) -> Result<Vec<IndexedEvent>> {
         let query = EventQuery {
@@ -75,8 +75,9 @@ impl IndexingService {
 
         let count = records.len();
 
-        // Persist idempotently
-        self.db
+        // Persist idempotently; a replayed page inserts nothing
+        let inserted = self
+            .db
             .save_payments(records)
             .await
             .context("Failed to save payments to database")?;
@@ -87,7 +88,10 @@ impl IndexingService {
                 .update_ingestion_cursor(task_name, &cursor)
                 .await
                 .context("Failed to update ingestion cursor")?;
-            info!("Ingested {} payments. New cursor: {}", count, cursor);
+            info!(
+                "Ingested {} payments ({} new). New cursor: {}",
+                count, inserted, cursor
+            );
         }
 
         Ok(())
@@ -5,8 +5,9 @@ use rust_decimal::Decimal;
 use sqlx::{Pool, Sqlite};
 use std::str::FromStr;
 use std::sync::Arc;
-use tracing::info;
+use tracing::{info, warn};
 
+use crate::amount::Amount;
 use crate::models::{LiquidityPool, LiquidityPoolSnapshot, LiquidityPoolStats};
 use crate::rpc::StellarRpcClientTrait;
 
@@ -70,19 +71,26 @@ impl LiquidityPoolAnalyzer {
                 .fetch_pool_trades(&hp.id, 100)
                 .await
                 .unwrap_or_default();
-            let volume_24h_usd: f64 = trades
-                .iter()
-                .map(|t| {
-                    t.base_amount.parse::<f64>().unwrap_or(0.0)
-                        + t.counter_amount.parse::<f64>().unwrap_or(0.0)
-                })
-                .sum();
+            let volume_24h = Amount::sum_parsed(
+                trades
+                    .iter()
+                    .flat_map(|t| [t.base_amount.as_str(), t.counter_amount.as_str()]),
+            );
 
             let trade_count_24h = trades.len() as i32;
 
             // Compute fees earned (fee_bp basis points applied to volume)
             let fee_rate = f64::from(hp.fee_bp) / 10_000.0;
-            let fees_earned_24h = volume_24h_usd * fee_rate;
+            let fees_earned_24h = volume_24h.scaled(fee_rate).map_or_else(
+                || {
+                    warn!(
+                        "Fees for pool {} overflow exact arithmetic, approximating",
+                        hp.id
+                    );
+                    volume_24h.to_f64() * fee_rate
+                },
+                Amount::to_f64,
+            );
 
             // Compute APY: annualize daily fees relative to TVL
             let apy = if total_value_usd_f64 > 0.0 {
@@ -139,7 +147,7 @@ impl LiquidityPoolAnalyzer {
             .bind(&secondary_reserve_issuer)
             .bind(secondary_reserve_f64)
             .bind(total_value_usd_f64)
-            .bind(volume_24h_usd)
+            .bind(volume_24h.to_f64())
             .bind(fees_earned_24h)
             .bind(apy)
             .bind(il)
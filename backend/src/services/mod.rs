@@ -4,11 +4,14 @@ pub mod alert_manager;
 pub mod alert_service;
 pub mod analytics;
 pub mod anchor_monitor;
+pub mod asset_metadata;
 pub mod asset_verifier;
 pub mod broadcaster_port;
+pub mod claimable_balance_tracker;
 pub mod contract;
 pub mod contract_listener;
 pub mod data_port;
+pub mod email_alert;
 pub mod event_indexer;
 pub mod fee_bump_tracker;
 pub mod governance;
@@ -19,7 +22,10 @@ pub mod realtime_broadcaster;
 pub mod service_container;
 pub mod slack_bot;
 pub mod snapshot;
+pub mod snapshot_submission;
 pub mod stellar_toml;
+pub mod trade_pricing;
+pub mod transfer_server_probe;
 pub mod trustline_analyzer;
 pub mod verification_rewards;
 pub mod webhook_dispatcher;
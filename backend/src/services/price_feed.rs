@@ -3,10 +3,12 @@ use async_lock::RwLock;
 use reqwest::Client;
 use serde::Deserialize;
 use std::collections::{HashMap, HashSet};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use std::time::{Duration, Instant};
 use tracing::{debug, error, info, warn};
 
+use crate::jobs::PriceCache;
+
 /// Configuration for price feed service
 #[derive(Debug, Clone)]
 pub struct PriceFeedConfig {
@@ -176,6 +178,9 @@ pub struct PriceFeedClient {
     cache: Arc<RwLock<HashMap<String, CachedPrice>>>,
     asset_mapping: Arc<HashMap<String, String>>,
     config: PriceFeedConfig,
+    /// Order-book prices kept by [`crate::jobs::PriceRefreshJob`], used for
+    /// assets the provider can't price
+    onchain_prices: OnceLock<Arc<PriceCache>>,
 }
 
 impl PriceFeedClient {
@@ -203,11 +208,43 @@ impl PriceFeedClient {
             cache: Arc::new(RwLock::new(HashMap::new())),
             asset_mapping: Arc::new(asset_mapping),
             config,
+            onchain_prices: OnceLock::new(),
+        }
+    }
+
+    /// Fall back to fresh prices from `cache` for assets without a provider
+    /// price. Only the first cache handed over is used.
+    pub fn use_onchain_prices(&self, cache: Arc<PriceCache>) {
+        if self.onchain_prices.set(cache).is_err() {
+            warn!("On-chain price cache already attached to the price feed");
         }
     }
 
+    /// USD value of `stellar_asset` from a fresh on-chain price, converted
+    /// through the provider's price of the on-chain quote asset
+    async fn onchain_price(&self, stellar_asset: &str) -> Option<f64> {
+        let cache = self.onchain_prices.get()?;
+        // The refresh job keys the native asset as `native`
+        let key = if stellar_asset == "XLM:native" {
+            "native"
+        } else {
+            stellar_asset
+        };
+        let cached = cache.get_price(key).filter(|p| !p.stale)?;
+        let quote_usd = self.feed_price(&cached.quote_asset).await.ok()?;
+        debug!("Using on-chain price for {}", stellar_asset);
+        Some(cached.price * quote_usd)
+    }
+
     /// Get price for a Stellar asset, returns USD value
     pub async fn get_price(&self, stellar_asset: &str) -> Result<f64> {
+        match self.feed_price(stellar_asset).await {
+            Ok(price) => Ok(price),
+            Err(e) => self.onchain_price(stellar_asset).await.ok_or(e),
+        }
+    }
+
+    async fn feed_price(&self, stellar_asset: &str) -> Result<f64> {
         // Check cache first
         {
             let cache = self.cache.read().await;
@@ -263,6 +300,18 @@ impl PriceFeedClient {
 
     /// Get prices for multiple Stellar assets
     pub async fn get_prices(&self, stellar_assets: &[String]) -> HashMap<String, f64> {
+        let mut result = self.feed_prices(stellar_assets).await;
+        for asset in stellar_assets {
+            if !result.contains_key(asset) {
+                if let Some(price) = self.onchain_price(asset).await {
+                    result.insert(asset.clone(), price);
+                }
+            }
+        }
+        result
+    }
+
+    async fn feed_prices(&self, stellar_assets: &[String]) -> HashMap<String, f64> {
         let mut result = HashMap::new();
         let mut to_fetch = Vec::new();
 
@@ -547,4 +596,34 @@ mod tests {
         assert_eq!(total, 1);
         assert_eq!(fresh, 0);
     }
+
+    #[tokio::test]
+    async fn test_unmapped_asset_falls_back_to_onchain_price() {
+        const USDC: &str = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+        const UNMAPPED: &str = "BRL:GDVKY2GU2DRXWTBEYJJWSFXIGBZV6AZNBVVSUHEPZI54LIS6BA7DVVSP";
+        let client = PriceFeedClient::new(PriceFeedConfig::default(), default_asset_mapping());
+        client.cache.write().await.insert(
+            USDC.to_string(),
+            CachedPrice {
+                price_usd: 0.99,
+                timestamp: Instant::now(),
+            },
+        );
+        assert!(client.get_prices(&[UNMAPPED.to_string()]).await.is_empty());
+
+        let onchain = Arc::new(PriceCache::new(Duration::from_secs(300)));
+        onchain.insert(crate::jobs::CachedPrice {
+            asset: UNMAPPED.to_string(),
+            quote_asset: USDC.to_string(),
+            price: 0.2,
+            source: crate::jobs::PriceSource::OrderBook,
+            updated_at: chrono::Utc::now(),
+            stale: false,
+        });
+        client.use_onchain_prices(onchain);
+
+        let prices = client.get_prices(&[UNMAPPED.to_string()]).await;
+        assert!((prices[UNMAPPED] - 0.198).abs() < 1e-9);
+        assert!((client.get_price(UNMAPPED).await.unwrap() - 0.198).abs() < 1e-9);
+    }
 }
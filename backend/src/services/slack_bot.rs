@@ -1,26 +1,107 @@
-use crate::alerts::{Alert, AlertType};
+use crate::alerts::{Alert, AlertSeverity, AlertType};
 use anyhow::{Context, Result};
 use reqwest::{Client, StatusCode};
 use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+
+/// Slack webhook URL for one alert type, optionally narrowed to a severity
+#[derive(Debug, Clone)]
+struct SlackRoute {
+    alert_type: AlertType,
+    severity: Option<AlertSeverity>,
+    webhook_url: String,
+}
+
+/// Picks the Slack webhook an alert is posted to.
+///
+/// A route matching both the alert type and severity wins over one for the
+/// type alone; alerts without a matching route go to the default URL.
+#[derive(Debug, Clone)]
+pub struct SlackRouting {
+    default_url: String,
+    routes: Vec<SlackRoute>,
+}
+
+impl SlackRouting {
+    #[must_use]
+    pub const fn new(default_url: String) -> Self {
+        Self {
+            default_url,
+            routes: Vec::new(),
+        }
+    }
+
+    /// Post every `alert_type` alert to `webhook_url`
+    #[must_use]
+    pub fn with_route(self, alert_type: AlertType, webhook_url: String) -> Self {
+        self.push(alert_type, None, webhook_url)
+    }
+
+    /// Post `alert_type` alerts of exactly `severity` to `webhook_url`
+    #[must_use]
+    pub fn with_severity_route(
+        self,
+        alert_type: AlertType,
+        severity: AlertSeverity,
+        webhook_url: String,
+    ) -> Self {
+        self.push(alert_type, Some(severity), webhook_url)
+    }
+
+    fn push(
+        mut self,
+        alert_type: AlertType,
+        severity: Option<AlertSeverity>,
+        webhook_url: String,
+    ) -> Self {
+        self.routes
+            .retain(|r| r.alert_type != alert_type || r.severity != severity);
+        self.routes.push(SlackRoute {
+            alert_type,
+            severity,
+            webhook_url,
+        });
+        self
+    }
+
+    /// Webhook URL `alert` should be posted to
+    #[must_use]
+    pub fn url_for(&self, alert: &Alert) -> &str {
+        let route = |severity: Option<AlertSeverity>| {
+            self.routes
+                .iter()
+                .find(|r| r.alert_type == alert.alert_type && r.severity == severity)
+        };
+        route(Some(alert.severity))
+            .or_else(|| route(None))
+            .map_or(&self.default_url, |r| &r.webhook_url)
+    }
+}
 
 /// Slack Bot Service for sending alerts to Slack channels
 pub struct SlackBotService {
-    webhook_url: String,
+    routing: SlackRouting,
     http_client: Client,
     alert_rx: broadcast::Receiver<Alert>,
 }
 
 impl SlackBotService {
-    /// Create a new `SlackBotService`
+    /// Create a new `SlackBotService` posting every alert to `webhook_url`
     #[must_use]
     pub fn new(webhook_url: String, alert_rx: broadcast::Receiver<Alert>) -> Self {
+        Self::with_routing(SlackRouting::new(webhook_url), alert_rx)
+    }
+
+    /// Create a new `SlackBotService` posting each alert to its routed channel
+    #[must_use]
+    pub fn with_routing(routing: SlackRouting, alert_rx: broadcast::Receiver<Alert>) -> Self {
         let http_client = Client::builder()
             .timeout(std::time::Duration::from_secs(5))
             .build()
             .unwrap_or_else(|_| Client::new());
 
         Self {
-            webhook_url,
+            routing,
             http_client,
             alert_rx,
         }
@@ -30,24 +111,33 @@ impl SlackBotService {
     pub async fn start(mut self) {
         tracing::info!("Slack Bot Service started, listening for alerts");
 
-        while let Ok(alert) = self.alert_rx.recv().await {
+        while let Some(alert) = next_alert(&mut self.alert_rx).await {
             if let Err(e) = self.send_alert_to_slack(&alert).await {
                 tracing::error!("Failed to send alert to Slack: {}", e);
             }
         }
+
+        tracing::info!("Alert channel closed, Slack Bot Service stopping");
     }
 
-    /// Send a single alert to Slack
+    /// Send a single alert to the Slack channel it is routed to
     pub async fn send_alert_to_slack(&self, alert: &Alert) -> Result<()> {
-        let (title, color, emoji) = match alert.alert_type {
-            AlertType::SuccessRateDrop => ("Success Rate Drop", "#E01E5A", "🔴"),
-            AlertType::LatencyIncrease => ("Latency Increase", "#ECB22E", "🟡"),
-            AlertType::LiquidityDecrease => ("Liquidity Decrease", "#E8912D", "🟠"),
-            AlertType::AnchorStatusChange => ("Anchor Status Change", "#36A64F", "🔵"),
-            AlertType::AnchorMetricChange => ("Anchor Metric Change", "#2EB67D", "📊"),
+        let (title, emoji) = match alert.alert_type {
+            AlertType::SuccessRateDrop => ("Success Rate Drop", "🔴"),
+            AlertType::LatencyIncrease => ("Latency Increase", "🟡"),
+            AlertType::LiquidityDecrease => ("Liquidity Decrease", "🟠"),
+            AlertType::AnchorStatusChange => ("Anchor Status Change", "🔵"),
+            AlertType::AnchorMetricChange => ("Anchor Metric Change", "📊"),
+            AlertType::IngestionLag => ("Ingestion Lag", "🚨"),
         };
+        let color = severity_color(alert.severity);
 
         let mut fields = vec![
+            serde_json::json!({
+                "title": "Severity",
+                "value": alert.severity.as_str(),
+                "short": true
+            }),
             serde_json::json!({
                 "title": "Timestamp",
                 "value": alert.timestamp,
@@ -103,7 +193,7 @@ impl SlackBotService {
 
         let response = self
             .http_client
-            .post(&self.webhook_url)
+            .post(self.routing.url_for(alert))
             .json(&payload)
             .send()
             .await
@@ -120,3 +210,127 @@ impl SlackBotService {
         Ok(())
     }
 }
+
+/// Receive the next alert, skipping over a lag instead of stopping.
+///
+/// A slow consumer that falls behind the channel capacity loses the oldest
+/// alerts; those are logged and delivery resumes with the oldest one still
+/// buffered. Returns `None` only once every sender has been dropped.
+async fn next_alert(rx: &mut broadcast::Receiver<Alert>) -> Option<Alert> {
+    loop {
+        match rx.recv().await {
+            Ok(alert) => return Some(alert),
+            Err(RecvError::Lagged(skipped)) => {
+                tracing::warn!(
+                    "Slack Bot Service fell behind, {} alerts were dropped",
+                    skipped
+                );
+            }
+            Err(RecvError::Closed) => return None,
+        }
+    }
+}
+
+/// Slack attachment color for an alert severity
+const fn severity_color(severity: AlertSeverity) -> &'static str {
+    match severity {
+        AlertSeverity::Info => "#2EB67D",
+        AlertSeverity::Warning => "#ECB22E",
+        AlertSeverity::Critical => "#E01E5A",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alerts::AlertManager;
+    use crate::rpc::test_server::{ScriptedResponse, TestServer};
+
+    fn alert(alert_type: AlertType, severity: AlertSeverity) -> Alert {
+        Alert {
+            alert_type,
+            severity,
+            corridor_id: None,
+            anchor_id: None,
+            message: "test alert".to_string(),
+            old_value: 100.0,
+            new_value: 50.0,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    #[test]
+    fn test_routing_prefers_severity_then_type_then_default() {
+        let routing = SlackRouting::new("default".to_string())
+            .with_route(AlertType::AnchorStatusChange, "anchors".to_string())
+            .with_severity_route(
+                AlertType::AnchorStatusChange,
+                AlertSeverity::Critical,
+                "anchors-oncall".to_string(),
+            );
+
+        let anchor = |severity| alert(AlertType::AnchorStatusChange, severity);
+        assert_eq!(routing.url_for(&anchor(AlertSeverity::Warning)), "anchors");
+        assert_eq!(
+            routing.url_for(&anchor(AlertSeverity::Critical)),
+            "anchors-oncall"
+        );
+        assert_eq!(
+            routing.url_for(&alert(AlertType::IngestionLag, AlertSeverity::Critical)),
+            "default"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_start_posts_each_alert_to_its_routed_channel() {
+        let default = TestServer::start([ScriptedResponse::json(StatusCode::OK, "ok")]).await;
+        let anchors = TestServer::start([ScriptedResponse::json(StatusCode::OK, "ok")]).await;
+        let routing = SlackRouting::new(default.url().to_string())
+            .with_route(AlertType::AnchorStatusChange, anchors.url().to_string());
+
+        let (tx, rx) = broadcast::channel(8);
+        tx.send(alert(AlertType::AnchorStatusChange, AlertSeverity::Warning))
+            .unwrap();
+        tx.send(alert(AlertType::SuccessRateDrop, AlertSeverity::Warning))
+            .unwrap();
+        drop(tx);
+
+        SlackBotService::with_routing(routing, rx).start().await;
+
+        assert_eq!(anchors.hits(), 1);
+        assert_eq!(default.hits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_next_alert_keeps_processing_after_lag() {
+        let (manager, mut rx) = AlertManager::with_capacity(2);
+
+        for i in 0..5 {
+            manager.send_anchor_alert(
+                AlertType::AnchorMetricChange,
+                "anchor-1",
+                format!("alert {i}"),
+                100.0,
+                50.0,
+            );
+        }
+
+        // Alerts 0..=2 were overwritten; delivery resumes with the buffered ones.
+        let alert = next_alert(&mut rx).await.unwrap();
+        assert_eq!(alert.message, "alert 3");
+        let alert = next_alert(&mut rx).await.unwrap();
+        assert_eq!(alert.message, "alert 4");
+
+        manager.send_anchor_alert(
+            AlertType::AnchorMetricChange,
+            "anchor-1",
+            "after lag".to_string(),
+            100.0,
+            50.0,
+        );
+        assert_eq!(next_alert(&mut rx).await.unwrap().message, "after lag");
+
+        drop(manager);
+        assert!(next_alert(&mut rx).await.is_none());
+    }
+}
@@ -3,6 +3,7 @@ use crate::rpc::stellar::{LedgerInfo, StellarRpcClient};
 use crate::snapshot::schema::{
     AnalyticsSnapshot, SnapshotAnchorMetrics, SnapshotCorridorMetrics, SCHEMA_VERSION,
 };
+use crate::snapshot::validation::{validate_snapshot, verify_stored_hash};
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::Serialize;
@@ -16,6 +17,21 @@ use uuid::Uuid;
 
 use super::contract::{ContractService, SubmissionResult};
 use super::event_indexer::{EventIndexer, VerificationSummary};
+use super::snapshot_submission::SnapshotSubmissionQueue;
+
+/// Decimal places USD amounts are fixed to before hashing, matching
+/// Stellar's seven-digit amount precision
+const AMOUNT_DECIMALS: usize = 7;
+
+/// SHA-256 hex digest of a snapshot's canonical serialization.
+///
+/// This is the hash submitted on-chain, so anything verifying a snapshot
+/// against a contract event must recompute it with this function.
+#[must_use]
+pub fn canonical_hash(snapshot: &AnalyticsSnapshot) -> String {
+    let canonical_json = SnapshotService::canonical_value(snapshot.clone()).to_string();
+    hex::encode(SnapshotService::compute_sha256_hash_bytes(&canonical_json))
+}
 
 /// Result of snapshot generation and submission process
 #[derive(Debug, Clone, Serialize)]
@@ -27,10 +43,20 @@ pub struct SnapshotGenerationResult {
     pub anchor_count: usize,
     pub corridor_count: usize,
     pub submission_result: Option<SubmissionResult>,
+    /// Queued submission, when a submission queue is configured
+    pub submission_id: Option<String>,
     pub verification_successful: bool,
     pub timestamp: DateTime<Utc>,
 }
 
+/// Snapshot as it would be generated, without having been stored or submitted
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapshotPreview {
+    pub snapshot: AnalyticsSnapshot,
+    pub hash: String,
+    pub canonical_json: String,
+}
+
 /// Service for creating cryptographically verifiable analytics snapshots
 ///
 /// This service ensures that:
@@ -45,6 +71,7 @@ pub struct SnapshotService {
     rpc_client: Arc<StellarRpcClient>,
     contract_service: Option<Arc<ContractService>>,
     event_indexer: Option<Arc<EventIndexer>>,
+    submission_queue: Option<Arc<SnapshotSubmissionQueue>>,
 }
 
 impl SnapshotService {
@@ -61,9 +88,19 @@ impl SnapshotService {
             rpc_client,
             contract_service,
             event_indexer,
+            submission_queue: None,
         }
     }
 
+    /// Enqueue hashes on `queue` instead of submitting them before
+    /// [`generate_and_submit_snapshot`](Self::generate_and_submit_snapshot)
+    /// returns
+    #[must_use]
+    pub fn with_submission_queue(mut self, queue: Arc<SnapshotSubmissionQueue>) -> Self {
+        self.submission_queue = Some(queue);
+        self
+    }
+
     /// Generate a complete analytics snapshot with hash generation and submission
     ///
     /// This is the main entry point that fulfills all acceptance criteria:
@@ -73,33 +110,27 @@ impl SnapshotService {
     /// 4. Store hash in database
     /// 5. Submit to smart contract
     /// 6. Verify submission success
+    ///
+    /// With a submission queue, step 5 only enqueues the hash and step 6 is
+    /// left to the queue's worker.
     pub async fn generate_and_submit_snapshot(
         &self,
         epoch: u64,
     ) -> Result<SnapshotGenerationResult> {
         info!("Starting snapshot generation for epoch {}", epoch);
 
-        // Step 1: Aggregate all metrics
-        let snapshot = self
-            .aggregate_all_metrics(epoch)
-            .await
-            .context("Failed to aggregate metrics")?;
-
-        info!(
-            "Aggregated {} anchor metrics and {} corridor metrics",
-            snapshot.anchor_metrics.len(),
-            snapshot.corridor_metrics.len()
-        );
-
-        // Step 2: Serialize to deterministic JSON
-        let canonical_json = Self::serialize_deterministically(snapshot.clone())
-            .context("Failed to serialize snapshot deterministically")?;
-
-        // Step 3: Compute SHA-256 hash
+        // Steps 1-3: Aggregate, serialize and hash
+        let SnapshotPreview {
+            snapshot,
+            hash: hash_hex,
+            canonical_json,
+        } = self.compute_snapshot_preview(epoch).await?;
         let hash = Self::compute_sha256_hash_bytes(&canonical_json);
-        let hash_hex = hex::encode(hash);
 
-        info!("Generated snapshot hash: {}", hash_hex);
+        // Step 3a: Refuse anything that would put bad data on-chain. The
+        // error is a SnapshotValidationError so callers can downcast it.
+        let previous_epoch = self.latest_stored_epoch().await?;
+        validate_snapshot(&snapshot, previous_epoch)?;
 
         // Step 3b: Verify the latest ledger hash to guard against orphaned ledgers
         // on network forks. We fetch the latest ledger, then re-fetch it by sequence
@@ -114,7 +145,7 @@ impl SnapshotService {
 
         let verified_ledger = self
             .rpc_client
-            .fetch_ledger_by_sequence(latest_ledger.sequence)
+            .fetch_ledger(latest_ledger.sequence)
             .await
             .context("Failed to verify ledger by sequence")?;
 
@@ -144,7 +175,30 @@ impl SnapshotService {
 
         info!("Stored snapshot in database with ID: {}", snapshot_id);
 
+        // Step 4a: Only submit a hash that matches the payload as stored
+        let (stored_payload, stored_hash) = self.load_stored_payload(&snapshot_id).await?;
+        verify_stored_hash(&stored_payload, &stored_hash)?;
+
         // Step 5: Submit to smart contract (if configured)
+        if let Some(queue) = &self.submission_queue {
+            let submission_id = queue
+                .enqueue(&snapshot_id, epoch, &hash_hex)
+                .await
+                .context("Failed to queue snapshot submission")?;
+            return Ok(SnapshotGenerationResult {
+                snapshot_id,
+                epoch,
+                hash: hash_hex,
+                canonical_json,
+                anchor_count: snapshot.anchor_metrics.len(),
+                corridor_count: snapshot.corridor_metrics.len(),
+                submission_result: None,
+                submission_id: Some(submission_id),
+                verification_successful: false,
+                timestamp: snapshot.timestamp,
+            });
+        }
+
         let submission_result = if let Some(contract_service) = &self.contract_service {
             match contract_service.submit_snapshot(hash, epoch).await {
                 Ok(result) => {
@@ -178,11 +232,46 @@ impl SnapshotService {
             anchor_count: snapshot.anchor_metrics.len(),
             corridor_count: snapshot.corridor_metrics.len(),
             submission_result,
+            submission_id: None,
             verification_successful: verification_result,
             timestamp: snapshot.timestamp,
         })
     }
 
+    /// Run the full aggregation, serialization and hashing for `epoch`
+    /// without writing to the database or submitting on-chain.
+    ///
+    /// Use this to diff a candidate snapshot against the previous epoch
+    /// before committing to it.
+    pub async fn compute_snapshot_preview(&self, epoch: u64) -> Result<SnapshotPreview> {
+        // Step 1: Aggregate all metrics
+        let snapshot = self
+            .aggregate_all_metrics(epoch)
+            .await
+            .context("Failed to aggregate metrics")?;
+
+        info!(
+            "Aggregated {} anchor metrics and {} corridor metrics",
+            snapshot.anchor_metrics.len(),
+            snapshot.corridor_metrics.len()
+        );
+
+        // Step 2: Serialize to deterministic JSON
+        let canonical_json = Self::serialize_deterministically(snapshot.clone())
+            .context("Failed to serialize snapshot deterministically")?;
+
+        // Step 3: Compute SHA-256 hash
+        let hash = hex::encode(Self::compute_sha256_hash_bytes(&canonical_json));
+
+        info!("Generated snapshot hash: {}", hash);
+
+        Ok(SnapshotPreview {
+            snapshot,
+            hash,
+            canonical_json,
+        })
+    }
+
     /// Aggregate all metrics from the database into a snapshot
     pub async fn aggregate_all_metrics(&self, epoch: u64) -> Result<AnalyticsSnapshot> {
         let timestamp = Utc::now();
@@ -364,6 +453,68 @@ impl SnapshotService {
         Ok(snapshot_id)
     }
 
+    /// Payload and hash of the stored snapshot row `snapshot_id`
+    async fn load_stored_payload(&self, snapshot_id: &str) -> Result<(String, String)> {
+        sqlx::query_as("SELECT data, hash FROM snapshots WHERE id = ?")
+            .bind(snapshot_id)
+            .fetch_one(self.db.pool())
+            .await
+            .context("Failed to read back stored snapshot")
+    }
+
+    /// Highest epoch with a stored snapshot
+    pub async fn latest_stored_epoch(&self) -> Result<Option<u64>> {
+        let epoch: Option<i64> = sqlx::query_scalar("SELECT MAX(epoch) FROM snapshots")
+            .fetch_one(self.db.pool())
+            .await
+            .context("Failed to read latest snapshot epoch")?;
+        Ok(epoch.map(|e| e as u64))
+    }
+
+    /// Load the stored snapshot for `epoch`, if one has been generated
+    pub async fn load_snapshot(&self, epoch: u64) -> Result<Option<AnalyticsSnapshot>> {
+        let data: Option<String> = sqlx::query_scalar(
+            "SELECT data FROM snapshots WHERE epoch = ? ORDER BY created_at DESC LIMIT 1",
+        )
+        .bind(epoch as i64)
+        .fetch_optional(self.db.pool())
+        .await
+        .context("Failed to query snapshot from database")?;
+
+        data.map(|json| {
+            serde_json::from_str(&json)
+                .with_context(|| format!("Stored snapshot for epoch {epoch} is not valid"))
+        })
+        .transpose()
+    }
+
+    /// Load the stored snapshots for epochs `from..=to` in epoch order,
+    /// keeping the most recent row where an epoch was stored more than once
+    pub async fn load_snapshot_range(&self, from: u64, to: u64) -> Result<Vec<AnalyticsSnapshot>> {
+        let rows: Vec<(i64, String)> = sqlx::query_as(
+            "SELECT epoch, data FROM snapshots WHERE epoch BETWEEN ? AND ? ORDER BY epoch ASC, created_at DESC",
+        )
+        .bind(from as i64)
+        .bind(to as i64)
+        .fetch_all(self.db.pool())
+        .await
+        .context("Failed to query snapshot range from database")?;
+
+        let mut snapshots: Vec<AnalyticsSnapshot> = Vec::with_capacity(rows.len());
+        let mut last_epoch = None;
+        for (epoch, json) in rows {
+            if last_epoch == Some(epoch) {
+                continue;
+            }
+            last_epoch = Some(epoch);
+            snapshots.push(
+                serde_json::from_str(&json)
+                    .with_context(|| format!("Stored snapshot for epoch {epoch} is not valid"))?,
+            );
+        }
+        Ok(snapshots)
+    }
+
     /// Verify that the submission was successful by querying the contract
     /// Verify that a snapshot submission was successful by checking on-chain
     ///
@@ -420,8 +571,13 @@ impl SnapshotService {
     /// # Returns
     /// A canonical JSON string representation suitable for hashing
     pub fn serialize_deterministically(
-        mut snapshot: AnalyticsSnapshot,
+        snapshot: AnalyticsSnapshot,
     ) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&Self::canonical_value(snapshot))
+    }
+
+    /// Canonical JSON value behind [`Self::serialize_deterministically`]
+    fn canonical_value(mut snapshot: AnalyticsSnapshot) -> Value {
         // Normalize the snapshot (sort all arrays by ID)
         snapshot.normalize();
 
@@ -460,14 +616,13 @@ impl SnapshotService {
             Value::Array(corridor_metrics),
         );
 
-        // Convert to JSON string with no extra whitespace
         // Note: serde_json::Map uses IndexMap internally which preserves insertion order.
         // Since we iterate over BTreeMap (sorted), insertion order is sorted, ensuring determinism.
         let mut json_map = Map::new();
         for (k, v) in map {
             json_map.insert(k, v);
         }
-        serde_json::to_string(&Value::Object(json_map))
+        Value::Object(json_map)
     }
 
     /// Serialize anchor metrics to a deterministic JSON value
@@ -515,7 +670,7 @@ impl SnapshotService {
         }
 
         if let Some(volume) = metrics.volume_usd {
-            map.insert("volume_usd".to_string(), Self::serialize_f64(volume));
+            map.insert("volume_usd".to_string(), Self::serialize_amount(volume));
         } else {
             map.insert("volume_usd".to_string(), Value::Null);
         }
@@ -574,7 +729,7 @@ impl SnapshotService {
         );
         map.insert(
             "volume_usd".to_string(),
-            Self::serialize_f64(metrics.volume_usd),
+            Self::serialize_amount(metrics.volume_usd),
         );
 
         if let Some(ms) = metrics.avg_settlement_latency_ms {
@@ -588,7 +743,7 @@ impl SnapshotService {
 
         map.insert(
             "liquidity_depth_usd".to_string(),
-            Self::serialize_f64(metrics.liquidity_depth_usd),
+            Self::serialize_amount(metrics.liquidity_depth_usd),
         );
 
         // serde_json::Map preserves insertion order (uses IndexMap internally)
@@ -627,6 +782,15 @@ impl SnapshotService {
         }
     }
 
+    /// Serialize a USD amount rounded to [`AMOUNT_DECIMALS`] places, so values
+    /// that differ only by floating point noise hash identically
+    fn serialize_amount(value: f64) -> Value {
+        let fixed = format!("{value:.AMOUNT_DECIMALS$}")
+            .parse::<f64>()
+            .unwrap_or(value);
+        Self::serialize_f64(fixed)
+    }
+
     /// Compute SHA-256 hash of a string and return the bytes
     fn compute_sha256_hash_bytes(data: &str) -> [u8; 32] {
         let mut hasher = Sha256::new();
@@ -747,7 +911,7 @@ impl SnapshotService {
 
         // Get backend snapshot data
         let query = r"
-            SELECT hash, canonical_json
+            SELECT hash, data AS canonical_json
             FROM snapshots
             WHERE epoch = ?
             ORDER BY created_at DESC
@@ -1200,4 +1364,160 @@ mod tests {
             );
         }
     }
+
+    #[tokio::test]
+    async fn test_dry_run_produces_hash_without_persisting() {
+        let pool = sqlx::SqlitePool::connect(":memory:").await.unwrap();
+        for ddl in [
+            "CREATE TABLE anchors (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                stellar_account TEXT NOT NULL,
+                total_transactions INTEGER NOT NULL DEFAULT 0,
+                successful_transactions INTEGER NOT NULL DEFAULT 0,
+                failed_transactions INTEGER NOT NULL DEFAULT 0,
+                total_volume_usd REAL DEFAULT 0,
+                avg_settlement_time_ms INTEGER DEFAULT 0,
+                reliability_score REAL NOT NULL DEFAULT 0,
+                status TEXT NOT NULL DEFAULT 'green'
+            )",
+            "CREATE TABLE corridor_metrics (
+                id TEXT PRIMARY KEY,
+                corridor_key TEXT NOT NULL,
+                asset_a_code TEXT NOT NULL,
+                asset_a_issuer TEXT NOT NULL,
+                asset_b_code TEXT NOT NULL,
+                asset_b_issuer TEXT NOT NULL,
+                date TEXT NOT NULL,
+                total_transactions INTEGER NOT NULL DEFAULT 0,
+                successful_transactions INTEGER NOT NULL DEFAULT 0,
+                failed_transactions INTEGER NOT NULL DEFAULT 0,
+                success_rate REAL NOT NULL DEFAULT 0,
+                volume_usd REAL NOT NULL DEFAULT 0,
+                avg_settlement_latency_ms INTEGER,
+                liquidity_depth_usd REAL NOT NULL DEFAULT 0
+            )",
+            "CREATE TABLE snapshots (
+                id TEXT PRIMARY KEY,
+                entity_id TEXT,
+                entity_type TEXT,
+                data TEXT,
+                hash TEXT,
+                epoch INTEGER UNIQUE,
+                timestamp TEXT NOT NULL,
+                created_at TEXT DEFAULT CURRENT_TIMESTAMP
+            )",
+        ] {
+            sqlx::query(ddl).execute(&pool).await.unwrap();
+        }
+        sqlx::query(
+            "INSERT INTO anchors (id, name, stellar_account, total_transactions,
+                successful_transactions, failed_transactions, reliability_score)
+             VALUES (?, 'Anchor', 'GANCHOR', 100, 98, 2, 0.98)",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let service = SnapshotService::new(
+            Arc::new(Database::new(pool.clone())),
+            Arc::new(StellarRpcClient::new_with_defaults(true)),
+            None,
+            None,
+        );
+
+        let preview = service.compute_snapshot_preview(42).await.unwrap();
+
+        assert_eq!(preview.snapshot.epoch, 42);
+        assert_eq!(preview.snapshot.anchor_metrics.len(), 1);
+        assert_eq!(preview.hash.len(), 64);
+        assert_eq!(
+            preview.hash,
+            SnapshotService::hash_snapshot_hex(preview.snapshot.clone()).unwrap()
+        );
+
+        let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM snapshots")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(stored, 0);
+    }
+
+    #[test]
+    fn test_canonical_hash_ignores_map_and_array_ordering() {
+        let first: AnalyticsSnapshot = serde_json::from_str(
+            r#"{
+                "schema_version": 1,
+                "epoch": 7,
+                "timestamp": "2024-01-01T00:00:00Z",
+                "anchor_metrics": [
+                    {"id": "00000000-0000-0000-0000-000000000001", "name": "A", "stellar_account": "GA",
+                     "success_rate": 0.9, "failure_rate": 0.1, "reliability_score": 0.9,
+                     "total_transactions": 10, "successful_transactions": 9, "failed_transactions": 1,
+                     "avg_settlement_time_ms": 100, "volume_usd": 1000.5, "status": "green"},
+                    {"id": "00000000-0000-0000-0000-000000000002", "name": "B", "stellar_account": "GB",
+                     "success_rate": 0.5, "failure_rate": 0.5, "reliability_score": 0.5,
+                     "total_transactions": 2, "successful_transactions": 1, "failed_transactions": 1,
+                     "avg_settlement_time_ms": null, "volume_usd": null, "status": "red"}
+                ],
+                "corridor_metrics": []
+            }"#,
+        )
+        .unwrap();
+        let second: AnalyticsSnapshot = serde_json::from_str(
+            r#"{
+                "corridor_metrics": [],
+                "anchor_metrics": [
+                    {"status": "red", "volume_usd": null, "avg_settlement_time_ms": null,
+                     "failed_transactions": 1, "successful_transactions": 1, "total_transactions": 2,
+                     "reliability_score": 0.5, "failure_rate": 0.5, "success_rate": 0.5,
+                     "stellar_account": "GB", "name": "B", "id": "00000000-0000-0000-0000-000000000002"},
+                    {"status": "green", "volume_usd": 1000.5, "avg_settlement_time_ms": 100,
+                     "failed_transactions": 1, "successful_transactions": 9, "total_transactions": 10,
+                     "reliability_score": 0.9, "failure_rate": 0.1, "success_rate": 0.9,
+                     "stellar_account": "GA", "name": "A", "id": "00000000-0000-0000-0000-000000000001"}
+                ],
+                "timestamp": "2024-01-01T00:00:00Z",
+                "epoch": 7,
+                "schema_version": 1
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(canonical_hash(&first), canonical_hash(&second));
+        assert_eq!(
+            canonical_hash(&first),
+            SnapshotService::hash_snapshot_hex(second).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_canonical_hash_fixes_amount_precision() {
+        let now = Utc::now();
+        let id = Uuid::from_u128(1);
+
+        let mut exact = AnalyticsSnapshot::new(1, now);
+        let mut corridor = create_test_corridor_metrics(id, "USDC->EURC");
+        corridor.volume_usd = 0.3;
+        exact.add_corridor_metrics(corridor.clone());
+
+        let mut noisy = AnalyticsSnapshot::new(1, now);
+        corridor.volume_usd = 0.1 + 0.2;
+        noisy.add_corridor_metrics(corridor);
+
+        assert_eq!(canonical_hash(&exact), canonical_hash(&noisy));
+    }
+
+    #[test]
+    fn test_canonical_hash_survives_stored_json_round_trip() {
+        let mut snapshot = AnalyticsSnapshot::new(3, Utc::now());
+        snapshot.add_anchor_metrics(create_test_anchor_metrics(Uuid::from_u128(1), "Anchor1"));
+        snapshot.add_corridor_metrics(create_test_corridor_metrics(Uuid::from_u128(2), "A->B"));
+
+        let stored = SnapshotService::serialize_deterministically(snapshot.clone()).unwrap();
+        let reloaded: AnalyticsSnapshot = serde_json::from_str(&stored).unwrap();
+
+        assert_eq!(canonical_hash(&reloaded), canonical_hash(&snapshot));
+    }
 }
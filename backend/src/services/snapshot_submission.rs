@@ -0,0 +1,892 @@
+//! Durable queue of snapshot hashes awaiting on-chain submission.
+//!
+//! Snapshots are enqueued once stored, so generating one doesn't wait on
+//! Soroban. A worker sends each due row with `max_fee` taken from
+//! `getFeeStats`, retries failed sends and on-chain failures with a bumped
+//! fee and exponential backoff, and confirms sent transactions with
+//! `getTransaction`. Rows move `pending` -> `submitted` -> `confirmed`, or to
+//! `failed` once `SNAPSHOT_SUBMIT_MAX_ATTEMPTS` is used up. A transaction
+//! still not in a ledger `SNAPSHOT_SUBMIT_CONFIRM_TIMEOUT_SECONDS` after it
+//! was sent counts as a failed attempt and goes back to `pending`. Hashes
+//! from earlier attempts are kept and rechecked before each resend and
+//! before a failure is recorded, so one that lands late still confirms the
+//! row instead of a duplicate being sent.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use tokio::sync::broadcast;
+use tokio::time::interval;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use super::contract::{ContractService, FeeStats, TransactionStatus};
+
+/// Stellar's base fee; no attempt offers less
+pub const MIN_FEE_STROOPS: u64 = 100;
+
+/// Rows picked up per worker pass
+const BATCH_SIZE: i64 = 20;
+
+/// The on-chain calls the queue makes, so tests can script them
+#[async_trait::async_trait]
+pub trait SnapshotSubmitter: Send + Sync {
+    async fn fetch_fee_stats(&self) -> Result<FeeStats>;
+
+    /// Send the submission transaction, returning its hash
+    async fn send_transaction(&self, hash: [u8; 32], epoch: u64, max_fee: u64) -> Result<String>;
+
+    async fn fetch_transaction(&self, tx_hash: &str) -> Result<TransactionStatus>;
+}
+
+#[async_trait::async_trait]
+impl SnapshotSubmitter for ContractService {
+    async fn fetch_fee_stats(&self) -> Result<FeeStats> {
+        ContractService::fetch_fee_stats(self).await
+    }
+
+    async fn send_transaction(&self, hash: [u8; 32], epoch: u64, max_fee: u64) -> Result<String> {
+        self.send_snapshot_transaction(hash, epoch, Some(max_fee))
+            .await
+    }
+
+    async fn fetch_transaction(&self, tx_hash: &str) -> Result<TransactionStatus> {
+        ContractService::fetch_transaction(self, tx_hash).await
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubmissionStatus {
+    /// Due for a send at `next_attempt_at`
+    Pending,
+    /// Sent, awaiting confirmation
+    Submitted,
+    Confirmed,
+    /// Attempts exhausted
+    Failed,
+}
+
+impl SubmissionStatus {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Submitted => "submitted",
+            Self::Confirmed => "confirmed",
+            Self::Failed => "failed",
+        }
+    }
+
+    fn parse(status: &str) -> Result<Self> {
+        match status {
+            "pending" => Ok(Self::Pending),
+            "submitted" => Ok(Self::Submitted),
+            "confirmed" => Ok(Self::Confirmed),
+            "failed" => Ok(Self::Failed),
+            other => anyhow::bail!("unknown snapshot submission status '{other}'"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapshotSubmission {
+    pub id: String,
+    pub snapshot_id: String,
+    pub epoch: u64,
+    /// Hex SHA-256 of the canonical snapshot
+    pub hash: String,
+    pub status: SubmissionStatus,
+    pub attempts: u32,
+    /// Stroops offered on the latest attempt
+    pub max_fee: Option<u64>,
+    pub transaction_hash: Option<String>,
+    /// Hashes sent on earlier attempts, oldest first
+    pub previous_transaction_hashes: Vec<String>,
+    pub ledger: Option<u64>,
+    pub last_error: Option<String>,
+    pub next_attempt_at: String,
+    /// When the latest attempt was sent
+    pub submitted_at: Option<String>,
+}
+
+#[derive(sqlx::FromRow)]
+struct SubmissionRow {
+    id: String,
+    snapshot_id: String,
+    epoch: i64,
+    hash: String,
+    status: String,
+    attempts: i64,
+    max_fee: Option<i64>,
+    transaction_hash: Option<String>,
+    previous_transaction_hashes: String,
+    ledger: Option<i64>,
+    last_error: Option<String>,
+    next_attempt_at: String,
+    submitted_at: Option<String>,
+}
+
+impl TryFrom<SubmissionRow> for SnapshotSubmission {
+    type Error = anyhow::Error;
+
+    fn try_from(row: SubmissionRow) -> Result<Self> {
+        let previous_transaction_hashes = serde_json::from_str(&row.previous_transaction_hashes)
+            .with_context(|| format!("Invalid previous hashes for submission {}", row.id))?;
+        Ok(Self {
+            id: row.id,
+            snapshot_id: row.snapshot_id,
+            epoch: row.epoch as u64,
+            hash: row.hash,
+            status: SubmissionStatus::parse(&row.status)?,
+            attempts: row.attempts as u32,
+            max_fee: row.max_fee.map(|fee| fee as u64),
+            transaction_hash: row.transaction_hash,
+            previous_transaction_hashes,
+            ledger: row.ledger.map(|ledger| ledger as u64),
+            last_error: row.last_error,
+            next_attempt_at: row.next_attempt_at,
+            submitted_at: row.submitted_at,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmissionQueueConfig {
+    /// Sends per snapshot before it is marked failed
+    pub max_attempts: u32,
+    /// How much each retry raises the previous `max_fee`, in percent
+    pub fee_bump_percent: u64,
+    /// Ceiling on `max_fee`, in stroops
+    pub max_fee_cap: u64,
+    /// Delay before the first retry; doubled on each later one
+    pub retry_delay: Duration,
+    /// How often the worker looks for due rows
+    pub poll_interval: Duration,
+    /// How long a sent transaction may stay unconfirmed before it is resent
+    pub confirm_timeout: Duration,
+}
+
+impl Default for SubmissionQueueConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            fee_bump_percent: 50,
+            max_fee_cap: 1_000_000,
+            retry_delay: Duration::from_secs(30),
+            poll_interval: Duration::from_secs(10),
+            confirm_timeout: Duration::from_secs(300),
+        }
+    }
+}
+
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+impl SubmissionQueueConfig {
+    /// Load from `SNAPSHOT_SUBMIT_MAX_ATTEMPTS` (default 5),
+    /// `SNAPSHOT_SUBMIT_FEE_BUMP_PERCENT` (default 50),
+    /// `SNAPSHOT_SUBMIT_MAX_FEE_STROOPS` (default 1000000),
+    /// `SNAPSHOT_SUBMIT_RETRY_DELAY_SECONDS` (default 30),
+    /// `SNAPSHOT_SUBMIT_POLL_INTERVAL_SECONDS` (default 10) and
+    /// `SNAPSHOT_SUBMIT_CONFIRM_TIMEOUT_SECONDS` (default 300).
+    #[must_use]
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            max_attempts: env_or("SNAPSHOT_SUBMIT_MAX_ATTEMPTS", defaults.max_attempts).max(1),
+            fee_bump_percent: env_or(
+                "SNAPSHOT_SUBMIT_FEE_BUMP_PERCENT",
+                defaults.fee_bump_percent,
+            ),
+            max_fee_cap: env_or("SNAPSHOT_SUBMIT_MAX_FEE_STROOPS", defaults.max_fee_cap)
+                .max(MIN_FEE_STROOPS),
+            retry_delay: Duration::from_secs(env_or(
+                "SNAPSHOT_SUBMIT_RETRY_DELAY_SECONDS",
+                defaults.retry_delay.as_secs(),
+            )),
+            poll_interval: Duration::from_secs(
+                env_or(
+                    "SNAPSHOT_SUBMIT_POLL_INTERVAL_SECONDS",
+                    defaults.poll_interval.as_secs(),
+                )
+                .max(1),
+            ),
+            confirm_timeout: Duration::from_secs(env_or(
+                "SNAPSHOT_SUBMIT_CONFIRM_TIMEOUT_SECONDS",
+                defaults.confirm_timeout.as_secs(),
+            )),
+        }
+    }
+
+    /// `max_fee` for the next send: the p90 inclusion fee on a first attempt
+    /// and the p99 on retries, but never less than the previous offer bumped
+    /// by `fee_bump_percent`. Capped at `max_fee_cap`.
+    #[must_use]
+    pub fn next_max_fee(&self, stats: Option<FeeStats>, previous: Option<u64>) -> u64 {
+        let market = stats.map_or(MIN_FEE_STROOPS, |stats| {
+            if previous.is_some() {
+                stats.p99
+            } else {
+                stats.p90
+            }
+        });
+        let bumped = previous.map_or(0, |fee| {
+            fee.saturating_mul(100 + self.fee_bump_percent) / 100
+        });
+        market
+            .max(bumped)
+            .max(MIN_FEE_STROOPS)
+            .min(self.max_fee_cap)
+    }
+
+    /// Delay before the retry following `attempts` sends
+    #[must_use]
+    pub fn backoff(&self, attempts: u32) -> Duration {
+        self.retry_delay
+            .saturating_mul(2u32.saturating_pow(attempts.saturating_sub(1)))
+    }
+}
+
+fn timestamp_after(delay: Duration) -> String {
+    let delay = chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::MAX);
+    Utc::now()
+        .checked_add_signed(delay)
+        .unwrap_or(chrono::DateTime::<Utc>::MAX_UTC)
+        .to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+pub struct SnapshotSubmissionQueue {
+    pool: SqlitePool,
+    submitter: Arc<dyn SnapshotSubmitter>,
+    config: SubmissionQueueConfig,
+}
+
+impl SnapshotSubmissionQueue {
+    #[must_use]
+    pub fn new(
+        pool: SqlitePool,
+        submitter: Arc<dyn SnapshotSubmitter>,
+        config: SubmissionQueueConfig,
+    ) -> Self {
+        Self {
+            pool,
+            submitter,
+            config,
+        }
+    }
+
+    /// Queue `hash_hex` for submission, due immediately. Returns the
+    /// submission id.
+    pub async fn enqueue(&self, snapshot_id: &str, epoch: u64, hash_hex: &str) -> Result<String> {
+        let id = Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO snapshot_submissions (id, snapshot_id, epoch, hash, status, next_attempt_at)
+             VALUES ($1, $2, $3, $4, $5, $6)",
+        )
+        .bind(&id)
+        .bind(snapshot_id)
+        .bind(epoch as i64)
+        .bind(hash_hex)
+        .bind(SubmissionStatus::Pending.as_str())
+        .bind(timestamp_after(Duration::ZERO))
+        .execute(&self.pool)
+        .await
+        .context("Failed to enqueue snapshot submission")?;
+
+        info!(
+            "Queued snapshot {} (epoch {}) for submission",
+            snapshot_id, epoch
+        );
+        Ok(id)
+    }
+
+    pub async fn get(&self, id: &str) -> Result<Option<SnapshotSubmission>> {
+        sqlx::query_as::<_, SubmissionRow>(
+            "SELECT id, snapshot_id, epoch, hash, status, attempts, max_fee, transaction_hash,
+                    previous_transaction_hashes, ledger, last_error, next_attempt_at,
+                    submitted_at
+             FROM snapshot_submissions WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?
+        .map(SnapshotSubmission::try_from)
+        .transpose()
+    }
+
+    async fn rows_with_status(&self, status: SubmissionStatus) -> Result<Vec<SnapshotSubmission>> {
+        let rows = sqlx::query_as::<_, SubmissionRow>(
+            "SELECT id, snapshot_id, epoch, hash, status, attempts, max_fee, transaction_hash,
+                    previous_transaction_hashes, ledger, last_error, next_attempt_at,
+                    submitted_at
+             FROM snapshot_submissions
+             WHERE status = $1 AND next_attempt_at <= $2
+             ORDER BY next_attempt_at
+             LIMIT $3",
+        )
+        .bind(status.as_str())
+        .bind(timestamp_after(Duration::ZERO))
+        .bind(BATCH_SIZE)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.into_iter().map(SnapshotSubmission::try_from).collect()
+    }
+
+    /// Send every due `pending` row and check every `submitted` one
+    pub async fn process_due(&self) -> Result<()> {
+        let pending = self.rows_with_status(SubmissionStatus::Pending).await?;
+        let stats = if pending.is_empty() {
+            None
+        } else {
+            match self.submitter.fetch_fee_stats().await {
+                Ok(stats) => Some(stats),
+                Err(e) => {
+                    warn!("Failed to fetch fee stats, bumping previous fees: {}", e);
+                    None
+                }
+            }
+        };
+        for submission in pending {
+            if let Err(e) = self.send(&submission, stats).await {
+                error!("Failed to record submission {}: {}", submission.id, e);
+            }
+        }
+
+        for submission in self.rows_with_status(SubmissionStatus::Submitted).await? {
+            if let Err(e) = self.confirm(&submission).await {
+                error!("Failed to record submission {}: {}", submission.id, e);
+            }
+        }
+        Ok(())
+    }
+
+    async fn send(&self, submission: &SnapshotSubmission, stats: Option<FeeStats>) -> Result<()> {
+        let mut hash = [0u8; 32];
+        hex::decode_to_slice(&submission.hash, &mut hash)
+            .with_context(|| format!("Invalid hash for submission {}", submission.id))?;
+        let sent = submission
+            .previous_transaction_hashes
+            .iter()
+            .chain(&submission.transaction_hash);
+        if self.confirm_earlier_attempt(submission, sent).await? {
+            return Ok(());
+        }
+
+        let max_fee = self.config.next_max_fee(stats, submission.max_fee);
+        let attempts = submission.attempts + 1;
+        let mut previous = submission.previous_transaction_hashes.clone();
+        previous.extend(submission.transaction_hash.clone());
+
+        match self
+            .submitter
+            .send_transaction(hash, submission.epoch, max_fee)
+            .await
+        {
+            Ok(transaction_hash) => {
+                info!(
+                    "Sent snapshot submission {} (attempt {}, max_fee {}): {}",
+                    submission.id, attempts, max_fee, transaction_hash
+                );
+                sqlx::query(
+                    "UPDATE snapshot_submissions
+                     SET status = $1, attempts = $2, max_fee = $3, transaction_hash = $4,
+                         previous_transaction_hashes = $5, last_error = NULL,
+                         submitted_at = $6, updated_at = CURRENT_TIMESTAMP
+                     WHERE id = $7",
+                )
+                .bind(SubmissionStatus::Submitted.as_str())
+                .bind(i64::from(attempts))
+                .bind(max_fee as i64)
+                .bind(transaction_hash)
+                .bind(serde_json::to_string(&previous)?)
+                .bind(timestamp_after(Duration::ZERO))
+                .bind(&submission.id)
+                .execute(&self.pool)
+                .await?;
+                Ok(())
+            }
+            Err(e) => {
+                self.record_failure(submission, attempts, max_fee, &e.to_string())
+                    .await
+            }
+        }
+    }
+
+    async fn confirm(&self, submission: &SnapshotSubmission) -> Result<()> {
+        let Some(transaction_hash) = &submission.transaction_hash else {
+            return self
+                .record_failure(
+                    submission,
+                    submission.attempts,
+                    submission.max_fee.unwrap_or(MIN_FEE_STROOPS),
+                    "submitted without a transaction hash",
+                )
+                .await;
+        };
+
+        let reason = match self.submitter.fetch_transaction(transaction_hash).await {
+            Ok(TransactionStatus::Success { ledger, .. }) => {
+                return self
+                    .mark_confirmed(submission, transaction_hash, ledger)
+                    .await;
+            }
+            Ok(TransactionStatus::Failed { reason }) => {
+                format!("transaction {transaction_hash} failed: {reason}")
+            }
+            Ok(TransactionStatus::Pending) if self.confirmation_timed_out(submission) => format!(
+                "transaction {transaction_hash} not confirmed within {}s",
+                self.config.confirm_timeout.as_secs()
+            ),
+            Ok(TransactionStatus::Pending) => return Ok(()),
+            Err(e) => {
+                warn!(
+                    "Failed to check submission {} transaction {}: {}",
+                    submission.id, transaction_hash, e
+                );
+                return Ok(());
+            }
+        };
+
+        if self
+            .confirm_earlier_attempt(submission, &submission.previous_transaction_hashes)
+            .await?
+        {
+            return Ok(());
+        }
+        self.record_failure(
+            submission,
+            submission.attempts,
+            submission.max_fee.unwrap_or(MIN_FEE_STROOPS),
+            &reason,
+        )
+        .await
+    }
+
+    /// Mark `submission` confirmed if any of `hashes`, sent on earlier
+    /// attempts, has since landed. Returns whether one had.
+    async fn confirm_earlier_attempt<'a>(
+        &self,
+        submission: &SnapshotSubmission,
+        hashes: impl IntoIterator<Item = &'a String>,
+    ) -> Result<bool> {
+        for transaction_hash in hashes {
+            if let TransactionStatus::Success { ledger, .. } =
+                self.submitter.fetch_transaction(transaction_hash).await?
+            {
+                self.mark_confirmed(submission, transaction_hash, ledger)
+                    .await?;
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    async fn mark_confirmed(
+        &self,
+        submission: &SnapshotSubmission,
+        transaction_hash: &str,
+        ledger: u64,
+    ) -> Result<()> {
+        info!(
+            "Snapshot submission {} confirmed in ledger {} by {}",
+            submission.id, ledger, transaction_hash
+        );
+        sqlx::query(
+            "UPDATE snapshot_submissions
+             SET status = $1, transaction_hash = $2, ledger = $3,
+                 updated_at = CURRENT_TIMESTAMP
+             WHERE id = $4",
+        )
+        .bind(SubmissionStatus::Confirmed.as_str())
+        .bind(transaction_hash)
+        .bind(ledger as i64)
+        .bind(&submission.id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Whether `submission` was sent more than `confirm_timeout` ago. A row
+    /// without a readable `submitted_at` counts as timed out.
+    fn confirmation_timed_out(&self, submission: &SnapshotSubmission) -> bool {
+        submission
+            .submitted_at
+            .as_deref()
+            .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+            .is_none_or(|sent| {
+                let elapsed = Utc::now().signed_duration_since(sent);
+                elapsed.to_std().unwrap_or_default() >= self.config.confirm_timeout
+            })
+    }
+
+    /// Put the row back to `pending` with backoff, or mark it `failed` once
+    /// `attempts` reaches the limit
+    async fn record_failure(
+        &self,
+        submission: &SnapshotSubmission,
+        attempts: u32,
+        max_fee: u64,
+        reason: &str,
+    ) -> Result<()> {
+        let status = if attempts >= self.config.max_attempts {
+            error!(
+                "Snapshot submission {} failed after {} attempts: {}",
+                submission.id, attempts, reason
+            );
+            SubmissionStatus::Failed
+        } else {
+            warn!(
+                "Snapshot submission {} attempt {} failed, retrying: {}",
+                submission.id, attempts, reason
+            );
+            SubmissionStatus::Pending
+        };
+
+        sqlx::query(
+            "UPDATE snapshot_submissions
+             SET status = $1, attempts = $2, max_fee = $3, last_error = $4,
+                 next_attempt_at = $5, updated_at = CURRENT_TIMESTAMP
+             WHERE id = $6",
+        )
+        .bind(status.as_str())
+        .bind(i64::from(attempts))
+        .bind(max_fee as i64)
+        .bind(reason)
+        .bind(timestamp_after(self.config.backoff(attempts)))
+        .bind(&submission.id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Process due submissions every `poll_interval` until shutdown
+    pub async fn start_worker(self: Arc<Self>, mut shutdown_rx: broadcast::Receiver<()>) {
+        info!(
+            "Starting snapshot submission worker (interval: {}s)",
+            self.config.poll_interval.as_secs()
+        );
+
+        let mut ticker = interval(self.config.poll_interval);
+
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {}
+                _ = shutdown_rx.recv() => break,
+            }
+
+            if let Err(e) = self.process_due().await {
+                error!("Snapshot submission pass failed: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    const HASH: &str = "a3f5c8e1b2d4f6a8c0e2b4d6f8a0c2e4b6d8f0a2c4e6b8d0f2a4c6e8b0d2f4a6";
+
+    /// Submitter that replays scripted results and records the fees it was
+    /// offered
+    #[derive(Default)]
+    struct MockSubmitter {
+        sends: Mutex<VecDeque<Result<String>>>,
+        statuses: Mutex<VecDeque<TransactionStatus>>,
+        fees: Mutex<Vec<u64>>,
+    }
+
+    impl MockSubmitter {
+        fn fees(&self) -> Vec<u64> {
+            self.fees.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl SnapshotSubmitter for MockSubmitter {
+        async fn fetch_fee_stats(&self) -> Result<FeeStats> {
+            Ok(FeeStats {
+                p50: 100,
+                p90: 1_000,
+                p99: 1_200,
+            })
+        }
+
+        async fn send_transaction(
+            &self,
+            _hash: [u8; 32],
+            _epoch: u64,
+            max_fee: u64,
+        ) -> Result<String> {
+            self.fees.lock().unwrap().push(max_fee);
+            self.sends
+                .lock()
+                .unwrap()
+                .pop_front()
+                .expect("unexpected send")
+        }
+
+        async fn fetch_transaction(&self, _tx_hash: &str) -> Result<TransactionStatus> {
+            Ok(self
+                .statuses
+                .lock()
+                .unwrap()
+                .pop_front()
+                .unwrap_or(TransactionStatus::Pending))
+        }
+    }
+
+    async fn queue_with(
+        submitter: Arc<MockSubmitter>,
+        config: SubmissionQueueConfig,
+    ) -> SnapshotSubmissionQueue {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::query(include_str!(
+            "../../migrations/044_create_snapshot_submissions.sql"
+        ))
+        .execute(&pool)
+        .await
+        .unwrap();
+        SnapshotSubmissionQueue::new(pool, submitter, config)
+    }
+
+    async fn queue(submitter: Arc<MockSubmitter>, max_attempts: u32) -> SnapshotSubmissionQueue {
+        queue_with(
+            submitter,
+            SubmissionQueueConfig {
+                max_attempts,
+                retry_delay: Duration::ZERO,
+                ..SubmissionQueueConfig::default()
+            },
+        )
+        .await
+    }
+
+    async fn timing_out_queue(submitter: Arc<MockSubmitter>) -> SnapshotSubmissionQueue {
+        queue_with(
+            submitter,
+            SubmissionQueueConfig {
+                retry_delay: Duration::ZERO,
+                confirm_timeout: Duration::ZERO,
+                ..SubmissionQueueConfig::default()
+            },
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_transient_failure_is_retried_with_bumped_fee() {
+        let submitter = Arc::new(MockSubmitter::default());
+        submitter.sends.lock().unwrap().extend([
+            Err(anyhow::anyhow!("RPC unavailable")),
+            Ok("tx-1".to_string()),
+        ]);
+        submitter.statuses.lock().unwrap().extend([
+            TransactionStatus::Pending,
+            TransactionStatus::Success {
+                ledger: 4242,
+                timestamp: 0,
+            },
+        ]);
+        let queue = queue(submitter.clone(), 5).await;
+        let id = queue.enqueue("snap-1", 7, HASH).await.unwrap();
+
+        queue.process_due().await.unwrap();
+        let submission = queue.get(&id).await.unwrap().unwrap();
+        assert_eq!(submission.status, SubmissionStatus::Pending);
+        assert_eq!(submission.attempts, 1);
+        assert_eq!(submission.last_error.as_deref(), Some("RPC unavailable"));
+
+        // The retry bumps the previous 1000 by 50%, above the p99 of 1200.
+        // The same pass finds it not yet in a ledger.
+        queue.process_due().await.unwrap();
+        let submission = queue.get(&id).await.unwrap().unwrap();
+        assert_eq!(submission.status, SubmissionStatus::Submitted);
+        assert_eq!(submission.transaction_hash.as_deref(), Some("tx-1"));
+        assert_eq!(submitter.fees(), vec![1_000, 1_500]);
+
+        queue.process_due().await.unwrap();
+        let submission = queue.get(&id).await.unwrap().unwrap();
+        assert_eq!(submission.status, SubmissionStatus::Confirmed);
+        assert_eq!(submission.ledger, Some(4242));
+        assert_eq!(submission.attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn test_submission_fails_once_attempts_are_exhausted() {
+        let submitter = Arc::new(MockSubmitter::default());
+        submitter
+            .sends
+            .lock()
+            .unwrap()
+            .extend([Ok("tx-1".to_string()), Err(anyhow::anyhow!("fee too low"))]);
+        submitter
+            .statuses
+            .lock()
+            .unwrap()
+            .push_back(TransactionStatus::Failed {
+                reason: "txINSUFFICIENT_FEE".to_string(),
+            });
+        let queue = queue(submitter.clone(), 2).await;
+        let id = queue.enqueue("snap-1", 7, HASH).await.unwrap();
+
+        // Sent, then rejected on-chain: back to pending for another attempt
+        queue.process_due().await.unwrap();
+        let submission = queue.get(&id).await.unwrap().unwrap();
+        assert_eq!(submission.status, SubmissionStatus::Pending);
+        assert!(submission
+            .last_error
+            .unwrap()
+            .contains("txINSUFFICIENT_FEE"));
+
+        queue.process_due().await.unwrap();
+        let submission = queue.get(&id).await.unwrap().unwrap();
+        assert_eq!(submission.status, SubmissionStatus::Failed);
+        assert_eq!(submission.attempts, 2);
+        assert_eq!(submission.last_error.as_deref(), Some("fee too low"));
+
+        queue.process_due().await.unwrap();
+        assert_eq!(submitter.fees().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_unconfirmed_transaction_is_resent_after_timeout() {
+        let submitter = Arc::new(MockSubmitter::default());
+        submitter
+            .sends
+            .lock()
+            .unwrap()
+            .extend([Ok("tx-1".to_string()), Ok("tx-2".to_string())]);
+        let queue = timing_out_queue(submitter.clone()).await;
+        let id = queue.enqueue("snap-1", 7, HASH).await.unwrap();
+
+        // Sent, then still pending on-chain past the (zero) timeout
+        queue.process_due().await.unwrap();
+        let submission = queue.get(&id).await.unwrap().unwrap();
+        assert_eq!(submission.status, SubmissionStatus::Pending);
+        assert_eq!(submission.attempts, 1);
+        assert!(submission
+            .last_error
+            .unwrap()
+            .contains("tx-1 not confirmed within 0s"));
+
+        queue.process_due().await.unwrap();
+        assert_eq!(submitter.fees(), vec![1_000, 1_500]);
+        let submission = queue.get(&id).await.unwrap().unwrap();
+        assert_eq!(submission.status, SubmissionStatus::Pending);
+        assert_eq!(submission.attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn test_timed_out_transaction_that_lands_before_resend_is_not_resent() {
+        let submitter = Arc::new(MockSubmitter::default());
+        submitter
+            .sends
+            .lock()
+            .unwrap()
+            .push_back(Ok("tx-1".to_string()));
+        submitter.statuses.lock().unwrap().extend([
+            TransactionStatus::Pending,
+            TransactionStatus::Success {
+                ledger: 77,
+                timestamp: 0,
+            },
+        ]);
+        let queue = timing_out_queue(submitter.clone()).await;
+        let id = queue.enqueue("snap-1", 7, HASH).await.unwrap();
+
+        queue.process_due().await.unwrap();
+        queue.process_due().await.unwrap();
+        let submission = queue.get(&id).await.unwrap().unwrap();
+        assert_eq!(submission.status, SubmissionStatus::Confirmed);
+        assert_eq!(submission.transaction_hash.as_deref(), Some("tx-1"));
+        assert_eq!(submission.ledger, Some(77));
+        assert_eq!(submitter.fees(), vec![1_000]);
+    }
+
+    #[tokio::test]
+    async fn test_timed_out_transaction_that_lands_after_resend_confirms_the_row() {
+        let submitter = Arc::new(MockSubmitter::default());
+        submitter
+            .sends
+            .lock()
+            .unwrap()
+            .extend([Ok("tx-1".to_string()), Ok("tx-2".to_string())]);
+        submitter.statuses.lock().unwrap().extend([
+            // tx-1 times out, and is still pending when rechecked before the resend
+            TransactionStatus::Pending,
+            TransactionStatus::Pending,
+            // tx-2 is rejected because tx-1 landed meanwhile
+            TransactionStatus::Failed {
+                reason: "txBAD_SEQ".to_string(),
+            },
+            TransactionStatus::Success {
+                ledger: 77,
+                timestamp: 0,
+            },
+        ]);
+        let queue = timing_out_queue(submitter.clone()).await;
+        let id = queue.enqueue("snap-1", 7, HASH).await.unwrap();
+
+        queue.process_due().await.unwrap();
+        queue.process_due().await.unwrap();
+        let submission = queue.get(&id).await.unwrap().unwrap();
+        assert_eq!(submission.status, SubmissionStatus::Confirmed);
+        assert_eq!(submission.transaction_hash.as_deref(), Some("tx-1"));
+        assert_eq!(submission.previous_transaction_hashes, vec!["tx-1"]);
+        assert_eq!(submission.ledger, Some(77));
+        assert_eq!(submitter.fees(), vec![1_000, 1_500]);
+    }
+
+    #[tokio::test]
+    async fn test_submitted_row_without_submitted_at_times_out() {
+        let submitter = Arc::new(MockSubmitter::default());
+        let queue = queue(submitter, 5).await;
+        let id = queue.enqueue("snap-1", 7, HASH).await.unwrap();
+        sqlx::query(
+            "UPDATE snapshot_submissions
+             SET status = 'submitted', attempts = 1, transaction_hash = 'tx-1'
+             WHERE id = $1",
+        )
+        .bind(&id)
+        .execute(&queue.pool)
+        .await
+        .unwrap();
+
+        queue.process_due().await.unwrap();
+        let submission = queue.get(&id).await.unwrap().unwrap();
+        assert_eq!(submission.status, SubmissionStatus::Pending);
+        assert!(submission
+            .last_error
+            .unwrap()
+            .contains("tx-1 not confirmed"));
+    }
+
+    #[test]
+    fn test_max_fee_is_bumped_and_capped() {
+        let config = SubmissionQueueConfig {
+            max_fee_cap: 2_000,
+            ..SubmissionQueueConfig::default()
+        };
+        let stats = FeeStats {
+            p50: 100,
+            p90: 1_000,
+            p99: 1_200,
+        };
+        assert_eq!(config.next_max_fee(Some(stats), None), 1_000);
+        assert_eq!(config.next_max_fee(Some(stats), Some(1_000)), 1_500);
+        assert_eq!(config.next_max_fee(None, Some(1_500)), 2_000);
+        assert_eq!(config.next_max_fee(None, None), MIN_FEE_STROOPS);
+    }
+}
@@ -64,6 +64,15 @@ pub struct StellarToml {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub network_passphrase: Option<String>,
 
+    // Transfer Servers
+    /// SEP-6 `TRANSFER_SERVER`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfer_server: Option<String>,
+
+    /// SEP-24 `TRANSFER_SERVER_SEP0024`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfer_server_sep0024: Option<String>,
+
     // Currencies
     #[serde(skip_serializing_if = "Option::is_none")]
     pub currencies: Option<Vec<CurrencyInfo>>,
@@ -453,6 +462,17 @@ impl StellarTomlClient {
             }
         }
 
+        // Extract transfer servers
+        let transfer_server = parsed
+            .get("TRANSFER_SERVER")
+            .and_then(|v| v.as_str())
+            .map(std::string::ToString::to_string);
+
+        let transfer_server_sep0024 = parsed
+            .get("TRANSFER_SERVER_SEP0024")
+            .and_then(|v| v.as_str())
+            .map(std::string::ToString::to_string);
+
         // Parse currencies
         let currencies = self.parse_currencies(&parsed)?;
 
@@ -476,6 +496,8 @@ impl StellarTomlClient {
             organization_official_email,
             organization_support_email,
             network_passphrase,
+            transfer_server,
+            transfer_server_sep0024,
             currencies,
             principals,
             documentation,
@@ -719,6 +741,8 @@ ORGANIZATION_LOGO = "https://test.com/logo.png"
 ORGANIZATION_DESCRIPTION = "A test anchor"
 ORGANIZATION_SUPPORT_EMAIL = "support@test.com"
 NETWORK_PASSPHRASE = "Test SDF Network ; September 2015"
+TRANSFER_SERVER = "https://test.com/sep6"
+TRANSFER_SERVER_SEP0024 = "https://test.com/sep24"
         "#;
 
         let result = client.parse_toml(toml_content, "test.com");
@@ -736,6 +760,14 @@ NETWORK_PASSPHRASE = "Test SDF Network ; September 2015"
             toml.organization_support_email,
             Some("support@test.com".to_string())
         );
+        assert_eq!(
+            toml.transfer_server,
+            Some("https://test.com/sep6".to_string())
+        );
+        assert_eq!(
+            toml.transfer_server_sep0024,
+            Some("https://test.com/sep24".to_string())
+        );
         assert_eq!(toml.domain, "test.com");
     }
 
@@ -0,0 +1,252 @@
+//! Trade prices normalized into a single quote asset.
+//!
+//! A trade's `price` is `n / d` units of its counter asset per unit of its base
+//! asset, so two trades can only be compared directly when they share a counter
+//! asset. [`Quote`] re-expresses trades in one chosen asset (usually USD, using
+//! prices from the price feed cache) so candles and corridor volumes from
+//! different pairs end up in the same unit.
+
+use std::collections::HashMap;
+
+use crate::rpc::{Price, Trade};
+
+/// Quote identifier for prices taken straight from the USD price feed
+pub const USD: &str = "USD";
+
+/// A trade's price and volume expressed in the quote asset
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuotedTrade {
+    /// Value of one unit of the base asset
+    pub price_in_quote: f64,
+    /// Value of the traded `base_amount`
+    pub volume_in_quote: f64,
+}
+
+/// Values of assets in one quote asset.
+///
+/// The quote asset itself is always worth 1, so a pair that trades directly
+/// against the quote needs no price at all.
+#[derive(Debug, Clone, Default)]
+pub struct Quote {
+    asset: String,
+    prices: HashMap<String, f64>,
+}
+
+impl Quote {
+    /// `prices` maps asset keys to the value of one unit in `asset`
+    #[must_use]
+    pub fn new(asset: impl Into<String>, prices: HashMap<String, f64>) -> Self {
+        Self {
+            asset: asset.into(),
+            prices,
+        }
+    }
+
+    /// Quote in `asset` built from USD prices, as returned by
+    /// [`PriceFeedClient::get_prices`](crate::services::price_feed::PriceFeedClient::get_prices).
+    ///
+    /// `asset` is either [`USD`] or an asset key. When the asset key has no USD
+    /// price only trades against it directly can be normalized.
+    #[must_use]
+    pub fn from_usd_prices(asset: &str, usd_prices: HashMap<String, f64>) -> Self {
+        if asset.eq_ignore_ascii_case(USD) {
+            return Self::new(USD, usd_prices);
+        }
+        let prices = match usd_prices.get(asset).copied().filter(|p| is_usable(*p)) {
+            Some(quote_usd) => usd_prices
+                .into_iter()
+                .map(|(key, usd)| (key, usd / quote_usd))
+                .collect(),
+            None => HashMap::new(),
+        };
+        Self::new(asset, prices)
+    }
+
+    #[must_use]
+    pub fn asset(&self) -> &str {
+        &self.asset
+    }
+
+    /// Value of one unit of `asset`, if it has a usable price
+    #[must_use]
+    pub fn unit_value(&self, asset: &str) -> Option<f64> {
+        if asset == self.asset {
+            return Some(1.0);
+        }
+        self.prices.get(asset).copied().filter(|p| is_usable(*p))
+    }
+
+    /// Whether trades of `base` against `counter` can be normalized
+    #[must_use]
+    pub fn has_path(&self, base: &str, counter: &str) -> bool {
+        self.unit_value(counter).is_some() || self.unit_value(base).is_some()
+    }
+
+    /// Normalize a trade of `base_amount` units of `base` at `price` (counter
+    /// per base).
+    ///
+    /// The counter asset's value is used when it is known, since the trade
+    /// price then yields the base asset's value; otherwise the base asset's own
+    /// value is used. `None` when neither asset can be valued or the price is
+    /// not a positive ratio.
+    #[must_use]
+    pub fn normalize(
+        &self,
+        base: &str,
+        counter: &str,
+        price: &Price,
+        base_amount: f64,
+    ) -> Option<QuotedTrade> {
+        let price_in_quote = match self.unit_value(counter) {
+            Some(counter_value) => price_ratio(price)? * counter_value,
+            None => {
+                price_ratio(price)?;
+                self.unit_value(base)?
+            }
+        };
+        Some(QuotedTrade {
+            price_in_quote,
+            volume_in_quote: base_amount * price_in_quote,
+        })
+    }
+
+    /// [`normalize`](Self::normalize) for a Horizon trade
+    #[must_use]
+    pub fn normalize_trade(&self, trade: &Trade) -> Option<QuotedTrade> {
+        self.normalize(
+            &trade.base_asset_key(),
+            &trade.counter_asset_key(),
+            &trade.price,
+            trade.base_amount.parse().ok()?,
+        )
+    }
+
+    /// Values of assets that have no price of their own but trade against one
+    /// that does, taken from the first such trade in `trades`.
+    ///
+    /// Assets already priced are not included.
+    #[must_use]
+    pub fn implied_prices(&self, trades: &[Trade]) -> HashMap<String, f64> {
+        let mut implied = HashMap::new();
+        for trade in trades {
+            let Some(ratio) = price_ratio(&trade.price) else {
+                continue;
+            };
+            let base = trade.base_asset_key();
+            let counter = trade.counter_asset_key();
+            match (self.unit_value(&base), self.unit_value(&counter)) {
+                (None, Some(counter_value)) => {
+                    implied.entry(base).or_insert(ratio * counter_value);
+                }
+                (Some(base_value), None) => {
+                    implied.entry(counter).or_insert(base_value / ratio);
+                }
+                _ => {}
+            }
+        }
+        implied
+    }
+}
+
+/// `n / d` as a float; `None` for a zero, negative or undefined price
+fn price_ratio(price: &Price) -> Option<f64> {
+    if price.n <= 0 || price.d <= 0 {
+        return None;
+    }
+    Some(price.n as f64 / price.d as f64)
+}
+
+fn is_usable(price: f64) -> bool {
+    price.is_finite() && price > 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USDC: &str = "USDC:GBXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX";
+    const EURC: &str = "EURC:GDHU6WRG4IEQXM5NZ4BMPKOXHW76MZM4Y2IEMFDVXBSDP6SJY4ITNPP2";
+    const XLM: &str = "XLM:native";
+
+    fn approx(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    /// XLM/USDC trade of `base_amount` XLM at `n / d` USDC per XLM
+    fn trade(n: i64, d: i64, base_amount: &str) -> Trade {
+        let mut trade = crate::rpc::mock_stellar::mock_trades(1).remove(0);
+        trade.price = Price { n, d };
+        trade.base_amount = base_amount.to_string();
+        trade
+    }
+
+    fn usd_prices(prices: &[(&str, f64)]) -> HashMap<String, f64> {
+        prices.iter().map(|(k, v)| ((*k).to_string(), *v)).collect()
+    }
+
+    #[test]
+    fn test_price_is_normalized_through_the_counter_asset() {
+        let quote = Quote::from_usd_prices(USD, usd_prices(&[(USDC, 0.5)]));
+
+        // 1/8 USDC per XLM with USDC at $0.50
+        let quoted = quote.normalize_trade(&trade(1, 8, "100.0000000")).unwrap();
+        approx(quoted.price_in_quote, 0.0625);
+        approx(quoted.volume_in_quote, 6.25);
+
+        // 3/2 USDC per XLM
+        let quoted = quote.normalize_trade(&trade(3, 2, "10.0000000")).unwrap();
+        approx(quoted.price_in_quote, 0.75);
+        approx(quoted.volume_in_quote, 7.5);
+    }
+
+    #[test]
+    fn test_base_value_is_used_when_counter_is_unpriced() {
+        let quote = Quote::from_usd_prices(USD, usd_prices(&[(XLM, 0.12)]));
+
+        let quoted = quote.normalize_trade(&trade(1, 10, "50.0000000")).unwrap();
+        approx(quoted.price_in_quote, 0.12);
+        approx(quoted.volume_in_quote, 6.0);
+    }
+
+    #[test]
+    fn test_quote_in_an_asset() {
+        // Quoting in the counter asset needs no prices: it's just n / d
+        let quote = Quote::from_usd_prices(USDC, HashMap::new());
+        let quoted = quote.normalize_trade(&trade(1, 4, "20.0000000")).unwrap();
+        approx(quoted.price_in_quote, 0.25);
+        approx(quoted.volume_in_quote, 5.0);
+
+        // USDC at $1.00 and EURC at $1.25, so 1 USDC = 0.8 EURC
+        let quote = Quote::from_usd_prices(EURC, usd_prices(&[(USDC, 1.0), (EURC, 1.25)]));
+        let quoted = quote.normalize_trade(&trade(1, 2, "10.0000000")).unwrap();
+        approx(quoted.price_in_quote, 0.4);
+        approx(quoted.volume_in_quote, 4.0);
+    }
+
+    #[test]
+    fn test_no_price_path_returns_none() {
+        let quote = Quote::from_usd_prices(USD, usd_prices(&[(EURC, 1.1), (USDC, 0.0)]));
+        assert!(!quote.has_path(XLM, USDC));
+        assert_eq!(quote.normalize_trade(&trade(1, 10, "1.0000000")), None);
+
+        let quote = Quote::from_usd_prices(USD, usd_prices(&[(USDC, 1.0)]));
+        assert_eq!(quote.normalize_trade(&trade(1, 0, "1.0000000")), None);
+        assert_eq!(quote.normalize_trade(&trade(0, 1, "1.0000000")), None);
+        assert_eq!(quote.normalize_trade(&trade(1, 10, "not a number")), None);
+    }
+
+    #[test]
+    fn test_implied_prices_fill_in_unpriced_assets() {
+        let quote = Quote::from_usd_prices(USD, usd_prices(&[(USDC, 1.0)]));
+        let implied = quote.implied_prices(&[trade(1, 10, "1.0000000"), trade(1, 5, "1.0000000")]);
+        assert_eq!(implied.len(), 1);
+        approx(implied[XLM], 0.1);
+
+        let quote = Quote::from_usd_prices(USD, usd_prices(&[(XLM, 0.1)]));
+        let implied = quote.implied_prices(&[trade(1, 5, "1.0000000")]);
+        approx(implied[USDC], 0.5);
+    }
+}
@@ -0,0 +1,356 @@
+//! Availability probe for anchor SEP-6/SEP-24 transfer servers.
+//!
+//! For each anchor with a `home_domain`, the probe reads `TRANSFER_SERVER_SEP0024`
+//! (falling back to `TRANSFER_SERVER`) from its stellar.toml and calls the
+//! server's `/info` endpoint. Transaction metrics lag behind an outage; this
+//! notices a transfer server that stops answering straight away.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::alerts::{AlertManager, AlertType};
+use crate::models::Anchor;
+use crate::services::stellar_toml::{StellarToml, StellarTomlClient};
+
+/// Health of an anchor's transfer server as seen by its `/info` endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferServerStatus {
+    /// `/info` answered successfully within the latency limit
+    Up,
+    /// `/info` answered slowly or with a client error
+    Degraded,
+    /// Unreachable, timed out, or answered with a server error
+    Down,
+}
+
+impl TransferServerStatus {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Up => "up",
+            Self::Degraded => "degraded",
+            Self::Down => "down",
+        }
+    }
+
+    /// Availability score sent as the alert's old/new value, so a drop to
+    /// `Down` is graded critical and a drop to `Degraded` a warning
+    const fn score(self) -> f64 {
+        match self {
+            Self::Up => 100.0,
+            Self::Degraded => 60.0,
+            Self::Down => 0.0,
+        }
+    }
+}
+
+impl std::fmt::Display for TransferServerStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Limits used to classify a probe
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferProbeConfig {
+    /// Requests taking longer than this count as `Down`
+    pub timeout: Duration,
+    /// Successful responses slower than this count as `Degraded`
+    pub degraded_latency: Duration,
+}
+
+impl Default for TransferProbeConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(10),
+            degraded_latency: Duration::from_secs(2),
+        }
+    }
+}
+
+impl TransferProbeConfig {
+    /// Load from `ANCHOR_PROBE_TIMEOUT_MS` (default 10000) and
+    /// `ANCHOR_PROBE_DEGRADED_LATENCY_MS` (default 2000).
+    #[must_use]
+    pub fn from_env() -> Self {
+        let ms = |name: &str, default: Duration| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .map_or(default, Duration::from_millis)
+        };
+        let defaults = Self::default();
+        Self {
+            timeout: ms("ANCHOR_PROBE_TIMEOUT_MS", defaults.timeout),
+            degraded_latency: ms(
+                "ANCHOR_PROBE_DEGRADED_LATENCY_MS",
+                defaults.degraded_latency,
+            ),
+        }
+    }
+
+    /// Classify a probe from its HTTP status (`None` when no response
+    /// arrived) and how long it took.
+    #[must_use]
+    pub fn classify(&self, status: Option<StatusCode>, latency: Duration) -> TransferServerStatus {
+        match status {
+            None => TransferServerStatus::Down,
+            Some(status) if status.is_server_error() => TransferServerStatus::Down,
+            Some(status) if status.is_success() && latency <= self.degraded_latency => {
+                TransferServerStatus::Up
+            }
+            Some(_) => TransferServerStatus::Degraded,
+        }
+    }
+}
+
+/// Transfer server URL to probe: SEP-24 when advertised, otherwise SEP-6
+#[must_use]
+pub fn transfer_server_url(toml: &StellarToml) -> Option<&str> {
+    toml.transfer_server_sep0024
+        .as_deref()
+        .or(toml.transfer_server.as_deref())
+        .filter(|url| !url.trim().is_empty())
+}
+
+pub struct TransferServerProbe {
+    http_client: Client,
+    toml_client: Arc<StellarTomlClient>,
+    config: TransferProbeConfig,
+    last_status: RwLock<HashMap<String, TransferServerStatus>>,
+}
+
+impl TransferServerProbe {
+    pub fn new(toml_client: Arc<StellarTomlClient>, config: TransferProbeConfig) -> Result<Self> {
+        let http_client = Client::builder()
+            .timeout(config.timeout)
+            .user_agent("StellarInsights/1.0")
+            .build()?;
+        Ok(Self {
+            http_client,
+            toml_client,
+            config,
+            last_status: RwLock::new(HashMap::new()),
+        })
+    }
+
+    /// Last status recorded for `anchor_id`
+    pub async fn status(&self, anchor_id: &str) -> Option<TransferServerStatus> {
+        self.last_status.read().await.get(anchor_id).copied()
+    }
+
+    /// Probe `anchor`'s transfer server and alert if its status changed.
+    ///
+    /// Returns `None` for anchors without a `home_domain` or whose
+    /// stellar.toml lists no transfer server. A stellar.toml that cannot be
+    /// loaded counts as `Down`.
+    pub async fn check_anchor(
+        &self,
+        alert_manager: &AlertManager,
+        anchor: &Anchor,
+    ) -> Option<TransferServerStatus> {
+        let domain = anchor.home_domain.as_deref()?;
+        let toml = match self.toml_client.fetch_toml(domain).await {
+            Ok(toml) => toml,
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to load stellar.toml for anchor {}: {}",
+                    anchor.id,
+                    e
+                );
+                self.record(alert_manager, anchor, TransferServerStatus::Down)
+                    .await;
+                return Some(TransferServerStatus::Down);
+            }
+        };
+        let url = transfer_server_url(&toml)?;
+
+        let status = self.probe(url).await;
+        self.record(alert_manager, anchor, status).await;
+        Some(status)
+    }
+
+    /// Call `{transfer_server}/info` and classify the response
+    pub async fn probe(&self, transfer_server: &str) -> TransferServerStatus {
+        let url = format!("{}/info", transfer_server.trim_end_matches('/'));
+        let started = Instant::now();
+        let status = match self.http_client.get(&url).send().await {
+            Ok(response) => Some(response.status()),
+            Err(e) => {
+                tracing::debug!("Transfer server probe of {} failed: {}", url, e);
+                None
+            }
+        };
+        self.config.classify(status, started.elapsed())
+    }
+
+    /// Store `status` for `anchor`, raising an [`AlertType::AnchorStatusChange`]
+    /// alert when it differs from the previous probe.
+    ///
+    /// Returns whether an alert was sent. The first probe of an anchor only
+    /// establishes its baseline.
+    pub async fn record(
+        &self,
+        alert_manager: &AlertManager,
+        anchor: &Anchor,
+        status: TransferServerStatus,
+    ) -> bool {
+        let previous = self
+            .last_status
+            .write()
+            .await
+            .insert(anchor.id.clone(), status);
+        match previous {
+            Some(previous) if previous != status => {
+                alert_manager.send_anchor_alert(
+                    AlertType::AnchorStatusChange,
+                    &anchor.id,
+                    format!(
+                        "Anchor '{}' transfer server went from {} to {}",
+                        anchor.name, previous, status
+                    ),
+                    previous.score(),
+                    status.score(),
+                );
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alerts::AlertSeverity;
+    use crate::rpc::test_server::{ScriptedResponse, TestServer};
+    use tokio::sync::RwLock as TokioRwLock;
+
+    fn anchor() -> Anchor {
+        Anchor {
+            id: "anchor-1".to_string(),
+            name: "Test Anchor".to_string(),
+            stellar_account: "GANCHOR".to_string(),
+            home_domain: Some("anchor.example".to_string()),
+            total_transactions: 0,
+            successful_transactions: 0,
+            failed_transactions: 0,
+            total_volume_usd: 0.0,
+            avg_settlement_time_ms: 0,
+            reliability_score: 100.0,
+            status: "green".to_string(),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    fn probe(config: TransferProbeConfig) -> TransferServerProbe {
+        let toml_client = StellarTomlClient::new(Arc::new(TokioRwLock::new(None)), None).unwrap();
+        TransferServerProbe::new(Arc::new(toml_client), config).unwrap()
+    }
+
+    #[test]
+    fn test_classify_by_status_and_latency() {
+        let config = TransferProbeConfig::default();
+        let fast = Duration::from_millis(100);
+        let slow = Duration::from_secs(5);
+
+        assert_eq!(
+            config.classify(Some(StatusCode::OK), fast),
+            TransferServerStatus::Up
+        );
+        assert_eq!(
+            config.classify(Some(StatusCode::OK), slow),
+            TransferServerStatus::Degraded
+        );
+        assert_eq!(
+            config.classify(Some(StatusCode::NOT_FOUND), fast),
+            TransferServerStatus::Degraded
+        );
+        assert_eq!(
+            config.classify(Some(StatusCode::SERVICE_UNAVAILABLE), fast),
+            TransferServerStatus::Down
+        );
+        assert_eq!(config.classify(None, fast), TransferServerStatus::Down);
+    }
+
+    #[tokio::test]
+    async fn test_status_transitions_against_mock_transfer_server() {
+        let server = TestServer::start([
+            ScriptedResponse::json(StatusCode::OK, r#"{"deposit":{}}"#),
+            ScriptedResponse::json(StatusCode::OK, r#"{"deposit":{}}"#),
+            ScriptedResponse::json(StatusCode::SERVICE_UNAVAILABLE, "{}"),
+            ScriptedResponse::json(StatusCode::OK, r#"{"deposit":{}}"#)
+                .with_delay(Duration::from_millis(200)),
+        ])
+        .await;
+        let probe = probe(TransferProbeConfig {
+            timeout: Duration::from_secs(5),
+            degraded_latency: Duration::from_millis(100),
+        });
+        let (manager, mut rx) = AlertManager::new();
+        let anchor = anchor();
+
+        let check = || async {
+            let status = probe.probe(server.url()).await;
+            (status, probe.record(&manager, &anchor, status).await)
+        };
+
+        // The first probe is the baseline; an unchanged status stays quiet
+        assert_eq!(check().await, (TransferServerStatus::Up, false));
+        assert_eq!(check().await, (TransferServerStatus::Up, false));
+        assert_eq!(check().await, (TransferServerStatus::Down, true));
+        assert_eq!(check().await, (TransferServerStatus::Degraded, true));
+        assert_eq!(server.hits(), 4);
+
+        let down = rx.try_recv().unwrap();
+        assert!(matches!(down.alert_type, AlertType::AnchorStatusChange));
+        assert_eq!(down.anchor_id.as_deref(), Some("anchor-1"));
+        assert_eq!(down.severity, AlertSeverity::Critical);
+        assert!(down.message.contains("from up to down"));
+
+        let recovering = rx.try_recv().unwrap();
+        assert!(recovering.message.contains("from down to degraded"));
+        assert!(rx.try_recv().is_err());
+        assert_eq!(
+            probe.status("anchor-1").await,
+            Some(TransferServerStatus::Degraded)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_transfer_server_is_down() {
+        let probe = probe(TransferProbeConfig::default());
+        // Nothing listens on port 1, so the connection is refused
+        assert_eq!(
+            probe.probe("http://127.0.0.1:1").await,
+            TransferServerStatus::Down
+        );
+    }
+
+    #[test]
+    fn test_transfer_server_url_prefers_sep24() {
+        let client = StellarTomlClient::new(Arc::new(TokioRwLock::new(None)), None).unwrap();
+        let both = client
+            .parse_toml(
+                "TRANSFER_SERVER = \"https://a.example/sep6\"\nTRANSFER_SERVER_SEP0024 = \"https://a.example/sep24\"",
+                "a.example",
+            )
+            .unwrap();
+        let sep6 = client
+            .parse_toml("TRANSFER_SERVER = \"https://a.example/sep6\"", "a.example")
+            .unwrap();
+        let neither = client.parse_toml("", "a.example").unwrap();
+
+        assert_eq!(transfer_server_url(&both), Some("https://a.example/sep24"));
+        assert_eq!(transfer_server_url(&sep6), Some("https://a.example/sep6"));
+        assert_eq!(transfer_server_url(&neither), None);
+    }
+}
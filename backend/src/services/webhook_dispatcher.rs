@@ -3,37 +3,106 @@
 use anyhow::Result;
 use reqwest::Client;
 use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
 use std::time::Duration;
+use tokio::sync::{broadcast, Semaphore};
 use uuid::Uuid;
 
-use crate::webhooks::{WebhookEventEnvelope, WebhookService, WebhookSignature};
+use crate::webhooks::{
+    PendingWebhookEvent, WebhookEventEnvelope, WebhookService, WebhookSignature,
+    DELIVERY_TIMEOUT_ERROR,
+};
+
+/// Limits that keep one slow consumer from starving the others
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookDeliveryConfig {
+    /// HTTP timeout for a single delivery attempt
+    pub timeout: Duration,
+    /// Deliveries in flight at once to any one webhook
+    pub max_concurrent_per_webhook: usize,
+}
+
+impl Default for WebhookDeliveryConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(10),
+            max_concurrent_per_webhook: 2,
+        }
+    }
+}
+
+impl WebhookDeliveryConfig {
+    /// Load from `WEBHOOK_DELIVERY_TIMEOUT_MS` (default 10000) and
+    /// `WEBHOOK_MAX_CONCURRENT_DELIVERIES` (default 2).
+    #[must_use]
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        Self {
+            timeout: std::env::var("WEBHOOK_DELIVERY_TIMEOUT_MS")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .map_or(default.timeout, |ms| {
+                    Duration::from_millis(ms.clamp(100, 120_000))
+                }),
+            max_concurrent_per_webhook: std::env::var("WEBHOOK_MAX_CONCURRENT_DELIVERIES")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(default.max_concurrent_per_webhook)
+                .clamp(1, 64),
+        }
+    }
+}
 
 /// Webhook dispatcher - sends events to webhooks asynchronously
 pub struct WebhookDispatcher {
     db: SqlitePool,
     http_client: Client,
+    config: WebhookDeliveryConfig,
+    /// Caps in-flight deliveries per webhook id
+    webhook_permits: Mutex<HashMap<String, Arc<Semaphore>>>,
 }
 
 impl WebhookDispatcher {
     /// Create new webhook dispatcher
     #[must_use]
     pub fn new(db: SqlitePool) -> Self {
+        Self::with_config(db, WebhookDeliveryConfig::from_env())
+    }
+
+    /// Create a dispatcher with explicit delivery limits
+    #[must_use]
+    pub fn with_config(db: SqlitePool, config: WebhookDeliveryConfig) -> Self {
         let http_client = Client::builder()
-            .timeout(Duration::from_secs(10))
+            .timeout(config.timeout)
             .build()
             .unwrap_or_else(|_| Client::new());
 
-        Self { db, http_client }
+        Self {
+            db,
+            http_client,
+            config,
+            webhook_permits: Mutex::new(HashMap::new()),
+        }
     }
 
-    /// Run dispatcher loop - processes pending webhook events
-    pub async fn run(&self) -> Result<()> {
+    /// Run dispatcher loop - processes pending webhook events.
+    ///
+    /// Returns `Ok(())` once `shutdown_rx` fires; a batch already being
+    /// delivered is finished first.
+    pub async fn run(&self, mut shutdown_rx: broadcast::Receiver<()>) -> Result<()> {
         tracing::info!("Starting webhook dispatcher");
 
         let mut interval = tokio::time::interval(Duration::from_secs(5));
 
         loop {
-            interval.tick().await;
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown_rx.recv() => {
+                    tracing::info!("Webhook dispatcher stopped");
+                    return Ok(());
+                }
+            }
 
             if let Err(e) = self.process_pending_events().await {
                 tracing::error!("Error processing webhook events: {}", e);
@@ -45,107 +114,169 @@ impl WebhookDispatcher {
     async fn process_pending_events(&self) -> Result<()> {
         let service = WebhookService::new(self.db.clone());
 
-        // Fetch pending events (max 10 per run)
-        let events = service.get_pending_events(10).await?;
+        // Fetch pending events (max 10 per run), no more per webhook than it
+        // may have in flight, so a backlog on one endpoint can't fill the batch
+        // and hold every pass for several of its timeouts
+        let events = service
+            .get_pending_events(10, self.config.max_concurrent_per_webhook)
+            .await?;
+
+        // Deliver concurrently; each webhook is capped by its own permits, so
+        // a slow endpoint only holds up its own events
+        let results = futures::future::join_all(
+            events
+                .into_iter()
+                .map(|event| self.process_event(&service, event)),
+        )
+        .await;
+        for result in results {
+            if let Err(e) = result {
+                tracing::error!("Error processing webhook event: {}", e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Deliver one pending event and record the outcome
+    async fn process_event(
+        &self,
+        service: &WebhookService,
+        event: PendingWebhookEvent,
+    ) -> Result<()> {
+        let PendingWebhookEvent {
+            id: event_id,
+            webhook_id,
+            event_type,
+            ..
+        } = &event;
+        // Get webhook details
+        let webhook = if let Some(w) = service.get_webhook(&webhook_id).await? {
+            w
+        } else {
+            // Webhook was deleted, mark event as failed
+            let _ = service
+                .update_event_status(&event_id, "failed", Some("webhook_deleted"), 0)
+                .await;
+            return Ok(());
+        };
+
+        if !webhook.is_active {
+            let _ = service
+                .update_event_status(&event_id, "failed", Some("webhook_inactive"), 0)
+                .await;
+            return Ok(());
+        }
+
+        // Attempt delivery
+        let semaphore = self.webhook_semaphore(&webhook_id);
+        let permit = semaphore.acquire().await?;
+        let delivery = self
+            .deliver_webhook(&webhook.url, &event, &webhook.secret)
+            .await;
+        drop(permit);
 
-        for (event_id, webhook_id, event_type, payload_str) in events {
-            // Get webhook details
-            let webhook = if let Some(w) = service.get_webhook(&webhook_id).await? {
-                w
-            } else {
-                // Webhook was deleted, mark event as failed
+        match delivery {
+            Ok(()) => {
+                // Success
                 let _ = service
-                    .update_event_status(&event_id, "failed", Some("webhook_deleted"), 0)
+                    .update_event_status(&event_id, "delivered", None, 0)
                     .await;
-                continue;
-            };
 
-            if !webhook.is_active {
+                // Update webhook's last_fired_at
+                let _ = service.update_last_fired(&webhook_id).await;
+
+                tracing::info!(
+                    "Webhook delivered successfully: webhook_id={}, event={}",
+                    webhook_id,
+                    event_type
+                );
+            }
+            Err(e) if is_timeout(&e) => {
+                // Recorded as failed; get_pending_events retries timeouts
+                let retries = self.get_event_retries(&event_id).await.unwrap_or(0) + 1;
+                let error = format!(
+                    "{DELIVERY_TIMEOUT_ERROR}: no response within {}ms",
+                    self.config.timeout.as_millis()
+                );
                 let _ = service
-                    .update_event_status(&event_id, "failed", Some("webhook_inactive"), 0)
+                    .update_event_status(&event_id, "failed", Some(&error), retries)
                     .await;
-                continue;
+
+                tracing::warn!(
+                    "Webhook delivery timed out: webhook_id={}, retries={}",
+                    webhook_id,
+                    retries
+                );
             }
+            Err(e) => {
+                // Determine retry count from event
+                let current_retries = self.get_event_retries(&event_id).await.unwrap_or(0);
 
-            // Attempt delivery
-            match self
-                .deliver_webhook(&webhook.url, &payload_str, &webhook.secret, &event_type)
-                .await
-            {
-                Ok(()) => {
-                    // Success
+                if current_retries < 3 {
+                    // Retry later
                     let _ = service
-                        .update_event_status(&event_id, "delivered", None, 0)
+                        .update_event_status(
+                            &event_id,
+                            "pending",
+                            Some(&e.to_string()),
+                            current_retries + 1,
+                        )
                         .await;
 
-                    // Update webhook's last_fired_at
-                    let _ = service.update_last_fired(&webhook_id).await;
+                    tracing::warn!(
+                        "Webhook delivery failed (will retry): webhook_id={}, error={}, retries={}",
+                        webhook_id,
+                        e,
+                        current_retries + 1
+                    );
+                } else {
+                    // Max retries exceeded
+                    let _ = service
+                        .update_event_status(&event_id, "failed", Some(&e.to_string()), 3)
+                        .await;
 
-                    tracing::info!(
-                        "Webhook delivered successfully: webhook_id={}, event={}",
+                    tracing::error!(
+                        "Webhook delivery failed (max retries): webhook_id={}, error={}",
                         webhook_id,
-                        event_type
+                        e
                     );
                 }
-                Err(e) => {
-                    // Determine retry count from event
-                    let current_retries = self.get_event_retries(&event_id).await.unwrap_or(0);
-
-                    if current_retries < 3 {
-                        // Retry later
-                        let _ = service
-                            .update_event_status(
-                                &event_id,
-                                "pending",
-                                Some(&e.to_string()),
-                                current_retries + 1,
-                            )
-                            .await;
-
-                        tracing::warn!(
-                            "Webhook delivery failed (will retry): webhook_id={}, error={}, retries={}",
-                            webhook_id,
-                            e,
-                            current_retries + 1
-                        );
-                    } else {
-                        // Max retries exceeded
-                        let _ = service
-                            .update_event_status(&event_id, "failed", Some(&e.to_string()), 3)
-                            .await;
-
-                        tracing::error!(
-                            "Webhook delivery failed (max retries): webhook_id={}, error={}",
-                            webhook_id,
-                            e
-                        );
-                    }
-                }
             }
         }
 
         Ok(())
     }
 
+    /// Semaphore bounding concurrent deliveries to `webhook_id`
+    fn webhook_semaphore(&self, webhook_id: &str) -> Arc<Semaphore> {
+        let mut permits = self
+            .webhook_permits
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        Arc::clone(
+            permits.entry(webhook_id.to_string()).or_insert_with(|| {
+                Arc::new(Semaphore::new(self.config.max_concurrent_per_webhook))
+            }),
+        )
+    }
+
     /// Deliver webhook to URL
     async fn deliver_webhook(
         &self,
         url: &str,
-        payload: &str,
+        event: &PendingWebhookEvent,
         secret: &str,
-        event_type: &str,
     ) -> Result<()> {
         let delivery_id = Uuid::new_v4().to_string();
         let timestamp = chrono::Utc::now().timestamp();
 
-        // Create envelope
-        let envelope = WebhookEventEnvelope {
-            id: delivery_id.clone(),
-            event: event_type.to_string(),
-            timestamp,
-            data: serde_json::from_str(payload)?,
-        };
-
+        let envelope = WebhookEventEnvelope::from_stored(
+            &event.id,
+            &event.event_type,
+            &event.created_at,
+            &event.payload,
+        )?;
         let body = serde_json::to_string(&envelope)?;
         let signature = WebhookSignature::sign(&body, secret);
 
@@ -159,7 +290,7 @@ impl WebhookDispatcher {
         let response = self
             .http_client
             .post(url)
-            .header("X-Zapier-Event", event_type)
+            .header("X-Zapier-Event", &event.event_type)
             .header("X-Zapier-Signature", signature)
             .header("X-Zapier-Timestamp", timestamp.to_string())
             .header("X-Zapier-Delivery-ID", delivery_id)
@@ -191,11 +322,238 @@ impl WebhookDispatcher {
     }
 }
 
+fn is_timeout(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<reqwest::Error>()
+        .is_some_and(reqwest::Error::is_timeout)
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::rpc::test_server::{ScriptedResponse, TestServer};
+    use axum::http::StatusCode;
+
     #[test]
     fn test_webhook_dispatcher_creation() {
         // This is a smoke test for basic creation
         // Full tests would require mocking the database and HTTP client
     }
+
+    async fn test_pool() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::query("CREATE TABLE users (id TEXT PRIMARY KEY); INSERT INTO users VALUES ('u1');")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(include_str!("../../migrations/019_oauth_webhooks.sql"))
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(include_str!(
+            "../../migrations/042_add_webhook_event_payload_truncation.sql"
+        ))
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool
+    }
+
+    async fn add_webhook(pool: &SqlitePool, id: &str, url: &str) {
+        sqlx::query(
+            "INSERT INTO webhooks (id, user_id, url, event_types, secret)
+             VALUES (?, 'u1', ?, 'payment.created', 'secret')",
+        )
+        .bind(id)
+        .bind(url)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    async fn event_state(pool: &SqlitePool, id: &str) -> (String, Option<String>, i64) {
+        sqlx::query_as("SELECT status, last_error, retries FROM webhook_events WHERE id = ?")
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_slow_endpoint_times_out_without_blocking_fast_one() {
+        let pool = test_pool().await;
+        let slow = TestServer::start([
+            ScriptedResponse::json(StatusCode::OK, "{}").with_delay(Duration::from_secs(5))
+        ])
+        .await;
+        let fast = TestServer::start([ScriptedResponse::json(StatusCode::OK, "{}")]).await;
+        add_webhook(&pool, "slow", slow.url()).await;
+        add_webhook(&pool, "fast", fast.url()).await;
+
+        // The slow endpoint's backlog is older and larger than a whole batch
+        let service = WebhookService::new(pool.clone());
+        let payload = serde_json::json!({ "amount": 1 });
+        let mut slow_events = Vec::new();
+        for _ in 0..12 {
+            slow_events.push(
+                service
+                    .create_webhook_event("slow", "payment.created", payload.clone())
+                    .await
+                    .unwrap(),
+            );
+        }
+        let mut fast_events = Vec::new();
+        for _ in 0..2 {
+            fast_events.push(
+                service
+                    .create_webhook_event("fast", "payment.created", payload.clone())
+                    .await
+                    .unwrap(),
+            );
+        }
+
+        let dispatcher = WebhookDispatcher::with_config(
+            pool.clone(),
+            WebhookDeliveryConfig {
+                timeout: Duration::from_millis(200),
+                max_concurrent_per_webhook: 2,
+            },
+        );
+        let started = std::time::Instant::now();
+        dispatcher.process_pending_events().await.unwrap();
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        for id in &fast_events {
+            assert_eq!(event_state(&pool, id).await.0, "delivered");
+        }
+        assert_eq!(fast.hits(), 2);
+
+        // Only the slow webhook's share of the batch was attempted
+        let (attempted, queued) = slow_events.split_at(2);
+        for id in attempted {
+            let (status, last_error, retries) = event_state(&pool, id).await;
+            assert_eq!(status, "failed");
+            assert!(last_error.unwrap().starts_with(DELIVERY_TIMEOUT_ERROR));
+            assert_eq!(retries, 1);
+        }
+        for id in queued {
+            assert_eq!(
+                event_state(&pool, id).await,
+                ("pending".to_string(), None, 0)
+            );
+        }
+        // Timed-out deliveries stay queued for another attempt
+        let pending = service.get_pending_events(100, 100).await.unwrap();
+        assert_eq!(pending.len(), 12);
+        assert!(pending.iter().all(|event| event.webhook_id == "slow"));
+    }
+
+    #[tokio::test]
+    async fn test_large_payload_is_truncated_in_storage_but_delivered_in_full() {
+        let pool = test_pool().await;
+        let server = TestServer::start([ScriptedResponse::json(StatusCode::OK, "{}")]).await;
+        add_webhook(&pool, "ledgers", server.url()).await;
+
+        let service = WebhookService::new(pool.clone()).with_max_stored_payload_bytes(256);
+        let payload = serde_json::json!({ "operations": "x".repeat(10_000) });
+        let small = service
+            .create_webhook_event("ledgers", "ledger.closed", serde_json::json!({ "seq": 1 }))
+            .await
+            .unwrap();
+        let large = service
+            .create_webhook_event("ledgers", "ledger.closed", payload.clone())
+            .await
+            .unwrap();
+
+        let stored = |id: String| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query_as::<_, (String, bool)>(
+                    "SELECT payload, payload_truncated FROM webhook_events WHERE id = ?",
+                )
+                .bind(id)
+                .fetch_one(&pool)
+                .await
+                .unwrap()
+            }
+        };
+        let (small_payload, small_truncated) = stored(small.clone()).await;
+        assert!(!small_truncated);
+        assert!(small_payload.contains(r#""seq":1"#));
+
+        let (large_payload, large_truncated) = stored(large.clone()).await;
+        assert!(large_truncated);
+        assert!(large_payload.len() < 1_000);
+        let preview: WebhookEventEnvelope = serde_json::from_str(&large_payload).unwrap();
+        assert_eq!(preview.id, large);
+        assert_eq!(preview.data["truncated"], true);
+
+        // A fresh service, as after a restart, still sees the full payload
+        let pending = WebhookService::new(pool.clone())
+            .get_pending_events(10, 10)
+            .await
+            .unwrap();
+        let pending_large = pending.iter().find(|event| event.id == large).unwrap();
+        let pending_large: WebhookEventEnvelope =
+            serde_json::from_str(&pending_large.payload).unwrap();
+        assert_eq!(pending_large.data, payload);
+
+        WebhookDispatcher::new(pool.clone())
+            .process_pending_events()
+            .await
+            .unwrap();
+
+        assert_eq!(event_state(&pool, &large).await.0, "delivered");
+        let delivered: Vec<WebhookEventEnvelope> = server
+            .request_bodies()
+            .iter()
+            .map(|body| serde_json::from_slice(body).unwrap())
+            .collect();
+        let full = delivered.iter().find(|e| e.id == large).unwrap();
+        assert_eq!(full.data, payload);
+        // Storage stays trimmed after delivery and the full copy is dropped
+        assert!(stored(large.clone()).await.1);
+        let full_payload: Option<String> =
+            sqlx::query_scalar("SELECT full_payload FROM webhook_events WHERE id = ?")
+                .bind(large)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(full_payload, None);
+    }
+
+    #[tokio::test]
+    async fn test_legacy_payload_keeps_row_created_at_across_retries() {
+        let pool = test_pool().await;
+        let server = TestServer::start([
+            ScriptedResponse::json(StatusCode::INTERNAL_SERVER_ERROR, "{}"),
+            ScriptedResponse::json(StatusCode::OK, "{}"),
+        ])
+        .await;
+        add_webhook(&pool, "legacy", server.url()).await;
+        // Queued as a bare payload, before events were stored enveloped
+        sqlx::query(
+            "INSERT INTO webhook_events (id, webhook_id, event_type, payload, status, retries, created_at)
+             VALUES ('evt-legacy', 'legacy', 'ledger.closed', '{\"seq\":1}', 'pending', 0,
+                     '2026-01-01 00:00:00')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let dispatcher = WebhookDispatcher::new(pool.clone());
+        dispatcher.process_pending_events().await.unwrap();
+        dispatcher.process_pending_events().await.unwrap();
+
+        assert_eq!(event_state(&pool, "evt-legacy").await.0, "delivered");
+        let delivered: Vec<WebhookEventEnvelope> = server
+            .request_bodies()
+            .iter()
+            .map(|body| serde_json::from_slice(body).unwrap())
+            .collect();
+        assert_eq!(delivered.len(), 2);
+        for envelope in delivered {
+            assert_eq!(envelope.id, "evt-legacy");
+            assert_eq!(envelope.created_at, "2026-01-01 00:00:00");
+        }
+    }
 }
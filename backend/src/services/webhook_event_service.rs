@@ -3,17 +3,25 @@
 use anyhow::Result;
 use serde_json::json;
 use sqlx::SqlitePool;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
 
+use crate::rpc::{LedgerInfo, Trade};
 use crate::webhooks::events::{
     AnchorStatusChangedEvent, CorridorHealthDegradedEvent, CorridorLiquidityDroppedEvent,
-    CorridorMetrics, PaymentCreatedEvent,
+    CorridorMetrics, LedgerClosedEvent, PaymentCreatedEvent, TradeExecutedEvent,
 };
 use crate::webhooks::{WebhookEventType, WebhookService};
 
+/// Filter key that limits a webhook to one in every N matching events,
+/// e.g. `{"sample_every": 10}` delivers every tenth ledger.
+pub const SAMPLE_FILTER_KEY: &str = "sample_every";
+
 /// Webhook Event Service - triggers events for registered webhooks
 pub struct WebhookEventService {
     webhook_service: Arc<WebhookService>,
+    /// Matching events seen per `webhook_id:event_type`, used for sampling
+    sample_counters: Mutex<HashMap<String, u64>>,
 }
 
 impl WebhookEventService {
@@ -21,6 +29,7 @@ impl WebhookEventService {
     pub fn new(db: SqlitePool) -> Self {
         Self {
             webhook_service: Arc::new(WebhookService::new(db)),
+            sample_counters: Mutex::new(HashMap::new()),
         }
     }
 
@@ -118,6 +127,63 @@ impl WebhookEventService {
             .await
     }
 
+    /// Trigger ledger closed event
+    pub async fn trigger_ledger_closed(&self, ledger: &LedgerInfo) -> Result<()> {
+        let event = LedgerClosedEvent {
+            sequence: ledger.sequence,
+            hash: ledger.hash.clone(),
+            previous_hash: ledger.previous_hash.clone(),
+            transaction_count: ledger.transaction_count,
+            operation_count: ledger.operation_count,
+            closed_at: ledger.closed_at.clone(),
+            protocol_version: ledger.protocol_version,
+        };
+
+        let payload = json!(event);
+        self.trigger_event(WebhookEventType::LedgerClosed, payload)
+            .await
+    }
+
+    /// Trigger trade executed event
+    pub async fn trigger_trade_executed(&self, trade: &Trade) -> Result<()> {
+        let price = if trade.price.d == 0 {
+            0.0
+        } else {
+            trade.price.n as f64 / trade.price.d as f64
+        };
+
+        let event = TradeExecutedEvent {
+            trade_id: trade.id.clone(),
+            trade_type: trade.trade_type.clone(),
+            base_account: trade.base_account.clone(),
+            base_asset_code: trade
+                .base_asset_code
+                .clone()
+                .unwrap_or_else(|| "XLM".to_string()),
+            base_asset_issuer: trade
+                .base_asset_issuer
+                .clone()
+                .unwrap_or_else(|| "native".to_string()),
+            base_amount: trade.base_amount.parse::<f64>().unwrap_or(0.0),
+            counter_account: trade.counter_account.clone(),
+            counter_asset_code: trade
+                .counter_asset_code
+                .clone()
+                .unwrap_or_else(|| "XLM".to_string()),
+            counter_asset_issuer: trade
+                .counter_asset_issuer
+                .clone()
+                .unwrap_or_else(|| "native".to_string()),
+            counter_amount: trade.counter_amount.parse::<f64>().unwrap_or(0.0),
+            price,
+            timestamp: trade.ledger_close_time.clone(),
+        };
+
+        let payload = json!(event);
+        self.trigger_event(WebhookEventType::TradeExecuted, payload)
+            .await
+    }
+
     /// Generic method to trigger an event for all matching webhooks
     async fn trigger_event(
         &self,
@@ -136,6 +202,9 @@ impl WebhookEventService {
                     if !self.apply_filters(&payload, &filter_obj) {
                         continue; // Skip this webhook as it doesn't match filters
                     }
+                    if !self.sample(&webhook.id, event_type_str, &filter_obj) {
+                        continue; // Skip events outside the webhook's sample
+                    }
                 }
             }
 
@@ -177,26 +246,97 @@ impl WebhookEventService {
         Ok(matching_webhooks)
     }
 
-    /// Apply filters to determine if webhook should be triggered
+    /// Apply filters to determine if webhook should be triggered.
+    ///
+    /// Each filter key is a dotted path into the payload (e.g. `asset.code`).
+    /// A plain value must equal the payload value; an object of operators
+    /// (`eq`, `ne`, `gt`, `gte`, `lt`, `lte`, `in`) must hold for all of them,
+    /// e.g. `{"amount": {"gt": 1000}, "asset_code": {"in": ["USDC", "EURC"]}}`.
     fn apply_filters(&self, payload: &serde_json::Value, filters: &serde_json::Value) -> bool {
-        // Simple filter implementation - can be extended
-        if let Some(filter_obj) = filters.as_object() {
-            for (key, expected_value) in filter_obj {
-                if let Some(payload_value) = payload.get(key) {
-                    // Simple equality check for now
-                    if payload_value != expected_value {
-                        return false;
-                    }
-                } else {
-                    // Filter key not found in payload
-                    return false;
-                }
+        let Some(filter_obj) = filters.as_object() else {
+            return true;
+        };
+        filter_obj
+            .iter()
+            .filter(|(key, _)| key.as_str() != SAMPLE_FILTER_KEY)
+            .all(|(path, condition)| {
+                lookup_path(payload, path).is_some_and(|value| matches_condition(value, condition))
+            })
+    }
+
+    /// Returns true if this event falls inside the webhook's `sample_every`
+    /// filter. The first matching event is always delivered.
+    fn sample(&self, webhook_id: &str, event_type: &str, filters: &serde_json::Value) -> bool {
+        let Some(every) = filters
+            .get(SAMPLE_FILTER_KEY)
+            .and_then(serde_json::Value::as_u64)
+            .filter(|n| *n > 1)
+        else {
+            return true;
+        };
+
+        let mut counters = self
+            .sample_counters
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let seen = counters
+            .entry(format!("{webhook_id}:{event_type}"))
+            .or_insert(0);
+        let deliver = *seen % every == 0;
+        *seen += 1;
+        deliver
+    }
+}
+
+/// Resolve a dotted path such as `asset.code` against nested payload objects
+fn lookup_path<'a>(payload: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    path.split('.')
+        .try_fold(payload, |value, segment| value.get(segment))
+}
+
+/// Check a payload value against a filter condition
+fn matches_condition(value: &serde_json::Value, condition: &serde_json::Value) -> bool {
+    match condition.as_object() {
+        Some(ops) if !ops.is_empty() && ops.keys().all(|op| is_filter_operator(op)) => ops
+            .iter()
+            .all(|(op, operand)| apply_operator(value, op, operand)),
+        _ => value == condition,
+    }
+}
+
+fn is_filter_operator(op: &str) -> bool {
+    matches!(op, "eq" | "ne" | "gt" | "gte" | "lt" | "lte" | "in")
+}
+
+fn apply_operator(value: &serde_json::Value, op: &str, operand: &serde_json::Value) -> bool {
+    match op {
+        "eq" => value == operand,
+        "ne" => value != operand,
+        "in" => operand
+            .as_array()
+            .is_some_and(|candidates| candidates.contains(value)),
+        _ => {
+            let (Some(actual), Some(bound)) = (as_number(value), as_number(operand)) else {
+                return false;
+            };
+            match op {
+                "gt" => actual > bound,
+                "gte" => actual >= bound,
+                "lt" => actual < bound,
+                "lte" => actual <= bound,
+                _ => false,
             }
         }
-        true
     }
 }
 
+/// Numbers compare directly; Horizon encodes amounts as decimal strings
+fn as_number(value: &serde_json::Value) -> Option<f64> {
+    value
+        .as_f64()
+        .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -223,5 +363,261 @@ mod tests {
         });
 
         assert!(!service.apply_filters(&payload, &mismatched_filters));
+
+        let sampled_filters = json!({
+            "severity": "warning",
+            "sample_every": 5
+        });
+
+        assert!(service.apply_filters(&payload, &sampled_filters));
+    }
+
+    fn payment_payload(amount: &str, asset_code: &str) -> serde_json::Value {
+        json!({
+            "id": "op-1",
+            "amount": amount,
+            "asset": { "code": asset_code, "issuer": "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN" },
+            "ledger": 123
+        })
+    }
+
+    #[tokio::test]
+    async fn test_numeric_greater_than_filter() {
+        let pool = sqlx::SqlitePool::connect(":memory:").await.unwrap();
+        let service = WebhookEventService::new(pool);
+        let filters = json!({ "amount": { "gt": 1000 } });
+
+        assert!(service.apply_filters(&payment_payload("2500.5", "USDC"), &filters));
+        assert!(!service.apply_filters(&payment_payload("1000", "USDC"), &filters));
+        assert!(service.apply_filters(
+            &json!({ "ledger": 123 }),
+            &json!({ "ledger": { "gte": 123, "lt": 124 } })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_in_filter_over_nested_asset_codes() {
+        let pool = sqlx::SqlitePool::connect(":memory:").await.unwrap();
+        let service = WebhookEventService::new(pool);
+        let filters = json!({ "asset.code": { "in": ["USDC", "EURC"] } });
+
+        assert!(service.apply_filters(&payment_payload("10", "USDC"), &filters));
+        assert!(service.apply_filters(&payment_payload("10", "EURC"), &filters));
+        assert!(!service.apply_filters(&payment_payload("10", "XLM"), &filters));
+    }
+
+    #[tokio::test]
+    async fn test_event_not_matching_filters_is_rejected() {
+        let pool = sqlx::SqlitePool::connect(":memory:").await.unwrap();
+        let service = WebhookEventService::new(pool);
+        let payload = payment_payload("50", "USDC");
+
+        let filters = json!({
+            "asset.code": { "in": ["USDC"] },
+            "amount": { "gt": 100 }
+        });
+        assert!(!service.apply_filters(&payload, &filters));
+        // Missing paths never match
+        assert!(!service.apply_filters(&payload, &json!({ "asset.type": "credit_alphanum4" })));
+        // Numeric operators don't match non-numeric values
+        assert!(!service.apply_filters(&payload, &json!({ "asset.code": { "gt": 1 } })));
+        // No filters matches everything
+        assert!(service.apply_filters(&payload, &json!({})));
+    }
+
+    async fn pool_with_webhooks(webhooks: &[(&str, &str, Option<&str>)]) -> SqlitePool {
+        let pool = sqlx::SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::query(
+            "CREATE TABLE webhooks (
+                id TEXT PRIMARY KEY,
+                user_id TEXT NOT NULL,
+                url TEXT NOT NULL,
+                event_types TEXT NOT NULL,
+                filters TEXT,
+                secret TEXT NOT NULL,
+                is_active INTEGER NOT NULL DEFAULT 1,
+                created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                last_fired_at TEXT
+            )",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "CREATE TABLE webhook_events (
+                id TEXT PRIMARY KEY,
+                webhook_id TEXT NOT NULL,
+                event_type TEXT NOT NULL,
+                payload TEXT NOT NULL,
+                payload_truncated INTEGER NOT NULL DEFAULT 0,
+                full_payload TEXT,
+                status TEXT NOT NULL,
+                retries INTEGER NOT NULL DEFAULT 0,
+                last_error TEXT,
+                created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+            )",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        for (id, event_types, filters) in webhooks {
+            sqlx::query(
+                "INSERT INTO webhooks (id, user_id, url, event_types, filters, secret)
+                 VALUES (?, 'user-1', 'https://example.com/hook', ?, ?, 'secret')",
+            )
+            .bind(id)
+            .bind(event_types)
+            .bind(filters)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        pool
+    }
+
+    async fn event_count(pool: &SqlitePool, webhook_id: &str, event_type: &str) -> i64 {
+        sqlx::query_scalar(
+            "SELECT COUNT(*) FROM webhook_events WHERE webhook_id = ? AND event_type = ?",
+        )
+        .bind(webhook_id)
+        .bind(event_type)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_trigger_ledger_closed_respects_subscription_and_sampling() {
+        let pool = pool_with_webhooks(&[
+            ("every-ledger", "ledger.closed", None),
+            (
+                "one-in-three",
+                "ledger.closed",
+                Some(r#"{"sample_every": 3}"#),
+            ),
+            ("payments-only", "payment.created", None),
+        ])
+        .await;
+        let service = WebhookEventService::new(pool.clone());
+
+        let mut ledger = crate::rpc::mock_stellar::mock_ledger_info();
+        for _ in 0..7 {
+            service.trigger_ledger_closed(&ledger).await.unwrap();
+            ledger.sequence += 1;
+        }
+
+        assert_eq!(event_count(&pool, "every-ledger", "ledger.closed").await, 7);
+        assert_eq!(event_count(&pool, "one-in-three", "ledger.closed").await, 3);
+        assert_eq!(
+            event_count(&pool, "payments-only", "ledger.closed").await,
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_trigger_trade_executed_applies_payload_filters() {
+        let pool = pool_with_webhooks(&[
+            (
+                "usdc-trades",
+                "trade.executed",
+                Some(r#"{"counter_asset_code": "USDC"}"#),
+            ),
+            (
+                "eurc-trades",
+                "trade.executed",
+                Some(r#"{"counter_asset_code": "EURC"}"#),
+            ),
+        ])
+        .await;
+        let service = WebhookEventService::new(pool.clone());
+
+        for trade in crate::rpc::mock_stellar::mock_trades(2) {
+            service.trigger_trade_executed(&trade).await.unwrap();
+        }
+
+        assert_eq!(event_count(&pool, "usdc-trades", "trade.executed").await, 2);
+        assert_eq!(event_count(&pool, "eurc-trades", "trade.executed").await, 0);
+
+        let payload: String = sqlx::query_scalar(
+            "SELECT payload FROM webhook_events WHERE webhook_id = 'usdc-trades' LIMIT 1",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let payload: serde_json::Value = serde_json::from_str(&payload).unwrap();
+        assert_eq!(payload["data"]["base_asset_code"], "XLM");
+        assert!(payload["data"]["price"].as_f64().unwrap() > 0.0);
+    }
+
+    fn sorted_keys(value: &serde_json::Value) -> Vec<&str> {
+        let mut keys: Vec<&str> = value
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    #[tokio::test]
+    async fn test_payment_created_payload_is_enveloped() {
+        let pool = pool_with_webhooks(&[("payments", "payment.created", None)]).await;
+        let service = WebhookEventService::new(pool.clone());
+
+        service
+            .trigger_payment_created(
+                "pay-1",
+                "GSOURCE",
+                "GDEST",
+                "USDC",
+                "GISSUER",
+                25.5,
+                "2026-01-01T00:00:00Z",
+            )
+            .await
+            .unwrap();
+
+        let (event_id, payload): (String, String) =
+            sqlx::query_as("SELECT id, payload FROM webhook_events WHERE webhook_id = 'payments'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        let envelope: serde_json::Value = serde_json::from_str(&payload).unwrap();
+
+        assert_eq!(
+            sorted_keys(&envelope),
+            vec!["created_at", "data", "event_type", "id", "version"]
+        );
+        assert_eq!(
+            envelope["version"],
+            crate::webhooks::WEBHOOK_PAYLOAD_VERSION
+        );
+        assert_eq!(envelope["version"], "1");
+        assert_eq!(envelope["event_type"], "payment.created");
+        assert_eq!(envelope["id"], event_id.as_str());
+        assert!(
+            chrono::DateTime::parse_from_rfc3339(envelope["created_at"].as_str().unwrap()).is_ok()
+        );
+        assert_eq!(
+            sorted_keys(&envelope["data"]),
+            vec![
+                "amount",
+                "asset_code",
+                "asset_issuer",
+                "destination",
+                "payment_id",
+                "source",
+                "timestamp"
+            ]
+        );
+        assert_eq!(envelope["data"]["payment_id"], "pay-1");
+
+        // The delivered body is the stored envelope, so the signature covers it
+        let delivered =
+            crate::webhooks::WebhookEventEnvelope::from_stored("other", "other", "", &payload)
+                .unwrap();
+        assert_eq!(serde_json::to_value(&delivered).unwrap(), envelope);
     }
 }
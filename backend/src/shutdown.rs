@@ -101,6 +101,25 @@ impl ShutdownCoordinator {
     pub const fn db_close_timeout(&self) -> Duration {
         self.config.db_close_timeout
     }
+
+    /// Run the post-signal teardown.
+    ///
+    /// Notifies subscribers, waits for `tasks` to finish their in-flight work,
+    /// flushes exported telemetry (APM spans) and finally closes the pool.
+    pub async fn drain<F>(
+        &self,
+        tasks: Vec<tokio::task::JoinHandle<()>>,
+        pool: sqlx::SqlitePool,
+        flush_telemetry: F,
+    ) where
+        F: FnOnce(),
+    {
+        self.trigger_shutdown();
+        shutdown_background_tasks(tasks, self.background_task_timeout()).await;
+        info!("Flushing telemetry exporters");
+        flush_telemetry();
+        shutdown_database(pool, self.db_close_timeout()).await;
+    }
 }
 
 /// Wait for shutdown signals (SIGTERM, SIGINT/Ctrl+C)
@@ -351,6 +370,43 @@ mod tests {
         assert!(rx2.recv().await.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_drain_stops_running_job_and_flushes_telemetry() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        let coordinator = ShutdownCoordinator::new(ShutdownConfig::default());
+        let pool = sqlx::SqlitePool::connect(":memory:").await.unwrap();
+
+        let stopped = Arc::new(AtomicBool::new(false));
+        let mut stop_rx = coordinator.subscribe();
+        let job_stopped = Arc::clone(&stopped);
+        let job = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(Duration::from_millis(10));
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {}
+                    _ = stop_rx.recv() => {
+                        job_stopped.store(true, Ordering::SeqCst);
+                        break;
+                    }
+                }
+            }
+        });
+
+        let flushed = Arc::new(AtomicBool::new(false));
+        let flush = Arc::clone(&flushed);
+        coordinator
+            .drain(vec![job], pool.clone(), move || {
+                flush.store(true, Ordering::SeqCst);
+            })
+            .await;
+
+        assert!(stopped.load(Ordering::SeqCst));
+        assert!(flushed.load(Ordering::SeqCst));
+        assert!(pool.is_closed());
+    }
+
     #[tokio::test]
     async fn test_shutdown_background_tasks_success() {
         let task1 = tokio::spawn(async {
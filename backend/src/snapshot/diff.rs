@@ -0,0 +1,109 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::snapshot::schema::{AnalyticsSnapshot, SnapshotCorridorMetrics};
+
+/// Structured difference between two stored snapshots
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct SnapshotDiff {
+    pub from_epoch: u64,
+    pub to_epoch: u64,
+    /// Corridors present only in the `to` snapshot
+    pub added_corridors: Vec<String>,
+    /// Corridors present only in the `from` snapshot
+    pub removed_corridors: Vec<String>,
+    /// Metric deltas (`to - from`) for corridors present in both snapshots
+    pub corridor_changes: Vec<CorridorDelta>,
+    /// Anchors whose status differs between the two snapshots
+    pub anchor_status_changes: Vec<AnchorStatusChange>,
+}
+
+/// Change in a corridor's metrics between two snapshots
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct CorridorDelta {
+    pub corridor_key: String,
+    pub success_rate: f64,
+    pub volume_usd: f64,
+    pub liquidity_depth_usd: f64,
+    pub total_transactions: i64,
+}
+
+/// Anchor status transition between two snapshots
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct AnchorStatusChange {
+    pub anchor_id: Uuid,
+    pub name: String,
+    pub from_status: String,
+    pub to_status: String,
+}
+
+impl SnapshotDiff {
+    /// Compare two snapshots. Corridors are matched by `corridor_key` and
+    /// anchors by id; all lists are sorted by those keys.
+    #[must_use]
+    pub fn between(from: &AnalyticsSnapshot, to: &AnalyticsSnapshot) -> Self {
+        let from_corridors = corridors_by_key(from);
+        let to_corridors = corridors_by_key(to);
+
+        let added_corridors = to_corridors
+            .keys()
+            .filter(|key| !from_corridors.contains_key(*key))
+            .map(|key| (*key).to_string())
+            .collect();
+        let removed_corridors = from_corridors
+            .keys()
+            .filter(|key| !to_corridors.contains_key(*key))
+            .map(|key| (*key).to_string())
+            .collect();
+        let corridor_changes = from_corridors
+            .iter()
+            .filter_map(|(key, before)| {
+                let after = to_corridors.get(key)?;
+                Some(CorridorDelta {
+                    corridor_key: (*key).to_string(),
+                    success_rate: after.success_rate - before.success_rate,
+                    volume_usd: after.volume_usd - before.volume_usd,
+                    liquidity_depth_usd: after.liquidity_depth_usd - before.liquidity_depth_usd,
+                    total_transactions: after.total_transactions - before.total_transactions,
+                })
+            })
+            .collect();
+
+        let from_anchors: BTreeMap<_, _> = from.anchor_metrics.iter().map(|a| (a.id, a)).collect();
+        let anchor_status_changes = to
+            .anchor_metrics
+            .iter()
+            .filter_map(|after| {
+                let before = from_anchors.get(&after.id)?;
+                let change = AnchorStatusChange {
+                    anchor_id: after.id,
+                    name: after.name.clone(),
+                    from_status: before.status.clone(),
+                    to_status: after.status.clone(),
+                };
+                (before.status != after.status).then_some((after.id, change))
+            })
+            .collect::<BTreeMap<_, _>>()
+            .into_values()
+            .collect();
+
+        Self {
+            from_epoch: from.epoch,
+            to_epoch: to.epoch,
+            added_corridors,
+            removed_corridors,
+            corridor_changes,
+            anchor_status_changes,
+        }
+    }
+}
+
+fn corridors_by_key(snapshot: &AnalyticsSnapshot) -> BTreeMap<&str, &SnapshotCorridorMetrics> {
+    snapshot
+        .corridor_metrics
+        .iter()
+        .map(|c| (c.corridor_key.as_str(), c))
+        .collect()
+}
@@ -1,7 +1,11 @@
+pub mod diff;
 pub mod generator;
 pub mod schema;
+pub mod validation;
 
+pub use diff::SnapshotDiff;
 pub use generator::SnapshotGenerator;
 pub use schema::{
     AnalyticsSnapshot, SnapshotAnchorMetrics, SnapshotCorridorMetrics, SCHEMA_VERSION,
 };
+pub use validation::{validate_snapshot, verify_stored_hash, SnapshotValidationError};
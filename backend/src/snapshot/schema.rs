@@ -2,8 +2,9 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-/// Snapshot schema version for backward compatibility
-pub const SCHEMA_VERSION: u32 = 1;
+/// Snapshot schema version for backward compatibility. Version 2 rounds USD
+/// amounts before canonical hashing, so its hashes differ from version 1's.
+pub const SCHEMA_VERSION: u32 = 2;
 
 /// Individual anchor metrics within a snapshot
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -0,0 +1,279 @@
+use std::collections::HashSet;
+
+use sha2::{Digest, Sha256};
+
+use crate::error::ApiError;
+use crate::snapshot::schema::AnalyticsSnapshot;
+
+/// Reason a snapshot must not be stored or submitted on-chain
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum SnapshotValidationError {
+    #[error("epoch must repeat or follow the previous epoch {previous}, got {actual}")]
+    NonMonotonicEpoch { previous: u64, actual: u64 },
+    #[error("{field} of {entity} must be a non-negative number, got {value}")]
+    InvalidMetric {
+        entity: String,
+        field: &'static str,
+        value: f64,
+    },
+    #[error("corridor {0} appears more than once")]
+    DuplicateCorridorKey(String),
+    #[error("stored hash {stored} does not match the hash {computed} of the stored payload")]
+    HashMismatch { computed: String, stored: String },
+}
+
+impl SnapshotValidationError {
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::NonMonotonicEpoch { .. } => "SNAPSHOT_NON_MONOTONIC_EPOCH",
+            Self::InvalidMetric { .. } => "SNAPSHOT_INVALID_METRIC",
+            Self::DuplicateCorridorKey(_) => "SNAPSHOT_DUPLICATE_CORRIDOR",
+            Self::HashMismatch { .. } => "SNAPSHOT_HASH_MISMATCH",
+        }
+    }
+}
+
+impl From<SnapshotValidationError> for ApiError {
+    fn from(err: SnapshotValidationError) -> Self {
+        Self::bad_request(err.code(), err.to_string())
+    }
+}
+
+/// Check a snapshot before it is stored.
+///
+/// `previous_epoch` is the latest epoch already stored, if any. The snapshot
+/// must follow it, or repeat it so a failed epoch can be regenerated.
+pub fn validate_snapshot(
+    snapshot: &AnalyticsSnapshot,
+    previous_epoch: Option<u64>,
+) -> Result<(), SnapshotValidationError> {
+    if let Some(previous) = previous_epoch {
+        if snapshot.epoch != previous && snapshot.epoch != previous.saturating_add(1) {
+            return Err(SnapshotValidationError::NonMonotonicEpoch {
+                previous,
+                actual: snapshot.epoch,
+            });
+        }
+    }
+
+    for anchor in &snapshot.anchor_metrics {
+        let entity = format!("anchor {}", anchor.id);
+        check_metrics(
+            &entity,
+            &[
+                ("success_rate", Some(anchor.success_rate)),
+                ("failure_rate", Some(anchor.failure_rate)),
+                ("reliability_score", Some(anchor.reliability_score)),
+                ("total_transactions", Some(anchor.total_transactions as f64)),
+                (
+                    "successful_transactions",
+                    Some(anchor.successful_transactions as f64),
+                ),
+                (
+                    "failed_transactions",
+                    Some(anchor.failed_transactions as f64),
+                ),
+                (
+                    "avg_settlement_time_ms",
+                    anchor.avg_settlement_time_ms.map(f64::from),
+                ),
+                ("volume_usd", anchor.volume_usd),
+            ],
+        )?;
+    }
+
+    let mut corridor_keys = HashSet::new();
+    for corridor in &snapshot.corridor_metrics {
+        if !corridor_keys.insert(corridor.corridor_key.as_str()) {
+            return Err(SnapshotValidationError::DuplicateCorridorKey(
+                corridor.corridor_key.clone(),
+            ));
+        }
+        let entity = format!("corridor {}", corridor.corridor_key);
+        check_metrics(
+            &entity,
+            &[
+                (
+                    "total_transactions",
+                    Some(corridor.total_transactions as f64),
+                ),
+                (
+                    "successful_transactions",
+                    Some(corridor.successful_transactions as f64),
+                ),
+                (
+                    "failed_transactions",
+                    Some(corridor.failed_transactions as f64),
+                ),
+                ("success_rate", Some(corridor.success_rate)),
+                ("volume_usd", Some(corridor.volume_usd)),
+                (
+                    "avg_settlement_latency_ms",
+                    corridor.avg_settlement_latency_ms.map(f64::from),
+                ),
+                ("liquidity_depth_usd", Some(corridor.liquidity_depth_usd)),
+            ],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Check a stored snapshot row before its hash is submitted: `hash` must be
+/// the hex SHA-256 digest of exactly the `payload` that was stored.
+pub fn verify_stored_hash(payload: &str, hash: &str) -> Result<(), SnapshotValidationError> {
+    let computed = hex::encode(Sha256::digest(payload.as_bytes()));
+    if !computed.eq_ignore_ascii_case(hash) {
+        return Err(SnapshotValidationError::HashMismatch {
+            computed,
+            stored: hash.to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn check_metrics(
+    entity: &str,
+    metrics: &[(&'static str, Option<f64>)],
+) -> Result<(), SnapshotValidationError> {
+    for &(field, value) in metrics {
+        if let Some(value) = value {
+            if !value.is_finite() || value < 0.0 {
+                return Err(SnapshotValidationError::InvalidMetric {
+                    entity: entity.to_string(),
+                    field,
+                    value,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::schema::{SnapshotAnchorMetrics, SnapshotCorridorMetrics};
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn corridor(key: &str) -> SnapshotCorridorMetrics {
+        SnapshotCorridorMetrics {
+            id: Uuid::new_v4(),
+            corridor_key: key.to_string(),
+            source_asset_code: "USDC".to_string(),
+            source_asset_issuer: "issuer1".to_string(),
+            destination_asset_code: "EURC".to_string(),
+            destination_asset_issuer: "issuer2".to_string(),
+            total_transactions: 10,
+            successful_transactions: 9,
+            failed_transactions: 1,
+            success_rate: 0.9,
+            volume_usd: 1_000.0,
+            avg_settlement_latency_ms: Some(250),
+            liquidity_depth_usd: 10_000.0,
+        }
+    }
+
+    fn valid_snapshot(epoch: u64) -> AnalyticsSnapshot {
+        let mut snapshot = AnalyticsSnapshot::new(epoch, Utc::now());
+        snapshot.add_anchor_metrics(SnapshotAnchorMetrics {
+            id: Uuid::from_u128(1),
+            name: "Anchor".to_string(),
+            stellar_account: "GANCHOR".to_string(),
+            success_rate: 0.99,
+            failure_rate: 0.01,
+            reliability_score: 0.99,
+            total_transactions: 100,
+            successful_transactions: 99,
+            failed_transactions: 1,
+            avg_settlement_time_ms: Some(500),
+            volume_usd: Some(1_000.0),
+            status: "green".to_string(),
+        });
+        snapshot.add_corridor_metrics(corridor("USDC->EURC"));
+        snapshot.add_corridor_metrics(corridor("USDC->NGN"));
+        snapshot
+    }
+
+    fn validate(
+        snapshot: &AnalyticsSnapshot,
+        previous_epoch: Option<u64>,
+    ) -> Result<(), &'static str> {
+        validate_snapshot(snapshot, previous_epoch).map_err(|e| e.code())
+    }
+
+    #[test]
+    fn test_valid_snapshot_is_accepted() {
+        assert_eq!(validate(&valid_snapshot(8), Some(7)), Ok(()));
+        // The first snapshot has no predecessor to follow
+        assert_eq!(validate(&valid_snapshot(1), None), Ok(()));
+    }
+
+    #[test]
+    fn test_latest_epoch_can_be_regenerated() {
+        assert_eq!(validate(&valid_snapshot(8), Some(8)), Ok(()));
+    }
+
+    #[test]
+    fn test_rejects_non_monotonic_epoch() {
+        for previous in [9, 6, 5] {
+            assert_eq!(
+                validate(&valid_snapshot(8), Some(previous)),
+                Err("SNAPSHOT_NON_MONOTONIC_EPOCH")
+            );
+        }
+    }
+
+    #[test]
+    fn test_rejects_negative_and_nan_metrics() {
+        let mut snapshot = valid_snapshot(2);
+        snapshot.corridor_metrics[0].volume_usd = -1.0;
+        assert_eq!(validate(&snapshot, Some(1)), Err("SNAPSHOT_INVALID_METRIC"));
+
+        let mut snapshot = valid_snapshot(2);
+        snapshot.anchor_metrics[0].success_rate = f64::NAN;
+        let err = validate_snapshot(&snapshot, Some(1)).unwrap_err();
+        assert!(matches!(
+            err,
+            SnapshotValidationError::InvalidMetric {
+                field: "success_rate",
+                ..
+            }
+        ));
+
+        let mut snapshot = valid_snapshot(2);
+        snapshot.corridor_metrics[1].failed_transactions = -3;
+        assert_eq!(validate(&snapshot, Some(1)), Err("SNAPSHOT_INVALID_METRIC"));
+    }
+
+    #[test]
+    fn test_rejects_duplicate_corridor_keys() {
+        let mut snapshot = valid_snapshot(2);
+        snapshot.add_corridor_metrics(corridor("USDC->EURC"));
+        assert_eq!(
+            validate(&snapshot, Some(1)),
+            Err("SNAPSHOT_DUPLICATE_CORRIDOR")
+        );
+    }
+
+    #[test]
+    fn test_stored_hash_must_match_stored_payload() {
+        let snapshot = valid_snapshot(2);
+        let payload = serde_json::to_string(&snapshot).unwrap();
+        let hash = hex::encode(Sha256::digest(payload.as_bytes()));
+        assert_eq!(verify_stored_hash(&payload, &hash), Ok(()));
+        assert_eq!(verify_stored_hash(&payload, &hash.to_uppercase()), Ok(()));
+
+        // A payload altered after hashing no longer matches
+        let mut other = snapshot.clone();
+        other.corridor_metrics[0].volume_usd += 1.0;
+        let tampered = serde_json::to_string(&other).unwrap();
+        let err = verify_stored_hash(&tampered, &hash).unwrap_err();
+        assert_eq!(err.code(), "SNAPSHOT_HASH_MISMATCH");
+
+        let err = ApiError::from(err).to_error_response(None);
+        assert_eq!(err.error.code, "SNAPSHOT_HASH_MISMATCH");
+    }
+}
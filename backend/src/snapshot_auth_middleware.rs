@@ -0,0 +1,423 @@
+//! Signature guard for snapshot submission.
+//!
+//! Submitters sign each request with a Stellar keypair, SEP-10 style, and only
+//! public keys on the `SNAPSHOT_SUBMITTER_KEYS` allowlist may submit:
+//!
+//! | Header              | Value                                               |
+//! |---------------------|-----------------------------------------------------|
+//! | `X-Stellar-Account` | Submitter public key (`G...`)                       |
+//! | `X-Timestamp`       | Unix seconds, within five minutes of server time    |
+//! | `X-Nonce`           | Unique per request, at most 128 characters          |
+//! | `X-Signature`       | Base64 ed25519 signature of [`signing_payload`]     |
+//!
+//! The signature covers the query string and `Idempotency-Key`, so a signed
+//! `?dry_run=true` request can't be replayed as a real submission. Each nonce
+//! is accepted once per submitter within the clock-skew window.
+//!
+//! Unsigned requests, bad signatures and reused nonces get `401`; a valid
+//! signature from a key that is not allowlisted gets `403`.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use axum::{
+    extract::{OriginalUri, Request, State},
+    http::Method,
+    middleware::Next,
+    response::Response,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chrono::Utc;
+use stellar_sdk::Keypair;
+
+use crate::error::ApiError;
+use crate::idempotency::idempotency_key;
+
+pub const ACCOUNT_HEADER: &str = "x-stellar-account";
+pub const TIMESTAMP_HEADER: &str = "x-timestamp";
+pub const NONCE_HEADER: &str = "x-nonce";
+pub const SIGNATURE_HEADER: &str = "x-signature";
+
+/// Oldest or furthest-ahead `X-Timestamp` accepted, to limit replays
+const MAX_CLOCK_SKEW_SECONDS: i64 = 300;
+const MAX_NONCE_LEN: usize = 128;
+/// Largest body buffered for signature verification
+const MAX_SIGNED_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// Public keys allowed to submit snapshots
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotSubmitters {
+    keys: HashSet<String>,
+    /// `account:nonce` of accepted requests, with their `X-Timestamp`
+    seen_nonces: Arc<Mutex<HashMap<String, i64>>>,
+}
+
+impl SnapshotSubmitters {
+    /// Allow `keys`; entries that are not valid Stellar public keys are dropped
+    #[must_use]
+    pub fn new(keys: impl IntoIterator<Item = String>) -> Self {
+        let keys = keys
+            .into_iter()
+            .map(|key| key.trim().to_string())
+            .filter(|key| !key.is_empty())
+            .filter(|key| {
+                let valid = Keypair::from_public_key(key).is_ok();
+                if !valid {
+                    tracing::warn!("Ignoring invalid snapshot submitter key {}", key);
+                }
+                valid
+            })
+            .collect();
+        Self {
+            keys,
+            seen_nonces: Arc::default(),
+        }
+    }
+
+    /// Read the comma-separated allowlist from `SNAPSHOT_SUBMITTER_KEYS`
+    #[must_use]
+    pub fn from_env() -> Self {
+        let keys = std::env::var("SNAPSHOT_SUBMITTER_KEYS").unwrap_or_default();
+        Self::new(keys.split(',').map(str::to_string))
+    }
+
+    #[must_use]
+    pub fn is_allowed(&self, account: &str) -> bool {
+        self.keys.contains(account)
+    }
+
+    /// Record `nonce` for `account`; `false` if it was already used.
+    ///
+    /// Nonces are forgotten once their timestamp falls outside the skew
+    /// window, since the timestamp check rejects those requests anyway.
+    fn use_nonce(&self, account: &str, nonce: &str, signed_at: i64) -> bool {
+        let cutoff = Utc::now().timestamp() - 2 * MAX_CLOCK_SKEW_SECONDS;
+        let mut seen = self
+            .seen_nonces
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        seen.retain(|_, at| *at >= cutoff);
+        seen.insert(format!("{account}:{nonce}"), signed_at)
+            .is_none()
+    }
+}
+
+/// Submitter whose signature was verified, attached as a request extension
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotSubmitter {
+    pub account: String,
+}
+
+/// Request fields covered by the signature, besides the body
+#[derive(Debug, Clone, Copy)]
+pub struct SignedFields<'a> {
+    pub method: &'a Method,
+    /// Full request path, e.g. `/api/snapshots/generate`
+    pub path: &'a str,
+    /// Raw query string, without the `?`; canonicalized before signing
+    pub query: &'a str,
+    /// Trimmed `Idempotency-Key`, empty when absent
+    pub idempotency_key: &'a str,
+    pub timestamp: &'a str,
+    pub nonce: &'a str,
+}
+
+/// Query parameters sorted and rejoined with `&`, left percent-encoded as sent
+#[must_use]
+pub fn canonical_query(query: &str) -> String {
+    let mut pairs: Vec<&str> = query.split('&').filter(|p| !p.is_empty()).collect();
+    pairs.sort_unstable();
+    pairs.join("&")
+}
+
+/// Bytes a submitter signs: method, path, [`canonical_query`], idempotency
+/// key, timestamp and nonce, each followed by `\n`, then the raw body.
+#[must_use]
+pub fn signing_payload(fields: &SignedFields<'_>, body: &[u8]) -> Vec<u8> {
+    let mut payload = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}\n",
+        fields.method,
+        fields.path,
+        canonical_query(fields.query),
+        fields.idempotency_key,
+        fields.timestamp,
+        fields.nonce,
+    )
+    .into_bytes();
+    payload.extend_from_slice(body);
+    payload
+}
+
+/// Middleware rejecting snapshot submissions that aren't signed by an allowlisted key
+pub async fn require_snapshot_signature(
+    State(submitters): State<Arc<SnapshotSubmitters>>,
+    req: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    // Work on the parts so no borrow of the (non-`Sync`) body is held across awaits
+    let (parts, body) = req.into_parts();
+    let header = |name: &str| {
+        parts
+            .headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    let (Some(account), Some(timestamp), Some(nonce), Some(signature)) = (
+        header(ACCOUNT_HEADER),
+        header(TIMESTAMP_HEADER),
+        header(NONCE_HEADER),
+        header(SIGNATURE_HEADER),
+    ) else {
+        return Err(ApiError::unauthorized(
+            "MISSING_SIGNATURE",
+            "Snapshot submissions must carry X-Stellar-Account, X-Timestamp, X-Nonce and X-Signature headers",
+        ));
+    };
+    if nonce.trim().is_empty() || nonce.len() > MAX_NONCE_LEN {
+        return Err(ApiError::unauthorized(
+            "INVALID_NONCE",
+            format!("X-Nonce must be 1 to {MAX_NONCE_LEN} characters"),
+        ));
+    }
+
+    let signed_at = timestamp.parse::<i64>().map_err(|_| {
+        ApiError::unauthorized("INVALID_TIMESTAMP", "X-Timestamp must be Unix seconds")
+    })?;
+    if (Utc::now().timestamp() - signed_at).abs() > MAX_CLOCK_SKEW_SECONDS {
+        return Err(ApiError::unauthorized(
+            "STALE_SIGNATURE",
+            "X-Timestamp is more than five minutes from server time",
+        ));
+    }
+
+    let keypair = Keypair::from_public_key(&account).map_err(|_| {
+        ApiError::unauthorized(
+            "INVALID_SUBMITTER_KEY",
+            "X-Stellar-Account is not a Stellar public key",
+        )
+    })?;
+    let signature = BASE64
+        .decode(signature.trim())
+        .map_err(|_| ApiError::unauthorized("INVALID_SIGNATURE", "X-Signature must be base64"))?;
+
+    let uri = parts
+        .extensions
+        .get::<OriginalUri>()
+        .map_or_else(|| parts.uri.clone(), |uri| uri.0.clone());
+    let idempotency_key = idempotency_key(&parts.headers).unwrap_or_default();
+    let body = axum::body::to_bytes(body, MAX_SIGNED_BODY_BYTES)
+        .await
+        .map_err(|_| {
+            ApiError::payload_too_large(
+                "PAYLOAD_TOO_LARGE",
+                format!("Signed request bodies are limited to {MAX_SIGNED_BODY_BYTES} bytes"),
+            )
+        })?;
+
+    let fields = SignedFields {
+        method: &parts.method,
+        path: uri.path(),
+        query: uri.query().unwrap_or_default(),
+        idempotency_key: &idempotency_key,
+        timestamp: &timestamp,
+        nonce: &nonce,
+    };
+    if !keypair.verify(&signing_payload(&fields, &body), &signature) {
+        return Err(ApiError::unauthorized(
+            "INVALID_SIGNATURE",
+            "X-Signature does not match the request",
+        ));
+    }
+    if !submitters.is_allowed(&account) {
+        tracing::warn!("Rejected snapshot submission from {}", account);
+        return Err(ApiError::forbidden(
+            "SUBMITTER_NOT_AUTHORIZED",
+            "This key is not allowed to submit snapshots",
+        ));
+    }
+    if !submitters.use_nonce(&account, &nonce, signed_at) {
+        tracing::warn!("Rejected replayed snapshot submission from {}", account);
+        return Err(ApiError::unauthorized(
+            "NONCE_REUSED",
+            "X-Nonce has already been used",
+        ));
+    }
+
+    let mut req = Request::from_parts(parts, axum::body::Body::from(body));
+    req.extensions_mut().insert(SnapshotSubmitter { account });
+    Ok(next.run(req).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::StatusCode, middleware, routing::post, Extension, Router};
+    use tower::ServiceExt;
+
+    const BODY: &str = r#"{"epoch":7}"#;
+
+    fn app(submitters: SnapshotSubmitters) -> Router {
+        Router::new()
+            .route(
+                "/generate",
+                post(
+                    |Extension(submitter): Extension<SnapshotSubmitter>| async move {
+                        submitter.account
+                    },
+                ),
+            )
+            .route_layer(middleware::from_fn_with_state(
+                Arc::new(submitters),
+                require_snapshot_signature,
+            ))
+    }
+
+    /// Request to `/generate?{query}` with `nonce`, signed by `keypair`
+    fn signed_request_with(keypair: &Keypair, query: &str, nonce: &str) -> Request {
+        let timestamp = Utc::now().timestamp().to_string();
+        let fields = SignedFields {
+            method: &Method::POST,
+            path: "/generate",
+            query,
+            idempotency_key: "key-1",
+            timestamp: &timestamp,
+            nonce,
+        };
+        let payload = signing_payload(&fields, BODY.as_bytes());
+        let signature = BASE64.encode(keypair.sign(&payload).unwrap());
+        let uri = if query.is_empty() {
+            "/generate".to_string()
+        } else {
+            format!("/generate?{query}")
+        };
+        Request::builder()
+            .method(Method::POST)
+            .uri(uri)
+            .header(ACCOUNT_HEADER, keypair.public_key())
+            .header(TIMESTAMP_HEADER, timestamp)
+            .header(NONCE_HEADER, nonce)
+            .header(crate::idempotency::IDEMPOTENCY_KEY_HEADER, "key-1")
+            .header(SIGNATURE_HEADER, signature)
+            .body(Body::from(BODY))
+            .unwrap()
+    }
+
+    fn signed_request(keypair: &Keypair) -> Request {
+        signed_request_with(keypair, "", &uuid::Uuid::new_v4().to_string())
+    }
+
+    async fn send(app: Router, request: Request) -> (StatusCode, String) {
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_signed_submission_from_allowlisted_key_passes() {
+        let keypair = Keypair::random().unwrap();
+        let submitters = SnapshotSubmitters::new([keypair.public_key()]);
+
+        let (status, body) = send(app(submitters), signed_request(&keypair)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, keypair.public_key());
+    }
+
+    #[tokio::test]
+    async fn test_unsigned_submission_is_unauthorized() {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/generate")
+            .body(Body::from(BODY))
+            .unwrap();
+
+        let (status, body) = send(app(SnapshotSubmitters::default()), request).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert!(body.contains("MISSING_SIGNATURE"));
+    }
+
+    #[tokio::test]
+    async fn test_tampered_body_is_unauthorized() {
+        let keypair = Keypair::random().unwrap();
+        let submitters = SnapshotSubmitters::new([keypair.public_key()]);
+        let (parts, _) = signed_request(&keypair).into_parts();
+        let request = Request::from_parts(parts, Body::from(r#"{"epoch":8}"#));
+
+        let (status, body) = send(app(submitters), request).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert!(body.contains("INVALID_SIGNATURE"));
+    }
+
+    #[tokio::test]
+    async fn test_signed_submission_from_unlisted_key_is_forbidden() {
+        let allowed = Keypair::random().unwrap();
+        let outsider = Keypair::random().unwrap();
+        let submitters = SnapshotSubmitters::new([allowed.public_key()]);
+
+        let (status, body) = send(app(submitters), signed_request(&outsider)).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert!(body.contains("SUBMITTER_NOT_AUTHORIZED"));
+    }
+
+    #[tokio::test]
+    async fn test_query_and_idempotency_key_are_signed() {
+        let keypair = Keypair::random().unwrap();
+        let submitters = SnapshotSubmitters::new([keypair.public_key()]);
+
+        // Signed as a dry run, sent as a real submission
+        let (mut parts, body) = signed_request_with(&keypair, "dry_run=true", "n-1").into_parts();
+        parts.uri = "/generate?dry_run=false".parse().unwrap();
+        let (status, body) = send(app(submitters.clone()), Request::from_parts(parts, body)).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert!(body.contains("INVALID_SIGNATURE"));
+
+        let (mut parts, body) = signed_request(&keypair).into_parts();
+        parts.headers.insert(
+            crate::idempotency::IDEMPOTENCY_KEY_HEADER,
+            "key-2".parse().unwrap(),
+        );
+        let (status, _) = send(app(submitters.clone()), Request::from_parts(parts, body)).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        // Parameter order doesn't matter
+        let (mut parts, body) =
+            signed_request_with(&keypair, "b=2&dry_run=true", "n-2").into_parts();
+        parts.uri = "/generate?dry_run=true&b=2".parse().unwrap();
+        let (status, _) = send(app(submitters), Request::from_parts(parts, body)).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_reused_nonce_is_rejected() {
+        let keypair = Keypair::random().unwrap();
+        let submitters = SnapshotSubmitters::new([keypair.public_key()]);
+
+        let request = || signed_request_with(&keypair, "", "once");
+        let (status, _) = send(app(submitters.clone()), request()).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, body) = send(app(submitters.clone()), request()).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert!(body.contains("NONCE_REUSED"));
+
+        let (status, _) = send(app(submitters), signed_request(&keypair)).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[test]
+    fn test_submitters_from_env_skips_invalid_keys() {
+        let _guard = crate::lock_env_test();
+        let keypair = Keypair::random().unwrap();
+        std::env::set_var(
+            "SNAPSHOT_SUBMITTER_KEYS",
+            format!(" {} ,not-a-key,", keypair.public_key()),
+        );
+        let submitters = SnapshotSubmitters::from_env();
+        std::env::remove_var("SNAPSHOT_SUBMITTER_KEYS");
+
+        assert!(submitters.is_allowed(&keypair.public_key()));
+        assert_eq!(submitters.keys.len(), 1);
+    }
+}
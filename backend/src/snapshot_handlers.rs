@@ -9,6 +9,7 @@ use tracing::{error, info};
 use crate::database::Database;
 use crate::services::contract::ContractService;
 use crate::services::snapshot::SnapshotService;
+use crate::snapshot::SCHEMA_VERSION;
 
 /// Response for snapshot generation
 #[derive(Debug, Serialize)]
@@ -71,7 +72,7 @@ pub async fn generate_snapshot(
                 epoch: result.epoch,
                 timestamp: result.timestamp.to_rfc3339(),
                 hash: result.hash,
-                schema_version: 1, // From SCHEMA_VERSION
+                schema_version: SCHEMA_VERSION,
                 anchor_count: result.anchor_count,
                 corridor_count: result.corridor_count,
                 submission: result.submission_result.map(|sr| SubmissionInfo {
@@ -100,11 +100,9 @@ impl CommandHandler {
             std::collections::HashMap::new();
 
         for payment in &payments {
-            let key = format!(
-                "{}:{}->XLM:native",
-                payment.get_asset_code().as_deref().unwrap_or("XLM"),
-                payment.get_asset_issuer().as_deref().unwrap_or("native")
-            );
+            let Some(key) = payment.corridor_key() else {
+                continue;
+            };
             let amount: f64 = payment.get_amount().parse().unwrap_or(0.0);
             let entry = corridor_map.entry(key).or_insert((0, 0.0));
             entry.0 += 1;
@@ -149,12 +147,7 @@ impl CommandHandler {
         let mut volume: f64 = 0.0;
 
         for payment in &payments {
-            let corridor_key = format!(
-                "{}:{}->XLM:native",
-                payment.get_asset_code().as_deref().unwrap_or("XLM"),
-                payment.get_asset_issuer().as_deref().unwrap_or("native")
-            );
-            if corridor_key == key {
+            if payment.corridor_key().as_deref() == Some(key) {
                 count += 1;
                 volume += payment.get_amount().parse::<f64>().unwrap_or(0.0);
             }
@@ -24,6 +24,7 @@ pub fn format_alert(alert: &Alert) -> String {
         AlertType::LiquidityDecrease => ("\u{1F7E0}", "Liquidity Decrease"),
         AlertType::AnchorStatusChange => ("\u{1F504}", "Anchor Status Change"),
         AlertType::AnchorMetricChange => ("\u{1F4CA}", "Anchor Metric Change"),
+        AlertType::IngestionLag => ("\u{1F6A8}", "Ingestion Lag"),
     };
 
     let corridor = escape_markdown(alert.corridor_id.as_deref().unwrap_or("N/A"));
@@ -1,6 +1,7 @@
 //! Request parameter validation to prevent invalid inputs (NaN, infinity, negative values, invalid ranges).
 
 use crate::error::{ApiError, ApiResult};
+use crate::muxed::{is_muxed_address, is_valid_muxed_address, is_valid_stellar_public_key};
 use axum::{
     extract::{FromRequest, Request},
     response::IntoResponse,
@@ -158,46 +159,58 @@ pub fn validate_corridor_not_self_referential(
     Ok(())
 }
 
-/// Business-logic validation for `CreateAnchorRequest`: stellar account must
-/// start with 'G' (Ed25519 public key prefix on Stellar).
-pub fn validate_stellar_account(account: &str) -> ApiResult<()> {
-    if !account.starts_with('G') {
-        return Err(ApiError::bad_request(
-            "VALIDATION_ERROR",
-            "Stellar account must be a valid public key starting with 'G'",
-        ));
-    }
-    Ok(())
+/// Where a Stellar address came from, which decides whether a muxed
+/// M-address is accepted and which error code a malformed one reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StellarAddressKind {
+    /// An account to look up; G- or M-addresses (`INVALID_ACCOUNT`)
+    Account,
+    /// An account registered as an anchor; G-addresses only (`INVALID_ADDRESS`)
+    Anchor,
+    /// An asset issuer; G-addresses only (`INVALID_ISSUER`)
+    Issuer,
 }
 
-/// Validates Stellar address format (G followed by 55 base32 characters)
-pub fn validate_stellar_address(address: &str) -> ApiResult<()> {
-    if address.len() != 56 {
-        return Err(ApiError::bad_request(
-            "INVALID_ADDRESS",
-            format!(
-                "Stellar address must be 56 characters (got {})",
-                address.len()
-            ),
-        ));
+impl StellarAddressKind {
+    const fn allows_muxed(self) -> bool {
+        matches!(self, Self::Account)
     }
-    if !address.starts_with('G') {
-        return Err(ApiError::bad_request(
-            "INVALID_ADDRESS",
-            "Stellar address must start with 'G'",
-        ));
+
+    const fn error_code(self) -> &'static str {
+        match self {
+            Self::Account => "INVALID_ACCOUNT",
+            Self::Anchor => "INVALID_ADDRESS",
+            Self::Issuer => "INVALID_ISSUER",
+        }
     }
-    // Validate base32 characters (A-Z, 2-7)
-    if !address[1..]
-        .chars()
-        .all(|c| c.is_ascii_uppercase() || ('2'..='7').contains(&c))
+
+    const fn description(self) -> &'static str {
+        match self {
+            Self::Account => "Account",
+            Self::Anchor => "Anchor account",
+            Self::Issuer => "Asset issuer",
+        }
+    }
+}
+
+/// Rejects an address that isn't a valid Stellar public key (checksum
+/// included) or, where `kind` allows it, a valid muxed M-address, so a typo
+/// fails here rather than as an upstream Horizon error
+pub fn validate_stellar_address(address: &str, kind: StellarAddressKind) -> ApiResult<()> {
+    if is_valid_stellar_public_key(address)
+        || (kind.allows_muxed() && is_valid_muxed_address(address))
     {
-        return Err(ApiError::bad_request(
-            "INVALID_ADDRESS",
-            "Stellar address contains invalid characters (must be A-Z, 2-7)",
-        ));
+        return Ok(());
     }
-    Ok(())
+    let reason = if is_muxed_address(address) && !kind.allows_muxed() {
+        "must be a G-address, not a muxed account"
+    } else {
+        "is not a valid Stellar public key"
+    };
+    Err(ApiError::bad_request(
+        kind.error_code(),
+        format!("{} '{address}' {reason}", kind.description()),
+    ))
 }
 
 /// Validates asset code format (1-12 alphanumeric characters)
@@ -391,34 +404,45 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_validate_stellar_account() {
-        assert!(validate_stellar_account(
-            "GBBD47IF6LWK7P7MDEVSCWR7DPUWV3NY3DTQEVFL4NAT4AQH3ZLLFLA5"
-        )
-        .is_ok());
-        assert!(validate_stellar_account(
-            "ABCD47IF6LWK7P7MDEVSCWR7DPUWV3NY3DTQEVFL4NAT4AQH3ZLLFLA5"
-        )
-        .is_err());
-        assert!(validate_stellar_account("").is_err());
-    }
-
     #[test]
     fn test_validate_stellar_address() {
-        assert!(validate_stellar_address(
-            "GBBD47IF6LWK7P7MDEVSCWR7DPUWV3NY3DTQEVFL4NAT4AQH3ZLLFLA5"
-        )
-        .is_ok());
-        assert!(validate_stellar_address("GBBD47IF6LWK7P7").is_err()); // Too short
-        assert!(validate_stellar_address(
-            "ABCD47IF6LWK7P7MDEVSCWR7DPUWV3NY3DTQEVFL4NAT4AQH3ZLLFLA5"
-        )
-        .is_err()); // Doesn't start with G
-        assert!(validate_stellar_address(
-            "GBBD47IF6LWK7P7MDEVSCWR7DPUWV3NY3DTQEVFL4NAT4AQH3ZLLFLA!"
-        )
-        .is_err()); // Invalid character
+        const ACCOUNT: &str = "GBBD47IF6LWK7P7MDEVSCWR7DPUWV3NY3DTQEVFL4NAT4AQH3ZLLFLA5";
+        const MUXED: &str = "MA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJUAAAAAAAAAAE2JUG6";
+        let code = |address: &str, kind| match validate_stellar_address(address, kind) {
+            Err(ApiError::BadRequest { code, .. }) => Some(code),
+            _ => None,
+        };
+
+        for kind in [
+            StellarAddressKind::Account,
+            StellarAddressKind::Anchor,
+            StellarAddressKind::Issuer,
+        ] {
+            assert!(validate_stellar_address(ACCOUNT, kind).is_ok());
+            assert!(validate_stellar_address("GBBD47IF6LWK7P7", kind).is_err()); // Too short
+            assert!(validate_stellar_address(
+                "ABCD47IF6LWK7P7MDEVSCWR7DPUWV3NY3DTQEVFL4NAT4AQH3ZLLFLA5",
+                kind
+            )
+            .is_err()); // Doesn't start with G
+            assert!(validate_stellar_address(
+                "GBBD47IF6LWK7P7MDEVSCWR7DPUWV3NY3DTQEVFL4NAT4AQH3ZLLFLA!",
+                kind
+            )
+            .is_err()); // Invalid character
+            assert_eq!(code("", kind).as_deref(), Some(kind.error_code()));
+        }
+
+        // Muxed accounts can be looked up but not issue assets or register as anchors
+        assert!(validate_stellar_address(MUXED, StellarAddressKind::Account).is_ok());
+        assert_eq!(
+            code(MUXED, StellarAddressKind::Issuer).as_deref(),
+            Some("INVALID_ISSUER")
+        );
+        assert_eq!(
+            code(MUXED, StellarAddressKind::Anchor).as_deref(),
+            Some("INVALID_ADDRESS")
+        );
     }
 
     #[test]
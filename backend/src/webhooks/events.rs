@@ -1,6 +1,8 @@
 /// Webhook event definitions and payloads
 use serde::{Deserialize, Serialize};
 
+use crate::alerts::AlertThresholds;
+
 /// Corridor Health Degradation Event
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CorridorHealthDegradedEvent {
@@ -44,6 +46,35 @@ pub struct CorridorLiquidityDroppedEvent {
     pub severity: String,        // "warning" | "critical"
 }
 
+/// Ledger Closed Event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LedgerClosedEvent {
+    pub sequence: u64,
+    pub hash: String,
+    pub previous_hash: String,
+    pub transaction_count: u32,
+    pub operation_count: u32,
+    pub closed_at: String,
+    pub protocol_version: u32,
+}
+
+/// Trade Executed Event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeExecutedEvent {
+    pub trade_id: String,
+    pub trade_type: String, // "orderbook" | "liquidity_pool"
+    pub base_account: String,
+    pub base_asset_code: String,
+    pub base_asset_issuer: String,
+    pub base_amount: f64,
+    pub counter_account: String,
+    pub counter_asset_code: String,
+    pub counter_asset_issuer: String,
+    pub counter_amount: f64,
+    pub price: f64,
+    pub timestamp: String,
+}
+
 /// Corridor Metrics snapshot
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CorridorMetrics {
@@ -58,17 +89,28 @@ pub struct CorridorMetrics {
     pub failed_payments: i64,
 }
 
-/// Check if corridor metrics have degraded
+/// Check if corridor metrics have degraded past the default alert thresholds
 #[must_use]
 pub fn check_corridor_degradation(
     old: &CorridorMetrics,
     new: &CorridorMetrics,
+) -> (bool, Vec<String>) {
+    check_corridor_degradation_with(old, new, &AlertThresholds::default())
+}
+
+/// Check if corridor metrics have degraded past `thresholds`, e.g. a
+/// corridor's effective thresholds from [`crate::alerts::AlertManager::thresholds_for`]
+#[must_use]
+pub fn check_corridor_degradation_with(
+    old: &CorridorMetrics,
+    new: &CorridorMetrics,
+    thresholds: &AlertThresholds,
 ) -> (bool, Vec<String>) {
     let mut degraded = false;
     let mut changes = Vec::new();
 
-    // Check success rate drop (>10%)
-    if (old.success_rate - new.success_rate) > 0.10 {
+    // Check success rate drop (percentage points; metrics hold fractions)
+    if (old.success_rate - new.success_rate) > thresholds.success_rate_drop / 100.0 {
         degraded = true;
         changes.push(format!(
             "success_rate_dropped: {:.1}% -> {:.1}%",
@@ -77,8 +119,8 @@ pub fn check_corridor_degradation(
         ));
     }
 
-    // Check latency increase (>50%)
-    if (new.p95_latency_ms - old.p95_latency_ms) / old.p95_latency_ms > 0.50 {
+    // Check latency increase
+    if new.p95_latency_ms / old.p95_latency_ms > thresholds.latency_increase_factor {
         degraded = true;
         changes.push(format!(
             "latency_increased: {:.0}ms -> {:.0}ms",
@@ -86,8 +128,8 @@ pub fn check_corridor_degradation(
         ));
     }
 
-    // Check liquidity decrease (>30%)
-    if (old.liquidity_depth_usd - new.liquidity_depth_usd) / old.liquidity_depth_usd > 0.30 {
+    // Check liquidity decrease
+    if new.liquidity_depth_usd / old.liquidity_depth_usd < thresholds.liquidity_decrease_factor {
         degraded = true;
         changes.push(format!(
             "liquidity_dropped: ${:.0} -> ${:.0}",
@@ -137,5 +179,12 @@ mod tests {
         let (degraded, changes) = check_corridor_degradation(&old, &new);
         assert!(degraded);
         assert!(!changes.is_empty());
+
+        // A looser corridor override tolerates the same drop
+        let loose = AlertThresholds {
+            success_rate_drop: 20.0,
+            ..AlertThresholds::default()
+        };
+        assert!(!check_corridor_degradation_with(&old, &new, &loose).0);
     }
 }
@@ -11,6 +11,29 @@ use uuid::Uuid;
 
 type HmacSha256 = Hmac<Sha256>;
 
+/// `last_error` prefix for deliveries that timed out; such events are
+/// `failed` but still picked up for retry until they run out of retries
+pub const DELIVERY_TIMEOUT_ERROR: &str = "timeout";
+
+/// Default cap on the stored size of an event payload, in bytes
+pub const DEFAULT_MAX_STORED_PAYLOAD_BYTES: usize = 64 * 1024;
+
+/// Max stored payload size from `WEBHOOK_MAX_STORED_PAYLOAD_BYTES`
+/// (default 64 KiB, `0` disables truncation).
+#[must_use]
+pub fn max_stored_payload_bytes_from_env() -> usize {
+    std::env::var("WEBHOOK_MAX_STORED_PAYLOAD_BYTES")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .map_or(DEFAULT_MAX_STORED_PAYLOAD_BYTES, |n| {
+            if n == 0 {
+                usize::MAX
+            } else {
+                n
+            }
+        })
+}
+
 pub use channel::{WebhookChannel, WebhookEndpoint};
 
 /// Webhook signature - for verifying webhook requests
@@ -48,6 +71,18 @@ pub struct Webhook {
     pub last_fired_at: Option<String>,
 }
 
+/// Stored event awaiting delivery, as returned by
+/// [`WebhookService::get_pending_events`]
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct PendingWebhookEvent {
+    pub id: String,
+    pub webhook_id: String,
+    pub event_type: String,
+    /// Full payload, even when the stored copy is truncated
+    pub payload: String,
+    pub created_at: String,
+}
+
 /// Webhook creation request
 #[derive(Debug, Deserialize)]
 pub struct CreateWebhookRequest {
@@ -67,15 +102,69 @@ pub struct WebhookResponse {
     pub created_at: String,
 }
 
-/// Webhook event envelope
-#[derive(Debug, Serialize, Deserialize)]
+/// Schema version of the envelope and event `data` shapes. Bump it whenever
+/// a payload changes in a way consumers would notice.
+pub const WEBHOOK_PAYLOAD_VERSION: &str = "1";
+
+/// Envelope wrapped around every webhook delivery; the signature covers the
+/// whole serialized envelope
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct WebhookEventEnvelope {
-    pub id: String, // Delivery ID for idempotency
-    pub event: String,
-    pub timestamp: i64,
+    pub version: String,
+    pub event_type: String,
+    /// Event ID, unchanged across delivery retries so consumers can deduplicate
+    pub id: String,
+    pub created_at: String,
     pub data: serde_json::Value,
 }
 
+impl WebhookEventEnvelope {
+    #[must_use]
+    pub fn new(id: &str, event_type: &str, created_at: &str, data: serde_json::Value) -> Self {
+        Self {
+            version: WEBHOOK_PAYLOAD_VERSION.to_string(),
+            event_type: event_type.to_string(),
+            id: id.to_string(),
+            created_at: created_at.to_string(),
+            data,
+        }
+    }
+
+    /// Copy of this envelope whose `data` is cut to a preview of its first
+    /// `max_bytes` serialized bytes, for storing oversized events.
+    pub fn truncated(&self, max_bytes: usize) -> serde_json::Result<Self> {
+        let data = serde_json::to_string(&self.data)?;
+        let mut end = max_bytes.min(data.len());
+        while !data.is_char_boundary(end) {
+            end -= 1;
+        }
+        Ok(Self {
+            data: serde_json::json!({
+                "truncated": true,
+                "original_bytes": data.len(),
+                "preview": &data[..end],
+            }),
+            ..self.clone()
+        })
+    }
+
+    /// Parse a stored event payload, wrapping bare payloads queued before
+    /// events were stored enveloped. The wrapper takes the row's
+    /// `created_at`, so it's the same on every delivery attempt.
+    pub fn from_stored(
+        id: &str,
+        event_type: &str,
+        created_at: &str,
+        payload: &str,
+    ) -> serde_json::Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(payload)?;
+        if value.get("version").is_some() && value.get("data").is_some() {
+            return serde_json::from_value(value);
+        }
+        Ok(Self::new(id, event_type, created_at, value))
+    }
+}
+
 /// Event types that can trigger webhooks
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum WebhookEventType {
@@ -83,6 +172,8 @@ pub enum WebhookEventType {
     AnchorStatusChanged,
     PaymentCreated,
     CorridorLiquidityDropped,
+    LedgerClosed,
+    TradeExecuted,
 }
 
 impl WebhookEventType {
@@ -93,6 +184,8 @@ impl WebhookEventType {
             Self::AnchorStatusChanged => "anchor.status_changed",
             Self::PaymentCreated => "payment.created",
             Self::CorridorLiquidityDropped => "corridor.liquidity_dropped",
+            Self::LedgerClosed => "ledger.closed",
+            Self::TradeExecuted => "trade.executed",
         }
     }
 
@@ -103,6 +196,8 @@ impl WebhookEventType {
             "anchor.status_changed" => Some(Self::AnchorStatusChanged),
             "payment.created" => Some(Self::PaymentCreated),
             "corridor.liquidity_dropped" => Some(Self::CorridorLiquidityDropped),
+            "ledger.closed" => Some(Self::LedgerClosed),
+            "trade.executed" => Some(Self::TradeExecuted),
             _ => None,
         }
     }
@@ -112,6 +207,7 @@ impl WebhookEventType {
 pub struct WebhookService {
     pub db: SqlitePool,
     encryption_key: String,
+    max_stored_payload_bytes: usize,
 }
 
 impl WebhookService {
@@ -120,7 +216,18 @@ impl WebhookService {
         let encryption_key = std::env::var("ENCRYPTION_KEY").unwrap_or_else(|_| {
             "0000000000000000000000000000000000000000000000000000000000000000".to_string()
         });
-        Self { db, encryption_key }
+        Self {
+            db,
+            encryption_key,
+            max_stored_payload_bytes: max_stored_payload_bytes_from_env(),
+        }
+    }
+
+    /// Override the stored payload cap read from `WEBHOOK_MAX_STORED_PAYLOAD_BYTES`.
+    #[must_use]
+    pub const fn with_max_stored_payload_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_stored_payload_bytes = max_bytes;
+        self
     }
 
     /// Register a new webhook
@@ -213,7 +320,12 @@ impl WebhookService {
         Ok(result.rows_affected() > 0)
     }
 
-    /// Record webhook event for delivery
+    /// Record webhook event for delivery.
+    ///
+    /// Payloads over the stored size cap are persisted truncated with
+    /// `payload_truncated` set. The full payload is kept in `full_payload`
+    /// and returned by [`Self::get_pending_events`] until the event will not
+    /// be delivered again, so every delivery carries it.
     pub async fn create_webhook_event(
         &self,
         webhook_id: &str,
@@ -221,17 +333,29 @@ impl WebhookService {
         payload: serde_json::Value,
     ) -> anyhow::Result<String> {
         let id = Uuid::new_v4().to_string();
-        let payload_str = payload.to_string();
         let now = chrono::Utc::now().to_rfc3339();
+        let envelope = WebhookEventEnvelope::new(&id, event_type, &now, payload);
+        let payload_str = serde_json::to_string(&envelope)?;
+        let truncated = payload_str.len() > self.max_stored_payload_bytes;
+        let (stored_payload, full_payload) = if truncated {
+            (
+                serde_json::to_string(&envelope.truncated(self.max_stored_payload_bytes)?)?,
+                Some(payload_str),
+            )
+        } else {
+            (payload_str, None)
+        };
 
         sqlx::query(
-            "INSERT INTO webhook_events (id, webhook_id, event_type, payload, status, retries, created_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?)"
+            "INSERT INTO webhook_events (id, webhook_id, event_type, payload, payload_truncated, full_payload, status, retries, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
         )
         .bind(id.clone())
         .bind(webhook_id)
         .bind(event_type)
-        .bind(payload_str)
+        .bind(stored_payload)
+        .bind(truncated)
+        .bind(full_payload)
         .bind("pending")
         .bind(0)
         .bind(now)
@@ -241,37 +365,40 @@ impl WebhookService {
         Ok(id)
     }
 
-    /// Get pending webhook events
+    /// Get pending webhook events, including timed-out deliveries due a retry.
+    ///
+    /// At most `per_webhook` events are taken from each webhook, oldest first,
+    /// and webhooks are interleaved, so a backlog on one endpoint can't fill
+    /// the whole `limit`. Truncated events come back with their full payload.
     pub async fn get_pending_events(
         &self,
         limit: usize,
-    ) -> anyhow::Result<Vec<(String, String, String, String)>> {
+        per_webhook: usize,
+    ) -> anyhow::Result<Vec<PendingWebhookEvent>> {
         let query_limit = limit as i64;
 
-        let rows = sqlx::query(
-            "SELECT we.id, we.webhook_id, we.event_type, we.payload
-             FROM webhook_events we
-             WHERE we.status = 'pending' AND we.retries < 3
-             ORDER BY we.created_at ASC
+        let events = sqlx::query_as::<_, PendingWebhookEvent>(
+            "SELECT id, webhook_id, event_type, payload, created_at
+             FROM (
+                 SELECT we.id, we.webhook_id, we.event_type,
+                        COALESCE(we.full_payload, we.payload) AS payload, we.created_at,
+                        ROW_NUMBER() OVER (
+                            PARTITION BY we.webhook_id ORDER BY we.created_at ASC
+                        ) AS webhook_rank
+                 FROM webhook_events we
+                 WHERE (we.status = 'pending' OR (we.status = 'failed' AND we.last_error LIKE ?))
+                   AND we.retries < 3
+             )
+             WHERE webhook_rank <= ?
+             ORDER BY webhook_rank ASC, created_at ASC
              LIMIT ?",
         )
+        .bind(format!("{DELIVERY_TIMEOUT_ERROR}%"))
+        .bind(per_webhook as i64)
         .bind(query_limit)
         .fetch_all(&self.db)
         .await?;
 
-        let events: Vec<(String, String, String, String)> = rows
-            .into_iter()
-            .map(|row| {
-                use sqlx::Row;
-                (
-                    row.get::<String, _>(0),
-                    row.get::<String, _>(1),
-                    row.get::<String, _>(2),
-                    row.get::<String, _>(3),
-                )
-            })
-            .collect();
-
         Ok(events)
     }
 
@@ -283,12 +410,22 @@ impl WebhookService {
         error: Option<&str>,
         retries: i32,
     ) -> anyhow::Result<()> {
+        // Mirrors the `get_pending_events` filter: drop the full payload once
+        // the event will not be picked up again
+        let retry_due = retries < 3
+            && (status == "pending"
+                || (status == "failed"
+                    && error.is_some_and(|e| e.starts_with(DELIVERY_TIMEOUT_ERROR))));
         sqlx::query(
-            "UPDATE webhook_events SET status = ?, last_error = ?, retries = ? WHERE id = ?",
+            "UPDATE webhook_events
+             SET status = ?, last_error = ?, retries = ?,
+                 full_payload = CASE WHEN ? THEN full_payload END
+             WHERE id = ?",
         )
         .bind(status)
         .bind(error)
         .bind(retries)
+        .bind(retry_due)
         .bind(event_id)
         .execute(&self.db)
         .await?;
@@ -322,6 +459,32 @@ mod tests {
         assert!(WebhookSignature::verify(payload, secret, &signature));
     }
 
+    #[test]
+    fn test_envelope_from_stored_payload() {
+        let envelope = WebhookEventEnvelope::new(
+            "evt-1",
+            "payment.created",
+            "2026-01-01T00:00:00+00:00",
+            serde_json::json!({ "amount": 1 }),
+        );
+        let stored = serde_json::to_string(&envelope).unwrap();
+        assert_eq!(
+            WebhookEventEnvelope::from_stored("other", "other", "2026-02-02 00:00:00", &stored)
+                .unwrap(),
+            envelope
+        );
+
+        // Bare payloads queued before enveloping are wrapped on delivery
+        let legacy =
+            WebhookEventEnvelope::from_stored("evt-2", "test", "2026-02-02 00:00:00", r#"{"a":1}"#)
+                .unwrap();
+        assert_eq!(legacy.version, WEBHOOK_PAYLOAD_VERSION);
+        assert_eq!(legacy.id, "evt-2");
+        assert_eq!(legacy.event_type, "test");
+        assert_eq!(legacy.created_at, "2026-02-02 00:00:00");
+        assert_eq!(legacy.data, serde_json::json!({ "a": 1 }));
+    }
+
     #[test]
     fn test_event_type_conversion() {
         let event = WebhookEventType::CorridorHealthDegraded;
@@ -331,4 +494,16 @@ mod tests {
             Some(WebhookEventType::CorridorHealthDegraded)
         );
     }
+
+    #[test]
+    fn test_ledger_and_trade_event_types_round_trip() {
+        for event in [
+            WebhookEventType::LedgerClosed,
+            WebhookEventType::TradeExecuted,
+        ] {
+            assert_eq!(WebhookEventType::from_str(event.as_str()), Some(event));
+        }
+        assert_eq!(WebhookEventType::LedgerClosed.as_str(), "ledger.closed");
+        assert_eq!(WebhookEventType::TradeExecuted.as_str(), "trade.executed");
+    }
 }
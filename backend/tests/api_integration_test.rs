@@ -15,6 +15,7 @@ use sqlx::SqlitePool;
 use std::sync::Arc;
 use tower::util::ServiceExt;
 
+use stellar_insights_backend::api::analytics_dashboard;
 use stellar_insights_backend::api::anchors::get_anchors;
 use stellar_insights_backend::cache::{CacheConfig, CacheManager};
 use stellar_insights_backend::database::Database;
@@ -282,6 +283,65 @@ async fn test_pool_metrics_response_is_json() {
     );
 }
 
+// ── GET /api/analytics/dashboard (conditional requests) ──────────────────────
+
+#[tokio::test]
+async fn test_analytics_dashboard_returns_304_for_matching_etag() {
+    let db = setup_db().await;
+    sqlx::query(
+        "CREATE TABLE ledgers (sequence INTEGER PRIMARY KEY, hash TEXT NOT NULL, close_time TEXT NOT NULL)",
+    )
+    .execute(db.pool())
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO ledgers (sequence, hash, close_time) VALUES (100, 'abc', '2024-03-01T12:00:00Z')",
+    )
+    .execute(db.pool())
+    .await
+    .unwrap();
+
+    let state = make_app_state(db).await;
+    let app = Router::new().nest("/api/analytics", analytics_dashboard::routes(state));
+
+    let first = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/api/analytics/dashboard")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(first.status(), StatusCode::OK);
+    assert_eq!(
+        first.headers().get("last-modified").unwrap(),
+        "Fri, 01 Mar 2024 12:00:00 GMT"
+    );
+    let etag = first
+        .headers()
+        .get("etag")
+        .expect("ETag header")
+        .to_str()
+        .unwrap()
+        .to_string();
+
+    let second = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/analytics/dashboard")
+                .header("if-none-match", &etag)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(second.status(), StatusCode::NOT_MODIFIED);
+    let body = to_bytes(second.into_body(), usize::MAX).await.unwrap();
+    assert!(body.is_empty());
+}
+
 // ── 404 for unknown routes ────────────────────────────────────────────────────
 
 #[tokio::test]
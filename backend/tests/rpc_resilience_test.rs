@@ -14,6 +14,7 @@ use stellar_insights_backend::rpc::circuit_breaker::{
 };
 use stellar_insights_backend::rpc::error::{with_retry, RetryConfig, RpcError};
 use stellar_insights_backend::rpc::stellar::StellarRpcClient;
+use stellar_insights_backend::rpc::RetryBudget;
 
 fn test_circuit_breaker(failure_threshold: u32, timeout: Duration) -> SharedCircuitBreaker {
     let backoff = backoff::constant(timeout);
@@ -28,13 +29,14 @@ async fn test_rpc_retry_on_failure() {
     let call_count_clone = Arc::clone(&call_count);
 
     let join = tokio::spawn(async move {
+        let budget = RetryBudget::new(10.0, 0.1);
         with_retry(
             move || {
                 let call_count = Arc::clone(&call_count_clone);
                 async move {
                     let current = call_count.fetch_add(1, Ordering::SeqCst) + 1;
                     if current < 3 {
-                        Err(RpcError::NetworkError("transient failure".to_string()))
+                        Err(RpcError::network("transient failure"))
                     } else {
                         Ok("success".to_string())
                     }
@@ -44,8 +46,10 @@ async fn test_rpc_retry_on_failure() {
                 max_attempts: 5,
                 base_delay_ms: 1,
                 max_delay_ms: 100,
+                ..RetryConfig::default()
             },
             test_circuit_breaker(5, Duration::from_secs(30)),
+            &budget,
         )
         .await
     });
@@ -68,12 +72,12 @@ async fn test_circuit_breaker_opens_on_failures() {
     let circuit_breaker = test_circuit_breaker(2, Duration::from_millis(100));
 
     let result1: Result<String, failsafe::Error<RpcError>> = circuit_breaker
-        .call(async { Err(RpcError::NetworkError("fail".to_string())) })
+        .call(async { Err(RpcError::network("fail")) })
         .await;
     assert!(matches!(result1, Err(failsafe::Error::Inner(_))));
 
     let result2: Result<String, failsafe::Error<RpcError>> = circuit_breaker
-        .call(async { Err(RpcError::NetworkError("fail".to_string())) })
+        .call(async { Err(RpcError::network("fail")) })
         .await;
     assert!(matches!(result2, Err(failsafe::Error::Inner(_))));
 
@@ -100,7 +104,7 @@ async fn test_circuit_breaker_fallback() {
     let mut tripped = false;
     for _ in 0..128 {
         let r: Result<(), failsafe::Error<RpcError>> = circuit_breaker
-            .call(async { Err(RpcError::NetworkError("fail".to_string())) })
+            .call(async { Err(RpcError::network("fail")) })
             .await;
         if matches!(r, Err(failsafe::Error::Rejected)) {
             tripped = true;
@@ -294,7 +294,7 @@ mod tests {
 
     #[test]
     fn test_snapshot_schema_version_constant() {
-        assert_eq!(SCHEMA_VERSION, 1, "Schema version should be 1");
+        assert_eq!(SCHEMA_VERSION, 2, "Schema version should be 2");
     }
 
     #[test]
@@ -36,6 +36,7 @@ mod webhook_integration_tests {
                 webhook_id TEXT NOT NULL,
                 event_type TEXT NOT NULL,
                 payload TEXT NOT NULL,
+                payload_truncated INTEGER NOT NULL DEFAULT 0,
                 status TEXT NOT NULL,
                 retries INTEGER NOT NULL DEFAULT 0,
                 last_error TEXT,